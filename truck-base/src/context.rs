@@ -0,0 +1,152 @@
+use crate::tolerance::TOLERANCE;
+use serde::{Deserialize, Serialize};
+
+/// Units of length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LengthUnit {
+    /// micrometer, "um"
+    Micrometer,
+    /// millimeter, "mm"
+    Millimeter,
+    /// centimeter, "cm"
+    Centimeter,
+    /// meter, "m"
+    Meter,
+    /// inch, "in"
+    Inch,
+    /// foot, "ft"
+    Foot,
+}
+
+/// Context of models: the unit of length and the tolerance.
+///
+/// The I/O modules and the builders convert the lengths in other units into
+/// the unit of the context, instead of silently mixing millimeters and meters.
+/// # Examples
+/// ```
+/// use truck_base::context::*;
+/// let context = ModelContext::new(LengthUnit::Millimeter, 1.0e-3);
+/// // lengths in other units are converted into millimeters.
+/// assert_eq!(context.length(2.5, LengthUnit::Centimeter), 25.0);
+/// assert_eq!(context.length(1.0, LengthUnit::Inch), 25.4);
+/// // the tolerance is in millimeters.
+/// assert!(f64::abs(context.tolerance_in(LengthUnit::Meter) - 1.0e-6) < 1.0e-20);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelContext {
+    /// the unit of lengths in models. Default is `LengthUnit::Millimeter`.
+    pub length_unit: LengthUnit,
+    /// the tolerance of lengths in `length_unit`. Default is `TOLERANCE`.
+    pub tolerance: f64,
+}
+
+impl LengthUnit {
+    /// Returns the length of the unit in meters.
+    #[inline(always)]
+    pub fn meters(self) -> f64 {
+        match self {
+            LengthUnit::Micrometer => 1.0e-6,
+            LengthUnit::Millimeter => 1.0e-3,
+            LengthUnit::Centimeter => 1.0e-2,
+            LengthUnit::Meter => 1.0,
+            LengthUnit::Inch => 0.0254,
+            LengthUnit::Foot => 0.3048,
+        }
+    }
+
+    /// Returns the factor converting lengths in `self` into lengths in `unit`.
+    /// # Examples
+    /// ```
+    /// use truck_base::context::LengthUnit;
+    /// assert_eq!(LengthUnit::Meter.factor_to(LengthUnit::Millimeter), 1000.0);
+    /// assert_eq!(LengthUnit::Foot.factor_to(LengthUnit::Inch), 12.0);
+    /// assert_eq!(LengthUnit::Inch.factor_to(LengthUnit::Inch), 1.0);
+    /// ```
+    #[inline(always)]
+    pub fn factor_to(self, unit: LengthUnit) -> f64 {
+        // the lengths in micrometers are integers, so the factors are exact as much as possible.
+        self.micrometers() / unit.micrometers()
+    }
+
+    #[inline(always)]
+    fn micrometers(self) -> f64 {
+        match self {
+            LengthUnit::Micrometer => 1.0,
+            LengthUnit::Millimeter => 1.0e3,
+            LengthUnit::Centimeter => 1.0e4,
+            LengthUnit::Meter => 1.0e6,
+            LengthUnit::Inch => 25_400.0,
+            LengthUnit::Foot => 304_800.0,
+        }
+    }
+
+    /// Returns the symbol of the unit.
+    #[inline(always)]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Micrometer => "um",
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Centimeter => "cm",
+            LengthUnit::Meter => "m",
+            LengthUnit::Inch => "in",
+            LengthUnit::Foot => "ft",
+        }
+    }
+
+    /// Returns the unit of the symbol, or `None` if the symbol is unknown.
+    /// # Examples
+    /// ```
+    /// use truck_base::context::LengthUnit;
+    /// assert_eq!(LengthUnit::from_symbol("mm"), Some(LengthUnit::Millimeter));
+    /// assert_eq!(LengthUnit::from_symbol("mile"), None);
+    /// ```
+    #[inline(always)]
+    pub fn from_symbol(symbol: &str) -> Option<LengthUnit> {
+        match symbol {
+            "um" => Some(LengthUnit::Micrometer),
+            "mm" => Some(LengthUnit::Millimeter),
+            "cm" => Some(LengthUnit::Centimeter),
+            "m" => Some(LengthUnit::Meter),
+            "in" => Some(LengthUnit::Inch),
+            "ft" => Some(LengthUnit::Foot),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ModelContext {
+    #[inline(always)]
+    fn default() -> ModelContext {
+        ModelContext {
+            length_unit: LengthUnit::Millimeter,
+            tolerance: TOLERANCE,
+        }
+    }
+}
+
+impl ModelContext {
+    /// Creates the context.
+    #[inline(always)]
+    pub fn new(length_unit: LengthUnit, tolerance: f64) -> ModelContext {
+        ModelContext {
+            length_unit,
+            tolerance,
+        }
+    }
+
+    /// Returns the factor converting lengths in `unit` into lengths in the context.
+    #[inline(always)]
+    pub fn scale_from(&self, unit: LengthUnit) -> f64 { unit.factor_to(self.length_unit) }
+
+    /// Returns the factor converting lengths in the context into lengths in `unit`.
+    #[inline(always)]
+    pub fn scale_to(&self, unit: LengthUnit) -> f64 { self.length_unit.factor_to(unit) }
+
+    /// Converts the length `value` in `unit` into the length in the context.
+    #[inline(always)]
+    pub fn length(&self, value: f64, unit: LengthUnit) -> f64 { value * self.scale_from(unit) }
+
+    /// Returns the tolerance in `unit`.
+    #[inline(always)]
+    pub fn tolerance_in(&self, unit: LengthUnit) -> f64 { self.tolerance * self.scale_to(unit) }
+}
@@ -1,4 +1,9 @@
 //! Basic structs and traits: importing cgmath, curve and surface traits, tolerance
+//!
+//! # `no_std`
+//!
+//! This crate requires `std`. Its vectors, matrices and points are the ones of cgmath 0.18,
+//! which links `std` and does not build on `no_std` targets, e.g. `thumbv7em-none-eabihf`.
 
 #![warn(
     missing_docs,
@@ -17,6 +22,8 @@ pub mod bounding_box;
 pub mod cgmath64;
 /// Additional traits for cgmath
 pub mod cgmath_extend_traits;
+/// Model context: the unit of length and the tolerance
+pub mod context;
 /// ID structure with `Copy`, `Hash` and `Eq` using raw pointers
 pub mod id;
 /// Setting Tolerance
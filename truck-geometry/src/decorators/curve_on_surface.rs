@@ -94,6 +94,12 @@ where
     }
 }
 
+impl<C, S: RoughlyBounded<P>, P> RoughlyBounded<P> for PCurve<C, S> {
+    /// Returns the bounding box of the surface, which includes the curve.
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<P> { self.surface.roughly_bounding_box() }
+}
+
 impl<C, S> ParameterDivision1D for PCurve<C, S>
 where
     C: ParametricCurve<Point = Point2, Vector = Vector2>,
@@ -1,4 +1,5 @@
 use crate::*;
+use std::ops::{Add, Index};
 
 impl<C, V: Copy> ExtrudedCurve<C, V> {
     /// Creates a linear extruded curve by extrusion.
@@ -59,6 +60,25 @@ where
     }
 }
 
+impl<C, P, V> RoughlyBounded<P> for ExtrudedCurve<C, V>
+where
+    C: RoughlyBounded<P>,
+    P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy,
+    P: Add<V, Output = P>,
+    V: Copy,
+{
+    /// Returns the union of the bounding boxes of the curve and the extruded curve.
+    fn roughly_bounding_box(&self) -> BoundingBox<P> {
+        let mut bdb = self.curve.roughly_bounding_box();
+        if !bdb.is_empty() {
+            let (min, max) = (*bdb.min(), *bdb.max());
+            bdb.push(&(min + self.vector));
+            bdb.push(&(max + self.vector));
+        }
+        bdb
+    }
+}
+
 impl<C: ParameterDivision1D, V> ParameterDivision2D for ExtrudedCurve<C, V> {
     #[inline(always)]
     fn parameter_division(&self, (urange, vrange): ((f64, f64), (f64, f64)), tol: f64) -> (Vec<f64>, Vec<f64>) {
@@ -283,6 +283,58 @@ where
     }
 }
 
+impl<E: RoughlyBounded<Point2>> RoughlyBounded<Point2> for Processor<E, Matrix3> {
+    /// Returns the bounding box of the transformed corners of the bounding box of the entity.
+    fn roughly_bounding_box(&self) -> BoundingBox<Point2> {
+        let bdb = self.entity.roughly_bounding_box();
+        if bdb.is_empty() || !bdb.size().is_finite() {
+            return match bdb.is_empty() {
+                true => bdb,
+                false => [Point2::from_value(f64::NEG_INFINITY), Point2::from_value(f64::INFINITY)]
+                    .iter()
+                    .collect(),
+            };
+        }
+        let (min, max) = (*bdb.min(), *bdb.max());
+        let corners = [
+            min,
+            Point2::new(max[0], min[1]),
+            Point2::new(min[0], max[1]),
+            max,
+        ];
+        corners
+            .iter()
+            .map(|pt| self.transform.transform_point(*pt))
+            .collect()
+    }
+}
+
+impl<E: RoughlyBounded<Point3>> RoughlyBounded<Point3> for Processor<E, Matrix4> {
+    /// Returns the bounding box of the transformed corners of the bounding box of the entity.
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+        let bdb = self.entity.roughly_bounding_box();
+        if bdb.is_empty() || !bdb.size().is_finite() {
+            return match bdb.is_empty() {
+                true => bdb,
+                false => [Point3::from_value(f64::NEG_INFINITY), Point3::from_value(f64::INFINITY)]
+                    .iter()
+                    .collect(),
+            };
+        }
+        let (min, max) = (*bdb.min(), *bdb.max());
+        (0..8)
+            .map(|i| {
+                let select = |j: usize| match i & (1 << j) == 0 {
+                    true => min[j],
+                    false => max[j],
+                };
+                let corner = Point3::new(select(0), select(1), select(2));
+                self.transform.transform_point(corner)
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
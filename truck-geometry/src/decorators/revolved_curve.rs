@@ -403,6 +403,42 @@ where
     }
 }
 
+impl<C: RoughlyBounded<Point3>> RoughlyBounded<Point3> for RevolutedCurve<C> {
+    /// Returns the bounding box of the cylinder including the revolution of the bounding box
+    /// of the curve.
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+        let bdb = self.curve.roughly_bounding_box();
+        if bdb.is_empty() {
+            return bdb;
+        }
+        let (min, max) = (*bdb.min(), *bdb.max());
+        let (mut h0, mut h1, mut radius) = (f64::INFINITY, f64::NEG_INFINITY, 0.0_f64);
+        (0..8).for_each(|i| {
+            let select = |j: usize| match i & (1 << j) == 0 {
+                true => min[j],
+                false => max[j],
+            };
+            let vec = Point3::new(select(0), select(1), select(2)) - self.origin;
+            let h = vec.dot(self.axis);
+            h0 = f64::min(h0, h);
+            h1 = f64::max(h1, h);
+            radius = f64::max(radius, (vec - h * self.axis).magnitude());
+        });
+        // the extent of the circles perpendicular to the axis
+        let extent = self
+            .axis
+            .map(|a| radius * f64::sqrt(f64::max(1.0 - a * a, 0.0)));
+        let (center0, center1) = (self.origin + h0 * self.axis, self.origin + h1 * self.axis);
+        let corners = [
+            center0 - extent,
+            center0 + extent,
+            center1 - extent,
+            center1 + extent,
+        ];
+        corners.iter().collect()
+    }
+}
+
 #[test]
 fn revolve_test() {
     let pt0 = Point3::new(0.0, 2.0, 1.0);
@@ -148,6 +148,22 @@ the number of control points: {1}")]
     /// ```
     #[error("The number of control points is irregular")]
     IrregularControlPoints,
+    /// The breakpoints of a parameter map must be strictly increasing arrays with the same length.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::*;
+    /// use errors::Error;
+    /// assert_eq!(
+    ///     ParameterMap::try_new(vec![0.0, 0.5, 1.0], vec![0.0, 1.0]),
+    ///     Err(Error::InvalidParameterMap),
+    /// );
+    /// assert_eq!(
+    ///     ParameterMap::try_new(vec![0.0, 0.5, 1.0], vec![0.0, 1.0, 0.5]),
+    ///     Err(Error::InvalidParameterMap),
+    /// );
+    /// ```
+    #[error("The breakpoints of the parameter map are not strictly increasing arrays with the same length.")]
+    InvalidParameterMap,
 }
 
 #[test]
@@ -165,5 +181,6 @@ fn print_messages() {
     writeln!(stderr, "{}\n", Error::EmptyControlPoints).unwrap();
     writeln!(stderr, "{}\n", Error::TooShortKnotVector(1, 2)).unwrap();
     writeln!(stderr, "{}\n", Error::IrregularControlPoints).unwrap();
+    writeln!(stderr, "{}\n", Error::InvalidParameterMap).unwrap();
     writeln!(stderr, "*******************************************************").unwrap();
 }
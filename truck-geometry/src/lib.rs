@@ -38,7 +38,7 @@ pub use nurbs::*;
 pub mod errors;
 pub use errors::*;
 
-/// Declares the specified gememetric items: Plane, Sphere, and so on.
+/// Declares the specified gememetric items: Plane, Sphere, conics, and so on.
 pub mod specifieds;
 pub use specifieds::*;
 
@@ -1211,6 +1211,13 @@ where P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> +
     pub fn roughly_bounding_box(&self) -> BoundingBox<P> { self.control_points.iter().collect() }
 }
 
+impl<P> RoughlyBounded<P> for BSplineCurve<P>
+where P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy
+{
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<P> { self.control_points.iter().collect() }
+}
+
 impl<P: Clone> Invertible for BSplineCurve<P> {
     #[inline(always)]
     fn invert(&mut self) { self.invert(); }
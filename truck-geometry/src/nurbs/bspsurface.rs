@@ -1736,6 +1736,90 @@ impl<P: ControlPoint<f64> + Tolerance> BSplineSurface<P> {
         self
     }
 
+    /// Reparameterizes the surface by the piecewise linear maps, and do not change `self` as a
+    /// surface, i.e. the new surface at `(umap.subs(u), vmap.subs(v))` is the old one at `(u, v)`.
+    ///
+    /// The multiplicities of the knots at the breakpoints of the maps are raised to the degrees.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::*;
+    /// let knot_vecs = (KnotVec::bezier_knot(2), KnotVec::bezier_knot(1));
+    /// let ctrl_pts = vec![
+    ///     vec![Point2::new(0.0, 0.0), Point2::new(0.0, 1.0)],
+    ///     vec![Point2::new(0.5, 0.5), Point2::new(0.5, 1.5)],
+    ///     vec![Point2::new(1.0, 0.0), Point2::new(1.0, 1.0)],
+    /// ];
+    /// let mut bspsurface = BSplineSurface::new(knot_vecs, ctrl_pts);
+    /// let org_surface = bspsurface.clone();
+    ///
+    /// let umap = ParameterMap::new(vec![0.0, 0.3, 1.0], vec![0.0, 0.5, 1.0]);
+    /// let vmap = ParameterMap::new(vec![0.0, 1.0], vec![1.0, 3.0]);
+    /// bspsurface.reparameterize(&umap, &vmap);
+    /// assert_eq!(bspsurface.uknot_vec().len(), org_surface.uknot_vec().len() + 2);
+    /// assert_eq!(bspsurface.parameter_range().1, (1.0, 3.0));
+    /// for i in 0..=10 {
+    ///     for j in 0..=10 {
+    ///         let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+    ///         assert_near!(
+    ///             bspsurface.subs(umap.subs(u), vmap.subs(v)),
+    ///             org_surface.subs(u, v),
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    pub fn reparameterize(&mut self, umap: &ParameterMap, vmap: &ParameterMap) -> &mut Self {
+        let (k0, k1) = self.degrees();
+        for u in umap.breaks() {
+            let multi = self.knot_vecs.0.iter().filter(|t| u.near(t)).count();
+            (multi..k0).for_each(|_| {
+                self.add_uknot(u);
+            });
+        }
+        for v in vmap.breaks() {
+            let multi = self.knot_vecs.1.iter().filter(|t| v.near(t)).count();
+            (multi..k1).for_each(|_| {
+                self.add_vknot(v);
+            });
+        }
+        self.knot_vecs.0 = umap.map_knot_vec(&self.knot_vecs.0);
+        self.knot_vecs.1 = vmap.map_knot_vec(&self.knot_vecs.1);
+        self
+    }
+
+    /// Reparameterizes the surface so that the speed of the parameters is roughly uniform,
+    /// and returns the maps from the old parameters to the new ones.
+    ///
+    /// The knot spans are rescaled in proportion to the mean lengths of the iso-curves on them,
+    /// keeping the parameter range. The surface does not change as a surface, and the maps are
+    /// available to move curves in the parameter space, e.g. the boundaries of trimmed faces.
+    /// The skew inside one knot span is not improved, which can be settled by adding knots
+    /// in advance.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::*;
+    /// // The first knot span is one tenth of the range, but as long as the second one.
+    /// let knot_vecs = (KnotVec::from(vec![0.0, 0.0, 0.1, 1.0, 1.0]), KnotVec::bezier_knot(1));
+    /// let ctrl_pts = vec![
+    ///     vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+    ///     vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+    ///     vec![Point3::new(2.0, 0.0, 0.0), Point3::new(2.0, 1.0, 0.0)],
+    /// ];
+    /// let mut bspsurface = BSplineSurface::new(knot_vecs, ctrl_pts);
+    /// let org_surface = bspsurface.clone();
+    ///
+    /// let (umap, vmap) = bspsurface.reparameterize_by_arc_length();
+    /// assert_near!(umap.subs(0.1), 0.5);
+    /// assert!(vmap.is_identity());
+    /// assert_near!(bspsurface.subs(0.5, 0.5), Point3::new(1.0, 0.5, 0.0));
+    /// assert_near!(bspsurface.subs(umap.subs(0.05), 0.5), org_surface.subs(0.05, 0.5));
+    /// ```
+    pub fn reparameterize_by_arc_length(&mut self) -> (ParameterMap, ParameterMap)
+    where P: MetricSpace<Metric = f64> {
+        let (umap, vmap) = parameter_map::arc_length_maps(|u, v| self.subs(u, v), &self.knot_vecs);
+        self.reparameterize(&umap, &vmap);
+        (umap, vmap)
+    }
+
     /// Removes knots in order from the back
     pub fn optimize(&mut self) -> &mut Self {
         loop {
@@ -1878,6 +1962,15 @@ where
     }
 }
 
+impl<V> RoughlyBounded<V> for BSplineSurface<V>
+where V: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy
+{
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<V> {
+        self.control_points.iter().flatten().collect()
+    }
+}
+
 impl<P: ControlPoint<f64>> ParameterDivision2D for BSplineSurface<P>
 where
     P: EuclideanSpace<Scalar = f64, Diff = <P as ControlPoint<f64>>::Diff>
@@ -2262,3 +2355,53 @@ fn test_include_bspcurve3() {
     *curve.control_point_mut(2) += Vector3::new(0.0, 0.0, 0.001);
     assert!(!surface.include(&curve));
 }
+
+#[test]
+fn test_reparameterize_by_arc_length() {
+    let uknot_vec = KnotVec::from(vec![0.0, 0.0, 0.0, 0.0, 0.05, 0.1, 1.0, 1.0, 1.0, 1.0]);
+    let ctrl_pts = (0..6)
+        .map(|i| {
+            let x = i as f64;
+            vec![Point3::new(x, 0.0, 0.0), Point3::new(x, 1.0, x * x / 10.0)]
+        })
+        .collect();
+    let mut surface = BSplineSurface::new((uknot_vec, KnotVec::bezier_knot(1)), ctrl_pts);
+    let org_surface = surface.clone();
+    let (umap, vmap) = surface.reparameterize_by_arc_length();
+    assert!(vmap.is_identity());
+    assert_eq!(surface.parameter_range(), ((0.0, 1.0), (0.0, 1.0)));
+
+    let length = |surface: &BSplineSurface<Point3>, (t0, t1): (f64, f64)| {
+        (0..100)
+            .map(|i| {
+                let p = t0 + (t1 - t0) * i as f64 / 100.0;
+                let q = t0 + (t1 - t0) * (i + 1) as f64 / 100.0;
+                surface.subs(p, 0.5).distance(surface.subs(q, 0.5))
+            })
+            .sum::<f64>()
+    };
+    let speeds = |surface: &BSplineSurface<Point3>, knots: &[f64]| {
+        knots
+            .windows(2)
+            .map(|t| length(surface, (t[0], t[1])) / (t[1] - t[0]))
+            .collect::<Vec<_>>()
+    };
+    let ratio = |speeds: Vec<f64>| {
+        let max = speeds.iter().fold(0.0, |x: f64, y| x.max(*y));
+        max / speeds.iter().fold(f64::INFINITY, |x: f64, y| x.min(*y))
+    };
+    let org_ratio = ratio(speeds(&org_surface, umap.source()));
+    let new_ratio = ratio(speeds(&surface, umap.target()));
+    assert!(org_ratio > 5.0, "{}", org_ratio);
+    assert!(new_ratio < 1.1, "{}", new_ratio);
+
+    for i in 0..=20 {
+        for j in 0..=4 {
+            let (u, v) = (i as f64 / 20.0, j as f64 / 4.0);
+            let pt = surface.subs(umap.subs(u), vmap.subs(v));
+            assert_near!(pt, org_surface.subs(u, v));
+            let (u, v) = (umap.inverse().subs(u), vmap.inverse().subs(v));
+            assert_near!(org_surface.subs(u, v), surface.subs(i as f64 / 20.0, j as f64 / 4.0));
+        }
+    }
+}
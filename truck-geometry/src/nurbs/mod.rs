@@ -118,11 +118,27 @@ pub struct NURBSCurve<V>(BSplineCurve<V>);
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct NURBSSurface<V>(BSplineSurface<V>);
 
+/// Piecewise linear map of parameters, used to reparameterize B-spline surfaces.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// let map = ParameterMap::new(vec![0.0, 0.2, 1.0], vec![0.0, 0.5, 1.0]);
+/// assert_near!(map.subs(0.1), 0.25);
+/// assert_near!(map.subs(0.6), 0.75);
+/// assert_near!(map.inverse().subs(0.75), 0.6);
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ParameterMap {
+    source: Vec<f64>,
+    target: Vec<f64>,
+}
+
 mod bspcurve;
 mod bspsurface;
 mod knot_vec;
 mod nurbscurve;
 mod nurbssurface;
+mod parameter_map;
 
 #[doc(hidden)]
 #[inline(always)]
@@ -503,6 +503,18 @@ where V::Point:
     }
 }
 
+impl<V: Homogeneous<f64>> RoughlyBounded<V::Point> for NURBSCurve<V>
+where V::Point:
+        MetricSpace<Metric = f64> + std::ops::Index<usize, Output = f64> + Bounded<f64> + Copy
+{
+    /// Returns the bounding box including all control points.
+    /// The weights are assumed to be positive.
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<V::Point> {
+        self.0.control_points.iter().map(|p| p.to_point()).collect()
+    }
+}
+
 impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V>> ParametricCurve for NURBSCurve<V> {
     type Point = V::Point;
     type Vector = <V::Point as EuclideanSpace>::Diff;
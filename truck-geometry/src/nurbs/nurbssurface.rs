@@ -414,6 +414,57 @@ impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> NURBSSurface
         self
     }
 
+    /// Reparameterizes the surface by the piecewise linear maps, and do not change `self` as a
+    /// surface. See [`BSplineSurface::reparameterize`] for details.
+    ///
+    /// [`BSplineSurface::reparameterize`]: ./struct.BSplineSurface.html#method.reparameterize
+    #[inline(always)]
+    pub fn reparameterize(&mut self, umap: &ParameterMap, vmap: &ParameterMap) -> &mut Self {
+        self.0.reparameterize(umap, vmap);
+        self
+    }
+
+    /// Reparameterizes the surface so that the speed of the parameters is roughly uniform,
+    /// and returns the maps from the old parameters to the new ones.
+    /// The lengths are measured on the rationalized surface.
+    /// See [`BSplineSurface::reparameterize_by_arc_length`] for details.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::*;
+    /// // a quarter of the unit circle extruded along the z-axis
+    /// let knot_vecs = (KnotVec::bezier_knot(2), KnotVec::from(vec![0.0, 0.0, 0.9, 1.0, 1.0]));
+    /// let c = f64::sqrt(0.5);
+    /// let arc = [Vector3::new(1.0, 0.0, 1.0), Vector3::new(c, c, c), Vector3::new(0.0, 1.0, 1.0)];
+    /// let ctrl_pts = arc
+    ///     .iter()
+    ///     .map(|v| {
+    ///         [0.0, 0.5, 1.0]
+    ///             .iter()
+    ///             .map(|z| Vector4::new(v[0], v[1], z * v[2], v[2]))
+    ///             .collect()
+    ///     })
+    ///     .collect();
+    /// let mut surface = NURBSSurface::new(BSplineSurface::new(knot_vecs, ctrl_pts));
+    /// let org_surface = surface.clone();
+    ///
+    /// let (umap, vmap) = surface.reparameterize_by_arc_length();
+    /// assert_near!(vmap.subs(0.9), 0.5);
+    /// for i in 0..=10 {
+    ///     for j in 0..=10 {
+    ///         let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+    ///         assert_near!(surface.subs(umap.subs(u), vmap.subs(v)), org_surface.subs(u, v));
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`BSplineSurface::reparameterize_by_arc_length`]: ./struct.BSplineSurface.html#method.reparameterize_by_arc_length
+    pub fn reparameterize_by_arc_length(&mut self) -> (ParameterMap, ParameterMap)
+    where V::Point: MetricSpace<Metric = f64> {
+        let (umap, vmap) = parameter_map::arc_length_maps(|u, v| self.subs(u, v), self.knot_vecs());
+        self.0.reparameterize(&umap, &vmap);
+        (umap, vmap)
+    }
+
     /// Removes knots in order from the back
     #[inline(always)]
     pub fn optimize(&mut self) -> &mut Self {
@@ -548,6 +599,25 @@ where
     }
 }
 
+impl<V> RoughlyBounded<V::Point> for NURBSSurface<V>
+where
+    V: Homogeneous<f64>,
+    V::Point:
+        MetricSpace<Metric = f64> + std::ops::Index<usize, Output = f64> + Bounded<f64> + Copy,
+{
+    /// Returns the bounding box including all control points.
+    /// The weights are assumed to be positive.
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<V::Point> {
+        self.0
+            .control_points
+            .iter()
+            .flatten()
+            .map(|pt| pt.to_point())
+            .collect()
+    }
+}
+
 impl SearchParameter for NURBSSurface<Vector3> {
     type Point = Point2;
     type Parameter = (f64, f64);
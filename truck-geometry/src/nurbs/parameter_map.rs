@@ -0,0 +1,195 @@
+use super::*;
+use crate::errors::Error;
+
+/// the number of the segments of the polylines measuring the iso-curves on each knot span
+const SPAN_DIVISION: usize = 8;
+
+impl ParameterMap {
+    /// Creates the piecewise linear map sending `source[i]` to `target[i]`.
+    /// # Failures
+    /// Returns [`Error::InvalidParameterMap`] if the lengths of `source` and `target` are
+    /// different or less than two, or either of them is not strictly increasing.
+    ///
+    /// [`Error::InvalidParameterMap`]: ../errors/enum.Error.html#variant.InvalidParameterMap
+    pub fn try_new(source: Vec<f64>, target: Vec<f64>) -> Result<ParameterMap> {
+        let increasing = |vec: &Vec<f64>| vec.windows(2).all(|a| a[0] < a[1]);
+        match source.len() == target.len()
+            && source.len() > 1
+            && increasing(&source)
+            && increasing(&target)
+        {
+            true => Ok(ParameterMap { source, target }),
+            false => Err(Error::InvalidParameterMap),
+        }
+    }
+
+    /// Creates the piecewise linear map sending `source[i]` to `target[i]`.
+    /// # Panics
+    /// Panic occurs if the lengths of `source` and `target` are different or less than two,
+    /// or either of them is not strictly increasing.
+    #[inline(always)]
+    pub fn new(source: Vec<f64>, target: Vec<f64>) -> ParameterMap {
+        ParameterMap::try_new(source, target).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Returns the identity map on `range`.
+    #[inline(always)]
+    pub fn identity(range: (f64, f64)) -> ParameterMap {
+        ParameterMap::new(vec![range.0, range.1], vec![range.0, range.1])
+    }
+
+    /// Returns the breakpoints in the source parameter.
+    #[inline(always)]
+    pub fn source(&self) -> &[f64] { &self.source }
+
+    /// Returns the images of the breakpoints.
+    #[inline(always)]
+    pub fn target(&self) -> &[f64] { &self.target }
+
+    /// Returns whether the map is an identity map or not.
+    #[inline(always)]
+    pub fn is_identity(&self) -> bool {
+        self.source.iter().zip(&self.target).all(|(s, t)| s.near(t))
+    }
+
+    /// Maps the parameter `t`. The outside of the breakpoints is mapped by extending the first
+    /// or the last linear piece.
+    pub fn subs(&self, t: f64) -> f64 {
+        let n = self.source.len();
+        let i = self.source[1..n - 1].partition_point(|s| *s <= t);
+        let (s0, s1) = (self.source[i], self.source[i + 1]);
+        let (t0, t1) = (self.target[i], self.target[i + 1]);
+        t0 + (t - s0) * (t1 - t0) / (s1 - s0)
+    }
+
+    /// Returns the inverse map.
+    #[inline(always)]
+    pub fn inverse(&self) -> ParameterMap {
+        ParameterMap {
+            source: self.target.clone(),
+            target: self.source.clone(),
+        }
+    }
+
+    /// the map sending the distinct `knots` so that the lengths of the spans are proportional
+    /// to `lengths`, keeping the range
+    pub(super) fn by_span_lengths(knots: Vec<f64>, lengths: &[f64]) -> ParameterMap {
+        let (front, back) = (knots[0], knots[knots.len() - 1]);
+        let total = lengths.iter().sum::<f64>();
+        if total.so_small() {
+            return ParameterMap::identity((front, back));
+        }
+        // Degenerated spans keep a little room not to collapse the knots.
+        let floor = total * 1.0e-3 / lengths.len() as f64;
+        let weights = lengths
+            .iter()
+            .map(|l| f64::max(*l, floor))
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        let mut target = vec![front];
+        let mut acc = 0.0;
+        for w in &weights[..weights.len() - 1] {
+            acc += w;
+            target.push(front + (back - front) * acc / total);
+        }
+        target.push(back);
+        // Removes the breakpoints at which the map is linear.
+        let mut map = ParameterMap {
+            source: vec![knots[0]],
+            target: vec![target[0]],
+        };
+        for i in 1..knots.len() {
+            let last = map.source.len() - 1;
+            let slope0 = (target[i] - map.target[last]) / (knots[i] - map.source[last]);
+            let slope1 = match i + 1 < knots.len() {
+                true => (target[i + 1] - target[i]) / (knots[i + 1] - knots[i]),
+                false => f64::NAN,
+            };
+            if !(slope1 / slope0 - 1.0).so_small() {
+                map.source.push(knots[i]);
+                map.target.push(target[i]);
+            }
+        }
+        map
+    }
+
+    /// the interior breakpoints at which the map is not linear
+    pub(super) fn breaks(&self) -> impl Iterator<Item = f64> + '_ {
+        let n = self.source.len();
+        (1..n - 1)
+            .filter(move |i| {
+                let slope = |j: usize| {
+                    (self.target[j + 1] - self.target[j]) / (self.source[j + 1] - self.source[j])
+                };
+                !(slope(*i) / slope(i - 1) - 1.0).so_small()
+            })
+            .map(move |i| self.source[i])
+    }
+
+    /// Maps all knots of `knot_vec`.
+    pub(super) fn map_knot_vec(&self, knot_vec: &KnotVec) -> KnotVec {
+        KnotVec(knot_vec.iter().map(|t| self.subs(*t)).collect())
+    }
+}
+
+/// the maps making the lengths of the knot spans of the surface `subs` with `knot_vecs`
+/// proportional to the mean lengths of the iso-curves on the spans
+pub(super) fn arc_length_maps<Q: MetricSpace<Metric = f64>>(
+    subs: impl Fn(f64, f64) -> Q,
+    knot_vecs: &(KnotVec, KnotVec),
+) -> (ParameterMap, ParameterMap) {
+    let (uknots, vknots) = (distinct_knots(&knot_vecs.0), distinct_knots(&knot_vecs.1));
+    let ulengths = uspan_lengths(&subs, &uknots, &vknots);
+    let vlengths = uspan_lengths(|v, u| subs(u, v), &vknots, &uknots);
+    (
+        ParameterMap::by_span_lengths(uknots, &ulengths),
+        ParameterMap::by_span_lengths(vknots, &vlengths),
+    )
+}
+
+/// the distinct knots of `knot_vec`
+fn distinct_knots(knot_vec: &KnotVec) -> Vec<f64> {
+    let mut knots: Vec<f64> = Vec::new();
+    knot_vec.iter().for_each(|t| match knots.last() {
+        Some(last) if last.near(t) => {}
+        _ => knots.push(*t),
+    });
+    knots
+}
+
+/// the mean lengths of the `u`-iso-curves of `subs` on the spans of `uknots`,
+/// sampled on the spans of `vknots`
+fn uspan_lengths<Q: MetricSpace<Metric = f64>>(
+    subs: impl Fn(f64, f64) -> Q,
+    uknots: &[f64],
+    vknots: &[f64],
+) -> Vec<f64> {
+    let vs = vknots
+        .windows(2)
+        .flat_map(|v| (0..SPAN_DIVISION).map(move |k| division_point(v[0], v[1], k)))
+        .chain(vknots.last().copied())
+        .collect::<Vec<_>>();
+    uknots
+        .windows(2)
+        .map(|u| {
+            let sum = vs
+                .iter()
+                .map(|v| {
+                    (0..SPAN_DIVISION)
+                        .map(|k| {
+                            let p = subs(division_point(u[0], u[1], k), *v);
+                            p.distance(subs(division_point(u[0], u[1], k + 1), *v))
+                        })
+                        .sum::<f64>()
+                })
+                .sum::<f64>();
+            sum / vs.len() as f64
+        })
+        .collect()
+}
+
+/// the `k`th point dividing `[a, b]` into `SPAN_DIVISION` parts
+#[inline(always)]
+fn division_point(a: f64, b: f64, k: usize) -> f64 {
+    a + (b - a) * k as f64 / SPAN_DIVISION as f64
+}
@@ -0,0 +1,901 @@
+use super::*;
+use std::f64::consts::PI;
+
+// The conic `origin + u * x_axis + v * y_axis` given by the local coordinates `(u, v)`.
+trait Conic: Copy {
+    // the coefficients `[A, C, D, E, F]` of the implicit equation
+    // `A * u^2 + C * v^2 + D * u + E * v + F = 0`
+    const IMPLICIT: [f64; 5];
+    // the coefficients of the polynomials `[U, V, W]` of degree 2, in ascending order,
+    // giving the rational parametrization `(u, v) = (U(z) / W(z), V(z) / W(z))`
+    const RATIONAL: [[f64; 3]; 3];
+    fn frame(&self) -> (Point3, Vector3, Vector3);
+    fn range_mut(&mut self) -> &mut (f64, f64);
+    fn y_axis_mut(&mut self) -> &mut Vector3;
+    fn basis(t: f64) -> (f64, f64);
+    fn basis_der(t: f64) -> (f64, f64);
+    fn basis_der2(t: f64) -> (f64, f64);
+    // the parameter of the point `(u, v)` on the whole conic, not considering the range
+    fn local_parameter(&self, u: f64, v: f64) -> Option<f64>;
+    // the number of the rational quadratic Bézier segments
+    fn segment_number(range: (f64, f64)) -> usize;
+    // the local coordinates and the weight of the middle control point of a Bézier segment
+    fn middle_control_point(t0: f64, t1: f64) -> ((f64, f64), f64);
+}
+
+impl Conic for CircleArc {
+    const IMPLICIT: [f64; 5] = [1.0, 1.0, 0.0, 0.0, -1.0];
+    // z = tan(t / 2)
+    const RATIONAL: [[f64; 3]; 3] = [[1.0, 0.0, -1.0], [0.0, 2.0, 0.0], [1.0, 0.0, 1.0]];
+    #[inline(always)]
+    fn frame(&self) -> (Point3, Vector3, Vector3) { (self.center, self.x_axis, self.y_axis) }
+    #[inline(always)]
+    fn range_mut(&mut self) -> &mut (f64, f64) { &mut self.range }
+    #[inline(always)]
+    fn y_axis_mut(&mut self) -> &mut Vector3 { &mut self.y_axis }
+    #[inline(always)]
+    fn basis(t: f64) -> (f64, f64) { Ellipse::basis(t) }
+    #[inline(always)]
+    fn basis_der(t: f64) -> (f64, f64) { Ellipse::basis_der(t) }
+    #[inline(always)]
+    fn basis_der2(t: f64) -> (f64, f64) { Ellipse::basis_der2(t) }
+    #[inline(always)]
+    fn local_parameter(&self, u: f64, v: f64) -> Option<f64> {
+        elliptic_parameter(self.range.0, u, v)
+    }
+    #[inline(always)]
+    fn segment_number(range: (f64, f64)) -> usize { Ellipse::segment_number(range) }
+    #[inline(always)]
+    fn middle_control_point(t0: f64, t1: f64) -> ((f64, f64), f64) {
+        Ellipse::middle_control_point(t0, t1)
+    }
+}
+
+impl Conic for Ellipse {
+    const IMPLICIT: [f64; 5] = [1.0, 1.0, 0.0, 0.0, -1.0];
+    // z = tan(t / 2)
+    const RATIONAL: [[f64; 3]; 3] = [[1.0, 0.0, -1.0], [0.0, 2.0, 0.0], [1.0, 0.0, 1.0]];
+    #[inline(always)]
+    fn frame(&self) -> (Point3, Vector3, Vector3) { (self.center, self.x_axis, self.y_axis) }
+    #[inline(always)]
+    fn range_mut(&mut self) -> &mut (f64, f64) { &mut self.range }
+    #[inline(always)]
+    fn y_axis_mut(&mut self) -> &mut Vector3 { &mut self.y_axis }
+    #[inline(always)]
+    fn basis(t: f64) -> (f64, f64) { (f64::cos(t), f64::sin(t)) }
+    #[inline(always)]
+    fn basis_der(t: f64) -> (f64, f64) { (-f64::sin(t), f64::cos(t)) }
+    #[inline(always)]
+    fn basis_der2(t: f64) -> (f64, f64) { (-f64::cos(t), -f64::sin(t)) }
+    #[inline(always)]
+    fn local_parameter(&self, u: f64, v: f64) -> Option<f64> {
+        elliptic_parameter(self.range.0, u, v)
+    }
+    #[inline(always)]
+    fn segment_number((t0, t1): (f64, f64)) -> usize {
+        usize::max(f64::ceil((t1 - t0) / (PI / 2.0)) as usize, 1)
+    }
+    #[inline(always)]
+    fn middle_control_point(t0: f64, t1: f64) -> ((f64, f64), f64) {
+        let (m, h) = ((t0 + t1) / 2.0, (t1 - t0) / 2.0);
+        let w = f64::cos(h);
+        ((f64::cos(m) / w, f64::sin(m) / w), w)
+    }
+}
+
+impl Conic for Parabola {
+    const IMPLICIT: [f64; 5] = [0.0, 0.25, -1.0, 0.0, 0.0];
+    // z = t
+    const RATIONAL: [[f64; 3]; 3] = [[0.0, 0.0, 1.0], [0.0, 2.0, 0.0], [1.0, 0.0, 0.0]];
+    #[inline(always)]
+    fn frame(&self) -> (Point3, Vector3, Vector3) { (self.vertex, self.x_axis, self.y_axis) }
+    #[inline(always)]
+    fn range_mut(&mut self) -> &mut (f64, f64) { &mut self.range }
+    #[inline(always)]
+    fn y_axis_mut(&mut self) -> &mut Vector3 { &mut self.y_axis }
+    #[inline(always)]
+    fn basis(t: f64) -> (f64, f64) { (t * t, 2.0 * t) }
+    #[inline(always)]
+    fn basis_der(t: f64) -> (f64, f64) { (2.0 * t, 2.0) }
+    #[inline(always)]
+    fn basis_der2(_: f64) -> (f64, f64) { (2.0, 0.0) }
+    #[inline(always)]
+    fn local_parameter(&self, _: f64, v: f64) -> Option<f64> { Some(v / 2.0) }
+    #[inline(always)]
+    fn segment_number(_: (f64, f64)) -> usize { 1 }
+    #[inline(always)]
+    fn middle_control_point(t0: f64, t1: f64) -> ((f64, f64), f64) { ((t0 * t1, t0 + t1), 1.0) }
+}
+
+impl Conic for Hyperbola {
+    const IMPLICIT: [f64; 5] = [1.0, -1.0, 0.0, 0.0, -1.0];
+    // z = e^t
+    const RATIONAL: [[f64; 3]; 3] = [[1.0, 0.0, 1.0], [-1.0, 0.0, 1.0], [0.0, 2.0, 0.0]];
+    #[inline(always)]
+    fn frame(&self) -> (Point3, Vector3, Vector3) { (self.center, self.x_axis, self.y_axis) }
+    #[inline(always)]
+    fn range_mut(&mut self) -> &mut (f64, f64) { &mut self.range }
+    #[inline(always)]
+    fn y_axis_mut(&mut self) -> &mut Vector3 { &mut self.y_axis }
+    #[inline(always)]
+    fn basis(t: f64) -> (f64, f64) { (f64::cosh(t), f64::sinh(t)) }
+    #[inline(always)]
+    fn basis_der(t: f64) -> (f64, f64) { (f64::sinh(t), f64::cosh(t)) }
+    #[inline(always)]
+    fn basis_der2(t: f64) -> (f64, f64) { (f64::cosh(t), f64::sinh(t)) }
+    #[inline(always)]
+    fn local_parameter(&self, u: f64, v: f64) -> Option<f64> {
+        match u > 0.0 {
+            true => Some(f64::asinh(v)),
+            false => None,
+        }
+    }
+    #[inline(always)]
+    fn segment_number((t0, t1): (f64, f64)) -> usize { usize::max(f64::ceil(t1 - t0) as usize, 1) }
+    #[inline(always)]
+    fn middle_control_point(t0: f64, t1: f64) -> ((f64, f64), f64) {
+        let (m, h) = ((t0 + t1) / 2.0, (t1 - t0) / 2.0);
+        let w = f64::cosh(h);
+        ((f64::cosh(m) / w, f64::sinh(m) / w), w)
+    }
+}
+
+// the angle of `(u, v)` not less than `t0`
+fn elliptic_parameter(t0: f64, u: f64, v: f64) -> Option<f64> {
+    if u.so_small() && v.so_small() {
+        return None;
+    }
+    let t = f64::atan2(v, u);
+    Some(t + 2.0 * PI * f64::ceil((t0 - TOLERANCE - t) / (2.0 * PI)))
+}
+
+#[inline(always)]
+fn include_parameter(range: (f64, f64), t: f64) -> bool {
+    range.0 - TOLERANCE <= t && t <= range.1 + TOLERANCE
+}
+
+#[inline(always)]
+fn subs<C: Conic>(conic: &C, t: f64) -> Point3 {
+    let (origin, x_axis, y_axis) = conic.frame();
+    let (u, v) = C::basis(t);
+    origin + u * x_axis + v * y_axis
+}
+
+// the local coordinates `(u, v, w)` such that `pt = origin + u * x_axis + v * y_axis + w * normal`
+fn local_coordinates<C: Conic>(conic: &C, pt: Point3) -> Option<Vector3> {
+    let (origin, x_axis, y_axis) = conic.frame();
+    let normal = x_axis.cross(y_axis).normalize();
+    let mat = Matrix3::from_cols(x_axis, y_axis, normal).invert()?;
+    Some(mat * (pt - origin))
+}
+
+fn search_parameter<C: Conic>(conic: &C, range: (f64, f64), pt: Point3) -> Option<f64> {
+    let uvw = local_coordinates(conic, pt)?;
+    let t = conic.local_parameter(uvw[0], uvw[1])?;
+    match include_parameter(range, t) && subs(conic, t).near(&pt) {
+        true => Some(t),
+        false => None,
+    }
+}
+
+// the real roots of `a * s^2 + b * s + c = 0`. The negative discriminant is regarded as zero,
+// since the candidates are verified by the caller.
+fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return match b == 0.0 {
+            true => Vec::new(),
+            false => vec![-c / b],
+        };
+    }
+    let det = b * b - 4.0 * a * c;
+    if det <= 0.0 {
+        return vec![-b / (2.0 * a)];
+    }
+    let q = -(b + f64::signum(b) * f64::sqrt(det)) / 2.0;
+    match q == 0.0 {
+        true => vec![0.0],
+        false => vec![q / a, c / q],
+    }
+}
+
+fn line_intersections<C: Conic>(
+    conic: &C,
+    range: (f64, f64),
+    origin: Point3,
+    dir: Vector3,
+) -> Vec<(f64, f64)> {
+    let (o, x_axis, y_axis) = conic.frame();
+    let normal = x_axis.cross(y_axis).normalize();
+    let mat = match Matrix3::from_cols(x_axis, y_axis, normal).invert() {
+        Some(mat) => mat,
+        None => return Vec::new(),
+    };
+    let (p, d) = (mat * (origin - o), mat * dir);
+    let candidates = if d[2].so_small() {
+        if !p[2].so_small() {
+            return Vec::new();
+        }
+        let [a, c, d0, e, f] = C::IMPLICIT;
+        solve_quadratic(
+            a * d[0] * d[0] + c * d[1] * d[1],
+            2.0 * a * p[0] * d[0] + 2.0 * c * p[1] * d[1] + d0 * d[0] + e * d[1],
+            a * p[0] * p[0] + c * p[1] * p[1] + d0 * p[0] + e * p[1] + f,
+        )
+    } else {
+        vec![-p[2] / d[2]]
+    };
+    let mut res: Vec<(f64, f64)> = candidates
+        .into_iter()
+        .filter_map(|s| {
+            let q = p + s * d;
+            let t = conic.local_parameter(q[0], q[1])?;
+            match include_parameter(range, t) && subs(conic, t).near(&(origin + s * dir)) {
+                true => Some((t, s)),
+                false => None,
+            }
+        })
+        .collect();
+    res.sort_by(|x, y| x.1.partial_cmp(&y.1).unwrap());
+    res.dedup_by(|x, y| x.0.near(&y.0));
+    res
+}
+
+// Bisects until the interval cannot be divided by floating point numbers.
+fn bisection<F: Fn(f64) -> f64>(f: F, mut t0: f64, mut t1: f64) -> f64 {
+    let positive = f(t0) > 0.0;
+    loop {
+        let t = (t0 + t1) / 2.0;
+        if t <= t0 || t1 <= t {
+            return t;
+        }
+        match (f(t) > 0.0) == positive {
+            true => t0 = t,
+            false => t1 = t,
+        }
+    }
+}
+
+// The real roots of the polynomial whose coefficients are `coef` in ascending order, and its
+// critical points, which are the candidates of the multiple roots. The polynomial is monotone
+// between the critical points, so each simple root is isolated and refined by bisection.
+fn root_candidates(coef: &[f64]) -> Vec<f64> {
+    let n = match coef.iter().rposition(|c| *c != 0.0) {
+        Some(n) if n > 0 => n,
+        _ => return Vec::new(),
+    };
+    let poly = |t: f64| coef[..=n].iter().rev().fold(0.0, |sum, c| sum * t + c);
+    // Cauchy's bound of the roots
+    let bound = 1.0
+        + coef[..n]
+            .iter()
+            .fold(0.0, |max, c| f64::max(max, f64::abs(c / coef[n])));
+    let der: Vec<f64> = (1..=n).map(|i| i as f64 * coef[i]).collect();
+    let mut critical = root_candidates(&der);
+    critical.retain(|t| -bound < *t && *t < bound);
+    critical.sort_by(f64::total_cmp);
+    critical.dedup();
+    let mut knots = vec![-bound];
+    knots.extend(&critical);
+    knots.push(bound);
+    let mut res: Vec<f64> = knots
+        .windows(2)
+        .filter(|w| poly(w[0]) * poly(w[1]) <= 0.0)
+        .map(|w| bisection(poly, w[0], w[1]))
+        .collect();
+    res.extend(critical);
+    res
+}
+
+// the coefficients of the product of the polynomials in ascending order
+fn product<S, T>(p: &[S], q: &[S], mul: impl Fn(&S, &S) -> T) -> Vec<T>
+where T: Copy + std::ops::Add<Output = T> {
+    (0..p.len() + q.len() - 1)
+        .map(|m| {
+            let i0 = (m + 1).saturating_sub(q.len());
+            let mut terms = (i0..=usize::min(m, p.len() - 1)).map(|i| mul(&p[i], &q[m - i]));
+            let first = terms.next().unwrap();
+            terms.fold(first, |sum, x| sum + x)
+        })
+        .collect()
+}
+
+// The conic is substituted by the rational parametrization `P(z) / W(z)`. If the conic is not
+// on the plane of the circle, the intersections are on the plane, i.e. the roots of the quadratic
+// equation `normal.dot(P(z) - W(z) * center) = 0`. Otherwise, they are the roots of the quartic
+// equation `|P(z) - W(z) * center|^2 = radius^2 * W(z)^2`. The point at `z = ∞` is added to the
+// candidates, and all candidates are verified on both curves.
+fn circle_intersections<C: Conic>(
+    conic: &C,
+    range: (f64, f64),
+    circle: &CircleArc,
+) -> Vec<(f64, f64)> {
+    let (origin, x_axis, y_axis) = conic.frame();
+    let (center, normal, r) = (circle.center, circle.normal(), circle.radius());
+    let [u, v, w] = C::RATIONAL;
+    // the coefficients of `P(z) - W(z) * center`
+    let vec: Vec<Vector3> = (0..3)
+        .map(|k| w[k] * (origin - center) + u[k] * x_axis + v[k] * y_axis)
+        .collect();
+    let plane: Vec<f64> = vec.iter().map(|p| p.dot(normal)).collect();
+    let candidates = if plane.iter().all(|c| c.so_small()) {
+        let (t0, t1) = range;
+        let overlap = (0..=PRESEARCH_DIVISION).all(|i| {
+            let t = t0 + (t1 - t0) * i as f64 / PRESEARCH_DIVISION as f64;
+            subs(conic, t).distance(center).near(&r)
+        });
+        if overlap {
+            return Vec::new();
+        }
+        let sphere = product(&vec, &vec, |p, q| p.dot(*q));
+        let weight = product(&w, &w, |p, q| r * r * p * q);
+        let quartic: Vec<f64> = sphere.iter().zip(weight).map(|(p, q)| p - q).collect();
+        root_candidates(&quartic)
+    } else {
+        solve_quadratic(plane[2], plane[1], plane[0])
+    };
+    let eval = |coef: [f64; 3], z: f64| coef[0] + coef[1] * z + coef[2] * z * z;
+    let mut points: Vec<Point3> = candidates
+        .into_iter()
+        .filter(|z| eval(w, *z) != 0.0)
+        .map(|z| {
+            let (u, v) = (eval(u, z) / eval(w, z), eval(v, z) / eval(w, z));
+            origin + u * x_axis + v * y_axis
+        })
+        .collect();
+    if w[2] != 0.0 {
+        points.push(origin + u[2] / w[2] * x_axis + v[2] / w[2] * y_axis);
+    }
+    let mut res: Vec<(f64, f64)> = points
+        .into_iter()
+        .filter_map(|pt| {
+            let t = search_parameter(conic, range, pt)?;
+            let s = circle.search_parameter(pt, None, 0)?;
+            Some((t, s))
+        })
+        .collect();
+    res.sort_by(|x, y| x.0.total_cmp(&y.0));
+    res.dedup_by(|x, y| subs(conic, x.0).near(&subs(conic, y.0)));
+    res
+}
+
+fn to_nurbs<C: Conic>(conic: &C, (t0, t1): (f64, f64)) -> NURBSCurve<Vector4> {
+    let (origin, x_axis, y_axis) = conic.frame();
+    let point =
+        |(u, v): (f64, f64), w: f64| (w * (origin + u * x_axis + v * y_axis).to_vec()).extend(w);
+    let n = C::segment_number((t0, t1));
+    let knots: Vec<f64> = (0..=n)
+        .map(|i| t0 + (t1 - t0) * i as f64 / n as f64)
+        .collect();
+    let mut knot_vec = vec![t0; 3];
+    let mut control_points = vec![point(C::basis(t0), 1.0)];
+    knots.windows(2).for_each(|w| {
+        let (uv, weight) = C::middle_control_point(w[0], w[1]);
+        control_points.push(point(uv, weight));
+        control_points.push(point(C::basis(w[1]), 1.0));
+        knot_vec.extend(&[w[1], w[1]]);
+    });
+    knot_vec.push(t1);
+    NURBSCurve::new(BSplineCurve::new(KnotVec::from(knot_vec), control_points))
+}
+
+macro_rules! impl_conic {
+    ($conic: ident) => {
+        impl $conic {
+            /// Returns the x-axis, the vector from the origin to the point of the parameter `0`.
+            #[inline(always)]
+            pub fn x_axis(&self) -> Vector3 { self.x_axis }
+            /// Returns the y-axis.
+            #[inline(always)]
+            pub fn y_axis(&self) -> Vector3 { self.y_axis }
+            /// Returns the unit normal of the plane including the curve.
+            #[inline(always)]
+            pub fn normal(&self) -> Vector3 { self.x_axis.cross(self.y_axis).normalize() }
+            /// Returns the intersections with the line `origin + s * direction`
+            /// as the pairs of the parameters `(t, s)`, sorted by `s`.
+            /// The intersections are solved exactly by the implicit equation of the conic.
+            #[inline(always)]
+            pub fn line_intersections(
+                &self,
+                origin: Point3,
+                direction: Vector3,
+            ) -> Vec<(f64, f64)> {
+                line_intersections(self, self.range, origin, direction)
+            }
+            /// Returns the intersections with `circle` as the pairs of the parameters of `self`
+            /// and `circle`, sorted by the parameters of `self`.
+            /// The intersections are the roots of the quadratic equation, or of the quartic one
+            /// if `self` is on the plane of `circle`, which are isolated by the critical points
+            /// and refined up to the floating point precision.
+            /// Returns the empty vector if `self` overlaps `circle`.
+            #[inline(always)]
+            pub fn circle_intersections(&self, circle: &CircleArc) -> Vec<(f64, f64)> {
+                circle_intersections(self, self.range, circle)
+            }
+            /// Converts into the NURBS curve, consisting of rational quadratic Bézier segments.
+            /// The converted curve has the same image and the same parameter range,
+            /// however, the parametrization agrees only at the knots.
+            #[inline(always)]
+            pub fn to_nurbs(&self) -> NURBSCurve<Vector4> { to_nurbs(self, self.range) }
+        }
+
+        impl ParametricCurve for $conic {
+            type Point = Point3;
+            type Vector = Vector3;
+            #[inline(always)]
+            fn subs(&self, t: f64) -> Point3 { subs(self, t) }
+            #[inline(always)]
+            fn der(&self, t: f64) -> Vector3 {
+                let (u, v) = Self::basis_der(t);
+                u * self.x_axis + v * self.y_axis
+            }
+            #[inline(always)]
+            fn der2(&self, t: f64) -> Vector3 {
+                let (u, v) = Self::basis_der2(t);
+                u * self.x_axis + v * self.y_axis
+            }
+            #[inline(always)]
+            fn parameter_range(&self) -> (f64, f64) { self.range }
+        }
+
+        impl ParameterDivision1D for $conic {
+            #[inline(always)]
+            fn parameter_division(&self, range: (f64, f64), tol: f64) -> Vec<f64> {
+                algo::curve::parameter_division(self, range, tol)
+            }
+        }
+
+        impl SearchParameter for $conic {
+            type Point = Point3;
+            type Parameter = f64;
+            /// Searches the parameter by the local coordinates. The hint and trials are not used.
+            #[inline(always)]
+            fn search_parameter(&self, point: Point3, _: Option<f64>, _: usize) -> Option<f64> {
+                search_parameter(self, self.range, point)
+            }
+        }
+
+        impl Invertible for $conic {
+            #[inline(always)]
+            fn invert(&mut self) {
+                let (t0, t1) = self.range;
+                *self.range_mut() = (-t1, -t0);
+                let y_axis = self.y_axis_mut();
+                *y_axis = -*y_axis;
+            }
+        }
+
+        impl Cut for $conic {
+            #[inline(always)]
+            fn cut(&mut self, t: f64) -> Self {
+                let mut res = *self;
+                self.range.1 = t;
+                res.range.0 = t;
+                res
+            }
+        }
+
+        impl RoughlyBounded<Point3> for $conic {
+            #[inline(always)]
+            fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+                self.to_nurbs().roughly_bounding_box()
+            }
+        }
+    };
+}
+
+impl_conic!(CircleArc);
+impl_conic!(Ellipse);
+impl_conic!(Parabola);
+impl_conic!(Hyperbola);
+
+macro_rules! impl_affine_conic {
+    ($conic: ident, $origin: ident) => {
+        impl SearchNearestParameter for $conic {
+            type Point = Point3;
+            type Parameter = f64;
+            #[inline(always)]
+            fn search_nearest_parameter(
+                &self,
+                point: Point3,
+                hint: Option<f64>,
+                trial: usize,
+            ) -> Option<f64> {
+                let hint = match hint {
+                    Some(hint) => hint,
+                    None => algo::curve::presearch(self, point, self.range, PRESEARCH_DIVISION),
+                };
+                algo::curve::search_nearest_parameter(self, point, hint, trial)
+            }
+        }
+
+        impl<T: Transform3<Scalar = f64>> Transformed<T> for $conic {
+            /// Transforms the curve by the affine transformation `trans`.
+            #[inline(always)]
+            fn transform_by(&mut self, trans: T) {
+                self.$origin = trans.transform_point(self.$origin);
+                self.x_axis = trans.transform_vector(self.x_axis);
+                self.y_axis = trans.transform_vector(self.y_axis);
+            }
+        }
+    };
+}
+
+impl_affine_conic!(Ellipse, center);
+impl_affine_conic!(Parabola, vertex);
+impl_affine_conic!(Hyperbola, center);
+
+impl CircleArc {
+    /// Creates the circle arc with the center, the direction of the point of the parameter `0`,
+    /// the normal of the plane, the radius, and the parameter range of the angle.
+    /// # Panics
+    /// Panic occurs if `x_axis` is parallel to `normal`.
+    #[inline(always)]
+    pub fn new(
+        center: Point3,
+        x_axis: Vector3,
+        normal: Vector3,
+        radius: f64,
+        range: (f64, f64),
+    ) -> CircleArc {
+        let normal = normal.normalize();
+        let x_axis = (x_axis - x_axis.dot(normal) * normal).normalize() * radius;
+        CircleArc {
+            center,
+            x_axis,
+            y_axis: normal.cross(x_axis),
+            range,
+        }
+    }
+    /// Creates the circle arc from `p0` to `p2` through `p1`.
+    /// Returns `None` if the three points are collinear.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::*;
+    /// use std::f64::consts::PI;
+    /// let arc = CircleArc::by_three_points(
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, -1.0, 0.0),
+    /// ).unwrap();
+    /// assert_near!(arc.center(), Point3::origin());
+    /// assert_near!(arc.normal(), Vector3::unit_z());
+    /// assert_near!(arc.parameter_range().1, 1.5 * PI);
+    /// assert_near!(arc.back(), Point3::new(0.0, -1.0, 0.0));
+    /// ```
+    pub fn by_three_points(p0: Point3, p1: Point3, p2: Point3) -> Option<CircleArc> {
+        let (a, b) = (p1 - p0, p2 - p0);
+        let normal = a.cross(b);
+        if normal.so_small() {
+            return None;
+        }
+        let vec = (a.magnitude2() * b.cross(normal) + b.magnitude2() * normal.cross(a))
+            / (2.0 * normal.magnitude2());
+        let center = p0 + vec;
+        let mut arc = CircleArc::new(center, -vec, normal, vec.magnitude(), (0.0, 0.0));
+        let uvw = local_coordinates(&arc, p2)?;
+        arc.range.1 = arc.local_parameter(uvw[0], uvw[1])?;
+        Some(arc)
+    }
+    /// Returns the center
+    #[inline(always)]
+    pub fn center(&self) -> Point3 { self.center }
+    /// Returns the radius
+    #[inline(always)]
+    pub fn radius(&self) -> f64 { self.x_axis.magnitude() }
+}
+
+impl SearchNearestParameter for CircleArc {
+    type Point = Point3;
+    type Parameter = f64;
+    /// Returns the angle of the projection of `point`, or the parameter of the nearer end point
+    /// if the angle is out of the range. The hint and trials are not used.
+    fn search_nearest_parameter(&self, point: Point3, _: Option<f64>, _: usize) -> Option<f64> {
+        let uvw = local_coordinates(self, point)?;
+        let t = self.local_parameter(uvw[0], uvw[1])?;
+        if include_parameter(self.range, t) {
+            Some(t)
+        } else {
+            let (t0, t1) = self.range;
+            match self.subs(t0).distance2(point) < self.subs(t1).distance2(point) {
+                true => Some(t0),
+                false => Some(t1),
+            }
+        }
+    }
+}
+
+impl From<CircleArc> for Ellipse {
+    #[inline(always)]
+    fn from(arc: CircleArc) -> Ellipse {
+        Ellipse {
+            center: arc.center,
+            x_axis: arc.x_axis,
+            y_axis: arc.y_axis,
+            range: arc.range,
+        }
+    }
+}
+
+impl Ellipse {
+    /// Creates the ellipse with the center, the conjugate semi-diameters,
+    /// and the parameter range.
+    #[inline(always)]
+    pub fn new(center: Point3, x_axis: Vector3, y_axis: Vector3, range: (f64, f64)) -> Ellipse {
+        Ellipse {
+            center,
+            x_axis,
+            y_axis,
+            range,
+        }
+    }
+    /// Returns the center
+    #[inline(always)]
+    pub fn center(&self) -> Point3 { self.center }
+}
+
+impl Parabola {
+    /// Creates the parabola with the vertex, the axes, and the parameter range.
+    #[inline(always)]
+    pub fn new(vertex: Point3, x_axis: Vector3, y_axis: Vector3, range: (f64, f64)) -> Parabola {
+        Parabola {
+            vertex,
+            x_axis,
+            y_axis,
+            range,
+        }
+    }
+    /// Returns the vertex
+    #[inline(always)]
+    pub fn vertex(&self) -> Point3 { self.vertex }
+    /// Returns the focus. The axes are assumed to be orthogonal and of the same length.
+    #[inline(always)]
+    pub fn focus(&self) -> Point3 { self.vertex + self.x_axis }
+}
+
+impl Hyperbola {
+    /// Creates the branch of hyperbola with the center, the conjugate semi-diameters,
+    /// and the parameter range.
+    #[inline(always)]
+    pub fn new(center: Point3, x_axis: Vector3, y_axis: Vector3, range: (f64, f64)) -> Hyperbola {
+        Hyperbola {
+            center,
+            x_axis,
+            y_axis,
+            range,
+        }
+    }
+    /// Returns the center
+    #[inline(always)]
+    pub fn center(&self) -> Point3 { self.center }
+}
+
+#[cfg(test)]
+fn random_center() -> Point3 {
+    Point3::new(
+        20.0 * rand::random::<f64>() - 10.0,
+        20.0 * rand::random::<f64>() - 10.0,
+        20.0 * rand::random::<f64>() - 10.0,
+    )
+}
+
+// non-orthogonal axes, and the normal of a circle
+#[cfg(test)]
+const AXES: [Vector3; 3] = [
+    Vector3::new(1.0, 0.2, -0.3),
+    Vector3::new(0.4, 1.1, 0.5),
+    Vector3::new(0.3, -0.2, 1.0),
+];
+
+#[cfg(test)]
+fn exec_conic_test<C>(curve: C)
+where C: ParametricCurve<Point = Point3, Vector = Vector3>
+        + SearchParameter<Point = Point3, Parameter = f64>
+        + SearchNearestParameter<Point = Point3, Parameter = f64>
+        + Invertible
+        + Cut
+        + RoughlyBounded<Point3> {
+    const EPS: f64 = 1.0e-4;
+    let (t0, t1) = curve.parameter_range();
+    let bdd = curve.roughly_bounding_box();
+    for i in 1..10 {
+        let t = t0 + (t1 - t0) * i as f64 / 10.0;
+        let der = (curve.subs(t + EPS) - curve.subs(t - EPS)) / (2.0 * EPS);
+        assert!((der - curve.der(t)).magnitude() < EPS);
+        let der2 = (curve.der(t + EPS) - curve.der(t - EPS)) / (2.0 * EPS);
+        assert!((der2 - curve.der2(t)).magnitude() < EPS);
+        let pt = curve.subs(t);
+        let (min, max) = (bdd.min(), bdd.max());
+        assert!((0..3).all(|i| min[i] <= pt[i] + TOLERANCE && pt[i] <= max[i] + TOLERANCE));
+        assert_near!(curve.search_parameter(pt, None, 0).unwrap(), t);
+        assert_near!(curve.search_nearest_parameter(pt, None, 100).unwrap(), t);
+        assert!(curve
+            .search_parameter(pt + 0.1 * Vector3::unit_z(), None, 0)
+            .is_none());
+        assert_near!(curve.inverse().subs(-t), pt);
+        let mut part0 = curve.clone();
+        let part1 = part0.cut(t);
+        assert_near!(part0.back(), pt);
+        assert_near!(part1.front(), pt);
+    }
+}
+
+#[test]
+fn conic_test() {
+    let center = random_center();
+    let [x_axis, y_axis, normal] = AXES;
+    let arc = CircleArc::new(center, x_axis, normal, 2.0, (-1.0, 4.0));
+    assert_near!(arc.radius(), 2.0);
+    exec_conic_test(arc);
+    exec_conic_test(Ellipse::new(center, x_axis, y_axis, (1.0, 6.0)));
+    exec_conic_test(Parabola::new(center, x_axis, y_axis, (-1.5, 2.0)));
+    exec_conic_test(Hyperbola::new(center, x_axis, y_axis, (-2.0, 1.5)));
+}
+
+#[cfg(test)]
+fn exec_to_nurbs_test<C>(curve: C, nurbs: NURBSCurve<Vector4>)
+where C: SearchParameter<Point = Point3, Parameter = f64> {
+    let (t0, t1) = nurbs.parameter_range();
+    let mut prev = f64::NEG_INFINITY;
+    for i in 0..=20 {
+        let pt = nurbs.subs(t0 + (t1 - t0) * i as f64 / 20.0);
+        let t = curve.search_parameter(pt, None, 0).unwrap();
+        assert!(prev < t);
+        prev = t;
+    }
+}
+
+#[test]
+fn to_nurbs_test() {
+    let center = random_center();
+    let [x_axis, y_axis, normal] = AXES;
+    let arc = CircleArc::new(center, x_axis, normal, 2.0, (-1.0, 5.0));
+    exec_to_nurbs_test(arc, arc.to_nurbs());
+    let ellipse = Ellipse::new(center, x_axis, y_axis, (0.0, 2.5));
+    exec_to_nurbs_test(ellipse, ellipse.to_nurbs());
+    let parabola = Parabola::new(center, x_axis, y_axis, (-1.5, 2.0));
+    exec_to_nurbs_test(parabola, parabola.to_nurbs());
+    let hyperbola = Hyperbola::new(center, x_axis, y_axis, (-2.0, 1.5));
+    exec_to_nurbs_test(hyperbola, hyperbola.to_nurbs());
+}
+
+#[test]
+fn line_intersections_test() {
+    let center = random_center();
+    let [x_axis, y_axis, _] = AXES;
+    let ellipse = Ellipse::new(center, x_axis, y_axis, (0.0, 2.0 * PI));
+    let (p, q) = (ellipse.subs(0.5), ellipse.subs(3.0));
+    let res = ellipse.line_intersections(p, q - p);
+    assert_eq!(res.len(), 2);
+    assert_near!(res[0].0, 0.5);
+    assert_near!(res[0].1, 0.0);
+    assert_near!(res[1].0, 3.0);
+    assert_near!(res[1].1, 1.0);
+    // tangent line
+    let res = ellipse.line_intersections(p + ellipse.der(0.5), ellipse.der(0.5));
+    assert_eq!(res.len(), 1);
+    assert_near!(res[0].0, 0.5);
+    // the line crossing the plane
+    let res = ellipse.line_intersections(q - ellipse.normal(), ellipse.normal());
+    assert_eq!(res.len(), 1);
+    assert_near!(ellipse.subs(res[0].0), q);
+    // the out of range
+    let ellipse = Ellipse::new(center, x_axis, y_axis, (1.0, 2.0 * PI));
+    assert_eq!(ellipse.line_intersections(p, q - p).len(), 1);
+
+    let hyperbola = Hyperbola::new(center, x_axis, y_axis, (-3.0, 3.0));
+    // parallel to the asymptote
+    let res = hyperbola.line_intersections(center + 0.5 * x_axis, x_axis + y_axis);
+    assert_eq!(res.len(), 1);
+    let (p, q) = (hyperbola.subs(-1.0), hyperbola.subs(2.0));
+    let res = hyperbola.line_intersections(q, p - q);
+    assert_eq!(res.len(), 2);
+    assert_near!(res[0].0, 2.0);
+    assert_near!(res[1].0, -1.0);
+}
+
+#[test]
+fn circle_intersections_test() {
+    let center = random_center();
+    let [x_axis, _, normal] = AXES;
+    let circle0 = CircleArc::new(center, x_axis, normal, 1.0, (0.0, 2.0 * PI));
+    let circle1 = CircleArc::new(center + circle0.x_axis(), x_axis, normal, 1.0, (1.0, 5.0));
+    let res = circle0.circle_intersections(&circle1);
+    assert_eq!(res.len(), 2);
+    assert_near!(res[0].0, PI / 3.0);
+    assert_near!(res[0].1, 2.0 * PI / 3.0);
+    assert_near!(res[1].0, 5.0 * PI / 3.0);
+    assert_near!(res[1].1, 4.0 * PI / 3.0);
+    // the overlapping circles
+    assert!(circle0.circle_intersections(&circle0).is_empty());
+
+    let (x_axis, y_axis) = (circle0.x_axis(), circle0.y_axis());
+    let parabola = Parabola::new(center - x_axis, x_axis / 4.0, y_axis / 4.0, (-4.0, 4.0));
+    // tangent at the vertex, and the crossing points
+    let res = parabola.circle_intersections(&circle0);
+    assert_eq!(res.len(), 3);
+    assert_near!(res[1].0, 0.0);
+    assert_near!(res[1].1, PI);
+    res.iter()
+        .for_each(|(t, s)| assert_near!(parabola.subs(*t), circle0.subs(*s)));
+}
+
+#[test]
+fn circle_intersections_degenerate_test() {
+    let center = random_center();
+    let [x_axis, y_axis, normal] = AXES;
+    let circle0 = CircleArc::new(center, x_axis, normal, 1.0, (0.0, 2.0 * PI));
+    // two great circles on the same sphere
+    let circle1 = CircleArc::new(center, normal, x_axis, 1.0, (0.0, 2.0 * PI));
+    let res = circle0.circle_intersections(&circle1);
+    assert_eq!(res.len(), 2);
+    res.iter().for_each(|(t, s)| {
+        assert_near!(circle0.subs(*t), circle1.subs(*s));
+        assert!(circle0.subs(*t).distance(center).near(&1.0));
+    });
+    // the parallel planes
+    let circle1 = CircleArc::new(center + 0.5 * normal, x_axis, normal, 1.0, (0.0, 2.0 * PI));
+    assert!(circle0.circle_intersections(&circle1).is_empty());
+    // the circle on the same sphere, but not on the same plane
+    let (x, y) = (circle0.x_axis(), circle0.y_axis());
+    let circle1 = CircleArc::by_three_points(center + x, center + y, center - x).unwrap();
+    assert!(circle0.circle_intersections(&circle1).is_empty());
+    let circle1 = CircleArc::by_three_points(center + x, center + normal.normalize(), center - x);
+    assert_eq!(circle0.circle_intersections(&circle1.unwrap()).len(), 2);
+
+    // the tangent circles on the same plane
+    let circle1 = CircleArc::new(center + 3.0 * x, x_axis, normal, 2.0, (0.0, 2.0 * PI));
+    let res = circle0.circle_intersections(&circle1);
+    assert_eq!(res.len(), 1);
+    assert_near!(res[0].0, 0.0);
+    assert_near!(res[0].1, PI);
+    let circle1 = CircleArc::new(center - x, x_axis, normal, 2.0, (0.0, 2.0 * PI));
+    let res = circle0.circle_intersections(&circle1);
+    assert_eq!(res.len(), 1);
+    assert_near!(res[0].1, 0.0);
+
+    // the point of the ellipse at `t = π`, out of the rational parametrization
+    let ellipse = Ellipse::new(center, x_axis, y_axis, (0.0, 2.0 * PI));
+    let (pt, der) = (ellipse.subs(PI), ellipse.der(PI));
+    let mut dir = der.cross(ellipse.normal()).normalize();
+    if dir.dot(pt - center) < 0.0 {
+        dir = -dir;
+    }
+    let circle1 = CircleArc::new(
+        pt + 0.5 * dir,
+        x_axis,
+        ellipse.normal(),
+        0.5,
+        (0.0, 2.0 * PI),
+    );
+    let res = ellipse.circle_intersections(&circle1);
+    assert_eq!(res.len(), 1);
+    assert_near!(res[0].0, PI);
+    // the circle touching the ellipse from the outside of its plane
+    let (pt, der) = (ellipse.subs(1.0), ellipse.der(1.0));
+    let dir = der.cross(ellipse.normal()).normalize() + ellipse.normal();
+    let circle1 = CircleArc::new(
+        pt + dir,
+        der,
+        der.cross(dir),
+        dir.magnitude(),
+        (0.0, 2.0 * PI),
+    );
+    let res = ellipse.circle_intersections(&circle1);
+    assert_eq!(res.len(), 1);
+    assert_near!(res[0].0, 1.0);
+
+    // the crossing points of the branch of the hyperbola and of the parabola
+    let hyperbola = Hyperbola::new(center, x, y, (-3.0, 3.0));
+    let circle1 = CircleArc::new(center, x_axis, normal, 2.0, (0.0, 2.0 * PI));
+    let res = hyperbola.circle_intersections(&circle1);
+    assert_eq!(res.len(), 2);
+    res.iter()
+        .for_each(|(t, s)| assert_near!(hyperbola.subs(*t), circle1.subs(*s)));
+    assert_near!(res[0].0, -res[1].0);
+    let parabola = Parabola::new(center, x, y / 2.0, (-4.0, 4.0));
+    let circle1 = CircleArc::new(center + x, x_axis, normal, 0.9, (0.0, 2.0 * PI));
+    let res = parabola.circle_intersections(&circle1);
+    assert_eq!(res.len(), 4);
+    res.iter()
+        .for_each(|(t, s)| assert_near!(parabola.subs(*t), circle1.subs(*s)));
+}
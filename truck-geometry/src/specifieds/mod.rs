@@ -63,5 +63,137 @@ pub struct Sphere {
     radius: f64,
 }
 
+/// circle arc: `center + cos(t) * x_axis + sin(t) * y_axis` for `t` in the parameter range
+///
+/// The axes are orthogonal and of the same length, the radius.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// use std::f64::consts::PI;
+///
+/// let arc = CircleArc::new(
+///     Point3::new(1.0, 2.0, 3.0),
+///     Vector3::unit_x(),
+///     Vector3::unit_z(),
+///     2.0,
+///     (0.0, PI / 2.0),
+/// );
+/// assert_near!(arc.front(), Point3::new(3.0, 2.0, 3.0));
+/// assert_near!(arc.back(), Point3::new(1.0, 4.0, 3.0));
+///
+/// // the line y = 2 cuts the arc only at the front point
+/// let line = (Point3::new(0.0, 2.0, 3.0), Vector3::unit_x());
+/// let intersections = arc.line_intersections(line.0, line.1);
+/// assert_eq!(intersections.len(), 1);
+/// assert_near!(intersections[0].0, 0.0);
+/// assert_near!(intersections[0].1, 3.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CircleArc {
+    center: Point3,
+    x_axis: Vector3,
+    y_axis: Vector3,
+    range: (f64, f64),
+}
+
+/// ellipse: `center + cos(t) * x_axis + sin(t) * y_axis` for `t` in the parameter range
+///
+/// The axes are conjugate semi-diameters, which are not necessarily orthogonal,
+/// so that ellipses are closed under affine transformations.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+/// use std::f64::consts::PI;
+///
+/// let ellipse = Ellipse::new(
+///     Point3::origin(),
+///     Vector3::new(2.0, 0.0, 0.0),
+///     Vector3::new(0.0, 1.0, 0.0),
+///     (0.0, 2.0 * PI),
+/// );
+/// // the implicit equation of the ellipse
+/// for i in 0..=10 {
+///     let p = ellipse.subs(2.0 * PI * i as f64 / 10.0);
+///     assert_near!(p[0] * p[0] / 4.0 + p[1] * p[1], 1.0);
+/// }
+///
+/// // the intersection with the unit circle
+/// let circle = CircleArc::new(
+///     Point3::origin(),
+///     Vector3::unit_x(),
+///     Vector3::unit_z(),
+///     1.0,
+///     (0.0, 2.0 * PI),
+/// );
+/// let intersections = ellipse.circle_intersections(&circle);
+/// assert_eq!(intersections.len(), 2);
+/// assert_near!(ellipse.subs(intersections[0].0), Point3::new(0.0, 1.0, 0.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ellipse {
+    center: Point3,
+    x_axis: Vector3,
+    y_axis: Vector3,
+    range: (f64, f64),
+}
+
+/// parabola: `vertex + t * t * x_axis + 2 * t * y_axis` for `t` in the parameter range
+///
+/// If the axes are orthogonal and of the same length, the length is the focal length.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+///
+/// // y^2 = 4x
+/// let parabola = Parabola::new(
+///     Point3::origin(),
+///     Vector3::unit_x(),
+///     Vector3::unit_y(),
+///     (-2.0, 2.0),
+/// );
+/// assert_near!(parabola.focus(), Point3::new(1.0, 0.0, 0.0));
+/// let intersections = parabola.line_intersections(Point3::new(1.0, 0.0, 0.0), Vector3::unit_y());
+/// assert_eq!(intersections.len(), 2);
+/// assert_near!(parabola.subs(intersections[0].0), Point3::new(1.0, -2.0, 0.0));
+/// assert_near!(parabola.subs(intersections[1].0), Point3::new(1.0, 2.0, 0.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Parabola {
+    vertex: Point3,
+    x_axis: Vector3,
+    y_axis: Vector3,
+    range: (f64, f64),
+}
+
+/// one branch of hyperbola: `center + cosh(t) * x_axis + sinh(t) * y_axis` for `t` in the range
+///
+/// The axes are conjugate semi-diameters, and the asymptotes are `x_axis ± y_axis`.
+/// # Examples
+/// ```
+/// use truck_geometry::*;
+///
+/// // x^2 - y^2 = 1, x > 0
+/// let hyperbola = Hyperbola::new(
+///     Point3::origin(),
+///     Vector3::unit_x(),
+///     Vector3::unit_y(),
+///     (-1.0, 1.0),
+/// );
+/// let p = hyperbola.subs(0.7);
+/// assert_near!(p[0] * p[0] - p[1] * p[1], 1.0);
+///
+/// // the line x = -1 does not meet the branch
+/// let line = (Point3::new(-1.0, 0.0, 0.0), Vector3::unit_y());
+/// assert!(hyperbola.line_intersections(line.0, line.1).is_empty());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hyperbola {
+    center: Point3,
+    x_axis: Vector3,
+    y_axis: Vector3,
+    range: (f64, f64),
+}
+
+mod conics;
 mod plane;
 mod sphere;
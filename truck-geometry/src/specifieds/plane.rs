@@ -245,6 +245,16 @@ impl<T: Transform3<Scalar = f64>> Transformed<T> for Plane {
     }
 }
 
+impl RoughlyBounded<Point3> for Plane {
+    /// Returns the infinite box, since planes are unbounded.
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+        [Point3::from_value(f64::NEG_INFINITY), Point3::from_value(f64::INFINITY)]
+            .iter()
+            .collect()
+    }
+}
+
 impl SearchParameter for Plane {
     type Point = Point3;
     type Parameter = (f64, f64);
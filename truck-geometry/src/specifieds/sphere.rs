@@ -139,6 +139,14 @@ impl ParameterDivision2D for Sphere {
     }
 }
 
+impl RoughlyBounded<Point3> for Sphere {
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+        let radius = Vector3::from_value(self.radius);
+        [self.center - radius, self.center + radius].iter().collect()
+    }
+}
+
 impl SearchParameter for Sphere {
     type Point = Point3;
     type Parameter = (f64, f64);
@@ -1,5 +1,10 @@
 //! Defines geometric traits: `ParametricCurve`, `ParametricSurface`, and so on.
 //! Implements some algorithms for traits.
+//!
+//! # `no_std`
+//!
+//! This crate requires `std` as [`truck-base`](../truck_base/index.html) does:
+//! the geometric traits are bounded by the cgmath traits.
 
 #![warn(
     missing_docs,
@@ -1,4 +1,4 @@
-use truck_base::cgmath64::*;
+use truck_base::{bounding_box::BoundingBox, cgmath64::*};
 
 mod curve;
 pub use curve::*;
@@ -37,6 +37,14 @@ pub trait SearchNearestParameter {
     ) -> Option<Self::Parameter>;
 }
 
+/// Geometry whose bounding box is computed without tessellation
+pub trait RoughlyBounded<P> {
+    /// Returns the box including the whole geometry, e.g. the bounding box of the control points.
+    /// The box is conservative, but not always the smallest one.
+    /// Unbounded geometries, e.g. planes, return the infinite box.
+    fn roughly_bounding_box(&self) -> BoundingBox<P>;
+}
+
 /// Oriented and reversible
 pub trait Invertible: Clone {
     /// Inverts `self`
@@ -184,7 +184,7 @@ fn collide_seg_triangle(seg: [Point3; 2], tri: [Point3; 3]) -> Option<Point3> {
     }
 }
 
-fn collide_triangles(tri0: [Point3; 3], tri1: [Point3; 3]) -> Option<(Point3, Point3)> {
+pub(crate) fn collide_triangles(tri0: [Point3; 3], tri1: [Point3; 3]) -> Option<(Point3, Point3)> {
     let mut tuple = (None, None);
     [
         collide_seg_triangle([tri0[0], tri0[1]], tri1),
@@ -257,6 +257,18 @@ fn collision(poly0: &PolygonMesh, poly1: &PolygonMesh) -> Vec<(Point3, Point3)>
         .collect()
 }
 
+/// Returns the pairs of the indices of the triangles in `tris0` and `tris1` whose bounding boxes
+/// overlap.
+pub(crate) fn overlapping_triangle_pairs(
+    tris0: &[[Point3; 3]],
+    tris1: &[[Point3; 3]],
+) -> Vec<(usize, usize)> {
+    let endpoints = sorted_endpoints(tris0.iter().copied(), tris1.iter().copied());
+    colliding_segment_pairs(endpoints)
+        .filter(|(idx0, idx1)| !disjoint_bdbs(tris0[*idx0], tris1[*idx1]))
+        .collect()
+}
+
 fn are_colliding(poly0: &PolygonMesh, poly1: &PolygonMesh) -> Option<(Point3, Point3)> {
     let tris0 = Triangulate::new(poly0);
     let tris1 = Triangulate::new(poly1);
@@ -0,0 +1,172 @@
+use super::*;
+use truck_topology::{Shell, Solid};
+
+type PolylineCurve = truck_polymesh::PolylineCurve<Point3>;
+
+/// The classification of faces by the draft angles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DraftType {
+    /// the draft angle is not less than the positive limit.
+    Positive,
+    /// the draft angle is not greater than minus the negative limit.
+    Negative,
+    /// the draft angle is between the limits, i.e. the face is almost parallel to the pulling.
+    Vertical,
+    /// the face of a shape includes the parts of different types.
+    Mixed,
+}
+
+/// Configures of draft analysis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DraftDescriptor {
+    /// the direction in which the mold is pulled. Default is `Vector3::unit_z()`.
+    pub pull_direction: Vector3,
+    /// the minimum draft angle of positive faces. Default is 1 degree.
+    pub positive_limit: Rad<f64>,
+    /// the minimum absolute value of the draft angle of negative faces. Default is 1 degree.
+    pub negative_limit: Rad<f64>,
+}
+
+impl Default for DraftDescriptor {
+    #[inline(always)]
+    fn default() -> DraftDescriptor {
+        DraftDescriptor {
+            pull_direction: Vector3::unit_z(),
+            positive_limit: Deg(1.0).into(),
+            negative_limit: Deg(1.0).into(),
+        }
+    }
+}
+
+impl DraftDescriptor {
+    /// Returns the draft angle of the face with `normal`, the angle between the face and
+    /// the pull direction. The angle is positive if the normal is toward the pull direction.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let descriptor = DraftDescriptor::default();
+    /// let angle = descriptor.draft_angle(Vector3::new(1.0, 0.0, 1.0));
+    /// assert!(angle.0.near(&(std::f64::consts::PI / 4.0)));
+    /// assert_eq!(descriptor.classify(angle), DraftType::Positive);
+    /// ```
+    #[inline(always)]
+    pub fn draft_angle(&self, normal: Vector3) -> Rad<f64> {
+        let cos = normal.normalize().dot(self.pull_direction.normalize());
+        Rad(f64::asin(f64::clamp(cos, -1.0, 1.0)))
+    }
+
+    /// Classifies the draft angle `angle`.
+    #[inline(always)]
+    pub fn classify(&self, angle: Rad<f64>) -> DraftType {
+        if angle >= self.positive_limit {
+            DraftType::Positive
+        } else if angle <= -self.negative_limit {
+            DraftType::Negative
+        } else {
+            DraftType::Vertical
+        }
+    }
+}
+
+impl DraftType {
+    /// Returns the color for visualizing the type:
+    /// green for `Positive`, red for `Negative`, yellow for `Vertical` and blue for `Mixed`.
+    #[inline(always)]
+    pub fn color(self) -> Vector4 {
+        match self {
+            DraftType::Positive => Vector4::new(0.0, 0.8, 0.0, 1.0),
+            DraftType::Negative => Vector4::new(0.8, 0.0, 0.0, 1.0),
+            DraftType::Vertical => Vector4::new(0.8, 0.8, 0.0, 1.0),
+            DraftType::Mixed => Vector4::new(0.0, 0.0, 0.8, 1.0),
+        }
+    }
+
+    fn merge(self, other: DraftType) -> DraftType {
+        match self == other {
+            true => self,
+            false => DraftType::Mixed,
+        }
+    }
+}
+
+/// Draft analysis for checking the feasibility of molding and casting.
+pub trait DraftAnalysis {
+    /// Returns the types of the faces by the draft angles relative to the pull direction.
+    ///
+    /// The types of polygon meshes are given in the order of `Faces::face_iter`,
+    /// and the faces whose normals are not determined are `DraftType::Vertical`.
+    /// For tessellated shells and solids, the types are given in the order of `face_iter`,
+    /// and a face is `DraftType::Mixed` if its polygons are of different types.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// // a frustum of a cone, pulled toward +z
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let e = builder::tsweep(&v, Vector3::new(-0.5, 0.0, 1.0));
+    /// let side = builder::rsweep(&e, Point3::origin(), -Vector3::unit_z(), Rad(7.0));
+    /// let shell = side.triangulation(0.01).unwrap();
+    ///
+    /// let types = shell.draft_analysis(&DraftDescriptor::default());
+    /// assert!(types.iter().all(|draft_type| *draft_type == DraftType::Positive));
+    ///
+    /// // pulled toward -z
+    /// let descriptor = DraftDescriptor {
+    ///     pull_direction: -Vector3::unit_z(),
+    ///     ..Default::default()
+    /// };
+    /// let types = shell.draft_analysis(&descriptor);
+    /// assert!(types.iter().all(|draft_type| *draft_type == DraftType::Negative));
+    ///
+    /// // colored meshes for visualization
+    /// let colored: Vec<(Vector4, PolygonMesh)> = shell
+    ///     .face_iter()
+    ///     .zip(&types)
+    ///     .map(|(face, draft_type)| (draft_type.color(), face.oriented_surface()))
+    ///     .collect();
+    /// assert_eq!(colored.len(), types.len());
+    /// ```
+    fn draft_analysis(&self, descriptor: &DraftDescriptor) -> Vec<DraftType>;
+}
+
+impl DraftAnalysis for PolygonMesh {
+    fn draft_analysis(&self, descriptor: &DraftDescriptor) -> Vec<DraftType> {
+        self.face_iter()
+            .enumerate()
+            .map(|(i, face)| {
+                let normal = FaceNormal::new(self.positions(), face, i).normal;
+                match normal.magnitude2().is_nan() {
+                    true => DraftType::Vertical,
+                    false => descriptor.classify(descriptor.draft_angle(normal)),
+                }
+            })
+            .collect()
+    }
+}
+
+impl DraftAnalysis for Shell<Point3, PolylineCurve, PolygonMesh> {
+    fn draft_analysis(&self, descriptor: &DraftDescriptor) -> Vec<DraftType> {
+        self.face_iter()
+            .map(|face| {
+                face.oriented_surface()
+                    .draft_analysis(descriptor)
+                    .into_iter()
+                    .fold(None, |res: Option<DraftType>, draft_type| match res {
+                        Some(res) => Some(res.merge(draft_type)),
+                        None => Some(draft_type),
+                    })
+                    .unwrap_or(DraftType::Vertical)
+            })
+            .collect()
+    }
+}
+
+impl DraftAnalysis for Solid<Point3, PolylineCurve, PolygonMesh> {
+    fn draft_analysis(&self, descriptor: &DraftDescriptor) -> Vec<DraftType> {
+        self.boundaries()
+            .iter()
+            .flat_map(|shell| shell.draft_analysis(descriptor))
+            .collect()
+    }
+}
@@ -0,0 +1,75 @@
+use super::*;
+use std::collections::HashMap;
+
+/// Extracts the feature edges: boundary edges and sharp edges.
+pub trait FeatureEdges {
+    /// Returns the feature edges as the pairs of the indices of positions, sorted and
+    /// without duplicates. The feature edges are
+    ///
+    /// - the boundary edges, adjacent to only one face,
+    /// - the non-manifold edges, adjacent to three or more faces, and
+    /// - the sharp edges, whose two adjacent faces make the angle of normals larger than `angle`.
+    ///
+    /// The edges of the faces are identified by the indices of positions, and the normals of
+    /// faces are computed from positions, ignoring the normals of the mesh.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // a folded square
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 2.0, 1.0),
+    ///     Point3::new(1.0, 2.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2, 3], [3, 2, 5, 4]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// // all edges are boundary or sharp
+    /// assert_eq!(mesh.extract_feature_edges(Deg(30.0).into()).len(), 7);
+    /// // the fold is not sharp
+    /// let edges = mesh.extract_feature_edges(Deg(60.0).into());
+    /// assert_eq!(edges.len(), 6);
+    /// assert!(!edges.contains(&[2, 3]));
+    /// ```
+    fn extract_feature_edges(&self, angle: Rad<f64>) -> Vec<[usize; 2]>;
+}
+
+impl FeatureEdges for PolygonMesh {
+    #[inline(always)]
+    fn extract_feature_edges(&self, angle: Rad<f64>) -> Vec<[usize; 2]> {
+        self.as_slice().extract_feature_edges(angle)
+    }
+}
+
+impl FeatureEdges for PolygonMeshSlice<'_> {
+    fn extract_feature_edges(&self, angle: Rad<f64>) -> Vec<[usize; 2]> {
+        let positions = self.positions();
+        let mut adjacency: HashMap<[usize; 2], Vec<Vector3>> = HashMap::new();
+        self.face_iter().enumerate().for_each(|(i, face)| {
+            let normal = FaceNormal::new(positions, face, i).normal;
+            let len = face.len();
+            (0..len).for_each(|j| {
+                let (v0, v1) = (face[j].pos, face[(j + 1) % len].pos);
+                if v0 != v1 {
+                    let edge = [usize::min(v0, v1), usize::max(v0, v1)];
+                    adjacency.entry(edge).or_default().push(normal);
+                }
+            });
+        });
+        let cos = f64::cos(angle.0);
+        let mut edges: Vec<[usize; 2]> = adjacency
+            .into_iter()
+            .filter(|(_, normals)| match normals.len() {
+                // the normals of degenerate faces are NaN and do not make sharp edges
+                2 => normals[0].dot(normals[1]) < cos,
+                _ => true,
+            })
+            .map(|(edge, _)| edge)
+            .collect();
+        edges.sort();
+        edges
+    }
+}
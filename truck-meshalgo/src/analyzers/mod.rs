@@ -4,9 +4,24 @@ mod topology;
 mod splitting;
 mod collision;
 mod point_cloud;
+mod ray_casting;
+mod draft;
+mod parting_line;
+mod skeleton;
+mod symmetry;
+mod feature_edges;
+mod visibility;
 
 pub use topology::Topology;
 pub use splitting::Splitting;
 pub use splitting::ExperimentalSplitters;
 pub use collision::Collision;
+pub(crate) use collision::{collide_triangles, overlapping_triangle_pairs};
 pub use point_cloud::WithPointCloud;
+pub use ray_casting::{MeshBVH, RayHit};
+pub use draft::{DraftAnalysis, DraftDescriptor, DraftType};
+pub use parting_line::PartingLine;
+pub use skeleton::{Skeleton, SkeletonBranch, SkeletonExtraction};
+pub use symmetry::{Symmetry, SymmetryDescriptor, SymmetryDetection};
+pub use feature_edges::FeatureEdges;
+pub use visibility::{FaceVisibility, VisibilityAnalysis, VisibilityDescriptor};
@@ -0,0 +1,137 @@
+use super::*;
+use crate::filters::OptimizingFilter;
+use crate::tessellation::MeshedShape;
+use std::collections::{HashMap, HashSet};
+use truck_topology::{Shell, Solid};
+
+type PolylineCurve = truck_polymesh::PolylineCurve<Point3>;
+
+/// Extracts the parting lines for molding and casting.
+pub trait PartingLine {
+    /// Returns the parting lines, the boundaries between the regions of the positive and
+    /// the negative draft angles relative to `pull_direction`.
+    ///
+    /// The draft angles of the vertices are given by the normals averaged by the areas of
+    /// the adjacent faces, and the parting lines are the curves on which the draft angles
+    /// interpolated linearly on the triangles are zero. So, the parting lines run through
+    /// the middle of the vertical faces, and the lines of closed meshes are closed, i.e. the
+    /// first and the last points are the same. The lines are oriented so that the regions
+    /// of the positive draft angles are on the left when seen from the front of the faces.
+    ///
+    /// The faces are connected by the indices of positions. For tessellated shells and solids,
+    /// the same positions of the faces are put together.
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the unit sphere
+    /// let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    /// let wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(PI));
+    /// let shell = builder::cone(&wire, Vector3::unit_z(), Rad(2.0 * PI));
+    /// let sphere = Solid::new(vec![shell]).triangulation(0.01).unwrap();
+    ///
+    /// // the parting line of the mold pulled toward +z is the equator
+    /// let lines = sphere.parting_lines(Vector3::unit_z());
+    /// assert_eq!(lines.len(), 1);
+    /// assert_eq!(lines[0].front(), lines[0].back());
+    /// for pt in lines[0].iter() {
+    ///     assert!(f64::abs(pt.z) < 0.05);
+    ///     assert!(f64::abs(pt.to_vec().magnitude() - 1.0) < 0.05);
+    /// }
+    /// ```
+    fn parting_lines(&self, pull_direction: Vector3) -> Vec<PolylineCurve>;
+}
+
+impl PartingLine for PolygonMesh {
+    fn parting_lines(&self, pull_direction: Vector3) -> Vec<PolylineCurve> {
+        let positions = self.positions();
+        let direction = pull_direction.normalize();
+        let triangles: Vec<[usize; 3]> = self
+            .face_iter()
+            .flat_map(|face| (2..face.len()).map(move |i| [face[0], face[i - 1], face[i]]))
+            .map(|tri| tri.map(|v| v.pos))
+            .collect();
+        let mut normals = vec![Vector3::zero(); positions.len()];
+        triangles.iter().for_each(|tri| {
+            let p = tri.map(|i| positions[i]);
+            // the length of the cross product is in proportion to the area
+            let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+            tri.iter().for_each(|i| normals[*i] += normal);
+        });
+        // the sines of the draft angles of the vertices
+        let drafts: Vec<f64> = normals
+            .iter()
+            .map(|normal| match normal.so_small() {
+                true => 0.0,
+                false => normal.normalize().dot(direction),
+            })
+            .collect();
+
+        // The edges crossing the parting lines are identified by the pairs of the positive
+        // vertex and the negative vertex, and the segments are from the edge on which
+        // the draft changes to negative to the edge on which it changes to positive.
+        let mut segments = HashMap::<[usize; 2], [usize; 2]>::new();
+        triangles.iter().for_each(|tri| {
+            let positive = tri.map(|i| drafts[i] >= 0.0);
+            let (mut start, mut end) = (None, None);
+            (0..3).for_each(|i| {
+                let j = (i + 1) % 3;
+                match (positive[i], positive[j]) {
+                    (true, false) => start = Some([tri[i], tri[j]]),
+                    (false, true) => end = Some([tri[j], tri[i]]),
+                    _ => {}
+                }
+            });
+            if let (Some(start), Some(end)) = (start, end) {
+                segments.insert(start, end);
+            }
+        });
+        let ends: HashSet<[usize; 2]> = segments.values().copied().collect();
+        let mut starts: Vec<[usize; 2]> = segments.keys().copied().collect();
+        starts.sort();
+        // the open lines begin at the boundaries of the open meshes
+        let (open, closed): (Vec<_>, Vec<_>) =
+            starts.into_iter().partition(|start| !ends.contains(start));
+        let mut lines = Vec::new();
+        open.into_iter().chain(closed).for_each(|start| {
+            let mut edges = Vec::new();
+            let mut current = start;
+            while let Some(next) = segments.remove(&current) {
+                edges.push(current);
+                current = next;
+            }
+            if !edges.is_empty() {
+                edges.push(current);
+                lines.push(edges);
+            }
+        });
+        lines
+            .into_iter()
+            .map(|edges| {
+                let points = edges.into_iter().map(|[a, b]| {
+                    let t = drafts[a] / (drafts[a] - drafts[b]);
+                    positions[a] + (positions[b] - positions[a]) * t
+                });
+                PolylineCurve(points.collect())
+            })
+            .collect()
+    }
+}
+
+impl PartingLine for Shell<Point3, PolylineCurve, PolygonMesh> {
+    fn parting_lines(&self, pull_direction: Vector3) -> Vec<PolylineCurve> {
+        let mut polygon = self.into_polygon();
+        polygon.put_together_same_attrs();
+        polygon.parting_lines(pull_direction)
+    }
+}
+
+impl PartingLine for Solid<Point3, PolylineCurve, PolygonMesh> {
+    fn parting_lines(&self, pull_direction: Vector3) -> Vec<PolylineCurve> {
+        let mut polygon = self.into_polygon();
+        polygon.put_together_same_attrs();
+        polygon.parting_lines(pull_direction)
+    }
+}
@@ -52,7 +52,7 @@ impl WithPointCloud for PolygonMesh {
 }
 
 // https://iquilezles.org/www/articles/distfunctions/distfunctions.htm
-fn distance2_point_triangle(point: Point3, triangle: [Point3; 3]) -> f64 {
+pub(super) fn distance2_point_triangle(point: Point3, triangle: [Point3; 3]) -> f64 {
     let ab = triangle[1] - triangle[0];
     let ap = point - triangle[0];
     let bc = triangle[2] - triangle[1];
@@ -0,0 +1,366 @@
+use super::*;
+use super::point_cloud::distance2_point_triangle;
+
+const LEAF_SIZE: usize = 4;
+
+/// Bounding volume hierarchy of the triangles of a polygon mesh, for casting rays.
+///
+/// Quadrangles and other polygons are divided into triangles, and each triangle remembers
+/// the index of the original face in the order of `Faces::face_iter`.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+/// let bvh = MeshBVH::new(&mesh);
+///
+/// let hit = bvh
+///     .ray_cast(Point3::new(0.25, 0.5, 1.0), Vector3::new(0.0, 0.0, -1.0))
+///     .unwrap();
+/// assert!(hit.distance.near(&1.0));
+/// assert!(hit.point().near(&Point3::new(0.25, 0.5, 0.0)));
+/// assert_eq!(hit.face_index, 0);
+///
+/// assert!(bvh
+///     .ray_cast(Point3::new(1.5, 0.5, 1.0), Vector3::new(0.0, 0.0, -1.0))
+///     .is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct MeshBVH {
+    triangles: Vec<Triangle>,
+    nodes: Vec<Node>,
+}
+
+/// The intersection of a ray and a polygon mesh.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// the parameter of the ray. The intersection point is `origin + distance * direction`.
+    pub distance: f64,
+    /// the index of the hit face in the order of `Faces::face_iter`.
+    pub face_index: usize,
+    /// the vertices of the hit triangle.
+    pub vertices: [Vertex; 3],
+    /// the positions of the hit triangle.
+    pub positions: [Point3; 3],
+    /// the barycentric coordinates of the intersection point in the hit triangle.
+    pub barycentric: [f64; 3],
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Triangle {
+    face_index: usize,
+    vertices: [Vertex; 3],
+    positions: [Point3; 3],
+    center: Point3,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bdb: BoundingBox<Point3>,
+    kind: NodeKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NodeKind {
+    Leaf(usize, usize),
+    Branch(usize, usize),
+}
+
+impl RayHit {
+    /// Returns the intersection point.
+    #[inline(always)]
+    pub fn point(&self) -> Point3 {
+        let [a, b, c] = self.barycentric;
+        let p = self.positions;
+        Point3::from_vec(a * p[0].to_vec() + b * p[1].to_vec() + c * p[2].to_vec())
+    }
+    /// Returns the unit normal of the hit triangle, oriented by the order of vertices.
+    #[inline(always)]
+    pub fn face_normal(&self) -> Vector3 {
+        let p = self.positions;
+        (p[1] - p[0]).cross(p[2] - p[0]).normalize()
+    }
+}
+
+impl MeshBVH {
+    /// Constructs the bounding volume hierarchy of `mesh`.
+    pub fn new(mesh: &PolygonMesh) -> MeshBVH {
+        let positions = mesh.positions();
+        let mut triangles = Vec::new();
+        mesh.face_iter().enumerate().for_each(|(face_index, face)| {
+            (2..face.len()).for_each(|i| {
+                let vertices = [face[0], face[i - 1], face[i]];
+                let positions = [
+                    positions[vertices[0].pos],
+                    positions[vertices[1].pos],
+                    positions[vertices[2].pos],
+                ];
+                let center = Point3::from_vec(
+                    (positions[0].to_vec() + positions[1].to_vec() + positions[2].to_vec()) / 3.0,
+                );
+                triangles.push(Triangle {
+                    face_index,
+                    vertices,
+                    positions,
+                    center,
+                });
+            })
+        });
+        let mut bvh = MeshBVH {
+            triangles,
+            nodes: Vec::new(),
+        };
+        if !bvh.triangles.is_empty() {
+            bvh.build(0, bvh.triangles.len());
+        }
+        bvh
+    }
+
+    /// Returns the number of triangles.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.triangles.len() }
+
+    /// Returns whether the hierarchy has no triangles or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.triangles.is_empty() }
+
+    /// Returns the bounding box of all triangles.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> {
+        match self.nodes.first() {
+            Some(node) => node.bdb.clone(),
+            None => BoundingBox::new(),
+        }
+    }
+
+    /// Builds the node of `triangles[start..end]` and returns its index.
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let bdb: BoundingBox<Point3> = self.triangles[start..end]
+            .iter()
+            .flat_map(|tri| tri.positions.iter())
+            .collect();
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            bdb,
+            kind: NodeKind::Leaf(start, end),
+        });
+        if end - start <= LEAF_SIZE {
+            return idx;
+        }
+        let centers: BoundingBox<Point3> = self.triangles[start..end]
+            .iter()
+            .map(|tri| tri.center)
+            .collect();
+        let diag = centers.diagonal();
+        let axis = match (diag[0] < diag[1], diag[1] < diag[2], diag[0] < diag[2]) {
+            (true, true, _) | (false, _, true) => 2,
+            (true, false, _) => 1,
+            (false, _, false) => 0,
+        };
+        if diag[axis].so_small() {
+            return idx;
+        }
+        let mid = (start + end) / 2;
+        self.triangles[start..end].sort_by(|a, b| {
+            a.center[axis]
+                .partial_cmp(&b.center[axis])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[idx].kind = NodeKind::Branch(left, right);
+        idx
+    }
+
+    /// Returns the nearest intersection of the ray and the mesh.
+    ///
+    /// Only the intersections with positive parameters are detected.
+    /// Both sides of triangles are hit.
+    pub fn ray_cast(&self, origin: Point3, direction: Vector3) -> Option<RayHit> {
+        let mut res: Option<RayHit> = None;
+        self.traverse(origin, direction, f64::INFINITY, |hit| {
+            if res.map(|res| hit.distance < res.distance).unwrap_or(true) {
+                res = Some(hit);
+            }
+            Some(hit.distance)
+        });
+        res
+    }
+
+    /// Returns whether the segment from `origin` to `origin + max_distance * direction`
+    /// intersects the mesh or not.
+    pub fn is_occluded(&self, origin: Point3, direction: Vector3, max_distance: f64) -> bool {
+        let mut res = false;
+        self.traverse(origin, direction, max_distance, |_| {
+            res = true;
+            None
+        });
+        res
+    }
+
+    /// Returns the distance between `point` and the mesh,
+    /// or `f64::INFINITY` if the hierarchy has no triangles.
+    pub fn distance(&self, point: Point3) -> f64 {
+        let mut res2 = f64::INFINITY;
+        if self.nodes.is_empty() {
+            return res2;
+        }
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            if distance2_point_box(&node.bdb, point) >= res2 {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Branch(left, right) => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+                NodeKind::Leaf(start, end) => {
+                    self.triangles[start..end].iter().for_each(|tri| {
+                        res2 = f64::min(res2, distance2_point_triangle(point, tri.positions))
+                    })
+                }
+            }
+        }
+        res2.sqrt()
+    }
+
+    /// Returns whether `point` is inside the mesh or not.
+    ///
+    /// The mesh is assumed to be closed. The parity of the number of intersections
+    /// with rays is decided by the majority of three rays for the robustness.
+    pub fn contains(&self, point: Point3) -> bool {
+        let directions = [
+            Vector3::new(0.5773, 0.5774, 0.5774),
+            Vector3::new(-0.6123, 0.5004, -0.6124),
+            Vector3::new(0.2673, -0.8018, -0.5345),
+        ];
+        let count = directions
+            .iter()
+            .filter(|dir| {
+                let mut counter = 0;
+                self.traverse(point, **dir, f64::INFINITY, |_| {
+                    counter += 1;
+                    Some(f64::INFINITY)
+                });
+                counter % 2 == 1
+            })
+            .count();
+        count >= 2
+    }
+
+    /// Calls `closure` for each intersection in `(0, max_distance)`.
+    /// Stops traversing if `closure` returns `None`,
+    /// otherwise continues with the returned value as the new `max_distance`.
+    pub(crate) fn traverse<F: FnMut(RayHit) -> Option<f64>>(
+        &self,
+        origin: Point3,
+        direction: Vector3,
+        mut max_distance: f64,
+        mut closure: F,
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let inv = Vector3::new(1.0 / direction[0], 1.0 / direction[1], 1.0 / direction[2]);
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            if !hit_box(&node.bdb, origin, inv, max_distance) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Branch(left, right) => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+                NodeKind::Leaf(start, end) => {
+                    for tri in &self.triangles[start..end] {
+                        if let Some(hit) = hit_triangle(tri, origin, direction) {
+                            if hit.distance < max_distance {
+                                match closure(hit) {
+                                    Some(distance) => max_distance = distance,
+                                    None => return,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn hit_box(bdb: &BoundingBox<Point3>, origin: Point3, inv: Vector3, max_distance: f64) -> bool {
+    let (min, max) = (bdb.min(), bdb.max());
+    let mut t0 = 0.0_f64;
+    let mut t1 = max_distance;
+    for i in 0..3 {
+        let a = (min[i] - origin[i]) * inv[i];
+        let b = (max[i] - origin[i]) * inv[i];
+        // NaN occurs when the ray is on the plane of the face of the box.
+        let (a, b) = match a <= b {
+            true => (a, b),
+            false => (b, a),
+        };
+        if !a.is_nan() {
+            t0 = f64::max(t0, a - TOLERANCE);
+        }
+        if !b.is_nan() {
+            t1 = f64::min(t1, b + TOLERANCE);
+        }
+        if t0 > t1 {
+            return false;
+        }
+    }
+    true
+}
+
+fn distance2_point_box(bdb: &BoundingBox<Point3>, point: Point3) -> f64 {
+    let (min, max) = (bdb.min(), bdb.max());
+    (0..3)
+        .map(|i| {
+            let d = f64::max(f64::max(min[i] - point[i], point[i] - max[i]), 0.0);
+            d * d
+        })
+        .sum()
+}
+
+/// Möller–Trumbore intersection algorithm
+fn hit_triangle(tri: &Triangle, origin: Point3, direction: Vector3) -> Option<RayHit> {
+    let p = tri.positions;
+    let (e1, e2) = (p[1] - p[0], p[2] - p[0]);
+    let pvec = direction.cross(e2);
+    let det = e1.dot(pvec);
+    if det.abs() < f64::EPSILON {
+        return None;
+    }
+    let tvec = origin - p[0];
+    let u = tvec.dot(pvec) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let qvec = tvec.cross(e1);
+    let v = direction.dot(qvec) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = e2.dot(qvec) / det;
+    match distance > 0.0 {
+        true => Some(RayHit {
+            distance,
+            face_index: tri.face_index,
+            vertices: tri.vertices,
+            positions: tri.positions,
+            barycentric: [1.0 - u - v, u, v],
+        }),
+        false => None,
+    }
+}
@@ -0,0 +1,394 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+
+/// The curve skeleton, a graph whose edges are polylines.
+#[derive(Clone, Debug, Default)]
+pub struct Skeleton {
+    /// the end points and the branch points
+    pub nodes: Vec<Point3>,
+    /// the polylines between the nodes
+    pub branches: Vec<SkeletonBranch>,
+}
+
+/// A branch of the curve skeleton.
+#[derive(Clone, Debug)]
+pub struct SkeletonBranch {
+    /// the indices of the nodes at the front and the back of `curve`.
+    /// The indices are the same if the branch is a loop.
+    pub ends: (usize, usize),
+    /// the polyline from the front node to the back node
+    pub curve: PolylineCurve<Point3>,
+}
+
+/// Extracts the curve skeletons of closed meshes.
+pub trait SkeletonExtraction {
+    /// Returns the curve skeleton by the topology-preserving thinning of the voxels.
+    ///
+    /// The inside of the mesh is voxelized by the cubes with the edge length `voxel_size`,
+    /// and the voxels are peeled from the six directions in turn, keeping the topology and
+    /// the end points of curves. The remaining voxels are traced into the branches, and
+    /// the spurs shorter than the distance from their branch points to the mesh are pruned.
+    /// The mesh is assumed to be closed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// // a pipe with the radius 1 and the length 10 along the x axis
+    /// let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    /// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_x(), Rad(7.0));
+    /// let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+    /// let pipe = builder::tsweep(&disk, Vector3::new(10.0, 0.0, 0.0));
+    /// let mut mesh = pipe.triangulation(0.01).unwrap().into_polygon();
+    /// mesh.put_together_same_attrs();
+    ///
+    /// let skeleton = mesh.curve_skeleton(0.25);
+    /// // the centerline
+    /// assert_eq!(skeleton.nodes.len(), 2);
+    /// assert_eq!(skeleton.branches.len(), 1);
+    /// let curve = &skeleton.branches[0].curve;
+    /// assert!(curve.iter().all(|pt| f64::hypot(pt[1], pt[2]) < 0.25));
+    /// assert!(curve[0].distance(curve[curve.len() - 1]) > 7.0);
+    /// ```
+    fn curve_skeleton(&self, voxel_size: f64) -> Skeleton;
+}
+
+impl SkeletonExtraction for PolygonMesh {
+    fn curve_skeleton(&self, voxel_size: f64) -> Skeleton {
+        let bvh = MeshBVH::new(self);
+        let bdb = bvh.bounding_box();
+        if bdb.is_empty() || voxel_size <= 0.0 {
+            return Skeleton::default();
+        }
+        let mut grid = VoxelGrid::new(&bdb, voxel_size, |p| bvh.contains(p));
+        grid.thin();
+        loop {
+            grid.remove_simple_points();
+            if !grid.prune_spurs(|p| bvh.distance(p)) {
+                break;
+            }
+        }
+        grid.skeleton()
+    }
+}
+
+const FACE_DIRECTIONS: [[i64; 3]; 6] = [
+    [1, 0, 0],
+    [-1, 0, 0],
+    [0, 1, 0],
+    [0, -1, 0],
+    [0, 0, 1],
+    [0, 0, -1],
+];
+
+#[derive(Clone, Debug)]
+struct VoxelGrid {
+    origin: Point3,
+    size: f64,
+    dims: [i64; 3],
+    data: Vec<bool>,
+}
+
+impl VoxelGrid {
+    /// Voxelizes the bounding box with one empty voxel around.
+    fn new<F: Fn(Point3) -> bool>(bdb: &BoundingBox<Point3>, size: f64, inside: F) -> VoxelGrid {
+        let diag = bdb.diagonal();
+        let dims = [
+            (diag[0] / size).ceil() as i64 + 2,
+            (diag[1] / size).ceil() as i64 + 2,
+            (diag[2] / size).ceil() as i64 + 2,
+        ];
+        let mut grid = VoxelGrid {
+            origin: bdb.min() - Vector3::new(size, size, size),
+            size,
+            dims,
+            data: vec![false; (dims[0] * dims[1] * dims[2]) as usize],
+        };
+        for voxel in grid.voxels().collect::<Vec<_>>() {
+            let idx = grid.index(voxel);
+            grid.data[idx] = inside(grid.center(voxel));
+        }
+        grid
+    }
+
+    fn voxels(&self) -> impl Iterator<Item = [i64; 3]> {
+        let dims = self.dims;
+        (0..dims[0]).flat_map(move |i| {
+            (0..dims[1]).flat_map(move |j| (0..dims[2]).map(move |k| [i, j, k]))
+        })
+    }
+
+    fn filled_voxels(&self) -> Vec<[i64; 3]> { self.voxels().filter(|v| self.get(*v)).collect() }
+
+    fn index(&self, v: [i64; 3]) -> usize {
+        ((v[0] * self.dims[1] + v[1]) * self.dims[2] + v[2]) as usize
+    }
+
+    fn get(&self, v: [i64; 3]) -> bool {
+        (0..3).all(|i| 0 <= v[i] && v[i] < self.dims[i]) && self.data[self.index(v)]
+    }
+
+    fn remove(&mut self, v: [i64; 3]) {
+        let idx = self.index(v);
+        self.data[idx] = false;
+    }
+
+    fn center(&self, v: [i64; 3]) -> Point3 {
+        let v = Vector3::new(v[0] as f64 + 0.5, v[1] as f64 + 0.5, v[2] as f64 + 0.5);
+        self.origin + v * self.size
+    }
+
+    /// the 3 x 3 x 3 neighborhood of `v`
+    fn neighborhood(&self, v: [i64; 3]) -> [bool; 27] {
+        let mut cube = [false; 27];
+        offsets().for_each(|(idx, d)| cube[idx] = self.get(add(v, d)));
+        cube
+    }
+
+    /// the filled voxels adjacent to `v`
+    fn neighbors(&self, v: [i64; 3]) -> impl Iterator<Item = [i64; 3]> + '_ {
+        offsets()
+            .filter(|(idx, _)| *idx != 13)
+            .map(move |(_, d)| add(v, d))
+            .filter(move |w| self.get(*w))
+    }
+
+    /// Removes the simple points which are not the end points, until no voxel is removed.
+    /// The voxels are peeled from the six directions in turn. In each direction, the voxels
+    /// one voxel thick are kept so that thin plates are not eaten away sequentially.
+    fn thin(&mut self) {
+        loop {
+            let mut changed = false;
+            for dir in FACE_DIRECTIONS.iter() {
+                let borders: Vec<[i64; 3]> = self
+                    .voxels()
+                    .filter(|v| self.get(*v) && !self.get(add(*v, *dir)))
+                    .collect();
+                let back = (13 - dir[0] * 9 - dir[1] * 3 - dir[2]) as usize;
+                for v in borders {
+                    let cube = self.neighborhood(v);
+                    if count(&cube) > 2 && cube[back] && is_simple(&cube) {
+                        self.remove(v);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Removes the remaining simple points which are not the end points,
+    /// e.g. the corners of curves and the diagonal plates.
+    fn remove_simple_points(&mut self) {
+        loop {
+            let mut changed = false;
+            for v in self.filled_voxels() {
+                let cube = self.neighborhood(v);
+                if count(&cube) > 2 && is_simple(&cube) {
+                    self.remove(v);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Removes the spurs, the branches from end points to branch points shorter than
+    /// `radius` at the branch points. Returns whether some spurs are removed or not.
+    fn prune_spurs<F: Fn(Point3) -> f64>(&mut self, radius: F) -> bool {
+        let degree = |v: [i64; 3]| self.neighbors(v).count();
+        let spurs: Vec<Vec<[i64; 3]>> = self
+            .paths()
+            .into_iter()
+            .filter_map(|mut path| {
+                let (front, back) = (path[0], path[path.len() - 1]);
+                if degree(front) > 2 && degree(back) == 1 {
+                    path.reverse();
+                } else if !(degree(front) == 1 && degree(back) > 2) {
+                    return None;
+                }
+                let junction = self.center(path[path.len() - 1]);
+                let length: f64 = path
+                    .windows(2)
+                    .map(|v| self.center(v[0]).distance(self.center(v[1])))
+                    .sum();
+                match length < radius(junction) {
+                    true => Some(path),
+                    false => None,
+                }
+            })
+            .collect();
+        spurs.iter().for_each(|path| {
+            path[..path.len() - 1].iter().for_each(|v| self.remove(*v));
+        });
+        !spurs.is_empty()
+    }
+
+    /// Traces the filled voxels into the paths between the voxels whose degrees are not 2.
+    /// The first and the last voxels of loops are the same.
+    fn paths(&self) -> Vec<Vec<[i64; 3]>> {
+        let voxels = self.filled_voxels();
+        let map: HashMap<[i64; 3], usize> =
+            voxels.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let adjacency: Vec<Vec<usize>> = voxels
+            .iter()
+            .map(|v| self.neighbors(*v).map(|w| map[&w]).collect())
+            .collect();
+        let mut is_node: Vec<bool> = adjacency.iter().map(|adj| adj.len() != 2).collect();
+        let mut tracer = Tracer {
+            adjacency: &adjacency,
+            visited: vec![false; voxels.len()],
+            visited_edges: HashSet::new(),
+        };
+        let mut paths = Vec::new();
+        for start in (0..voxels.len()).filter(|i| is_node[*i]) {
+            for &next in &adjacency[start] {
+                if !tracer.visited_edges.contains(&edge(start, next)) {
+                    paths.push(tracer.trace(start, next, &is_node));
+                }
+            }
+        }
+        // loops without nodes
+        for i in 0..voxels.len() {
+            if !tracer.visited[i] && !is_node[i] {
+                is_node[i] = true;
+                paths.push(tracer.trace(i, adjacency[i][0], &is_node));
+            }
+        }
+        paths
+            .into_iter()
+            .map(|path| path.into_iter().map(|i| voxels[i]).collect())
+            .collect()
+    }
+
+    /// Returns the skeleton of the filled voxels.
+    fn skeleton(&self) -> Skeleton {
+        let mut skeleton = Skeleton::default();
+        let mut node_map = HashMap::new();
+        let mut node_index = |v: [i64; 3], nodes: &mut Vec<Point3>| {
+            *node_map.entry(v).or_insert_with(|| {
+                nodes.push(self.center(v));
+                nodes.len() - 1
+            })
+        };
+        for path in self.paths() {
+            let front = node_index(path[0], &mut skeleton.nodes);
+            let back = node_index(path[path.len() - 1], &mut skeleton.nodes);
+            skeleton.branches.push(SkeletonBranch {
+                ends: (front, back),
+                curve: path.into_iter().map(|v| self.center(v)).collect(),
+            });
+        }
+        // isolated voxels
+        for v in self.filled_voxels() {
+            if self.neighbors(v).next().is_none() {
+                node_index(v, &mut skeleton.nodes);
+            }
+        }
+        skeleton
+    }
+}
+
+struct Tracer<'a> {
+    adjacency: &'a [Vec<usize>],
+    visited: Vec<bool>,
+    visited_edges: HashSet<(usize, usize)>,
+}
+
+impl<'a> Tracer<'a> {
+    /// Traces the voxels from the edge `(start, next)` until a node, and returns the path.
+    fn trace(&mut self, start: usize, next: usize, is_node: &[bool]) -> Vec<usize> {
+        let mut path = vec![start, next];
+        self.visited[start] = true;
+        self.visited_edges.insert(edge(start, next));
+        let (mut prev, mut current) = (start, next);
+        while !is_node[current] {
+            self.visited[current] = true;
+            let following = match self.adjacency[current].iter().find(|w| **w != prev) {
+                Some(following) => *following,
+                None => break,
+            };
+            self.visited_edges.insert(edge(current, following));
+            path.push(following);
+            prev = current;
+            current = following;
+        }
+        path
+    }
+}
+
+#[inline(always)]
+fn edge(i: usize, j: usize) -> (usize, usize) { (usize::min(i, j), usize::max(i, j)) }
+
+#[inline(always)]
+fn add(v: [i64; 3], d: [i64; 3]) -> [i64; 3] { [v[0] + d[0], v[1] + d[1], v[2] + d[2]] }
+
+/// the number of the filled voxels in the neighborhood, including the center
+#[inline(always)]
+fn count(cube: &[bool; 27]) -> usize { cube.iter().filter(|b| **b).count() }
+
+/// the indices in the 3 x 3 x 3 neighborhood and the offsets
+fn offsets() -> impl Iterator<Item = (usize, [i64; 3])> {
+    (0..27).map(|idx| {
+        let i = idx as i64;
+        (idx, [i / 9 - 1, i / 3 % 3 - 1, i % 3 - 1])
+    })
+}
+
+/// Whether the center of the neighborhood is a simple point: the removal does not change
+/// the topology of the object by 26-adjacency and the background by 6-adjacency.
+fn is_simple(cube: &[bool; 27]) -> bool {
+    let offsets: Vec<[i64; 3]> = offsets().map(|(_, d)| d).collect();
+    let norm1 = |d: [i64; 3]| d[0].abs() + d[1].abs() + d[2].abs();
+    // the components of the object in the 26-neighborhood
+    let object: Vec<usize> = (0..27).filter(|i| *i != 13 && cube[*i]).collect();
+    let object_components = components(&object, |i, j| {
+        (0..3).all(|k| (offsets[i][k] - offsets[j][k]).abs() <= 1)
+    });
+    // the components of the background in the 18-neighborhood, 6-adjacent to the center
+    let background: Vec<usize> = (0..27)
+        .filter(|i| *i != 13 && !cube[*i] && norm1(offsets[*i]) <= 2)
+        .collect();
+    let background_components = components(&background, |i, j| {
+        let d = [
+            offsets[i][0] - offsets[j][0],
+            offsets[i][1] - offsets[j][1],
+            offsets[i][2] - offsets[j][2],
+        ];
+        norm1(d) == 1
+    });
+    let touching = background_components
+        .iter()
+        .filter(|component| component.iter().any(|i| norm1(offsets[*i]) == 1))
+        .count();
+    object_components.len() == 1 && touching == 1
+}
+
+/// the connected components of `cells` by `adjacent`
+fn components<F: Fn(usize, usize) -> bool>(cells: &[usize], adjacent: F) -> Vec<Vec<usize>> {
+    let mut done = vec![false; cells.len()];
+    let mut res = Vec::new();
+    for i in 0..cells.len() {
+        if done[i] {
+            continue;
+        }
+        done[i] = true;
+        let mut component = vec![cells[i]];
+        let mut stack = vec![i];
+        while let Some(j) = stack.pop() {
+            for k in 0..cells.len() {
+                if !done[k] && adjacent(cells[j], cells[k]) {
+                    done[k] = true;
+                    component.push(cells[k]);
+                    stack.push(k);
+                }
+            }
+        }
+        res.push(component);
+    }
+    res
+}
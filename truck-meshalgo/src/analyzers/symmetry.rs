@@ -0,0 +1,261 @@
+use super::*;
+use std::f64::consts::PI;
+
+/// the number of the largest triangles whose normals are the candidates of the symmetries
+const FACE_CANDIDATES: usize = 16;
+
+/// Symmetries of shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Symmetry {
+    /// the reflection by the plane through `origin` with the unit normal `normal`.
+    Reflection {
+        /// a point on the plane
+        origin: Point3,
+        /// the unit normal of the plane
+        normal: Vector3,
+    },
+    /// the rotation by `2 pi / order` around the axis through `origin` with the direction `axis`.
+    Rotation {
+        /// a point on the axis
+        origin: Point3,
+        /// the unit direction of the axis
+        axis: Vector3,
+        /// the number of the rotations to make the round
+        order: usize,
+    },
+}
+
+/// Configures of symmetry detection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymmetryDescriptor {
+    /// the maximum fitting error relative to the diagonal of the bounding box.
+    /// Default is `1.0e-3`.
+    pub tolerance: f64,
+    /// the maximum order of rotational symmetries. Default is `12`.
+    pub max_order: usize,
+}
+
+impl Default for SymmetryDescriptor {
+    #[inline(always)]
+    fn default() -> SymmetryDescriptor {
+        SymmetryDescriptor {
+            tolerance: 1.0e-3,
+            max_order: 12,
+        }
+    }
+}
+
+impl Symmetry {
+    /// Returns the transform of the symmetry.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let symmetry = Symmetry::Rotation {
+    ///     origin: Point3::new(1.0, 0.0, 0.0),
+    ///     axis: Vector3::unit_z(),
+    ///     order: 4,
+    /// };
+    /// let pt = symmetry.transform().transform_point(Point3::new(2.0, 0.0, 3.0));
+    /// assert!(pt.near(&Point3::new(1.0, 1.0, 3.0)));
+    /// ```
+    pub fn transform(&self) -> Matrix4 {
+        match *self {
+            Symmetry::Reflection { origin, normal } => {
+                let n = normal.normalize();
+                let reflection = Matrix3::identity() - outer(n, n) * 2.0;
+                Matrix4::from_translation(origin.to_vec())
+                    * Matrix4::from(reflection)
+                    * Matrix4::from_translation(-origin.to_vec())
+            }
+            Symmetry::Rotation {
+                origin,
+                axis,
+                order,
+            } => {
+                let angle = Rad(2.0 * PI / usize::max(order, 1) as f64);
+                Matrix4::from_translation(origin.to_vec())
+                    * Matrix4::from_axis_angle(axis.normalize(), angle)
+                    * Matrix4::from_translation(-origin.to_vec())
+            }
+        }
+    }
+}
+
+/// Detection of planar reflective and rotational symmetries.
+pub trait SymmetryDetection {
+    /// Returns the fitting error of `symmetry`, the root mean square of the distances between
+    /// the mesh and the transformed vertices and centroids of the triangles.
+    fn symmetry_error(&self, symmetry: &Symmetry) -> f64;
+    /// Returns the symmetries whose fitting errors are within the tolerance, with the errors,
+    /// in the ascending order of the errors.
+    ///
+    /// The candidates of the normals of the planes and the axes of the rotations are the principal
+    /// axes through the centroid, their bisectors, the normals of the largest faces and the cross
+    /// products of them and the principal axes. For each axis, the rotation of the largest order
+    /// up to `descriptor.max_order` is returned.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // an isosceles triangular prism
+    /// let positions = vec![
+    ///     Point3::new(-1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 3.0, 0.0),
+    ///     Point3::new(-1.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 3.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[
+    ///     &[0, 2, 1][..],
+    ///     &[3, 4, 5],
+    ///     &[0, 1, 4, 3],
+    ///     &[1, 2, 5, 4],
+    ///     &[2, 0, 3, 5],
+    /// ]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let symmetries = mesh.symmetries(&SymmetryDescriptor::default());
+    /// // the planes x = 0 and z = 0.5, and the half turn around the line x = z - 0.5 = 0
+    /// assert_eq!(symmetries.len(), 3);
+    /// let parallel = |v: &Vector3, w: Vector3| v.cross(w).so_small();
+    /// symmetries.iter().for_each(|(symmetry, error)| {
+    ///     assert!(error.so_small());
+    ///     match symmetry {
+    ///         Symmetry::Reflection { normal, .. } => {
+    ///             let x = parallel(normal, Vector3::unit_x());
+    ///             assert!(x || parallel(normal, Vector3::unit_z()));
+    ///         }
+    ///         Symmetry::Rotation { axis, order, .. } => {
+    ///             assert!(parallel(axis, Vector3::unit_y()));
+    ///             assert_eq!(*order, 2);
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    fn symmetries(&self, descriptor: &SymmetryDescriptor) -> Vec<(Symmetry, f64)>;
+}
+
+impl SymmetryDetection for PolygonMesh {
+    fn symmetry_error(&self, symmetry: &Symmetry) -> f64 {
+        symmetry_error(&MeshBVH::new(self), &sample_points(self), symmetry)
+    }
+
+    fn symmetries(&self, descriptor: &SymmetryDescriptor) -> Vec<(Symmetry, f64)> {
+        let bvh = MeshBVH::new(self);
+        let samples = sample_points(self);
+        let tolerance = descriptor.tolerance * bvh.bounding_box().diameter();
+        if samples.is_empty() {
+            return Vec::new();
+        }
+        let frame = match self.clone().align_principal_axes().invert() {
+            Some(frame) => frame,
+            None => return Vec::new(),
+        };
+        let origin = frame.transform_point(Point3::origin());
+        let mut res = Vec::new();
+        for direction in candidates(self, frame) {
+            let reflection = Symmetry::Reflection {
+                origin,
+                normal: direction,
+            };
+            let error = symmetry_error(&bvh, &samples, &reflection);
+            if error <= tolerance {
+                res.push((reflection, error));
+            }
+            let rotation = (2..=descriptor.max_order)
+                .rev()
+                .map(|order| {
+                    let rotation = Symmetry::Rotation {
+                        origin,
+                        axis: direction,
+                        order,
+                    };
+                    (rotation, symmetry_error(&bvh, &samples, &rotation))
+                })
+                .find(|(_, error)| *error <= tolerance);
+            if let Some(rotation) = rotation {
+                res.push(rotation);
+            }
+        }
+        res.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        res
+    }
+}
+
+/// the vertices and the centroids of the triangles
+fn sample_points(mesh: &PolygonMesh) -> Vec<Point3> {
+    let positions = mesh.positions();
+    let centroids = mesh.face_iter().flat_map(|face| {
+        (2..face.len()).map(move |i| {
+            let sum = positions[face[0].pos].to_vec()
+                + positions[face[i - 1].pos].to_vec()
+                + positions[face[i].pos].to_vec();
+            Point3::from_vec(sum / 3.0)
+        })
+    });
+    positions.iter().copied().chain(centroids).collect()
+}
+
+fn symmetry_error(bvh: &MeshBVH, samples: &[Point3], symmetry: &Symmetry) -> f64 {
+    let trans = symmetry.transform();
+    let sum: f64 = samples
+        .iter()
+        .map(|p| bvh.distance(trans.transform_point(*p)).powi(2))
+        .sum();
+    f64::sqrt(sum / samples.len() as f64)
+}
+
+/// the unit directions of the candidates without duplications up to the signs
+fn candidates(mesh: &PolygonMesh, frame: Matrix4) -> Vec<Vector3> {
+    let axes = [
+        frame.x.truncate().normalize(),
+        frame.y.truncate().normalize(),
+        frame.z.truncate().normalize(),
+    ];
+    let bisectors = (0..3).flat_map(|i| {
+        let (a, b) = (axes[i], axes[(i + 1) % 3]);
+        vec![(a + b).normalize(), (a - b).normalize()]
+    });
+    let positions = mesh.positions();
+    let mut normals: Vec<Vector3> = mesh
+        .face_iter()
+        .flat_map(|face| {
+            (2..face.len()).map(move |i| {
+                let p = [
+                    positions[face[0].pos],
+                    positions[face[i - 1].pos],
+                    positions[face[i].pos],
+                ];
+                (p[1] - p[0]).cross(p[2] - p[0])
+            })
+        })
+        .collect();
+    normals.sort_by(|a, b| b.magnitude2().partial_cmp(&a.magnitude2()).unwrap());
+    let normals: Vec<Vector3> = normals
+        .into_iter()
+        .take(FACE_CANDIDATES)
+        .filter(|n| !n.so_small())
+        .map(|n| n.normalize())
+        .collect();
+    let crosses = normals.iter().flat_map(|n| {
+        axes.iter()
+            .map(move |a| a.cross(*n))
+            .filter(|v| !v.so_small())
+            .map(|v| v.normalize())
+    });
+    let mut res: Vec<Vector3> = Vec::new();
+    axes.iter()
+        .copied()
+        .chain(bisectors)
+        .chain(normals.iter().copied())
+        .chain(crosses)
+        .for_each(|v| {
+            if res.iter().all(|w| f64::abs(v.dot(*w)) < 1.0 - TOLERANCE) {
+                res.push(v);
+            }
+        });
+    res
+}
+
+#[inline(always)]
+fn outer(a: Vector3, b: Vector3) -> Matrix3 { Matrix3::from_cols(a * b[0], a * b[1], a * b[2]) }
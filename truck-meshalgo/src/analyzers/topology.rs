@@ -119,3 +119,8 @@ impl Topology for PolygonMesh {
     fn extract_boundaries(&self) -> Vec<Vec<usize>> { self.faces().extract_boundaries() }
     fn shell_condition(&self) -> ShellCondition { self.faces().shell_condition() }
 }
+
+impl Topology for PolygonMeshSlice<'_> {
+    fn extract_boundaries(&self) -> Vec<Vec<usize>> { self.faces().extract_boundaries() }
+    fn shell_condition(&self) -> ShellCondition { self.faces().shell_condition() }
+}
@@ -0,0 +1,241 @@
+use super::*;
+use crate::tessellation::MeshedShape;
+use truck_topology::{Shell, Solid};
+
+type PolylineCurve = truck_polymesh::PolylineCurve<Point3>;
+
+/// Configures of visibility analysis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisibilityDescriptor {
+    /// the number of the divisions of each edge of the triangles, each of whose sub-triangles
+    /// has a sample point at the center. Default is `3`.
+    pub subdivision: usize,
+    /// the minimum draft angle for the faces to be seen from the directions, so that
+    /// the faces almost parallel to the directions are hidden. Default is 1 degree.
+    pub min_draft: Rad<f64>,
+}
+
+impl Default for VisibilityDescriptor {
+    #[inline(always)]
+    fn default() -> VisibilityDescriptor {
+        VisibilityDescriptor {
+            subdivision: 3,
+            min_draft: Deg(1.0).into(),
+        }
+    }
+}
+
+/// The visibility of a face from the directions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaceVisibility {
+    /// the ratios of the sample points of the face visible from the directions,
+    /// in the order of the directions.
+    pub ratios: Vec<f64>,
+}
+
+impl FaceVisibility {
+    /// Returns the index of the direction from which the largest part of the face is visible,
+    /// or `None` if the face is hidden from all the directions.
+    pub fn best_direction(&self) -> Option<usize> {
+        self.ratios
+            .iter()
+            .enumerate()
+            .filter(|(_, ratio)| **ratio > 0.0)
+            .fold(None, |res: Option<(usize, f64)>, (i, ratio)| match res {
+                Some((_, max)) if max >= *ratio => res,
+                _ => Some((i, *ratio)),
+            })
+            .map(|(i, _)| i)
+    }
+    /// Returns whether the face is entirely visible from none of the directions, i.e. the face
+    /// is an undercut for the mold parts pulled in the directions.
+    #[inline(always)]
+    pub fn is_undercut(&self) -> bool { self.ratios.iter().all(|ratio| *ratio < 1.0) }
+}
+
+/// Visibility analysis from multiple directions, for parting the molds and detecting
+/// the undercuts.
+pub trait VisibilityAnalysis {
+    /// Returns the visibilities of the faces from `directions`, e.g. the pull directions of
+    /// the mold parts. A point on a face is visible from a direction if the face is faced to
+    /// the direction with the draft angle not less than `descriptor.min_draft`, and the ray
+    /// from the point toward the direction does not hit the other faces.
+    ///
+    /// The visibilities of polygon meshes are given in the order of `Faces::face_iter`.
+    /// For tessellated shells and solids, the visibilities are given in the order of
+    /// `face_iter`, and the rays are occluded by the whole shape.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the large square at the bottom shadowed by the small square above
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(4.0, 0.0, 0.0),
+    ///     Point3::new(4.0, 4.0, 0.0),
+    ///     Point3::new(0.0, 4.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 1.0),
+    ///     Point3::new(2.0, 1.0, 1.0),
+    ///     Point3::new(2.0, 2.0, 1.0),
+    ///     Point3::new(1.0, 2.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2, 3], [4, 5, 6, 7]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let directions = [Vector3::unit_z(), -Vector3::unit_z()];
+    /// let descriptor = VisibilityDescriptor {
+    ///     subdivision: 8,
+    ///     ..Default::default()
+    /// };
+    /// let visibilities = mesh.visibility(&directions, &descriptor);
+    /// // the large square is partially visible from the top
+    /// assert!(0.5 < visibilities[0].ratios[0] && visibilities[0].ratios[0] < 1.0);
+    /// assert_eq!(visibilities[0].ratios[1], 0.0);
+    /// assert!(visibilities[0].is_undercut());
+    /// // the small square is entirely visible from the top
+    /// assert_eq!(visibilities[1].ratios, vec![1.0, 0.0]);
+    /// assert_eq!(visibilities[1].best_direction(), Some(0));
+    /// assert!(!visibilities[1].is_undercut());
+    /// ```
+    fn visibility(
+        &self,
+        directions: &[Vector3],
+        descriptor: &VisibilityDescriptor,
+    ) -> Vec<FaceVisibility>;
+}
+
+impl VisibilityAnalysis for PolygonMesh {
+    fn visibility(
+        &self,
+        directions: &[Vector3],
+        descriptor: &VisibilityDescriptor,
+    ) -> Vec<FaceVisibility> {
+        let bvh = MeshBVH::new(self);
+        visible_samples(self, &bvh, directions, descriptor)
+            .into_iter()
+            .map(|(visible, total)| visibility_from_counts(&visible, total))
+            .collect()
+    }
+}
+
+impl VisibilityAnalysis for Shell<Point3, PolylineCurve, PolygonMesh> {
+    fn visibility(
+        &self,
+        directions: &[Vector3],
+        descriptor: &VisibilityDescriptor,
+    ) -> Vec<FaceVisibility> {
+        let bvh = MeshBVH::new(&self.into_polygon());
+        shell_visibility(self, &bvh, directions, descriptor)
+    }
+}
+
+impl VisibilityAnalysis for Solid<Point3, PolylineCurve, PolygonMesh> {
+    fn visibility(
+        &self,
+        directions: &[Vector3],
+        descriptor: &VisibilityDescriptor,
+    ) -> Vec<FaceVisibility> {
+        let bvh = MeshBVH::new(&self.into_polygon());
+        self.boundaries()
+            .iter()
+            .flat_map(|shell| shell_visibility(shell, &bvh, directions, descriptor))
+            .collect()
+    }
+}
+
+fn shell_visibility(
+    shell: &Shell<Point3, PolylineCurve, PolygonMesh>,
+    bvh: &MeshBVH,
+    directions: &[Vector3],
+    descriptor: &VisibilityDescriptor,
+) -> Vec<FaceVisibility> {
+    shell
+        .face_iter()
+        .map(|face| {
+            let polygon = face.oriented_surface();
+            let counts = visible_samples(&polygon, bvh, directions, descriptor);
+            let init = (vec![0; directions.len()], 0);
+            let (visible, total) = counts.into_iter().fold(init, |(mut sum, total), (v, t)| {
+                sum.iter_mut().zip(v).for_each(|(s, v)| *s += v);
+                (sum, total + t)
+            });
+            visibility_from_counts(&visible, total)
+        })
+        .collect()
+}
+
+fn visibility_from_counts(visible: &[usize], total: usize) -> FaceVisibility {
+    let ratios = visible
+        .iter()
+        .map(|v| match total {
+            0 => 0.0,
+            _ => *v as f64 / total as f64,
+        })
+        .collect();
+    FaceVisibility { ratios }
+}
+
+/// Returns the numbers of the visible sample points from each direction and the number of
+/// all the sample points of each face of `mesh`, whose rays are occluded by `bvh`.
+fn visible_samples(
+    mesh: &PolygonMesh,
+    bvh: &MeshBVH,
+    directions: &[Vector3],
+    descriptor: &VisibilityDescriptor,
+) -> Vec<(Vec<usize>, usize)> {
+    let directions: Vec<Vector3> = directions.iter().map(|dir| dir.normalize()).collect();
+    let n = descriptor.subdivision.max(1);
+    let positions = mesh.positions();
+    mesh.face_iter()
+        .map(|face| {
+            let mut visible = vec![0; directions.len()];
+            let mut total = 0;
+            for i in 2..face.len() {
+                let p = [
+                    positions[face[0].pos],
+                    positions[face[i - 1].pos],
+                    positions[face[i].pos],
+                ];
+                let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+                if normal.so_small() {
+                    continue;
+                }
+                let normal = normal.normalize();
+                let samples = triangle_samples(p, n);
+                total += samples.len();
+                for (dir, count) in directions.iter().zip(&mut visible) {
+                    let draft = f64::asin(f64::clamp(normal.dot(*dir), -1.0, 1.0));
+                    if draft < descriptor.min_draft.0 {
+                        continue;
+                    }
+                    *count += samples
+                        .iter()
+                        .filter(|pt| {
+                            // the origin is moved off the triangle itself
+                            !bvh.is_occluded(*pt + *dir * TOLERANCE, *dir, f64::INFINITY)
+                        })
+                        .count();
+                }
+            }
+            (visible, total)
+        })
+        .collect()
+}
+
+/// The centers of the `n * n` sub-triangles of the triangle `p`.
+fn triangle_samples(p: [Point3; 3], n: usize) -> Vec<Point3> {
+    let (e1, e2) = ((p[1] - p[0]) / n as f64, (p[2] - p[0]) / n as f64);
+    (0..n)
+        .flat_map(|i| {
+            (0..n - i).flat_map(move |j| {
+                let corner = p[0] + e1 * i as f64 + e2 * j as f64;
+                // the upward and the downward sub-triangles
+                let upward = corner + (e1 + e2) / 3.0;
+                let downward = corner + (e1 + e2) * 2.0 / 3.0;
+                match i + j + 1 < n {
+                    true => vec![upward, downward],
+                    false => vec![upward],
+                }
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,314 @@
+use crate::analyzers::{ExperimentalSplitters, MeshBVH};
+use crate::*;
+use image::{DynamicImage, ImageBuffer, LumaA, Rgba};
+
+/// The surface attributes baked into images.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BakingAttribute {
+    /// the unit normals, encoded into RGB by `(n + 1) / 2`.
+    Normal,
+    /// the ambient occlusion, the ratio of the unoccluded rays from the surface in gray.
+    AmbientOcclusion {
+        /// the number of the rays per pixel
+        samples: usize,
+        /// the distance within which the rays are checked to be occluded
+        max_distance: f64,
+    },
+    /// the Gaussian curvature `k`, encoded into gray by `(scale * k + 1) / 2`.
+    GaussianCurvature {
+        /// the scale of the curvature
+        scale: f64,
+    },
+    /// the indices of the faces in the order of `Faces::face_iter`, encoded into RGB
+    /// as the little-endian 24-bit integers.
+    FaceId,
+}
+
+/// Configures of baking.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BakingDescriptor {
+    /// the width of images. Default is `512`.
+    pub width: u32,
+    /// the height of images. Default is `512`.
+    pub height: u32,
+    /// the number of the pixels by which the baked regions are extended for
+    /// avoiding seams in the filtered textures. Default is `2`.
+    pub padding: u32,
+}
+
+impl Default for BakingDescriptor {
+    #[inline(always)]
+    fn default() -> BakingDescriptor {
+        BakingDescriptor {
+            width: 512,
+            height: 512,
+            padding: 2,
+        }
+    }
+}
+
+/// A sample of the surface at the center of a pixel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TexelSample {
+    /// the column and the row of the pixel
+    pub pixel: [u32; 2],
+    /// the index of the face in the order of `Faces::face_iter`
+    pub face_index: usize,
+    /// the barycentric coordinates in the triangle of `triangle`
+    pub barycentric: [f64; 3],
+    /// the indices of the positions of the triangle including the sample
+    pub triangle: [usize; 3],
+    /// the position on the surface
+    pub position: Point3,
+    /// the unit normal, interpolated if the normals are attached to the vertices
+    pub normal: Vector3,
+}
+
+/// Samples and bakes the surface attributes over the uv layout of meshes.
+///
+/// The pixel `(i, j)` corresponds to the uv coordinate `((i + 0.5) / width, (j + 0.5) / height)`,
+/// the same convention as the textures of `truck-rendimpl`.
+pub trait AttributeBaking {
+    /// Returns the samples of the surface at the centers of the pixels covered by the uv layout.
+    /// The faces without uv coordinates are ignored. If the faces overlap in the uv layout,
+    /// the latter face is sampled.
+    fn texel_samples(&self, width: u32, height: u32) -> Vec<TexelSample>;
+    /// Bakes `attribute` into an image over the uv layout.
+    /// The pixels not covered by the uv layout are transparent.
+    /// Returns `None` if the mesh has no uv coordinates.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // a unit square in the xy-plane, whose uv coordinates are the same as xy
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let uv_coords = vec![
+    ///     Vector2::new(0.0, 0.0),
+    ///     Vector2::new(1.0, 0.0),
+    ///     Vector2::new(1.0, 1.0),
+    ///     Vector2::new(0.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[
+    ///     (0, Some(0), None),
+    ///     (1, Some(1), None),
+    ///     (2, Some(2), None),
+    ///     (3, Some(3), None),
+    /// ]]);
+    /// let mesh = PolygonMesh::new(positions, uv_coords, Vec::new(), faces);
+    ///
+    /// let descriptor = BakingDescriptor {
+    ///     width: 16,
+    ///     height: 16,
+    ///     ..Default::default()
+    /// };
+    /// let image = mesh.bake(&BakingAttribute::Normal, &descriptor).unwrap().to_rgba8();
+    /// // the normal (0, 0, 1) is encoded into (128, 128, 255).
+    /// assert!(image.pixels().all(|pixel| pixel.0 == [128, 128, 255, 255]));
+    /// ```
+    fn bake(
+        &self,
+        attribute: &BakingAttribute,
+        descriptor: &BakingDescriptor,
+    ) -> Option<DynamicImage>;
+}
+
+impl AttributeBaking for PolygonMesh {
+    fn texel_samples(&self, width: u32, height: u32) -> Vec<TexelSample> {
+        let (positions, uv_coords, normals) = (self.positions(), self.uv_coords(), self.normals());
+        let mut samples: Vec<Option<TexelSample>> = vec![None; (width * height) as usize];
+        for (face_index, face) in self.face_iter().enumerate() {
+            if face.iter().any(|v| v.uv.is_none()) {
+                continue;
+            }
+            let face_normal = FaceNormal::new(positions, face, face_index).normal;
+            for i in 2..face.len() {
+                let tri = [face[0], face[i - 1], face[i]];
+                let uv = [
+                    pixel_coord(uv_coords[tri[0].uv.unwrap()], width, height),
+                    pixel_coord(uv_coords[tri[1].uv.unwrap()], width, height),
+                    pixel_coord(uv_coords[tri[2].uv.unwrap()], width, height),
+                ];
+                let min = |k: usize| f64::min(f64::min(uv[0][k], uv[1][k]), uv[2][k]);
+                let max = |k: usize| f64::max(f64::max(uv[0][k], uv[1][k]), uv[2][k]);
+                let range = |k: usize, len: u32| {
+                    let start = f64::max((min(k) - 0.5).ceil(), 0.0) as u32;
+                    let end = f64::min((max(k) - 0.5).floor() + 1.0, len as f64);
+                    start..u32::max(end as u32, start)
+                };
+                for j in range(1, height) {
+                    for i in range(0, width) {
+                        let c = Vector2::new(i as f64 + 0.5, j as f64 + 0.5);
+                        let bary = match barycentric(uv, c) {
+                            Some(bary) => bary,
+                            None => continue,
+                        };
+                        let interpolate =
+                            |p: [Vector3; 3]| p[0] * bary[0] + p[1] * bary[1] + p[2] * bary[2];
+                        let position = Point3::from_vec(interpolate([
+                            positions[tri[0].pos].to_vec(),
+                            positions[tri[1].pos].to_vec(),
+                            positions[tri[2].pos].to_vec(),
+                        ]));
+                        let normal = match (tri[0].nor, tri[1].nor, tri[2].nor) {
+                            (Some(n0), Some(n1), Some(n2)) => {
+                                interpolate([normals[n0], normals[n1], normals[n2]])
+                            }
+                            _ => face_normal,
+                        };
+                        samples[(j * width + i) as usize] = Some(TexelSample {
+                            pixel: [i, j],
+                            face_index,
+                            barycentric: bary,
+                            triangle: [tri[0].pos, tri[1].pos, tri[2].pos],
+                            position,
+                            normal: normal.normalize(),
+                        });
+                    }
+                }
+            }
+        }
+        samples.into_iter().flatten().collect()
+    }
+
+    fn bake(
+        &self,
+        attribute: &BakingAttribute,
+        descriptor: &BakingDescriptor,
+    ) -> Option<DynamicImage> {
+        if self.uv_coords().is_empty() {
+            return None;
+        }
+        let (width, height) = (descriptor.width, descriptor.height);
+        let samples = self.texel_samples(width, height);
+        let mut image = ImageBuffer::<Rgba<u8>, Vec<u8>>::new(width, height);
+        match *attribute {
+            BakingAttribute::Normal => samples.iter().for_each(|sample| {
+                let n = sample
+                    .normal
+                    .map(|x| ((x + 1.0) / 2.0 * 255.0).round() as u8);
+                image.put_pixel(
+                    sample.pixel[0],
+                    sample.pixel[1],
+                    Rgba([n[0], n[1], n[2], 255]),
+                );
+            }),
+            BakingAttribute::AmbientOcclusion {
+                samples: rays,
+                max_distance,
+            } => {
+                let bvh = MeshBVH::new(self);
+                let offset = bvh.bounding_box().diameter() * 1.0e-6;
+                samples.iter().for_each(|sample| {
+                    let origin = sample.position + sample.normal * offset;
+                    let visible = hemisphere(sample.normal, rays)
+                        .filter(|dir| !bvh.is_occluded(origin, *dir, max_distance))
+                        .count();
+                    let gray = (visible as f64 / usize::max(rays, 1) as f64 * 255.0).round() as u8;
+                    image.put_pixel(
+                        sample.pixel[0],
+                        sample.pixel[1],
+                        Rgba([gray, gray, gray, 255]),
+                    );
+                })
+            }
+            BakingAttribute::GaussianCurvature { scale } => {
+                let curvatures = self.get_gcurve();
+                samples.iter().for_each(|sample| {
+                    let k: f64 = (0..3)
+                        .map(|i| curvatures[sample.triangle[i]] * sample.barycentric[i])
+                        .sum();
+                    let gray = f64::clamp((scale * k + 1.0) / 2.0, 0.0, 1.0);
+                    let gray = (gray * 255.0).round() as u8;
+                    image.put_pixel(
+                        sample.pixel[0],
+                        sample.pixel[1],
+                        Rgba([gray, gray, gray, 255]),
+                    );
+                })
+            }
+            BakingAttribute::FaceId => samples.iter().for_each(|sample| {
+                let id = sample.face_index as u32;
+                let [r, g, b, _] = id.to_le_bytes();
+                image.put_pixel(sample.pixel[0], sample.pixel[1], Rgba([r, g, b, 255]));
+            }),
+        }
+        (0..descriptor.padding).for_each(|_| dilate(&mut image));
+        match attribute {
+            BakingAttribute::AmbientOcclusion { .. }
+            | BakingAttribute::GaussianCurvature { .. } => {
+                let gray = ImageBuffer::from_fn(width, height, |i, j| {
+                    let pixel = image.get_pixel(i, j);
+                    LumaA([pixel[0], pixel[3]])
+                });
+                Some(DynamicImage::ImageLumaA8(gray))
+            }
+            _ => Some(DynamicImage::ImageRgba8(image)),
+        }
+    }
+}
+
+#[inline(always)]
+fn pixel_coord(uv: Vector2, width: u32, height: u32) -> Vector2 {
+    Vector2::new(uv[0] * width as f64, uv[1] * height as f64)
+}
+
+/// the barycentric coordinates of `c` in the triangle, or `None` if `c` is outside
+fn barycentric(tri: [Vector2; 3], c: Vector2) -> Option<[f64; 3]> {
+    let (a, b) = (tri[1] - tri[0], tri[2] - tri[0]);
+    let det = a.x * b.y - a.y * b.x;
+    if det.abs() < TOLERANCE2 {
+        return None;
+    }
+    let d = c - tri[0];
+    let u = (d.x * b.y - d.y * b.x) / det;
+    let v = (a.x * d.y - a.y * d.x) / det;
+    match u >= -TOLERANCE && v >= -TOLERANCE && u + v <= 1.0 + TOLERANCE {
+        true => Some([1.0 - u - v, u, v]),
+        false => None,
+    }
+}
+
+/// the cosine-weighted directions on the hemisphere around `normal`
+fn hemisphere(normal: Vector3, n: usize) -> impl Iterator<Item = Vector3> {
+    let other = match normal.x.abs() < 0.9 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let e0 = other.cross(normal).normalize();
+    let e1 = normal.cross(e0);
+    let golden_angle = std::f64::consts::PI * (3.0 - f64::sqrt(5.0));
+    (0..n).map(move |i| {
+        let r = f64::sqrt((i as f64 + 0.5) / n as f64);
+        let t = golden_angle * i as f64;
+        let z = f64::sqrt(1.0 - r * r);
+        e0 * (r * f64::cos(t)) + e1 * (r * f64::sin(t)) + normal * z
+    })
+}
+
+/// Extends the opaque region by one pixel, copying the opaque neighbors.
+fn dilate(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>) {
+    let (width, height) = image.dimensions();
+    let source = image.clone();
+    for j in 0..height {
+        for i in 0..width {
+            if source.get_pixel(i, j)[3] != 0 {
+                continue;
+            }
+            let neighbors = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+            let neighbor = neighbors.iter().find_map(|(di, dj)| {
+                let (x, y) = (i as i64 + di, j as i64 + dj);
+                match 0 <= x && x < width as i64 && 0 <= y && y < height as i64 {
+                    true => Some(*source.get_pixel(x as u32, y as u32)).filter(|p| p[3] != 0),
+                    false => None,
+                }
+            });
+            if let Some(pixel) = neighbor {
+                image.put_pixel(i, j, pixel);
+            }
+        }
+    }
+}
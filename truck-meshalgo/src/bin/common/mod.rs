@@ -0,0 +1,131 @@
+//! The file types and the options shared by `truck-mesh` and `truck-tessellate`.
+
+// Each tool uses only some of the helpers.
+#![allow(dead_code)]
+
+use std::path::Path;
+use truck_meshalgo::prelude::{gltf, obj, off, ply, stl, threemf, LengthUnit, PolygonMesh};
+
+/// the usage of the options shared by the tools
+const COMMON_OPTIONS: &str = "    --stl-ascii                          write stl in ascii format
+    --ply-ascii                          write ply in ascii format
+    -h, --help                           print this message";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileType {
+    Obj,
+    Stl,
+    Off,
+    Ply,
+    Glb,
+    Gltf,
+    ThreeMF,
+}
+
+/// the options shared by the tools
+#[derive(Debug, Default)]
+pub struct CommonConfig {
+    pub stl_type: stl::STLType,
+    pub ply_format: ply::PLYFormat,
+    pub paths: Vec<String>,
+}
+
+pub fn next_f64(args: &mut impl Iterator<Item = String>, option: &str) -> Result<f64, String> {
+    args.next()
+        .ok_or_else(|| format!("{} requires an argument", option))?
+        .parse::<f64>()
+        .map_err(|e| format!("invalid argument of {}: {}", option, e))
+}
+
+pub fn next_usize(args: &mut impl Iterator<Item = String>, option: &str) -> Result<usize, String> {
+    args.next()
+        .ok_or_else(|| format!("{} requires an argument", option))?
+        .parse::<usize>()
+        .map_err(|e| format!("invalid argument of {}: {}", option, e))
+}
+
+/// Parses the arguments. The options of each tool are passed to `parse_option` with the rest
+/// of the arguments, which returns `Ok(false)` if the option is unknown.
+pub fn parse_args<I: Iterator<Item = String>>(
+    mut args: I,
+    mut parse_option: impl FnMut(&str, &mut I) -> Result<bool, String>,
+) -> Result<CommonConfig, String> {
+    let mut config = CommonConfig::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stl-ascii" => config.stl_type = stl::STLType::ASCII,
+            "--ply-ascii" => config.ply_format = ply::PLYFormat::ASCII,
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') => {
+                if !parse_option(&arg, &mut args)? {
+                    return Err(format!("unknown option: {}", arg));
+                }
+            }
+            _ => config.paths.push(arg),
+        }
+    }
+    match config.paths.len() {
+        2 => Ok(config),
+        _ => Err("requires exactly one input file and one output file".to_string()),
+    }
+}
+
+/// Prints the error and the usage followed by the shared options, and exits.
+pub fn exit_with_usage(msg: String, usage: &str) -> ! {
+    if !msg.is_empty() {
+        eprintln!("error: {}\n", msg);
+    }
+    eprintln!("{}\n{}", usage, COMMON_OPTIONS);
+    std::process::exit(1);
+}
+
+pub fn file_type(path: &Path) -> Result<FileType, String> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("obj") => Ok(FileType::Obj),
+        Some("stl") => Ok(FileType::Stl),
+        Some("off") => Ok(FileType::Off),
+        Some("ply") => Ok(FileType::Ply),
+        Some("glb") => Ok(FileType::Glb),
+        Some("gltf") => Ok(FileType::Gltf),
+        Some("3mf") => Ok(FileType::ThreeMF),
+        _ => Err(format!("cannot infer file type: {}", path.display())),
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Writes `mesh` to `path`. `unit` is the unit of the lengths written in 3mf.
+pub fn write_mesh(
+    mesh: &PolygonMesh,
+    path: &Path,
+    config: &CommonConfig,
+    unit: LengthUnit,
+) -> Result<(), String> {
+    let file_type = file_type(path)?;
+    let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let res = match file_type {
+        FileType::Obj => obj::write(mesh, file),
+        FileType::Stl => stl::write(mesh, &mut file, config.stl_type),
+        FileType::Off => off::write(mesh, file),
+        FileType::Ply => ply::write(mesh, file, config.ply_format),
+        FileType::Glb => gltf::write(mesh, file, gltf::GLTFFormat::Binary, None),
+        FileType::Gltf => gltf::write(mesh, file, gltf::GLTFFormat::Embedded, None),
+        FileType::ThreeMF => {
+            let meshes = [(file_stem(path), mesh.clone())];
+            let desc = threemf::ThreeMFDescriptor {
+                unit,
+                ..Default::default()
+            };
+            threemf::write(&meshes, file, &desc)
+        }
+    };
+    res.map_err(|e| e.to_string())
+}
@@ -0,0 +1,192 @@
+//! Converts mesh files and applies filters.
+//!
+//! usage:
+//!
+//! ```bash
+//! cargo run --features cli --bin truck-mesh -- [OPTIONS] <input-file> <output-file>
+//! ```
+//!
+//! The file types are inferred from the extensions: obj, stl, off, ply, glb, gltf, or 3mf.
+//! Filters are applied in the order of the options.
+
+mod common;
+
+use common::*;
+use std::path::Path;
+use truck_meshalgo::filters::*;
+use truck_meshalgo::prelude::{
+    gltf, obj, off, ply, stl, LengthUnit, Matrix4, ModelContext, PolygonMesh, Transformed,
+};
+
+const USAGE: &str = "usage: truck-mesh [OPTIONS] <input-file> <output-file>
+
+The file types are inferred from the extensions: obj, stl, off, ply, glb, gltf, or 3mf.
+Filters are applied in the order of the options.
+
+OPTIONS:
+    --weld                               put together same attributes and remove unused ones
+    --repair                             remove degenerate faces and normalize normals
+    --naive-normals                      add face normals to all vertices
+    --smooth-normals <angle>             add smooth normals, <angle> is tolerance in degrees
+    --triangulate                        triangulate all faces
+    --quadrangulate <plane> <score>      make quadrangles from pairs of triangles
+    --decimate <faces>                   decimate into <faces> triangles, keeping boundaries
+    --decimate-error <error>             decimate while the error is less than <error>
+    --units <input> <output>             convert lengths between units: um, mm, cm, m, in, or ft";
+
+#[derive(Clone, Copy, Debug)]
+enum Filter {
+    Weld,
+    Repair,
+    NaiveNormals,
+    SmoothNormals(f64),
+    Triangulate,
+    Quadrangulate(f64, f64),
+    Decimate(Option<usize>, Option<f64>),
+}
+
+#[derive(Debug)]
+struct Config {
+    filters: Vec<Filter>,
+    units: Option<(LengthUnit, LengthUnit)>,
+    common: CommonConfig,
+}
+
+fn next_unit(args: &mut impl Iterator<Item = String>, option: &str) -> Result<LengthUnit, String> {
+    let symbol = args
+        .next()
+        .ok_or_else(|| format!("{} requires two arguments", option))?;
+    LengthUnit::from_symbol(&symbol)
+        .ok_or_else(|| format!("invalid argument of {}: unknown unit {}", option, symbol))
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Config, String> {
+    let mut filters = Vec::new();
+    let mut units = None;
+    let common = common::parse_args(args, |arg, args| {
+        match arg {
+            "--weld" => filters.push(Filter::Weld),
+            "--repair" => filters.push(Filter::Repair),
+            "--naive-normals" => filters.push(Filter::NaiveNormals),
+            "--smooth-normals" => {
+                let angle = next_f64(args, arg)?;
+                filters.push(Filter::SmoothNormals(angle.to_radians()));
+            }
+            "--triangulate" => filters.push(Filter::Triangulate),
+            "--quadrangulate" => {
+                let plane_tol = next_f64(args, arg)?;
+                let score_tol = next_f64(args, arg)?;
+                filters.push(Filter::Quadrangulate(plane_tol, score_tol));
+            }
+            "--decimate" => {
+                let faces = next_usize(args, arg)?;
+                filters.push(Filter::Decimate(Some(faces), None));
+            }
+            "--decimate-error" => {
+                let error = next_f64(args, arg)?;
+                filters.push(Filter::Decimate(None, Some(error)));
+            }
+            "--units" => {
+                let input = next_unit(args, arg)?;
+                let output = next_unit(args, arg)?;
+                units = Some((input, output));
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok(Config {
+        filters,
+        units,
+        common,
+    })
+}
+
+fn read_mesh(path: &Path, units: Option<(LengthUnit, LengthUnit)>) -> Result<PolygonMesh, String> {
+    let file_type = file_type(path)?;
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    // The lengths are converted into the output unit at reading.
+    let (file_unit, output_unit) =
+        units.unwrap_or((LengthUnit::Millimeter, LengthUnit::Millimeter));
+    let context = ModelContext {
+        length_unit: output_unit,
+        ..Default::default()
+    };
+    let res = match file_type {
+        FileType::Obj => obj::read_with_context(file, file_unit, &context),
+        FileType::Stl => stl::read_with_context(file, stl::STLType::Automatic, file_unit, &context),
+        FileType::Off => off::read_with_context(file, file_unit, &context),
+        FileType::Ply => ply::read_with_context(file, file_unit, &context),
+        FileType::Glb | FileType::Gltf => read_gltf(file, file_unit, &context),
+        FileType::ThreeMF => return Err("reading 3mf is not supported".to_string()),
+    };
+    res.map_err(|e| e.to_string())
+}
+
+/// Reads all the primitives of glTF as one mesh.
+fn read_gltf(
+    file: std::fs::File,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> truck_meshalgo::prelude::Result<PolygonMesh> {
+    let mut mesh = PolygonMesh::default();
+    gltf::read(file)?
+        .into_iter()
+        .for_each(|(_, primitive)| mesh.merge(primitive));
+    if file_unit != context.length_unit {
+        mesh.transform_by(Matrix4::from_scale(context.scale_from(file_unit)));
+    }
+    Ok(mesh)
+}
+
+fn apply_filter(mesh: &mut PolygonMesh, filter: Filter) {
+    match filter {
+        Filter::Weld => {
+            mesh.put_together_same_attrs().remove_unused_attrs();
+        }
+        Filter::Repair => {
+            mesh.remove_degenerate_faces()
+                .normalize_normals()
+                .remove_unused_attrs();
+        }
+        Filter::NaiveNormals => {
+            mesh.add_naive_normals(true);
+        }
+        Filter::SmoothNormals(tol_ang) => {
+            mesh.add_smooth_normals(tol_ang, true);
+        }
+        Filter::Triangulate => {
+            mesh.triangulate();
+        }
+        Filter::Quadrangulate(plane_tol, score_tol) => {
+            mesh.quadrangulate(plane_tol, score_tol);
+        }
+        Filter::Decimate(target_faces, max_error) => {
+            mesh.decimate(&DecimationDescriptor {
+                target_faces,
+                max_error,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+fn main() {
+    let config = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| exit_with_usage(e, USAGE));
+    let paths = &config.common.paths;
+    let (input, output) = (Path::new(&paths[0]), Path::new(&paths[1]));
+    let mut mesh = read_mesh(input, config.units).unwrap_or_else(|e| {
+        eprintln!("error: failed to read {}: {}", input.display(), e);
+        std::process::exit(1);
+    });
+    for filter in &config.filters {
+        apply_filter(&mut mesh, *filter);
+    }
+    let unit = config
+        .units
+        .map_or(LengthUnit::Millimeter, |(_, unit)| unit);
+    write_mesh(&mesh, output, &config.common, unit).unwrap_or_else(|e| {
+        eprintln!("error: failed to write {}: {}", output.display(), e);
+        std::process::exit(1);
+    });
+}
@@ -0,0 +1,88 @@
+//! Tessellates a shape saved in json and writes the mesh.
+//!
+//! usage:
+//!
+//! ```bash
+//! cargo run --features cli --bin truck-tessellate -- [OPTIONS] <input-file> <output-file>
+//! ```
+//!
+//! The input file is a compressed solid or shell of `truck-modeling` serialized by `serde_json`.
+//! The type of the output file is inferred from the extension:
+//! obj, stl, off, ply, glb, gltf, or 3mf.
+
+mod common;
+
+use common::*;
+use std::path::Path;
+use truck_meshalgo::filters::*;
+use truck_meshalgo::prelude::{LengthUnit, PolygonMesh};
+use truck_meshalgo::tessellation::*;
+use truck_modeling::{Shell, Solid};
+
+const USAGE: &str = "usage: truck-tessellate [OPTIONS] <input-file> <output-file>
+
+The input file is a json file of a solid or a shell of truck-modeling.
+The type of the output file is inferred from the extension:
+obj, stl, off, ply, glb, gltf, or 3mf.
+
+OPTIONS:
+    -t, --tolerance <tol>                tolerance of tessellation, default: 0.01";
+
+#[derive(Debug)]
+struct Config {
+    tolerance: f64,
+    common: CommonConfig,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Config, String> {
+    let mut tolerance = 0.01;
+    let common = common::parse_args(args, |arg, args| {
+        match arg {
+            "-t" | "--tolerance" => {
+                tolerance = next_f64(args, arg)?;
+                if !tolerance.is_finite() || tolerance <= 0.0 {
+                    return Err("tolerance must be positive and finite".to_string());
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok(Config { tolerance, common })
+}
+
+/// Reads the shape and tessellates it. Tries a solid first, and then a shell.
+fn tessellate(path: &Path, tol: f64) -> Result<PolygonMesh, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let json: serde_json::Value = serde_json::from_reader(file).map_err(|e| e.to_string())?;
+    let polygon = if let Ok(csolid) = serde_json::from_value(json.clone()) {
+        let solid = Solid::extract(csolid).map_err(|e| e.to_string())?;
+        solid.triangulation(tol).map(|shape| shape.into_polygon())
+    } else {
+        let cshell = serde_json::from_value(json)
+            .map_err(|_| "the input is neither a solid nor a shell".to_string())?;
+        let shell = Shell::extract(cshell).map_err(|e| e.to_string())?;
+        shell.triangulation(tol).map(|shape| shape.into_polygon())
+    };
+    polygon.ok_or_else(|| "failed to tessellate the shape".to_string())
+}
+
+fn main() {
+    let config = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| exit_with_usage(e, USAGE));
+    let paths = &config.common.paths;
+    let (input, output) = (Path::new(&paths[0]), Path::new(&paths[1]));
+    // check the output type before the heavy tessellation
+    if let Err(e) = file_type(output) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+    let mut mesh = tessellate(input, config.tolerance).unwrap_or_else(|e| {
+        eprintln!("error: failed to tessellate {}: {}", input.display(), e);
+        std::process::exit(1);
+    });
+    mesh.put_together_same_attrs().remove_unused_attrs();
+    write_mesh(&mesh, output, &config.common, LengthUnit::Millimeter).unwrap_or_else(|e| {
+        eprintln!("error: failed to write {}: {}", output.display(), e);
+        std::process::exit(1);
+    });
+}
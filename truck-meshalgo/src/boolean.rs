@@ -0,0 +1,510 @@
+use crate::analyzers::{collide_triangles, overlapping_triangle_pairs, MeshBVH, Topology};
+use crate::*;
+use spade::delaunay::*;
+use spade::kernels::FloatKernel;
+use std::collections::{HashMap, HashSet};
+use truck_topology::shell::ShellCondition;
+
+/// Boolean operations of closed polygon meshes.
+///
+/// # Details
+///
+/// The algorithm is as follows.
+/// 1. The vertices of both meshes are welded within the tolerance `tol`, and the faces are
+///    divided into triangles.
+/// 1. The intersection segments of all pairs of the triangles are computed. The pairs of
+///    the coplanar triangles are clipped by each other.
+/// 1. The triangles are retriangulated along the intersection segments by the constrained
+///    Delaunay triangulation.
+/// 1. The triangles are divided into the regions bounded by the intersection curves,
+///    and each region is classified by the other mesh into inside, outside, or on the other
+///    mesh with the same or the opposite orientation.
+///
+/// # Remarks
+///
+/// The results have no normals and no texture coordinates. Use `NormalFilters` if necessary.
+/// The regions are classified by the largest triangles, so the features smaller than `tol`
+/// may be classified wrongly.
+pub trait MeshBoolean {
+    /// Returns the union of `self` and `other`, or `None` if `self` or `other` is not closed
+    /// after welding the vertices within `tol`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    /// use truck_topology::shell::ShellCondition;
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    /// let mesh0 = cube.triangulation(0.01).unwrap().into_polygon();
+    /// let mut mesh1 = mesh0.clone();
+    /// mesh1.transform_by(Matrix4::from_translation(Vector3::new(0.5, 0.5, 0.5)));
+    ///
+    /// let union = mesh0.union(&mesh1, 1.0e-6).unwrap();
+    /// assert_eq!(union.shell_condition(), ShellCondition::Closed);
+    /// let bdb = union.bounding_box();
+    /// assert!(bdb.min().near(&Point3::new(0.0, 0.0, 0.0)));
+    /// assert!(bdb.max().near(&Point3::new(1.5, 1.5, 1.5)));
+    /// ```
+    fn union(&self, other: &PolygonMesh, tol: f64) -> Option<PolygonMesh>;
+    /// Returns the intersection of `self` and `other`, or `None` if `self` or `other` is not
+    /// closed after welding the vertices within `tol`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    /// use truck_topology::shell::ShellCondition;
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    /// let mesh0 = cube.triangulation(0.01).unwrap().into_polygon();
+    /// let mut mesh1 = mesh0.clone();
+    /// mesh1.transform_by(Matrix4::from_translation(Vector3::new(0.5, 0.5, 0.5)));
+    ///
+    /// let intersection = mesh0.intersection(&mesh1, 1.0e-6).unwrap();
+    /// assert_eq!(intersection.shell_condition(), ShellCondition::Closed);
+    /// let bdb = intersection.bounding_box();
+    /// assert!(bdb.min().near(&Point3::new(0.5, 0.5, 0.5)));
+    /// assert!(bdb.max().near(&Point3::new(1.0, 1.0, 1.0)));
+    /// ```
+    fn intersection(&self, other: &PolygonMesh, tol: f64) -> Option<PolygonMesh>;
+    /// Returns the difference of `other` from `self`, or `None` if `self` or `other` is not
+    /// closed after welding the vertices within `tol`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    /// use truck_topology::shell::ShellCondition;
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    /// let mesh0 = cube.triangulation(0.01).unwrap().into_polygon();
+    /// let mut mesh1 = mesh0.clone();
+    /// mesh1.transform_by(Matrix4::from_translation(Vector3::new(0.5, 0.5, 0.5)));
+    ///
+    /// let difference = mesh0.difference(&mesh1, 1.0e-6).unwrap();
+    /// assert_eq!(difference.shell_condition(), ShellCondition::Closed);
+    /// // the corner of the cube is cut out
+    /// let bvh = MeshBVH::new(&difference);
+    /// assert!(bvh.contains(Point3::new(0.25, 0.25, 0.25)));
+    /// assert!(!bvh.contains(Point3::new(0.75, 0.75, 0.75)));
+    /// ```
+    fn difference(&self, other: &PolygonMesh, tol: f64) -> Option<PolygonMesh>;
+}
+
+impl MeshBoolean for PolygonMesh {
+    #[inline(always)]
+    fn union(&self, other: &PolygonMesh, tol: f64) -> Option<PolygonMesh> {
+        boolean(self, other, tol, Operation::Union)
+    }
+    #[inline(always)]
+    fn intersection(&self, other: &PolygonMesh, tol: f64) -> Option<PolygonMesh> {
+        boolean(self, other, tol, Operation::Intersection)
+    }
+    #[inline(always)]
+    fn difference(&self, other: &PolygonMesh, tol: f64) -> Option<PolygonMesh> {
+        boolean(self, other, tol, Operation::Difference)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// The location of a region of a mesh relative to the other mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Location {
+    Outside,
+    Inside,
+    /// on the other mesh with the same orientation
+    OnSame,
+    /// on the other mesh with the opposite orientation
+    OnOpposite,
+}
+
+impl Operation {
+    /// Returns whether the region at `location` is kept or not, and whether it is inverted or not.
+    fn select(self, first: bool, location: Location) -> Option<bool> {
+        use Location::*;
+        match (self, first, location) {
+            (Operation::Union, true, Outside | OnSame) => Some(false),
+            (Operation::Union, false, Outside) => Some(false),
+            (Operation::Intersection, true, Inside | OnSame) => Some(false),
+            (Operation::Intersection, false, Inside) => Some(false),
+            (Operation::Difference, true, Outside | OnOpposite) => Some(false),
+            (Operation::Difference, false, Inside) => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// Welds the points within the tolerance by the hash grid.
+#[derive(Clone, Debug)]
+struct Welder {
+    tol: f64,
+    positions: Vec<Point3>,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl Welder {
+    #[inline(always)]
+    fn new(tol: f64) -> Welder {
+        Welder {
+            tol,
+            positions: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+    /// Returns the index of the registered point within the tolerance from `pt`,
+    /// or registers `pt`.
+    fn weld(&mut self, pt: Point3) -> usize {
+        let key = pt.to_vec().map(|x| (x / self.tol).floor() as i64);
+        let key = [key[0], key[1], key[2]];
+        let positions = &self.positions;
+        let tol = self.tol;
+        let found = (-1..=1).find_map(|i| {
+            (-1..=1).find_map(|j| {
+                (-1..=1).find_map(|k| {
+                    let cell = self.cells.get(&[key[0] + i, key[1] + j, key[2] + k])?;
+                    cell.iter().find(|idx| positions[**idx].distance(pt) < tol).copied()
+                })
+            })
+        });
+        match found {
+            Some(idx) => idx,
+            None => {
+                self.positions.push(pt);
+                self.cells.entry(key).or_default().push(self.positions.len() - 1);
+                self.positions.len() - 1
+            }
+        }
+    }
+}
+
+fn boolean(
+    mesh0: &PolygonMesh,
+    mesh1: &PolygonMesh,
+    tol: f64,
+    operation: Operation,
+) -> Option<PolygonMesh> {
+    if tol <= 0.0 {
+        return None;
+    }
+    let mut welder = Welder::new(tol);
+    let tris = [welded_triangles(mesh0, &mut welder), welded_triangles(mesh1, &mut welder)];
+    let meshes = [
+        triangle_mesh(welder.positions.clone(), &tris[0]),
+        triangle_mesh(welder.positions.clone(), &tris[1]),
+    ];
+    if meshes.iter().any(|mesh| mesh.shell_condition() != ShellCondition::Closed) {
+        return None;
+    }
+    let point_tris = [
+        tris[0].iter().map(|tri| tri.map(|i| welder.positions[i])).collect::<Vec<_>>(),
+        tris[1].iter().map(|tri| tri.map(|i| welder.positions[i])).collect::<Vec<_>>(),
+    ];
+    let mut segments = [vec![Vec::new(); tris[0].len()], vec![Vec::new(); tris[1].len()]];
+    overlapping_triangle_pairs(&point_tris[0], &point_tris[1])
+        .into_iter()
+        .for_each(|(i, j)| {
+            intersection_segments(point_tris[0][i], point_tris[1][j], tol)
+                .into_iter()
+                .for_each(|(p, q)| {
+                    let edge = [welder.weld(p), welder.weld(q)];
+                    if edge[0] != edge[1] {
+                        segments[0][i].push(edge);
+                        segments[1][j].push(edge);
+                    }
+                })
+        });
+    let positions = welder.positions;
+    let bvhs = [MeshBVH::new(&meshes[0]), MeshBVH::new(&meshes[1])];
+    let mut faces = Vec::<[usize; 3]>::new();
+    (0..2).for_each(|k| {
+        let (split, cuts) = split_triangles(&tris[k], &segments[k], &positions, tol);
+        let locations = locate_regions(&split, &cuts, &positions, &bvhs[1 - k], tol);
+        split.into_iter().zip(locations).for_each(|(tri, location)| {
+            match operation.select(k == 0, location) {
+                Some(false) => faces.push(tri),
+                Some(true) => faces.push([tri[0], tri[2], tri[1]]),
+                None => {}
+            }
+        });
+    });
+    // the positions only of the kept triangles
+    let mut indices = HashMap::<usize, usize>::new();
+    let mut new_positions = Vec::new();
+    faces.iter_mut().flatten().for_each(|idx| {
+        *idx = *indices.entry(*idx).or_insert_with(|| {
+            new_positions.push(positions[*idx]);
+            new_positions.len() - 1
+        });
+    });
+    Some(triangle_mesh(new_positions, &faces))
+}
+
+/// Divides the faces of `mesh` into the triangles of the welded vertices,
+/// except for the degenerate triangles.
+fn welded_triangles(mesh: &PolygonMesh, welder: &mut Welder) -> Vec<[usize; 3]> {
+    let indices: Vec<usize> = mesh.positions().iter().map(|pt| welder.weld(*pt)).collect();
+    let indices = &indices;
+    mesh.face_iter()
+        .flat_map(|face| {
+            (2..face.len()).map(move |i| [face[0], face[i - 1], face[i]].map(|v| indices[v.pos]))
+        })
+        .filter(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0])
+        .collect()
+}
+
+#[inline(always)]
+fn triangle_mesh(positions: Vec<Point3>, tris: &[[usize; 3]]) -> PolygonMesh {
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), Faces::from_iter(tris))
+}
+
+#[inline(always)]
+fn edge_key(a: usize, b: usize) -> [usize; 2] {
+    match a < b {
+        true => [a, b],
+        false => [b, a],
+    }
+}
+
+/// Returns the intersection segments of two triangles. The coplanar triangles are clipped by
+/// each other, and the clipped edges are returned.
+fn intersection_segments(tri0: [Point3; 3], tri1: [Point3; 3], tol: f64) -> Vec<(Point3, Point3)> {
+    let coplanar = |tri0: [Point3; 3], tri1: [Point3; 3]| {
+        let normal = (tri0[1] - tri0[0]).cross(tri0[2] - tri0[0]).normalize();
+        tri1.iter().all(|p| (*p - tri0[0]).dot(normal).abs() < tol)
+    };
+    if coplanar(tri0, tri1) && coplanar(tri1, tri0) {
+        let clip = |tri0: [Point3; 3], tri1: [Point3; 3]| {
+            (0..3).filter_map(move |i| clip_segment(tri0[i], tri0[(i + 1) % 3], tri1, tol))
+        };
+        clip(tri0, tri1).chain(clip(tri1, tri0)).collect()
+    } else {
+        collide_triangles(tri0, tri1).into_iter().collect()
+    }
+}
+
+/// Clips the segment from `p` to `q` by the coplanar triangle `tri`. The end points within `tol`
+/// from the triangle are not clipped, so that the segments on the edges are kept.
+fn clip_segment(p: Point3, q: Point3, tri: [Point3; 3], tol: f64) -> Option<(Point3, Point3)> {
+    let normal = (tri[1] - tri[0]).cross(tri[2] - tri[0]);
+    let (mut t0, mut t1) = (0.0, 1.0);
+    for i in 0..3 {
+        let (a, b) = (tri[i], tri[(i + 1) % 3]);
+        // the signed distances from the edge, positive inside
+        let inward = normal.cross(b - a).normalize();
+        let (dist0, dist1) = ((p - a).dot(inward), (q - a).dot(inward));
+        if dist0 < -tol && dist1 < -tol {
+            return None;
+        } else if dist0 < -tol {
+            t0 = f64::max(t0, dist0 / (dist0 - dist1));
+        } else if dist1 < -tol {
+            t1 = f64::min(t1, dist0 / (dist0 - dist1));
+        }
+    }
+    match (t1 - t0) * p.distance(q) > tol {
+        true => Some((p + (q - p) * t0, p + (q - p) * t1)),
+        false => None,
+    }
+}
+
+/// Returns the parameter of the projection of `pt` on the segment from `a` to `b`
+/// if `pt` is on the segment within `tol`, except for the end points.
+fn edge_parameter(pt: Point3, a: Point3, b: Point3, tol: f64) -> Option<f64> {
+    let ab = b - a;
+    let t = (pt - a).dot(ab) / ab.magnitude2();
+    match 0.0 < t && t < 1.0 && (a + ab * t).distance(pt) < tol {
+        true => Some(t),
+        false => None,
+    }
+}
+
+/// Retriangulates the triangles along the segments, and returns the new triangles and
+/// the edges on the segments. The points on the edges are shared by the adjacent triangles,
+/// so that the new triangles have no T-junctions.
+fn split_triangles(
+    tris: &[[usize; 3]],
+    segments: &[Vec<[usize; 2]>],
+    positions: &[Point3],
+    tol: f64,
+) -> (Vec<[usize; 3]>, HashSet<[usize; 2]>) {
+    let mut edge_points = HashMap::<[usize; 2], Vec<usize>>::new();
+    tris.iter().zip(segments).for_each(|(tri, segments)| {
+        segments.iter().flatten().filter(|idx| !tri.contains(idx)).for_each(|idx| {
+            (0..3).for_each(|i| {
+                let (a, b) = (tri[i], tri[(i + 1) % 3]);
+                if edge_parameter(positions[*idx], positions[a], positions[b], tol).is_some() {
+                    let points = edge_points.entry(edge_key(a, b)).or_default();
+                    if !points.contains(idx) {
+                        points.push(*idx);
+                    }
+                }
+            })
+        })
+    });
+    let mut new_tris = Vec::new();
+    let mut cuts = HashSet::new();
+    tris.iter().zip(segments).for_each(|(tri, segments)| {
+        let has_points = (0..3).any(|i| {
+            let edge = edge_key(tri[i], tri[(i + 1) % 3]);
+            edge_points.contains_key(&edge)
+        });
+        if segments.is_empty() && !has_points {
+            new_tris.push(*tri);
+        } else {
+            retriangulate(*tri, segments, &edge_points, positions, &mut new_tris, &mut cuts);
+        }
+    });
+    (new_tris, cuts)
+}
+
+/// Retriangulates the triangle `tri` with the points on the edges by the constrained Delaunay
+/// triangulation whose constraints are `segments`.
+fn retriangulate(
+    tri: [usize; 3],
+    segments: &[[usize; 2]],
+    edge_points: &HashMap<[usize; 2], Vec<usize>>,
+    positions: &[Point3],
+    new_tris: &mut Vec<[usize; 3]>,
+    cuts: &mut HashSet<[usize; 2]>,
+) {
+    let p = tri.map(|i| positions[i]);
+    let normal = (p[1] - p[0]).cross(p[2] - p[0]).normalize();
+    let (x, y) = ((p[1] - p[0]).normalize(), normal.cross((p[1] - p[0]).normalize()));
+    let project = |pt: Point3| [(pt - p[0]).dot(x), (pt - p[0]).dot(y)];
+    let corners = p.map(project);
+    // the indices of the points and the flags of the edges on which the points are
+    let mut vertices = vec![(tri[0], 0b101_u8), (tri[1], 0b011), (tri[2], 0b110)];
+    let mut coords = corners.to_vec();
+    (0..3).for_each(|i| {
+        let (a, b) = (tri[i], tri[(i + 1) % 3]);
+        let points = edge_points.get(&edge_key(a, b)).into_iter().flatten();
+        points.for_each(|idx| {
+            if vertices.iter().any(|(v, _)| v == idx) {
+                return;
+            }
+            let ab = positions[b] - positions[a];
+            let t = (positions[*idx] - positions[a]).dot(ab) / ab.magnitude2();
+            let (c, d) = (corners[i], corners[(i + 1) % 3]);
+            // exactly on the edge in the plane
+            coords.push([c[0] + (d[0] - c[0]) * t, c[1] + (d[1] - c[1]) * t]);
+            vertices.push((*idx, 1 << i));
+        })
+    });
+    segments.iter().flatten().for_each(|idx| {
+        if !vertices.iter().any(|(v, _)| v == idx) {
+            coords.push(project(positions[*idx]));
+            vertices.push((*idx, 0));
+        }
+    });
+
+    let mut triangulation = ConstrainedDelaunayTriangulation::<[f64; 2], FloatKernel>::new();
+    let mut handles = HashMap::<usize, usize>::new();
+    let mut vmap = HashMap::<usize, (usize, u8)>::new();
+    vertices.iter().zip(coords).for_each(|(vertex, coord)| {
+        let handle = triangulation.insert(coord);
+        handles.insert(vertex.0, handle);
+        vmap.entry(handle).or_insert(*vertex);
+    });
+    segments.iter().for_each(|segment| {
+        let (from, to) = (handles[&segment[0]], handles[&segment[1]]);
+        if from != to && triangulation.can_add_constraint(from, to) {
+            triangulation.add_constraint(from, to);
+        }
+    });
+    triangulation.triangles().for_each(|face| {
+        let [v0, v1, v2] = face.as_triangle().map(|v| vmap[&v.fix()]);
+        // the degenerate triangles on the edges are removed
+        if v0.1 & v1.1 & v2.1 == 0 && v0.0 != v1.0 && v1.0 != v2.0 && v2.0 != v0.0 {
+            new_tris.push([v0.0, v1.0, v2.0]);
+        }
+    });
+    triangulation.edges().for_each(|edge| {
+        if triangulation.is_constraint_edge(edge.fix()) {
+            let (from, to) = (vmap[&edge.from().fix()].0, vmap[&edge.to().fix()].0);
+            cuts.insert(edge_key(from, to));
+        }
+    });
+}
+
+/// Classifies the regions of `tris` bounded by `cuts` by the other mesh, and returns the
+/// locations of the triangles.
+fn locate_regions(
+    tris: &[[usize; 3]],
+    cuts: &HashSet<[usize; 2]>,
+    positions: &[Point3],
+    bvh: &MeshBVH,
+    tol: f64,
+) -> Vec<Location> {
+    let mut adjacency = HashMap::<[usize; 2], Vec<usize>>::new();
+    tris.iter().enumerate().for_each(|(i, tri)| {
+        (0..3).for_each(|j| {
+            let edge = edge_key(tri[j], tri[(j + 1) % 3]);
+            if !cuts.contains(&edge) {
+                adjacency.entry(edge).or_default().push(i);
+            }
+        })
+    });
+    let area = |tri: &[usize; 3]| {
+        let p = tri.map(|i| positions[i]);
+        (p[1] - p[0]).cross(p[2] - p[0]).magnitude()
+    };
+    let mut locations = vec![None; tris.len()];
+    (0..tris.len()).for_each(|i| {
+        if locations[i].is_some() {
+            return;
+        }
+        // the region containing the `i`th triangle
+        let mut region = vec![i];
+        let mut checked = HashSet::new();
+        checked.insert(i);
+        let mut stack = vec![i];
+        while let Some(j) = stack.pop() {
+            (0..3).for_each(|k| {
+                let edge = edge_key(tris[j][k], tris[j][(k + 1) % 3]);
+                adjacency.get(&edge).into_iter().flatten().for_each(|l| {
+                    if checked.insert(*l) {
+                        region.push(*l);
+                        stack.push(*l);
+                    }
+                })
+            })
+        }
+        let largest = region
+            .iter()
+            .max_by(|j, k| area(&tris[**j]).partial_cmp(&area(&tris[**k])).unwrap())
+            .unwrap();
+        let location = locate(tris[*largest].map(|i| positions[i]), bvh, tol);
+        region.into_iter().for_each(|j| locations[j] = Some(location));
+    });
+    locations.into_iter().map(Option::unwrap).collect()
+}
+
+/// Classifies the triangle by the mesh of `bvh` at the center of the triangle.
+fn locate(tri: [Point3; 3], bvh: &MeshBVH, tol: f64) -> Location {
+    let center = Point3::from_vec((tri[0].to_vec() + tri[1].to_vec() + tri[2].to_vec()) / 3.0);
+    let normal = (tri[1] - tri[0]).cross(tri[2] - tri[0]).normalize();
+    if bvh.distance(center) < tol {
+        if let Some(hit) = bvh.ray_cast(center + normal * (2.0 * tol), -normal) {
+            let cos = hit.face_normal().dot(normal);
+            if hit.distance < 3.0 * tol && cos.abs() > 0.5 {
+                return match cos > 0.0 {
+                    true => Location::OnSame,
+                    false => Location::OnOpposite,
+                };
+            }
+        }
+    }
+    match bvh.contains(center) {
+        true => Location::Inside,
+        false => Location::Outside,
+    }
+}
@@ -0,0 +1,346 @@
+use crate::analyzers::Topology;
+use crate::filters::OptimizingFilter;
+use crate::*;
+use spade::delaunay::*;
+use spade::kernels::FloatKernel;
+use std::collections::HashMap;
+
+/// Clips `mesh` by the plane through `origin` with the normal `normal`, and returns the part of
+/// `mesh` on the back of the plane, i.e. the points `p` with `(p - origin).dot(normal) <= 0`.
+///
+/// The faces crossing the plane are cut along the plane, and the new vertices on the cut edges
+/// interpolate the positions, the uv coordinates, the normals and the colors of the ends of
+/// the edges. The new vertices take the vertex attributes of the kept ends, and the cut faces
+/// take the face attributes of the original faces. The unused attributes are removed.
+///
+/// If `cap` is `true`, the closed loops of the cut are filled by the planar faces facing toward
+/// `normal`, so that the sections of closed meshes are closed. The loops are filled by the
+/// even-odd rule, so the holes in the sections are kept. The cap faces have the normal
+/// `normal` if the mesh has normals, no uv coordinates and no colors, and take the face
+/// attributes of a face adjacent to the cut.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_topology::shell::ShellCondition;
+/// // the unit cube
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(1.0, 0.0, 1.0),
+///     Point3::new(1.0, 1.0, 1.0),
+///     Point3::new(0.0, 1.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[
+///     [3, 2, 1, 0],
+///     [0, 1, 5, 4],
+///     [1, 2, 6, 5],
+///     [2, 3, 7, 6],
+///     [3, 0, 4, 7],
+///     [4, 5, 6, 7],
+/// ]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // the lower half of the cube
+/// let origin = Point3::new(0.5, 0.5, 0.25);
+/// let open = clip_by_plane(&mesh, origin, Vector3::unit_z(), false);
+/// assert_eq!(open.faces().len(), 5);
+/// assert_eq!(open.extract_boundaries().len(), 1);
+/// let bdb = open.bounding_box();
+/// assert!(bdb.max().near(&Point3::new(1.0, 1.0, 0.25)));
+///
+/// // the section is capped
+/// let closed = clip_by_plane(&mesh, origin, Vector3::unit_z(), true);
+/// assert_eq!(closed.shell_condition(), ShellCondition::Closed);
+/// ```
+pub fn clip_by_plane(
+    mesh: &PolygonMesh,
+    origin: Point3,
+    normal: Vector3,
+    cap: bool,
+) -> PolygonMesh {
+    let normal = normal.normalize();
+    let mut clipped = mesh.clone();
+    clip_by_half_space(&mut clipped, origin, normal);
+    if cap {
+        add_caps(&mut clipped, origin, normal);
+    }
+    clipped.remove_unused_attrs();
+    clipped
+}
+
+/// Clips `mesh` by the box `bdb`, and returns the part of `mesh` in the box.
+///
+/// The faces crossing the faces of the box are cut like [`clip_by_plane`], and the sections
+/// are not capped. Returns the empty mesh if `bdb` is empty.
+/// # Examples
+/// ```
+/// use std::iter::FromIterator;
+/// use truck_meshalgo::prelude::*;
+/// // the square [0, 4] x [0, 4] divided into 4 x 4 squares
+/// let positions: Vec<Point3> = (0..5)
+///     .flat_map(|j| (0..5).map(move |i| Point3::new(i as f64, j as f64, 0.0)))
+///     .collect();
+/// let faces = Faces::from_iter((0..4).flat_map(|j| {
+///     (0..4).map(move |i| [5 * j + i, 5 * j + i + 1, 5 * j + i + 6, 5 * j + i + 5])
+/// }));
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let bdb = BoundingBox::from_iter(&[Point3::new(0.5, 0.5, -1.0), Point3::new(2.0, 2.5, 1.0)]);
+/// let clipped = clip_by_box(&mesh, &bdb);
+/// // the 2 x 3 squares are cut
+/// assert_eq!(clipped.faces().len(), 6);
+/// assert!(clipped.bounding_box().min().near(&Point3::new(0.5, 0.5, 0.0)));
+/// assert!(clipped.bounding_box().max().near(&Point3::new(2.0, 2.5, 0.0)));
+/// ```
+///
+/// [`clip_by_plane`]: ./fn.clip_by_plane.html
+pub fn clip_by_box(mesh: &PolygonMesh, bdb: &BoundingBox<Point3>) -> PolygonMesh {
+    if bdb.is_empty() {
+        return PolygonMesh::default();
+    }
+    let (min, max) = (*bdb.min(), *bdb.max());
+    let mut clipped = mesh.clone();
+    [
+        (min, -Vector3::unit_x()),
+        (min, -Vector3::unit_y()),
+        (min, -Vector3::unit_z()),
+        (max, Vector3::unit_x()),
+        (max, Vector3::unit_y()),
+        (max, Vector3::unit_z()),
+    ]
+    .iter()
+    .for_each(|(origin, normal)| clip_by_half_space(&mut clipped, *origin, *normal));
+    clipped.remove_unused_attrs();
+    clipped
+}
+
+/// Creates the vertices on the cut edges. The vertices are shared by the faces adjacent to
+/// the edges.
+#[derive(Debug)]
+struct EdgeSplitter<'a> {
+    dists: &'a [f64],
+    positions: &'a mut Vec<Point3>,
+    uv_coords: &'a mut Vec<Vector2>,
+    normals: &'a mut Vec<Vector3>,
+    colors: &'a mut Vec<Vector4>,
+    /// the indices of the original positions of the vertex attributes
+    vertex_origins: Vec<usize>,
+    /// the new indices of the attributes by the indices of the positions and the attributes
+    /// of the ends of the edges
+    positions_cache: HashMap<[usize; 2], usize>,
+    uv_coords_cache: HashMap<[usize; 4], usize>,
+    normals_cache: HashMap<[usize; 4], usize>,
+    colors_cache: HashMap<[usize; 4], usize>,
+}
+
+impl<'a> EdgeSplitter<'a> {
+    fn split(&mut self, a: Vertex, b: Vertex) -> Vertex {
+        // the ends are sorted so that the shared edges are cut at the same parameter
+        let (a, b) = match a.pos < b.pos {
+            true => (a, b),
+            false => (b, a),
+        };
+        let dists = self.dists;
+        let t = dists[a.pos] / (dists[a.pos] - dists[b.pos]);
+        let (positions, vertex_origins) = (&mut *self.positions, &mut self.vertex_origins);
+        let pos = *self.positions_cache.entry([a.pos, b.pos]).or_insert_with(|| {
+            let (p, q) = (positions[a.pos], positions[b.pos]);
+            positions.push(p + (q - p) * t);
+            vertex_origins.push(match dists[a.pos] <= 0.0 {
+                true => a.pos,
+                false => b.pos,
+            });
+            positions.len() - 1
+        });
+        let uv = a.uv.zip(b.uv).map(|(i, j)| {
+            let uv_coords = &mut *self.uv_coords;
+            *self.uv_coords_cache.entry([a.pos, b.pos, i, j]).or_insert_with(|| {
+                let (p, q) = (uv_coords[i], uv_coords[j]);
+                uv_coords.push(p + (q - p) * t);
+                uv_coords.len() - 1
+            })
+        });
+        let nor = a.nor.zip(b.nor).map(|(i, j)| {
+            let normals = &mut *self.normals;
+            *self.normals_cache.entry([a.pos, b.pos, i, j]).or_insert_with(|| {
+                let (p, q) = (normals[i], normals[j]);
+                let normal = p + (q - p) * t;
+                normals.push(match normal.so_small() {
+                    true => p,
+                    false => normal.normalize(),
+                });
+                normals.len() - 1
+            })
+        });
+        let col = a.col.zip(b.col).map(|(i, j)| {
+            let colors = &mut *self.colors;
+            *self.colors_cache.entry([a.pos, b.pos, i, j]).or_insert_with(|| {
+                let (p, q) = (colors[i], colors[j]);
+                colors.push(p + (q - p) * t);
+                colors.len() - 1
+            })
+        });
+        Vertex { pos, uv, nor, col }
+    }
+}
+
+/// Removes the part of `mesh` in front of the plane through `origin` with the unit normal
+/// `normal` by the Sutherland-Hodgman algorithm.
+fn clip_by_half_space(mesh: &mut PolygonMesh, origin: Point3, normal: Vector3) {
+    // the vertices near the plane are on the plane
+    let dists: Vec<f64> = mesh
+        .positions()
+        .iter()
+        .map(|pt| match (*pt - origin).dot(normal) {
+            dist if dist.so_small() => 0.0,
+            dist => dist,
+        })
+        .collect();
+    if dists.iter().all(|dist| *dist <= 0.0) {
+        return;
+    }
+    let mesh = mesh.debug_editor();
+    let mut splitter = EdgeSplitter {
+        dists: &dists,
+        vertex_origins: (0..mesh.positions.len()).collect(),
+        positions: mesh.positions,
+        uv_coords: mesh.uv_coords,
+        normals: mesh.normals,
+        colors: mesh.colors,
+        positions_cache: HashMap::new(),
+        uv_coords_cache: HashMap::new(),
+        normals_cache: HashMap::new(),
+        colors_cache: HashMap::new(),
+    };
+    let mut faces = Faces::default();
+    // the indices of the original faces of the triangles, the quadrangles and the others
+    let mut origins = [Vec::new(), Vec::new(), Vec::new()];
+    mesh.faces.face_iter().enumerate().for_each(|(i, face)| {
+        let len = face.len();
+        let mut polygon = Vec::with_capacity(len + 1);
+        (0..len).for_each(|j| {
+            let (a, b) = (face[j], face[(j + 1) % len]);
+            let (dist0, dist1) = (dists[a.pos], dists[b.pos]);
+            if dist0 <= 0.0 {
+                polygon.push(a);
+            }
+            if dist0 * dist1 < 0.0 {
+                polygon.push(splitter.split(a, b));
+            }
+        });
+        if polygon.len() >= 3 {
+            origins[usize::min(polygon.len(), 5) - 3].push(i);
+            faces.push(&polygon);
+        }
+    });
+    let vertex_origins = splitter.vertex_origins;
+    *mesh.faces = faces;
+    mesh.face_attributes.reindex(&origins.concat());
+    mesh.vertex_attributes.reindex(&vertex_origins);
+}
+
+/// Fills the closed boundaries on the plane by the even-odd rule.
+fn add_caps(mesh: &mut PolygonMesh, origin: Point3, normal: Vector3) {
+    let positions = mesh.positions();
+    let on_plane = |idx: &usize| (positions[*idx] - origin).dot(normal).so_small();
+    let loops: Vec<Vec<usize>> = mesh
+        .extract_boundaries()
+        .into_iter()
+        .filter(|boundary| boundary.len() >= 3 && boundary.iter().all(on_plane))
+        .collect();
+    if loops.is_empty() {
+        return;
+    }
+    // the frame of the plane, whose counterclockwise triangles face toward `normal`
+    let axis = match normal.x.abs() < 0.5 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let x = normal.cross(axis).normalize();
+    let y = normal.cross(x);
+    let project = |idx: usize| {
+        let vec = positions[idx] - origin;
+        [vec.dot(x), vec.dot(y)]
+    };
+
+    let mut triangulation = ConstrainedDelaunayTriangulation::<[f64; 2], FloatKernel>::new();
+    let mut indices = HashMap::<usize, usize>::new();
+    loops.iter().for_each(|boundary| {
+        let handles: Vec<usize> = boundary
+            .iter()
+            .map(|idx| {
+                let handle = triangulation.insert(project(*idx));
+                indices.entry(handle).or_insert(*idx);
+                handle
+            })
+            .collect();
+        (0..handles.len()).for_each(|i| {
+            let (from, to) = (handles[i], handles[(i + 1) % handles.len()]);
+            if from != to && triangulation.can_add_constraint(from, to) {
+                triangulation.add_constraint(from, to);
+            }
+        });
+    });
+    // The faces are filled by the even-odd rule, i.e. the parities of the numbers of
+    // the constraint edges crossed from the outside, which are robust for the slivers
+    // along the straight parts of the loops.
+    let mut inside = vec![None; triangulation.num_faces()];
+    let outer = triangulation.infinite_face().fix();
+    inside[outer] = Some(false);
+    let mut stack = vec![outer];
+    while let Some(face) = stack.pop() {
+        let parity = inside[face] == Some(true);
+        triangulation.face(face).adjacent_edges().for_each(|edge| {
+            let next = edge.sym().face().fix();
+            if inside[next].is_none() {
+                inside[next] = Some(parity ^ triangulation.is_constraint_edge(edge.fix()));
+                stack.push(next);
+            }
+        });
+    }
+    let caps: Vec<[usize; 3]> = triangulation
+        .triangles()
+        .filter(|face| inside[face.fix()] == Some(true))
+        .map(|face| face.as_triangle().map(|v| indices[&v.fix()]))
+        .collect();
+
+    // the face adjacent to the first edge of the loops
+    let (first, second) = (loops[0][0], loops[0][1]);
+    let origin_face = mesh
+        .face_iter()
+        .position(|face| {
+            let len = face.len();
+            (0..len).any(|i| {
+                let (a, b) = (face[i].pos, face[(i + 1) % len].pos);
+                (a == first && b == second) || (a == second && b == first)
+            })
+        })
+        .unwrap_or(0);
+    let (n_tri, n_faces) = (mesh.faces().tri_faces().len(), mesh.faces().len());
+    let has_normals = !mesh.normals().is_empty();
+    let mesh = mesh.debug_editor();
+    let nor = match has_normals {
+        true => {
+            mesh.normals.push(normal);
+            Some(mesh.normals.len() - 1)
+        }
+        false => None,
+    };
+    caps.iter().for_each(|tri| {
+        let face = tri.map(|pos| Vertex {
+            pos,
+            uv: None,
+            nor,
+            col: None,
+        });
+        mesh.faces.push(face);
+    });
+    let origins: Vec<usize> = (0..n_tri)
+        .chain(std::iter::repeat_n(origin_face, caps.len()))
+        .chain(n_tri..n_faces)
+        .collect();
+    mesh.face_attributes.reindex(&origins);
+}
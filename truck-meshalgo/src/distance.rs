@@ -0,0 +1,445 @@
+use crate::tessellation::{MeshableShape, MeshableSurface, PolylineableCurve};
+use crate::*;
+use std::collections::HashSet;
+use truck_topology::{Edge, Face, Shell, Solid};
+
+/// Pair of the closest points on two shapes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestPoints {
+    /// the point on `self` of [`ExactDistance::closest_points`]
+    ///
+    /// [`ExactDistance::closest_points`]: ./trait.ExactDistance.html#tymethod.closest_points
+    pub point0: Point3,
+    /// the point on `other` of [`ExactDistance::closest_points`]
+    ///
+    /// [`ExactDistance::closest_points`]: ./trait.ExactDistance.html#tymethod.closest_points
+    pub point1: Point3,
+    /// the distance between the points
+    pub distance: f64,
+}
+
+/// Measures the distances between points and shapes on the exact curves and surfaces.
+pub trait ExactDistance<T> {
+    /// Returns the closest points on `self` and `other`.
+    ///
+    /// The shapes are decomposed into the vertices, the edges and the faces, and the closest
+    /// points are searched for each pair of them. The initial guesses are the closest points
+    /// of the divisions of the curves and the tessellations of the faces with `tol`, and they
+    /// are refined by Newton's method on the exact curves and surfaces.
+    /// Returns `None` if the tessellation fails.
+    ///
+    /// The shapes are assumed not to intersect each other. For solids, the distance is
+    /// measured between the boundaries, and is not zero even if one solid includes the other.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::{builder, Solid};
+    /// use std::f64::consts::PI;
+    ///
+    /// // the unit cube and the unit sphere centered at (3, 0.5, 0.5)
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    /// let v = builder::vertex(Point3::new(3.0, 0.5, 1.5));
+    /// let center = Point3::new(3.0, 0.5, 0.5);
+    /// let wire = builder::rsweep(&v, center, Vector3::unit_y(), Rad(PI));
+    /// let shell = builder::cone(&wire, Vector3::unit_z(), Rad(2.0 * PI));
+    /// let sphere = Solid::new(vec![shell]);
+    ///
+    /// let closest = cube.closest_points(&sphere, 0.05).unwrap();
+    /// assert!(closest.distance.near(&1.0));
+    /// assert!(closest.point0[0].near(&1.0) && closest.point1[0].near(&2.0));
+    /// assert!(closest.point1.near(&Point3::new(2.0, 0.5, 0.5)));
+    /// ```
+    fn closest_points(&self, other: &T, tol: f64) -> Option<ClosestPoints>;
+    /// Returns the distance between `self` and `other` by [`closest_points`].
+    ///
+    /// [`closest_points`]: #tymethod.closest_points
+    #[inline(always)]
+    fn distance(&self, other: &T, tol: f64) -> Option<f64> {
+        self.closest_points(other, tol)
+            .map(|closest| closest.distance)
+    }
+}
+
+/// the parametrized geometry of a vertex, an edge or a face
+trait CellGeometry {
+    /// the number of the parameters
+    fn dim(&self) -> usize;
+    fn subs(&self, x: [f64; 2]) -> Point3;
+    /// the first and the second derivatives, which are zero for the unused parameters
+    fn ders(&self, x: [f64; 2]) -> ([Vector3; 2], [[Vector3; 2]; 2]);
+    /// moves the parameter into the domain
+    fn clamp(&self, x: [f64; 2]) -> [f64; 2] { x }
+    /// whether the parameter is in the domain
+    fn contains(&self, _: [f64; 2]) -> bool { true }
+    /// the outer unit normal of the face, which is not defined for the vertices and the edges
+    fn normal(&self, _: [f64; 2]) -> Option<Vector3> { None }
+}
+
+struct VertexGeometry(Point3);
+
+impl CellGeometry for VertexGeometry {
+    fn dim(&self) -> usize { 0 }
+    fn subs(&self, _: [f64; 2]) -> Point3 { self.0 }
+    fn ders(&self, _: [f64; 2]) -> ([Vector3; 2], [[Vector3; 2]; 2]) {
+        ([Vector3::zero(); 2], [[Vector3::zero(); 2]; 2])
+    }
+}
+
+struct EdgeGeometry<C>(C);
+
+impl<C: PolylineableCurve> CellGeometry for EdgeGeometry<C> {
+    fn dim(&self) -> usize { 1 }
+    fn subs(&self, x: [f64; 2]) -> Point3 { self.0.subs(x[0]) }
+    fn ders(&self, x: [f64; 2]) -> ([Vector3; 2], [[Vector3; 2]; 2]) {
+        let zero = Vector3::zero();
+        let der2 = [[self.0.der2(x[0]), zero], [zero; 2]];
+        ([self.0.der(x[0]), zero], der2)
+    }
+    fn clamp(&self, x: [f64; 2]) -> [f64; 2] {
+        let (t0, t1) = self.0.parameter_range();
+        [x[0].clamp(t0, t1), 0.0]
+    }
+}
+
+/// the surface, its tessellation, whose uv coordinates are the parameters on the surface,
+/// and the orientation of the face
+struct FaceGeometry<S>(S, PolygonMesh, bool);
+
+impl<S: MeshableSurface> CellGeometry for FaceGeometry<S> {
+    fn dim(&self) -> usize { 2 }
+    fn subs(&self, x: [f64; 2]) -> Point3 { self.0.subs(x[0], x[1]) }
+    fn ders(&self, x: [f64; 2]) -> ([Vector3; 2], [[Vector3; 2]; 2]) {
+        let (u, v) = (x[0], x[1]);
+        let uvder = self.0.uvder(u, v);
+        let der2 = [[self.0.uuder(u, v), uvder], [uvder, self.0.vvder(u, v)]];
+        ([self.0.uder(u, v), self.0.vder(u, v)], der2)
+    }
+    /// whether the parameter is in the triangles of the tessellation
+    fn contains(&self, x: [f64; 2]) -> bool { parameter_in_tessellation(&self.1, Vector2::from(x)) }
+    fn normal(&self, x: [f64; 2]) -> Option<Vector3> {
+        let normal = self.0.normal(x[0], x[1]);
+        match self.2 {
+            true => Some(normal),
+            false => Some(-normal),
+        }
+    }
+}
+
+/// whether the parameter `uv` is in the triangles of the tessellation `mesh` in the parameter space
+pub(crate) fn parameter_in_tessellation(mesh: &PolygonMesh, uv: Vector2) -> bool {
+    let uv_coords = mesh.uv_coords();
+    mesh.face_iter().any(|face| {
+        (2..face.len()).any(|i| {
+            let tri = [face[0].uv, face[i - 1].uv, face[i].uv];
+            let (a, b, c) = match tri {
+                [Some(a), Some(b), Some(c)] => (uv_coords[a], uv_coords[b], uv_coords[c]),
+                _ => return false,
+            };
+            let area = |p: Vector2, q: Vector2| (q - p).perp_dot(uv - p);
+            let (s0, s1, s2) = (area(a, b), area(b, c), area(c, a));
+            (s0 > -TOLERANCE && s1 > -TOLERANCE && s2 > -TOLERANCE)
+                || (s0 < TOLERANCE && s1 < TOLERANCE && s2 < TOLERANCE)
+        })
+    })
+}
+
+/// vertex, edge or face with the sample points on it
+pub(crate) struct Cell<'a> {
+    geometry: Box<dyn CellGeometry + 'a>,
+    /// the parameters and the points of the division or the tessellation
+    samples: Vec<([f64; 2], Point3)>,
+    /// the bounding box of the samples
+    bdb: BoundingBox<Point3>,
+}
+
+impl<'a> Cell<'a> {
+    fn new<G: CellGeometry + 'a>(geometry: G, params: Vec<[f64; 2]>) -> Cell<'a> {
+        let samples: Vec<_> = params.into_iter().map(|x| (x, geometry.subs(x))).collect();
+        let bdb = samples.iter().map(|(_, pt)| pt).collect();
+        Cell {
+            geometry: Box::new(geometry),
+            samples,
+            bdb,
+        }
+    }
+
+    pub(crate) fn vertex(point: Point3) -> Cell<'a> {
+        Cell::new(VertexGeometry(point), vec![[0.0; 2]])
+    }
+
+    fn edge<C: PolylineableCurve + 'a>(edge: &Edge<Point3, C>, tol: f64) -> Cell<'a> {
+        let curve = edge.get_curve();
+        let params = curve.parameter_division(curve.parameter_range(), tol);
+        let params = params.into_iter().map(|t| [t, 0.0]).collect();
+        Cell::new(EdgeGeometry(curve), params)
+    }
+
+    fn face<S: MeshableSurface + 'a>(surface: S, mesh: PolygonMesh, orientation: bool) -> Cell<'a> {
+        let params = mesh.uv_coords().iter().map(|uv| [uv[0], uv[1]]).collect();
+        Cell::new(FaceGeometry(surface, mesh, orientation), params)
+    }
+
+    /// the lower bound of the distances between the points on the cells
+    fn lower_bound(&self, other: &Cell<'_>, tol: f64) -> f64 {
+        let (min0, max0) = (self.bdb.min(), self.bdb.max());
+        let (min1, max1) = (other.bdb.min(), other.bdb.max());
+        let gap = Vector3::new(
+            f64::max(f64::max(min0[0] - max1[0], min1[0] - max0[0]), 0.0),
+            f64::max(f64::max(min0[1] - max1[1], min1[1] - max0[1]), 0.0),
+            f64::max(f64::max(min0[2] - max1[2], min1[2] - max0[2]), 0.0),
+        );
+        gap.magnitude() - 2.0 * tol
+    }
+
+    /// Returns the closest points on the cells, refined from the closest samples.
+    fn closest_points(&self, other: &Cell<'_>) -> Option<ClosestPoints> {
+        self.closest_params(other).map(|(_, _, closest)| closest)
+    }
+
+    /// Returns the parameters of the closest points on the cells and the closest points.
+    fn closest_params(&self, other: &Cell<'_>) -> Option<([f64; 2], [f64; 2], ClosestPoints)> {
+        let (x, pt0, y, pt1) = self
+            .samples
+            .iter()
+            .flat_map(|(x, pt0)| {
+                other
+                    .samples
+                    .iter()
+                    .map(move |(y, pt1)| (*x, *pt0, *y, *pt1))
+            })
+            .min_by(|a, b| {
+                let (dist0, dist1) = (a.1.distance2(a.3), b.1.distance2(b.3));
+                dist0.partial_cmp(&dist1).unwrap()
+            })?;
+        let sample = ClosestPoints {
+            point0: pt0,
+            point1: pt1,
+            distance: pt0.distance(pt1),
+        };
+        let refined = newton(&*self.geometry, &*other.geometry, (x, y))
+            .filter(|(x, y)| self.geometry.contains(*x) && other.geometry.contains(*y))
+            .map(|(x, y)| {
+                let (point0, point1) = (self.geometry.subs(x), other.geometry.subs(y));
+                let distance = point0.distance(point1);
+                let closest = ClosestPoints {
+                    point0,
+                    point1,
+                    distance,
+                };
+                (x, y, closest)
+            });
+        match refined {
+            Some(refined) if refined.2.distance < sample.distance => Some(refined),
+            _ => Some((x, y, sample)),
+        }
+    }
+}
+
+/// Searches the critical point of the squared distance by Newton's method from `hint`.
+fn newton(
+    geom0: &dyn CellGeometry,
+    geom1: &dyn CellGeometry,
+    hint: ([f64; 2], [f64; 2]),
+) -> Option<([f64; 2], [f64; 2])> {
+    let (mut x, mut y) = hint;
+    let (dim0, dim1) = (geom0.dim(), geom1.dim());
+    for _ in 0..100 {
+        let diff = geom0.subs(x) - geom1.subs(y);
+        let (der0, der20) = geom0.ders(x);
+        let (der1, der21) = geom1.ders(y);
+        // the gradient and the Hessian of the half of the squared distance
+        // in the parameters (x[0], x[1], y[0], y[1]), whose unused parameters are fixed.
+        let mut grad = Vector4::zero();
+        let mut hess = Matrix4::identity();
+        for i in 0..dim0 {
+            grad[i] = der0[i].dot(diff);
+            for j in 0..dim0 {
+                hess[i][j] = der0[i].dot(der0[j]) + der20[i][j].dot(diff);
+            }
+            for j in 0..dim1 {
+                hess[i][2 + j] = -der0[i].dot(der1[j]);
+                hess[2 + j][i] = -der0[i].dot(der1[j]);
+            }
+        }
+        for i in 0..dim1 {
+            grad[2 + i] = -der1[i].dot(diff);
+            for j in 0..dim1 {
+                hess[2 + i][2 + j] = der1[i].dot(der1[j]) - der21[i][j].dot(diff);
+            }
+        }
+        let delta = hess.invert()? * grad;
+        let x0 = geom0.clamp([x[0] - delta[0], x[1] - delta[1]]);
+        let y0 = geom1.clamp([y[0] - delta[2], y[1] - delta[3]]);
+        let step = Vector4::new(x0[0] - x[0], x0[1] - x[1], y0[0] - y[0], y0[1] - y[1]);
+        x = x0;
+        y = y0;
+        if step.magnitude().so_small() {
+            return Some((x, y));
+        }
+    }
+    None
+}
+
+/// Returns the closest points of the cells, pruned by the bounding boxes.
+pub(crate) fn closest_points(
+    cells0: &[Cell<'_>],
+    cells1: &[Cell<'_>],
+    tol: f64,
+) -> Option<ClosestPoints> {
+    let mut pairs: Vec<_> = cells0
+        .iter()
+        .flat_map(|cell0| cells1.iter().map(move |cell1| (cell0, cell1)))
+        .map(|(cell0, cell1)| (cell0.lower_bound(cell1, tol), cell0, cell1))
+        .collect();
+    pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    pairs.into_iter().fold(
+        None,
+        |closest: Option<ClosestPoints>, (bound, cell0, cell1)| match closest {
+            Some(closest) if closest.distance <= bound => Some(closest),
+            _ => match (closest, cell0.closest_points(cell1)) {
+                (Some(a), Some(b)) if a.distance <= b.distance => Some(a),
+                (a, b) => b.or(a),
+            },
+        },
+    )
+}
+
+/// Returns the outer normal at the closest point on the faces to `point`, whose distance from
+/// the cells is `distance`. Returns `None` if the closest point is on no faces, or on the edge
+/// where the normals of the faces are different.
+pub(crate) fn closest_normal(
+    cells: &[Cell<'_>],
+    point: Point3,
+    distance: f64,
+    tol: f64,
+) -> Option<Vector3> {
+    let vertex = Cell::vertex(point);
+    let normals: Vec<Vector3> = cells
+        .iter()
+        .filter(|cell| cell.geometry.dim() == 2 && cell.lower_bound(&vertex, tol) <= distance)
+        .filter_map(|cell| {
+            let (x, _, closest) = cell.closest_params(&vertex)?;
+            match closest.distance < distance + TOLERANCE {
+                true => cell.geometry.normal(x),
+                false => None,
+            }
+        })
+        .collect();
+    let normal = *normals.first()?;
+    match normals.iter().all(|n| n.near(&normal)) {
+        true => Some(normal),
+        false => None,
+    }
+}
+
+/// Decomposes shapes into the cells.
+trait Cells {
+    fn cells<'a>(&'a self, tol: f64) -> Option<Vec<Cell<'a>>>;
+}
+
+impl Cells for Point3 {
+    fn cells<'a>(&'a self, _: f64) -> Option<Vec<Cell<'a>>> { Some(vec![Cell::vertex(*self)]) }
+}
+
+impl<C: PolylineableCurve> Cells for Edge<Point3, C> {
+    fn cells<'a>(&'a self, tol: f64) -> Option<Vec<Cell<'a>>> {
+        Some(vec![
+            Cell::edge(self, tol),
+            Cell::vertex(self.front().get_point()),
+            Cell::vertex(self.back().get_point()),
+        ])
+    }
+}
+
+/// the cells of the faces, the edges and the vertices of the shells
+fn shell_cells<'a, C, S>(shells: &[Shell<Point3, C, S>], tol: f64) -> Option<Vec<Cell<'a>>>
+where
+    C: PolylineableCurve + 'a,
+    S: MeshableSurface + 'a, {
+    let meshed = shells
+        .iter()
+        .map(|shell| shell.triangulation(tol))
+        .collect::<Option<Vec<_>>>()?;
+    Some(meshed_shell_cells(shells, &meshed, tol))
+}
+
+/// the cells of the shells whose faces are given by the tessellations `meshed`
+pub(crate) fn meshed_shell_cells<'a, C, S>(
+    shells: &[Shell<Point3, C, S>],
+    meshed: &[Shell<Point3, PolylineCurve<Point3>, PolygonMesh>],
+    tol: f64,
+) -> Vec<Cell<'a>>
+where
+    C: PolylineableCurve + 'a,
+    S: MeshableSurface + 'a,
+{
+    let mut cells = Vec::new();
+    let (mut vertices, mut edges) = (HashSet::new(), HashSet::new());
+    for (shell, meshed) in shells.iter().zip(meshed) {
+        for (face, meshed) in shell.face_iter().zip(meshed.face_iter()) {
+            let (surface, mesh) = (face.get_surface(), meshed.get_surface());
+            cells.push(Cell::face(surface, mesh, face.orientation()));
+        }
+        for edge in shell.edge_iter() {
+            if edges.insert(edge.id()) {
+                cells.push(Cell::edge(&edge, tol));
+            }
+        }
+        for vertex in shell.vertex_iter() {
+            if vertices.insert(vertex.id()) {
+                cells.push(Cell::vertex(vertex.get_point()));
+            }
+        }
+    }
+    cells
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> Cells for Face<Point3, C, S> {
+    fn cells<'a>(&'a self, tol: f64) -> Option<Vec<Cell<'a>>> {
+        shell_cells(&[Shell::from(vec![self.clone()])], tol)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> Cells for Shell<Point3, C, S> {
+    fn cells<'a>(&'a self, tol: f64) -> Option<Vec<Cell<'a>>> {
+        shell_cells(std::slice::from_ref(self), tol)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> Cells for Solid<Point3, C, S> {
+    fn cells<'a>(&'a self, tol: f64) -> Option<Vec<Cell<'a>>> {
+        shell_cells(self.boundaries(), tol)
+    }
+}
+
+macro_rules! impl_exact_distance {
+    ($shape: ty, $other: ty) => {
+        impl<C: PolylineableCurve, S: MeshableSurface> ExactDistance<$other> for $shape {
+            fn closest_points(&self, other: &$other, tol: f64) -> Option<ClosestPoints> {
+                closest_points(&self.cells(tol)?, &other.cells(tol)?, tol)
+            }
+        }
+    };
+}
+
+impl<C: PolylineableCurve> ExactDistance<Point3> for Edge<Point3, C> {
+    fn closest_points(&self, other: &Point3, tol: f64) -> Option<ClosestPoints> {
+        closest_points(&self.cells(tol)?, &other.cells(tol)?, tol)
+    }
+}
+
+impl<C: PolylineableCurve> ExactDistance<Edge<Point3, C>> for Edge<Point3, C> {
+    fn closest_points(&self, other: &Edge<Point3, C>, tol: f64) -> Option<ClosestPoints> {
+        closest_points(&self.cells(tol)?, &other.cells(tol)?, tol)
+    }
+}
+
+impl_exact_distance!(Face<Point3, C, S>, Point3);
+impl_exact_distance!(Face<Point3, C, S>, Edge<Point3, C>);
+impl_exact_distance!(Face<Point3, C, S>, Face<Point3, C, S>);
+impl_exact_distance!(Shell<Point3, C, S>, Shell<Point3, C, S>);
+impl_exact_distance!(Solid<Point3, C, S>, Point3);
+impl_exact_distance!(Solid<Point3, C, S>, Solid<Point3, C, S>);
@@ -0,0 +1,139 @@
+use super::*;
+use crate::progressive;
+use truck_polymesh::progress::Progress;
+
+/// Configures of decimation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecimationDescriptor<'a> {
+    /// the number of the triangles at which the decimation stops. Default is `None`,
+    /// decimating until no collapse is allowed.
+    pub target_faces: Option<usize>,
+    /// the maximum distance from the moved vertices to the planes of the original faces
+    /// around them, and to the original boundary edges if the boundaries are not preserved.
+    /// Default is `None`, not bounding the error.
+    pub max_error: Option<f64>,
+    /// whether the boundary vertices are kept. Default is `true`.
+    pub preserve_boundaries: bool,
+    /// whether the vertices on the seams, whose corners have different uv coordinates,
+    /// are kept. Default is `true`.
+    pub preserve_uv_seams: bool,
+    /// the name of the face selection whose faces are kept, in order to decimate the mesh
+    /// except the selected region. Default is `None`, decimating the whole mesh.
+    pub preserve_selection: Option<&'a str>,
+}
+
+impl<'a> Default for DecimationDescriptor<'a> {
+    #[inline(always)]
+    fn default() -> DecimationDescriptor<'a> {
+        DecimationDescriptor {
+            target_faces: None,
+            max_error: None,
+            preserve_boundaries: true,
+            preserve_uv_seams: true,
+            preserve_selection: None,
+        }
+    }
+}
+
+/// Decimates meshes by the quadric error metrics.
+pub trait Decimation {
+    /// Decimates the mesh by collapsing the edges in the ascending order of the quadric errors,
+    /// until the number of the triangles reaches `descriptor.target_faces` or no collapse
+    /// within `descriptor.max_error` remains.
+    ///
+    /// The polygons are divided into triangles. The collapses never change the topology or
+    /// flip the faces, and the moved corners take the attributes of the kept vertices.
+    /// The faces of the selection `descriptor.preserve_selection` are not changed,
+    /// except that the polygons are divided.
+    /// The attributes of the faces follow the triangles, and the unused attributes are removed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the plane divided into 10 x 10 squares
+    /// let positions: Vec<Point3> = (0..=10)
+    ///     .flat_map(|i| (0..=10).map(move |j| Point3::new(i as f64, j as f64, 0.0)))
+    ///     .collect();
+    /// let index = |i: usize, j: usize| i * 11 + j;
+    /// let faces = Faces::from_iter((0..10).flat_map(|i| {
+    ///     (0..10).map(move |j| {
+    ///         [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]
+    ///     })
+    /// }));
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// mesh.decimate(&DecimationDescriptor {
+    ///     max_error: Some(1.0e-6),
+    ///     ..Default::default()
+    /// });
+    /// // almost only the 40 boundary vertices remain from 121 vertices
+    /// assert!(mesh.positions().len() < 50);
+    /// assert!(mesh.faces().len() < 100);
+    /// assert!(mesh.positions().iter().all(|p| p.z == 0.0));
+    /// ```
+    fn decimate(&mut self, descriptor: &DecimationDescriptor) -> &mut Self;
+    /// Decimates the mesh like [`decimate`], reporting the number of the removed triangles
+    /// to `progress`.
+    ///
+    /// If the filter is cancelled, returns [`Error::Cancelled`] and the mesh is not changed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the plane divided into 10 x 10 squares
+    /// let positions: Vec<Point3> = (0..=10)
+    ///     .flat_map(|i| (0..=10).map(move |j| Point3::new(i as f64, j as f64, 0.0)))
+    ///     .collect();
+    /// let index = |i: usize, j: usize| i * 11 + j;
+    /// let faces = Faces::from_iter((0..10).flat_map(|i| {
+    ///     (0..10).map(move |j| {
+    ///         [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]
+    ///     })
+    /// }));
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// let descriptor = DecimationDescriptor {
+    ///     target_faces: Some(100),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let res = mesh.decimate_with_progress(&descriptor, |done, _| done < 50);
+    /// assert!(matches!(res, Err(errors::Error::Cancelled)));
+    /// assert_eq!(mesh.faces().len(), 100);
+    ///
+    /// let mut last = (0, 0);
+    /// mesh.decimate_with_progress(&descriptor, |done, total| {
+    ///     last = (done, total);
+    ///     true
+    /// })
+    /// .unwrap();
+    /// // 200 triangles are decimated into 100 triangles
+    /// assert_eq!(last, (100, 100));
+    /// assert_eq!(mesh.faces().len(), 100);
+    /// ```
+    ///
+    /// [`decimate`]: ./trait.Decimation.html#tymethod.decimate
+    /// [`Error::Cancelled`]: ../../truck_polymesh/errors/enum.Error.html#variant.Cancelled
+    fn decimate_with_progress<P: Progress>(
+        &mut self,
+        descriptor: &DecimationDescriptor,
+        progress: P,
+    ) -> Result<&mut Self>;
+}
+
+impl Decimation for PolygonMesh {
+    #[inline(always)]
+    fn decimate(&mut self, descriptor: &DecimationDescriptor) -> &mut Self {
+        let _ = self.decimate_with_progress(descriptor, |_, _| true);
+        self
+    }
+    fn decimate_with_progress<P: Progress>(
+        &mut self,
+        descriptor: &DecimationDescriptor,
+        progress: P,
+    ) -> Result<&mut Self> {
+        let (triangles, origins) = progressive::decimated_triangles(self, descriptor, progress)?;
+        let mesh = self.debug_editor();
+        *mesh.faces = Faces::from_iter(triangles);
+        mesh.face_attributes.reindex(&origins);
+        drop(mesh);
+        Ok(self.remove_unused_attrs())
+    }
+}
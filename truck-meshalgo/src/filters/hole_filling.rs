@@ -0,0 +1,333 @@
+use super::*;
+use crate::analyzers::Topology;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+
+/// the number of the iterations of the umbrella operator in fairing
+const FAIRING_ITERATIONS: usize = 100;
+/// the limit of the iterations of splitting and relaxing the triangles in refinement
+const REFINEMENT_ITERATIONS: usize = 20;
+
+/// Configures of hole filling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HoleFillingDescriptor {
+    /// the maximum perimeter of the filled holes. Default is `None`, not bounding the perimeter.
+    pub max_perimeter: Option<f64>,
+    /// the maximum area of the filled holes, the magnitude of the vector area of the boundary
+    /// loops. Default is `None`, not bounding the area.
+    pub max_area: Option<f64>,
+    /// whether the filling triangles are refined to the lengths of the edges of the holes
+    /// and faired by the umbrella operator. Default is `false`.
+    pub refine: bool,
+}
+
+impl Default for HoleFillingDescriptor {
+    #[inline(always)]
+    fn default() -> HoleFillingDescriptor {
+        HoleFillingDescriptor {
+            max_perimeter: None,
+            max_area: None,
+            refine: false,
+        }
+    }
+}
+
+/// Fills the holes of open meshes.
+pub trait HoleFilling {
+    /// Fills the boundary loops whose perimeters and areas are within `descriptor` by
+    /// the triangles, which are made by the ear clipping on the plane of the vector area of
+    /// each loop, and optionally refined and faired.
+    ///
+    /// The loops passing the same vertex twice are not filled. The filling triangles have
+    /// no uv coordinates, normals or colors, and take the face attributes of a face adjacent
+    /// to the hole. The new vertices take the vertex attributes of a vertex of the hole.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_topology::shell::ShellCondition;
+    /// // the unit cube without the top and the bottom
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 1.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[
+    ///     [0, 1, 5, 4],
+    ///     [1, 2, 6, 5],
+    ///     [2, 3, 7, 6],
+    ///     [3, 0, 4, 7],
+    /// ]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// // the holes are larger than the limit
+    /// mesh.fill_holes(&HoleFillingDescriptor {
+    ///     max_perimeter: Some(3.0),
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(mesh.extract_boundaries().len(), 2);
+    ///
+    /// mesh.fill_holes(&Default::default());
+    /// assert_eq!(mesh.extract_boundaries().len(), 0);
+    /// assert_eq!(mesh.faces().len(), 8);
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    /// ```
+    fn fill_holes(&mut self, descriptor: &HoleFillingDescriptor) -> &mut Self;
+}
+
+impl HoleFilling for PolygonMesh {
+    fn fill_holes(&mut self, descriptor: &HoleFillingDescriptor) -> &mut Self {
+        // the faces of the directed edges, in the order of `face_iter`
+        let edge_faces: HashMap<(usize, usize), usize> = self
+            .face_iter()
+            .enumerate()
+            .flat_map(|(i, face)| {
+                let len = face.len();
+                (0..len).map(move |j| ((face[j].pos, face[(j + 1) % len].pos), i))
+            })
+            .collect();
+        let mut edges: HashSet<[usize; 2]> =
+            edge_faces.keys().map(|(a, b)| sorted(*a, *b)).collect();
+        let mut positions = self.positions().clone();
+        let mut triangles = Vec::<([usize; 3], usize)>::new();
+        let mut vertex_origins = Vec::<usize>::new();
+        for boundary in self.extract_boundaries() {
+            let unique: HashSet<usize> = boundary.iter().copied().collect();
+            if boundary.len() < 3 || unique.len() != boundary.len() {
+                continue;
+            }
+            // the loop of the filling, reversed from the boundary
+            let hole: Vec<usize> = boundary.iter().rev().copied().collect();
+            let len = hole.len();
+            let perimeter: f64 = (0..len)
+                .map(|i| positions[hole[i]].distance(positions[hole[(i + 1) % len]]))
+                .sum();
+            let vector_area = (0..len).fold(Vector3::zero(), |sum, i| {
+                let (p, q) = (positions[hole[i]], positions[hole[(i + 1) % len]]);
+                sum + p.to_vec().cross(q.to_vec()) / 2.0
+            });
+            let too_large = matches!(descriptor.max_perimeter, Some(max) if perimeter > max)
+                || matches!(descriptor.max_area, Some(max) if vector_area.magnitude() > max);
+            if too_large {
+                continue;
+            }
+            let mut tris = ear_clipping(&positions, &hole, vector_area);
+            if descriptor.refine {
+                let first = positions.len();
+                refine(&mut positions, &mut tris, &hole, &edges);
+                fair(&mut positions, &tris, first);
+                vertex_origins.extend(std::iter::repeat_n(hole[0], positions.len() - first));
+            }
+            tris.iter().for_each(|tri| {
+                (0..3).for_each(|i| {
+                    edges.insert(sorted(tri[i], tri[(i + 1) % 3]));
+                })
+            });
+            let origin = edge_faces[&(boundary[0], boundary[1])];
+            triangles.extend(tris.into_iter().map(|tri| (tri, origin)));
+        }
+        if triangles.is_empty() {
+            return self;
+        }
+        let n_tri = self.faces().tri_faces().len();
+        let n_faces = self.faces().len();
+        let mesh = self.debug_editor();
+        let origins: Vec<usize> = (0..n_tri)
+            .chain(triangles.iter().map(|(_, origin)| *origin))
+            .chain(n_tri..n_faces)
+            .collect();
+        triangles.iter().for_each(|(tri, _)| mesh.faces.push(tri));
+        mesh.face_attributes.reindex(&origins);
+        let vertex_map: Vec<usize> = (0..mesh.positions.len()).chain(vertex_origins).collect();
+        mesh.vertex_attributes.reindex(&vertex_map);
+        *mesh.positions = positions;
+        drop(mesh);
+        self
+    }
+}
+
+#[inline(always)]
+fn sorted(a: usize, b: usize) -> [usize; 2] {
+    match a < b {
+        true => [a, b],
+        false => [b, a],
+    }
+}
+
+/// Triangulates the loop `hole` by the ear clipping on the plane perpendicular to `normal`.
+/// The ears with the smaller angles are clipped first, and the loops non-simple on the plane
+/// are triangulated by the convex ears or the sharpest corners.
+fn ear_clipping(positions: &[Point3], hole: &[usize], normal: Vector3) -> Vec<[usize; 3]> {
+    let normal = match normal.so_small() {
+        true => Vector3::unit_z(),
+        false => normal.normalize(),
+    };
+    let axis = match normal[0].abs() < 0.5 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let e0 = axis.cross(normal).normalize();
+    let e1 = normal.cross(e0);
+    let project = |i: usize| {
+        let v = positions[i].to_vec();
+        Vector2::new(v.dot(e0), v.dot(e1))
+    };
+    let cross = |a: Vector2, b: Vector2| a[0] * b[1] - a[1] * b[0];
+    let mut polygon: Vec<usize> = hole.to_vec();
+    let mut triangles = Vec::new();
+    while polygon.len() > 3 {
+        let len = polygon.len();
+        // (whether the ear is valid, whether it is convex, the angle at the corner, index)
+        let best = (0..len)
+            .map(|i| {
+                let (a, b, c) = (polygon[(i + len - 1) % len], polygon[i], polygon[(i + 1) % len]);
+                let (pa, pb, pc) = (project(a), project(b), project(c));
+                let convex = cross(pb - pa, pc - pb) > 0.0;
+                let contains = |p: Vector2| {
+                    cross(pb - pa, p - pa) > 0.0
+                        && cross(pc - pb, p - pb) > 0.0
+                        && cross(pa - pc, p - pc) > 0.0
+                };
+                let empty = polygon
+                    .iter()
+                    .filter(|j| **j != a && **j != b && **j != c)
+                    .all(|j| !contains(project(*j)));
+                let (u, v) = (positions[a] - positions[b], positions[c] - positions[b]);
+                let angle = match convex {
+                    true => u.angle(v).0,
+                    false => 2.0 * PI - u.angle(v).0,
+                };
+                (convex && empty, convex, angle, i)
+            })
+            .min_by(|x, y| {
+                (!x.0, !x.1, x.2)
+                    .partial_cmp(&(!y.0, !y.1, y.2))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        let i = best.3;
+        triangles.push([polygon[(i + len - 1) % len], polygon[i], polygon[(i + 1) % len]]);
+        polygon.remove(i);
+    }
+    triangles.push([polygon[0], polygon[1], polygon[2]]);
+    triangles
+}
+
+/// Refines the triangles by splitting at the centroids and relaxing the edges, until
+/// the lengths of the edges are about those of the boundary of the hole.
+fn refine(
+    positions: &mut Vec<Point3>,
+    triangles: &mut Vec<[usize; 3]>,
+    hole: &[usize],
+    edges: &HashSet<[usize; 2]>,
+) {
+    let len = hole.len();
+    // the desired lengths of the edges around the vertices
+    let mut scales: HashMap<usize, f64> = (0..len)
+        .map(|i| {
+            let (prev, next) = (hole[(i + len - 1) % len], hole[(i + 1) % len]);
+            let p = positions[hole[i]];
+            (hole[i], (p.distance(positions[prev]) + p.distance(positions[next])) / 2.0)
+        })
+        .collect();
+    let boundary: HashSet<[usize; 2]> =
+        (0..len).map(|i| sorted(hole[i], hole[(i + 1) % len])).collect();
+    for _ in 0..REFINEMENT_ITERATIONS {
+        let mut split = false;
+        let mut new_triangles = Vec::with_capacity(triangles.len());
+        for tri in triangles.iter() {
+            let p = tri.map(|i| positions[i]);
+            let center = Point3::from_vec((p[0].to_vec() + p[1].to_vec() + p[2].to_vec()) / 3.0);
+            let scale = tri.iter().map(|i| scales[i]).sum::<f64>() / 3.0;
+            let too_large = (0..3).all(|i| {
+                f64::sqrt(2.0) * center.distance(p[i]) > f64::max(scale, scales[&tri[i]])
+            });
+            match too_large {
+                true => {
+                    positions.push(center);
+                    let c = positions.len() - 1;
+                    scales.insert(c, scale);
+                    new_triangles.push([tri[0], tri[1], c]);
+                    new_triangles.push([tri[1], tri[2], c]);
+                    new_triangles.push([tri[2], tri[0], c]);
+                    split = true;
+                }
+                false => new_triangles.push(*tri),
+            }
+        }
+        *triangles = new_triangles;
+        relax(positions, triangles, &boundary, edges);
+        if !split {
+            break;
+        }
+    }
+}
+
+/// Flips the inner edges of the triangles whose opposite angles sum more than `PI`.
+fn relax(
+    positions: &[Point3],
+    triangles: &mut [[usize; 3]],
+    boundary: &HashSet<[usize; 2]>,
+    edges: &HashSet<[usize; 2]>,
+) {
+    let angle = |a: usize, b: usize, c: usize| {
+        (positions[a] - positions[c]).angle(positions[b] - positions[c]).0
+    };
+    for _ in 0..4 * triangles.len() {
+        let mut map = HashMap::<(usize, usize), usize>::new();
+        triangles.iter().enumerate().for_each(|(i, tri)| {
+            (0..3).for_each(|j| {
+                map.insert((tri[j], tri[(j + 1) % 3]), i);
+            })
+        });
+        let current: HashSet<[usize; 2]> = map.keys().map(|(a, b)| sorted(*a, *b)).collect();
+        let mut directed = triangles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, tri)| (0..3).map(move |j| (tri[j], tri[(j + 1) % 3], i)));
+        let flip = directed.find_map(|(a, b, i)| {
+            let j = *map.get(&(b, a))?;
+            let c = triangles[i].iter().copied().find(|v| *v != a && *v != b)?;
+            let d = triangles[j].iter().copied().find(|v| *v != a && *v != b)?;
+            let fixed = boundary.contains(&sorted(a, b))
+                || current.contains(&sorted(c, d))
+                || edges.contains(&sorted(c, d));
+            match !fixed && angle(a, b, c) + angle(a, b, d) > PI + TOLERANCE {
+                true => Some((i, j, a, b, c, d)),
+                false => None,
+            }
+        });
+        match flip {
+            Some((i, j, a, b, c, d)) => {
+                triangles[i] = [a, d, c];
+                triangles[j] = [d, b, c];
+            }
+            None => break,
+        }
+    }
+}
+
+/// Moves the vertices from the `first`-th position to the averages of the adjacent vertices.
+fn fair(positions: &mut [Point3], triangles: &[[usize; 3]], first: usize) {
+    let mut adjacency = HashMap::<usize, HashSet<usize>>::new();
+    triangles.iter().for_each(|tri| {
+        (0..3).for_each(|i| {
+            let (a, b) = (tri[i], tri[(i + 1) % 3]);
+            adjacency.entry(a).or_default().insert(b);
+            adjacency.entry(b).or_default().insert(a);
+        })
+    });
+    for _ in 0..FAIRING_ITERATIONS {
+        for i in first..positions.len() {
+            let neighbors = &adjacency[&i];
+            let sum = neighbors
+                .iter()
+                .fold(Vector3::zero(), |sum, j| sum + positions[*j].to_vec());
+            positions[i] = Point3::from_vec(sum / neighbors.len() as f64);
+        }
+    }
+}
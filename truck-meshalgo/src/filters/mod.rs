@@ -1,9 +1,17 @@
 use crate::*;
 
+mod decimation;
+mod hole_filling;
 mod normal_filters;
 mod optimizing;
+mod smoothing;
 mod structuring;
+mod uv_projection;
 
+pub use decimation::{Decimation, DecimationDescriptor};
+pub use hole_filling::{HoleFilling, HoleFillingDescriptor};
 pub use normal_filters::NormalFilters;
 pub use optimizing::OptimizingFilter;
+pub use smoothing::{Smoothing, SmoothingDescriptor};
 pub use structuring::StructuringFilter;
+pub use uv_projection::{UVProjection, UVProjectionFilter};
@@ -1,5 +1,6 @@
 use super::*;
 use std::collections::HashMap;
+use truck_polymesh::progress::{Progress, ProgressCounter};
 
 /// Filters for adding normals
 pub trait NormalFilters {
@@ -108,6 +109,45 @@ pub trait NormalFilters {
     /// assert!(mesh.normals()[v1.nor.unwrap()].near(&Vector3::new(2.0, 5.0, 0.0).normalize()));
     /// ```
     fn add_smooth_normals(&mut self, tol_ang: f64, overwrite: bool) -> &mut Self;
+    /// Adds the smooth normal vectors like [`add_smooth_normals`], reporting the number of
+    /// the clustered faces to `progress`.
+    ///
+    /// If the filter is cancelled, returns [`Error::Cancelled`] and the mesh is not changed.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::filters::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2], [2, 1, 3]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let res = mesh.add_smooth_normals_with_progress(0.8, true, |done, _| done < 1);
+    /// assert!(matches!(res, Err(errors::Error::Cancelled)));
+    /// assert!(mesh.normals().is_empty());
+    ///
+    /// let mut log = Vec::new();
+    /// let progress = |done, total| {
+    ///     log.push((done, total));
+    ///     true
+    /// };
+    /// mesh.add_smooth_normals_with_progress(0.8, true, progress).unwrap();
+    /// assert_eq!(log, vec![(1, 2), (2, 2)]);
+    /// assert_eq!(mesh.normals(), &[Vector3::unit_z()]);
+    /// ```
+    ///
+    /// [`add_smooth_normals`]: ./trait.NormalFilters.html#tymethod.add_smooth_normals
+    /// [`Error::Cancelled`]: ../../truck_polymesh/errors/enum.Error.html#variant.Cancelled
+    fn add_smooth_normals_with_progress<P: Progress>(
+        &mut self,
+        tol_ang: f64,
+        overwrite: bool,
+        progress: P,
+    ) -> Result<&mut Self>;
     /// Makes the orientation of faces compatible to the normal vectors.
     /// # Examples
     /// ```
@@ -235,14 +275,31 @@ impl NormalFilters for PolygonMesh {
         self
     }
     fn add_smooth_normals(&mut self, tol_ang: f64, overwrite: bool) -> &mut Self {
-        let vnmap = self.clustering_noraml_faces(tol_ang.cos());
-        self.reflect_normal_clusters(vnmap, overwrite);
+        let mut counter = ProgressCounter::new(0, |_, _| true);
+        if let Ok(vnmap) = self.clustering_noraml_faces(tol_ang.cos(), &mut counter) {
+            self.reflect_normal_clusters(vnmap, overwrite);
+        }
         self
     }
+    fn add_smooth_normals_with_progress<P: Progress>(
+        &mut self,
+        tol_ang: f64,
+        overwrite: bool,
+        progress: P,
+    ) -> Result<&mut Self> {
+        let mut counter = ProgressCounter::new(self.faces().len(), progress);
+        let vnmap = self.clustering_noraml_faces(tol_ang.cos(), &mut counter)?;
+        self.reflect_normal_clusters(vnmap, overwrite);
+        Ok(self)
+    }
 }
 
 trait SubNormalFilter {
-    fn clustering_noraml_faces(&self, inf: f64) -> HashMap<usize, Vec<Vec<FaceNormal>>>;
+    fn clustering_noraml_faces<P: Progress>(
+        &self,
+        inf: f64,
+        counter: &mut ProgressCounter<P>,
+    ) -> Result<HashMap<usize, Vec<Vec<FaceNormal>>>>;
     fn reflect_normal_clusters(
         &mut self,
         vnmap: HashMap<usize, Vec<Vec<FaceNormal>>>,
@@ -251,13 +308,18 @@ trait SubNormalFilter {
 }
 
 impl SubNormalFilter for PolygonMesh {
-    fn clustering_noraml_faces(&self, inf: f64) -> HashMap<usize, Vec<Vec<FaceNormal>>> {
+    fn clustering_noraml_faces<P: Progress>(
+        &self,
+        inf: f64,
+        counter: &mut ProgressCounter<P>,
+    ) -> Result<HashMap<usize, Vec<Vec<FaceNormal>>>> {
         let positions = self.positions();
         let mut vnmap = HashMap::new();
-        self.face_iter()
-            .enumerate()
-            .for_each(|(i, face)| add_face_normal(positions, i, face, &mut vnmap, inf));
-        vnmap
+        for (i, face) in self.face_iter().enumerate() {
+            add_face_normal(positions, i, face, &mut vnmap, inf);
+            counter.try_advance(1)?;
+        }
+        Ok(vnmap)
     }
 
     fn reflect_normal_clusters(
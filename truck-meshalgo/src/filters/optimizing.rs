@@ -2,10 +2,11 @@ use super::*;
 use std::collections::HashMap;
 use std::iter::Iterator;
 use std::ops::{Div, Mul};
+use truck_polymesh::progress::{Progress, ProgressCounter};
 
 /// Filters for optimizing data
 pub trait OptimizingFilter {
-    /// remove all unused position, texture coordinates, and normal vectors.
+    /// remove all unused position, texture coordinates, normal vectors, and colors.
     /// # Examples
     /// ```
     /// use truck_polymesh::*;
@@ -47,10 +48,12 @@ pub trait OptimizingFilter {
     /// assert_eq!(mesh.faces().len(), 2);
     /// ```
     fn remove_degenerate_faces(&mut self) -> &mut Self;
-    /// Gives the same indices to the same positions, texture coordinate, and normal vectors, respectively.
+    /// Gives the same indices to the same positions, texture coordinate, normal vectors,
+    /// and colors, respectively.
     /// # Remarks
     /// No longer needed attributes are NOT autoremoved.
     /// One can remove such attributes by running [`remove_unused_attrs`] mannually.
+    /// Then, the positions put together keep the vertex attributes of the first ones.
     ///
     /// [`remove_unused_attrs`]: ./trait.WasteEliminatingFilter.html#tymethod.remove_unused_attrs
     ///
@@ -72,9 +75,9 @@ pub trait OptimizingFilter {
     /// ]);
     /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
     ///
-    /// assert_eq!(mesh.faces()[1][1], Vertex { pos: 4, uv: None, nor: None });
+    /// assert_eq!(mesh.faces()[1][1], Vertex { pos: 4, uv: None, nor: None, col: None });
     /// mesh.put_together_same_attrs();
-    /// assert_eq!(mesh.faces()[1][1], Vertex { pos: 2, uv: None, nor: None });
+    /// assert_eq!(mesh.faces()[1][1], Vertex { pos: 2, uv: None, nor: None, col: None });
     ///
     /// // Remarks: No longer needed attributes are NOT autoremoved!
     /// assert_eq!(mesh.positions().len(), 6);
@@ -82,6 +85,46 @@ pub trait OptimizingFilter {
     /// assert_eq!(mesh.positions().len(), 4);
     /// ```
     fn put_together_same_attrs(&mut self) -> &mut Self;
+    /// Puts together the same attributes like [`put_together_same_attrs`], reporting the number
+    /// of the compared positions, texture coordinates, normal vectors, and colors to `progress`.
+    ///
+    /// If the filter is cancelled, returns [`Error::Cancelled`] and the mesh is not changed.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::filters::*;
+    /// use truck_polymesh::progress::CancellationToken;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[&[0, 1, 2], &[0, 1, 3]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    /// let res = mesh.put_together_same_attrs_with_progress(&token);
+    /// assert!(matches!(res, Err(errors::Error::Cancelled)));
+    /// assert_eq!(mesh.faces()[1][2].pos, 3);
+    ///
+    /// let mut done = 0;
+    /// mesh.put_together_same_attrs_with_progress(|i, _| {
+    ///     done = i;
+    ///     true
+    /// })
+    /// .unwrap();
+    /// assert_eq!(done, 4);
+    /// assert_eq!(mesh.faces()[1][2].pos, 2);
+    /// ```
+    ///
+    /// [`put_together_same_attrs`]: ./trait.OptimizingFilter.html#tymethod.put_together_same_attrs
+    /// [`Error::Cancelled`]: ../../truck_polymesh/errors/enum.Error.html#variant.Cancelled
+    fn put_together_same_attrs_with_progress<P: Progress>(
+        &mut self,
+        progress: P,
+    ) -> Result<&mut Self>;
 }
 
 fn all_pos_mut(faces: &mut Faces) -> impl Iterator<Item = &mut usize> {
@@ -102,18 +145,29 @@ fn all_nor_mut(faces: &mut Faces) -> impl Iterator<Item = &mut usize> {
         .filter_map(move |v| v.nor.as_mut())
 }
 
+fn all_col_mut(faces: &mut Faces) -> impl Iterator<Item = &mut usize> {
+    faces
+        .face_iter_mut()
+        .flatten()
+        .filter_map(move |v| v.col.as_mut())
+}
+
 impl OptimizingFilter for PolygonMesh {
     fn remove_unused_attrs(&mut self) -> &mut Self {
         let mesh = self.debug_editor();
         let pos_iter = all_pos_mut(mesh.faces);
         let idcs = sub_remove_unused_attrs(pos_iter, mesh.positions.len());
         *mesh.positions = idcs.iter().map(|i| mesh.positions[*i]).collect();
+        mesh.vertex_attributes.reindex(&idcs);
         let uv_iter = all_uv_mut(mesh.faces);
         let idcs = sub_remove_unused_attrs(uv_iter, mesh.uv_coords.len());
         *mesh.uv_coords = idcs.iter().map(|i| mesh.uv_coords[*i]).collect();
         let nor_iter = all_nor_mut(mesh.faces);
         let idcs = sub_remove_unused_attrs(nor_iter, mesh.normals.len());
         *mesh.normals = idcs.iter().map(|i| mesh.normals[*i]).collect();
+        let col_iter = all_col_mut(mesh.faces);
+        let idcs = sub_remove_unused_attrs(col_iter, mesh.colors.len());
+        *mesh.colors = idcs.iter().map(|i| mesh.colors[*i]).collect();
         drop(mesh);
         self
     }
@@ -121,45 +175,90 @@ impl OptimizingFilter for PolygonMesh {
     fn remove_degenerate_faces(&mut self) -> &mut Self {
         let mesh = self.debug_editor();
         let mut faces = Faces::default();
-        for tri in mesh.faces.tri_faces() {
+        // the indices of the original faces of the triangles, the quadrangles and the others
+        let mut origins = [Vec::new(), Vec::new(), Vec::new()];
+        let mut push = |face: &[Vertex], origin: usize| {
+            if face.len() >= 3 {
+                origins[usize::min(face.len(), 5) - 3].push(origin);
+                faces.push(face);
+            }
+        };
+        let (n_tri, n_quad) = (mesh.faces.tri_faces().len(), mesh.faces.quad_faces().len());
+        for (i, tri) in mesh.faces.tri_faces().iter().enumerate() {
             if !degenerate_triangle(*tri) {
-                faces.push(tri);
+                push(tri, i);
             }
         }
-        for quad in mesh.faces.quad_faces() {
+        for (i, quad) in mesh.faces.quad_faces().iter().enumerate() {
             match degenerate_quadrangle(*quad) {
                 QuadrangleType::TotallyDegenerate => {}
-                QuadrangleType::Triangle(tri) => faces.push(tri),
-                QuadrangleType::NonDegenerate => faces.push(quad),
+                QuadrangleType::Triangle(tri) => push(&tri, n_tri + i),
+                QuadrangleType::NonDegenerate => push(quad, n_tri + i),
             }
         }
-        for face in mesh.faces.other_faces() {
-            faces.extend(split_into_nondegenerate(face.clone()));
+        for (i, face) in mesh.faces.other_faces().iter().enumerate() {
+            split_into_nondegenerate(face.clone())
+                .iter()
+                .for_each(|face| push(face, n_tri + n_quad + i));
         }
         *mesh.faces = faces;
+        mesh.face_attributes.reindex(&origins.concat());
         drop(mesh);
         self
     }
 
     fn put_together_same_attrs(&mut self) -> &mut Self {
-        let mesh = self.debug_editor();
-        let bnd_box: BoundingBox<_> = mesh.positions.iter().collect();
-        let center = bnd_box.center();
-        let diag = bnd_box.diagonal().map(|a| f64::max(a.abs(), 1.0));
-        let normalized_positions = mesh
-            .positions
-            .iter()
-            .map(move |position| 2.0 * (position - center).zip(diag, |a, b| a / b))
-            .collect::<Vec<_>>();
-        let pos_map = sub_put_together_same_attrs(&normalized_positions);
-        all_pos_mut(mesh.faces).for_each(|idx| *idx = pos_map[*idx]);
-        let uv_map = sub_put_together_same_attrs(&mesh.uv_coords);
-        all_uv_mut(mesh.faces).for_each(|idx| *idx = uv_map[*idx]);
-        let nor_map = sub_put_together_same_attrs(&mesh.normals);
-        all_nor_mut(mesh.faces).for_each(|idx| *idx = nor_map[*idx]);
-        drop(mesh);
+        let mut counter = ProgressCounter::new(0, |_, _| true);
+        if let Ok(maps) = same_attrs_maps(self, &mut counter) {
+            reflect_same_attrs_maps(self, maps);
+        }
         self
     }
+
+    fn put_together_same_attrs_with_progress<P: Progress>(
+        &mut self,
+        progress: P,
+    ) -> Result<&mut Self> {
+        let len = self.positions().len()
+            + self.uv_coords().len()
+            + self.normals().len()
+            + self.colors().len();
+        let mut counter = ProgressCounter::new(len, progress);
+        let maps = same_attrs_maps(self, &mut counter)?;
+        reflect_same_attrs_maps(self, maps);
+        Ok(self)
+    }
+}
+
+/// the maps from the indices of the positions, the uv coords, the normals and the colors to
+/// the indices of the first same attributes
+fn same_attrs_maps<P: Progress>(
+    mesh: &PolygonMesh,
+    counter: &mut ProgressCounter<P>,
+) -> Result<[Vec<usize>; 4]> {
+    let bnd_box: BoundingBox<_> = mesh.positions().iter().collect();
+    let center = bnd_box.center();
+    let diag = bnd_box.diagonal().map(|a| f64::max(a.abs(), 1.0));
+    let normalized_positions = mesh
+        .positions()
+        .iter()
+        .map(move |position| 2.0 * (position - center).zip(diag, |a, b| a / b))
+        .collect::<Vec<_>>();
+    Ok([
+        sub_put_together_same_attrs(&normalized_positions, counter)?,
+        sub_put_together_same_attrs(mesh.uv_coords(), counter)?,
+        sub_put_together_same_attrs(mesh.normals(), counter)?,
+        sub_put_together_same_attrs(mesh.colors(), counter)?,
+    ])
+}
+
+fn reflect_same_attrs_maps(mesh: &mut PolygonMesh, maps: [Vec<usize>; 4]) {
+    let [pos_map, uv_map, nor_map, col_map] = maps;
+    let mesh = mesh.debug_editor();
+    all_pos_mut(mesh.faces).for_each(|idx| *idx = pos_map[*idx]);
+    all_uv_mut(mesh.faces).for_each(|idx| *idx = uv_map[*idx]);
+    all_nor_mut(mesh.faces).for_each(|idx| *idx = nor_map[*idx]);
+    all_col_mut(mesh.faces).for_each(|idx| *idx = col_map[*idx]);
 }
 
 fn sub_remove_unused_attrs<'a, I: Iterator<Item = &'a mut usize>>(
@@ -182,10 +281,14 @@ fn sub_remove_unused_attrs<'a, I: Iterator<Item = &'a mut usize>>(
     new2old
 }
 
-fn sub_put_together_same_attrs<T: Copy + CastIntVector>(attrs: &[T]) -> Vec<usize> {
+fn sub_put_together_same_attrs<T: Copy + CastIntVector, P: Progress>(
+    attrs: &[T],
+    counter: &mut ProgressCounter<P>,
+) -> Result<Vec<usize>> {
     let mut res = Vec::new();
     let mut map = HashMap::new();
     for (i, attr) in attrs.iter().enumerate() {
+        counter.try_advance(1)?;
         let v = ((*attr).add_element_wise(TOLERANCE) / (TOLERANCE * 2.0)).cast_int();
         match map.get(&v) {
             Some(j) => res.push(*j),
@@ -195,7 +298,7 @@ fn sub_put_together_same_attrs<T: Copy + CastIntVector>(attrs: &[T]) -> Vec<usiz
             }
         }
     }
-    res
+    Ok(res)
 }
 
 fn degenerate_triangle(tri: [Vertex; 3]) -> bool {
@@ -256,6 +359,7 @@ macro_rules! impl_cast_int {
 }
 impl_cast_int!(Vector2, 2);
 impl_cast_int!(Vector3, 3);
+impl_cast_int!(Vector4, 4);
 impl_cast_int!(Point3, 3);
 
 #[cfg(test)]
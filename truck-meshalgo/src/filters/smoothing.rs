@@ -0,0 +1,132 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+
+/// Configures of smoothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmoothingDescriptor<'a> {
+    /// the number of the iterations of the pairs of the shrinking and the inflating steps.
+    /// Default is `10`.
+    pub iterations: usize,
+    /// the positive factor of the shrinking steps. Default is `0.5`.
+    pub lambda: f64,
+    /// the negative factor of the inflating steps, whose magnitude is slightly larger than
+    /// `lambda` so that the volume is kept. Default is `-0.53`.
+    pub mu: f64,
+    /// whether the boundary vertices are kept. Default is `true`.
+    pub preserve_boundaries: bool,
+    /// the name of the vertex selection whose vertices are moved, in order to smooth only
+    /// the selected region. Default is `None`, smoothing the whole mesh.
+    pub selection: Option<&'a str>,
+}
+
+impl<'a> Default for SmoothingDescriptor<'a> {
+    #[inline(always)]
+    fn default() -> SmoothingDescriptor<'a> {
+        SmoothingDescriptor {
+            iterations: 10,
+            lambda: 0.5,
+            mu: -0.53,
+            preserve_boundaries: true,
+            selection: None,
+        }
+    }
+}
+
+/// Smoothes meshes by the Laplacian operator.
+pub trait Smoothing {
+    /// Smoothes the mesh by the Taubin's method, moving each vertex toward the average of
+    /// the adjacent vertices by `descriptor.lambda` and then away by `descriptor.mu`.
+    ///
+    /// Only the positions are moved, and the normals are not updated. If
+    /// `descriptor.selection` is given, the vertices out of the selection are kept, and
+    /// nothing is moved if the mesh has no such vertex selection.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the plane divided into 4 x 4 squares, whose center is raised
+    /// let positions: Vec<Point3> = (0..=4)
+    ///     .flat_map(|i| (0..=4).map(move |j| Point3::new(i as f64, j as f64, 0.0)))
+    ///     .map(|p| match p.x == 2.0 && p.y == 2.0 {
+    ///         true => Point3::new(p.x, p.y, 1.0),
+    ///         false => p,
+    ///     })
+    ///     .collect();
+    /// let index = |i: usize, j: usize| i * 5 + j;
+    /// let faces = Faces::from_iter((0..4).flat_map(|i| {
+    ///     (0..4).map(move |j| {
+    ///         [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]
+    ///     })
+    /// }));
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// // only the vertices around the corner (1, 1) can move
+    /// mesh.select_vertices("corner", [index(1, 1), index(1, 2), index(2, 1)])
+    ///     .unwrap();
+    ///
+    /// mesh.smooth(&SmoothingDescriptor {
+    ///     selection: Some("corner"),
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(mesh.positions()[index(2, 2)], Point3::new(2.0, 2.0, 1.0));
+    /// assert!(mesh.positions()[index(1, 2)].z > 0.0);
+    /// assert!(mesh.positions()[index(1, 1)].z > 0.0);
+    /// assert_eq!(mesh.positions()[index(3, 3)].z, 0.0);
+    /// ```
+    fn smooth(&mut self, descriptor: &SmoothingDescriptor) -> &mut Self;
+}
+
+impl Smoothing for PolygonMesh {
+    fn smooth(&mut self, descriptor: &SmoothingDescriptor) -> &mut Self {
+        let mut movable = match descriptor.selection {
+            Some(name) => match self.vertex_attributes().selection(name) {
+                Some(selection) => selection.clone(),
+                None => return self,
+            },
+            None => vec![true; self.positions().len()],
+        };
+        let mut edge_faces = HashMap::<[usize; 2], usize>::new();
+        self.face_iter().for_each(|face| {
+            (0..face.len()).for_each(|i| {
+                let (a, b) = (face[i].pos, face[(i + 1) % face.len()].pos);
+                if a != b {
+                    *edge_faces.entry([a.min(b), a.max(b)]).or_default() += 1;
+                }
+            })
+        });
+        let mut neighbors = vec![HashSet::new(); self.positions().len()];
+        edge_faces.iter().for_each(|([a, b], count)| {
+            neighbors[*a].insert(*b);
+            neighbors[*b].insert(*a);
+            if descriptor.preserve_boundaries && *count == 1 {
+                movable[*a] = false;
+                movable[*b] = false;
+            }
+        });
+        let neighbors: Vec<Vec<usize>> = neighbors
+            .into_iter()
+            .map(|set| set.into_iter().collect())
+            .collect();
+        let positions = self.positions_mut();
+        let mut step = |factor: f64| {
+            let moved: Vec<Point3> = positions
+                .iter()
+                .enumerate()
+                .map(|(i, p)| match movable[i] && !neighbors[i].is_empty() {
+                    true => {
+                        let sum = neighbors[i]
+                            .iter()
+                            .fold(Vector3::zero(), |sum, j| sum + positions[*j].to_vec());
+                        let average = Point3::from_vec(sum / neighbors[i].len() as f64);
+                        p + (average - p) * factor
+                    }
+                    false => *p,
+                })
+                .collect();
+            positions.copy_from_slice(&moved);
+        };
+        for _ in 0..descriptor.iterations {
+            step(descriptor.lambda);
+            step(descriptor.mu);
+        }
+        self
+    }
+}
@@ -4,6 +4,8 @@ use crate::common::Triangulate;
 /// triangulation, quadrangulation, give a structure
 pub trait StructuringFilter {
     /// triangulate all n-gons
+    ///
+    /// Each triangle takes the face attributes of the original n-gon.
     /// # Examples
     /// ```
     /// use truck_polymesh::*;
@@ -46,6 +48,8 @@ pub trait StructuringFilter {
     /// 1. sort the list of the pairs of triangles by the score
     /// 1. take a pair of triangles in order from the top of the list and register a new one
     /// if it doesn't conflict with the one has been already registered.
+    ///
+    /// Each quadrangle takes the face attributes of one of the joined triangles.
     /// # Examples
     /// ```
     /// use truck_polymesh::*;
@@ -80,7 +84,16 @@ pub trait StructuringFilter {
 impl StructuringFilter for PolygonMesh {
     fn triangulate(&mut self) -> &mut Self {
         let tri_faces = Triangulate::new(&*self).into_iter().collect::<Vec<_>>();
-        *self.debug_editor().faces = Faces::from_tri_and_quad_faces(tri_faces, Vec::new());
+        // each triangle takes the face attributes of the original face
+        let origins: Vec<usize> = self
+            .face_iter()
+            .enumerate()
+            .flat_map(|(i, face)| std::iter::repeat_n(i, face.len() - 2))
+            .collect();
+        let mesh = self.debug_editor();
+        *mesh.faces = Faces::from_tri_and_quad_faces(tri_faces, Vec::new());
+        mesh.face_attributes.reindex(&origins);
+        drop(mesh);
         self
     }
     fn quadrangulate(&mut self, plane_tol: f64, score_tol: f64) -> &mut Self {
@@ -120,8 +133,12 @@ impl SubStructureFilter for PolygonMesh {
     }
 
     fn reflect_face_edge_list(&mut self, list: Vec<FaceEdge>) {
-        let mut used = vec![false; self.faces().tri_faces().len()];
+        let n_tri = self.faces().tri_faces().len();
+        let n_quad = self.faces().quad_faces().len();
+        let mut used = vec![false; n_tri];
         let mut quad_faces = self.faces().quad_faces().clone();
+        // the original faces of the new quadrangles, which take the face attributes
+        let mut quad_origins: Vec<usize> = (n_tri..n_tri + n_quad).collect();
         quad_faces.extend(list.into_iter().filter_map(|face_edge| {
             let (i, j) = face_edge.faces;
             if used[i] || used[j] {
@@ -129,19 +146,22 @@ impl SubStructureFilter for PolygonMesh {
             } else {
                 used[i] = true;
                 used[j] = true;
+                quad_origins.push(i);
                 Some(face_edge.positions)
             }
         }));
         let tri_faces = self.faces().tri_faces();
-        let tri_faces = used
+        let (tri_origins, tri_faces): (Vec<_>, Vec<_>) = used
             .into_iter()
             .enumerate()
             .filter_map(move |(i, flag)| match flag {
                 true => None,
-                false => Some(tri_faces[i]),
+                false => Some((i, tri_faces[i])),
             })
-            .collect::<Vec<_>>();
-        *self.debug_editor().faces = Faces::from_tri_and_quad_faces(tri_faces, quad_faces);
+            .unzip();
+        let mesh = self.debug_editor();
+        *mesh.faces = Faces::from_tri_and_quad_faces(tri_faces, quad_faces);
+        mesh.face_attributes.reindex(&[tri_origins, quad_origins].concat());
     }
     fn get_face_edge(
         &self,
@@ -0,0 +1,323 @@
+use super::*;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// The projections generating uv coordinates.
+///
+/// The lengths of the axes are the sizes of a tile of the texture, so the textures can be
+/// stretched anisotropically by the axes of different lengths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UVProjection {
+    /// the parallel projection onto the plane spanned by `u_axis` and `v_axis`.
+    Planar {
+        /// the position projected to the uv coordinate `(0, 0)`
+        origin: Point3,
+        /// the vector from `origin` to the position projected to `(1, 0)`
+        u_axis: Vector3,
+        /// the vector from `origin` to the position projected to `(0, 1)`
+        v_axis: Vector3,
+    },
+    /// the planar projections along the axis closest to the normal of each face.
+    /// The face along the first axis is projected to the plane spanned by the second and
+    /// the third axes, the second to the third and the first, and the third to the first and
+    /// the second.
+    Box {
+        /// the position projected to the uv coordinate `(0, 0)`
+        origin: Point3,
+        /// the axes of the box, assumed to be orthogonal
+        axes: [Vector3; 3],
+    },
+    /// the projection onto the cylinder around `axis`. The u coordinate is the angle around
+    /// the axis from `reference` divided by `2 pi`, and the v coordinate is the height along
+    /// the axis divided by the length of `axis`.
+    Cylindrical {
+        /// a point on the axis, projected to the height `0`
+        origin: Point3,
+        /// the direction of the axis
+        axis: Vector3,
+        /// the direction of the angle `0`, assumed to be not parallel to the axis
+        reference: Vector3,
+    },
+    /// the projection onto the sphere around `origin`. The u coordinate is the longitude around
+    /// `axis` from `reference` divided by `2 pi`, and the v coordinate is the angle from
+    /// the south pole `-axis` divided by `pi`.
+    Spherical {
+        /// the center of the sphere
+        origin: Point3,
+        /// the direction of the north pole
+        axis: Vector3,
+        /// the direction of the longitude `0`, assumed to be not parallel to the axis
+        reference: Vector3,
+    },
+}
+
+/// Filters for generating uv coordinates by projections.
+pub trait UVProjectionFilter {
+    /// Replaces the uv coordinates with the ones by `projection`.
+    ///
+    /// The uv coordinates are assigned face by face, so the faces across the seams of
+    /// the cylindrical and spherical projections have continuous uv coordinates exceeding `1`,
+    /// and the vertices at the poles have the average u coordinates of the other vertices
+    /// in each face.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::filters::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(4.0, 0.0, 0.0),
+    ///     Point3::new(4.0, 2.0, 0.0),
+    ///     Point3::new(0.0, 2.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// // the texture is stretched twice in the x direction.
+    /// mesh.add_projected_uv_coords(&UVProjection::Planar {
+    ///     origin: Point3::origin(),
+    ///     u_axis: Vector3::new(2.0, 0.0, 0.0),
+    ///     v_axis: Vector3::new(0.0, 1.0, 0.0),
+    /// });
+    /// let uv = mesh.faces()[0][2].uv.unwrap();
+    /// assert!(mesh.uv_coords()[uv].near(&Vector2::new(2.0, 2.0)));
+    /// ```
+    fn add_projected_uv_coords(&mut self, projection: &UVProjection) -> &mut Self;
+    /// Splits the uv coordinates on the seams of the periodic uv coordinates, e.g. the surface
+    /// parameters of closed surfaces normalized into `[0, 1)`, so that no face is stretched
+    /// across the whole texture.
+    ///
+    /// `period` is the periods of the u and v coordinates, and `None` means that the
+    /// coordinate is not periodic. The coordinates of each face are moved by the periods to
+    /// the nearest ones to the first vertex, and then into the range from `0` to the end of
+    /// the face. The vertices on the seams have two uv coordinates, e.g. `u = 0` and `u = 1`.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_meshalgo::filters::*;
+    /// let positions = vec![
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    /// ];
+    /// // the angle around the z axis divided by `2 pi`
+    /// let uv_coords = vec![
+    ///     Vector2::new(0.75, 0.0),
+    ///     Vector2::new(0.0, 0.0),
+    ///     Vector2::new(0.0, 1.0),
+    ///     Vector2::new(0.75, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[
+    ///     (0, Some(0), None),
+    ///     (1, Some(1), None),
+    ///     (2, Some(2), None),
+    ///     (3, Some(3), None),
+    /// ]]);
+    /// let mut mesh = PolygonMesh::new(positions, uv_coords, Vec::new(), faces);
+    ///
+    /// mesh.wrap_uv_seams((Some(1.0), None));
+    /// let uv = mesh.faces()[0][1].uv.unwrap();
+    /// assert!(mesh.uv_coords()[uv].near(&Vector2::new(1.0, 0.0)));
+    /// ```
+    fn wrap_uv_seams(&mut self, period: (Option<f64>, Option<f64>)) -> &mut Self;
+}
+
+impl UVProjectionFilter for PolygonMesh {
+    fn add_projected_uv_coords(&mut self, projection: &UVProjection) -> &mut Self {
+        let mesh = self.debug_editor();
+        let (positions, uv_coords, faces) =
+            (&*mesh.positions, &mut *mesh.uv_coords, &mut *mesh.faces);
+        uv_coords.clear();
+        let mut indices = HashMap::new();
+        for face in faces.face_iter_mut() {
+            let uvs = project_face(positions, face, projection);
+            face.iter_mut().zip(uvs).for_each(|(v, uv)| {
+                let key = (uv[0].to_bits(), uv[1].to_bits());
+                let idx = *indices.entry(key).or_insert_with(|| {
+                    uv_coords.push(uv);
+                    uv_coords.len() - 1
+                });
+                v.uv = Some(idx);
+            });
+        }
+        drop(mesh);
+        self
+    }
+    fn wrap_uv_seams(&mut self, period: (Option<f64>, Option<f64>)) -> &mut Self {
+        let mesh = self.debug_editor();
+        let (uv_coords, faces) = (&mut *mesh.uv_coords, &mut *mesh.faces);
+        let old_uv_coords = std::mem::take(uv_coords);
+        let mut indices = HashMap::new();
+        for face in faces.face_iter_mut() {
+            let mut uvs = face
+                .iter()
+                .map(|v| v.uv.map(|uv| old_uv_coords[uv]))
+                .collect::<Vec<_>>();
+            unwrap_periodic(&mut uvs, 0, period.0);
+            unwrap_periodic(&mut uvs, 1, period.1);
+            face.iter_mut().zip(uvs).for_each(|(v, uv)| {
+                v.uv = uv.map(|uv| {
+                    let key = (uv[0].to_bits(), uv[1].to_bits());
+                    *indices.entry(key).or_insert_with(|| {
+                        uv_coords.push(uv);
+                        uv_coords.len() - 1
+                    })
+                });
+            });
+        }
+        drop(mesh);
+        self
+    }
+}
+
+/// Moves the `i`th coordinates of `uvs` by `period` to the nearest ones to the first one,
+/// and then to the range from `0`.
+fn unwrap_periodic(uvs: &mut [Option<Vector2>], i: usize, period: Option<f64>) {
+    let period = match period {
+        Some(period) if period > 0.0 => period,
+        _ => return,
+    };
+    let mut iter = uvs.iter_mut().flatten();
+    let first = match iter.next() {
+        Some(uv) => uv[i],
+        None => return,
+    };
+    iter.for_each(|uv| uv[i] -= period * f64::round((uv[i] - first) / period));
+    let min = uvs.iter().flatten().fold(f64::INFINITY, |min, uv| f64::min(min, uv[i]));
+    let shift = period * f64::floor(min / period + TOLERANCE);
+    uvs.iter_mut().flatten().for_each(|uv| uv[i] -= shift);
+}
+
+fn project_face(positions: &[Point3], face: &[Vertex], projection: &UVProjection) -> Vec<Vector2> {
+    let planar = |origin: Point3, u_axis: Vector3, v_axis: Vector3| {
+        face.iter()
+            .map(|v| {
+                let p = positions[v.pos] - origin;
+                Vector2::new(
+                    p.dot(u_axis) / u_axis.magnitude2(),
+                    p.dot(v_axis) / v_axis.magnitude2(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    match *projection {
+        UVProjection::Planar {
+            origin,
+            u_axis,
+            v_axis,
+        } => planar(origin, u_axis, v_axis),
+        UVProjection::Box { origin, axes } => {
+            let normal = FaceNormal::new(positions, face, 0).normal;
+            let (i, _) = axes
+                .iter()
+                .map(|axis| f64::abs(normal.dot(axis.normalize())))
+                .enumerate()
+                .fold((0, f64::NEG_INFINITY), |res, (i, x)| match x > res.1 {
+                    true => (i, x),
+                    false => res,
+                });
+            planar(origin, axes[(i + 1) % 3], axes[(i + 2) % 3])
+        }
+        UVProjection::Cylindrical {
+            origin,
+            axis,
+            reference,
+        } => {
+            let frame = Frame::new(axis, reference);
+            let uvs = face
+                .iter()
+                .map(|v| {
+                    let p = positions[v.pos] - origin;
+                    let height = p.dot(axis) / axis.magnitude2();
+                    frame
+                        .longitude(p)
+                        .map(|u| Vector2::new(u, height))
+                        .unwrap_or_else(|| Vector2::new(f64::NAN, height))
+                })
+                .collect();
+            fix_seam(uvs)
+        }
+        UVProjection::Spherical {
+            origin,
+            axis,
+            reference,
+        } => {
+            let frame = Frame::new(axis, reference);
+            let uvs = face
+                .iter()
+                .map(|v| {
+                    let p = positions[v.pos] - origin;
+                    let cos = match p.so_small() {
+                        true => 0.0,
+                        false => f64::clamp(p.normalize().dot(frame.axis), -1.0, 1.0),
+                    };
+                    let latitude = 1.0 - f64::acos(cos) / PI;
+                    frame
+                        .longitude(p)
+                        .map(|u| Vector2::new(u, latitude))
+                        .unwrap_or_else(|| Vector2::new(f64::NAN, latitude))
+                })
+                .collect();
+            fix_seam(uvs)
+        }
+    }
+}
+
+/// the orthonormal frame of the cylindrical and spherical projections
+struct Frame {
+    axis: Vector3,
+    e0: Vector3,
+    e1: Vector3,
+}
+
+impl Frame {
+    fn new(axis: Vector3, reference: Vector3) -> Frame {
+        let axis = axis.normalize();
+        let e0 = (reference - axis * reference.dot(axis)).normalize();
+        Frame {
+            axis,
+            e0,
+            e1: axis.cross(e0),
+        }
+    }
+
+    /// the angle around the axis divided by `2 pi` in `[0, 1)`, or `None` on the axis
+    fn longitude(&self, p: Vector3) -> Option<f64> {
+        let (x, y) = (p.dot(self.e0), p.dot(self.e1));
+        match f64::hypot(x, y).so_small() {
+            true => None,
+            false => Some(f64::atan2(y, x).rem_euclid(2.0 * PI) / (2.0 * PI)),
+        }
+    }
+}
+
+/// Unwraps the u coordinates across the seam, and fills the u coordinates at the poles.
+fn fix_seam(mut uvs: Vec<Vector2>) -> Vec<Vector2> {
+    let min = uvs
+        .iter()
+        .filter(|uv| !uv[0].is_nan())
+        .map(|uv| uv[0])
+        .fold(f64::INFINITY, f64::min);
+    let max = uvs
+        .iter()
+        .filter(|uv| !uv[0].is_nan())
+        .map(|uv| uv[0])
+        .fold(f64::NEG_INFINITY, f64::max);
+    if max - min > 0.5 {
+        uvs.iter_mut()
+            .filter(|uv| uv[0] < 0.5)
+            .for_each(|uv| uv[0] += 1.0);
+    }
+    let (sum, count) = uvs
+        .iter()
+        .filter(|uv| !uv[0].is_nan())
+        .fold((0.0, 0), |(sum, count), uv| (sum + uv[0], count + 1));
+    let average = match count {
+        0 => 0.0,
+        _ => sum / count as f64,
+    };
+    uvs.iter_mut()
+        .filter(|uv| uv[0].is_nan())
+        .for_each(|uv| uv[0] = average);
+    uvs
+}
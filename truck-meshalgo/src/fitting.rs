@@ -0,0 +1,200 @@
+use crate::*;
+use truck_geometry::{BSplineSurface, KnotVec};
+
+/// Fits B-spline surfaces to meshes.
+pub trait BSplineFitting {
+    /// Approximates the grid of positions by a B-spline surface.
+    ///
+    /// The control points are fitted by least squares, and the knots are refined until the
+    /// distances between the positions and the corresponding points on the surface are within `tol`.
+    /// If the mesh has uv divisions, they are used as the parameters of the positions.
+    /// Otherwise, the parameters are the averaged chord lengths normalized to `[0, 1]`.
+    ///
+    /// The degree in each direction is reduced to the number of the positions minus one.
+    /// Returns `None` if `degree == 0`, the mesh has less than two positions in some direction,
+    /// some position or uv division is not finite, or the surface within `tol` is not found.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the paraboloid z = x^2 + y^2
+    /// let div: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    /// let positions: Vec<Vec<Point3>> = div
+    ///     .iter()
+    ///     .map(|x| div.iter().map(|y| Point3::new(*x, *y, x * x + y * y)).collect())
+    ///     .collect();
+    /// let mesh = StructuredMesh::from_positions_and_uvs(positions, (div.clone(), div.clone()));
+    ///
+    /// let surface = mesh.fit_bspline(2, 1.0e-6).unwrap();
+    /// // the quadratic surface is reproduced by one patch.
+    /// assert_eq!(surface.degrees(), (2, 2));
+    /// assert_eq!(surface.control_points().len(), 3);
+    /// assert_eq!(surface.control_points()[0].len(), 3);
+    /// assert!(surface.subs(0.35, 0.6).near(&Point3::new(0.35, 0.6, 0.4825)));
+    /// ```
+    fn fit_bspline(&self, degree: usize, tol: f64) -> Option<BSplineSurface<Point3>>;
+}
+
+impl BSplineFitting for StructuredMesh {
+    fn fit_bspline(&self, degree: usize, tol: f64) -> Option<BSplineSurface<Point3>> {
+        let positions = self.positions();
+        let (ulen, vlen) = (positions.len(), positions.first().map_or(0, Vec::len));
+        if degree == 0 || ulen < 2 || vlen < 2 {
+            return None;
+        }
+        if !positions
+            .iter()
+            .flatten()
+            .all(|p| (0..3).all(|i| p[i].is_finite()))
+        {
+            return None;
+        }
+        let (uparams, vparams) = match self.uv_division() {
+            Some((udiv, vdiv)) => {
+                if !udiv.iter().chain(vdiv).all(|t| t.is_finite()) {
+                    return None;
+                }
+                (udiv.clone(), vdiv.clone())
+            }
+            None => (
+                chord_parameters((0..vlen).map(|j| positions.iter().map(move |row| row[j]))),
+                chord_parameters(positions.iter().map(|row| row.iter().copied())),
+            ),
+        };
+        let (udegree, vdegree) = (usize::min(degree, ulen - 1), usize::min(degree, vlen - 1));
+        let (mut udim, mut vdim) = (udegree + 1, vdegree + 1);
+        loop {
+            let uknot_vec = fitting_knot_vec(&uparams, udegree, udim);
+            let vknot_vec = fitting_knot_vec(&vparams, vdegree, vdim);
+            let ubasis = basis_matrix(&uknot_vec, udegree, &uparams);
+            let vbasis = basis_matrix(&vknot_vec, vdegree, &vparams);
+            // The tensor product least squares is separated into the ones of each direction.
+            let columns = (0..vlen)
+                .map(|j| {
+                    let column: Vec<Vector3> =
+                        positions.iter().map(|row| row[j].to_vec()).collect();
+                    least_squares(&ubasis, &column)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let control_points = (0..udim)
+                .map(|i| {
+                    let row: Vec<Vector3> = columns.iter().map(|column| column[i]).collect();
+                    let row = least_squares(&vbasis, &row)?;
+                    Some(row.into_iter().map(Point3::from_vec).collect())
+                })
+                .collect::<Option<Vec<Vec<Point3>>>>()?;
+            let surface = BSplineSurface::new((uknot_vec, vknot_vec), control_points);
+            let within = positions.iter().zip(&uparams).all(|(row, u)| {
+                row.iter()
+                    .zip(&vparams)
+                    .all(|(pt, v)| surface.subs(*u, *v).distance(*pt) <= tol)
+            });
+            if within {
+                return Some(surface);
+            } else if udim == ulen && vdim == vlen {
+                return None;
+            }
+            udim = usize::min(udegree + 2 * (udim - udegree), ulen);
+            vdim = usize::min(vdegree + 2 * (vdim - vdegree), vlen);
+        }
+    }
+}
+
+/// Returns the parameters by the chord lengths averaged over `lines`.
+fn chord_parameters<I, J>(lines: I) -> Vec<f64>
+where
+    I: Iterator<Item = J>,
+    J: Iterator<Item = Point3>, {
+    let mut sum: Vec<f64> = Vec::new();
+    let mut count = 0;
+    for line in lines {
+        let mut line = line.peekable();
+        let mut lengths = vec![0.0];
+        while let (Some(p), Some(q)) = (line.next(), line.peek()) {
+            lengths.push(lengths[lengths.len() - 1] + p.distance(*q));
+        }
+        let total = lengths[lengths.len() - 1];
+        sum.resize(lengths.len(), 0.0);
+        if !total.so_small() {
+            sum.iter_mut()
+                .zip(lengths)
+                .for_each(|(s, l)| *s += l / total);
+            count += 1;
+        }
+    }
+    let len = sum.len();
+    match count {
+        0 => (0..len).map(|i| i as f64 / (len - 1) as f64).collect(),
+        _ => sum.into_iter().map(|s| s / count as f64).collect(),
+    }
+}
+
+/// Returns the clamped knot vector such that every span contains a parameter.
+fn fitting_knot_vec(params: &[f64], degree: usize, dim: usize) -> KnotVec {
+    let len = params.len();
+    let mut knots = vec![params[0]; degree + 1];
+    if dim == len {
+        knots.extend(
+            (1..dim - degree).map(|j| params[j..j + degree].iter().sum::<f64>() / degree as f64),
+        );
+    } else {
+        let d = len as f64 / (dim - degree) as f64;
+        knots.extend((1..dim - degree).map(|j| {
+            let i = (j as f64 * d) as usize;
+            let alpha = j as f64 * d - i as f64;
+            (1.0 - alpha) * params[i - 1] + alpha * params[i]
+        }));
+    }
+    knots.resize(knots.len() + degree + 1, params[len - 1]);
+    KnotVec::from(knots)
+}
+
+fn basis_matrix(knot_vec: &KnotVec, degree: usize, params: &[f64]) -> Vec<Vec<f64>> {
+    params
+        .iter()
+        .map(|t| knot_vec.bspline_basis_functions(degree, *t))
+        .collect()
+}
+
+/// Solves the normal equation of `basis * x = data`.
+fn least_squares(basis: &[Vec<f64>], data: &[Vector3]) -> Option<Vec<Vector3>> {
+    let dim = basis[0].len();
+    let mut mat: Vec<Vec<f64>> = (0..dim)
+        .map(|i| {
+            (0..dim)
+                .map(|j| basis.iter().map(|row| row[i] * row[j]).sum())
+                .collect()
+        })
+        .collect();
+    let mut rhs: Vec<Vector3> = (0..dim)
+        .map(|i| {
+            basis
+                .iter()
+                .zip(data)
+                .fold(Vector3::zero(), |sum, (row, v)| sum + row[i] * v)
+        })
+        .collect();
+    // Gaussian elimination with partial pivoting
+    for k in 0..dim {
+        let pivot = (k..dim).max_by(|i, j| mat[*i][k].abs().total_cmp(&mat[*j][k].abs()))?;
+        if mat[pivot][k].so_small() {
+            return None;
+        }
+        mat.swap(k, pivot);
+        rhs.swap(k, pivot);
+        let pivot_row = mat[k].clone();
+        for i in k + 1..dim {
+            let r = mat[i][k] / pivot_row[k];
+            mat[i]
+                .iter_mut()
+                .zip(&pivot_row)
+                .skip(k)
+                .for_each(|(a, b)| *a -= r * b);
+            rhs[i] = rhs[i] - r * rhs[k];
+        }
+    }
+    for k in (0..dim).rev() {
+        let sum = (k + 1..dim).fold(rhs[k], |sum, j| sum - mat[k][j] * rhs[j]);
+        rhs[k] = sum / mat[k][k];
+    }
+    Some(rhs)
+}
@@ -0,0 +1,357 @@
+use crate::analyzers::Topology;
+use crate::filters::OptimizingFilter;
+use crate::tessellation::{MeshableShape, MeshableSurface, MeshedShape, PolylineableCurve};
+use crate::*;
+use std::collections::{HashMap, VecDeque};
+use truck_topology::{Face, Shell};
+
+type PolylineCurve = truck_polymesh::PolylineCurve<Point3>;
+
+const RELAXATION_ITERATIONS: usize = 100;
+
+/// The flat pattern unrolled from a mesh onto the xy-plane
+#[derive(Clone, Debug)]
+pub struct FlatPattern {
+    /// the unrolled triangles on the xy-plane
+    pub mesh: PolygonMesh,
+    /// the index of the original position of each position of `mesh`
+    pub original_indices: Vec<usize>,
+    /// the closed boundaries of `mesh`, including the cuts along the seams
+    pub outlines: Vec<PolylineCurve>,
+    /// the maximum of the relative differences between the lengths of the unrolled edges and
+    /// the original edges
+    pub max_length_distortion: f64,
+    /// the relative difference between the area of the unrolled triangles and the original area
+    pub area_distortion: f64,
+}
+
+/// Unrolls meshes of developable surfaces into the flat patterns.
+pub trait Flattening {
+    /// Unrolls the mesh onto the xy-plane.
+    ///
+    /// The triangles are placed one by one across the shared edges, keeping their edge lengths,
+    /// from the first triangle of each connected component. The unrolled vertices with the same
+    /// original position are welded if they are closer than the half of the average length of
+    /// the edges, and are left as the cuts otherwise, e.g. along the seam of a cylinder.
+    /// The gaps of the welded vertices are distributed over the edges by relaxing the lengths.
+    /// The connected components are arranged along the x-axis.
+    ///
+    /// The mesh is assumed to be oriented, and the faces are assumed to share the indices of
+    /// the positions. The distortions are zero up to rounding errors if the mesh is
+    /// developable, and grow with the Gaussian curvature otherwise.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    ///
+    /// // the two faces of a folded sheet
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    ///     Point3::new(1.0, 1.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2, 3], [3, 2, 5, 4]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let pattern = mesh.flat_pattern();
+    /// assert_eq!(pattern.mesh.positions().len(), 6);
+    /// assert!(pattern.max_length_distortion.so_small());
+    /// assert!(pattern.area_distortion.so_small());
+    /// // the outline of the 1 x 2 rectangle
+    /// assert_eq!(pattern.outlines.len(), 1);
+    /// let length: f64 = pattern.outlines[0].windows(2).map(|p| p[0].distance(p[1])).sum();
+    /// assert!(length.near(&6.0));
+    /// ```
+    fn flat_pattern(&self) -> FlatPattern;
+}
+
+/// Unrolls developable faces, e.g. cylinders, cones and ruled surfaces, into the flat patterns.
+pub trait ShapeFlattening {
+    /// Unrolls the tessellation of the shape with the tolerance `tol` by [`Flattening`].
+    /// Returns `None` if the tessellation fails.
+    ///
+    /// The tessellation may be slightly non-developable around the vertices inserted on
+    /// the boundaries, so the distortions of developable faces may be small but not zero.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the side of the cylinder with the radius 1 and the height 2
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let e = builder::tsweep(&v, 2.0 * Vector3::unit_z());
+    /// let cylinder: Shell = builder::rsweep(&e, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    ///
+    /// let pattern = cylinder.flat_pattern(0.01).unwrap();
+    /// assert!(pattern.max_length_distortion < 1.0e-6);
+    /// // the rectangle cut along the seam
+    /// assert_eq!(pattern.outlines.len(), 1);
+    /// let mesh = &pattern.mesh;
+    /// let area: f64 = mesh
+    ///     .face_iter()
+    ///     .map(|face| {
+    ///         let p: Vec<Point3> = face.iter().map(|v| mesh.positions()[v.pos]).collect();
+    ///         (p[1] - p[0]).cross(p[2] - p[0]).magnitude() / 2.0
+    ///     })
+    ///     .sum();
+    /// assert!(f64::abs(area - 4.0 * std::f64::consts::PI) < 0.05);
+    /// ```
+    ///
+    /// [`Flattening`]: ./trait.Flattening.html
+    fn flat_pattern(&self, tol: f64) -> Option<FlatPattern>;
+}
+
+impl Flattening for PolygonMesh {
+    fn flat_pattern(&self) -> FlatPattern {
+        let triangles: Vec<[usize; 3]> = self
+            .face_iter()
+            .flat_map(|face| {
+                (2..face.len()).map(move |i| [face[0].pos, face[i - 1].pos, face[i].pos])
+            })
+            .collect();
+        let positions = self.positions();
+        let length = |a: usize, b: usize| positions[a].distance(positions[b]);
+        let sum: f64 = triangles
+            .iter()
+            .flat_map(|tri| (0..3).map(move |i| length(tri[i], tri[(i + 1) % 3])))
+            .sum();
+        let weld_distance = match triangles.len() {
+            0 => 0.0,
+            len => sum / (3 * len) as f64 / 2.0,
+        };
+
+        let mut edge_map = HashMap::<[usize; 2], Vec<usize>>::new();
+        triangles.iter().enumerate().for_each(|(i, tri)| {
+            (0..3).for_each(|j| {
+                let (a, b) = (tri[j], tri[(j + 1) % 3]);
+                let key = [usize::min(a, b), usize::max(a, b)];
+                edge_map.entry(key).or_default().push(i);
+            })
+        });
+
+        // the unrolled positions, their original indices and components
+        let mut flat: Vec<(Point2, usize, usize)> = Vec::new();
+        let mut flat_triangles: Vec<Option<[usize; 3]>> = vec![None; triangles.len()];
+        let mut component = 0;
+        for seed in 0..triangles.len() {
+            if flat_triangles[seed].is_some() {
+                continue;
+            }
+            let [a, b, c] = triangles[seed];
+            let (pa, pb) = (Point2::origin(), Point2::new(length(a, b), 0.0));
+            let pc = third_point(pa, pb, length(b, c), length(c, a));
+            flat.extend(vec![(pa, a, component), (pb, b, component), (pc, c, component)]);
+            let len = flat.len();
+            flat_triangles[seed] = Some([len - 3, len - 2, len - 1]);
+            let mut queue = VecDeque::from(vec![seed]);
+            while let Some(current) = queue.pop_front() {
+                let flat_tri = flat_triangles[current].unwrap();
+                for j in 0..3 {
+                    let (a, b) = (triangles[current][j], triangles[current][(j + 1) % 3]);
+                    let (fa, fb) = (flat_tri[j], flat_tri[(j + 1) % 3]);
+                    let neighbors = &edge_map[&[usize::min(a, b), usize::max(a, b)]];
+                    // unrolls across only the manifold edges
+                    if neighbors.len() != 2 {
+                        continue;
+                    }
+                    let next = neighbors[0] + neighbors[1] - current;
+                    if flat_triangles[next].is_some() {
+                        continue;
+                    }
+                    let tri = triangles[next];
+                    let k = match (0..3).find(|k| tri[*k] != a && tri[*k] != b) {
+                        Some(k) => k,
+                        None => continue,
+                    };
+                    // the base of the next triangle in its own winding
+                    let (base0, base1) = (tri[(k + 1) % 3], tri[(k + 2) % 3]);
+                    let (f0, f1) = match base0 == a {
+                        true => (fa, fb),
+                        false => (fb, fa),
+                    };
+                    let pt = third_point(
+                        flat[f0].0,
+                        flat[f1].0,
+                        length(base1, tri[k]),
+                        length(tri[k], base0),
+                    );
+                    flat.push((pt, tri[k], component));
+                    let mut flat_tri = [0; 3];
+                    flat_tri[k] = flat.len() - 1;
+                    flat_tri[(k + 1) % 3] = f0;
+                    flat_tri[(k + 2) % 3] = f1;
+                    flat_triangles[next] = Some(flat_tri);
+                    queue.push_back(next);
+                }
+            }
+            component += 1;
+        }
+
+        // welds the unrolled vertices with the same original positions
+        let mut clusters = HashMap::<(usize, usize), Vec<usize>>::new();
+        let mut sums: Vec<(Vector2, usize, usize, usize)> = Vec::new();
+        let weld_map: Vec<usize> = flat
+            .iter()
+            .map(|(pt, original, component)| {
+                let candidates = clusters.entry((*original, *component)).or_default();
+                let found = candidates.iter().find(|idx| {
+                    let (sum, _, _, count) = sums[**idx];
+                    (sum / count as f64).distance(pt.to_vec()) < weld_distance
+                });
+                match found {
+                    Some(idx) => {
+                        sums[*idx].0 += pt.to_vec();
+                        sums[*idx].3 += 1;
+                        *idx
+                    }
+                    None => {
+                        candidates.push(sums.len());
+                        sums.push((pt.to_vec(), *original, *component, 1));
+                        sums.len() - 1
+                    }
+                }
+            })
+            .collect();
+        let mut points: Vec<Point2> = sums
+            .iter()
+            .map(|(sum, _, _, count)| Point2::from_vec(sum / *count as f64))
+            .collect();
+
+        // distributes the gaps of the welded vertices over the edges
+        let welded_edges: Vec<([usize; 2], f64)> = flat_triangles
+            .iter()
+            .flatten()
+            .zip(&triangles)
+            .flat_map(|(tri, original)| {
+                let weld_map = &weld_map;
+                (0..3).map(move |j| {
+                    let edge = [weld_map[tri[j]], weld_map[tri[(j + 1) % 3]]];
+                    (edge, length(original[j], original[(j + 1) % 3]))
+                })
+            })
+            .collect();
+        if sums.len() < flat.len() {
+            relax(&mut points, &welded_edges);
+        }
+
+        // arranges the components along the x-axis
+        let mut offset = 0.0;
+        for i in 0..component {
+            let bdd: BoundingBox<Point2> = points
+                .iter()
+                .zip(&sums)
+                .filter(|(_, (_, _, c, _))| *c == i)
+                .map(|(pt, _)| *pt)
+                .collect();
+            let shift = Vector2::new(offset - bdd.min()[0], -bdd.min()[1]);
+            points
+                .iter_mut()
+                .zip(&sums)
+                .filter(|(_, (_, _, c, _))| *c == i)
+                .for_each(|(pt, _)| *pt += shift);
+            offset += bdd.max()[0] - bdd.min()[0] + weld_distance * 2.0;
+        }
+
+        let faces: Vec<[usize; 3]> = flat_triangles
+            .iter()
+            .flatten()
+            .map(|tri| [weld_map[tri[0]], weld_map[tri[1]], weld_map[tri[2]]])
+            .collect();
+        let (mut max_length_distortion, mut flat_area, mut area) = (0.0, 0.0, 0.0);
+        for (tri, original) in faces.iter().zip(&triangles) {
+            for j in 0..3 {
+                let length = length(original[j], original[(j + 1) % 3]);
+                if length > TOLERANCE {
+                    let flat_length = points[tri[j]].distance(points[tri[(j + 1) % 3]]);
+                    let distortion = f64::abs(flat_length - length) / length;
+                    max_length_distortion = f64::max(max_length_distortion, distortion);
+                }
+            }
+            let (p, q) = (points[tri[1]] - points[tri[0]], points[tri[2]] - points[tri[0]]);
+            flat_area += f64::abs(p.perp_dot(q)) / 2.0;
+            let (p, q) = (
+                positions[original[1]] - positions[original[0]],
+                positions[original[2]] - positions[original[0]],
+            );
+            area += p.cross(q).magnitude() / 2.0;
+        }
+        let area_distortion = match area < TOLERANCE2 {
+            true => 0.0,
+            false => f64::abs(flat_area - area) / area,
+        };
+
+        let positions: Vec<Point3> =
+            points.iter().map(|pt| Point3::new(pt[0], pt[1], 0.0)).collect();
+        let faces = Faces::from_iter(&faces);
+        let outlines = faces
+            .extract_boundaries()
+            .into_iter()
+            .map(|boundary| {
+                let mut polyline: Vec<Point3> =
+                    boundary.iter().map(|idx| positions[*idx]).collect();
+                polyline.push(polyline[0]);
+                PolylineCurve(polyline)
+            })
+            .collect();
+        FlatPattern {
+            mesh: PolygonMesh::new(positions, Vec::new(), Vec::new(), faces),
+            original_indices: sums.iter().map(|(_, original, _, _)| *original).collect(),
+            outlines,
+            max_length_distortion,
+            area_distortion,
+        }
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> ShapeFlattening for Face<Point3, C, S> {
+    #[inline(always)]
+    fn flat_pattern(&self, tol: f64) -> Option<FlatPattern> {
+        Shell::from(vec![self.clone()]).flat_pattern(tol)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> ShapeFlattening for Shell<Point3, C, S> {
+    fn flat_pattern(&self, tol: f64) -> Option<FlatPattern> {
+        let mut polygon = self.triangulation(tol)?.into_polygon();
+        polygon.put_together_same_attrs();
+        Some(polygon.flat_pattern())
+    }
+}
+
+/// Moves the points so that the distances between the ends of the edges approach their lengths.
+fn relax(points: &mut [Point2], edges: &[([usize; 2], f64)]) {
+    for _ in 0..RELAXATION_ITERATIONS {
+        let mut moves = vec![(Vector2::zero(), 0); points.len()];
+        for ([i, j], length) in edges {
+            let vec = points[*j] - points[*i];
+            let distance = vec.magnitude();
+            if distance < TOLERANCE {
+                continue;
+            }
+            let diff = vec * ((distance - length) / distance / 2.0);
+            moves[*i] = (moves[*i].0 + diff, moves[*i].1 + 1);
+            moves[*j] = (moves[*j].0 - diff, moves[*j].1 + 1);
+        }
+        points.iter_mut().zip(moves).for_each(|(pt, (vec, count))| {
+            if count > 0 {
+                *pt += vec / count as f64;
+            }
+        });
+    }
+}
+
+/// the third point of the triangle with the base from `p0` to `p1` in the counterclockwise order,
+/// with the lengths of the other sides `length1` from `p1` and `length0` to `p0`.
+fn third_point(p0: Point2, p1: Point2, length1: f64, length0: f64) -> Point2 {
+    let base = p1 - p0;
+    let len = base.magnitude();
+    if len < TOLERANCE {
+        return p0;
+    }
+    let dir = base / len;
+    let along = (len * len + length0 * length0 - length1 * length1) / (2.0 * len);
+    let perp = f64::sqrt(f64::max(length0 * length0 - along * along, 0.0));
+    p0 + dir * along + Vector2::new(-dir[1], dir[0]) * perp
+}
@@ -0,0 +1,464 @@
+use crate::tessellation::{MeshableSurface, Polyline, PolylineableCurve};
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use truck_topology::{Face, Shell, Solid};
+
+type PolylineCurve = truck_polymesh::PolylineCurve<Point3>;
+
+/// Returns the curvature comb of `curve`.
+///
+/// The comb consists of `division + 1` teeth at the uniformly divided parameters, and the envelope
+/// through the tips of the teeth as the last polyline. Each tooth is the segment from the point
+/// of the curve toward the opposite side of the center of curvature, whose length is `scale`
+/// times the curvature.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // the unit upper semicircle, whose curvature is 1
+/// let vertex0 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let vertex1 = builder::vertex(Point3::new(-1.0, 0.0, 0.0));
+/// let semi_circle = builder::circle_arc(&vertex0, &vertex1, Point3::new(0.0, 1.0, 0.0));
+///
+/// let comb = curvature_comb(&semi_circle.oriented_curve(), 8, 0.5);
+/// // 9 teeth and the envelope
+/// assert_eq!(comb.len(), 10);
+/// // the envelope is on the circle with the radius 1.5
+/// for pt in comb[9].iter() {
+///     assert!(pt.to_vec().magnitude().near(&1.5));
+/// }
+/// ```
+pub fn curvature_comb<C>(curve: &C, division: usize, scale: f64) -> Vec<PolylineCurve>
+where C: ParametricCurve<Point = Point3, Vector = Vector3> {
+    let (t0, t1) = curve.parameter_range();
+    let division = usize::max(division, 1);
+    let mut teeth: Vec<PolylineCurve> = (0..=division)
+        .map(|i| {
+            let t = t0 + (t1 - t0) * i as f64 / division as f64;
+            let pt = curve.subs(t);
+            PolylineCurve::from(vec![pt, pt - curvature_vector(curve, t) * scale])
+        })
+        .collect();
+    let envelope = teeth.iter().map(|tooth| tooth[1]).collect();
+    teeth.push(envelope);
+    teeth
+}
+
+/// Returns the iso-parameter curves of `surface` in `range`.
+///
+/// The `division.0 + 1` curves with constant `u` and the `division.1 + 1` curves with constant `v`
+/// are placed uniformly in `range`, including the boundaries. The curves are divided
+/// by `ParameterDivision2D` with the tolerance `tol`.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// let (p0, p1, p2) = (Point3::origin(), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+/// let plane = Plane::new(p0, p1, p2);
+/// let grid = iso_parameter_grid(&plane, ((0.0, 1.0), (0.0, 2.0)), (4, 8), 0.01);
+/// assert_eq!(grid.len(), 5 + 9);
+/// assert_eq!(grid[1][0], Point3::new(0.25, 0.0, 0.0));
+/// ```
+pub fn iso_parameter_grid<S>(
+    surface: &S,
+    range: ((f64, f64), (f64, f64)),
+    division: (usize, usize),
+    tol: f64,
+) -> Vec<PolylineCurve>
+where
+    S: ParametricSurface3D + ParameterDivision2D, {
+    let (udiv, vdiv) = surface.parameter_division(range, tol);
+    let (division0, division1) = (usize::max(division.0, 1), usize::max(division.1, 1));
+    let ((u0, u1), (v0, v1)) = range;
+    let ulines = (0..=division0).map(|i| {
+        let u = u0 + (u1 - u0) * i as f64 / division0 as f64;
+        vdiv.iter().map(|v| surface.subs(u, *v)).collect()
+    });
+    let vlines = (0..=division1).map(|i| {
+        let v = v0 + (v1 - v0) * i as f64 / division1 as f64;
+        udiv.iter().map(|u| surface.subs(*u, v)).collect()
+    });
+    ulines.chain(vlines).collect()
+}
+
+/// Generates the curves for inspecting the quality of the curves and surfaces of shapes.
+///
+/// The curves can be rendered by `WireFrameInstance` of `truck-rendimpl`.
+pub trait InspectionCurves {
+    /// Returns the curvature combs of all edges, by [`curvature_comb`].
+    /// The edges shared by faces appear only once.
+    ///
+    /// [`curvature_comb`]: ./fn.curvature_comb.html
+    fn curvature_combs(&self, division: usize, scale: f64) -> Vec<PolylineCurve>;
+    /// Returns the iso-parameter curves of all faces, trimmed by the boundaries of the faces.
+    ///
+    /// The curves are placed uniformly in the bounding box of the boundary in the parameter space
+    /// by the same way as [`iso_parameter_grid`], except the curves on the bounding box,
+    /// which are drawn by the edges. Returns `None` if the parameters of the boundaries
+    /// are not found on the surfaces.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the disk with radius 1
+    /// let vertex = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let wire = builder::rsweep(&vertex, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let disk = builder::try_attach_plane(&vec![wire]).unwrap();
+    ///
+    /// let grid = disk.iso_parameter_grids((4, 4), 0.01).unwrap();
+    /// // all curves are in the disk.
+    /// assert!(!grid.is_empty());
+    /// for pt in grid.iter().flat_map(|curve| curve.iter()) {
+    ///     assert!(pt.to_vec().magnitude() < 1.0 + 0.01);
+    /// }
+    /// ```
+    ///
+    /// [`iso_parameter_grid`]: ./fn.iso_parameter_grid.html
+    fn iso_parameter_grids(&self, division: (usize, usize), tol: f64)
+        -> Option<Vec<PolylineCurve>>;
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> InspectionCurves for Face<Point3, C, S> {
+    fn curvature_combs(&self, division: usize, scale: f64) -> Vec<PolylineCurve> {
+        let mut set = HashSet::new();
+        self.boundary_iters()
+            .into_iter()
+            .flatten()
+            .filter(|edge| set.insert(edge.id()))
+            .flat_map(|edge| curvature_comb(&*edge.read_curve(), division, scale))
+            .collect()
+    }
+    fn iso_parameter_grids(
+        &self,
+        division: (usize, usize),
+        tol: f64,
+    ) -> Option<Vec<PolylineCurve>> {
+        let surface = self.read_surface();
+        let mut polyline = Polyline::default();
+        let closed = self.absolute_boundaries().iter().all(|wire| {
+            let polylines = wire.edge_iter().map(|edge| {
+                let curve = edge.oriented_curve();
+                let mut params = curve.parameter_division(curve.parameter_range(), tol);
+                params.pop();
+                params.into_iter().map(|t| curve.subs(t)).collect()
+            });
+            polyline.add_closed_polylines(&*surface, polylines, tol).is_ok()
+        });
+        match closed {
+            true => Some(trimmed_iso_parameter_grid(
+                &*surface, &polyline, division, tol,
+            )),
+            false => None,
+        }
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> InspectionCurves for Shell<Point3, C, S> {
+    fn curvature_combs(&self, division: usize, scale: f64) -> Vec<PolylineCurve> {
+        let mut set = HashSet::new();
+        self.edge_iter()
+            .filter(|edge| set.insert(edge.id()))
+            .flat_map(|edge| curvature_comb(&*edge.read_curve(), division, scale))
+            .collect()
+    }
+    fn iso_parameter_grids(
+        &self,
+        division: (usize, usize),
+        tol: f64,
+    ) -> Option<Vec<PolylineCurve>> {
+        let grids = self
+            .face_iter()
+            .map(|face| face.iso_parameter_grids(division, tol))
+            .collect::<Option<Vec<_>>>()?;
+        Some(grids.into_iter().flatten().collect())
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> InspectionCurves for Solid<Point3, C, S> {
+    fn curvature_combs(&self, division: usize, scale: f64) -> Vec<PolylineCurve> {
+        let mut set = HashSet::new();
+        self.edge_iter()
+            .filter(|edge| set.insert(edge.id()))
+            .flat_map(|edge| curvature_comb(&*edge.read_curve(), division, scale))
+            .collect()
+    }
+    fn iso_parameter_grids(
+        &self,
+        division: (usize, usize),
+        tol: f64,
+    ) -> Option<Vec<PolylineCurve>> {
+        let grids = self
+            .boundaries()
+            .iter()
+            .map(|shell| shell.iso_parameter_grids(division, tol))
+            .collect::<Option<Vec<_>>>()?;
+        Some(grids.into_iter().flatten().collect())
+    }
+}
+
+/// The continuity across the shared edges of two faces, the maxima at the sample points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeContinuity {
+    /// the maximum distance between the points of the two surfaces, for G0 continuity
+    pub max_gap: f64,
+    /// the maximum angle between the oriented normals of the two surfaces, for G1 continuity
+    pub max_normal_angle: Rad<f64>,
+    /// the maximum difference of the normal curvatures of the two surfaces in the directions
+    /// across the edge, for G2 continuity
+    pub max_curvature_jump: f64,
+}
+
+/// Measures the continuity between `face0` and `face1` across their shared edges.
+///
+/// Each shared edge is sampled at the `division + 1` uniformly divided parameters, and
+/// the parameters of the sample points are searched on both surfaces. The normals are oriented
+/// by the orientations of the faces. The normal curvature of each surface is taken in the
+/// direction perpendicular to the edge on the surface.
+///
+/// Returns `None` if the faces share no edges or the parameters are not found.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let shell = &cube.boundaries()[0];
+/// let continuity = edge_continuity(&shell[0], &shell[2], 4).unwrap();
+/// // the faces of the cube meet at the right angle
+/// assert!(continuity.max_gap.so_small());
+/// assert!(continuity.max_normal_angle.0.near(&(std::f64::consts::PI / 2.0)));
+/// // the bottom and the top share no edges
+/// assert!(edge_continuity(&shell[0], &shell[5], 4).is_none());
+/// ```
+pub fn edge_continuity<C, S>(
+    face0: &Face<Point3, C, S>,
+    face1: &Face<Point3, C, S>,
+    division: usize,
+) -> Option<EdgeContinuity>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    let ids: HashSet<_> = face0
+        .boundary_iters()
+        .into_iter()
+        .flatten()
+        .map(|edge| edge.id())
+        .collect();
+    let mut set = HashSet::new();
+    let curves: Vec<C> = face1
+        .boundary_iters()
+        .into_iter()
+        .flatten()
+        .filter(|edge| ids.contains(&edge.id()) && set.insert(edge.id()))
+        .map(|edge| edge.get_curve())
+        .collect();
+    if curves.is_empty() {
+        return None;
+    }
+    let surface0 = (face0.get_surface(), face0.orientation());
+    let surface1 = (face1.get_surface(), face1.orientation());
+    let division = usize::max(division, 1);
+    let mut res = EdgeContinuity {
+        max_gap: 0.0,
+        max_normal_angle: Rad(0.0),
+        max_curvature_jump: 0.0,
+    };
+    for curve in &curves {
+        let (t0, t1) = curve.parameter_range();
+        let (mut hint0, mut hint1) = (None, None);
+        for i in 0..=division {
+            let t = t0 + (t1 - t0) * i as f64 / division as f64;
+            let (pt, tangent) = (curve.subs(t), curve.der(t));
+            let uv0 = surface0.0.search_parameter(pt, hint0, 100)?;
+            let uv1 = surface1.0.search_parameter(pt, hint1, 100)?;
+            let (pt0, normal0, curvature0) = surface_property(&surface0, uv0, tangent);
+            let (pt1, normal1, curvature1) = surface_property(&surface1, uv1, tangent);
+            let angle = Rad(f64::acos(normal0.dot(normal1).clamp(-1.0, 1.0)));
+            res.max_gap = f64::max(res.max_gap, pt0.distance(pt1));
+            res.max_normal_angle = Rad(f64::max(res.max_normal_angle.0, angle.0));
+            let jump = f64::abs(curvature0 - curvature1);
+            res.max_curvature_jump = f64::max(res.max_curvature_jump, jump);
+            hint0 = Some(uv0);
+            hint1 = Some(uv1);
+        }
+    }
+    Some(res)
+}
+
+/// Analyzes the continuity between the adjacent faces of shells.
+pub trait EdgeContinuityAnalysis {
+    /// Returns the continuities by [`edge_continuity`] of all pairs of the faces sharing edges,
+    /// with the indices of the faces in the order of `face_iter`.
+    /// Returns `None` if the parameters of some edges are not found on the surfaces.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// // the pairs of the faces sharing the 12 edges
+    /// let continuities = cube.boundaries()[0].edge_continuities(4).unwrap();
+    /// assert_eq!(continuities.len(), 12);
+    /// for (_, continuity) in &continuities {
+    ///     assert!(continuity.max_normal_angle.0.near(&(std::f64::consts::PI / 2.0)));
+    /// }
+    /// ```
+    ///
+    /// [`edge_continuity`]: ./fn.edge_continuity.html
+    fn edge_continuities(&self, division: usize) -> Option<Vec<((usize, usize), EdgeContinuity)>>;
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> EdgeContinuityAnalysis for Shell<Point3, C, S> {
+    fn edge_continuities(&self, division: usize) -> Option<Vec<((usize, usize), EdgeContinuity)>> {
+        let mut map = HashMap::new();
+        let mut pairs = Vec::new();
+        for (i, face) in self.face_iter().enumerate() {
+            let mut set = HashSet::new();
+            for edge in face.boundary_iters().into_iter().flatten() {
+                let entry = map.entry(edge.id()).or_insert_with(Vec::new);
+                if set.insert(edge.id()) {
+                    for j in entry.iter() {
+                        if !pairs.contains(&(*j, i)) {
+                            pairs.push((*j, i));
+                        }
+                    }
+                    entry.push(i);
+                }
+            }
+        }
+        pairs
+            .into_iter()
+            .map(|(i, j)| Some(((i, j), edge_continuity(&self[i], &self[j], division)?)))
+            .collect()
+    }
+}
+
+/// the point, the oriented normal, and the normal curvature in the direction across `tangent`
+fn surface_property<S: MeshableSurface>(
+    surface: &(S, bool),
+    (u, v): (f64, f64),
+    tangent: Vector3,
+) -> (Point3, Vector3, f64) {
+    let (surface, orientation) = surface;
+    let normal = match orientation {
+        true => surface.normal(u, v),
+        false => -surface.normal(u, v),
+    };
+    let direction = normal.cross(tangent);
+    (surface.subs(u, v), normal, normal_curvature(surface, (u, v), normal, direction))
+}
+
+/// the normal curvature of `surface` in `direction` with respect to `normal`
+fn normal_curvature<S: MeshableSurface>(
+    surface: &S,
+    (u, v): (f64, f64),
+    normal: Vector3,
+    direction: Vector3,
+) -> f64 {
+    let (uder, vder) = (surface.uder(u, v), surface.vder(u, v));
+    let (e, f, g) = (uder.dot(uder), uder.dot(vder), vder.dot(vder));
+    let det = e * g - f * f;
+    if det.abs() < TOLERANCE2 {
+        return 0.0;
+    }
+    let (du, dv) = (uder.dot(direction), vder.dot(direction));
+    let (a, b) = ((g * du - f * dv) / det, (e * dv - f * du) / det);
+    let der = uder * a + vder * b;
+    if der.magnitude2() < TOLERANCE2 {
+        return 0.0;
+    }
+    let der2 = surface.uuder(u, v) * (a * a)
+        + surface.uvder(u, v) * (2.0 * a * b)
+        + surface.vvder(u, v) * (b * b);
+    der2.dot(normal) / der.magnitude2()
+}
+
+/// the curvature vector, whose direction is toward the center of curvature.
+fn curvature_vector<C>(curve: &C, t: f64) -> Vector3
+where C: ParametricCurve<Point = Point3, Vector = Vector3> {
+    let der = curve.der(t);
+    let der2 = curve.der2(t);
+    let mag2 = der.magnitude2();
+    match mag2 < TOLERANCE2 {
+        true => Vector3::zero(),
+        false => (der2 - der * (der.dot(der2) / mag2)) / mag2,
+    }
+}
+
+/// The iso-parameter curves trimmed by `polyline` in the parameter space.
+fn trimmed_iso_parameter_grid<S>(
+    surface: &S,
+    polyline: &Polyline,
+    division: (usize, usize),
+    tol: f64,
+) -> Vec<PolylineCurve>
+where S: MeshableSurface {
+    let range = polyline.parameter_range();
+    let (udiv, vdiv) = surface.parameter_division(range, tol);
+    let (division0, division1) = (usize::max(division.0, 1), usize::max(division.1, 1));
+    let ((u0, u1), (v0, v1)) = range;
+    let ulines = (1..division0).flat_map(|i| {
+        let u = u0 + (u1 - u0) * i as f64 / division0 as f64;
+        trimmed_iso_line(polyline, 0, u, &vdiv)
+    });
+    let vlines = (1..division1).flat_map(|i| {
+        let v = v0 + (v1 - v0) * i as f64 / division1 as f64;
+        trimmed_iso_line(polyline, 1, v, &udiv)
+    });
+    ulines
+        .chain(vlines)
+        .map(|line| {
+            line.into_iter()
+                .map(|uv| surface.subs(uv[0], uv[1]))
+                .collect()
+        })
+        .collect()
+}
+
+/// The parts of the iso-parameter line `uv[axis] == value` included in the domain with
+/// the boundary `polyline`, divided by `params`.
+fn trimmed_iso_line(
+    polyline: &Polyline,
+    axis: usize,
+    value: f64,
+    params: &[f64],
+) -> Vec<Vec<Point2>> {
+    let other = 1 - axis;
+    let mut crossings: Vec<f64> = polyline
+        .segments()
+        .filter(|(a, b)| (a[axis] <= value) != (b[axis] <= value))
+        .map(|(a, b)| {
+            let t = (value - a[axis]) / (b[axis] - a[axis]);
+            a[other] + (b[other] - a[other]) * t
+        })
+        .collect();
+    crossings.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    let to_point = |t: f64| {
+        let mut pt = Point2::new(value, value);
+        pt[other] = t;
+        pt
+    };
+    crossings
+        .chunks_exact(2)
+        .map(|pair| {
+            let inner = params.iter().filter(|t| pair[0] < **t && **t < pair[1]);
+            std::iter::once(pair[0])
+                .chain(inner.copied())
+                .chain(std::iter::once(pair[1]))
+                .map(to_point)
+                .collect()
+        })
+        .collect()
+}
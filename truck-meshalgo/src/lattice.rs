@@ -0,0 +1,314 @@
+use crate::analyzers::MeshBVH;
+use crate::tessellation::{MeshableShape, MeshableSurface, MeshedShape, PolylineableCurve};
+use crate::*;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use truck_polymesh::progress::{Progress, ProgressCounter};
+use truck_topology::Solid;
+
+/// The kinds of periodic lattice structures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LatticeType {
+    /// sheet of the gyroid, a triply periodic minimal surface
+    Gyroid,
+    /// sheet of the Schwarz P surface, a triply periodic minimal surface
+    SchwarzP,
+    /// sheet of the Schwarz D (diamond) surface, a triply periodic minimal surface
+    Diamond,
+    /// struts along the edges of cubic cells
+    CubicStrut,
+}
+
+/// Configures of lattice infills.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatticeDescriptor {
+    /// the type of lattice. Default is `LatticeType::Gyroid`.
+    pub lattice_type: LatticeType,
+    /// the length of the edges of the unit cells. Default is `1.0`.
+    pub cell_size: f64,
+    /// the thickness of sheets, or the diameter of struts. Default is `0.1`.
+    pub thickness: f64,
+    /// the thickness of the outer skin along the boundary. No skin if `0.0`. Default is `0.0`.
+    pub skin: f64,
+    /// the number of samples along each edge of the unit cells. Default is `8`.
+    pub resolution: usize,
+}
+
+impl Default for LatticeDescriptor {
+    #[inline(always)]
+    fn default() -> LatticeDescriptor {
+        LatticeDescriptor {
+            lattice_type: LatticeType::Gyroid,
+            cell_size: 1.0,
+            thickness: 0.1,
+            skin: 0.0,
+            resolution: 8,
+        }
+    }
+}
+
+/// Fills closed shapes with periodic lattice structures.
+pub trait LatticeInfill {
+    /// Returns the closed triangle mesh of the lattice clipped by `self`.
+    ///
+    /// The iso-surface of the signed distance field is extracted by marching tetrahedra,
+    /// so the output has no normals. Use `NormalFilters` if necessary.
+    /// Returns the empty mesh if `cell_size` is not positive and finite, or `resolution` is zero.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    /// use truck_topology::shell::ShellCondition;
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// let descriptor = LatticeDescriptor {
+    ///     cell_size: 0.5,
+    ///     thickness: 0.1,
+    ///     resolution: 6,
+    ///     ..Default::default()
+    /// };
+    /// let lattice = cube.lattice_infill(&descriptor);
+    /// assert_eq!(lattice.shell_condition(), ShellCondition::Closed);
+    ///
+    /// let descriptor = LatticeDescriptor {
+    ///     cell_size: f64::NAN,
+    ///     ..descriptor
+    /// };
+    /// assert!(cube.lattice_infill(&descriptor).positions().is_empty());
+    /// ```
+    fn lattice_infill(&self, descriptor: &LatticeDescriptor) -> PolygonMesh;
+}
+
+impl LatticeDescriptor {
+    /// whether the grid of the samples is defined
+    fn is_valid(&self) -> bool {
+        self.cell_size.is_finite() && self.cell_size > 0.0 && self.resolution > 0
+    }
+}
+
+impl LatticeInfill for PolygonMesh {
+    fn lattice_infill(&self, descriptor: &LatticeDescriptor) -> PolygonMesh {
+        let bvh = MeshBVH::new(self);
+        let skin = descriptor.skin;
+        let field = |p: Point3| {
+            let lattice = lattice_distance(descriptor, p);
+            let mesh = match bvh.contains(p) {
+                true => -bvh.distance(p),
+                false => bvh.distance(p),
+            };
+            f64::max(mesh, f64::min(lattice, -mesh - skin))
+        };
+        let bdb = bvh.bounding_box();
+        if bdb.is_empty() || !descriptor.is_valid() {
+            return PolygonMesh::default();
+        }
+        let spacing = descriptor.cell_size / descriptor.resolution as f64;
+        marching_tetrahedra(field, *bdb.min(), *bdb.max(), spacing)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> LatticeInfill for Solid<Point3, C, S> {
+    /// The solid is tessellated with the tolerance of a quarter of the sampling spacing.
+    fn lattice_infill(&self, descriptor: &LatticeDescriptor) -> PolygonMesh {
+        if !descriptor.is_valid() {
+            return PolygonMesh::default();
+        }
+        let tol = descriptor.cell_size / descriptor.resolution as f64 / 4.0;
+        match self.triangulation(tol) {
+            Some(meshed) => meshed.into_polygon().lattice_infill(descriptor),
+            None => PolygonMesh::default(),
+        }
+    }
+}
+
+/// Approximated signed distance from the surface of the lattice. Negative inside.
+fn lattice_distance(descriptor: &LatticeDescriptor, p: Point3) -> f64 {
+    let half = descriptor.thickness / 2.0;
+    let k = 2.0 * PI / descriptor.cell_size;
+    let (x, y, z) = (k * p[0], k * p[1], k * p[2]);
+    let (sx, sy, sz) = (x.sin(), y.sin(), z.sin());
+    let (cx, cy, cz) = (x.cos(), y.cos(), z.cos());
+    // the distance from the level set is approximated by |f| / |grad f|.
+    let (f, grad) = match descriptor.lattice_type {
+        LatticeType::Gyroid => (
+            sx * cy + sy * cz + sz * cx,
+            Vector3::new(cx * cy - sz * sx, cy * cz - sx * sy, cz * cx - sy * sz),
+        ),
+        LatticeType::SchwarzP => (cx + cy + cz, Vector3::new(-sx, -sy, -sz)),
+        LatticeType::Diamond => (
+            sx * sy * sz + sx * cy * cz + cx * sy * cz + cx * cy * sz,
+            Vector3::new(
+                cx * sy * sz + cx * cy * cz - sx * sy * cz - sx * cy * sz,
+                sx * cy * sz - sx * sy * cz + cx * cy * cz - cx * sy * sz,
+                sx * sy * cz - sx * cy * sz - cx * sy * sz + cx * cy * cz,
+            ),
+        ),
+        LatticeType::CubicStrut => {
+            let l = descriptor.cell_size;
+            let q = p.to_vec().map(|t| t - l * (t / l).round());
+            let dist = f64::min(
+                f64::min(q[1].hypot(q[2]), q[2].hypot(q[0])),
+                q[0].hypot(q[1]),
+            );
+            return dist - half;
+        }
+    };
+    let grad = k * grad.magnitude();
+    f.abs() / f64::max(grad, k * 1.0e-3) - half
+}
+
+/// the Kuhn triangulation of the unit cube, conforming between adjacent cubes.
+const TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 5, 1, 6],
+    [0, 1, 2, 6],
+    [0, 2, 3, 6],
+    [0, 3, 7, 6],
+    [0, 7, 4, 6],
+    [0, 4, 5, 6],
+];
+const CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [1, 1, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [1, 1, 1],
+    [0, 1, 1],
+];
+
+/// Extracts the closed iso-surface `field < 0` in the box expanded by one `spacing`.
+pub(crate) fn marching_tetrahedra(
+    field: impl Fn(Point3) -> f64,
+    min: Point3,
+    max: Point3,
+    spacing: f64,
+) -> PolygonMesh {
+    marching_tetrahedra_with_progress(field, min, max, spacing, |_, _| true).unwrap_or_default()
+}
+
+/// Extracts the iso-surface like `marching_tetrahedra`, reporting the number of the sampled
+/// nodes of the grid to `progress`.
+pub(crate) fn marching_tetrahedra_with_progress<P: Progress>(
+    field: impl Fn(Point3) -> f64,
+    min: Point3,
+    max: Point3,
+    spacing: f64,
+    progress: P,
+) -> Result<PolygonMesh> {
+    let origin = min - Vector3::new(spacing, spacing, spacing);
+    let size: Vec<usize> = (0..3)
+        .map(|i| ((max[i] - min[i]) / spacing).ceil() as usize + 3)
+        .collect();
+    let (nx, ny, nz) = (size[0], size[1], size[2]);
+    let mut counter = ProgressCounter::new(nx * ny * nz, progress);
+    let values = (0..nx * ny * nz)
+        .map(|idx| {
+            counter.try_advance(1)?;
+            let (i, j, k) = (idx % nx, idx / nx % ny, idx / (nx * ny));
+            // The boundary nodes are forced outside for closing the surface.
+            Ok(
+                match i == 0 || j == 0 || k == 0 || i + 1 == nx || j + 1 == ny || k + 1 == nz {
+                    true => spacing,
+                    false => field(origin + spacing * Vector3::new(i as f64, j as f64, k as f64)),
+                },
+            )
+        })
+        .collect::<Result<Vec<f64>>>()?;
+    Ok(marching_tetrahedra_on_grid(&values, [nx, ny, nz], origin, spacing))
+}
+
+/// Extracts the iso-surface `value < 0` of `values` on the nodes of the grid with `size`,
+/// whose `(i, j, k)`-th node is at `origin + spacing * (i, j, k)` and has the value
+/// `values[(k * ny + j) * nx + i]`. The surface is closed if the boundary nodes are outside.
+pub(crate) fn marching_tetrahedra_on_grid(
+    values: &[f64],
+    size: [usize; 3],
+    origin: Point3,
+    spacing: f64,
+) -> PolygonMesh {
+    let [nx, ny, nz] = size;
+    if nx < 2 || ny < 2 || nz < 2 {
+        return PolygonMesh::default();
+    }
+    let node_index = |i: usize, j: usize, k: usize| (k * ny + j) * nx + i;
+    let node_position = |idx: usize| {
+        let (i, j, k) = (idx % nx, idx / nx % ny, idx / (nx * ny));
+        origin + spacing * Vector3::new(i as f64, j as f64, k as f64)
+    };
+
+    let mut positions = Vec::new();
+    let mut edge_points: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut faces = Faces::default();
+    for k in 0..nz - 1 {
+        for j in 0..ny - 1 {
+            for i in 0..nx - 1 {
+                let cube = CORNERS.map(|c| node_index(i + c[0], j + c[1], k + c[2]));
+                for tet in &TETRAHEDRA {
+                    let nodes = tet.map(|idx| cube[idx]);
+                    let (inside, outside): (Vec<usize>, Vec<usize>) =
+                        nodes.iter().partition(|idx| values[**idx] < 0.0);
+                    // triangles whose vertices are on the edges (inside node, outside node)
+                    let triangles = match (inside.len(), outside.len()) {
+                        (1, 3) => vec![[
+                            (inside[0], outside[0]),
+                            (inside[0], outside[1]),
+                            (inside[0], outside[2]),
+                        ]],
+                        (3, 1) => vec![[
+                            (inside[0], outside[0]),
+                            (inside[1], outside[0]),
+                            (inside[2], outside[0]),
+                        ]],
+                        (2, 2) => vec![
+                            [
+                                (inside[0], outside[0]),
+                                (inside[0], outside[1]),
+                                (inside[1], outside[1]),
+                            ],
+                            [
+                                (inside[0], outside[0]),
+                                (inside[1], outside[1]),
+                                (inside[1], outside[0]),
+                            ],
+                        ],
+                        _ => continue,
+                    };
+                    let center = |nodes: &[usize]| {
+                        let sum = nodes.iter().fold(Vector3::zero(), |sum, idx| {
+                            sum + node_position(*idx).to_vec()
+                        });
+                        sum / nodes.len() as f64
+                    };
+                    let outer = center(&outside) - center(&inside);
+                    for mut tri in triangles {
+                        // The orientation is decided by the midpoints of edges,
+                        // since the interpolated points may degenerate.
+                        let mid = |(a, b): (usize, usize)| {
+                            (node_position(a).to_vec() + node_position(b).to_vec()) / 2.0
+                        };
+                        let (p0, p1, p2) = (mid(tri[0]), mid(tri[1]), mid(tri[2]));
+                        if (p1 - p0).cross(p2 - p0).dot(outer) < 0.0 {
+                            tri.swap(1, 2);
+                        }
+                        let face = tri.map(|(a, b)| {
+                            *edge_points.entry((a, b)).or_insert_with(|| {
+                                let (pa, pb) = (node_position(a), node_position(b));
+                                let (va, vb) = (values[a], values[b]);
+                                let t = f64::clamp(va / (va - vb), 0.0, 1.0);
+                                positions.push(pa + t * (pb - pa));
+                                positions.len() - 1
+                            })
+                        });
+                        faces.push(face);
+                    }
+                }
+            }
+        }
+    }
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
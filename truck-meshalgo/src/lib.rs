@@ -19,17 +19,77 @@ use truck_polymesh::*;
 /// - determines topological properties: connectivity, boundary extraction, or shell conditions (colsed or oriented)
 /// - detects collisions between two meshes and extracts interference lines
 /// - investigates positional relations between mesh and point clouds.
+/// - casts rays to meshes by the bounding volume hierarchy.
+/// - classifies faces by the draft angles for molding and casting.
+/// - extracts parting lines between the regions of positive and negative draft angles.
+/// - extracts curve skeletons of closed meshes.
+/// - detects planar reflective and rotational symmetries.
+/// - extracts feature edges: boundaries and sharp edges.
+/// - computes the visibilities of faces from the pull directions for parting molds.
 pub mod analyzers;
+/// Bakes surface attributes into images over the uv layout of meshes.
+pub mod baking;
+/// Boolean operations of closed polygon meshes: union, intersection and difference.
+pub mod boolean;
+/// Clips meshes by planes and boxes for sectioning.
+pub mod clipping;
 mod common;
+/// Measures the distances between points and shapes on the exact curves and surfaces.
+pub mod distance;
 /// Edits meshes. Add normals, optimizing data, and so on.
 pub mod filters;
+/// Fits B-spline surfaces to structured meshes.
+pub mod fitting;
+/// Unrolls developable faces and meshes into flat patterns with the distortions.
+pub mod flattening;
+/// Generates curves for inspecting the quality of curves and surfaces:
+/// curvature combs and iso-parameter grids, and the continuity across the edges between faces.
+pub mod inspection;
+/// Fills closed meshes and solids with periodic lattice structures.
+pub mod lattice;
+/// Arranges parts in build volumes without overlaps.
+pub mod packing;
+/// Encodes meshes into progressive meshes, the sequences of vertex splits, for streaming.
+pub mod progressive;
+/// Estimates normals of point clouds and reconstructs surfaces from them.
+pub mod reconstruction;
+/// Repairs polygon soups into closed manifold meshes by the generalized winding numbers.
+pub mod repair;
+/// Samples points with approximately uniform density on meshes and the exact faces of shapes.
+pub mod sampling;
+/// Casts rays to the exact faces of shapes, and classifies points by solids.
+pub mod shape_ray_casting;
+/// Generates support structures for 3D printing.
+pub mod support;
 /// Tessellates shapes.
 pub mod tessellation;
+/// Analyzes the wall thickness of solids on the exact surfaces.
+pub mod thickness;
+/// Voxelizes meshes into the grids of the signed distances, for remeshing, offsetting and
+/// boolean operations in voxel space.
+pub mod voxelization;
 
 /// This module contains all traits and re-exports `truck_polymesh`.
 pub mod prelude {
     pub use crate::analyzers::*;
+    pub use crate::baking::*;
+    pub use crate::boolean::*;
+    pub use crate::clipping::*;
+    pub use crate::distance::*;
     pub use crate::filters::*;
+    pub use crate::fitting::*;
+    pub use crate::flattening::*;
+    pub use crate::inspection::*;
+    pub use crate::lattice::*;
+    pub use crate::packing::*;
+    pub use crate::progressive::*;
+    pub use crate::reconstruction::*;
+    pub use crate::repair::*;
+    pub use crate::sampling::*;
+    pub use crate::shape_ray_casting::*;
+    pub use crate::support::*;
     pub use crate::tessellation::*;
+    pub use crate::thickness::*;
+    pub use crate::voxelization::*;
     pub use truck_polymesh::*;
 }
@@ -0,0 +1,174 @@
+use crate::*;
+
+/// Configures of packing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PackingDescriptor {
+    /// the size of the build volume, the box from the origin to `build_volume`.
+    pub build_volume: Vector3,
+    /// the minimum gap between the parts.
+    pub spacing: f64,
+    /// whether the parts may be rotated by 90 degrees around the z axis or not.
+    pub allow_rotation: bool,
+}
+
+/// Arranges the bounding boxes of parts in the build volume without overlaps.
+///
+/// The boxes are put on shelves: the boxes are lined up along the x axis in rows, the rows are
+/// lined up along the y axis in layers, and the layers are stacked along the z axis.
+/// The taller boxes are placed earlier. Returns the placement transforms in the order of `boxes`,
+/// and `None` for the boxes which cannot be placed in the build volume.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// let bdb = |min: Point3, max: Point3| [min, max].iter().collect::<BoundingBox<Point3>>();
+/// let boxes = vec![
+///     bdb(Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 4.0, 1.0)),
+///     bdb(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0)),
+///     bdb(Point3::new(0.0, 0.0, 0.0), Point3::new(20.0, 1.0, 1.0)),
+/// ];
+/// let descriptor = PackingDescriptor {
+///     build_volume: Vector3::new(10.0, 10.0, 10.0),
+///     spacing: 1.0,
+///     allow_rotation: false,
+/// };
+/// let placements = pack_bounding_boxes(&boxes, &descriptor);
+/// // the tallest box is placed at the corner.
+/// let placed = placements[1].unwrap().transform_point(Point3::new(-1.0, -1.0, -1.0));
+/// assert!(placed.near(&Point3::origin()));
+/// // the row of the tallest box is not deep enough, so the next row is started with spacing.
+/// let placed = placements[0].unwrap().transform_point(Point3::origin());
+/// assert!(placed.near(&Point3::new(0.0, 3.0, 0.0)));
+/// // too long for the build volume
+/// assert!(placements[2].is_none());
+/// ```
+pub fn pack_bounding_boxes(
+    boxes: &[BoundingBox<Point3>],
+    descriptor: &PackingDescriptor,
+) -> Vec<Option<Matrix4>> {
+    let spacing = Vector3::new(descriptor.spacing, descriptor.spacing, descriptor.spacing);
+    let volume = descriptor.build_volume + spacing;
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    let sizes: Vec<Vector3> = boxes
+        .iter()
+        .map(|bdb| match bdb.is_empty() {
+            true => Vector3::zero(),
+            false => bdb.diagonal() + spacing,
+        })
+        .collect();
+    order.sort_by(|i, j| {
+        let (a, b) = (sizes[*i], sizes[*j]);
+        (b[2], b[0] * b[1])
+            .partial_cmp(&(a[2], a[0] * a[1]))
+            .unwrap()
+    });
+    let mut shelves = Shelves::default();
+    let mut placements = vec![None; boxes.len()];
+    for i in order {
+        if boxes[i].is_empty() {
+            continue;
+        }
+        let size = sizes[i];
+        let rotated = Vector3::new(size[1], size[0], size[2]);
+        let position = shelves
+            .place(size, volume)
+            .map(|pos| (pos, false))
+            .or_else(|| match descriptor.allow_rotation {
+                true => shelves.place(rotated, volume).map(|pos| (pos, true)),
+                false => None,
+            });
+        placements[i] = position.map(|(pos, rotate)| {
+            let min = boxes[i].min().to_vec();
+            match rotate {
+                true => {
+                    let offset = Vector3::new(size[1] - descriptor.spacing, 0.0, 0.0);
+                    Matrix4::from_translation(pos + offset)
+                        * Matrix4::from_angle_z(Deg(90.0))
+                        * Matrix4::from_translation(-min)
+                }
+                false => Matrix4::from_translation(pos - min),
+            }
+        });
+    }
+    placements
+}
+
+/// Arranges the polygon meshes in the build volume without overlaps of their bounding boxes,
+/// by [`pack_bounding_boxes`].
+///
+/// [`pack_bounding_boxes`]: ./fn.pack_bounding_boxes.html
+#[inline(always)]
+pub fn pack_meshes(meshes: &[PolygonMesh], descriptor: &PackingDescriptor) -> Vec<Option<Matrix4>> {
+    let boxes: Vec<_> = meshes.iter().map(PolygonMesh::bounding_box).collect();
+    pack_bounding_boxes(&boxes, descriptor)
+}
+
+#[derive(Clone, Debug, Default)]
+struct Shelves {
+    layers: Vec<Layer>,
+    height: f64,
+}
+
+#[derive(Clone, Debug)]
+struct Layer {
+    z: f64,
+    height: f64,
+    rows: Vec<Row>,
+    depth: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Row {
+    y: f64,
+    depth: f64,
+    width: f64,
+}
+
+impl Shelves {
+    /// Returns the position of the box with `size`, or `None` if there is no room.
+    fn place(&mut self, size: Vector3, volume: Vector3) -> Option<Vector3> {
+        if size[0] > volume[0] || size[1] > volume[1] || size[2] > volume[2] {
+            return None;
+        }
+        for layer in self
+            .layers
+            .iter_mut()
+            .filter(|layer| size[2] <= layer.height)
+        {
+            let row = layer
+                .rows
+                .iter_mut()
+                .find(|row| size[1] <= row.depth && row.width + size[0] <= volume[0]);
+            if let Some(row) = row {
+                let pos = Vector3::new(row.width, row.y, layer.z);
+                row.width += size[0];
+                return Some(pos);
+            }
+            if layer.depth + size[1] <= volume[1] {
+                let pos = Vector3::new(0.0, layer.depth, layer.z);
+                layer.rows.push(Row {
+                    y: layer.depth,
+                    depth: size[1],
+                    width: size[0],
+                });
+                layer.depth += size[1];
+                return Some(pos);
+            }
+        }
+        if self.height + size[2] <= volume[2] {
+            let pos = Vector3::new(0.0, 0.0, self.height);
+            self.layers.push(Layer {
+                z: self.height,
+                height: size[2],
+                rows: vec![Row {
+                    y: 0.0,
+                    depth: size[1],
+                    width: size[0],
+                }],
+                depth: size[1],
+            });
+            self.height += size[2];
+            return Some(pos);
+        }
+        None
+    }
+}
@@ -0,0 +1,683 @@
+use crate::filters::{DecimationDescriptor, OptimizingFilter};
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use truck_polymesh::progress::{Progress, ProgressCounter};
+
+// the weight of the quadrics of the boundary edges relative to those of the faces
+const BOUNDARY_WEIGHT: f64 = 100.0;
+
+/// The record restoring an edge collapse: the vertex `vertex` is split into itself and
+/// a new vertex at `position`.
+///
+/// The new vertex has the index next to the last vertex, and the new faces are pushed
+/// after the last face.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VertexSplit {
+    /// the index of the vertex to be split
+    pub vertex: usize,
+    /// the position of the new vertex
+    pub position: Point3,
+    /// the indices of the faces whose corners `vertex` are replaced with the new vertex
+    pub moved_faces: Vec<usize>,
+    /// the faces added by the split, the triangles of the indices of the vertices
+    pub new_faces: Vec<[usize; 3]>,
+}
+
+/// Progressive mesh: the base mesh and the sequence of vertex splits.
+///
+/// The base mesh is the result of the edge collapses from the original mesh,
+/// and applying all the splits to the base mesh reconstructs the triangles of the original mesh.
+/// Any intermediate level of detail is reconstructed by applying the first splits, so the mesh
+/// can be streamed by sending the base mesh first and the splits one by one.
+/// Only the positions are encoded, so the normals should be added to decoded meshes by
+/// [`NormalFilters`].
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+///
+/// // the square divided into 8 x 8 cells
+/// let positions = (0..81)
+///     .map(|i| Point3::new((i / 9) as f64, (i % 9) as f64, 0.0))
+///     .collect();
+/// let faces = Faces::from_iter((0..64).map(|i| {
+///     let idx = i / 8 * 9 + i % 8;
+///     [idx, idx + 9, idx + 10, idx + 1]
+/// }));
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+/// // the number of the triangles
+/// let faces = 128;
+///
+/// let progressive = ProgressiveMesh::new(&mesh, 40);
+/// assert!(progressive.base_faces().len() <= 40);
+/// // the intermediate level of detail
+/// let lod = progressive.polygon_mesh(progressive.len() / 2);
+/// assert!(40 < lod.faces().len() && lod.faces().len() < faces);
+/// // the original mesh
+/// let decoded = progressive.polygon_mesh(progressive.len());
+/// assert_eq!(decoded.positions().len(), 81);
+/// assert_eq!(decoded.faces().len(), faces);
+/// ```
+///
+/// [`NormalFilters`]: ./filters/trait.NormalFilters.html
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProgressiveMesh {
+    base_positions: Vec<Point3>,
+    base_faces: Vec<[usize; 3]>,
+    splits: Vec<VertexSplit>,
+}
+
+/// Decoder of progressive meshes, applying the vertex splits one by one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgressiveMeshDecoder {
+    positions: Vec<Point3>,
+    faces: Vec<[usize; 3]>,
+}
+
+impl ProgressiveMesh {
+    /// Encodes `mesh` into the progressive mesh by collapsing edges until the number of
+    /// the triangles is not greater than `min_faces` or no edge can be collapsed.
+    ///
+    /// The polygons are divided into triangles, and the faces are connected by the indices of
+    /// the positions, so the same positions should be put together in advance. The edges are
+    /// collapsed in the ascending order of the quadric errors, keeping the vertices on
+    /// the boundaries, the manifoldness and the orientations of the triangles.
+    pub fn new(mesh: &PolygonMesh, min_faces: usize) -> ProgressiveMesh {
+        let mut encoder = Encoder::new(mesh);
+        encoder.collapse_edges(min_faces);
+        encoder.into_progressive_mesh()
+    }
+    /// Returns the positions of the base mesh.
+    #[inline(always)]
+    pub fn base_positions(&self) -> &[Point3] {
+        &self.base_positions
+    }
+    /// Returns the triangles of the base mesh.
+    #[inline(always)]
+    pub fn base_faces(&self) -> &[[usize; 3]] {
+        &self.base_faces
+    }
+    /// Returns the vertex splits.
+    #[inline(always)]
+    pub fn splits(&self) -> &[VertexSplit] {
+        &self.splits
+    }
+    /// Returns the number of the vertex splits.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.splits.len()
+    }
+    /// Returns whether there are no vertex splits or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.splits.is_empty()
+    }
+    /// Returns the decoder at the base mesh.
+    #[inline(always)]
+    pub fn decoder(&self) -> ProgressiveMeshDecoder {
+        ProgressiveMeshDecoder {
+            positions: self.base_positions.clone(),
+            faces: self.base_faces.clone(),
+        }
+    }
+    /// Returns the mesh with the first `level` vertex splits applied.
+    pub fn polygon_mesh(&self, level: usize) -> PolygonMesh {
+        let mut decoder = self.decoder();
+        self.splits
+            .iter()
+            .take(level)
+            .for_each(|split| decoder.apply(split));
+        decoder.polygon_mesh()
+    }
+}
+
+impl ProgressiveMeshDecoder {
+    /// Applies the vertex split.
+    pub fn apply(&mut self, split: &VertexSplit) {
+        let new_vertex = self.positions.len();
+        self.positions.push(split.position);
+        split.moved_faces.iter().for_each(|idx| {
+            self.faces[*idx]
+                .iter_mut()
+                .filter(|v| **v == split.vertex)
+                .for_each(|v| *v = new_vertex);
+        });
+        self.faces.extend(split.new_faces.iter().copied());
+    }
+    /// Returns the positions of the current mesh.
+    #[inline(always)]
+    pub fn positions(&self) -> &[Point3] {
+        &self.positions
+    }
+    /// Returns the triangles of the current mesh.
+    #[inline(always)]
+    pub fn faces(&self) -> &[[usize; 3]] {
+        &self.faces
+    }
+    /// Returns the current mesh.
+    #[inline(always)]
+    pub fn polygon_mesh(&self) -> PolygonMesh {
+        let faces = Faces::from_iter(&self.faces);
+        PolygonMesh::new(self.positions.clone(), Vec::new(), Vec::new(), faces)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Collapse {
+    removed: usize,
+    kept: usize,
+    removed_faces: Vec<(usize, [usize; 3])>,
+    moved_faces: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    cost: f64,
+    removed: usize,
+    kept: usize,
+    stamps: (usize, usize),
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    // reversed for the min-heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| (other.removed, other.kept).cmp(&(self.removed, self.kept)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Encoder {
+    positions: Vec<Point3>,
+    faces: Vec<Option<[usize; 3]>>,
+    vertex_faces: Vec<HashSet<usize>>,
+    quadrics: Vec<Matrix4>,
+    stamps: Vec<usize>,
+    removed: Vec<bool>,
+    // whether a collapse may connect two boundary vertices by a new edge
+    boundary_chords: bool,
+    // the vertices which are not removed by collapses
+    locked: Vec<bool>,
+    // whether a boundary vertex may be collapsed along a boundary edge
+    free_boundaries: bool,
+    // the planes of the original faces, and those around the vertices if the error is bounded
+    planes: Vec<Vector4>,
+    vertex_planes: Vec<Vec<usize>>,
+    max_error: Option<f64>,
+    collapses: Vec<Collapse>,
+}
+
+impl Encoder {
+    fn new(mesh: &PolygonMesh) -> Encoder {
+        let triangles = mesh.face_iter().flat_map(|face| {
+            (2..face.len()).map(move |i| [face[0].pos, face[i - 1].pos, face[i].pos])
+        });
+        Encoder::from_triangles(mesh.positions().clone(), triangles)
+    }
+
+    fn from_triangles<I>(positions: Vec<Point3>, triangles: I) -> Encoder
+    where I: IntoIterator<Item = [usize; 3]> {
+        let faces: Vec<Option<[usize; 3]>> = triangles
+            .into_iter()
+            .filter(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0])
+            .map(Some)
+            .collect();
+        let mut vertex_faces = vec![HashSet::new(); positions.len()];
+        let mut quadrics = vec![Matrix4::zero(); positions.len()];
+        let mut planes = Vec::with_capacity(faces.len());
+        faces.iter().enumerate().for_each(|(i, tri)| {
+            let tri = tri.unwrap();
+            let p = [positions[tri[0]], positions[tri[1]], positions[tri[2]]];
+            let area_normal = (p[1] - p[0]).cross(p[2] - p[0]);
+            let area = area_normal.magnitude() / 2.0;
+            let plane = match area.so_small() {
+                true => Vector4::zero(),
+                false => {
+                    let n = area_normal.normalize();
+                    n.extend(-n.dot(p[0].to_vec()))
+                }
+            };
+            let quadric = outer(plane, plane) * area;
+            tri.iter().for_each(|v| {
+                vertex_faces[*v].insert(i);
+                quadrics[*v] += quadric;
+            });
+            planes.push(plane);
+        });
+        Encoder {
+            stamps: vec![0; positions.len()],
+            removed: vec![false; positions.len()],
+            boundary_chords: true,
+            locked: vec![false; positions.len()],
+            free_boundaries: false,
+            planes,
+            vertex_planes: Vec::new(),
+            max_error: None,
+            positions,
+            faces,
+            vertex_faces,
+            quadrics,
+            collapses: Vec::new(),
+        }
+    }
+
+    /// Lets the boundary vertices be collapsed along the boundary edges, adding the quadrics
+    /// of the planes through the boundary edges perpendicular to the faces.
+    /// If the error is bounded, the distances from these planes are also bounded.
+    fn free_boundaries(&mut self) {
+        self.free_boundaries = true;
+        for i in 0..self.faces.len() {
+            let tri = self.faces[i].unwrap();
+            for j in 0..3 {
+                let (v, w) = (tri[j], tri[(j + 1) % 3]);
+                if self.shared_faces(v, w).len() != 1 {
+                    continue;
+                }
+                let edge = self.positions[w] - self.positions[v];
+                let normal = edge.cross(self.planes[i].truncate());
+                if normal.so_small() {
+                    continue;
+                }
+                let n = normal.normalize();
+                let plane = n.extend(-n.dot(self.positions[v].to_vec()));
+                let quadric = outer(plane, plane) * edge.magnitude2() * BOUNDARY_WEIGHT;
+                self.quadrics[v] += quadric;
+                self.quadrics[w] += quadric;
+                if self.max_error.is_some() {
+                    self.vertex_planes[v].push(self.planes.len());
+                    self.vertex_planes[w].push(self.planes.len());
+                }
+                self.planes.push(plane);
+            }
+        }
+    }
+
+    /// Bounds the distances from the kept vertices to the planes of the original faces
+    /// around the collapsed vertices.
+    fn bound_error(&mut self, max_error: f64) {
+        self.max_error = Some(max_error);
+        self.vertex_planes = self
+            .vertex_faces
+            .iter()
+            .map(|faces| faces.iter().copied().collect())
+            .collect();
+    }
+
+    fn neighbors(&self, v: usize) -> HashSet<usize> {
+        self.vertex_faces[v]
+            .iter()
+            .flat_map(|f| self.faces[*f].unwrap().to_vec())
+            .filter(|w| *w != v)
+            .collect()
+    }
+
+    fn shared_faces(&self, v: usize, w: usize) -> Vec<usize> {
+        self.vertex_faces[v]
+            .intersection(&self.vertex_faces[w])
+            .copied()
+            .collect()
+    }
+
+    fn is_boundary(&self, v: usize) -> bool {
+        self.neighbors(v)
+            .into_iter()
+            .any(|w| self.shared_faces(v, w).len() != 2)
+    }
+
+    fn candidate(&self, removed: usize, kept: usize) -> Candidate {
+        let p = self.positions[kept].to_homogeneous();
+        let quadric = self.quadrics[removed] + self.quadrics[kept];
+        Candidate {
+            cost: p.dot(quadric * p),
+            removed,
+            kept,
+            stamps: (self.stamps[removed], self.stamps[kept]),
+        }
+    }
+
+    fn push_candidates(&self, v: usize, heap: &mut BinaryHeap<Candidate>) {
+        self.neighbors(v).into_iter().for_each(|w| {
+            heap.push(self.candidate(v, w));
+            heap.push(self.candidate(w, v));
+        });
+    }
+
+    /// Whether the collapse keeps the manifoldness and the orientations.
+    fn is_valid(&self, removed: usize, kept: usize) -> bool {
+        let shared = self.shared_faces(removed, kept);
+        if shared.is_empty() || self.locked[removed] {
+            return false;
+        }
+        if self.is_boundary(removed) && !(self.free_boundaries && shared.len() == 1) {
+            return false;
+        }
+        if let Some(max_error) = self.max_error {
+            let p = self.positions[kept].to_homogeneous();
+            let exceeded = self.vertex_planes[removed]
+                .iter()
+                .any(|f| self.planes[*f].dot(p).abs() > max_error);
+            if exceeded {
+                return false;
+            }
+        }
+        // the link condition
+        let opposites: HashSet<usize> = shared
+            .iter()
+            .flat_map(|f| self.faces[*f].unwrap().to_vec())
+            .filter(|w| *w != removed && *w != kept)
+            .collect();
+        let removed_neighbors = self.neighbors(removed);
+        let common = self
+            .neighbors(kept)
+            .into_iter()
+            .filter(|w| removed_neighbors.contains(w))
+            .count();
+        // the tetrahedra are not collapsed
+        if common != opposites.len()
+            || self.vertex_faces[kept].len() + self.vertex_faces[removed].len() <= 6
+        {
+            return false;
+        }
+        if !self.boundary_chords && self.is_boundary(kept) {
+            let kept_neighbors = self.neighbors(kept);
+            let chord = removed_neighbors
+                .iter()
+                .any(|w| *w != kept && !kept_neighbors.contains(w) && self.is_boundary(*w));
+            if chord {
+                return false;
+            }
+        }
+        // the orientations of the moved faces
+        self.vertex_faces[removed]
+            .iter()
+            .filter(|f| !shared.contains(f))
+            .all(|f| {
+                let tri = self.faces[*f].unwrap();
+                let p = tri.map(|v| self.positions[v]);
+                let q = tri.map(|v| match v == removed {
+                    true => self.positions[kept],
+                    false => self.positions[v],
+                });
+                let old = (p[1] - p[0]).cross(p[2] - p[0]);
+                let new = (q[1] - q[0]).cross(q[2] - q[0]);
+                !new.so_small() && old.dot(new) > 0.0
+            })
+    }
+
+    fn collapse(&mut self, removed: usize, kept: usize) {
+        let shared = self.shared_faces(removed, kept);
+        let removed_faces: Vec<(usize, [usize; 3])> = shared
+            .iter()
+            .map(|f| (*f, self.faces[*f].take().unwrap()))
+            .collect();
+        removed_faces.iter().for_each(|(f, tri)| {
+            tri.iter().for_each(|v| {
+                self.vertex_faces[*v].remove(f);
+            });
+        });
+        let moved_faces: Vec<usize> = self.vertex_faces[removed].iter().copied().collect();
+        moved_faces.iter().for_each(|f| {
+            self.faces[*f]
+                .iter_mut()
+                .flatten()
+                .filter(|v| **v == removed)
+                .for_each(|v| *v = kept);
+            self.vertex_faces[kept].insert(*f);
+        });
+        self.vertex_faces[removed].clear();
+        self.removed[removed] = true;
+        let quadric = self.quadrics[removed];
+        self.quadrics[kept] += quadric;
+        if self.max_error.is_some() {
+            let planes = std::mem::take(&mut self.vertex_planes[removed]);
+            let kept_planes = &mut self.vertex_planes[kept];
+            kept_planes.extend(planes);
+            kept_planes.sort_unstable();
+            kept_planes.dedup();
+        }
+        self.collapses.push(Collapse {
+            removed,
+            kept,
+            removed_faces,
+            moved_faces,
+        });
+    }
+
+    #[inline(always)]
+    fn collapse_edges(&mut self, min_faces: usize) {
+        let counter = &mut ProgressCounter::new(0, |_, _| true);
+        let _ = self.collapse_edges_with_progress(min_faces, counter);
+    }
+
+    /// Collapses edges like `collapse_edges`, advancing `counter` by the removed triangles.
+    fn collapse_edges_with_progress<P: Progress>(
+        &mut self,
+        min_faces: usize,
+        counter: &mut ProgressCounter<P>,
+    ) -> Result<()> {
+        let mut heap = BinaryHeap::new();
+        (0..self.positions.len()).for_each(|v| {
+            self.neighbors(v)
+                .into_iter()
+                .for_each(|w| heap.push(self.candidate(v, w)))
+        });
+        let mut faces = self.faces.len();
+        while faces > min_faces {
+            let candidate = match heap.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+            let (removed, kept) = (candidate.removed, candidate.kept);
+            if self.removed[removed]
+                || self.removed[kept]
+                || candidate.stamps != (self.stamps[removed], self.stamps[kept])
+                || !self.is_valid(removed, kept)
+            {
+                continue;
+            }
+            let neighbors = self.neighbors(removed);
+            self.collapse(removed, kept);
+            let removed_faces = self.collapses.last().unwrap().removed_faces.len();
+            faces -= removed_faces;
+            counter.try_advance(removed_faces)?;
+            neighbors.iter().for_each(|v| self.stamps[*v] += 1);
+            self.push_candidates(kept, &mut heap);
+            neighbors
+                .iter()
+                .filter(|v| **v != kept)
+                .for_each(|v| self.push_candidates(*v, &mut heap));
+        }
+        Ok(())
+    }
+
+    fn into_progressive_mesh(self) -> ProgressiveMesh {
+        let mut vertex_map = vec![0; self.positions.len()];
+        let mut base_positions = Vec::new();
+        (0..self.positions.len())
+            .filter(|v| !self.removed[*v])
+            .for_each(|v| {
+                vertex_map[v] = base_positions.len();
+                base_positions.push(self.positions[v]);
+            });
+        let mut counter = base_positions.len();
+        self.collapses.iter().rev().for_each(|collapse| {
+            vertex_map[collapse.removed] = counter;
+            counter += 1;
+        });
+        let mut face_map = vec![0; self.faces.len()];
+        let mut base_faces = Vec::new();
+        self.faces.iter().enumerate().for_each(|(i, tri)| {
+            if let Some(tri) = tri {
+                face_map[i] = base_faces.len();
+                base_faces.push(tri.map(|v| vertex_map[v]));
+            }
+        });
+        let mut counter = base_faces.len();
+        self.collapses.iter().rev().for_each(|collapse| {
+            collapse.removed_faces.iter().for_each(|(f, _)| {
+                face_map[*f] = counter;
+                counter += 1;
+            })
+        });
+        let splits = self
+            .collapses
+            .iter()
+            .rev()
+            .map(|collapse| VertexSplit {
+                vertex: vertex_map[collapse.kept],
+                position: self.positions[collapse.removed],
+                moved_faces: collapse.moved_faces.iter().map(|f| face_map[*f]).collect(),
+                new_faces: collapse
+                    .removed_faces
+                    .iter()
+                    .map(|(_, tri)| tri.map(|v| vertex_map[v]))
+                    .collect(),
+            })
+            .collect();
+        ProgressiveMesh {
+            base_positions,
+            base_faces,
+            splits,
+        }
+    }
+}
+
+/// Decimates `mesh` by the edge collapses of the progressive mesh until the number of
+/// the triangles is not greater than `min_faces`, keeping the boundaries.
+///
+/// The face vertices with the same indices of positions, uv coordinates and normals are
+/// regarded as one vertex, so the kept vertices keep their attributes.
+pub(crate) fn decimation(mesh: &PolygonMesh, min_faces: usize) -> PolygonMesh {
+    let mut indices = HashMap::new();
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    for face in mesh.face_iter() {
+        for i in 2..face.len() {
+            triangles.push([face[0], face[i - 1], face[i]].map(|v| {
+                *indices.entry(v).or_insert_with(|| {
+                    vertices.push(v);
+                    vertices.len() - 1
+                })
+            }));
+        }
+    }
+    let positions = vertices.iter().map(|v| mesh.positions()[v.pos]).collect();
+    let mut encoder = Encoder::from_triangles(positions, triangles);
+    encoder.boundary_chords = false;
+    encoder.collapse_edges(min_faces);
+    let faces = encoder
+        .faces
+        .iter()
+        .flatten()
+        .map(|tri| tri.map(|v| vertices[v]));
+    let mut polygon = PolygonMesh::new(
+        mesh.positions().clone(),
+        mesh.uv_coords().clone(),
+        mesh.normals().clone(),
+        Faces::from_iter(faces),
+    );
+    polygon.remove_unused_attrs();
+    polygon
+}
+
+/// Decimates the triangles of `mesh` by the edge collapses as configured by `descriptor`.
+///
+/// Returns the corners of the remaining triangles and the indices of the original faces of
+/// them. Unlike [`decimation`], the corners are identified by the positions, and the moved
+/// corners take the attributes of the kept vertices on the same sides of the seams.
+pub(crate) fn decimated_triangles<P: Progress>(
+    mesh: &PolygonMesh,
+    descriptor: &DecimationDescriptor,
+    progress: P,
+) -> Result<(Vec<[Vertex; 3]>, Vec<usize>)> {
+    let mut corners = Vec::new();
+    let mut origins = Vec::new();
+    for (i, face) in mesh.face_iter().enumerate() {
+        for j in 2..face.len() {
+            let tri = [face[0], face[j - 1], face[j]];
+            if tri[0].pos != tri[1].pos && tri[1].pos != tri[2].pos && tri[2].pos != tri[0].pos {
+                corners.push(tri);
+                origins.push(i);
+            }
+        }
+    }
+    let triangles: Vec<[usize; 3]> = corners.iter().map(|tri| tri.map(|v| v.pos)).collect();
+    let mut encoder = Encoder::from_triangles(mesh.positions().clone(), triangles.clone());
+    if descriptor.preserve_uv_seams {
+        let uv = |v: &Vertex| v.uv.map(|i| mesh.uv_coords()[i]);
+        let mut first_uv = HashMap::new();
+        corners.iter().flatten().for_each(|v| {
+            let first = *first_uv.entry(v.pos).or_insert_with(|| uv(v));
+            if first != uv(v) {
+                encoder.locked[v.pos] = true;
+            }
+        });
+    }
+    let selection = descriptor
+        .preserve_selection
+        .and_then(|name| mesh.face_attributes().selection(name));
+    if let Some(selection) = selection {
+        corners
+            .iter()
+            .zip(&origins)
+            .filter(|(_, i)| selection[**i])
+            .for_each(|(tri, _)| tri.iter().for_each(|v| encoder.locked[v.pos] = true));
+    }
+    if let Some(max_error) = descriptor.max_error {
+        encoder.bound_error(max_error);
+    }
+    encoder.boundary_chords = !descriptor.preserve_boundaries;
+    if !descriptor.preserve_boundaries {
+        encoder.free_boundaries();
+    }
+    let min_faces = descriptor.target_faces.unwrap_or(0);
+    let mut counter = ProgressCounter::new(corners.len().saturating_sub(min_faces), progress);
+    encoder.collapse_edges_with_progress(min_faces, &mut counter)?;
+    // replays the collapses on the corners
+    let mut triangles = triangles;
+    for collapse in &encoder.collapses {
+        let (removed, kept) = (collapse.removed, collapse.kept);
+        for f in &collapse.moved_faces {
+            let k = triangles[*f].iter().position(|v| *v == removed).unwrap();
+            let old = corners[*f][k];
+            let shared = collapse
+                .removed_faces
+                .iter()
+                .find(|(s, _)| corners[*s].contains(&old))
+                .unwrap_or(&collapse.removed_faces[0]);
+            let l = triangles[shared.0].iter().position(|v| *v == kept).unwrap();
+            corners[*f][k] = corners[shared.0][l];
+            triangles[*f][k] = kept;
+        }
+    }
+    Ok(encoder
+        .faces
+        .iter()
+        .zip(corners.into_iter().zip(origins))
+        .filter(|(tri, _)| tri.is_some())
+        .map(|(_, pair)| pair)
+        .unzip())
+}
+
+#[inline(always)]
+fn outer(a: Vector4, b: Vector4) -> Matrix4 {
+    Matrix4::from_cols(a * b[0], a * b[1], a * b[2], a * b[3])
+}
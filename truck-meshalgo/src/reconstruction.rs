@@ -0,0 +1,245 @@
+use crate::lattice::marching_tetrahedra;
+use crate::*;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Estimates the normals of point clouds and reconstructs surfaces from them.
+pub trait PointCloudReconstruction {
+    /// Estimates the normals by the principal component analysis of the `neighbors` nearest
+    /// points of each point, and orients them consistently by propagating the orientations
+    /// between the neighboring points. The normals of the outermost points face outward.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the points on the unit sphere
+    /// let positions: Vec<Point3> = (0..400)
+    ///     .map(|i| {
+    ///         let z = 1.0 - (2 * i + 1) as f64 / 400.0;
+    ///         let theta = i as f64 * 2.399963;
+    ///         let r = f64::sqrt(1.0 - z * z);
+    ///         Point3::new(r * theta.cos(), r * theta.sin(), z)
+    ///     })
+    ///     .collect();
+    /// let mut cloud = PointCloud::from_positions(positions);
+    ///
+    /// cloud.estimate_normals(10);
+    /// for (pt, normal) in cloud.positions().iter().zip(cloud.normals()) {
+    ///     assert!(normal.dot(pt.to_vec()) > 0.95);
+    /// }
+    /// ```
+    fn estimate_normals(&mut self, neighbors: usize);
+    /// Reconstructs the closed triangle mesh surrounding the points, as the zero set of
+    /// the signed distance from the tangent planes of the nearest points, sampled on the grid
+    /// with `spacing`. The normals are estimated by `estimate_normals` with 10 neighbors
+    /// if the point cloud has no normals.
+    ///
+    /// The iso-surface is extracted by marching tetrahedra, so the output has no normals.
+    /// Use `NormalFilters` if necessary.
+    fn reconstruct_surface(&self, spacing: f64) -> PolygonMesh;
+}
+
+impl PointCloudReconstruction for PointCloud {
+    fn estimate_normals(&mut self, neighbors: usize) {
+        if self.is_empty() {
+            return;
+        }
+        let grid = PointGrid::new(self.positions(), neighbors);
+        let neighbors: Vec<Vec<usize>> = self
+            .positions()
+            .iter()
+            .map(|pt| grid.nearest(*pt, neighbors + 1))
+            .collect();
+        let mut normals: Vec<Vector3> = neighbors
+            .iter()
+            .map(|indices| {
+                let points: Vec<Point3> = indices.iter().map(|i| self.positions()[*i]).collect();
+                plane_normal(&points)
+            })
+            .collect();
+        orient_normals(self.positions(), &neighbors, &mut normals);
+        self.set_normals(normals).unwrap();
+    }
+    fn reconstruct_surface(&self, spacing: f64) -> PolygonMesh {
+        let bdb = self.bounding_box();
+        if bdb.is_empty() || spacing <= 0.0 {
+            return PolygonMesh::default();
+        }
+        let mut cloud = self.clone();
+        if cloud.normals().is_empty() {
+            cloud.estimate_normals(10);
+        }
+        let grid = PointGrid::new(cloud.positions(), 16);
+        let field = |pt: Point3| {
+            let idx = grid.nearest(pt, 1)[0];
+            cloud.normals()[idx].dot(pt - cloud.positions()[idx])
+        };
+        marching_tetrahedra(field, *bdb.min(), *bdb.max(), spacing)
+    }
+}
+
+/// the indices of points hashed by the uniform grid
+struct PointGrid<'a> {
+    points: &'a [Point3],
+    origin: Point3,
+    cell_size: f64,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+    max_ring: i64,
+}
+
+impl<'a> PointGrid<'a> {
+    /// The size of the cells is decided so that each cell contains about `density` points
+    /// if the points are on a surface.
+    fn new(points: &'a [Point3], density: usize) -> PointGrid<'a> {
+        let bdb: BoundingBox<Point3> = points.iter().collect();
+        let diag = bdb.diagonal().magnitude();
+        let cell_size = diag * f64::sqrt(density as f64 / points.len() as f64);
+        let cell_size = f64::max(cell_size, f64::max(diag, 1.0) * 1.0e-9);
+        let mut grid = PointGrid {
+            points,
+            origin: *bdb.min(),
+            cell_size,
+            cells: HashMap::new(),
+            max_ring: (diag / cell_size).ceil() as i64 + 1,
+        };
+        for (i, pt) in points.iter().enumerate() {
+            let key = grid.key(*pt);
+            grid.cells.entry(key).or_default().push(i);
+        }
+        grid
+    }
+
+    #[inline(always)]
+    fn key(&self, pt: Point3) -> [i64; 3] {
+        let vec = (pt - self.origin) / self.cell_size;
+        [vec[0].floor() as i64, vec[1].floor() as i64, vec[2].floor() as i64]
+    }
+
+    /// Returns the indices of the `k` nearest points in the order of the distances.
+    fn nearest(&self, pt: Point3, k: usize) -> Vec<usize> {
+        let center = self.key(pt);
+        // the distance from `pt` to the grid of the points
+        let outside = (0..3)
+            .map(|i| i64::max(-center[i], center[i] - self.max_ring).max(0))
+            .max()
+            .unwrap_or(0);
+        let mut found: Vec<(f64, usize)> = Vec::new();
+        for ring in 0_i64.. {
+            for i in -ring..=ring {
+                for j in -ring..=ring {
+                    for l in -ring..=ring {
+                        if i64::max(i.abs(), i64::max(j.abs(), l.abs())) != ring {
+                            continue;
+                        }
+                        let key = [center[0] + i, center[1] + j, center[2] + l];
+                        if let Some(indices) = self.cells.get(&key) {
+                            found.extend(indices.iter().map(|idx| {
+                                (self.points[*idx].distance2(pt), *idx)
+                            }));
+                        }
+                    }
+                }
+            }
+            // the points in the next rings are farther than `ring * cell_size`.
+            let radius = ring as f64 * self.cell_size;
+            let enough = found.len() >= k && {
+                found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                found[k - 1].0 <= radius * radius
+            };
+            if enough || found.len() == self.points.len() || ring > outside + self.max_ring {
+                break;
+            }
+        }
+        found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        found.into_iter().take(k).map(|(_, idx)| idx).collect()
+    }
+}
+
+/// Returns the normal of the plane fitted to `points`, the eigenvector of the covariance matrix
+/// with the smallest eigenvalue, by the Jacobi eigenvalue algorithm.
+fn plane_normal(points: &[Point3]) -> Vector3 {
+    let center = points.iter().fold(Vector3::zero(), |sum, pt| sum + pt.to_vec());
+    let center = center / points.len() as f64;
+    let mut mat = points.iter().fold(Matrix3::zero(), |sum, pt| {
+        let vec = pt.to_vec() - center;
+        sum + Matrix3::from_cols(vec * vec[0], vec * vec[1], vec * vec[2])
+    });
+    let mut vectors = Matrix3::identity();
+    for _ in 0..50 {
+        let (p, q) = [(0, 1), (0, 2), (1, 2)]
+            .iter()
+            .copied()
+            .fold((0, 1), |(p, q), (i, j)| {
+                match mat[j][i].abs() > mat[q][p].abs() {
+                    true => (i, j),
+                    false => (p, q),
+                }
+            });
+        if mat[q][p].abs() < 1.0e-15 * (mat[0][0].abs() + mat[1][1].abs() + mat[2][2].abs()) {
+            break;
+        }
+        let theta = (mat[q][q] - mat[p][p]) / (2.0 * mat[q][p]);
+        let t = theta.signum() / (theta.abs() + f64::sqrt(theta * theta + 1.0));
+        let c = 1.0 / f64::sqrt(t * t + 1.0);
+        let s = t * c;
+        let mut rot = Matrix3::identity();
+        rot[p][p] = c;
+        rot[q][q] = c;
+        rot[q][p] = s;
+        rot[p][q] = -s;
+        mat = rot.transpose() * mat * rot;
+        vectors = vectors * rot;
+    }
+    let idx = (0..3).fold(0, |idx, i| match mat[i][i] < mat[idx][idx] {
+        true => i,
+        false => idx,
+    });
+    vectors[idx]
+}
+
+/// the candidate of the propagation of the orientation, ordered by the parallelism of normals
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Propagation(f64, usize, usize);
+
+impl Eq for Propagation {}
+
+impl PartialOrd for Propagation {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Propagation {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.0.total_cmp(&other.0) }
+}
+
+/// Orients the normals along the maximum spanning tree of the neighbor graph weighted by
+/// the parallelism of normals. The root of each component faces to the positive x-axis.
+fn orient_normals(points: &[Point3], neighbors: &[Vec<usize>], normals: &mut [Vector3]) {
+    let mut oriented = vec![false; points.len()];
+    let mut roots: Vec<usize> = (0..points.len()).collect();
+    roots.sort_by(|i, j| points[*j][0].partial_cmp(&points[*i][0]).unwrap());
+    for root in roots {
+        if oriented[root] {
+            continue;
+        }
+        if normals[root][0] < 0.0 {
+            normals[root] = -normals[root];
+        }
+        let mut heap = BinaryHeap::new();
+        heap.push(Propagation(f64::INFINITY, root, root));
+        while let Some(Propagation(_, from, to)) = heap.pop() {
+            if oriented[to] {
+                continue;
+            }
+            if normals[from].dot(normals[to]) < 0.0 {
+                normals[to] = -normals[to];
+            }
+            oriented[to] = true;
+            for next in &neighbors[to] {
+                if !oriented[*next] {
+                    let weight = normals[to].dot(normals[*next]).abs();
+                    heap.push(Propagation(weight, to, *next));
+                }
+            }
+        }
+    }
+}
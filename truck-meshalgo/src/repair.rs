@@ -0,0 +1,271 @@
+use crate::lattice::marching_tetrahedra_with_progress;
+use crate::*;
+use std::f64::consts::PI;
+use truck_polymesh::progress::Progress;
+
+/// Repairs polygon soups into closed manifold meshes.
+pub trait ManifoldRepair {
+    /// Returns the closed manifold triangle mesh surrounding the region whose generalized
+    /// winding number with respect to `self` is more than one half, sampled on the grid
+    /// with `spacing`.
+    ///
+    /// The winding number is defined even for the soups with holes, gaps, self-intersections
+    /// or duplicated faces, so such meshes, e.g. dirty STL files, are remeshed into
+    /// the boundaries of solids. The faces are required to be oriented almost consistently,
+    /// but the inverted meshes are allowed since the absolute value of the winding number is
+    /// adopted. The features smaller than `spacing` are not kept. The grid has at most
+    /// `MAX_DIVISIONS` cells along each side of the bounding box, so too small `spacing` is
+    /// enlarged to the longest side divided by `MAX_DIVISIONS`. Returns the empty mesh if
+    /// `spacing` is not positive.
+    ///
+    /// The iso-surface is extracted by marching tetrahedra, so the output has no normals.
+    /// Use `NormalFilters` if necessary. The winding number is approximated by the dipoles
+    /// of the clusters of the faces far from the sampling point, as the fast winding number
+    /// by Barill et al., so the time is almost proportional to the number of the sampling
+    /// points and the logarithm of the number of the faces.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_topology::shell::ShellCondition;
+    /// // the unit cube one of whose faces is missing
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 1.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[
+    ///     [3, 2, 1, 0],
+    ///     [0, 1, 5, 4],
+    ///     [1, 2, 6, 5],
+    ///     [2, 3, 7, 6],
+    ///     [3, 0, 4, 7],
+    /// ]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// assert_eq!(mesh.shell_condition(), ShellCondition::Oriented);
+    ///
+    /// let repaired = mesh.make_manifold(0.1);
+    /// assert_eq!(repaired.shell_condition(), ShellCondition::Closed);
+    /// let bdb = repaired.bounding_box();
+    /// assert!(bdb.min().distance(Point3::new(0.0, 0.0, 0.0)) < 0.2);
+    /// assert!(bdb.max().distance(Point3::new(1.0, 1.0, 1.0)) < 0.2);
+    /// ```
+    fn make_manifold(&self, spacing: f64) -> PolygonMesh;
+    /// Repairs the mesh like [`make_manifold`], reporting the number of the sampled nodes of
+    /// the grid to `progress`.
+    ///
+    /// If the repair is cancelled, returns [`Error::Cancelled`].
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_polymesh::progress::CancellationToken;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter([[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let mut last = (0, 0);
+    /// let progress = |done, total| {
+    ///     last = (done, total);
+    ///     true
+    /// };
+    /// let repaired = mesh.make_manifold_with_progress(0.1, progress).unwrap();
+    /// assert_eq!(last.0, last.1);
+    /// assert!(!repaired.faces().is_empty());
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    /// let res = mesh.make_manifold_with_progress(0.1, &token);
+    /// assert!(matches!(res, Err(errors::Error::Cancelled)));
+    /// ```
+    ///
+    /// [`make_manifold`]: ./trait.ManifoldRepair.html#tymethod.make_manifold
+    /// [`Error::Cancelled`]: ../truck_polymesh/errors/enum.Error.html#variant.Cancelled
+    fn make_manifold_with_progress<P: Progress>(
+        &self,
+        spacing: f64,
+        progress: P,
+    ) -> Result<PolygonMesh>;
+}
+
+/// the maximum number of the cells of the grid of [`ManifoldRepair::make_manifold`] along
+/// each side of the bounding box
+pub const MAX_DIVISIONS: usize = 128;
+
+impl ManifoldRepair for PolygonMesh {
+    #[inline(always)]
+    fn make_manifold(&self, spacing: f64) -> PolygonMesh {
+        self.make_manifold_with_progress(spacing, |_, _| true)
+            .unwrap_or_default()
+    }
+    fn make_manifold_with_progress<P: Progress>(
+        &self,
+        spacing: f64,
+        progress: P,
+    ) -> Result<PolygonMesh> {
+        let bdb = self.bounding_box();
+        if bdb.is_empty() || spacing.is_nan() || spacing <= 0.0 {
+            return Ok(PolygonMesh::default());
+        }
+        let diag = bdb.diagonal();
+        let longest = f64::max(f64::max(diag[0], diag[1]), diag[2]);
+        let spacing = f64::max(spacing, longest / MAX_DIVISIONS as f64);
+        let triangles: Vec<[Point3; 3]> = self
+            .face_iter()
+            .flat_map(|face| {
+                let positions = self.positions();
+                (2..face.len()).map(move |i| {
+                    [
+                        positions[face[0].pos],
+                        positions[face[i - 1].pos],
+                        positions[face[i].pos],
+                    ]
+                })
+            })
+            .collect();
+        let tree = WindingTree::new(triangles);
+        let field = |pt: Point3| 0.5 - f64::abs(tree.winding_number(pt));
+        // The grid is shifted so that the nodes avoid the axis-aligned faces,
+        // where the winding number jumps.
+        let shift = Vector3::new(0.3, 0.2, 0.1) * spacing / std::f64::consts::E;
+        marching_tetrahedra_with_progress(field, *bdb.min() - shift, *bdb.max(), spacing, progress)
+    }
+}
+
+/// the ratio of the distance to the radius of the cluster approximated by its dipole
+const ACCURACY: f64 = 2.0;
+/// the maximum number of the triangles in a leaf
+const LEAF_SIZE: usize = 8;
+
+/// the hierarchy of the triangles for the fast winding number
+#[derive(Clone, Debug)]
+struct WindingTree {
+    triangles: Vec<[Point3; 3]>,
+    nodes: Vec<WindingNode>,
+}
+
+/// the cluster of the triangles `triangles[start..end]`
+#[derive(Clone, Debug)]
+struct WindingNode {
+    /// the area-weighted center of the triangles
+    center: Point3,
+    /// the radius of the ball with `center` including the triangles
+    radius: f64,
+    /// the sum of the area vectors of the triangles
+    dipole: Vector3,
+    start: usize,
+    end: usize,
+    children: Option<(usize, usize)>,
+}
+
+impl WindingTree {
+    fn new(triangles: Vec<[Point3; 3]>) -> WindingTree {
+        let mut tree = WindingTree {
+            triangles,
+            nodes: Vec::new(),
+        };
+        if !tree.triangles.is_empty() {
+            tree.build(0, tree.triangles.len());
+        }
+        tree
+    }
+
+    /// Builds the node of `triangles[start..end]` and returns its index.
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let triangles = &mut self.triangles[start..end];
+        let centroid = |tri: &[Point3; 3]| {
+            Point3::from_vec((tri[0].to_vec() + tri[1].to_vec() + tri[2].to_vec()) / 3.0)
+        };
+        let area = |tri: &[Point3; 3]| (tri[1] - tri[0]).cross(tri[2] - tri[0]) / 2.0;
+        let dipole = triangles.iter().map(area).sum::<Vector3>();
+        let weight: f64 = triangles.iter().map(|tri| area(tri).magnitude()).sum();
+        let center = match weight.so_small() {
+            true => centroid(&triangles[0]),
+            false => {
+                let sum = triangles
+                    .iter()
+                    .map(|tri| centroid(tri).to_vec() * area(tri).magnitude())
+                    .sum::<Vector3>();
+                Point3::from_vec(sum / weight)
+            }
+        };
+        let radius = triangles
+            .iter()
+            .flatten()
+            .map(|pt| pt.distance(center))
+            .fold(0.0, f64::max);
+        let idx = self.nodes.len();
+        self.nodes.push(WindingNode {
+            center,
+            radius,
+            dipole,
+            start,
+            end,
+            children: None,
+        });
+        if end - start <= LEAF_SIZE {
+            return idx;
+        }
+        let centers: BoundingBox<Point3> = triangles.iter().map(centroid).collect();
+        let diag = centers.diagonal();
+        let axis = match (diag[0] < diag[1], diag[1] < diag[2], diag[0] < diag[2]) {
+            (true, true, _) | (false, _, true) => 2,
+            (true, false, _) => 1,
+            (false, _, false) => 0,
+        };
+        triangles.sort_by(|a, b| centroid(a)[axis].total_cmp(&centroid(b)[axis]));
+        let mid = (start + end) / 2;
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[idx].children = Some((left, right));
+        idx
+    }
+
+    /// The generalized winding number at `pt`, where the clusters far from `pt` are
+    /// replaced by their dipoles.
+    fn winding_number(&self, pt: Point3) -> f64 {
+        let mut angle = 0.0;
+        let mut stack = match self.nodes.is_empty() {
+            true => Vec::new(),
+            false => vec![0],
+        };
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            let vec = node.center - pt;
+            let dist = vec.magnitude();
+            match node.children {
+                _ if dist > ACCURACY * node.radius => {
+                    angle += vec.dot(node.dipole) / (dist * dist * dist)
+                }
+                Some((left, right)) => stack.extend([left, right]),
+                None => {
+                    angle += solid_angle(&self.triangles[node.start..node.end], pt);
+                }
+            }
+        }
+        angle / (4.0 * PI)
+    }
+}
+
+/// The sum of the signed solid angles of the triangles at `pt`.
+fn solid_angle(triangles: &[[Point3; 3]], pt: Point3) -> f64 {
+    triangles
+        .iter()
+        .map(|tri| {
+            let (a, b, c) = (tri[0] - pt, tri[1] - pt, tri[2] - pt);
+            let (la, lb, lc) = (a.magnitude(), b.magnitude(), c.magnitude());
+            // the formula by Van Oosterom and Strackee
+            let det = a.dot(b.cross(c));
+            let div = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+            2.0 * f64::atan2(det, div)
+        })
+        .sum()
+}
@@ -0,0 +1,217 @@
+use crate::tessellation::{MeshableShape, MeshableSurface, PolylineableCurve};
+use crate::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use truck_topology::{Face, Shell, Solid};
+
+/// the number of the candidates per the area `radius * radius`
+const CANDIDATE_DENSITY: f64 = 16.0;
+/// the seed of the random numbers, fixed for the reproducible samples
+const SEED: u64 = 0x5eed;
+
+/// Samples points on surfaces with approximately uniform density.
+pub trait PoissonDiskSampling {
+    /// Returns the points on the surface with the normals, no two of which are closer
+    /// than `radius`, and which cover the surface so that every point on the surface is within
+    /// about `radius` from a sample, i.e. the blue noise.
+    ///
+    /// The candidates are scattered at random in proportion to the areas, and are accepted
+    /// in turn if no accepted point is within `radius`. The random numbers are seeded,
+    /// so the same input yields the same samples.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the square [0, 10] x [0, 10]
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(10.0, 0.0, 0.0),
+    ///     Point3::new(10.0, 10.0, 0.0),
+    ///     Point3::new(0.0, 10.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let cloud = mesh.poisson_disk_sampling(1.0);
+    /// assert!(cloud.len() > 50 && cloud.len() < 130);
+    /// assert!(cloud.normals().iter().all(|n| n.near(&Vector3::unit_z())));
+    /// for (i, p) in cloud.positions().iter().enumerate() {
+    ///     for q in &cloud.positions()[..i] {
+    ///         assert!(p.distance(*q) >= 1.0);
+    ///     }
+    /// }
+    /// ```
+    fn poisson_disk_sampling(&self, radius: f64) -> PointCloud;
+}
+
+impl PoissonDiskSampling for PolygonMesh {
+    fn poisson_disk_sampling(&self, radius: f64) -> PointCloud {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let candidates = mesh_candidates(self, radius, &mut rng, |p, n, _| Some((p, n)));
+        poisson_disk(candidates, radius, &mut rng)
+    }
+}
+
+/// The points are on the exact surface. The face is tessellated with the tolerance of
+/// a tenth of `radius`, and the candidates on the triangles are mapped by the uv coordinates.
+impl<C, S> PoissonDiskSampling for Face<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    fn poisson_disk_sampling(&self, radius: f64) -> PointCloud {
+        let shell: Shell<Point3, C, S> = vec![self.clone()].into();
+        shell.poisson_disk_sampling(radius)
+    }
+}
+
+/// The points are on the exact surfaces. The shell is tessellated with the tolerance of
+/// a tenth of `radius`, and the candidates on the triangles are mapped by the uv coordinates.
+impl<C, S> PoissonDiskSampling for Shell<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    fn poisson_disk_sampling(&self, radius: f64) -> PointCloud {
+        if radius <= 0.0 {
+            return PointCloud::default();
+        }
+        let meshed = match self.triangulation(radius / 10.0) {
+            Some(meshed) => meshed,
+            None => return PointCloud::default(),
+        };
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let candidates = self
+            .face_iter()
+            .zip(meshed.face_iter())
+            .flat_map(|(face, meshed_face)| {
+                let surface = face.get_surface();
+                let orientation = face.orientation();
+                let polygon = meshed_face.get_surface();
+                mesh_candidates(&polygon, radius, &mut rng, |_, _, uv| {
+                    let (u, v) = uv?;
+                    let normal = surface.normal(u, v);
+                    match orientation {
+                        true => Some((surface.subs(u, v), normal)),
+                        false => Some((surface.subs(u, v), -normal)),
+                    }
+                })
+            })
+            .collect();
+        poisson_disk(candidates, radius, &mut rng)
+    }
+}
+
+impl<C, S> PoissonDiskSampling for Solid<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    fn poisson_disk_sampling(&self, radius: f64) -> PointCloud {
+        let mut cloud = PointCloud::default();
+        self.boundaries().iter().for_each(|shell| {
+            let samples = shell.poisson_disk_sampling(radius);
+            let iter = samples.positions().iter().zip(samples.normals());
+            iter.for_each(|(p, n)| cloud.push(*p, Some(*n), None));
+        });
+        cloud
+    }
+}
+
+/// Scatters the candidates on the triangles of `mesh` in proportion to the areas. `map` is
+/// applied to the position, the normal and the uv coordinate interpolated on the triangle.
+fn mesh_candidates(
+    mesh: &PolygonMesh,
+    radius: f64,
+    rng: &mut StdRng,
+    map: impl Fn(Point3, Vector3, Option<(f64, f64)>) -> Option<(Point3, Vector3)>,
+) -> Vec<(Point3, Vector3)> {
+    if radius <= 0.0 {
+        return Vec::new();
+    }
+    let triangles: Vec<[Vertex; 3]> = mesh
+        .face_iter()
+        .flat_map(|face| (2..face.len()).map(move |i| [face[0], face[i - 1], face[i]]))
+        .collect();
+    let (positions, uv_coords, normals) = (mesh.positions(), mesh.uv_coords(), mesh.normals());
+    triangles
+        .iter()
+        .flat_map(|tri| {
+            let p = tri.map(|v| positions[v.pos]);
+            let cross = (p[1] - p[0]).cross(p[2] - p[0]);
+            let area = cross.magnitude() / 2.0;
+            // the fraction of the expected number is decided at random
+            let expected = CANDIDATE_DENSITY * area / (radius * radius);
+            let count = expected.floor() as usize + (rng.gen::<f64>() < expected.fract()) as usize;
+            let face_normal = match area.so_small() {
+                true => Vector3::zero(),
+                false => cross.normalize(),
+            };
+            let coords: Vec<(f64, f64)> = (0..count)
+                .map(|_| {
+                    let (s, t) = (rng.gen::<f64>(), rng.gen::<f64>());
+                    match s + t > 1.0 {
+                        true => (1.0 - s, 1.0 - t),
+                        false => (s, t),
+                    }
+                })
+                .collect();
+            let map = &map;
+            coords.into_iter().filter_map(move |(s, t)| {
+                let weights = [1.0 - s - t, s, t];
+                let pt = p[0] + (p[1] - p[0]) * s + (p[2] - p[0]) * t;
+                let normal = match tri.iter().all(|v| v.nor.is_some()) {
+                    true => {
+                        let normal = (0..3).fold(Vector3::zero(), |sum, i| {
+                            sum + normals[tri[i].nor.unwrap()] * weights[i]
+                        });
+                        normal.normalize()
+                    }
+                    false => face_normal,
+                };
+                let uv = match tri.iter().all(|v| v.uv.is_some()) {
+                    true => {
+                        let uv = (0..3).fold(Vector2::zero(), |sum, i| {
+                            sum + uv_coords[tri[i].uv.unwrap()] * weights[i]
+                        });
+                        Some((uv[0], uv[1]))
+                    }
+                    false => None,
+                };
+                map(pt, normal, uv)
+            })
+        })
+        .collect()
+}
+
+/// Accepts the candidates in random order if no accepted point is within `radius`.
+fn poisson_disk(
+    mut candidates: Vec<(Point3, Vector3)>,
+    radius: f64,
+    rng: &mut StdRng,
+) -> PointCloud {
+    candidates.shuffle(rng);
+    let key = |p: Point3| p.to_vec().map(|x| (x / radius).floor() as i64);
+    let mut cells = HashMap::<[i64; 3], Vec<Point3>>::new();
+    let mut cloud = PointCloud::default();
+    for (pt, normal) in candidates {
+        let k = key(pt);
+        let near = (-1..=1).any(|i| {
+            (-1..=1).any(|j| {
+                (-1..=1).any(|l| {
+                    let neighbor = [k[0] + i, k[1] + j, k[2] + l];
+                    match cells.get(&neighbor) {
+                        Some(points) => points.iter().any(|p| p.distance(pt) < radius),
+                        None => false,
+                    }
+                })
+            })
+        });
+        if !near {
+            cells.entry([k[0], k[1], k[2]]).or_default().push(pt);
+            cloud.push(pt, Some(normal), None);
+        }
+    }
+    cloud
+}
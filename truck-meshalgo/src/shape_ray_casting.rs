@@ -0,0 +1,401 @@
+use crate::analyzers::MeshBVH;
+use crate::distance::{
+    closest_normal, closest_points, meshed_shell_cells, parameter_in_tessellation, Cell,
+};
+use crate::tessellation::{MeshableShape, MeshableSurface, PolylineableCurve};
+use crate::thickness::{ray_intersection, triangles_with_faces};
+use crate::*;
+use truck_topology::{Face, Shell, Solid};
+
+/// The crossing of a ray and the boundary of a shape
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RayCrossing {
+    /// the ray goes into the solid, against the normal of the face
+    Entering,
+    /// the ray goes out of the solid, along the normal of the face
+    Exiting,
+    /// the ray touches the face tangentially
+    Tangent,
+}
+
+/// The classification of a point by a solid
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointContainment {
+    /// the point is in the interior of the solid
+    Inside,
+    /// the point is in the exterior of the solid
+    Outside,
+    /// the point is on the boundary of the solid, within `TOLERANCE`
+    OnBoundary,
+}
+
+/// The intersection of a ray and a face of a shape on the exact surface
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeRayHit {
+    /// the parameter of the ray: the intersection is `origin + distance * direction`
+    pub distance: f64,
+    /// the index of the face in the order of `face_iter`
+    pub face_index: usize,
+    /// the parameter of the intersection on the surface of the face
+    pub parameter: (f64, f64),
+    /// the intersection point
+    pub point: Point3,
+    /// the normal of the face at the intersection, oriented by the face
+    pub normal: Vector3,
+    /// whether the ray enters or exits the solid at the intersection
+    pub crossing: RayCrossing,
+}
+
+/// Casts rays to the exact faces of shapes.
+pub trait ShapeRayCasting {
+    /// Returns all intersections of the ray and the faces, sorted by the distance.
+    ///
+    /// The intersections are searched from the tessellation of the faces with tolerance
+    /// `tol`, and are refined on the exact surfaces and trimmed by the boundaries of the faces.
+    /// Returns `None` if the boundary of some face cannot be mapped to its parameter space.
+    fn ray_hits(&self, origin: Point3, direction: Vector3, tol: f64) -> Option<Vec<ShapeRayHit>>;
+    /// Returns the nearest intersection of the ray and the faces.
+    ///
+    /// Returns `None` if the ray misses the faces, or the boundary of some face cannot be
+    /// mapped to its parameter space.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the unit cube
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// let origin = Point3::new(0.5, 0.5, 3.0);
+    /// let hit = cube.ray_cast(origin, -Vector3::unit_z(), 0.01).unwrap();
+    /// assert_near!(hit.point, Point3::new(0.5, 0.5, 1.0));
+    /// assert_near!(hit.distance, 2.0);
+    /// assert_eq!(hit.crossing, RayCrossing::Entering);
+    /// // the ray passing by the cube
+    /// assert!(cube.ray_cast(origin, Vector3::unit_x(), 0.01).is_none());
+    /// ```
+    fn ray_cast(&self, origin: Point3, direction: Vector3, tol: f64) -> Option<ShapeRayHit> {
+        self.ray_hits(origin, direction, tol)?.into_iter().next()
+    }
+}
+
+/// Classifies points by solids on the exact surfaces.
+pub trait PointInSolid {
+    /// Classifies `point` into the interior, the exterior or the boundary of the solid.
+    ///
+    /// The solid is tessellated at each call. Use [`SolidClassifier`] for classifying many
+    /// points by the same solid.
+    /// Returns `None` if the tessellation with `tol` fails, or [`SolidClassifier::classify`]
+    /// returns `None`.
+    ///
+    /// [`SolidClassifier`]: ./struct.SolidClassifier.html
+    /// [`SolidClassifier::classify`]: ./struct.SolidClassifier.html#method.classify
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // the unit sphere
+    /// let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    /// let wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(PI));
+    /// let shell = builder::cone(&wire, Vector3::unit_z(), Rad(2.0 * PI));
+    /// let sphere = Solid::new(vec![shell]);
+    ///
+    /// let res = sphere.contains(Point3::new(0.2, 0.3, 0.4), 0.01).unwrap();
+    /// assert_eq!(res, PointContainment::Inside);
+    /// // the tessellation of the sphere does not contain the point, but the sphere does.
+    /// let res = sphere.contains(Point3::new(0.999, 0.0, 0.0), 0.01).unwrap();
+    /// assert_eq!(res, PointContainment::Inside);
+    /// let res = sphere.contains(Point3::new(1.001, 0.0, 0.0), 0.01).unwrap();
+    /// assert_eq!(res, PointContainment::Outside);
+    /// let res = sphere.contains(Point3::new(0.0, 0.6, 0.8), 0.01).unwrap();
+    /// assert_eq!(res, PointContainment::OnBoundary);
+    /// ```
+    fn contains(&self, point: Point3, tol: f64) -> Option<PointContainment>;
+}
+
+/// the surfaces of the faces with their tessellations
+struct FacesRayCaster<S> {
+    surfaces: Vec<(S, bool)>,
+    meshes: Vec<PolygonMesh>,
+    mesh: PolygonMesh,
+    triangle_faces: Vec<usize>,
+    bvh: MeshBVH,
+    bdb: BoundingBox<Point3>,
+}
+
+impl<S: MeshableSurface> FacesRayCaster<S> {
+    fn new<C: PolylineableCurve>(shells: &[Shell<Point3, C, S>], tol: f64) -> Option<Self> {
+        let meshed = shells
+            .iter()
+            .map(|shell| shell.triangulation(tol))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::from_meshed(shells, &meshed))
+    }
+
+    fn from_meshed<C: PolylineableCurve>(
+        shells: &[Shell<Point3, C, S>],
+        meshed: &[Shell<Point3, PolylineCurve<Point3>, PolygonMesh>],
+    ) -> Self {
+        let mut surfaces = Vec::new();
+        let mut meshes = Vec::new();
+        for (shell, meshed) in shells.iter().zip(meshed) {
+            shell
+                .face_iter()
+                .zip(meshed.face_iter())
+                .for_each(|(face, meshed)| {
+                    surfaces.push((face.get_surface(), face.orientation()));
+                    meshes.push(meshed.get_surface());
+                });
+        }
+        let (mesh, triangle_faces) = triangles_with_faces(&meshes);
+        let bvh = MeshBVH::new(&mesh);
+        let bdb = mesh.positions().iter().collect();
+        FacesRayCaster {
+            surfaces,
+            meshes,
+            mesh,
+            triangle_faces,
+            bvh,
+            bdb,
+        }
+    }
+
+    /// Returns the intersections sorted by the distance, and the nearest distance of
+    /// the triangles of the tessellations whose intersections with the exact faces are not found,
+    /// that is, the ray passes near the boundaries of the faces there.
+    fn ray_hits(
+        &self,
+        origin: Point3,
+        direction: Vector3,
+        tol: f64,
+    ) -> (Vec<ShapeRayHit>, Option<f64>) {
+        let unit = direction.normalize();
+        let mut hits = Vec::<ShapeRayHit>::new();
+        let mut uncertain: Option<f64> = None;
+        let mut update = |distance: f64| match uncertain {
+            Some(x) if x <= distance => {}
+            _ => uncertain = Some(distance),
+        };
+        // The tessellation is traversed from behind its bounding box, since the exact face in
+        // front of the origin may be tessellated behind it.
+        let margin = match self.bdb.is_empty() {
+            true => 0.0,
+            false => {
+                let far = self.bdb.diameter() + origin.distance(self.bdb.center());
+                far / direction.magnitude()
+            }
+        };
+        self.bvh.traverse(
+            origin - margin * direction,
+            direction,
+            f64::INFINITY,
+            |hit| {
+                let hit_distance = hit.distance - margin;
+                let face_index = self.triangle_faces[hit.face_index];
+                let (surface, orientation) = &self.surfaces[face_index];
+                let uv = hit
+                    .vertices
+                    .iter()
+                    .zip(&hit.barycentric)
+                    .try_fold(Vector2::zero(), |sum, (v, b)| {
+                        Some(sum + self.mesh.uv_coords()[v.uv?] * *b)
+                    });
+                let res = uv.and_then(|uv| {
+                    let hint = (uv[0], uv[1], hit_distance);
+                    ray_intersection(surface, origin, direction, hint)
+                });
+                let (u, v, t) = match res {
+                    Some(res) => res,
+                    None => {
+                        update(hit_distance);
+                        return Some(f64::INFINITY);
+                    }
+                };
+                let point = surface.subs(u, v);
+                // The tessellation is hit, but the exact face is not.
+                let far = point.distance(hit.point()) > 10.0 * tol;
+                let trimmed =
+                    !parameter_in_tessellation(&self.meshes[face_index], Vector2::new(u, v));
+                if far || trimmed {
+                    update(hit_distance);
+                }
+                let found = hits
+                    .iter()
+                    .any(|h| h.face_index == face_index && h.point.near(&point));
+                if t < 0.0 || far || trimmed || found {
+                    return Some(f64::INFINITY);
+                }
+                let normal = match orientation {
+                    true => surface.normal(u, v),
+                    false => -surface.normal(u, v),
+                };
+                let cos = normal.dot(unit);
+                let crossing = match (cos.so_small(), cos < 0.0) {
+                    (true, _) => RayCrossing::Tangent,
+                    (false, true) => RayCrossing::Entering,
+                    (false, false) => RayCrossing::Exiting,
+                };
+                hits.push(ShapeRayHit {
+                    distance: t,
+                    face_index,
+                    parameter: (u, v),
+                    point,
+                    normal,
+                    crossing,
+                });
+                Some(f64::INFINITY)
+            },
+        );
+        hits.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+        (hits, uncertain)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> ShapeRayCasting for Face<Point3, C, S> {
+    fn ray_hits(&self, origin: Point3, direction: Vector3, tol: f64) -> Option<Vec<ShapeRayHit>> {
+        let caster = FacesRayCaster::new(&[Shell::from(vec![self.clone()])], tol)?;
+        Some(caster.ray_hits(origin, direction, tol).0)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> ShapeRayCasting for Shell<Point3, C, S> {
+    fn ray_hits(&self, origin: Point3, direction: Vector3, tol: f64) -> Option<Vec<ShapeRayHit>> {
+        let caster = FacesRayCaster::new(std::slice::from_ref(self), tol)?;
+        Some(caster.ray_hits(origin, direction, tol).0)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> ShapeRayCasting for Solid<Point3, C, S> {
+    fn ray_hits(&self, origin: Point3, direction: Vector3, tol: f64) -> Option<Vec<ShapeRayHit>> {
+        let caster = FacesRayCaster::new(self.boundaries(), tol)?;
+        Some(caster.ray_hits(origin, direction, tol).0)
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> PointInSolid for Solid<Point3, C, S> {
+    fn contains(&self, point: Point3, tol: f64) -> Option<PointContainment> {
+        SolidClassifier::new(self, tol)?.classify(point)
+    }
+}
+
+/// the number of the directions of the rays tried by [`SolidClassifier::classify`]
+const RAY_TRIALS: usize = 32;
+
+/// Classifier of points by a solid on the exact surfaces.
+///
+/// The solid is tessellated only once at the creation, and the bounding volume hierarchy of
+/// the tessellation is shared by all classifications.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_modeling::*;
+///
+/// // the unit cube
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let classifier = SolidClassifier::new(&cube, 0.01).unwrap();
+/// let inside = (0..10)
+///     .map(|i| Point3::new(0.1 * i as f64 + 0.05, 0.5, 0.5))
+///     .all(|pt| classifier.classify(pt) == Some(PointContainment::Inside));
+/// assert!(inside);
+/// let res = classifier.classify(Point3::new(1.5, 0.5, 0.5));
+/// assert_eq!(res, Some(PointContainment::Outside));
+/// ```
+pub struct SolidClassifier<'a, S> {
+    caster: FacesRayCaster<S>,
+    cells: Vec<Cell<'a>>,
+    tol: f64,
+}
+
+impl<S> std::fmt::Debug for SolidClassifier<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolidClassifier")
+            .field("faces", &self.caster.surfaces.len())
+            .field("tol", &self.tol)
+            .finish()
+    }
+}
+
+impl<'a, S: MeshableSurface + 'a> SolidClassifier<'a, S> {
+    /// Creates the classifier by tessellating `solid` with tolerance `tol`.
+    /// Returns `None` if the tessellation fails.
+    pub fn new<C: PolylineableCurve + 'a>(solid: &Solid<Point3, C, S>, tol: f64) -> Option<Self> {
+        let shells = solid.boundaries();
+        let meshed = shells
+            .iter()
+            .map(|shell| shell.triangulation(tol))
+            .collect::<Option<Vec<_>>>()?;
+        Some(SolidClassifier {
+            caster: FacesRayCaster::from_meshed(shells, &meshed),
+            cells: meshed_shell_cells(shells, &meshed, tol),
+            tol,
+        })
+    }
+
+    /// Classifies `point` into the interior, the exterior or the boundary of the solid.
+    ///
+    /// The point is on the boundary if its distance from the exact faces is less than
+    /// `TOLERANCE`. If the point is within `tol` from the interior of a face, it is classified
+    /// by the side of the face. Otherwise, the point is inside if a ray from the point crosses
+    /// the exact faces an odd number of times. The ray is cast to the closest point on the
+    /// faces first, and then in the directions spread on the sphere. The rays touching faces,
+    /// or passing through edges or near the boundaries of faces are retried, since their
+    /// crossings may be missed. The rays grazing the faces near the point are also skipped.
+    /// Returns `None` if all rays are such ones.
+    pub fn classify(&self, point: Point3) -> Option<PointContainment> {
+        let closest = closest_points(&self.cells, &[Cell::vertex(point)], self.tol)?;
+        if closest.distance < TOLERANCE {
+            return Some(PointContainment::OnBoundary);
+        }
+        // The point near a face may be between the exact face and the tessellation, where the
+        // crossings of the rays are uncertain.
+        let near = closest.distance < self.tol;
+        if near {
+            if let Some(normal) = closest_normal(&self.cells, point, closest.distance, self.tol) {
+                return match (closest.point0 - point).dot(normal) > 0.0 {
+                    true => Some(PointContainment::Inside),
+                    false => Some(PointContainment::Outside),
+                };
+            }
+        }
+        // The ray to the closest point crosses the faces transversally. It is tilted slightly
+        // so as not to run along the edges on the planes of symmetry.
+        let normal = (closest.point0 - point).normalize();
+        let tilt = 0.01 * Vector3::new(0.2673, -0.8018, -0.5345);
+        let directions = std::iter::once(normal + tilt).chain((0..RAY_TRIALS).map(ray_direction));
+        // The crossings of the rays grazing the faces near the point may be missed.
+        let mut directions =
+            directions.filter(|direction| !near || direction.dot(normal).abs() > 0.5);
+        directions.find_map(|direction| {
+            let (hits, uncertain) = self.caster.ray_hits(point, direction, self.tol);
+            let tangent = hits.iter().any(|hit| hit.crossing == RayCrossing::Tangent);
+            // the ray through the edge between two faces
+            let through_edge = hits.windows(2).any(|w| w[0].distance.near(&w[1].distance));
+            if tangent || through_edge || uncertain.is_some() {
+                return None;
+            }
+            match hits.len() % 2 {
+                1 => Some(PointContainment::Inside),
+                _ => Some(PointContainment::Outside),
+            }
+        })
+    }
+}
+
+/// The `i`-th direction of the spherical Fibonacci lattice, which spreads the directions
+/// on the sphere without any symmetry of the axes.
+fn ray_direction(i: usize) -> Vector3 {
+    let golden_angle = std::f64::consts::PI * (3.0 - f64::sqrt(5.0));
+    let z = 1.0 - (2 * i + 1) as f64 / RAY_TRIALS as f64;
+    let r = f64::sqrt(1.0 - z * z);
+    let phi = golden_angle * i as f64 + 0.5;
+    Vector3::new(r * f64::cos(phi), r * f64::sin(phi), z)
+}
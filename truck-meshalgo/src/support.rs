@@ -0,0 +1,217 @@
+use crate::analyzers::MeshBVH;
+use crate::*;
+use std::collections::HashSet;
+
+/// Configures of support structures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SupportDescriptor {
+    /// the direction in which the layers are built up. Default is `Vector3::unit_z()`.
+    pub build_direction: Vector3,
+    /// the maximum angle of overhangs from the vertical printable without supports.
+    /// Default is 45 degrees.
+    pub overhang_angle: Rad<f64>,
+    /// the interval of pillars on the grid orthogonal to the build direction. Default is `2.0`.
+    pub spacing: f64,
+    /// the radius of pillars. Default is `0.4`.
+    pub radius: f64,
+    /// the radius of the tips of pillars touching the overhangs. Default is `0.15`.
+    pub tip_radius: f64,
+    /// the length of the tapered tips of pillars. Default is `1.0`.
+    pub tip_length: f64,
+    /// the number of sides of the cross sections of pillars, at least 3. Default is `8`.
+    pub sides: usize,
+}
+
+impl Default for SupportDescriptor {
+    #[inline(always)]
+    fn default() -> SupportDescriptor {
+        SupportDescriptor {
+            build_direction: Vector3::unit_z(),
+            overhang_angle: Deg(45.0).into(),
+            spacing: 2.0,
+            radius: 0.4,
+            tip_radius: 0.15,
+            tip_length: 1.0,
+            sides: 8,
+        }
+    }
+}
+
+/// Generates support structures for 3D printing.
+pub trait SupportGeneration {
+    /// Returns the indices of the faces whose overhangs from the vertical exceed
+    /// the overhang angle, in the order of `Faces::face_iter`.
+    /// The mesh is assumed to be oriented outward.
+    fn overhanging_faces(&self, descriptor: &SupportDescriptor) -> Vec<usize>;
+    /// Returns the mesh of pillars beneath the overhanging faces.
+    ///
+    /// The pillars stand on the grid with the interval `descriptor.spacing`, from the build plate
+    /// at the bottom of the mesh or from the part below, up to the overhanging faces.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // a square roof at the height 5, facing downward
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 5.0),
+    ///     Point3::new(0.0, 3.9, 5.0),
+    ///     Point3::new(3.9, 3.9, 5.0),
+    ///     Point3::new(3.9, 0.0, 5.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[1, 2, 3, 4]]);
+    /// let roof = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let descriptor = SupportDescriptor::default();
+    /// assert_eq!(roof.overhanging_faces(&descriptor), vec![0]);
+    /// let support = roof.support_structure(&descriptor);
+    /// // 2 x 2 pillars from the build plate to the roof
+    /// let bdb = support.bounding_box();
+    /// assert!(bdb.min()[2].near(&0.0));
+    /// assert!(bdb.max()[2].near(&5.0));
+    /// assert_eq!(support.faces().len(), 4 * (2 + 2 * 8));
+    /// ```
+    fn support_structure(&self, descriptor: &SupportDescriptor) -> PolygonMesh;
+}
+
+impl SupportGeneration for PolygonMesh {
+    fn overhanging_faces(&self, descriptor: &SupportDescriptor) -> Vec<usize> {
+        let up = descriptor.build_direction.normalize();
+        let threshold = descriptor.overhang_angle.sin();
+        self.face_iter()
+            .enumerate()
+            .filter(|(i, face)| {
+                let normal = FaceNormal::new(self.positions(), face, *i).normal;
+                -normal.dot(up) > threshold
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn support_structure(&self, descriptor: &SupportDescriptor) -> PolygonMesh {
+        let up = descriptor.build_direction.normalize();
+        let (e0, e1) = orthonormal_basis(up);
+        let plate = self
+            .positions()
+            .iter()
+            .map(|p| p.to_vec().dot(up))
+            .fold(f64::INFINITY, f64::min);
+        let overhangs: HashSet<usize> = self.overhanging_faces(descriptor).into_iter().collect();
+        let bvh = MeshBVH::new(self);
+        let spacing = descriptor.spacing;
+        let mut grid = HashSet::new();
+        let mut support = PolygonMesh::default();
+        let positions = self.positions();
+        let triangles = self
+            .face_iter()
+            .enumerate()
+            .filter(|(i, _)| overhangs.contains(i))
+            .flat_map(|(_, face)| {
+                (2..face.len()).map(move |i| {
+                    [
+                        positions[face[0].pos],
+                        positions[face[i - 1].pos],
+                        positions[face[i].pos],
+                    ]
+                })
+            });
+        for tri in triangles {
+            let project = |p: Point3| Point2::new(p.to_vec().dot(e0), p.to_vec().dot(e1));
+            let uv = [project(tri[0]), project(tri[1]), project(tri[2])];
+            let bdb: BoundingBox<Point2> = uv.iter().collect();
+            let (i0, i1) = (
+                (bdb.min()[0] / spacing).ceil() as i64,
+                (bdb.max()[0] / spacing).floor() as i64,
+            );
+            let (j0, j1) = (
+                (bdb.min()[1] / spacing).ceil() as i64,
+                (bdb.max()[1] / spacing).floor() as i64,
+            );
+            for (i, j) in (i0..=i1).flat_map(|i| (j0..=j1).map(move |j| (i, j))) {
+                let c = Point2::new(i as f64 * spacing, j as f64 * spacing);
+                let bary = match barycentric(uv, c) {
+                    Some(bary) => bary,
+                    None => continue,
+                };
+                let top = Point3::from_vec(
+                    tri[0].to_vec() * bary[0]
+                        + tri[1].to_vec() * bary[1]
+                        + tri[2].to_vec() * bary[2],
+                );
+                let height = top.to_vec().dot(up);
+                if !grid.insert((i, j, (height / spacing).round() as i64)) {
+                    continue;
+                }
+                let bottom = match bvh.ray_cast(top - up * TOLERANCE, -up) {
+                    Some(hit) => hit.point(),
+                    None => top - up * (height - plate),
+                };
+                if top.distance(bottom) > TOLERANCE {
+                    support.merge(pillar(bottom, top, (e0, e1), descriptor));
+                }
+            }
+        }
+        support
+    }
+}
+
+fn orthonormal_basis(up: Vector3) -> (Vector3, Vector3) {
+    let other = match up.x.abs() < 0.9 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let e0 = other.cross(up).normalize();
+    (e0, up.cross(e0))
+}
+
+/// the barycentric coordinates of `c` in the triangle, or `None` if `c` is outside
+fn barycentric(tri: [Point2; 3], c: Point2) -> Option<[f64; 3]> {
+    let (a, b) = (tri[1] - tri[0], tri[2] - tri[0]);
+    let det = a.x * b.y - a.y * b.x;
+    if det.abs() < TOLERANCE2 {
+        return None;
+    }
+    let d = c - tri[0];
+    let u = (d.x * b.y - d.y * b.x) / det;
+    let v = (a.x * d.y - a.y * d.x) / det;
+    match u >= 0.0 && v >= 0.0 && u + v <= 1.0 {
+        true => Some([1.0 - u - v, u, v]),
+        false => None,
+    }
+}
+
+/// a prism from `bottom` with the tapered tip touching `top`
+fn pillar(
+    bottom: Point3,
+    top: Point3,
+    basis: (Vector3, Vector3),
+    descriptor: &SupportDescriptor,
+) -> PolygonMesh {
+    let n = usize::max(descriptor.sides, 3);
+    let length = top.distance(bottom);
+    let axis = (top - bottom) / length;
+    let tip_length = f64::min(descriptor.tip_length, length);
+    let rings = [
+        (bottom, descriptor.radius),
+        (top - axis * tip_length, descriptor.radius),
+        (top, descriptor.tip_radius),
+    ];
+    let positions: Vec<Point3> = rings
+        .iter()
+        .flat_map(|(center, radius)| {
+            (0..n).map(move |k| {
+                let t = 2.0 * std::f64::consts::PI * k as f64 / n as f64;
+                center + (basis.0 * f64::cos(t) + basis.1 * f64::sin(t)) * *radius
+            })
+        })
+        .collect();
+    let mut faces = Faces::default();
+    faces.push((0..n).rev().collect::<Vec<_>>());
+    for r in 0..2 {
+        (0..n).for_each(|k| {
+            let (k0, k1) = (r * n + k, r * n + (k + 1) % n);
+            faces.push([k0, k1, k1 + n, k0 + n]);
+        });
+    }
+    faces.push((2 * n..3 * n).collect::<Vec<_>>());
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
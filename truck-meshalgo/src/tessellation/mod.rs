@@ -1,6 +1,8 @@
 use crate::*;
 use spade::delaunay::*;
 use spade::kernels::*;
+use thiserror::Error;
+use truck_polymesh::progress::{Progress, ProgressCounter};
 use truck_topology::{*, Vertex};
 
 /// Gathered the traits used in tessellation.
@@ -16,6 +18,101 @@ impl<S: ParametricSurface3D + Invertible + ParameterDivision2D + SearchParameter
 
 type PolylineCurve = truck_polymesh::PolylineCurve<Point3>;
 
+/// Failures of tessellating shapes.
+#[derive(Debug, PartialEq, Error)]
+pub enum TessellationError {
+    /// The parameter of the point on the boundary of the face is found on the surface neither
+    /// by `SearchParameter::search_parameter` nor by the subdivision of the parameter region.
+    #[error("The point {point:?} on the edge {edge} of the wire {wire} of the face {face} is not on the surface.")]
+    ParameterNotFound {
+        /// the index of the face in the shape
+        face: usize,
+        /// the index of the wire in the absolute boundaries of the face
+        wire: usize,
+        /// the index of the edge in the wire
+        edge: usize,
+        /// the point whose parameter is not found
+        point: Point3,
+    },
+    /// The tessellated boundaries are not a solid.
+    #[error(transparent)]
+    FromTopology(#[from] truck_topology::errors::Error),
+    /// The tessellation by [`MeshableShape::triangulation`] fails without the reason.
+    #[error("The tessellation fails.")]
+    Unknown,
+    /// The tessellation is cancelled by [`Progress`].
+    ///
+    /// [`Progress`]: ../../truck_polymesh/progress/trait.Progress.html
+    #[error("The tessellation is cancelled.")]
+    Cancelled,
+}
+
+/// The quality of the tessellation of a face.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FaceQuality {
+    /// the number of the triangles
+    pub triangles: usize,
+    /// the minimum angle of the triangles in radians, which is `π` if there are no triangles
+    pub min_angle: f64,
+    /// the estimate of the maximum distance between the triangles and the surface,
+    /// measured at the centroids and the midpoints of the edges of the triangles
+    pub max_deviation: f64,
+}
+
+/// The report of the tessellation by [`MeshableShape::triangulation_with_report`].
+///
+/// [`MeshableShape::triangulation_with_report`]: ./trait.MeshableShape.html#method.triangulation_with_report
+#[derive(Debug, Default)]
+pub struct TessellationReport {
+    /// the qualities of the faces in the order of `face_iter`, `None` for the failed faces
+    pub faces: Vec<Option<FaceQuality>>,
+    /// the failures of the tessellation
+    pub failures: Vec<TessellationError>,
+}
+
+impl TessellationReport {
+    /// Returns the total number of the triangles.
+    #[inline(always)]
+    pub fn triangles(&self) -> usize { self.faces.iter().flatten().map(|q| q.triangles).sum() }
+    /// Returns the minimum angle of all the triangles in radians.
+    #[inline(always)]
+    pub fn min_angle(&self) -> f64 {
+        let iter = self.faces.iter().flatten();
+        iter.fold(std::f64::consts::PI, |min, q| f64::min(min, q.min_angle))
+    }
+    /// Returns the estimate of the maximum distance between the triangles and the surfaces.
+    #[inline(always)]
+    pub fn max_deviation(&self) -> f64 {
+        self.faces.iter().flatten().fold(0.0, |max, q| f64::max(max, q.max_deviation))
+    }
+    /// Returns the indices of the failed faces.
+    #[inline(always)]
+    pub fn failed_faces(&self) -> Vec<usize> {
+        let iter = self.faces.iter().enumerate();
+        iter.filter(|(_, q)| q.is_none()).map(|(i, _)| i).collect()
+    }
+}
+
+impl TessellationError {
+    /// shifts the index of the face by `offset`
+    fn face_offset(self, offset: usize) -> Self {
+        match self {
+            TessellationError::ParameterNotFound {
+                face,
+                wire,
+                edge,
+                point,
+            } => TessellationError::ParameterNotFound {
+                face: face + offset,
+                wire,
+                edge,
+                point,
+            },
+            error => error,
+        }
+    }
+}
+
 /// Trait for converting tessellated shape into polygon.
 pub trait MeshedShape {
     /// Converts tessellated shape into polygon.
@@ -42,6 +139,69 @@ impl MeshedShape for Solid<Point3, PolylineCurve, PolygonMesh> {
     }
 }
 
+/// Trait for simplifying tessellated shapes face by face.
+pub trait DecimatableShape: Sized {
+    /// Decimates the mesh of each face into `ratio` of its triangles by the edge collapses
+    /// of [`ProgressiveMesh`], keeping the polylines of the edges.
+    ///
+    /// The vertices on the boundaries of the meshes of faces are never removed, so the meshes
+    /// of the adjacent faces still share the polylines and the faces are not changed
+    /// topologically. The returned shape shares the vertices and the edges with `self`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::{builder, Solid};
+    /// use truck_topology::shell::ShellCondition;
+    ///
+    /// // torus
+    /// let v = builder::vertex(Point3::new(2.0, 0.0, 0.0));
+    /// let circle = builder::rsweep(&v, Point3::new(3.0, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    /// let torus = builder::rsweep(&circle, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let torus = Solid::new(vec![torus]);
+    /// let meshed = torus.triangulation(0.005).unwrap();
+    /// let decimated = meshed.decimation(0.1);
+    ///
+    /// let mut poly = decimated.into_polygon();
+    /// assert!(poly.faces().len() < meshed.into_polygon().faces().len() / 5);
+    /// poly.put_together_same_attrs()
+    ///     .remove_degenerate_faces()
+    ///     .remove_unused_attrs();
+    /// assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    /// ```
+    ///
+    /// [`ProgressiveMesh`]: ../progressive/struct.ProgressiveMesh.html
+    fn decimation(&self, ratio: f64) -> Self;
+}
+
+impl DecimatableShape for Shell<Point3, PolylineCurve, PolygonMesh> {
+    fn decimation(&self, ratio: f64) -> Self {
+        self.face_iter()
+            .map(|face| {
+                let polygon = face.read_surface();
+                let triangles: usize = polygon.face_iter().map(|face| face.len() - 2).sum();
+                let min_faces = (triangles as f64 * ratio).ceil() as usize;
+                let polygon = progressive::decimation(&polygon, min_faces);
+                let mut new_face = Face::debug_new(face.absolute_boundaries().clone(), polygon);
+                if !face.orientation() {
+                    new_face.invert();
+                }
+                new_face
+            })
+            .collect()
+    }
+}
+
+impl DecimatableShape for Solid<Point3, PolylineCurve, PolygonMesh> {
+    fn decimation(&self, ratio: f64) -> Self {
+        let boundaries = self
+            .boundaries()
+            .iter()
+            .map(|shell| shell.decimation(ratio))
+            .collect();
+        Solid::new_unchecked(boundaries)
+    }
+}
+
 /// Trait for tessellating `Shell` and `Solid` in `truck-modeling`.
 pub trait MeshableShape {
     /// Shape whose edges are made polylines and faces polygon surface.
@@ -49,6 +209,12 @@ pub trait MeshableShape {
     /// Tessellates shapes. The division of curves and surfaces are by `ParameterDivision1D` and `ParameterDivision2D`,
     /// and the constrained Delauney triangulation is based on the crate [`spade`](https://crates.io/crates/spade).
     ///
+    /// The faces which can be tessellated into grids by
+    /// [`StructuredMeshableFace::structured_tessellation`] are meshed by the grids, each of
+    /// whose cells is divided into two triangles, instead of the triangulations.
+    ///
+    /// [`StructuredMeshableFace::structured_tessellation`]: ./trait.StructuredMeshableFace.html#tymethod.structured_tessellation
+    ///
     /// # Remarks
     ///
     /// The tessellated mesh is not necessarily closed even if `self` is `Solid`.
@@ -73,25 +239,575 @@ pub trait MeshableShape {
     /// assert!(mesh.shell_condition() == ShellCondition::Closed);
     /// ```
     fn triangulation(&self, tol: f64) -> Option<Self::MeshedShape>;
+    /// Tessellates shapes with reporting the progress.
+    ///
+    /// `progress` receives the number of tessellated faces and the total number of faces.
+    /// If `progress` cancels the work, returns `None`.
+    /// The default implementation calls [`MeshableShape::triangulation`] and reports no progress.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// let mut log = Vec::new();
+    /// let progress = |i, n| {
+    ///     log.push((i, n));
+    ///     true
+    /// };
+    /// cube.triangulation_with_progress(0.01, progress).unwrap();
+    /// assert_eq!(log, (1..=6).map(|i| (i, 6)).collect::<Vec<_>>());
+    ///
+    /// // cancels after three faces
+    /// assert!(cube.triangulation_with_progress(0.01, |i, _| i < 3).is_none());
+    /// ```
+    #[inline(always)]
+    fn triangulation_with_progress<P: Progress>(
+        &self,
+        tol: f64,
+        _progress: P,
+    ) -> Option<Self::MeshedShape> {
+        self.triangulation(tol)
+    }
+    /// Tessellates shapes like [`MeshableShape::triangulation`], returning the reason of
+    /// the failure.
+    ///
+    /// The parameters of the points on the boundaries which are not found by
+    /// `SearchParameter::search_parameter` are searched again by subdividing the region of
+    /// the parameters of the other points, and the face fails only if the point is not on the
+    /// surface even then.
+    /// # Failures
+    /// - Returns [`TessellationError::ParameterNotFound`] with the point and the indices of the
+    ///   face, the wire and the edge if the parameter of a point on a boundary is not found.
+    /// - Returns [`TessellationError::FromTopology`] if the tessellated boundaries of `Solid`
+    ///   are not a solid.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// let meshed = cube.try_triangulation(0.01).unwrap();
+    /// assert_eq!(meshed.into_polygon().faces().len(), 12);
+    /// ```
+    ///
+    /// [`TessellationError::ParameterNotFound`]: ./enum.TessellationError.html#variant.ParameterNotFound
+    /// [`TessellationError::FromTopology`]: ./enum.TessellationError.html#variant.FromTopology
+    #[inline(always)]
+    fn try_triangulation(
+        &self,
+        tol: f64,
+    ) -> std::result::Result<Self::MeshedShape, TessellationError> {
+        self.try_triangulation_with_progress(tol, |_, _| true)
+    }
+    /// Tessellates shapes with reporting the progress like
+    /// [`MeshableShape::triangulation_with_progress`], returning the reason of the failure
+    /// like [`MeshableShape::try_triangulation`].
+    ///
+    /// Returns [`TessellationError::Cancelled`] if `progress` cancels the work.
+    /// The default implementation returns [`TessellationError::Unknown`] if
+    /// [`MeshableShape::triangulation_with_progress`] fails.
+    ///
+    /// [`TessellationError::Cancelled`]: ./enum.TessellationError.html#variant.Cancelled
+    /// [`TessellationError::Unknown`]: ./enum.TessellationError.html#variant.Unknown
+    #[inline(always)]
+    fn try_triangulation_with_progress<P: Progress>(
+        &self,
+        tol: f64,
+        progress: P,
+    ) -> std::result::Result<Self::MeshedShape, TessellationError> {
+        self.triangulation_with_progress(tol, progress)
+            .ok_or(TessellationError::Unknown)
+    }
+    /// Tessellates shapes like [`MeshableShape::triangulation`] with the report of the quality
+    /// of each face, for tuning the tolerance.
+    ///
+    /// All faces are tessellated even if some faces fail, and the report has all failures.
+    /// The tessellated shape is `None` if some faces fail.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let cylinder = builder::tsweep(&circle, Vector3::unit_z());
+    ///
+    /// let (coarse, report0) = cylinder.triangulation_with_report(0.1);
+    /// assert!(coarse.is_some() && report0.failures.is_empty());
+    /// assert!(report0.max_deviation() < 0.1);
+    ///
+    /// let (_, report1) = cylinder.triangulation_with_report(0.01);
+    /// assert!(report1.max_deviation() < 0.01);
+    /// assert!(report0.triangles() < report1.triangles());
+    /// ```
+    ///
+    /// The default implementation reports only the failure of
+    /// [`MeshableShape::try_triangulation`], without the qualities of the faces.
+    fn triangulation_with_report(
+        &self,
+        tol: f64,
+    ) -> (Option<Self::MeshedShape>, TessellationReport) {
+        match self.try_triangulation(tol) {
+            Ok(meshed) => (Some(meshed), TessellationReport::default()),
+            Err(error) => {
+                let report = TessellationReport {
+                    faces: Vec::new(),
+                    failures: vec![error],
+                };
+                (None, report)
+            }
+        }
+    }
 }
 
 impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for Shell<Point3, C, S> {
     type MeshedShape = Shell<Point3, PolylineCurve, PolygonMesh>;
+    #[inline(always)]
     fn triangulation(&self, tol: f64) -> Option<Self::MeshedShape> {
-        triangulation::tessellation(self, tol)
+        self.try_triangulation(tol).ok()
+    }
+    #[inline(always)]
+    fn triangulation_with_progress<P: Progress>(
+        &self,
+        tol: f64,
+        progress: P,
+    ) -> Option<Self::MeshedShape> {
+        self.try_triangulation_with_progress(tol, progress).ok()
+    }
+    fn try_triangulation_with_progress<P: Progress>(
+        &self,
+        tol: f64,
+        progress: P,
+    ) -> std::result::Result<Self::MeshedShape, TessellationError> {
+        let mut counter = ProgressCounter::new(self.len(), progress);
+        triangulation::tessellation(self, tol, &|_, _, _| None, &mut counter)
+    }
+    fn triangulation_with_report(
+        &self,
+        tol: f64,
+    ) -> (Option<Self::MeshedShape>, TessellationReport) {
+        triangulation::tessellation_with_report(self, tol)
     }
 }
 
 impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for Solid<Point3, C, S> {
     type MeshedShape = Solid<Point3, PolylineCurve, PolygonMesh>;
+    #[inline(always)]
     fn triangulation(&self, tol: f64) -> Option<Self::MeshedShape> {
+        self.try_triangulation(tol).ok()
+    }
+    #[inline(always)]
+    fn triangulation_with_progress<P: Progress>(
+        &self,
+        tol: f64,
+        progress: P,
+    ) -> Option<Self::MeshedShape> {
+        self.try_triangulation_with_progress(tol, progress).ok()
+    }
+    fn try_triangulation_with_progress<P: Progress>(
+        &self,
+        tol: f64,
+        progress: P,
+    ) -> std::result::Result<Self::MeshedShape, TessellationError> {
+        let len = self.boundaries().iter().map(|shell| shell.len()).sum();
+        let mut counter = ProgressCounter::new(len, progress);
         let boundaries = self
             .boundaries()
             .iter()
-            .map(|shell| shell.triangulation(tol))
+            .map(|shell| {
+                let offset = counter.done();
+                triangulation::tessellation(shell, tol, &|_, _, _| None, &mut counter)
+                    .map_err(|error| error.face_offset(offset))
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Solid::try_new(boundaries)?)
+    }
+    fn triangulation_with_report(
+        &self,
+        tol: f64,
+    ) -> (Option<Self::MeshedShape>, TessellationReport) {
+        let mut report = TessellationReport::default();
+        let mut boundaries = Vec::new();
+        for shell in self.boundaries() {
+            let (meshed, shell_report) = triangulation::tessellation_with_report(shell, tol);
+            let offset = report.faces.len();
+            report.faces.extend(shell_report.faces);
+            let failures = shell_report.failures.into_iter();
+            report.failures.extend(failures.map(|error| error.face_offset(offset)));
+            boundaries.extend(meshed);
+        }
+        if !report.failures.is_empty() {
+            return (None, report);
+        }
+        match Solid::try_new(boundaries) {
+            Ok(solid) => (Some(solid), report),
+            Err(error) => {
+                report.failures.push(error.into());
+                (None, report)
+            }
+        }
+    }
+}
+
+/// Trait for tessellating untrimmed faces into grids.
+pub trait StructuredMeshableFace {
+    /// Tessellates the face into the grid of the parameters of the surface, with the uv
+    /// coordinates and the normals.
+    ///
+    /// The face can be tessellated into the grid if its boundary is the rectangle of
+    /// the parameters, e.g. the natural boundary of the surface, whose opposite sides are divided
+    /// by the polylines of the edges at the same parameters, and the grid is within `tol` from
+    /// the surface. Otherwise, e.g. the face is trimmed, returns `None`.
+    /// The quadrangles of the grid are oriented by the orientation of the face.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    ///
+    /// // a side of the cylinder, a quarter of the circle swept along the z-axis
+    /// let v0 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let v1 = builder::vertex(Point3::new(0.0, 1.0, 0.0));
+    /// let transit = Point3::new(f64::sqrt(0.5), f64::sqrt(0.5), 0.0);
+    /// let arc = builder::circle_arc(&v0, &v1, transit);
+    /// let face = builder::tsweep(&arc, Vector3::unit_z());
+    ///
+    /// let mesh = face.structured_tessellation(0.01).unwrap();
+    /// // the grid is divided only along the arc
+    /// assert_eq!(mesh.positions()[0].len(), 2);
+    /// assert!(mesh.positions().len() > 2);
+    ///
+    /// // the face trimmed by a triangle is not a grid
+    /// let v0 = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+    /// let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let v2 = builder::vertex(Point3::new(0.0, 1.0, 0.0));
+    /// let wire: Wire = vec![
+    ///     builder::line(&v0, &v1),
+    ///     builder::line(&v1, &v2),
+    ///     builder::line(&v2, &v0),
+    /// ].into();
+    /// let triangle = builder::try_attach_plane(&vec![wire]).unwrap();
+    /// assert!(triangle.structured_tessellation(0.01).is_none());
+    /// ```
+    fn structured_tessellation(&self, tol: f64) -> Option<StructuredMesh>;
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> StructuredMeshableFace for Face<Point3, C, S> {
+    #[inline(always)]
+    fn structured_tessellation(&self, tol: f64) -> Option<StructuredMesh> {
+        triangulation::structured_tessellation(self, tol)
+    }
+}
+
+/// Trait for tessellating shapes with the pcurves of the edges.
+pub trait PCurveMeshableShape<Q>: MeshableShape {
+    /// the pcurves of the edges on the faces
+    type PCurves;
+    /// Tessellates shapes like [`MeshableShape::triangulation`], where the points of
+    /// the edges with the pcurves are mapped into the parameter spaces of the surfaces by
+    /// the pcurves instead of searching the parameters.
+    ///
+    /// The pcurves determine the parameters uniquely even on the seams of the closed surfaces,
+    /// where the searched parameters are ambiguous. The edges without pcurves are tessellated
+    /// by searching the parameters as in [`MeshableShape::triangulation`].
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::*;
+    /// use truck_topology::PCurveMap;
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    ///
+    /// // the pcurves of the edges, lines in the parameter spaces
+    /// let mut pcurves = PCurveMap::<Curve, Surface, BSplineCurve<Point2>>::new();
+    /// for face in cube.face_iter() {
+    ///     let surface = face.get_surface();
+    ///     for edge in face.absolute_boundaries().iter().flatten() {
+    ///         let curve = edge.get_curve();
+    ///         let (t0, t1) = curve.parameter_range();
+    ///         let uv0 = surface.search_parameter(curve.subs(t0), None, 100).unwrap();
+    ///         let uv1 = surface.search_parameter(curve.subs(t1), None, 100).unwrap();
+    ///         let knot_vec = KnotVec::from(vec![t0, t0, t1, t1]);
+    ///         let pcurve = BSplineCurve::new(knot_vec, vec![uv0.into(), uv1.into()]);
+    ///         pcurves.insert(edge.id(), face.id(), pcurve);
+    ///     }
+    /// }
+    /// assert!(pcurves.desynchronized(&cube.boundaries()[0], 1.0e-6).is_empty());
+    ///
+    /// let meshed = cube.triangulation_with_pcurves(0.01, &pcurves).unwrap();
+    /// let mut polygon = meshed.into_polygon();
+    /// polygon.put_together_same_attrs();
+    /// assert_eq!(polygon.shell_condition(), ShellCondition::Closed);
+    /// ```
+    fn triangulation_with_pcurves(
+        &self,
+        tol: f64,
+        pcurves: &Self::PCurves,
+    ) -> Option<Self::MeshedShape>;
+}
+
+impl<C, S, Q> PCurveMeshableShape<Q> for Shell<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+    Q: ParametricCurve<Point = Point2>,
+{
+    type PCurves = PCurveMap<C, S, Q>;
+    fn triangulation_with_pcurves(
+        &self,
+        tol: f64,
+        pcurves: &PCurveMap<C, S, Q>,
+    ) -> Option<Self::MeshedShape> {
+        let pcurve_uv = |edge: &Edge<Point3, C>, face: &Face<Point3, C, S>, t| {
+            let pcurve = pcurves.get(edge.id(), face.id())?;
+            Some(pcurve.subs(t))
+        };
+        let mut counter = ProgressCounter::new(0, |_, _| true);
+        triangulation::tessellation(self, tol, &pcurve_uv, &mut counter).ok()
+    }
+}
+
+impl<C, S, Q> PCurveMeshableShape<Q> for Solid<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+    Q: ParametricCurve<Point = Point2>,
+{
+    type PCurves = PCurveMap<C, S, Q>;
+    fn triangulation_with_pcurves(
+        &self,
+        tol: f64,
+        pcurves: &PCurveMap<C, S, Q>,
+    ) -> Option<Self::MeshedShape> {
+        let boundaries = self
+            .boundaries()
+            .iter()
+            .map(|shell| shell.triangulation_with_pcurves(tol, pcurves))
             .collect::<Option<Vec<_>>>()?;
         Solid::try_new(boundaries).ok()
     }
 }
 
+/// Regions of interest re-tessellated by `RemeshableShape::region_retriangulation`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RemeshRegion<FaceID> {
+    /// the inside of the bounding box
+    BoundingBox(BoundingBox<Point3>),
+    /// the bounding box of the tessellated face enlarged by `margin` in each direction
+    NearFace {
+        /// the selected face
+        face: FaceID,
+        /// the margin around the face
+        margin: f64,
+    },
+}
+
+/// Trait for re-tessellating only the modified parts of `Shell` and `Solid`.
+pub trait RemeshableShape: MeshableShape {
+    /// ID of faces
+    type FaceID;
+    /// ID of edges
+    type EdgeID;
+    /// Re-tessellates the faces in `modified_faces` and the faces adjacent to the edges
+    /// in `modified_edges`, and patches `meshed`, which is tessellated from `self` in advance.
+    /// The other faces and edges of `meshed` are reused as they are.
+    ///
+    /// Returns the number of re-tessellated faces, or `None` if the re-tessellation fails
+    /// or the topology of `self` does not match `meshed`. In that case, `meshed` is not changed.
+    /// # Remarks
+    /// If a vertex is moved, all edges incident to the vertex have to be in `modified_edges`.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::{builder, Surface, Plane};
+    ///
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let cube = builder::tsweep(&f, Vector3::unit_z());
+    /// let mut meshed = cube.triangulation(0.01).unwrap();
+    ///
+    /// // modify the geometry of the top face without changing its shape
+    /// let top = &cube.boundaries()[0][5];
+    /// let plane = Plane::new(
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    /// );
+    /// top.set_surface(Surface::Plane(plane));
+    ///
+    /// let count = cube.retriangulation(&mut meshed, &[top.id()], &[], 0.01);
+    /// assert_eq!(count, Some(1));
+    /// assert_eq!(meshed.boundaries()[0].len(), 6);
+    /// ```
+    fn retriangulation(
+        &self,
+        meshed: &mut Self::MeshedShape,
+        modified_faces: &[Self::FaceID],
+        modified_edges: &[Self::EdgeID],
+        tol: f64,
+    ) -> Option<usize>;
+    /// Re-tessellates the part of `meshed` inside `region` by the finer tolerance `tol`, and
+    /// stitches it to the surrounding part, for inspecting the region without re-tessellating
+    /// the whole shape. `meshed` is tessellated from `self` in advance.
+    ///
+    /// The faces and edges intersecting the region are re-tessellated: the division points
+    /// by `tol` are inserted inside the region, and the vertices of `meshed` are kept outside it,
+    /// so the re-tessellated faces share the polylines of the edges with the other faces.
+    ///
+    /// Returns the number of re-tessellated faces, or `None` if the re-tessellation fails,
+    /// the topology of `self` does not match `meshed`, or the face of `region` is not found.
+    /// In that case, `meshed` is not changed.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    /// let e = builder::tsweep(&v, Vector3::unit_z());
+    /// let cylinder = builder::rsweep(&e, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    /// let mut meshed = cylinder.triangulation(0.1).unwrap();
+    /// let coarse = meshed.into_polygon();
+    ///
+    /// // the region around the point (1, 0, 0)
+    /// let region: BoundingBox<Point3> = [
+    ///     Point3::new(0.5, -0.3, -0.3),
+    ///     Point3::new(1.5, 0.3, 0.3),
+    /// ]
+    /// .iter()
+    /// .collect();
+    /// let count = cylinder.region_retriangulation(
+    ///     &mut meshed,
+    ///     &RemeshRegion::BoundingBox(region.clone()),
+    ///     0.001,
+    /// );
+    /// assert!(count.unwrap() > 0);
+    /// let fine = meshed.into_polygon();
+    /// assert!(fine.positions().len() > coarse.positions().len());
+    ///
+    /// // the vertices outside the region are kept
+    /// let include = |p: &Point3| {
+    ///     (0..3).all(|i| region.min()[i] <= p[i] && p[i] <= region.max()[i])
+    /// };
+    /// let outside = |mesh: &PolygonMesh| {
+    ///     mesh.positions().iter().filter(|p| !include(p)).count()
+    /// };
+    /// assert_eq!(outside(&fine), outside(&coarse));
+    /// ```
+    fn region_retriangulation(
+        &self,
+        meshed: &mut Self::MeshedShape,
+        region: &RemeshRegion<Self::FaceID>,
+        tol: f64,
+    ) -> Option<usize>;
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> RemeshableShape for Shell<Point3, C, S> {
+    type FaceID = FaceID<S>;
+    type EdgeID = EdgeID<C>;
+    fn retriangulation(
+        &self,
+        meshed: &mut Self::MeshedShape,
+        modified_faces: &[FaceID<S>],
+        modified_edges: &[EdgeID<C>],
+        tol: f64,
+    ) -> Option<usize> {
+        let faces = modified_faces.iter().copied().collect();
+        let edges = modified_edges.iter().copied().collect();
+        let res = triangulation::retessellation(self, meshed, &faces, &edges, tol)?;
+        Some(res.apply(meshed))
+    }
+    fn region_retriangulation(
+        &self,
+        meshed: &mut Self::MeshedShape,
+        region: &RemeshRegion<FaceID<S>>,
+        tol: f64,
+    ) -> Option<usize> {
+        let region = triangulation::region_bounding_box(self, meshed, region)?;
+        let res = triangulation::region_retessellation(self, meshed, &region, tol)?;
+        Some(res.apply(meshed))
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> RemeshableShape for Solid<Point3, C, S> {
+    type FaceID = FaceID<S>;
+    type EdgeID = EdgeID<C>;
+    fn retriangulation(
+        &self,
+        meshed: &mut Self::MeshedShape,
+        modified_faces: &[FaceID<S>],
+        modified_edges: &[EdgeID<C>],
+        tol: f64,
+    ) -> Option<usize> {
+        if self.boundaries().len() != meshed.boundaries().len() {
+            return None;
+        }
+        let faces = modified_faces.iter().copied().collect();
+        let edges = modified_edges.iter().copied().collect();
+        // All shells are re-tessellated before changing `meshed`.
+        let results = self
+            .boundaries()
+            .iter()
+            .zip(meshed.boundaries())
+            .map(|(shell, meshed)| {
+                triangulation::retessellation(shell, meshed, &faces, &edges, tol)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(apply_retessellations(meshed, results))
+    }
+    fn region_retriangulation(
+        &self,
+        meshed: &mut Self::MeshedShape,
+        region: &RemeshRegion<FaceID<S>>,
+        tol: f64,
+    ) -> Option<usize> {
+        if self.boundaries().len() != meshed.boundaries().len() {
+            return None;
+        }
+        let region =
+            self.boundaries()
+                .iter()
+                .zip(meshed.boundaries())
+                .find_map(|(shell, meshed)| {
+                    triangulation::region_bounding_box(shell, meshed, region)
+                })?;
+        let results = self
+            .boundaries()
+            .iter()
+            .zip(meshed.boundaries())
+            .map(|(shell, meshed)| {
+                triangulation::region_retessellation(shell, meshed, &region, tol)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(apply_retessellations(meshed, results))
+    }
+}
+
+/// Applies the re-tessellations of the boundaries, and returns the number of re-tessellated faces.
+fn apply_retessellations(
+    meshed: &mut Solid<Point3, PolylineCurve, PolygonMesh>,
+    results: Vec<triangulation::Retessellation>,
+) -> usize {
+    let mut boundaries = meshed.boundaries().clone();
+    let count = results
+        .into_iter()
+        .zip(&mut boundaries)
+        .map(|(res, shell)| res.apply(shell))
+        .sum();
+    *meshed = Solid::new_unchecked(boundaries);
+    count
+}
+
 mod triangulation;
+pub(crate) use triangulation::Polyline;
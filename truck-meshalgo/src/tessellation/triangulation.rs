@@ -1,16 +1,76 @@
 use super::*;
-use crate::filters::NormalFilters;
-use std::collections::HashMap;
+use crate::analyzers::Topology;
+use crate::filters::{NormalFilters, StructuringFilter};
+use std::collections::{HashMap, HashSet};
 
 type CDT<V, K> = ConstrainedDelaunayTriangulation<V, K>;
 type MeshedShell = Shell<Point3, PolylineCurve, PolygonMesh>;
 
-/// Tessellates faces
-pub(super) fn tessellation<'a, C, S>(shell: &Shell<Point3, C, S>, tol: f64) -> Option<MeshedShell>
+/// the point in the parameter space of the surface of the face at the parameter of the curve
+/// of the edge by the pcurve, or `None` if the edge has no pcurve on the face
+pub(super) type PCurveUV<'a, C, S> =
+    dyn Fn(&Edge<Point3, C>, &Face<Point3, C, S>, f64) -> Option<Point2> + 'a;
+
+#[inline(always)]
+fn no_pcurve<C, S>(_: &Edge<Point3, C>, _: &Face<Point3, C, S>, _: f64) -> Option<Point2> { None }
+
+/// Tessellates faces. `counter` is advanced each time a face is tessellated.
+pub(super) fn tessellation<'a, C, S, P>(
+    shell: &Shell<Point3, C, S>,
+    tol: f64,
+    pcurve_uv: &PCurveUV<'_, C, S>,
+    counter: &mut ProgressCounter<P>,
+) -> std::result::Result<MeshedShell, TessellationError>
+where
+    C: PolylineableCurve + 'a,
+    S: MeshableSurface + 'a,
+    P: Progress, {
+    let (vmap, mut edge_map) = (new_vertex_map(shell), HashMap::new());
+    let mut shell0 = Shell::new();
+    for (i, face) in shell.face_iter().enumerate() {
+        shell0.push(face_tessellation(i, face, &vmap, &mut edge_map, pcurve_uv, tol)?);
+        if !counter.advance(1) {
+            return Err(TessellationError::Cancelled);
+        }
+    }
+    Ok(shell0)
+}
+
+/// Tessellates all faces, even after some faces fail, and reports the qualities of the
+/// tessellated faces and the failures.
+pub(super) fn tessellation_with_report<'a, C, S>(
+    shell: &Shell<Point3, C, S>,
+    tol: f64,
+) -> (Option<MeshedShell>, TessellationReport)
 where
     C: PolylineableCurve + 'a,
     S: MeshableSurface + 'a, {
+    let (vmap, mut edge_map) = (new_vertex_map(shell), HashMap::new());
     let mut shell0 = Shell::new();
+    let mut report = TessellationReport::default();
+    for (i, face) in shell.face_iter().enumerate() {
+        match face_tessellation(i, face, &vmap, &mut edge_map, &no_pcurve, tol) {
+            Ok(meshed) => {
+                let surface = face.read_surface();
+                report.faces.push(Some(face_quality(&*surface, &meshed.get_surface())));
+                shell0.push(meshed);
+            }
+            Err(error) => {
+                report.faces.push(None);
+                report.failures.push(error);
+            }
+        }
+    }
+    match report.failures.is_empty() {
+        true => (Some(shell0), report),
+        false => (None, report),
+    }
+}
+
+/// the map from the vertices of `shell` to the new vertices at the same points
+fn new_vertex_map<C, S>(
+    shell: &Shell<Point3, C, S>,
+) -> HashMap<VertexID<Point3>, Vertex<Point3>> {
     let mut vmap: HashMap<VertexID<Point3>, Vertex<Point3>> = HashMap::new();
     for vertex in shell.vertex_iter() {
         if vmap.get(&vertex.id()).is_none() {
@@ -18,84 +78,552 @@ where
             vmap.insert(vertex.id(), new_vertex);
         }
     }
+    vmap
+}
+
+/// the quality of `polygon`, the tessellation of `surface`
+fn face_quality<S: MeshableSurface>(surface: &S, polygon: &PolygonMesh) -> FaceQuality {
+    let (positions, uv_coords) = (polygon.positions(), polygon.uv_coords());
+    let triangles = polygon.face_iter().flat_map(|face| {
+        (2..face.len()).map(move |i| [face[0], face[i - 1], face[i]])
+    });
+    let mut quality = FaceQuality {
+        triangles: 0,
+        min_angle: std::f64::consts::PI,
+        max_deviation: 0.0,
+    };
+    for tri in triangles {
+        quality.triangles += 1;
+        let p = [positions[tri[0].pos], positions[tri[1].pos], positions[tri[2].pos]];
+        for i in 0..3 {
+            let v0 = p[(i + 1) % 3] - p[i];
+            let v1 = p[(i + 2) % 3] - p[i];
+            let angle = match v0.so_small() || v1.so_small() {
+                true => 0.0,
+                false => v0.angle(v1).0,
+            };
+            quality.min_angle = f64::min(quality.min_angle, angle);
+        }
+        let uv = match (tri[0].uv, tri[1].uv, tri[2].uv) {
+            (Some(a), Some(b), Some(c)) => [uv_coords[a], uv_coords[b], uv_coords[c]],
+            _ => continue,
+        };
+        // the centroid and the midpoints of the edges
+        let samples = [
+            [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+            [0.5, 0.5, 0.0],
+            [0.0, 0.5, 0.5],
+            [0.5, 0.0, 0.5],
+        ];
+        for w in &samples {
+            let vec = p[0].to_vec() * w[0] + p[1].to_vec() * w[1] + p[2].to_vec() * w[2];
+            let pt = Point3::from_vec(vec);
+            let uv = uv[0] * w[0] + uv[1] * w[1] + uv[2] * w[2];
+            let dist = surface.subs(uv[0], uv[1]).distance(pt);
+            quality.max_deviation = f64::max(quality.max_deviation, dist);
+        }
+    }
+    quality
+}
+
+/// The re-tessellated faces of a meshed shell and the new points of its vertices.
+///
+/// Nothing is changed until [`Retessellation::apply`], so that the meshed shape is kept
+/// if the re-tessellation of some shell fails.
+pub(super) struct Retessellation {
+    faces: Vec<(usize, Face<Point3, PolylineCurve, PolygonMesh>)>,
+    points: Vec<(Vertex<Point3>, Point3)>,
+}
+
+impl Retessellation {
+    /// Replaces the faces and moves the vertices of `meshed`.
+    /// Returns the number of re-tessellated faces.
+    pub(super) fn apply(self, meshed: &mut MeshedShell) -> usize {
+        self.points.iter().for_each(|(v, p)| v.set_point(*p));
+        let len = self.faces.len();
+        self.faces.into_iter().for_each(|(i, face)| meshed[i] = face);
+        len
+    }
+}
+
+/// Re-tessellates the faces in `faces` and the faces adjacent to the edges in `edges`,
+/// for replacing the corresponding faces of `meshed`.
+pub(super) fn retessellation<C, S>(
+    shell: &Shell<Point3, C, S>,
+    meshed: &MeshedShell,
+    faces: &HashSet<FaceID<S>>,
+    edges: &HashSet<EdgeID<C>>,
+    tol: f64,
+) -> Option<Retessellation>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let (vmap, mut edge_map) = meshed_maps(shell, meshed)?;
+    edge_map.retain(|id, _| !edges.contains(id));
+    let mut new_faces = Vec::new();
+    for (i, face) in shell.face_iter().enumerate() {
+        let modified = faces.contains(&face.id())
+            || face
+                .absolute_boundaries()
+                .iter()
+                .flatten()
+                .any(|edge| edges.contains(&edge.id()));
+        if modified {
+            let new_face = face_tessellation(i, face, &vmap, &mut edge_map, &no_pcurve, tol).ok()?;
+            new_faces.push((i, new_face));
+        }
+    }
+    let mut points = Vec::new();
+    for vertex in shell.vertex_iter() {
+        let meshed_vertex = vmap.get(&vertex.id())?;
+        let point = vertex.get_point();
+        if meshed_vertex.get_point() != point {
+            points.push((meshed_vertex.clone(), point));
+        }
+    }
+    Some(Retessellation {
+        faces: new_faces,
+        points,
+    })
+}
+
+/// Re-tessellates the faces of `meshed` intersecting `region` by the tolerance `tol` inside
+/// `region`, keeping the vertices of `meshed` outside `region`.
+pub(super) fn region_retessellation<C, S>(
+    shell: &Shell<Point3, C, S>,
+    meshed: &MeshedShell,
+    region: &BoundingBox<Point3>,
+    tol: f64,
+) -> Option<Retessellation>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let (vmap, mut edge_map) = meshed_maps(shell, meshed)?;
+    let mut refined_edges = HashSet::new();
+    for edge in shell
+        .face_iter()
+        .flat_map(|face| face.absolute_boundaries().iter().flatten())
+    {
+        if refined_edges.contains(&edge.id()) {
+            continue;
+        }
+        let meshed_edge = edge_map.get(&edge.id())?;
+        let polyline = refined_polyline(&*edge.read_curve(), &meshed_edge.get_curve(), region, tol);
+        if let Some(polyline) = polyline {
+            let v0 = vmap.get(&edge.absolute_front().id())?;
+            let v1 = vmap.get(&edge.absolute_back().id())?;
+            edge_map.insert(edge.id(), Edge::debug_new(v0, v1, polyline));
+            refined_edges.insert(edge.id());
+        }
+    }
+    let mut new_faces = Vec::new();
+    for (i, (face, meshed_face)) in shell.face_iter().zip(meshed.face_iter()).enumerate() {
+        let old_polygon = meshed_face.get_surface();
+        let bdb: BoundingBox<Point3> = old_polygon.positions().iter().collect();
+        let refined = !(bdb ^ region).is_empty()
+            || face
+                .absolute_boundaries()
+                .iter()
+                .flatten()
+                .any(|edge| refined_edges.contains(&edge.id()));
+        if refined {
+            let wires = face_wires(face, &vmap, &mut edge_map, tol);
+            let polyline = boundary_polyline(i, face, &wires, &no_pcurve, tol).ok()?;
+            let surface = face.read_surface();
+            let polygon = region_tessellation(&*surface, &polyline, &old_polygon, region, tol);
+            new_faces.push((i, meshed_face_from(face, wires, polygon)));
+        }
+    }
+    Some(Retessellation {
+        faces: new_faces,
+        points: Vec::new(),
+    })
+}
+
+/// Returns the bounding box of `region`, or `None` if the face of `region` is not in `shell`.
+pub(super) fn region_bounding_box<C, S>(
+    shell: &Shell<Point3, C, S>,
+    meshed: &MeshedShell,
+    region: &RemeshRegion<FaceID<S>>,
+) -> Option<BoundingBox<Point3>> {
+    match region {
+        RemeshRegion::BoundingBox(bdb) => Some(bdb.clone()),
+        RemeshRegion::NearFace { face, margin } => {
+            let idx = shell.face_iter().position(|f| f.id() == *face)?;
+            let bdb: BoundingBox<Point3> =
+                meshed.get(idx)?.get_surface().positions().iter().collect();
+            let margin = Vector3::new(*margin, *margin, *margin);
+            Some([*bdb.min() - margin, *bdb.max() + margin].iter().collect())
+        }
+    }
+}
+
+/// Returns the maps from the vertices and the edges of `shell` to those of `meshed`,
+/// or `None` if the topology of `shell` does not match `meshed`.
+#[allow(clippy::type_complexity)]
+fn meshed_maps<C, S>(
+    shell: &Shell<Point3, C, S>,
+    meshed: &MeshedShell,
+) -> Option<(
+    HashMap<VertexID<Point3>, Vertex<Point3>>,
+    HashMap<EdgeID<C>, Edge<Point3, PolylineCurve>>,
+)> {
+    if shell.len() != meshed.len() {
+        return None;
+    }
+    let mut vmap: HashMap<VertexID<Point3>, Vertex<Point3>> = HashMap::new();
     let mut edge_map: HashMap<EdgeID<C>, Edge<Point3, PolylineCurve>> = HashMap::new();
-    for face in shell.face_iter() {
-        let mut wires = Vec::new();
-        for biter in face.absolute_boundaries() {
-            let mut wire = Wire::new();
-            for edge in biter {
-                if let Some(new_edge) = edge_map.get(&edge.id()) {
-                    if edge.absolute_front() == edge.front() {
-                        wire.push_back(new_edge.clone());
-                    } else {
-                        wire.push_back(new_edge.inverse());
-                    }
+    for (face, meshed_face) in shell.face_iter().zip(meshed.face_iter()) {
+        let mut iter = face.absolute_boundaries().iter().flatten();
+        let mut meshed_iter = meshed_face.absolute_boundaries().iter().flatten();
+        loop {
+            let (edge, meshed_edge) = match (iter.next(), meshed_iter.next()) {
+                (Some(edge), Some(meshed_edge)) => (edge, meshed_edge),
+                (None, None) => break,
+                _ => return None,
+            };
+            let meshed_edge = match meshed_edge.orientation() {
+                true => meshed_edge.clone(),
+                false => meshed_edge.inverse(),
+            };
+            vmap.entry(edge.absolute_front().id())
+                .or_insert_with(|| meshed_edge.absolute_front().clone());
+            vmap.entry(edge.absolute_back().id())
+                .or_insert_with(|| meshed_edge.absolute_back().clone());
+            edge_map.entry(edge.id()).or_insert(meshed_edge);
+        }
+    }
+    Some((vmap, edge_map))
+}
+
+/// whether `region` includes `pt` or not
+#[inline(always)]
+fn region_include(region: &BoundingBox<Point3>, pt: Point3) -> bool {
+    (0..3).all(|i| region.min()[i] <= pt[i] && pt[i] <= region.max()[i])
+}
+
+/// Returns the polyline of `curve` whose points inside `region` are divided by `tol` and
+/// the others are the ones of `old`, or `None` if no division points are inside `region`
+/// or the curve is degenerate.
+fn refined_polyline<C: PolylineableCurve>(
+    curve: &C,
+    old: &PolylineCurve,
+    region: &BoundingBox<Point3>,
+    tol: f64,
+) -> Option<PolylineCurve> {
+    if old.iter().all(|pt| pt.near(&old[0])) {
+        return None;
+    }
+    let params = curve.parameter_division(curve.parameter_range(), tol);
+    let fine: Vec<(f64, Point3)> = params
+        .iter()
+        .map(|t| (*t, curve.subs(*t)))
+        .filter(|(_, pt)| region_include(region, *pt))
+        .collect();
+    if fine.is_empty() {
+        return None;
+    }
+    let len = old.len();
+    let mut points: Vec<(f64, Point3)> = old
+        .iter()
+        .enumerate()
+        .filter(|(i, pt)| *i == 0 || *i + 1 == len || !region_include(region, **pt))
+        .map(|(i, pt)| {
+            let t = match i {
+                0 => params[0],
+                _ if i + 1 == len => params[params.len() - 1],
+                _ => curve_parameter(curve, *pt, &params),
+            };
+            (t, *pt)
+        })
+        .chain(
+            fine.into_iter()
+                .filter(|(t, _)| *t != params[0] && *t != params[params.len() - 1]),
+        )
+        .collect();
+    points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    points.dedup_by(|a, b| a.1.near(&b.1));
+    Some(PolylineCurve(
+        points.into_iter().map(|(_, pt)| pt).collect(),
+    ))
+}
+
+/// the parameter of `pt` on `curve` by Newton's method from the nearest point of `params`
+fn curve_parameter<C: PolylineableCurve>(curve: &C, pt: Point3, params: &[f64]) -> f64 {
+    let (t0, t1) = curve.parameter_range();
+    let mut t = params
+        .iter()
+        .copied()
+        .min_by(|s, t| {
+            let ds = curve.subs(*s).distance2(pt);
+            let dt = curve.subs(*t).distance2(pt);
+            ds.partial_cmp(&dt).unwrap()
+        })
+        .unwrap();
+    for _ in 0..16 {
+        let diff = curve.subs(t) - pt;
+        let der = curve.der(t);
+        let denom = der.magnitude2() + diff.dot(curve.der2(t));
+        if denom.so_small() {
+            break;
+        }
+        let delta = diff.dot(der) / denom;
+        t = f64::clamp(t - delta, t0, t1);
+        if delta.so_small() {
+            break;
+        }
+    }
+    t
+}
+
+/// Tessellates the `index`th face. The vertices and edges already tessellated are reused.
+fn face_tessellation<C, S>(
+    index: usize,
+    face: &Face<Point3, C, S>,
+    vmap: &HashMap<VertexID<Point3>, Vertex<Point3>>,
+    edge_map: &mut HashMap<EdgeID<C>, Edge<Point3, PolylineCurve>>,
+    pcurve_uv: &PCurveUV<'_, C, S>,
+    tol: f64,
+) -> std::result::Result<Face<Point3, PolylineCurve, PolygonMesh>, TessellationError>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let wires = face_wires(face, vmap, edge_map, tol);
+    let polyline = boundary_polyline(index, face, &wires, pcurve_uv, tol)?;
+    let surface = face.read_surface();
+    let polygon = match grid_tessellation(&*surface, &polyline, tol) {
+        Some(mesh) => {
+            let mut polygon = mesh.destruct();
+            polygon.triangulate();
+            polygon
+        }
+        None => trimming_tessellation(&*surface, &polyline, tol),
+    };
+    Ok(meshed_face_from(face, wires, polygon))
+}
+
+/// Tessellates the `face` alone into the grid, or returns `None` if the face cannot be
+/// tessellated into the grid.
+pub(super) fn structured_tessellation<C, S>(
+    face: &Face<Point3, C, S>,
+    tol: f64,
+) -> Option<StructuredMesh>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let shell: Shell<Point3, C, S> = vec![face.clone()].into();
+    let (vmap, mut edge_map) = (new_vertex_map(&shell), HashMap::new());
+    let wires = face_wires(face, &vmap, &mut edge_map, tol);
+    let polyline = boundary_polyline(0, face, &wires, &no_pcurve, tol).ok()?;
+    let surface = face.read_surface();
+    let mesh = grid_tessellation(&*surface, &polyline, tol)?;
+    match face.orientation() {
+        true => Some(mesh),
+        false => {
+            // reverses the direction of u
+            let (udiv, vdiv) = mesh.uv_division().unwrap();
+            let udiv = udiv.iter().rev().copied().collect();
+            let vdiv = vdiv.clone();
+            let positions = mesh.positions().iter().rev().cloned().collect();
+            let normals = mesh.normals().unwrap().iter().rev();
+            let normals = normals.map(|row| row.iter().map(|n| -*n).collect()).collect();
+            Some(StructuredMesh::new_unchecked(positions, (udiv, vdiv), normals))
+        }
+    }
+}
+
+/// Converts the boundaries of `face` into polylines. The edges already tessellated are reused.
+fn face_wires<C, S>(
+    face: &Face<Point3, C, S>,
+    vmap: &HashMap<VertexID<Point3>, Vertex<Point3>>,
+    edge_map: &mut HashMap<EdgeID<C>, Edge<Point3, PolylineCurve>>,
+    tol: f64,
+) -> Vec<Wire<Point3, PolylineCurve>>
+where C: PolylineableCurve {
+    let mut wires = Vec::new();
+    for biter in face.absolute_boundaries() {
+        let mut wire = Wire::new();
+        for edge in biter {
+            if let Some(new_edge) = edge_map.get(&edge.id()) {
+                if edge.absolute_front() == edge.front() {
+                    wire.push_back(new_edge.clone());
                 } else {
-                    let v0 = vmap.get(&edge.absolute_front().id()).unwrap();
-                    let v1 = vmap.get(&edge.absolute_back().id()).unwrap();
-                    let curve = edge.get_curve();
-                    let poly: Vec<Point3> = curve
-                        .parameter_division(curve.parameter_range(), tol)
-                        .into_iter()
-                        .map(|t| curve.subs(t))
-                        .collect();
-                    let new_edge = Edge::debug_new(v0, v1, PolylineCurve(poly));
-                    if edge.orientation() {
-                        wire.push_back(new_edge.clone());
-                    } else {
-                        wire.push_back(new_edge.inverse());
-                    }
-                    edge_map.insert(edge.id(), new_edge);
+                    wire.push_back(new_edge.inverse());
+                }
+            } else {
+                let v0 = vmap.get(&edge.absolute_front().id()).unwrap();
+                let v1 = vmap.get(&edge.absolute_back().id()).unwrap();
+                let curve = edge.read_curve();
+                let poly: Vec<Point3> = curve
+                    .parameter_division(curve.parameter_range(), tol)
+                    .into_iter()
+                    .map(|t| curve.subs(t))
+                    .collect();
+                let new_edge = Edge::debug_new(v0, v1, PolylineCurve(poly));
+                if edge.orientation() {
+                    wire.push_back(new_edge.clone());
+                } else {
+                    wire.push_back(new_edge.inverse());
                 }
+                edge_map.insert(edge.id(), new_edge);
             }
-            wires.push(wire);
-        }
-        let surface = face.get_surface();
-        let mut polyline = Polyline::default();
-        let polygon = match wires
-            .iter()
-            .all(|wire| polyline.add_wire(&surface, wire))
-        {
-            true => Some(trimming_tessellation(&surface, &polyline, tol)),
-            false => None,
-        }?;
-        let mut new_face = Face::debug_new(wires, polygon);
-        if !face.orientation() {
-            new_face.invert();
         }
-        shell0.push(new_face);
+        wires.push(wire);
     }
-    Some(shell0)
+    wires
 }
 
-/// polyline, not always connected
+/// the polyline in the parameter space of the surface of `face` of the boundaries `wires`,
+/// the polylines of the boundaries of `face`, the `index`th face of the shell.
+/// The points of the edges with pcurves are mapped by the pcurves.
+fn boundary_polyline<C, S>(
+    index: usize,
+    face: &Face<Point3, C, S>,
+    wires: &[Wire<Point3, PolylineCurve>],
+    pcurve_uv: &PCurveUV<'_, C, S>,
+    tol: f64,
+) -> std::result::Result<Polyline, TessellationError>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface, {
+    let surface = face.read_surface();
+    let mut polyline = Polyline::default();
+    let boundaries = face.absolute_boundaries().iter().zip(wires);
+    for (i, (wire, meshed_wire)) in boundaries.enumerate() {
+        let polylines = wire.iter().zip(meshed_wire).map(|(edge, meshed_edge)| {
+            let mut points = Vec::from(meshed_edge.oriented_curve());
+            points.pop();
+            let curve = edge.read_curve();
+            let range = curve.parameter_range();
+            match pcurve_uv(edge, face, range.0) {
+                Some(_) => {
+                    let params = curve.parameter_division(range, tol);
+                    let uv = |pt| pcurve_uv(edge, face, curve_parameter(&*curve, pt, &params));
+                    points.into_iter().map(|pt| (pt, uv(pt))).collect()
+                }
+                None => points.into_iter().map(|pt| (pt, None)).collect(),
+            }
+        });
+        polyline
+            .add_closed_polylines_with_uv(&*surface, polylines, tol)
+            .map_err(|(edge, point)| TessellationError::ParameterNotFound {
+                face: index,
+                wire: i,
+                edge,
+                point,
+            })?;
+    }
+    Ok(polyline)
+}
+
+/// the tessellated face with the orientation of `face`
+fn meshed_face_from<C, S>(
+    face: &Face<Point3, C, S>,
+    wires: Vec<Wire<Point3, PolylineCurve>>,
+    polygon: PolygonMesh,
+) -> Face<Point3, PolylineCurve, PolygonMesh> {
+    let mut new_face = Face::debug_new(wires, polygon);
+    if !face.orientation() {
+        new_face.invert();
+    }
+    new_face
+}
+
+/// polyline in the parameter space, not always connected
 #[derive(Debug, Default, Clone)]
-struct Polyline {
+pub(crate) struct Polyline {
     positions: Vec<Point2>,
     indices: Vec<[usize; 2]>,
 }
 
 impl Polyline {
-    /// add an wire into polyline
-    fn add_wire<S>(&mut self, surface: &S, wire: &Wire<Point3, PolylineCurve>) -> bool
-    where S: MeshableSurface {
-        let mut counter = 0;
-        let len = self.positions.len();
-        let res = wire.into_iter().all(|edge| {
-            let mut poly_edge = edge.oriented_curve();
-            poly_edge.pop();
-            counter += poly_edge.len();
+    /// add the closed chain of polylines in the space, whose end points are omitted,
+    /// by searching the parameters on `surface`.
+    #[inline(always)]
+    pub(crate) fn add_closed_polylines<S, I>(
+        &mut self,
+        surface: &S,
+        polylines: I,
+        tol: f64,
+    ) -> std::result::Result<(), (usize, Point3)>
+    where
+        S: MeshableSurface,
+        I: IntoIterator<Item = Vec<Point3>>, {
+        let polylines = polylines
+            .into_iter()
+            .map(|polyline| polyline.into_iter().map(|pt| (pt, None)).collect());
+        self.add_closed_polylines_with_uv(surface, polylines, tol)
+    }
+
+    /// add the closed chain of polylines in the space, whose end points are omitted.
+    /// The parameters on `surface` are searched for the points without the parameters.
+    ///
+    /// The points whose parameters are not found by `search_parameter` are projected by the
+    /// subdivision of the region of the found parameters. Returns the index of the polyline and
+    /// the point if the projection is farther than `tol` from the point.
+    fn add_closed_polylines_with_uv<S, I>(
+        &mut self,
+        surface: &S,
+        polylines: I,
+        tol: f64,
+    ) -> std::result::Result<(), (usize, Point3)>
+    where
+        S: MeshableSurface,
+        I: IntoIterator<Item = Vec<(Point3, Option<Point2>)>>, {
+        let mut points = Vec::new();
+        for (i, polyline) in polylines.into_iter().enumerate() {
             let mut hint = None;
-            Vec::from(poly_edge).into_iter().all(|pt| {
-                hint = surface
-                    .search_parameter(pt, hint, 100)
-                    .or_else(|| surface.search_parameter(pt, None, 100));
-                hint.map(|hint| self.positions.push(hint.into())).is_some()
-            })
-        });
+            for (pt, uv) in polyline {
+                let uv = match uv {
+                    Some(uv) => Some(uv),
+                    None => surface
+                        .search_parameter(pt, hint, 100)
+                        .or_else(|| surface.search_parameter(pt, None, 100))
+                        .map(Point2::from),
+                };
+                hint = uv.map(|uv| (uv[0], uv[1])).or(hint);
+                points.push((i, pt, uv));
+            }
+        }
+        if points.iter().any(|(_, _, uv)| uv.is_none()) {
+            let bdb: BoundingBox<Point2> = self
+                .positions
+                .iter()
+                .chain(points.iter().filter_map(|(_, _, uv)| uv.as_ref()))
+                .collect();
+            let range = search_region(&bdb);
+            for (i, pt, uv) in &mut points {
+                if uv.is_none() {
+                    *uv = range.and_then(|range| project_by_subdivision(surface, *pt, range, tol));
+                    if uv.is_none() {
+                        return Err((*i, *pt));
+                    }
+                }
+            }
+        }
+        let points = points
+            .into_iter()
+            .filter_map(|(_, pt, uv)| Some((pt, uv?)))
+            .collect();
+        let len = self.positions.len();
+        self.positions.extend(collapse_degenerate(surface, points));
+        let counter = self.positions.len() - len;
         self.indices
             .extend((0..counter).map(|i| [len + i, len + (i + 1) % counter]));
-        res
+        Ok(())
+    }
+
+    /// the bounding box of the polyline in the parameter space
+    pub(crate) fn parameter_range(&self) -> ((f64, f64), (f64, f64)) {
+        let bdb: BoundingBox<Point2> = self.positions.iter().collect();
+        ((bdb.min()[0], bdb.max()[0]), (bdb.min()[1], bdb.max()[1]))
+    }
+
+    /// the iterator of the segments of the polyline
+    pub(crate) fn segments(&self) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+        self.indices
+            .iter()
+            .map(move |edge| (self.positions[edge[0]], self.positions[edge[1]]))
     }
 
     /// whether `c` is included in the domain with bounday = `self`.
@@ -120,6 +648,21 @@ impl Polyline {
             .unwrap_or(false)
     }
 
+    /// whether `c` is included in the domain, by the winding number which does not miss the points
+    /// on the same level as the vertices of `self`.
+    fn winding_include(&self, c: Point2) -> bool {
+        let angle: f64 = self
+            .indices
+            .iter()
+            .map(|edge| {
+                let a = self.positions[edge[0]] - c;
+                let b = self.positions[edge[1]] - c;
+                f64::atan2(a[0] * b[1] - a[1] * b[0], a.dot(b))
+            })
+            .sum();
+        angle > std::f64::consts::PI
+    }
+
     /// Inserts points and adds constraint into triangulation.
     fn insert_to(&self, triangulation: &mut CDT<[f64; 2], impl DelaunayKernel<f64>>) {
         let poly2tri: Vec<usize> = self
@@ -133,6 +676,123 @@ impl Polyline {
     }
 }
 
+/// the number of the divisions of each side of the region in a step of the subdivision search
+const SUBDIVISION: usize = 16;
+/// the number of the steps of the subdivision search
+const SUBDIVISION_STEPS: usize = 8;
+
+/// the region of the parameters searched by the subdivision, `bdb` extended by its size,
+/// or `None` if `bdb` is empty or a point
+fn search_region(bdb: &BoundingBox<Point2>) -> Option<((f64, f64), (f64, f64))> {
+    if bdb.is_empty() {
+        return None;
+    }
+    let (min, max) = (bdb.min(), bdb.max());
+    let margin = f64::max(max[0] - min[0], max[1] - min[1]);
+    match margin.so_small() {
+        true => None,
+        false => Some((
+            (min[0] - margin, max[0] + margin),
+            (min[1] - margin, max[1] + margin),
+        )),
+    }
+}
+
+/// Projects `pt` to `surface` by dividing `range` and the cells around the nearest points
+/// repeatedly, and polishes the parameter by `search_parameter`.
+/// Returns `None` if the projected point is farther than `tol` from `pt`.
+fn project_by_subdivision<S: MeshableSurface>(
+    surface: &S,
+    pt: Point3,
+    range: ((f64, f64), (f64, f64)),
+    tol: f64,
+) -> Option<Point2> {
+    let ((mut u0, mut u1), (mut v0, mut v1)) = range;
+    let mut uv = (u0, v0);
+    for _ in 0..SUBDIVISION_STEPS {
+        uv = algo::surface::presearch(surface, pt, ((u0, u1), (v0, v1)), SUBDIVISION);
+        let du = (u1 - u0) / SUBDIVISION as f64;
+        let dv = (v1 - v0) / SUBDIVISION as f64;
+        u0 = uv.0 - du;
+        u1 = uv.0 + du;
+        v0 = uv.1 - dv;
+        v1 = uv.1 + dv;
+    }
+    let uv = surface.search_parameter(pt, Some(uv), 100).unwrap_or(uv);
+    match surface.subs(uv.0, uv.1).distance(pt) < tol {
+        true => Some(uv.into()),
+        false => None,
+    }
+}
+
+/// the direction of the parameter along which `surface` does not move at `uv`, e.g. `v` at
+/// the poles of spheres and the apexes of cones made by revolutions
+fn degenerate_direction<S: ParametricSurface3D>(surface: &S, uv: Point2) -> Option<usize> {
+    let ud = surface.uder(uv[0], uv[1]).magnitude2();
+    let vd = surface.vder(uv[0], uv[1]).magnitude2();
+    if vd < TOLERANCE2 * ud {
+        Some(1)
+    } else if ud < TOLERANCE2 * vd {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+/// the parameters of the closed chain of `points`, whose points on the degenerate boundaries
+/// of `surface` are spread along the collapsed iso-lines between the parameters of the
+/// neighbors. The duplicated parameters are removed.
+fn collapse_degenerate<S>(surface: &S, points: Vec<(Point3, Point2)>) -> Vec<Point2>
+where S: MeshableSurface {
+    let n = points.len();
+    let degenerate: Vec<Option<usize>> = points
+        .iter()
+        .map(|(_, uv)| degenerate_direction(surface, *uv))
+        .collect();
+    let mut res = Vec::with_capacity(n);
+    match degenerate.iter().position(Option::is_none) {
+        // The points at which the parameters are not determined are kept.
+        None => res.extend(points.iter().map(|(_, uv)| *uv)),
+        Some(start) => {
+            let mut k = 0;
+            while k < n {
+                let i = (start + k) % n;
+                let dir = match degenerate[i] {
+                    Some(dir) => dir,
+                    None => {
+                        res.push(points[i].1);
+                        k += 1;
+                        continue;
+                    }
+                };
+                // the run of the points collapsed into one point
+                let len = (k + 1..n)
+                    .take_while(|l| {
+                        let j = (start + l) % n;
+                        degenerate[j] == Some(dir) && points[j].0.near(&points[i].0)
+                    })
+                    .count()
+                    + 1;
+                // `res` is not empty since the first point is not degenerate.
+                let a = res[res.len() - 1][dir];
+                let b = points[(start + k + len) % n].1[dir];
+                let m = usize::max(len, 2);
+                res.extend((0..m).map(|j| {
+                    let mut uv = points[i].1;
+                    uv[dir] = a + (b - a) * j as f64 / (m - 1) as f64;
+                    uv
+                }));
+                k += len;
+            }
+        }
+    }
+    res.dedup_by(|a, b| Point2::near(a, b));
+    while res.len() > 1 && res[0].near(&res[res.len() - 1]) {
+        res.pop();
+    }
+    res
+}
+
 /// Tessellates one surface trimmed by polyline.
 fn trimming_tessellation<S>(surface: &S, polyline: &Polyline, tol: f64) -> PolygonMesh
 where S: MeshableSurface {
@@ -149,6 +809,136 @@ where S: MeshableSurface {
     mesh
 }
 
+/// Tessellates one surface into the grid of the parameters, if `polyline` is the rectangle of
+/// the parameters whose opposite sides are divided at the same parameters, no side of
+/// the rectangle is degenerate, and the midpoints of the cells are within `tol` from the surface.
+fn grid_tessellation<S>(surface: &S, polyline: &Polyline, tol: f64) -> Option<StructuredMesh>
+where S: MeshableSurface {
+    let ((u0, u1), (v0, v1)) = polyline.parameter_range();
+    if (u1 - u0).so_small() || (v1 - v0).so_small() {
+        return None;
+    }
+    let on_side = |x: f64, side: f64| (x - side).abs() < TOLERANCE;
+    let mut sides: [Vec<f64>; 4] = Default::default();
+    for pt in &polyline.positions {
+        let mut on_some_side = false;
+        // the bottom, the top, the left and the right sides
+        let params = [
+            (pt[1], v0, pt[0]),
+            (pt[1], v1, pt[0]),
+            (pt[0], u0, pt[1]),
+            (pt[0], u1, pt[1]),
+        ];
+        for (i, (x, side, y)) in params.iter().enumerate() {
+            if on_side(*x, *side) {
+                sides[i].push(*y);
+                on_some_side = true;
+            }
+        }
+        if !on_some_side {
+            return None;
+        }
+    }
+    // the segments run along the sides
+    let along_sides = polyline.segments().all(|(p, q)| {
+        (on_side(p[1], q[1]) && (on_side(p[1], v0) || on_side(p[1], v1)))
+            || (on_side(p[0], q[0]) && (on_side(p[0], u0) || on_side(p[0], u1)))
+    });
+    if !along_sides {
+        return None;
+    }
+    let division = |mut params: Vec<f64>| {
+        params.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        params.dedup_by(|a, b| on_side(*a, *b));
+        params
+    };
+    let [bottom, top, left, right] = sides;
+    let (udiv, vdiv) = (division(bottom), division(left));
+    let same = |a: &[f64], b: &[f64]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| on_side(*x, *y))
+    };
+    if !same(&udiv, &division(top)) || !same(&vdiv, &division(right)) {
+        return None;
+    }
+    let corners = [(u0, v0), (u1, v0), (u1, v1), (u0, v1)];
+    let degenerate = corners
+        .iter()
+        .any(|(u, v)| degenerate_direction(surface, Point2::new(*u, *v)).is_some());
+    if degenerate {
+        return None;
+    }
+    let positions: Vec<Vec<Point3>> = udiv
+        .iter()
+        .map(|u| vdiv.iter().map(|v| surface.subs(*u, *v)).collect())
+        .collect();
+    let within_tolerance = (1..udiv.len()).all(|i| {
+        (1..vdiv.len()).all(|j| {
+            let (um, vm) = ((udiv[i - 1] + udiv[i]) / 2.0, (vdiv[j - 1] + vdiv[j]) / 2.0);
+            let p = [
+                positions[i - 1][j - 1],
+                positions[i][j - 1],
+                positions[i][j],
+                positions[i - 1][j],
+            ];
+            let mid = |a: Point3, b: Point3| a.midpoint(b);
+            let center = mid(mid(p[0], p[2]), mid(p[1], p[3]));
+            surface.subs(um, vm).distance(center) < tol
+                && surface.subs(um, vdiv[j - 1]).distance(mid(p[0], p[1])) < tol
+                && surface.subs(udiv[i - 1], vm).distance(mid(p[0], p[3])) < tol
+        })
+    });
+    if !within_tolerance {
+        return None;
+    }
+    let normals = udiv
+        .iter()
+        .map(|u| vdiv.iter().map(|v| surface.normal(*u, *v)).collect())
+        .collect();
+    Some(StructuredMesh::new_unchecked(positions, (udiv, vdiv), normals))
+}
+
+/// Tessellates one surface trimmed by polyline, by the parameter division inside `region` and
+/// the inner vertices of `old` outside `region`.
+fn region_tessellation<S>(
+    surface: &S,
+    polyline: &Polyline,
+    old: &PolygonMesh,
+    region: &BoundingBox<Point3>,
+    tol: f64,
+) -> PolygonMesh
+where S: MeshableSurface {
+    let mut triangulation = CDT::<[f64; 2], FloatKernel>::new();
+    polyline.insert_to(&mut triangulation);
+    let boundary: HashSet<usize> = old.extract_boundaries().into_iter().flatten().collect();
+    let mut inserted = HashSet::new();
+    old.faces()
+        .face_iter()
+        .flatten()
+        .filter(|v| !boundary.contains(&v.pos) && inserted.insert(v.pos))
+        .filter(|v| !region_include(region, old.positions()[v.pos]))
+        .filter_map(|v| v.uv.map(|uv| Point2::from_vec(old.uv_coords()[uv])))
+        .filter(|pt| polyline.winding_include(*pt))
+        .for_each(|pt| {
+            triangulation.insert(pt.into());
+        });
+    let (udiv, vdiv) = surface.parameter_division(polyline.parameter_range(), tol);
+    udiv.into_iter()
+        .flat_map(|u| vdiv.iter().map(move |v| Point2::new(u, *v)))
+        .filter(|pt| region_include(region, surface.subs(pt[0], pt[1])))
+        .filter(|pt| polyline.include(*pt, TOLERANCE))
+        .for_each(|pt| {
+            triangulation.insert(pt.into());
+        });
+    let mut mesh = triangulation_into_polymesh(
+        triangulation.vertices(),
+        triangulation.triangles(),
+        surface,
+        polyline,
+    );
+    mesh.make_face_compatible_to_normal();
+    mesh
+}
+
 /// Inserts parameter divisions into triangulation.
 fn insert_surface(
     triangulation: &mut CDT<[f64; 2], impl DelaunayKernel<f64>>,
@@ -156,9 +946,7 @@ fn insert_surface(
     polyline: &Polyline,
     tol: f64,
 ) {
-    let bdb: BoundingBox<Point2> = polyline.positions.iter().collect();
-    let range = ((bdb.min()[0], bdb.max()[0]), (bdb.min()[1], bdb.max()[1]));
-    let (udiv, vdiv) = surface.parameter_division(range, tol);
+    let (udiv, vdiv) = surface.parameter_division(polyline.parameter_range(), tol);
     udiv.into_iter()
         .flat_map(|u| vdiv.iter().map(move |v| Point2::new(u, *v)))
         .filter(|pt| polyline.include(*pt, TOLERANCE))
@@ -177,14 +965,37 @@ fn triangulation_into_polymesh<'a>(
     let mut positions = Vec::<Point3>::new();
     let mut uv_coords = Vec::<Vector2>::new();
     let mut normals = Vec::<Vector3>::new();
-    let vmap: HashMap<usize, usize> = vertices
+    let ((u0, u1), (v0, v1)) = polyline.parameter_range();
+    let center = Point2::new((u0 + u1) / 2.0, (v0 + v1) / 2.0);
+    // The points on a degenerate boundary share the position.
+    let mut collapsed = Vec::<usize>::new();
+    let vmap: HashMap<usize, (usize, usize)> = vertices
         .enumerate()
         .map(|(i, v)| {
-            let uv = Vector2::from(*v);
-            positions.push(surface.subs(uv[0], uv[1]));
-            uv_coords.push(uv);
-            normals.push(surface.normal(uv[0], uv[1]));
-            (v.fix(), i)
+            let uv = Point2::from(*v);
+            let pt = surface.subs(uv[0], uv[1]);
+            let (pos, normal) = match degenerate_direction(surface, uv) {
+                None => {
+                    positions.push(pt);
+                    (positions.len() - 1, surface.normal(uv[0], uv[1]))
+                }
+                Some(_) => {
+                    let pos = match collapsed.iter().find(|j| positions[**j].near(&pt)) {
+                        Some(j) => *j,
+                        None => {
+                            positions.push(pt);
+                            collapsed.push(positions.len() - 1);
+                            positions.len() - 1
+                        }
+                    };
+                    // the normal at the parameter slightly moved into the domain
+                    let uv = uv + (center - uv) * 1.0e-3;
+                    (pos, surface.normal(uv[0], uv[1]))
+                }
+            };
+            uv_coords.push(uv.to_vec());
+            normals.push(normal);
+            (v.fix(), (pos, i))
         })
         .collect();
     let tri_faces: Vec<[truck_polymesh::Vertex; 3]> = triangles
@@ -197,15 +1008,19 @@ fn triangulation_into_polymesh<'a>(
             polyline.include(c, 0.0)
         })
         .map(|tri| {
-            let idcs = [
+            [
                 vmap[&tri[0].fix()],
                 vmap[&tri[1].fix()],
                 vmap[&tri[2].fix()],
-            ];
+            ]
+        })
+        // the triangles collapsed at the degenerate boundaries
+        .filter(|idcs| (0..3).all(|i| idcs[i].0 != idcs[(i + 1) % 3].0))
+        .map(|idcs| {
             [
-                [idcs[0], idcs[0], idcs[0]].into(),
-                [idcs[1], idcs[1], idcs[1]].into(),
-                [idcs[2], idcs[2], idcs[2]].into(),
+                [idcs[0].0, idcs[0].1, idcs[0].1].into(),
+                [idcs[1].0, idcs[1].1, idcs[1].1].into(),
+                [idcs[2].0, idcs[2].1, idcs[2].1].into(),
             ]
         })
         .collect();
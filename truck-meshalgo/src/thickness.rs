@@ -0,0 +1,203 @@
+use crate::analyzers::MeshBVH;
+use crate::tessellation::{MeshableShape, MeshableSurface, PolylineableCurve};
+use crate::*;
+use truck_topology::Solid;
+
+/// Wall thickness at a point on the boundary of a solid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WallThickness {
+    /// the index of the face in the order of `face_iter` of the boundary shells
+    pub face_index: usize,
+    /// the parameter of the point on the surface of the face
+    pub parameter: (f64, f64),
+    /// the point on the face
+    pub point: Point3,
+    /// the index of the opposite face, hit by the ray in the inward normal
+    pub opposite_face_index: usize,
+    /// the parameter of the opposite point on the surface of the opposite face
+    pub opposite_parameter: (f64, f64),
+    /// the point on the opposite face
+    pub opposite_point: Point3,
+    /// the distance between the point and the opposite point
+    pub thickness: f64,
+}
+
+/// Analyzes the wall thickness of solids on the exact surfaces, before tessellation.
+pub trait WallThicknessAnalysis {
+    /// Returns the wall thicknesses at the sample points on the faces.
+    ///
+    /// The sample points are the centroids, in the parameter space, of the triangles of
+    /// the tessellation with `tol`. The thickness at each point is the distance to the first
+    /// intersection of the ray in the inward normal and the surfaces of the faces.
+    /// The intersections are found on the tessellation, and refined by Newton's method on
+    /// the exact surfaces. The points whose rays escape the solid are omitted.
+    ///
+    /// The boundary of the solid is assumed to be oriented outward. Returns `None` if
+    /// the tessellation fails.
+    fn wall_thicknesses(&self, tol: f64) -> Option<Vec<WallThickness>>;
+    /// Returns the minimum of the wall thicknesses by [`wall_thicknesses`].
+    /// Returns `None` if the tessellation fails or no thickness is found.
+    ///
+    /// The minimum between the sample points may be missed. Make `tol` smaller than
+    /// the expected thickness for the reliable results.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// use truck_modeling::builder;
+    ///
+    /// // the plate with thickness 0.2
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let f = builder::tsweep(&e, Vector3::unit_y());
+    /// let plate = builder::tsweep(&f, 0.2 * Vector3::unit_z());
+    ///
+    /// let thickness = plate.minimum_wall_thickness(0.05).unwrap();
+    /// assert!(thickness.thickness.near(&0.2));
+    /// // the top and the bottom face each other
+    /// let z0 = thickness.point[2];
+    /// let z1 = thickness.opposite_point[2];
+    /// assert!(f64::min(z0, z1).near(&0.0) && f64::max(z0, z1).near(&0.2));
+    /// ```
+    ///
+    /// [`wall_thicknesses`]: #tymethod.wall_thicknesses
+    fn minimum_wall_thickness(&self, tol: f64) -> Option<WallThickness> {
+        self.wall_thicknesses(tol)?
+            .into_iter()
+            .min_by(|a, b| a.thickness.partial_cmp(&b.thickness).unwrap())
+    }
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> WallThicknessAnalysis for Solid<Point3, C, S> {
+    fn wall_thicknesses(&self, tol: f64) -> Option<Vec<WallThickness>> {
+        let meshed = self.triangulation(tol)?;
+        let faces: Vec<_> = self.boundaries().iter().flat_map(|shell| shell.face_iter()).collect();
+        let meshes: Vec<_> = meshed
+            .boundaries()
+            .iter()
+            .flat_map(|shell| shell.face_iter())
+            .map(|face| face.get_surface())
+            .collect();
+        let (mesh, triangle_faces) = triangles_with_faces(&meshes);
+        let bvh = MeshBVH::new(&mesh);
+        let surfaces: Vec<(S, bool)> = faces
+            .iter()
+            .map(|face| (face.get_surface(), face.orientation()))
+            .collect();
+        let res = meshes
+            .iter()
+            .enumerate()
+            .flat_map(|(face_index, polygon)| {
+                let uvs = polygon.uv_coords();
+                polygon.face_iter().filter_map(move |tri| {
+                    let uv = tri
+                        .iter()
+                        .try_fold(Vector2::zero(), |sum, v| Some(sum + uvs[v.uv?]))?
+                        / tri.len() as f64;
+                    Some((face_index, (uv[0], uv[1])))
+                })
+            })
+            .filter_map(|(face_index, (u, v))| {
+                let (surface, orientation) = &surfaces[face_index];
+                let point = surface.subs(u, v);
+                let normal = surface.normal(u, v);
+                let direction = match orientation {
+                    true => -normal,
+                    false => normal,
+                };
+                let mut nearest: Option<WallThickness> = None;
+                bvh.traverse(point, direction, f64::INFINITY, |hit| {
+                    let opposite_face_index = triangle_faces[hit.face_index];
+                    let surface = &surfaces[opposite_face_index].0;
+                    let uv = hit
+                        .vertices
+                        .iter()
+                        .zip(&hit.barycentric)
+                        .try_fold(Vector2::zero(), |sum, (v, b)| {
+                            Some(sum + mesh.uv_coords()[v.uv?] * *b)
+                        });
+                    let res = uv.and_then(|uv| {
+                        let hint = (uv[0], uv[1], hit.distance);
+                        ray_intersection(surface, point, direction, hint)
+                    });
+                    let (u0, v0, t) = match res {
+                        Some(res) => res,
+                        None => return Some(f64::INFINITY),
+                    };
+                    // the intersection with the face itself at the origin of the ray,
+                    // or the intersection far from the face
+                    let opposite_point = surface.subs(u0, v0);
+                    let far = opposite_point.distance(hit.point()) > 10.0 * tol;
+                    if t < TOLERANCE || far {
+                        return Some(f64::INFINITY);
+                    }
+                    if nearest.map(|nearest| t < nearest.thickness).unwrap_or(true) {
+                        nearest = Some(WallThickness {
+                            face_index,
+                            parameter: (u, v),
+                            point,
+                            opposite_face_index,
+                            opposite_parameter: (u0, v0),
+                            opposite_point,
+                            thickness: t,
+                        });
+                    }
+                    Some(f64::INFINITY)
+                });
+                nearest
+            })
+            .collect();
+        Some(res)
+    }
+}
+
+/// Concatenates the meshes into the triangle mesh, and returns it with the indices of
+/// the meshes of the triangles.
+pub(crate) fn triangles_with_faces(meshes: &[PolygonMesh]) -> (PolygonMesh, Vec<usize>) {
+    let mut positions = Vec::new();
+    let mut uv_coords = Vec::new();
+    let mut triangles = Vec::new();
+    let mut triangle_faces = Vec::new();
+    for (i, polygon) in meshes.iter().enumerate() {
+        let (pos_offset, uv_offset) = (positions.len(), uv_coords.len());
+        let shift = |v: &Vertex| Vertex {
+            pos: v.pos + pos_offset,
+            uv: v.uv.map(|uv| uv + uv_offset),
+            nor: None,
+            col: None,
+        };
+        polygon.face_iter().for_each(|face| {
+            (2..face.len()).for_each(|j| {
+                triangles.push([shift(&face[0]), shift(&face[j - 1]), shift(&face[j])]);
+                triangle_faces.push(i);
+            })
+        });
+        positions.extend(polygon.positions());
+        uv_coords.extend(polygon.uv_coords());
+    }
+    let faces = Faces::from_tri_and_quad_faces(triangles, Vec::new());
+    let mesh = PolygonMesh::new(positions, uv_coords, Vec::new(), faces);
+    (mesh, triangle_faces)
+}
+
+/// Solves `surface.subs(u, v) == origin + t * direction` by Newton's method from `hint`,
+/// and returns `(u, v, t)`.
+pub(crate) fn ray_intersection<S: ParametricSurface3D>(
+    surface: &S,
+    origin: Point3,
+    direction: Vector3,
+    hint: (f64, f64, f64),
+) -> Option<(f64, f64, f64)> {
+    let (mut u, mut v, mut t) = hint;
+    for _ in 0..100 {
+        let diff = surface.subs(u, v) - (origin + t * direction);
+        if diff.so_small() {
+            return Some((u, v, t));
+        }
+        let mat = Matrix3::from_cols(surface.uder(u, v), surface.vder(u, v), -direction);
+        let delta = mat.invert()? * diff;
+        u -= delta[0];
+        v -= delta[1];
+        t -= delta[2];
+    }
+    None
+}
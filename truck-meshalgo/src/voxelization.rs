@@ -0,0 +1,364 @@
+use crate::analyzers::MeshBVH;
+use crate::lattice::marching_tetrahedra_on_grid;
+use crate::*;
+
+/// the directions of the rays counting the winding numbers
+const RAY_DIRECTIONS: [Vector3; 3] = [
+    Vector3::new(0.5773, 0.5774, 0.5774),
+    Vector3::new(-0.6123, 0.5004, -0.6124),
+    Vector3::new(0.2673, -0.8018, -0.5345),
+];
+
+/// The cubic voxels on the regular grid, which have the signed distances to the surface
+/// at the centers, negative inside and positive outside.
+///
+/// The `(i, j, k)`-th voxel is the cube from `origin + cell_size * (i, j, k)` to
+/// `origin + cell_size * (i + 1, j + 1, k + 1)`, and is filled if its value is negative.
+/// The operations on the signed distances, i.e. offsetting and boolean operations, are done
+/// in voxel space, and the results are converted into polygon meshes by [`to_polygon`].
+///
+/// [`to_polygon`]: ./struct.VoxelGrid.html#method.to_polygon
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoxelGrid {
+    origin: Point3,
+    cell_size: f64,
+    size: [usize; 3],
+    values: Vec<f64>,
+}
+
+impl Default for VoxelGrid {
+    #[inline(always)]
+    fn default() -> VoxelGrid {
+        VoxelGrid {
+            origin: Point3::origin(),
+            cell_size: 1.0,
+            size: [0; 3],
+            values: Vec::new(),
+        }
+    }
+}
+
+impl VoxelGrid {
+    /// Returns the min corner of the grid.
+    #[inline(always)]
+    pub fn origin(&self) -> Point3 { self.origin }
+    /// Returns the length of the edges of the voxels.
+    #[inline(always)]
+    pub fn cell_size(&self) -> f64 { self.cell_size }
+    /// Returns the numbers of the voxels along the axes.
+    #[inline(always)]
+    pub fn size(&self) -> [usize; 3] { self.size }
+    /// Returns the signed distances of the voxels, whose index of the `(i, j, k)`-th voxel is
+    /// `(k * size[1] + j) * size[0] + i`.
+    #[inline(always)]
+    pub fn values(&self) -> &[f64] { &self.values }
+    /// Returns whether the grid has no voxels or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+    /// Returns the bounding box of the grid, which is empty if the grid has no voxels.
+    pub fn bounding_box(&self) -> BoundingBox<Point3> {
+        match self.is_empty() {
+            true => BoundingBox::new(),
+            false => {
+                let [nx, ny, nz] = self.size;
+                let diag = self.cell_size * Vector3::new(nx as f64, ny as f64, nz as f64);
+                [self.origin, self.origin + diag].iter().collect()
+            }
+        }
+    }
+    /// Returns the center of the `(i, j, k)`-th voxel.
+    #[inline(always)]
+    pub fn center(&self, i: usize, j: usize, k: usize) -> Point3 {
+        let index = Vector3::new(i as f64, j as f64, k as f64);
+        self.origin + self.cell_size * (index + Vector3::new(0.5, 0.5, 0.5))
+    }
+    /// Returns whether the `(i, j, k)`-th voxel is filled or not.
+    /// The voxels out of the grid are not filled.
+    pub fn is_filled(&self, i: usize, j: usize, k: usize) -> bool {
+        let [nx, ny, nz] = self.size;
+        i < nx && j < ny && k < nz && self.values[(k * ny + j) * nx + i] < 0.0
+    }
+    /// Returns the total volume of the filled voxels.
+    pub fn volume(&self) -> f64 {
+        let count = self.values.iter().filter(|value| **value < 0.0).count();
+        count as f64 * self.cell_size.powi(3)
+    }
+
+    /// Returns the signed distance at `point` interpolated trilinearly by the centers of
+    /// the voxels. Out of the centers, the value at the nearest point in the range of
+    /// the centers is extrapolated by adding the distance to the point.
+    /// Returns `f64::INFINITY` if the grid has no voxels.
+    pub fn value_at(&self, point: Point3) -> f64 {
+        if self.is_empty() {
+            return f64::INFINITY;
+        }
+        let [nx, ny, _] = self.size;
+        let mut base = [0; 3];
+        let mut params = [0.0; 3];
+        let mut outer = Vector3::zero();
+        (0..3).for_each(|i| {
+            let coord = (point[i] - self.origin[i]) / self.cell_size - 0.5;
+            let clamped = f64::clamp(coord, 0.0, (self.size[i] - 1) as f64);
+            outer[i] = (coord - clamped) * self.cell_size;
+            base[i] = usize::min(clamped as usize, self.size[i] - 1);
+            params[i] = clamped - base[i] as f64;
+        });
+        let value = (0..8).fold(0.0, |sum, corner| {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let idx: Vec<usize> = (0..3)
+                .map(|i| usize::min(base[i] + offset[i], self.size[i] - 1))
+                .collect();
+            let weight = (0..3).fold(1.0, |w, i| match offset[i] {
+                0 => w * (1.0 - params[i]),
+                _ => w * params[i],
+            });
+            sum + weight * self.values[(idx[2] * ny + idx[1]) * nx + idx[0]]
+        });
+        value + outer.magnitude()
+    }
+
+    /// Returns the grid of the signed distances to the surface offset by `distance`,
+    /// outward if `distance` is positive and inward if negative. The grid is extended so that
+    /// the offset surface is in the grid.
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// // the regular tetrahedron
+    /// let positions = vec![
+    ///     Point3::new(1.0, 1.0, 1.0),
+    ///     Point3::new(1.0, -1.0, -1.0),
+    ///     Point3::new(-1.0, 1.0, -1.0),
+    ///     Point3::new(-1.0, -1.0, 1.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let grid = voxelize(&mesh, 0.1);
+    /// let offset = grid.offset(0.5);
+    /// let bdb = offset.to_polygon().bounding_box();
+    /// assert!(bdb.max().distance(Point3::new(1.5, 1.5, 1.5)) < 0.1);
+    /// ```
+    pub fn offset(&self, distance: f64) -> VoxelGrid {
+        if self.is_empty() {
+            return self.clone();
+        }
+        let n = (f64::max(distance, 0.0) / self.cell_size).ceil() as usize;
+        let shift = self.cell_size * n as f64;
+        let origin = self.origin - Vector3::new(shift, shift, shift);
+        let size = self.size.map(|len| len + 2 * n);
+        VoxelGrid::sample(origin, self.cell_size, size, |pt| {
+            self.value_at(pt) - distance
+        })
+    }
+
+    /// Returns the union of the regions of `self` and `other`.
+    ///
+    /// The result has the voxels of the size of `self` covering both the grids, and the values
+    /// of `other` are resampled by [`value_at`].
+    ///
+    /// [`value_at`]: ./struct.VoxelGrid.html#method.value_at
+    pub fn union(&self, other: &VoxelGrid) -> VoxelGrid {
+        match (self.is_empty(), other.is_empty()) {
+            (true, _) => other.clone(),
+            (_, true) => self.clone(),
+            _ => self.combine(other, f64::min),
+        }
+    }
+    /// Returns the intersection of the regions of `self` and `other`.
+    ///
+    /// The result has the voxels of the size of `self` covering both the grids, and the values
+    /// of `other` are resampled by [`value_at`].
+    ///
+    /// [`value_at`]: ./struct.VoxelGrid.html#method.value_at
+    pub fn intersection(&self, other: &VoxelGrid) -> VoxelGrid {
+        match self.is_empty() || other.is_empty() {
+            true => VoxelGrid::default(),
+            false => self.combine(other, f64::max),
+        }
+    }
+    /// Returns the region of `self` subtracted by the region of `other`.
+    ///
+    /// The result has the voxels of the size of `self` covering both the grids, and the values
+    /// of `other` are resampled by [`value_at`].
+    /// # Examples
+    /// ```
+    /// use truck_meshalgo::prelude::*;
+    /// let cube = |x: f64| {
+    ///     let positions = vec![
+    ///         Point3::new(x, 0.0, 0.0),
+    ///         Point3::new(x + 1.0, 0.0, 0.0),
+    ///         Point3::new(x + 1.0, 1.0, 0.0),
+    ///         Point3::new(x, 1.0, 0.0),
+    ///         Point3::new(x, 0.0, 1.0),
+    ///         Point3::new(x + 1.0, 0.0, 1.0),
+    ///         Point3::new(x + 1.0, 1.0, 1.0),
+    ///         Point3::new(x, 1.0, 1.0),
+    ///     ];
+    ///     let faces = Faces::from_iter(&[
+    ///         [3, 2, 1, 0],
+    ///         [0, 1, 5, 4],
+    ///         [1, 2, 6, 5],
+    ///         [2, 3, 7, 6],
+    ///         [3, 0, 4, 7],
+    ///         [4, 5, 6, 7],
+    ///     ]);
+    ///     PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+    /// };
+    /// let grid0 = voxelize(&cube(0.0), 0.1);
+    /// let grid1 = voxelize(&cube(0.5), 0.1);
+    ///
+    /// assert!(f64::abs(grid0.union(&grid1).volume() - 1.5) < 0.01);
+    /// assert!(f64::abs(grid0.intersection(&grid1).volume() - 0.5) < 0.01);
+    /// assert!(f64::abs(grid0.difference(&grid1).volume() - 0.5) < 0.01);
+    /// ```
+    pub fn difference(&self, other: &VoxelGrid) -> VoxelGrid {
+        match (self.is_empty(), other.is_empty()) {
+            (true, _) => VoxelGrid::default(),
+            (_, true) => self.clone(),
+            _ => self.combine(other, |a, b| f64::max(a, -b)),
+        }
+    }
+
+    /// Returns the closed surface of the filled region by marching tetrahedra, on the grid
+    /// whose nodes are the centers of the voxels.
+    ///
+    /// The grid is surrounded by the empty voxels, so the surface is closed even if
+    /// the filled voxels touch the boundary of the grid. The output has no normals.
+    /// Use `NormalFilters` if necessary.
+    pub fn to_polygon(&self) -> PolygonMesh {
+        let [nx, ny, nz] = self.size.map(|len| len + 2);
+        let values: Vec<f64> = (0..nx * ny * nz)
+            .map(|idx| {
+                let (i, j, k) = (idx % nx, idx / nx % ny, idx / (nx * ny));
+                match i == 0 || j == 0 || k == 0 || i + 1 == nx || j + 1 == ny || k + 1 == nz {
+                    true => self.cell_size,
+                    false => self.values[((k - 1) * self.size[1] + j - 1) * self.size[0] + i - 1],
+                }
+            })
+            .collect();
+        let half = self.cell_size / 2.0;
+        let origin = self.origin - Vector3::new(half, half, half);
+        marching_tetrahedra_on_grid(&values, [nx, ny, nz], origin, self.cell_size)
+    }
+
+    fn sample(
+        origin: Point3,
+        cell_size: f64,
+        size: [usize; 3],
+        field: impl Fn(Point3) -> f64,
+    ) -> VoxelGrid {
+        let mut grid = VoxelGrid {
+            origin,
+            cell_size,
+            size,
+            values: Vec::new(),
+        };
+        let [nx, ny, nz] = size;
+        grid.values = (0..nx * ny * nz)
+            .map(|idx| field(grid.center(idx % nx, idx / nx % ny, idx / (nx * ny))))
+            .collect();
+        grid
+    }
+
+    /// Resamples the values of `self` and `other` on the grid covering both the grids
+    /// aligned with `self`, and combines them by `op`.
+    fn combine(&self, other: &VoxelGrid, op: impl Fn(f64, f64) -> f64) -> VoxelGrid {
+        let mut bdb = self.bounding_box();
+        bdb += &other.bounding_box();
+        let cell_size = self.cell_size;
+        let lower = (0..3).map(|i| ((bdb.min()[i] - self.origin[i]) / cell_size).floor());
+        let lower: Vec<f64> = lower.collect();
+        let origin = self.origin + cell_size * Vector3::new(lower[0], lower[1], lower[2]);
+        let size = [0, 1, 2].map(|i| ((bdb.max()[i] - origin[i]) / cell_size).ceil() as usize);
+        VoxelGrid::sample(origin, cell_size, size, |pt| {
+            op(self.value_at(pt), other.value_at(pt))
+        })
+    }
+}
+
+/// Voxelizes `mesh` into the grid of the voxels with `cell_size`, which have the signed
+/// distances to `mesh` at the centers.
+///
+/// The voxels are inside if the winding numbers of the centers with respect to `mesh` are
+/// not zero. The winding numbers are counted by the signed crossings of the rays, and
+/// the majority of three rays is adopted for the robustness. So, the meshes with
+/// self-intersections, overlapping parts or the inverted orientation, e.g. scan data, are
+/// voxelized into the union of the regions surrounded by them. The mesh is assumed to be
+/// closed, but the small gaps are allowed since the rays rarely go through them.
+///
+/// The grid has one layer of the voxels around the bounding box of `mesh`.
+/// Returns the empty grid if `mesh` has no positions or `cell_size` is not positive.
+/// # Examples
+/// ```
+/// use truck_meshalgo::prelude::*;
+/// use truck_topology::shell::ShellCondition;
+/// // the unit cube
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(1.0, 0.0, 1.0),
+///     Point3::new(1.0, 1.0, 1.0),
+///     Point3::new(0.0, 1.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[
+///     [3, 2, 1, 0],
+///     [0, 1, 5, 4],
+///     [1, 2, 6, 5],
+///     [2, 3, 7, 6],
+///     [3, 0, 4, 7],
+///     [4, 5, 6, 7],
+/// ]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let grid = voxelize(&mesh, 0.1);
+/// assert_eq!(grid.size(), [12, 12, 12]);
+/// assert!(grid.is_filled(1, 1, 1) && !grid.is_filled(0, 1, 1));
+/// assert!(f64::abs(grid.volume() - 1.0) < 0.01);
+///
+/// let remeshed = grid.to_polygon();
+/// assert_eq!(remeshed.shell_condition(), ShellCondition::Closed);
+/// let bdb = remeshed.bounding_box();
+/// assert!(bdb.min().distance(Point3::new(0.0, 0.0, 0.0)) < 0.1);
+/// assert!(bdb.max().distance(Point3::new(1.0, 1.0, 1.0)) < 0.1);
+/// ```
+pub fn voxelize(mesh: &PolygonMesh, cell_size: f64) -> VoxelGrid {
+    let bdb = mesh.bounding_box();
+    if bdb.is_empty() || cell_size <= 0.0 {
+        return VoxelGrid::default();
+    }
+    let bvh = MeshBVH::new(mesh);
+    let (min, max) = (*bdb.min(), *bdb.max());
+    let origin = min - Vector3::new(cell_size, cell_size, cell_size);
+    let size = [0, 1, 2].map(|i| ((max[i] - min[i]) / cell_size).ceil() as usize + 2);
+    VoxelGrid::sample(origin, cell_size, size, |pt| {
+        let dist = bvh.distance(pt);
+        let inside = RAY_DIRECTIONS
+            .iter()
+            .filter(|dir| winding_number(&bvh, pt, **dir) != 0)
+            .count();
+        match inside >= 2 {
+            true => -dist,
+            false => dist,
+        }
+    })
+}
+
+/// The winding number of `point` counted by the signed crossings of the ray toward
+/// `direction`, positive if the ray goes out through the front of the faces.
+fn winding_number(bvh: &MeshBVH, point: Point3, direction: Vector3) -> i64 {
+    let mut res = 0;
+    bvh.traverse(point, direction, f64::INFINITY, |hit| {
+        let p = hit.positions;
+        let dot = (p[1] - p[0]).cross(p[2] - p[0]).dot(direction);
+        if dot > 0.0 {
+            res += 1;
+        } else if dot < 0.0 {
+            res -= 1;
+        }
+        Some(f64::INFINITY)
+    });
+    res
+}
@@ -0,0 +1,53 @@
+use super::*;
+use truck_modeling::builder;
+
+#[test]
+fn draft_analysis_of_cube() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let meshed = cube.triangulation(0.01).unwrap();
+
+    let descriptor = DraftDescriptor::default();
+    let types = meshed.draft_analysis(&descriptor);
+    assert_eq!(types.len(), 6);
+    let count = |draft_type: DraftType| types.iter().filter(|t| **t == draft_type).count();
+    assert_eq!(count(DraftType::Positive), 1);
+    assert_eq!(count(DraftType::Negative), 1);
+    assert_eq!(count(DraftType::Vertical), 4);
+
+    // the polygons of the mesh
+    let mesh = meshed.into_polygon();
+    let types = mesh.draft_analysis(&descriptor);
+    assert_eq!(types.len(), mesh.faces().len());
+    assert!(types.iter().all(|t| *t != DraftType::Mixed));
+    assert_eq!(
+        types.iter().filter(|t| **t == DraftType::Vertical).count(),
+        8
+    );
+}
+
+#[test]
+fn draft_limits() {
+    // a quadrangle tilted by 2 degrees from the pull direction
+    let angle = Rad::from(Deg(2.0));
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(-angle.sin(), 1.0, angle.cos()),
+        Point3::new(-angle.sin(), 0.0, angle.cos()),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2, 3], [3, 2, 1, 0]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+
+    let types = mesh.draft_analysis(&DraftDescriptor::default());
+    assert_eq!(types, vec![DraftType::Positive, DraftType::Negative]);
+
+    let descriptor = DraftDescriptor {
+        positive_limit: Deg(3.0).into(),
+        ..Default::default()
+    };
+    let types = mesh.draft_analysis(&descriptor);
+    assert_eq!(types, vec![DraftType::Vertical, DraftType::Negative]);
+}
@@ -0,0 +1,46 @@
+use super::*;
+use truck_modeling::builder;
+
+#[test]
+fn feature_edges_of_cube() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let mut mesh = cube.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    let edges = mesh.extract_feature_edges(Deg(30.0).into());
+    // the 12 edges of the cube, divided at the vertices of triangles on them
+    assert!(edges.len() >= 12);
+    edges.iter().for_each(|edge| {
+        let (p, q) = (mesh.positions()[edge[0]], mesh.positions()[edge[1]]);
+        let on_edge = |i: usize, j: usize| {
+            let near = |a: f64, b: f64| a.near(&b);
+            (near(p[i], 0.0) || near(p[i], 1.0))
+                && (near(p[j], 0.0) || near(p[j], 1.0))
+                && p[i].near(&q[i])
+                && p[j].near(&q[j])
+        };
+        assert!(on_edge(0, 1) || on_edge(1, 2) || on_edge(2, 0), "{:?} {:?}", p, q);
+    });
+    // no feature edges by the straight angle in the closed mesh
+    assert!(mesh.extract_feature_edges(Deg(180.0).into()).is_empty());
+    // the borrowed view gives the same edges
+    assert_eq!(mesh.as_slice().extract_feature_edges(Deg(30.0).into()), edges);
+}
+
+#[test]
+fn feature_edges_of_non_manifold() {
+    // three triangles sharing an edge
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(-1.0, 0.1, 0.0),
+        Point3::new(-1.0, -0.1, 0.0),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2], [1, 0, 3], [1, 0, 4]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let edges = mesh.extract_feature_edges(Deg(180.0).into());
+    assert_eq!(edges, vec![[0, 1], [0, 2], [0, 3], [0, 4], [1, 2], [1, 3], [1, 4]]);
+}
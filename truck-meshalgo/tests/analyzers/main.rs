@@ -4,3 +4,9 @@ mod topology;
 mod collision;
 mod point_cloud;
 mod splitting;
+mod ray_casting;
+mod draft;
+mod parting_line;
+mod symmetry;
+mod feature_edges;
+mod visibility;
@@ -0,0 +1,84 @@
+use super::*;
+use truck_modeling::*;
+
+// the signed area of the projection of the closed polyline onto the xy-plane
+fn signed_area(line: &PolylineCurve<Point3>) -> f64 {
+    line.windows(2)
+        .map(|p| (p[0].x * p[1].y - p[1].x * p[0].y) / 2.0)
+        .sum()
+}
+
+#[test]
+fn parting_line_of_cube() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let meshed = cube.triangulation(0.01).unwrap();
+
+    let lines = meshed.parting_lines(Vector3::unit_z());
+    assert_eq!(lines.len(), 1);
+    let line = &lines[0];
+    assert_eq!(line.front(), line.back());
+    // around the side faces
+    line.iter().for_each(|pt| {
+        assert!(0.0 < pt.z && pt.z < 1.0);
+        assert!([pt.x, pt.y].iter().any(|x| x.near(&0.0) || x.near(&1.0)));
+    });
+    assert!(signed_area(line).near(&1.0));
+
+    // the inverted line for the opposite direction
+    let lines = meshed.parting_lines(-Vector3::unit_z());
+    assert_eq!(lines.len(), 1);
+    assert!(signed_area(&lines[0]).near(&-1.0));
+}
+
+#[test]
+fn parting_lines_of_torus() {
+    let v = builder::vertex(Point3::new(3.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::new(2.0, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    let shell = builder::rsweep(&circle, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let torus = Solid::new(vec![shell]).triangulation(0.01).unwrap();
+
+    let mut lines = torus.parting_lines(Vector3::unit_z());
+    assert_eq!(lines.len(), 2);
+    lines.sort_by(|a, b| signed_area(a).partial_cmp(&signed_area(b)).unwrap());
+    // the inner equator is clockwise, and the outer equator is counterclockwise
+    lines.iter().zip([1.0, 3.0]).for_each(|(line, radius)| {
+        assert_eq!(line.front(), line.back());
+        line.iter().for_each(|pt| {
+            assert!(f64::abs(pt.z) < 0.05);
+            assert!(f64::abs(pt.x.hypot(pt.y) - radius) < 0.05);
+        });
+    });
+    assert!(signed_area(&lines[0]) < 0.0);
+    assert!(signed_area(&lines[1]) > 0.0);
+}
+
+#[test]
+fn parting_line_of_open_mesh() {
+    // a roof whose ridge is parallel to the y-axis, pulled toward +x
+    let positions = vec![
+        Point3::new(-1.0, 0.0, 0.0),
+        Point3::new(-1.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(0.0, 1.0, 1.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+    ];
+    // the triangles are symmetric so that the normals of the ridge are vertical
+    let faces = Faces::from_iter([[0, 2, 3], [0, 3, 1], [2, 4, 3], [3, 4, 5]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+
+    let lines = mesh.parting_lines(Vector3::unit_x());
+    assert_eq!(lines.len(), 1);
+    let line = &lines[0];
+    assert_ne!(line.front(), line.back());
+    // the ridge, from the end of y = 1 to the end of y = 0
+    line.iter().for_each(|pt| assert!(pt.x.near(&0.0) && pt.z.near(&1.0)));
+    assert!(line.front().y.near(&1.0));
+    assert!(line.back().y.near(&0.0));
+
+    // no parting lines if all faces are positive
+    assert!(mesh.parting_lines(Vector3::unit_z()).is_empty());
+}
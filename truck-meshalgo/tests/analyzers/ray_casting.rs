@@ -0,0 +1,107 @@
+use super::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+#[test]
+fn sphere_ray_cast() {
+    let sphere = common::shapes::sphere(Point3::new(1.0, 2.0, 3.0), 2.0, 50, 50);
+    let bvh = MeshBVH::new(&sphere);
+    assert_eq!(
+        bvh.len(),
+        sphere.face_iter().map(|face| face.len() - 2).sum::<usize>()
+    );
+    for _ in 0..100 {
+        let dir = Vector3::new(
+            2.0 * rand::random::<f64>() - 1.0,
+            2.0 * rand::random::<f64>() - 1.0,
+            2.0 * rand::random::<f64>() - 1.0,
+        )
+        .normalize();
+        // from the outside toward the center
+        let origin = Point3::new(1.0, 2.0, 3.0) + 10.0 * dir;
+        let hit = bvh.ray_cast(origin, -dir).unwrap();
+        assert!(f64::abs(hit.distance - 8.0) < 0.05, "{}", hit.distance);
+        assert!(hit.point().distance(origin + hit.distance * (-dir)) < 1.0e-6);
+        // from the inside
+        let hit = bvh.ray_cast(Point3::new(1.0, 2.0, 3.0), dir).unwrap();
+        assert!(f64::abs(hit.distance - 2.0) < 0.05, "{}", hit.distance);
+        // outward
+        assert!(bvh.ray_cast(origin, dir).is_none());
+        assert!(bvh.is_occluded(origin, -dir, 9.0));
+        assert!(!bvh.is_occluded(origin, -dir, 7.0));
+    }
+}
+
+#[test]
+fn compare_with_brute_force() {
+    let positions: Vec<_> = (0..300)
+        .map(|_| {
+            Point3::new(
+                rand::random::<f64>(),
+                rand::random::<f64>(),
+                rand::random::<f64>(),
+            )
+        })
+        .collect();
+    let faces = Faces::from_iter((0..100).map(|i| [i, i + 100, i + 200]));
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let bvh = MeshBVH::new(&mesh);
+    for _ in 0..100 {
+        let origin = Point3::new(rand::random::<f64>(), rand::random::<f64>(), -1.0);
+        let dir = Vector3::new(
+            rand::random::<f64>() - 0.5,
+            rand::random::<f64>() - 0.5,
+            1.0,
+        );
+        let answer = mesh
+            .face_iter()
+            .enumerate()
+            .filter_map(|(i, face)| {
+                let bvh = MeshBVH::new(&PolygonMesh::new(
+                    face.iter().map(|v| mesh.positions()[v.pos]).collect(),
+                    Vec::new(),
+                    Vec::new(),
+                    Faces::from_iter([[0, 1, 2]]),
+                ));
+                bvh.ray_cast(origin, dir).map(|hit| (i, hit.distance))
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let hit = bvh.ray_cast(origin, dir);
+        match answer {
+            Some((i, distance)) => {
+                let hit = hit.unwrap();
+                assert_eq!(hit.face_index, i);
+                assert!(f64::abs(hit.distance - distance) < 1.0e-10);
+            }
+            None => assert!(hit.is_none()),
+        }
+    }
+}
+
+#[test]
+fn sphere_distance_and_contains() {
+    let center = Point3::new(1.0, 2.0, 3.0);
+    let sphere = common::shapes::sphere(center, 2.0, 50, 50);
+    let bvh = MeshBVH::new(&sphere);
+    for _ in 0..100 {
+        let dir = Vector3::new(
+            2.0 * rand::random::<f64>() - 1.0,
+            2.0 * rand::random::<f64>() - 1.0,
+            2.0 * rand::random::<f64>() - 1.0,
+        )
+        .normalize();
+        let r = 4.0 * rand::random::<f64>();
+        let point = center + r * dir;
+        if f64::abs(r - 2.0) > 0.05 {
+            assert_eq!(bvh.contains(point), r < 2.0, "{}", r);
+        }
+        let distance = bvh.distance(point);
+        let brute_force = sphere
+            .positions()
+            .iter()
+            .map(|p| p.distance(point))
+            .fold(f64::INFINITY, f64::min);
+        assert!(f64::abs(distance - f64::abs(r - 2.0)) < 0.05, "{} {}", distance, r);
+        assert!(distance <= brute_force + 1.0e-10);
+    }
+}
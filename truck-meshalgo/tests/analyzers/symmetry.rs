@@ -0,0 +1,99 @@
+use super::*;
+use truck_modeling::builder;
+
+fn hexagonal_prism() -> PolygonMesh {
+    let vertices: Vec<_> = (0..6)
+        .map(|i| {
+            let t = std::f64::consts::PI * i as f64 / 3.0;
+            builder::vertex(Point3::new(f64::cos(t), f64::sin(t), 0.0))
+        })
+        .collect();
+    let wire: truck_modeling::Wire = (0..6)
+        .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % 6]))
+        .collect();
+    let hexagon = builder::try_attach_plane(&vec![wire]).unwrap();
+    let prism = builder::tsweep(&hexagon, Vector3::new(0.0, 0.0, 0.5));
+    let mut mesh = prism.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    mesh
+}
+
+#[test]
+fn symmetries_of_hexagonal_prism() {
+    let mesh = hexagonal_prism();
+    let symmetries = mesh.symmetries(&SymmetryDescriptor::default());
+    let center = Point3::new(0.0, 0.0, 0.25);
+    let mut reflections = 0;
+    let mut half_turns = 0;
+    let mut sixfold = 0;
+    symmetries.iter().for_each(|(symmetry, error)| {
+        assert!(error.so_small());
+        match symmetry {
+            Symmetry::Reflection { origin, normal } => {
+                assert!((origin - center).dot(*normal).so_small());
+                reflections += 1;
+            }
+            Symmetry::Rotation {
+                origin,
+                axis,
+                order,
+            } => {
+                assert!((origin - center).cross(*axis).so_small());
+                match order {
+                    2 => {
+                        assert!(axis.z.so_small());
+                        half_turns += 1;
+                    }
+                    6 => {
+                        assert!(axis.cross(Vector3::unit_z()).so_small());
+                        sixfold += 1;
+                    }
+                    _ => panic!("unexpected order: {}", order),
+                }
+            }
+        }
+    });
+    // the horizontal plane and the six vertical planes
+    assert_eq!(reflections, 7);
+    // the six horizontal axes
+    assert_eq!(half_turns, 6);
+    assert_eq!(sixfold, 1);
+}
+
+#[test]
+fn symmetry_errors() {
+    let mut mesh = hexagonal_prism();
+    let turn = Symmetry::Rotation {
+        origin: Point3::origin(),
+        axis: Vector3::unit_z(),
+        order: 6,
+    };
+    assert!(mesh.symmetry_error(&turn).so_small());
+    let quarter = Symmetry::Rotation {
+        origin: Point3::origin(),
+        axis: Vector3::unit_z(),
+        order: 4,
+    };
+    assert!(mesh.symmetry_error(&quarter) > 0.01);
+
+    // stretched along the x axis, only the symmetries of a box remain.
+    mesh.transform_by(Matrix4::from_nonuniform_scale(2.0, 1.0, 1.0));
+    let symmetries = mesh.symmetries(&SymmetryDescriptor::default());
+    assert_eq!(symmetries.len(), 6);
+    assert!(symmetries.iter().all(|(symmetry, _)| match symmetry {
+        Symmetry::Reflection { .. } => true,
+        Symmetry::Rotation { order, .. } => *order == 2,
+    }));
+}
+
+#[test]
+fn symmetries_of_degenerate_mesh() {
+    let positions = vec![Point3::new(1.0, 2.0, 3.0); 3];
+    let faces = Faces::from_iter([[0, 1, 2]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let symmetries = mesh.symmetries(&SymmetryDescriptor::default());
+    assert!(symmetries.is_empty(), "{:?}", symmetries);
+    assert!(PolygonMesh::default()
+        .symmetries(&SymmetryDescriptor::default())
+        .is_empty());
+}
@@ -0,0 +1,121 @@
+use super::*;
+use truck_modeling::builder;
+
+// the box with outward quadrangles in the order of -z, +z, -y, +y, -x, +x
+fn box_mesh(min: Point3, max: Point3) -> PolygonMesh {
+    let positions: Vec<Point3> = (0..8)
+        .map(|i| {
+            Point3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        })
+        .collect();
+    let faces = Faces::from_iter([
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ]);
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+#[test]
+fn visibility_of_cube() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let meshed = cube.triangulation(0.01).unwrap();
+
+    let directions = [Vector3::unit_z() * 2.0, -Vector3::unit_z()];
+    let visibilities = meshed.visibility(&directions, &Default::default());
+    assert_eq!(visibilities.len(), 6);
+    let normals = cube.boundaries()[0]
+        .face_iter()
+        .map(|face| face.oriented_surface().normal(0.5, 0.5))
+        .collect::<Vec<_>>();
+    normals.iter().zip(&visibilities).for_each(|(normal, visibility)| {
+        match normal.z {
+            z if z > 0.5 => {
+                assert_eq!(visibility.ratios, vec![1.0, 0.0]);
+                assert_eq!(visibility.best_direction(), Some(0));
+            }
+            z if z < -0.5 => {
+                assert_eq!(visibility.ratios, vec![0.0, 1.0]);
+                assert_eq!(visibility.best_direction(), Some(1));
+            }
+            // the side faces are parallel to the directions
+            _ => {
+                assert_eq!(visibility.ratios, vec![0.0, 0.0]);
+                assert_eq!(visibility.best_direction(), None);
+            }
+        }
+        assert_eq!(visibility.is_undercut(), normal.z.abs() < 0.5);
+    });
+
+    // the same visibilities from the shell
+    let shell_visibilities = meshed.boundaries()[0].visibility(&directions, &Default::default());
+    assert_eq!(shell_visibilities, visibilities);
+}
+
+#[test]
+fn shadowed_box() {
+    // the small box floating above the large box
+    let mut mesh = box_mesh(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+    mesh.merge(box_mesh(
+        Point3::new(0.5, 0.5, 3.0),
+        Point3::new(1.5, 1.5, 4.0),
+    ));
+    let directions = [Vector3::unit_z(), -Vector3::unit_z(), Vector3::unit_x()];
+    let descriptor = VisibilityDescriptor {
+        subdivision: 10,
+        ..Default::default()
+    };
+    let visibilities = mesh.visibility(&directions, &descriptor);
+    assert_eq!(visibilities.len(), 12);
+
+    // a quarter of the top of the large box is shadowed
+    let top = &visibilities[1];
+    assert!(f64::abs(top.ratios[0] - 0.75) < 0.1, "{:?}", top.ratios);
+    assert_eq!(&top.ratios[1..], &[0.0, 0.0]);
+    assert!(top.is_undercut());
+    assert_eq!(top.best_direction(), Some(0));
+    // the bottom of the small box is hidden by the large box
+    let bottom = &visibilities[6];
+    assert_eq!(bottom.ratios, vec![0.0, 0.0, 0.0]);
+    assert_eq!(bottom.best_direction(), None);
+    // the other faces are not shadowed
+    assert_eq!(visibilities[0].ratios, vec![0.0, 1.0, 0.0]);
+    assert_eq!(visibilities[7].ratios, vec![1.0, 0.0, 0.0]);
+    assert_eq!(visibilities[5].ratios, vec![0.0, 0.0, 1.0]);
+    assert_eq!(visibilities[11].ratios, vec![0.0, 0.0, 1.0]);
+    assert!(!visibilities[11].is_undercut());
+}
+
+#[test]
+fn visibility_of_steep_face() {
+    // a quadrangle tilted by 2 degrees from the pull direction
+    let angle = Rad::from(Deg(2.0));
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(-angle.sin(), 1.0, angle.cos()),
+        Point3::new(-angle.sin(), 0.0, angle.cos()),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2, 3]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let directions = [Vector3::unit_z()];
+    let visibilities = mesh.visibility(&directions, &Default::default());
+    assert_eq!(visibilities[0].ratios, vec![1.0]);
+    let descriptor = VisibilityDescriptor {
+        min_draft: Deg(3.0).into(),
+        ..Default::default()
+    };
+    let visibilities = mesh.visibility(&directions, &descriptor);
+    assert_eq!(visibilities[0].ratios, vec![0.0]);
+    assert!(visibilities[0].is_undercut());
+}
@@ -0,0 +1,99 @@
+use truck_meshalgo::prelude::*;
+
+// the unit square in the xy-plane divided into two triangles, with the uv coordinates `scale` * xy
+fn square(scale: f64) -> PolygonMesh {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let uv_coords = positions
+        .iter()
+        .map(|p| Vector2::new(p[0], p[1]) * scale)
+        .collect();
+    let faces = Faces::from_iter([
+        [(0, Some(0), None), (1, Some(1), None), (2, Some(2), None)],
+        [(0, Some(0), None), (2, Some(2), None), (3, Some(3), None)],
+    ]);
+    PolygonMesh::new(positions, uv_coords, Vec::new(), faces)
+}
+
+#[test]
+fn bake_face_ids() {
+    let mesh = square(0.5);
+    let descriptor = BakingDescriptor {
+        width: 32,
+        height: 32,
+        padding: 2,
+    };
+    let samples = mesh.texel_samples(32, 32);
+    assert_eq!(samples.len(), 16 * 16);
+    assert!(samples
+        .iter()
+        .all(|sample| sample.position[0].near(&((sample.pixel[0] as f64 + 0.5) / 16.0))));
+
+    let image = mesh.bake(&BakingAttribute::FaceId, &descriptor).unwrap();
+    let image = image.to_rgba8();
+    // below and above the diagonal
+    assert_eq!(image.get_pixel(10, 2).0, [0, 0, 0, 255]);
+    assert_eq!(image.get_pixel(2, 10).0, [1, 0, 0, 255]);
+    // padding
+    assert_eq!(image.get_pixel(17, 5).0, [0, 0, 0, 255]);
+    assert_eq!(image.get_pixel(5, 17).0, [1, 0, 0, 255]);
+    assert_eq!(image.get_pixel(18, 5)[3], 0);
+    assert_eq!(image.get_pixel(5, 18)[3], 0);
+    assert_eq!(image.get_pixel(25, 25)[3], 0);
+}
+
+#[test]
+fn bake_ambient_occlusion() {
+    // the square with a low roof over the half x < 0.5
+    let mut mesh = square(1.0);
+    let roof = PolygonMesh::new(
+        vec![
+            Point3::new(-1.0, -1.0, 0.1),
+            Point3::new(0.5, -1.0, 0.1),
+            Point3::new(0.5, 2.0, 0.1),
+            Point3::new(-1.0, 2.0, 0.1),
+        ],
+        Vec::new(),
+        Vec::new(),
+        Faces::from_iter([[3, 2, 1, 0]]),
+    );
+    mesh.merge(roof);
+    let descriptor = BakingDescriptor {
+        width: 16,
+        height: 16,
+        padding: 0,
+    };
+    let attribute = BakingAttribute::AmbientOcclusion {
+        samples: 64,
+        max_distance: f64::INFINITY,
+    };
+    let image = mesh.bake(&attribute, &descriptor).unwrap().to_luma_alpha8();
+    let gray = |i: u32| image.get_pixel(i, 8)[0];
+    // under the roof
+    assert!(gray(2) < 64);
+    // in the open
+    assert!(gray(13) > 128);
+    assert!(gray(13) < 255);
+    assert!(gray(9) < gray(13));
+}
+
+#[test]
+fn no_uv_coords() {
+    let mesh = PolygonMesh::new(
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ],
+        Vec::new(),
+        Vec::new(),
+        Faces::from_iter([[0, 1, 2]]),
+    );
+    let descriptor = BakingDescriptor::default();
+    assert!(mesh.bake(&BakingAttribute::Normal, &descriptor).is_none());
+    assert!(mesh.texel_samples(8, 8).is_empty());
+}
@@ -0,0 +1,105 @@
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+fn check_booleans(mesh0: &PolygonMesh, mesh1: &PolygonMesh, volumes: [f64; 3]) {
+    let union = mesh0.union(mesh1, 1.0e-6).unwrap();
+    let intersection = mesh0.intersection(mesh1, 1.0e-6).unwrap();
+    let difference = mesh0.difference(mesh1, 1.0e-6).unwrap();
+    [union, intersection, difference]
+        .iter()
+        .zip(volumes)
+        .for_each(|(mesh, volume0)| {
+            if volume0 > 0.0 {
+                assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+            } else {
+                assert_eq!(mesh.faces().len(), 0);
+            }
+            assert!(f64::abs(volume(mesh) - volume0) < 1.0e-6, "{} {}", volume(mesh), volume0);
+        });
+}
+
+#[test]
+fn overlapping_cubes() {
+    let mesh0 = cube_mesh(Point3::origin());
+    let mesh1 = cube_mesh(Point3::new(0.5, 0.5, 0.5));
+    check_booleans(&mesh0, &mesh1, [1.875, 0.125, 0.875]);
+    check_booleans(&mesh1, &mesh0, [1.875, 0.125, 0.875]);
+}
+
+#[test]
+fn rotated_cubes() {
+    let mesh0 = cube_mesh(Point3::origin());
+    let mut mesh1 = cube_mesh(Point3::new(-0.5, -0.5, -0.5));
+    mesh1.transform_by(
+        Matrix4::from_translation(Vector3::new(0.8, 0.7, 0.9))
+            * Matrix4::from_angle_x(Rad(0.3))
+            * Matrix4::from_angle_z(Rad(0.5)),
+    );
+    let union = mesh0.union(&mesh1, 1.0e-6).unwrap();
+    let intersection = mesh0.intersection(&mesh1, 1.0e-6).unwrap();
+    let difference = mesh0.difference(&mesh1, 1.0e-6).unwrap();
+    for mesh in [&union, &intersection, &difference] {
+        assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    }
+    assert!(f64::abs(volume(&union) + volume(&intersection) - 2.0) < 1.0e-6);
+    assert!(f64::abs(volume(&difference) + volume(&intersection) - 1.0) < 1.0e-6);
+    assert!(volume(&intersection) > 0.1);
+}
+
+#[test]
+fn coplanar_cubes() {
+    // the cubes share the planes of four faces
+    let mesh0 = cube_mesh(Point3::origin());
+    let mesh1 = cube_mesh(Point3::new(0.5, 0.0, 0.0));
+    check_booleans(&mesh0, &mesh1, [1.5, 0.5, 0.5]);
+    // the same cubes
+    check_booleans(&mesh0, &mesh0, [1.0, 1.0, 0.0]);
+    // the cubes touching at a face
+    let mesh1 = cube_mesh(Point3::new(1.0, 0.0, 0.0));
+    check_booleans(&mesh0, &mesh1, [2.0, 0.0, 1.0]);
+    // the disjoint cubes
+    let mesh1 = cube_mesh(Point3::new(2.0, 0.0, 0.0));
+    check_booleans(&mesh0, &mesh1, [2.0, 0.0, 1.0]);
+}
+
+#[test]
+fn sphere_and_cube() {
+    let sphere = sphere_mesh(Point3::origin(), 1.0);
+    let cube = cube_mesh(Point3::origin());
+    let (volume0, volume1) = (volume(&sphere), volume(&cube));
+    let union = sphere.union(&cube, 1.0e-6).unwrap();
+    let intersection = sphere.intersection(&cube, 1.0e-6).unwrap();
+    let difference = sphere.difference(&cube, 1.0e-6).unwrap();
+    for mesh in [&union, &intersection, &difference] {
+        assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    }
+    // an octant of the sphere is in the cube
+    assert!(f64::abs(volume(&intersection) - volume0 / 8.0) < 1.0e-2);
+    assert!(f64::abs(volume(&union) + volume(&intersection) - volume0 - volume1) < 1.0e-6);
+    assert!(f64::abs(volume(&difference) + volume(&intersection) - volume0) < 1.0e-6);
+    // the corner of the cube is outside of the sphere
+    let bvh = MeshBVH::new(&union);
+    assert!(bvh.contains(Point3::new(0.9, 0.9, 0.9)));
+    assert!(bvh.contains(Point3::new(-0.5, -0.5, -0.5)));
+    let bvh = MeshBVH::new(&difference);
+    assert!(!bvh.contains(Point3::new(0.5, 0.5, 0.5)));
+    assert!(bvh.contains(Point3::new(-0.5, 0.5, 0.5)));
+}
+
+#[test]
+fn open_mesh() {
+    let mesh0 = cube_mesh(Point3::origin());
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.5),
+        Point3::new(1.0, 0.0, 0.5),
+        Point3::new(1.0, 1.0, 0.5),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2]]);
+    let mesh1 = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    assert!(mesh0.union(&mesh1, 1.0e-6).is_none());
+    assert!(mesh1.difference(&mesh0, 1.0e-6).is_none());
+    assert!(mesh0.intersection(&mesh0, 0.0).is_none());
+}
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+use std::process::Command;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("truck-meshalgo-cli");
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn unit_cube() -> Solid {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    builder::tsweep(&f, Vector3::unit_z())
+}
+
+#[test]
+fn tessellate_and_decimate() {
+    let json = temp_path("cube.json");
+    let obj = temp_path("cube.obj");
+    let stl = temp_path("decimated.stl");
+    std::fs::write(&json, serde_json::to_vec(&unit_cube().compress()).unwrap()).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_truck-tessellate"))
+        .args(["-t", "0.01"])
+        .arg(&json)
+        .arg(&obj)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mesh = obj::read(std::fs::File::open(&obj).unwrap()).unwrap();
+    assert_eq!(mesh.positions().len(), 8);
+
+    // the plane divided into 10 x 10 squares
+    let positions: Vec<Point3> = (0..=10)
+        .flat_map(|i| (0..=10).map(move |j| Point3::new(i as f64, j as f64, 0.0)))
+        .collect();
+    let index = |i: usize, j: usize| i * 11 + j;
+    let faces = Faces::from_iter((0..10).flat_map(|i| {
+        (0..10).map(move |j| [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)])
+    }));
+    let plane = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    obj::write(&plane, std::fs::File::create(&obj).unwrap()).unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_truck-mesh"))
+        .args(["--triangulate", "--decimate-error", "1.0e-6", "--weld"])
+        .arg(&obj)
+        .arg(&stl)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let file = std::fs::File::open(&stl).unwrap();
+    let mut mesh = stl::read(file, stl::STLType::Automatic).unwrap();
+    mesh.put_together_same_attrs();
+    assert!(mesh.tri_faces().len() < 200);
+    let bdb = mesh.bounding_box();
+    assert_near!(*bdb.min(), Point3::new(0.0, 0.0, 0.0));
+    assert_near!(*bdb.max(), Point3::new(10.0, 10.0, 0.0));
+}
+
+#[test]
+fn invalid_arguments() {
+    let (json, obj) = (temp_path("invalid.json"), temp_path("invalid.obj"));
+    let tessellate = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_truck-tessellate"))
+            .args(args)
+            .arg(&json)
+            .arg(&obj)
+            .output()
+            .unwrap()
+    };
+    for tol in ["-1.0", "0.0", "NaN", "inf"].iter() {
+        let output = tessellate(&["-t", tol]);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("tolerance must be positive and finite"), "{}", tol);
+    }
+    let output = tessellate(&["--unknown"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown option: --unknown"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_truck-mesh"))
+        .args(["--decimate", "many"])
+        .arg(&obj)
+        .arg(temp_path("invalid.stl"))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid argument of --decimate"));
+}
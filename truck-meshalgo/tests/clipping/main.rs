@@ -0,0 +1,140 @@
+use std::iter::FromIterator;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+fn torus_mesh() -> PolygonMesh {
+    let v = builder::vertex(Point3::new(1.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::new(1.0, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    let torus = builder::rsweep(&circle, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    welded(&Solid::new(vec![torus]))
+}
+
+#[test]
+fn sphere_section() {
+    let sphere = sphere_mesh(Point3::origin(), 1.0);
+    assert_eq!(sphere.shell_condition(), ShellCondition::Closed);
+    let origin = Point3::new(0.0, 0.0, 0.3);
+    let normal = Vector3::new(0.0, 1.0, 1.0);
+    let lower = clip_by_plane(&sphere, origin, normal, true);
+    let upper = clip_by_plane(&sphere, origin, -normal, true);
+    assert_eq!(lower.shell_condition(), ShellCondition::Closed);
+    assert_eq!(upper.shell_condition(), ShellCondition::Closed);
+    lower.positions().iter().for_each(|pt| {
+        assert!((*pt - origin).dot(normal) < TOLERANCE);
+    });
+    let (v0, v1) = (volume(&lower), volume(&upper));
+    assert!(v0 > v1 && v1 > 0.0);
+    assert!(f64::abs(v0 + v1 - volume(&sphere)) < 1.0e-6);
+
+    // the caps take the face attributes of the faces on the cut
+    let mut labeled = sphere.clone();
+    let len = labeled.faces().len();
+    labeled.set_face_attribute("label", (0..len).collect::<Vec<_>>()).unwrap();
+    let lower = clip_by_plane(&labeled, origin, normal, true);
+    let labels = lower.face_attributes().get::<usize>("label").unwrap();
+    assert_eq!(labels.len(), lower.faces().len());
+
+    // without the caps, the sections are open
+    let open = clip_by_plane(&sphere, origin, normal, false);
+    assert_eq!(open.extract_boundaries().len(), 1);
+    assert!(lower.faces().len() > open.faces().len());
+}
+
+#[test]
+fn torus_sections() {
+    let torus = torus_mesh();
+    assert_eq!(torus.shell_condition(), ShellCondition::Closed);
+    let total = volume(&torus);
+    // the horizontal section is an annulus
+    let lower = clip_by_plane(&torus, Point3::origin(), Vector3::unit_z(), true);
+    assert_eq!(lower.shell_condition(), ShellCondition::Closed);
+    assert!(f64::abs(volume(&lower) - total / 2.0) < total * 0.01);
+    let positions = lower.positions();
+    lower
+        .face_iter()
+        .filter(|face| face.iter().all(|v| positions[v.pos].z.so_small()))
+        .for_each(|face| {
+            let center = face.iter().fold(Vector3::zero(), |sum, v| {
+                sum + positions[v.pos].to_vec()
+            }) / face.len() as f64;
+            let radius = center.magnitude();
+            assert!(0.5 < radius && radius < 1.5);
+        });
+    // the vertical section is two disks
+    let half = clip_by_plane(&torus, Point3::origin(), Vector3::unit_x(), true);
+    assert_eq!(half.shell_condition(), ShellCondition::Closed);
+    assert!(f64::abs(volume(&half) - total / 2.0) < total * 0.01);
+}
+
+#[test]
+fn attributes() {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(2.0, 0.0, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let uv_coords = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(1.0, 0.0),
+        Vector2::new(1.0, 1.0),
+        Vector2::new(0.0, 1.0),
+    ];
+    let normals = vec![Vector3::unit_z()];
+    let faces = Faces::from_iter([
+        [(0, Some(0), Some(0)), (1, Some(1), Some(0)), (2, Some(2), Some(0))],
+        [(0, Some(0), Some(0)), (2, Some(2), Some(0)), (3, Some(3), Some(0))],
+    ]);
+    let mut mesh = PolygonMesh::new(positions, uv_coords, normals, faces);
+    mesh.set_face_attribute("label", vec![0_usize, 1]).unwrap();
+    mesh.set_vertex_attribute("weight", vec![0.0, 1.0, 2.0, 3.0]).unwrap();
+
+    let clipped = clip_by_plane(&mesh, Point3::new(1.0, 0.0, 0.0), Vector3::unit_x(), true);
+    // the diagonal is also cut
+    assert_eq!(clipped.positions().len(), 5);
+    assert_eq!(clipped.faces().len(), 2);
+    let (positions, uv_coords) = (clipped.positions(), clipped.uv_coords());
+    clipped.face_iter().flatten().for_each(|v| {
+        let (pt, uv) = (positions[v.pos], uv_coords[v.uv.unwrap()]);
+        assert!(uv.near(&Vector2::new(pt.x / 2.0, pt.y)));
+        assert_eq!(clipped.normals()[v.nor.unwrap()], Vector3::unit_z());
+    });
+    // the open mesh has no caps
+    assert_eq!(clipped.extract_boundaries().len(), 1);
+    let labels = clipped.face_attributes().get::<usize>("label").unwrap();
+    assert_eq!(labels, &vec![0, 1]);
+    let weights = clipped.vertex_attributes().get::<f64>("weight").unwrap();
+    // the new vertices take the weights of the kept ends
+    assert_eq!(weights.len(), 5);
+    positions.iter().zip(weights).for_each(|(pt, weight)| match pt.y < 0.75 {
+        true => assert_eq!(*weight, 0.0),
+        false => assert_eq!(*weight, 3.0),
+    });
+}
+
+#[test]
+fn box_clipping() {
+    let sphere = sphere_mesh(Point3::origin(), 1.0);
+    // the box containing the sphere
+    let bdb = BoundingBox::from_iter(&[Point3::new(-2.0, -2.0, -2.0), Point3::new(2.0, 2.0, 2.0)]);
+    let clipped = clip_by_box(&sphere, &bdb);
+    assert_eq!(clipped.faces().len(), sphere.faces().len());
+    // the box apart from the sphere
+    let bdb = BoundingBox::from_iter(&[Point3::new(2.0, 2.0, 2.0), Point3::new(3.0, 3.0, 3.0)]);
+    let clipped = clip_by_box(&sphere, &bdb);
+    assert!(clipped.positions().is_empty());
+    assert_eq!(clipped.faces().len(), 0);
+    // the box cutting the sphere
+    let bdb = BoundingBox::from_iter(&[Point3::new(-0.5, -0.5, -2.0), Point3::new(0.5, 0.5, 2.0)]);
+    let clipped = clip_by_box(&sphere, &bdb);
+    assert_eq!(clipped.extract_boundaries().len(), 2);
+    clipped.positions().iter().for_each(|pt| {
+        assert!(pt.x.abs() < 0.5 + TOLERANCE && pt.y.abs() < 0.5 + TOLERANCE);
+    });
+    let result = clipped.bounding_box();
+    assert!(result.max().x.near(&0.5) && result.min().y.near(&-0.5));
+}
@@ -1,3 +1,5 @@
+// Each test uses only some of the fixtures.
+#![allow(dead_code)]
 use truck_meshalgo::prelude::*;
 
 pub mod shapes;
\ No newline at end of file
@@ -1,5 +1,6 @@
 use super::*;
 use std::f64::consts::PI;
+use truck_modeling::{builder, Rad, Solid};
 
 pub fn sphere(center: Point3, radius: f64, udiv: usize, vdiv: usize) -> PolygonMesh {
     let positions = (0..udiv)
@@ -23,3 +24,52 @@ pub fn sphere(center: Point3, radius: f64, udiv: usize, vdiv: usize) -> PolygonM
     })); 
     PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
 }
+
+
+// the tessellation of `solid` whose same attributes are put together
+pub fn welded(solid: &Solid) -> PolygonMesh {
+    let mut mesh = solid.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    mesh
+}
+
+// the unit cube with the corner at `origin`
+pub fn cube_solid(origin: Point3) -> Solid {
+    let v = builder::vertex(origin);
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    builder::tsweep(&f, Vector3::unit_z())
+}
+
+// the tessellation of the unit cube with the corner at `origin`
+pub fn cube_mesh(origin: Point3) -> PolygonMesh { welded(&cube_solid(origin)) }
+
+// the sphere of revolution
+pub fn sphere_solid(center: Point3, radius: f64) -> Solid {
+    let v = builder::vertex(center + radius * Vector3::unit_z());
+    let wire = builder::rsweep(&v, center, Vector3::unit_y(), Rad(PI));
+    let shell = builder::cone(&wire, Vector3::unit_z(), Rad(2.0 * PI));
+    Solid::new(vec![shell])
+}
+
+// the tessellation of the sphere of revolution
+pub fn sphere_mesh(center: Point3, radius: f64) -> PolygonMesh {
+    welded(&sphere_solid(center, radius))
+}
+
+// the volume by the divergence theorem
+pub fn volume(mesh: &PolygonMesh) -> f64 {
+    let positions = mesh.positions();
+    mesh.face_iter()
+        .flat_map(|face| {
+            (2..face.len()).map(move |i| {
+                let (a, b, c) = (
+                    positions[face[0].pos].to_vec(),
+                    positions[face[i - 1].pos].to_vec(),
+                    positions[face[i].pos].to_vec(),
+                );
+                a.dot(b.cross(c)) / 6.0
+            })
+        })
+        .sum()
+}
@@ -0,0 +1,104 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+#[test]
+fn face_point() {
+    // the square [0, 1] x [0, 1] on the xy-plane
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let face = builder::tsweep(&e, Vector3::unit_y());
+
+    // the projection onto the inside of the face
+    let point = Point3::new(0.3, 0.4, 2.0);
+    let closest = face.closest_points(&point, 0.01).unwrap();
+    assert!(closest.distance.near(&2.0), "{:?}", closest);
+    assert_near!(closest.point0, Point3::new(0.3, 0.4, 0.0));
+    assert_near!(closest.point1, point);
+    // the nearest points on the boundary edge and the vertex
+    let closest = face.closest_points(&Point3::new(0.5, -1.0, 1.0), 0.01).unwrap();
+    assert!(closest.distance.near(&f64::sqrt(2.0)), "{:?}", closest);
+    assert_near!(closest.point0, Point3::new(0.5, 0.0, 0.0));
+    let distance = face.distance(&Point3::new(2.0, 2.0, 0.0), 0.01).unwrap();
+    assert!(distance.near(&f64::sqrt(2.0)));
+
+    // the curved face: the points on the axis of the cylinder are at distance the radius.
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::unit_z());
+    let faces = builder::rsweep(&e, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+    for face in faces.face_iter() {
+        let closest = face.closest_points(&Point3::new(0.0, 0.0, 0.3), 0.01).unwrap();
+        assert!(closest.distance.near(&1.0), "{:?}", closest);
+        assert!(closest.point0[2].near(&0.3), "{:?}", closest);
+    }
+}
+
+#[test]
+fn edge_edge() {
+    // the skew lines
+    let v0 = builder::vertex(Point3::new(-1.0, 0.0, 0.0));
+    let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let v2 = builder::vertex(Point3::new(0.2, -1.0, 1.0));
+    let v3 = builder::vertex(Point3::new(0.2, 1.0, 1.0));
+    let line0 = builder::line(&v0, &v1);
+    let line1 = builder::line(&v2, &v3);
+    let closest = line0.closest_points(&line1, 0.01).unwrap();
+    assert!(closest.distance.near(&1.0));
+    assert_near!(closest.point0, Point3::new(0.2, 0.0, 0.0));
+    assert_near!(closest.point1, Point3::new(0.2, 0.0, 1.0));
+
+    // the closest point is the end of the line
+    let v2 = builder::vertex(Point3::new(2.0, -1.0, 1.0));
+    let v3 = builder::vertex(Point3::new(2.0, 1.0, 1.0));
+    let line1 = builder::line(&v2, &v3);
+    let closest = line0.closest_points(&line1, 0.01).unwrap();
+    assert!(closest.distance.near(&f64::sqrt(2.0)));
+    assert_near!(closest.point0, Point3::new(1.0, 0.0, 0.0));
+
+    // the circle arc on the xy-plane and the line parallel to the z-axis
+    let v0 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let v1 = builder::vertex(Point3::new(-1.0, 0.0, 0.0));
+    let arc = builder::circle_arc(&v0, &v1, Point3::new(0.0, 1.0, 0.0));
+    let v2 = builder::vertex(Point3::new(2.0, 2.0, -1.0));
+    let v3 = builder::vertex(Point3::new(2.0, 2.0, 1.0));
+    let line = builder::line(&v2, &v3);
+    let closest = arc.closest_points(&line, 0.01).unwrap();
+    assert!(closest.distance.near(&(f64::sqrt(8.0) - 1.0)), "{:?}", closest);
+    let p = f64::sqrt(0.5);
+    assert_near!(closest.point0, Point3::new(p, p, 0.0));
+    assert_near!(closest.point1, Point3::new(2.0, 2.0, 0.0));
+}
+
+#[test]
+fn solid_solid() {
+    let sphere0 = sphere_solid(Point3::new(0.0, 0.0, 0.0), 1.0);
+    let sphere1 = sphere_solid(Point3::new(2.0, 2.0, 1.0), 0.5);
+    let closest = sphere0.closest_points(&sphere1, 0.02).unwrap();
+    assert!(f64::abs(closest.distance - 1.5) < 1.0e-6, "{:?}", closest);
+    assert_near!(closest.point0, Point3::new(2.0, 2.0, 1.0) / 3.0);
+    assert_near!(closest.point1, Point3::new(2.0, 2.0, 1.0) * (2.5 / 3.0));
+
+    // the edges of the cubes
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube0 = builder::tsweep(&f, Vector3::unit_z());
+    let cube1 = builder::rotated(&cube0, Point3::origin(), Vector3::unit_z(), Rad(PI / 4.0));
+    // the vertical edge at (2, 0.5) is the nearest to cube0
+    let p = f64::sqrt(0.5);
+    let cube1 = builder::translated(&cube1, Vector3::new(2.0 + p, 0.5 - p, 0.5));
+    let closest = cube0.closest_points(&cube1, 0.01).unwrap();
+    assert!(closest.distance.near(&1.0), "{:?}", closest);
+    assert!(closest.point0[0].near(&1.0) && closest.point1[0].near(&2.0));
+    assert!(closest.point1[1].near(&0.5));
+    // symmetric
+    let distance = cube1.distance(&cube0, 0.01).unwrap();
+    assert!(distance.near(&closest.distance));
+
+    // the point and the solid
+    let distance = sphere0.distance(&Point3::new(0.0, 3.0, 0.0), 0.02).unwrap();
+    assert!(distance.near(&2.0));
+}
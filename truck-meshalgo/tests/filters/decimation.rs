@@ -0,0 +1,189 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+
+// the unit sphere divided by the latitudes and the longitudes
+fn sphere(n: usize) -> PolygonMesh {
+    let mut positions = vec![Point3::new(0.0, 0.0, 1.0), Point3::new(0.0, 0.0, -1.0)];
+    for i in 1..n {
+        let theta = PI * i as f64 / n as f64;
+        for j in 0..2 * n {
+            let phi = PI * j as f64 / n as f64;
+            positions.push(Point3::new(
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            ));
+        }
+    }
+    let index = |i: usize, j: usize| 2 + (i - 1) * 2 * n + j % (2 * n);
+    let mut faces = Faces::default();
+    for j in 0..2 * n {
+        faces.push([0, index(1, j), index(1, j + 1)]);
+        faces.push([1, index(n - 1, j + 1), index(n - 1, j)]);
+        for i in 1..n - 1 {
+            faces.push([index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]);
+        }
+    }
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+// the square `[0, n] x [0, n]` divided into unit squares, whose uv coordinates are cut
+// along `x = n / 2`
+fn grid(n: usize) -> PolygonMesh {
+    let positions: Vec<Point3> = (0..=n)
+        .flat_map(|i| (0..=n).map(move |j| Point3::new(i as f64, j as f64, 0.0)))
+        .collect();
+    let mut uv_coords: Vec<Vector2> = positions.iter().map(|p| Vector2::new(p.x, p.y)).collect();
+    // the uv coordinates of the seam seen from the right half
+    uv_coords.extend((0..=n).map(|j| Vector2::new(n as f64, j as f64)));
+    let index = |i: usize, j: usize| i * (n + 1) + j;
+    let vertex = |i: usize, j: usize| match i == n / 2 && j <= n {
+        true => (index(i, j), Some((n + 1) * (n + 1) + j), None),
+        false => (index(i, j), Some(index(i, j)), None),
+    };
+    let mut faces = Faces::default();
+    for i in 0..n {
+        for j in 0..n {
+            let face = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+            // the left half refers the original uv coordinates on the seam
+            let face: Vec<_> = face
+                .iter()
+                .map(|(k, l)| match i < n / 2 {
+                    true => (index(*k, *l), Some(index(*k, *l)), None),
+                    false => vertex(*k, *l),
+                })
+                .collect();
+            faces.push(face);
+        }
+    }
+    PolygonMesh::new(positions, uv_coords, Vec::new(), faces)
+}
+
+#[test]
+fn decimate_to_target_faces() {
+    let mut mesh = sphere(16);
+    mesh.set_face_attribute("label", (0..mesh.faces().len()).collect::<Vec<_>>())
+        .unwrap();
+    mesh.decimate(&DecimationDescriptor {
+        target_faces: Some(200),
+        ..Default::default()
+    });
+    assert!(mesh.faces().len() <= 200);
+    assert!(mesh.faces().len() >= 198);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    let labels = mesh.face_attributes().get::<usize>("label").unwrap();
+    assert_eq!(labels.len(), mesh.faces().len());
+    // the remaining vertices are on the sphere
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!((p.to_vec().magnitude() - 1.0).abs() < 1.0e-10));
+}
+
+#[test]
+fn decimate_within_error() {
+    let mut mesh = sphere(16);
+    let len = mesh.tri_faces().len() + 2 * mesh.quad_faces().len();
+    mesh.decimate(&DecimationDescriptor {
+        max_error: Some(1.0e-6),
+        ..Default::default()
+    });
+    // the sphere has no flat region
+    assert_eq!(mesh.faces().len(), len);
+
+    let mut coarse = sphere(16);
+    coarse.decimate(&DecimationDescriptor {
+        max_error: Some(0.05),
+        ..Default::default()
+    });
+    let mut coarser = sphere(16);
+    coarser.decimate(&DecimationDescriptor {
+        max_error: Some(0.2),
+        ..Default::default()
+    });
+    assert!(coarse.faces().len() < len);
+    assert!(coarser.faces().len() < coarse.faces().len());
+    assert_eq!(coarser.shell_condition(), ShellCondition::Closed);
+}
+
+#[test]
+fn decimate_boundaries_and_seams() {
+    let descriptor = DecimationDescriptor {
+        max_error: Some(1.0e-6),
+        ..Default::default()
+    };
+    let mut mesh = grid(10);
+    mesh.decimate(&descriptor);
+    // the vertices on the boundary and the seam are kept
+    assert_eq!(mesh.positions().len(), 40 + 9);
+    mesh.faces().face_iter().flatten().for_each(|v| {
+        let p = mesh.positions()[v.pos];
+        let uv = mesh.uv_coords()[v.uv.unwrap()];
+        match p.x == 5.0 && uv.x == 10.0 {
+            // the right side of the seam
+            true => assert_eq!(uv.y, p.y),
+            false => assert_eq!(uv, Vector2::new(p.x, p.y)),
+        }
+    });
+
+    let mut mesh = grid(10);
+    mesh.decimate(&DecimationDescriptor {
+        preserve_uv_seams: false,
+        ..descriptor
+    });
+    assert!(mesh.positions().len() < 40 + 9);
+    assert!(mesh.positions().len() >= 40);
+
+    let mut mesh = grid(10);
+    mesh.decimate(&DecimationDescriptor {
+        preserve_boundaries: false,
+        preserve_uv_seams: false,
+        ..descriptor
+    });
+    assert!(mesh.positions().len() < 40);
+    // the corners are kept
+    let corners = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+    corners.iter().for_each(|(x, y)| {
+        assert!(mesh.positions().contains(&Point3::new(*x, *y, 0.0)));
+    });
+    assert_eq!(mesh.extract_boundaries().len(), 1);
+}
+
+#[test]
+fn decimate_except_selection() {
+    let mut mesh = sphere(16);
+    // the faces around the north pole
+    let upper: Vec<usize> = mesh
+        .face_iter()
+        .enumerate()
+        .filter(|(_, face)| face.iter().all(|v| mesh.positions()[v.pos].z > 0.5))
+        .map(|(i, _)| i)
+        .collect();
+    let triangles: usize = upper.iter().map(|i| mesh.faces()[*i].len() - 2).sum();
+    let vertices: Vec<Point3> = mesh
+        .positions()
+        .iter()
+        .filter(|p| p.z > 0.5)
+        .copied()
+        .collect();
+    mesh.select_faces("upper", upper).unwrap();
+    mesh.decimate(&DecimationDescriptor {
+        target_faces: Some(100),
+        preserve_selection: Some("upper"),
+        ..Default::default()
+    });
+    assert!(mesh.faces().len() <= 100 + triangles);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    // the selected faces are only divided into triangles
+    let selection = mesh.face_attributes().selection("upper").unwrap();
+    assert_eq!(selection.iter().filter(|flag| **flag).count(), triangles);
+    mesh.face_iter()
+        .zip(selection)
+        .filter(|(_, flag)| **flag)
+        .for_each(|(face, _)| {
+            assert!(face.iter().all(|v| mesh.positions()[v.pos].z > 0.5));
+        });
+    vertices
+        .iter()
+        .for_each(|p| assert!(mesh.positions().contains(p)));
+}
@@ -0,0 +1,153 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+
+// the unit sphere divided by `n` latitudes and `2n` longitudes, without the polar caps
+// of `top` and `bottom` latitudes
+fn open_sphere(n: usize, top: usize, bottom: usize) -> PolygonMesh {
+    let positions: Vec<Point3> = (top..=n - bottom)
+        .flat_map(|i| {
+            let theta = PI * i as f64 / n as f64;
+            (0..2 * n).map(move |j| {
+                let phi = PI * j as f64 / n as f64;
+                Point3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                )
+            })
+        })
+        .collect();
+    let index = |i: usize, j: usize| (i - top) * 2 * n + j % (2 * n);
+    let faces = Faces::from_iter((top..n - bottom).flat_map(|i| {
+        (0..2 * n).map(move |j| {
+            [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]
+        })
+    }));
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+fn triangles(mesh: &PolygonMesh) -> Vec<[Point3; 3]> {
+    let positions = mesh.positions();
+    mesh.face_iter()
+        .flat_map(|face| {
+            (2..face.len()).map(move |i| {
+                [positions[face[0].pos], positions[face[i - 1].pos], positions[face[i].pos]]
+            })
+        })
+        .collect()
+}
+
+fn area(mesh: &PolygonMesh) -> f64 {
+    triangles(mesh)
+        .iter()
+        .map(|p| (p[1] - p[0]).cross(p[2] - p[0]).magnitude() / 2.0)
+        .sum()
+}
+
+#[test]
+fn fill_sphere_caps() {
+    let mut mesh = open_sphere(16, 2, 2);
+    let len = mesh.faces().len();
+    mesh.set_face_attribute("label", (0..len).collect::<Vec<_>>())
+        .unwrap();
+    mesh.fill_holes(&Default::default());
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    // each cap of 32 vertices is filled by 30 triangles
+    assert_eq!(mesh.faces().len(), len + 2 * 30);
+    assert_eq!(mesh.positions().len(), 32 * 13);
+    let labels = mesh.face_attributes().get::<usize>("label").unwrap();
+    assert_eq!(labels.len(), mesh.faces().len());
+    // the labels of the filling are those of the faces of the first and the last rows
+    mesh.faces()
+        .face_iter()
+        .zip(labels)
+        .filter(|(face, _)| face.len() == 3)
+        .for_each(|(_, label)| assert!(*label < 32 || *label >= len - 32));
+}
+
+#[test]
+fn refine_and_fair_sphere_caps() {
+    let mut mesh = open_sphere(16, 3, 3);
+    let len = mesh.positions().len();
+    mesh.set_vertex_attribute("weight", vec![1.0; len]).unwrap();
+    mesh.fill_holes(&HoleFillingDescriptor {
+        refine: true,
+        ..Default::default()
+    });
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    assert!(mesh.positions().len() > len);
+    let weights = mesh.vertex_attributes().get::<f64>("weight").unwrap();
+    assert_eq!(weights.len(), mesh.positions().len());
+    assert!(weights.iter().all(|w| *w == 1.0));
+    // the new vertices are faired in the caps between the planes of the holes
+    let z = f64::cos(PI * 3.0 / 16.0);
+    mesh.positions()[len..].iter().for_each(|p| {
+        assert!(f64::abs(p.z) <= z + TOLERANCE && f64::abs(p.z) > z - 0.1);
+        assert!(p.x.hypot(p.y) < f64::sin(PI * 3.0 / 16.0));
+    });
+    // the edges are about the boundary edges
+    let boundary_edge = 2.0 * f64::sin(PI * 3.0 / 16.0) * f64::sin(PI / 32.0);
+    triangles(&mesh).iter().for_each(|p| {
+        (0..3).for_each(|i| assert!(p[i].distance(p[(i + 1) % 3]) < 4.0 * boundary_edge));
+    });
+}
+
+#[test]
+fn fill_concave_hole() {
+    // the square [0, 4] x [0, 4] with the L-shaped hole
+    let positions: Vec<Point3> = (0..5)
+        .flat_map(|j| (0..5).map(move |i| Point3::new(i as f64, j as f64, 0.0)))
+        .collect();
+    let index = |i: usize, j: usize| 5 * j + i;
+    let holes = [(1, 1), (2, 1), (1, 2)];
+    let faces = Faces::from_iter((0..4).flat_map(|j| {
+        (0..4)
+            .filter(move |i| !holes.contains(&(*i, j)))
+            .map(move |i| [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)])
+    }));
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let area0 = area(&mesh);
+    // the outer boundary is also a loop
+    mesh.fill_holes(&HoleFillingDescriptor {
+        max_area: Some(5.0),
+        ..Default::default()
+    });
+    assert_eq!(mesh.extract_boundaries().len(), 1);
+    // the loop of 8 vertices is filled by 6 triangles
+    assert_eq!(mesh.faces().len(), 13 + 6);
+    // the filling covers the hole without overlaps
+    assert!(f64::abs(area(&mesh) - area0 - 3.0) < 1.0e-10);
+    mesh.faces().tri_faces().iter().for_each(|tri| {
+        let p = tri.map(|v| mesh.positions()[v.pos]);
+        assert!((p[1] - p[0]).cross(p[2] - p[0]).z > 0.0);
+    });
+}
+
+#[test]
+fn fill_holes_within_limits() {
+    // the small cap and the large cap
+    let mesh = open_sphere(16, 2, 5);
+    assert_eq!(mesh.extract_boundaries().len(), 2);
+
+    let small_perimeter = 2.0 * PI * f64::sin(PI * 2.0 / 16.0);
+    let mut filled = mesh.clone();
+    filled.fill_holes(&HoleFillingDescriptor {
+        max_perimeter: Some(small_perimeter * 1.1),
+        ..Default::default()
+    });
+    assert_eq!(filled.extract_boundaries().len(), 1);
+    let small_area = PI * f64::sin(PI * 2.0 / 16.0).powi(2);
+    let mut filled = mesh.clone();
+    filled.fill_holes(&HoleFillingDescriptor {
+        max_area: Some(small_area * 1.1),
+        ..Default::default()
+    });
+    assert_eq!(filled.extract_boundaries().len(), 1);
+    let mut filled = mesh.clone();
+    filled.fill_holes(&HoleFillingDescriptor {
+        max_area: Some(small_area * 0.5),
+        ..Default::default()
+    });
+    assert_eq!(filled, mesh);
+}
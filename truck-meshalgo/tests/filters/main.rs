@@ -1,3 +1,7 @@
+mod decimation;
+mod hole_filling;
 mod normal_filter;
 mod optimizing;
+mod smoothing;
 mod structuring;
+mod uv_projection;
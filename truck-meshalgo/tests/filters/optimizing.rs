@@ -170,3 +170,31 @@ fn put_together_same_attrs_test() {
     assert_eq!(mesh.uv_coords().len(), 18);
     assert_eq!(mesh.normals().len(), 17);
 }
+
+#[test]
+fn optimizing_attributes_test() {
+    let positions: Vec<_> = (0..6).map(|i| Point3::new(i as f64, (i % 2) as f64, 0.0)).collect();
+    let faces = Faces::from_iter([
+        [1, 2, 3].as_ref(),
+        &[2, 2, 3],
+        &[3, 4, 5, 5],
+        &[1, 3, 4, 1, 5],
+    ]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let normals: Vec<_> = (0..6).map(|i| Vector3::new(0.0, 0.0, i as f64)).collect();
+    mesh.set_vertex_attribute("normal", normals).unwrap();
+    mesh.set_face_attribute("scalar", vec![0.0, 1.0, 2.0, 3.0]).unwrap();
+
+    mesh.remove_unused_attrs();
+    assert_eq!(mesh.positions().len(), 5);
+    let normals = mesh.vertex_attributes().get::<Vector3>("normal").unwrap();
+    mesh.face_iter().flatten().for_each(|v| {
+        assert_eq!(normals[v.pos][2], mesh.positions()[v.pos][0]);
+    });
+
+    mesh.remove_degenerate_faces();
+    // the degenerate triangle is removed, the quadrangle becomes a triangle,
+    // and the pentagon is split into a triangle and a degenerate face.
+    let scalars = mesh.face_attributes().get::<f64>("scalar").unwrap();
+    assert_eq!(scalars, &vec![0.0, 2.0, 3.0]);
+}
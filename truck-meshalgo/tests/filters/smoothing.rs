@@ -0,0 +1,130 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+
+// the unit sphere divided by the latitudes and the longitudes, whose positions are perturbed
+fn noisy_sphere(n: usize) -> PolygonMesh {
+    let mut positions = vec![Point3::new(0.0, 0.0, 1.0), Point3::new(0.0, 0.0, -1.0)];
+    for i in 1..n {
+        let theta = PI * i as f64 / n as f64;
+        for j in 0..2 * n {
+            let phi = PI * j as f64 / n as f64;
+            let radius = 1.0 + 0.05 * ((i * 7 + j * 13) % 5) as f64 / 4.0;
+            positions.push(Point3::new(
+                radius * theta.sin() * phi.cos(),
+                radius * theta.sin() * phi.sin(),
+                radius * theta.cos(),
+            ));
+        }
+    }
+    let index = |i: usize, j: usize| 2 + (i - 1) * 2 * n + j % (2 * n);
+    let mut faces = Faces::default();
+    for j in 0..2 * n {
+        faces.push([0, index(1, j), index(1, j + 1)]);
+        faces.push([1, index(n - 1, j + 1), index(n - 1, j)]);
+        for i in 1..n - 1 {
+            faces.push([index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]);
+        }
+    }
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+// the deviation of the distances from the origin
+fn roughness<'a>(positions: impl Iterator<Item = &'a Point3> + Clone) -> f64 {
+    let len = positions.clone().count() as f64;
+    let radii = positions.map(|p| p.to_vec().magnitude());
+    let average = radii.clone().sum::<f64>() / len;
+    f64::sqrt(radii.map(|r| (r - average) * (r - average)).sum::<f64>() / len)
+}
+
+#[test]
+fn smooth_whole_mesh() {
+    let mut mesh = noisy_sphere(16);
+    let before = roughness(mesh.positions().iter());
+    mesh.smooth(&Default::default());
+    let after = roughness(mesh.positions().iter());
+    assert!(after < before * 0.5, "{} {}", before, after);
+    // the volume is kept by the inflating steps
+    let average = mesh
+        .positions()
+        .iter()
+        .map(|p| p.to_vec().magnitude())
+        .sum::<f64>()
+        / mesh.positions().len() as f64;
+    assert!(average > 0.95, "{}", average);
+}
+
+#[test]
+fn smooth_selected_region() {
+    let mut mesh = noisy_sphere(16);
+    let original = mesh.positions().clone();
+    let upper: Vec<usize> = (0..original.len())
+        .filter(|i| original[*i].z > 0.0)
+        .collect();
+    mesh.select_vertices("upper", upper).unwrap();
+    mesh.smooth(&SmoothingDescriptor {
+        selection: Some("upper"),
+        ..Default::default()
+    });
+    let selection = mesh.vertex_attributes().selection("upper").unwrap();
+    mesh.positions()
+        .iter()
+        .zip(&original)
+        .zip(selection)
+        .filter(|(_, flag)| !**flag)
+        .for_each(|((p, q), _)| assert_eq!(p, q));
+    let upper = mesh.positions().iter().filter(|p| p.z > 0.0);
+    let lower = mesh.positions().iter().filter(|p| p.z < 0.0);
+    assert!(roughness(upper) < roughness(lower) * 0.5);
+
+    // nothing is moved by the unknown selection
+    let mut mesh = noisy_sphere(16);
+    mesh.smooth(&SmoothingDescriptor {
+        selection: Some("unknown"),
+        ..Default::default()
+    });
+    assert_eq!(mesh.positions(), &original);
+}
+
+#[test]
+fn smooth_with_boundaries() {
+    // the plane divided into 8 x 8 squares, whose inner vertices are raised and lowered alternately
+    let positions: Vec<Point3> = (0..=8)
+        .flat_map(|i| (0..=8).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            let inner = i > 0 && i < 8 && j > 0 && j < 8;
+            let z = match (inner, (i + j) % 2 == 0) {
+                (false, _) => 0.0,
+                (true, true) => 0.1,
+                (true, false) => -0.1,
+            };
+            Point3::new(i as f64, j as f64, z)
+        })
+        .collect();
+    let index = |i: usize, j: usize| i * 9 + j;
+    let faces = Faces::from_iter((0..8).flat_map(|i| {
+        (0..8).map(move |j| [index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)])
+    }));
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+
+    let mut smoothed = mesh.clone();
+    smoothed.smooth(&Default::default());
+    let boundary = |p: &Point3| p.x == 0.0 || p.x == 8.0 || p.y == 0.0 || p.y == 8.0;
+    mesh.positions()
+        .iter()
+        .zip(smoothed.positions())
+        .filter(|(p, _)| boundary(p))
+        .for_each(|(p, q)| assert_eq!(p, q));
+    let height = |mesh: &PolygonMesh| mesh.positions().iter().map(|p| p.z.abs()).sum::<f64>();
+    assert!(height(&smoothed) < height(&mesh) * 0.5);
+
+    let mut smoothed = mesh.clone();
+    smoothed.smooth(&SmoothingDescriptor {
+        preserve_boundaries: false,
+        ..Default::default()
+    });
+    assert!(mesh
+        .positions()
+        .iter()
+        .zip(smoothed.positions())
+        .any(|(p, q)| boundary(p) && p != q));
+}
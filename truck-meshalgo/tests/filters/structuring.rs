@@ -82,3 +82,38 @@ fn quadrangulate_test() {
     tri_mesh.quadrangulate(TOLERANCE, TOLERANCE);
     assert_eq!(tri_mesh.faces(), quad_mesh.faces());
 }
+
+#[test]
+fn structuring_face_attributes_test() {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(2.0, 0.0, 0.0),
+        Point3::new(3.0, 0.5, 0.0),
+        Point3::new(2.0, 1.0, 0.0),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2, 3].as_ref(), &[1, 4, 5, 6, 2]]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    mesh.set_face_attribute("label", vec![10_usize, 20]).unwrap();
+    mesh.set_vertex_attribute("weight", (0..7).map(|i| i as f64).collect()).unwrap();
+
+    mesh.triangulate();
+    let labels = mesh.face_attributes().get::<usize>("label").unwrap();
+    assert_eq!(labels, &vec![10, 10, 20, 20, 20]);
+    // the triangles of the quadrangle are joined again
+    mesh.quadrangulate(0.01, 1.0);
+    let labels = mesh.face_attributes().get::<usize>("label").unwrap();
+    assert_eq!(labels.len(), mesh.faces().len());
+    mesh.face_iter().zip(labels).for_each(|(face, label)| {
+        let expected = match face.iter().any(|v| v.pos == 0 || v.pos == 3) {
+            true => 10,
+            false => 20,
+        };
+        assert_eq!(*label, expected);
+    });
+    // the positions are not changed
+    let weights = mesh.vertex_attributes().get::<f64>("weight").unwrap();
+    assert_eq!(weights[6], 6.0);
+}
@@ -0,0 +1,153 @@
+use std::f64::consts::PI;
+use truck_meshalgo::filters::*;
+use truck_polymesh::*;
+
+const N: usize = 16;
+
+// the uv coordinates of the vertices of the `i`th face
+fn face_uvs(mesh: &PolygonMesh, i: usize) -> Vec<Vector2> {
+    let face = mesh.face_iter().nth(i).unwrap();
+    face.iter()
+        .map(|v| mesh.uv_coords()[v.uv.unwrap()])
+        .collect()
+}
+
+#[test]
+fn box_projection() {
+    let positions: Vec<Point3> = (0..8)
+        .map(|i| Point3::new((i % 2) as f64, (i / 2 % 2) as f64, (i / 4) as f64))
+        .collect();
+    let faces = Faces::from_iter([
+        [0, 2, 3, 1],
+        [0, 1, 5, 4],
+        [1, 3, 7, 5],
+        [3, 2, 6, 7],
+        [2, 0, 4, 6],
+        [4, 5, 7, 6],
+    ]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    mesh.add_projected_uv_coords(&UVProjection::Box {
+        origin: Point3::origin(),
+        axes: [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+    });
+    for i in 0..6 {
+        let uvs = face_uvs(&mesh, i);
+        // each face covers the whole texture.
+        let bdb: BoundingBox<Vector2> = uvs.into_iter().collect();
+        assert!(bdb.min().near(&Vector2::new(0.0, 0.0)));
+        assert!(bdb.max().near(&Vector2::new(1.0, 1.0)));
+    }
+    // the side x = 1 is projected onto the yz-plane.
+    let face = mesh.face_iter().nth(2).unwrap();
+    face.iter().for_each(|v| {
+        let (p, uv) = (mesh.positions()[v.pos], mesh.uv_coords()[v.uv.unwrap()]);
+        assert!(uv.near(&Vector2::new(p[1], p[2])));
+    })
+}
+
+#[test]
+fn cylindrical_projection() {
+    // the side of the cylinder with the radius 1 and the height 2 around the z axis
+    let positions: Vec<Point3> = (0..2 * N)
+        .map(|i| {
+            let t = 2.0 * PI * (i % N) as f64 / N as f64;
+            Point3::new(f64::cos(t), f64::sin(t), (i / N) as f64 * 2.0)
+        })
+        .collect();
+    let faces = Faces::from_iter((0..N).map(|i| [i, (i + 1) % N, (i + 1) % N + N, i + N]));
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    mesh.add_projected_uv_coords(&UVProjection::Cylindrical {
+        origin: Point3::origin(),
+        axis: Vector3::new(0.0, 0.0, 2.0),
+        reference: Vector3::unit_x(),
+    });
+    for i in 0..N {
+        let uvs = face_uvs(&mesh, i);
+        // the width of each face is 1 / N, also across the seam.
+        assert!((uvs[1][0] - uvs[0][0]).near(&(1.0 / N as f64)));
+        assert!(uvs[0][0].near(&(i as f64 / N as f64)));
+        assert!(uvs[0][1].near(&0.0));
+        assert!(uvs[3][1].near(&1.0));
+    }
+}
+
+#[test]
+fn spherical_projection() {
+    // the octahedron
+    let positions = vec![
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(-1.0, 0.0, 0.0),
+        Point3::new(0.0, -1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(0.0, 0.0, -1.0),
+    ];
+    let faces =
+        Faces::from_iter((0..4).flat_map(|i| vec![[i, (i + 1) % 4, 4], [(i + 1) % 4, i, 5]]));
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    mesh.add_projected_uv_coords(&UVProjection::Spherical {
+        origin: Point3::origin(),
+        axis: Vector3::unit_z(),
+        reference: Vector3::unit_x(),
+    });
+    for i in 0..8 {
+        let uvs = face_uvs(&mesh, i);
+        let k = (i / 2) as f64;
+        let (u0, u1) = (k / 4.0, (k + 1.0) / 4.0);
+        match i % 2 {
+            0 => {
+                assert!(uvs[0].near(&Vector2::new(u0, 0.5)));
+                assert!(uvs[1].near(&Vector2::new(u1, 0.5)));
+                // the north pole at the middle
+                assert!(uvs[2].near(&Vector2::new((u0 + u1) / 2.0, 1.0)));
+            }
+            _ => {
+                assert!(uvs[0].near(&Vector2::new(u1, 0.5)));
+                assert!(uvs[1].near(&Vector2::new(u0, 0.5)));
+                assert!(uvs[2].near(&Vector2::new((u0 + u1) / 2.0, 0.0)));
+            }
+        }
+    }
+}
+
+#[test]
+fn wrap_uv_seams() {
+    // the side of the cylinder whose u coordinates are the angles divided by `2 pi` in [0, 1)
+    let positions: Vec<Point3> = (0..2 * N)
+        .map(|i| {
+            let t = 2.0 * PI * (i % N) as f64 / N as f64;
+            Point3::new(f64::cos(t), f64::sin(t), (i / N) as f64)
+        })
+        .collect();
+    let uv_coords: Vec<Vector2> = (0..2 * N)
+        .map(|i| Vector2::new((i % N) as f64 / N as f64, (i / N) as f64))
+        .collect();
+    let faces = Faces::from_iter((0..N).map(|i| {
+        let j = (i + 1) % N;
+        [i, j, j + N, i + N].map(|k| (k, Some(k), None))
+    }));
+    let mut mesh = PolygonMesh::new(positions, uv_coords, Vec::new(), faces);
+    assert!(face_uvs(&mesh, N - 1).iter().any(|uv| uv[0].near(&0.0)));
+
+    mesh.wrap_uv_seams((Some(1.0), None));
+    // the seam vertices have both u = 0 and u = 1
+    assert_eq!(mesh.uv_coords().len(), 2 * N + 2);
+    for i in 0..N {
+        let uvs = face_uvs(&mesh, i);
+        let bdb: BoundingBox<Vector2> = uvs.into_iter().collect();
+        assert!(bdb.min().near(&Vector2::new(i as f64 / N as f64, 0.0)));
+        assert!(bdb.max().near(&Vector2::new((i + 1) as f64 / N as f64, 1.0)));
+    }
+    // the projected uv coordinates, exceeding `1` across the seam, are wrapped into `[0, 1]`
+    mesh.add_projected_uv_coords(&UVProjection::Cylindrical {
+        origin: Point3::origin(),
+        axis: Vector3::unit_z(),
+        reference: Vector3::new(0.0, 1.0, 0.0),
+    })
+    .wrap_uv_seams((Some(1.0), None));
+    for i in 0..N {
+        face_uvs(&mesh, i).iter().for_each(|uv| {
+            assert!(-TOLERANCE < uv[0] && uv[0] < 1.0 + TOLERANCE);
+        });
+    }
+}
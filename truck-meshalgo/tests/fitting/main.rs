@@ -0,0 +1,64 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+
+fn sphere_patch(division: usize) -> StructuredMesh {
+    let positions = (0..=division)
+        .map(|i| {
+            let theta = PI / 2.0 * i as f64 / division as f64;
+            (0..=division)
+                .map(|j| {
+                    let phi = PI / 2.0 * j as f64 / division as f64;
+                    Point3::new(
+                        theta.sin() * phi.cos(),
+                        theta.sin() * phi.sin(),
+                        theta.cos(),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    StructuredMesh::from_positions(positions)
+}
+
+#[test]
+fn fit_sphere_patch() {
+    let mesh = sphere_patch(20);
+    let coarse = mesh.fit_bspline(3, 1.0e-2).unwrap();
+    let fine = mesh.fit_bspline(3, 1.0e-5).unwrap();
+    assert_eq!(fine.degrees(), (3, 3));
+    assert!(coarse.control_points().len() <= fine.control_points().len());
+    assert!(coarse.control_points()[0].len() <= fine.control_points()[0].len());
+
+    let ((u0, u1), (v0, v1)) = fine.parameter_range();
+    assert!(u0.near(&0.0) && u1.near(&1.0) && v0.near(&0.0) && v1.near(&1.0));
+    // the points between the grid are also on the sphere.
+    for i in 0..=50 {
+        for j in 0..=50 {
+            let pt = fine.subs(i as f64 / 50.0, j as f64 / 50.0);
+            assert!(f64::abs(pt.to_vec().magnitude() - 1.0) < 1.0e-4, "{:?}", pt);
+        }
+    }
+}
+
+#[test]
+fn fit_small_grid() {
+    let positions = vec![
+        vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+        vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0)],
+    ];
+    let mesh = StructuredMesh::from_positions(positions);
+    // the degree is reduced to the bilinear surface.
+    let surface = mesh.fit_bspline(3, 1.0e-6).unwrap();
+    assert_eq!(surface.degrees(), (1, 1));
+    assert!(surface.subs(0.5, 0.5).near(&Point3::new(0.5, 0.5, 0.25)));
+    assert!(mesh.fit_bspline(0, 1.0e-6).is_none());
+
+    let line =
+        StructuredMesh::from_positions(vec![vec![Point3::origin(), Point3::new(1.0, 0.0, 0.0)]]);
+    assert!(line.fit_bspline(1, 1.0e-6).is_none());
+
+    let mut positions = mesh.positions().clone();
+    positions[1][0] = Point3::new(f64::NAN, 0.0, 0.0);
+    let mesh = StructuredMesh::from_positions(positions);
+    assert!(mesh.fit_bspline(1, 1.0e-6).is_none());
+}
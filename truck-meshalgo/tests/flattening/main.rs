@@ -0,0 +1,66 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+
+fn perimeter(outline: &PolylineCurve<Point3>) -> f64 {
+    outline.windows(2).map(|p| p[0].distance(p[1])).sum()
+}
+
+#[test]
+fn truncated_cone() {
+    // the bottom radius 2, the top radius 1, and the slant height sqrt(2)
+    let v0 = builder::vertex(Point3::new(2.0, 0.0, 0.0));
+    let v1 = builder::vertex(Point3::new(1.0, 0.0, 1.0));
+    let e = builder::line(&v0, &v1);
+    let cone: Shell = builder::rsweep(&e, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+
+    let pattern = cone.flat_pattern(0.01).unwrap();
+    assert!(pattern.max_length_distortion < 0.05, "{}", pattern.max_length_distortion);
+    assert!(pattern.area_distortion < 0.01, "{}", pattern.area_distortion);
+    // the annular sector cut along the seam
+    assert_eq!(pattern.outlines.len(), 1);
+    let expected = 2.0 * PI * 2.0 + 2.0 * PI * 1.0 + 2.0 * f64::sqrt(2.0);
+    let length = perimeter(&pattern.outlines[0]);
+    assert!(f64::abs(length - expected) < 0.1, "{}", length);
+    assert!(pattern.mesh.positions().iter().all(|pt| pt[2] == 0.0));
+    assert_eq!(pattern.original_indices.len(), pattern.mesh.positions().len());
+}
+
+#[test]
+fn sphere_is_not_developable() {
+    // the spherical zone between the latitudes -pi/4 and pi/4
+    let (c, s) = (f64::cos(PI / 4.0), f64::sin(PI / 4.0));
+    let v0 = builder::vertex(Point3::new(c, 0.0, -s));
+    let v1 = builder::vertex(Point3::new(c, 0.0, s));
+    let arc = builder::circle_arc(&v0, &v1, Point3::new(1.0, 0.0, 0.0));
+    let sphere: Shell = builder::rsweep(&arc, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+
+    let pattern = sphere.flat_pattern(0.05).unwrap();
+    assert!(pattern.max_length_distortion > 0.1, "{}", pattern.max_length_distortion);
+}
+
+#[test]
+fn separated_components() {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(5.0, 5.0, 5.0),
+        Point3::new(5.0, 6.0, 5.0),
+        Point3::new(5.0, 5.0, 6.0),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2], [3, 4, 5]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+
+    let pattern = mesh.flat_pattern();
+    assert_eq!(pattern.outlines.len(), 2);
+    assert!(pattern.max_length_distortion.so_small());
+    // the triangles are arranged without overlaps
+    let boxes: Vec<BoundingBox<Point3>> = pattern
+        .mesh
+        .face_iter()
+        .map(|face| face.iter().map(|v| pattern.mesh.positions()[v.pos]).collect())
+        .collect();
+    assert!(boxes[0].max()[0] < boxes[1].min()[0] || boxes[1].max()[0] < boxes[0].min()[0]);
+    assert!(boxes.iter().all(|bdd| bdd.diagonal().magnitude().near(&f64::sqrt(2.0))));
+}
@@ -0,0 +1,73 @@
+use truck_meshalgo::prelude::*;
+use truck_modeling::{builder, Shell, Wire};
+
+#[test]
+fn inspection_curves_of_cube() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    // 12 edges, 5 teeth and the envelope for each edge
+    let combs = cube.curvature_combs(4, 1.0);
+    assert_eq!(combs.len(), 12 * 6);
+    for comb in combs.chunks(6) {
+        // the edges are lines, so the teeth are degenerate.
+        comb[..5]
+            .iter()
+            .for_each(|tooth| assert!(tooth[0].near(&tooth[1])));
+        // the envelope is the edge itself.
+        assert_eq!(comb[5].len(), 5);
+        assert!(comb[5][0].distance(comb[5][4]).near(&1.0));
+    }
+
+    // 3 curves in each direction on each face
+    let grids = cube.iso_parameter_grids((4, 4), 0.01).unwrap();
+    assert_eq!(grids.len(), 6 * 6);
+    for curve in &grids {
+        let (front, back) = (curve[0], curve[curve.len() - 1]);
+        assert!(front.distance(back).near(&1.0), "{:?}", curve);
+        curve
+            .iter()
+            .for_each(|pt| assert!((0..3).all(|i| -TOLERANCE < pt[i] && pt[i] < 1.0 + TOLERANCE)));
+    }
+}
+
+#[test]
+fn iso_parameter_grids_of_punched_face() {
+    let v = builder::vertex(Point3::new(-1.0, -1.0, 0.0));
+    let e = builder::tsweep(&v, 2.0 * Vector3::unit_x());
+    let square = builder::tsweep(&e, 2.0 * Vector3::unit_y());
+    let v = builder::vertex(Point3::new(0.5, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let mut face = square;
+    face.add_boundary(circle.inverse());
+
+    let grids = face.iso_parameter_grids((2, 2), 0.01).unwrap();
+    // the center lines are divided by the hole.
+    assert_eq!(grids.len(), 4);
+    for curve in &grids {
+        let length: f64 = curve.windows(2).map(|pts| pts[0].distance(pts[1])).sum();
+        assert!(f64::abs(length - 0.5) < 0.01, "{}", length);
+    }
+}
+
+#[test]
+fn continuity_between_plane_and_cylinder() {
+    // the line tangent to the quarter arc with the radius 2
+    let v0 = builder::vertex(Point3::origin());
+    let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let v2 = builder::vertex(Point3::new(3.0, 2.0, 0.0));
+    let transit = Point3::new(1.0 + f64::sqrt(2.0), 2.0 - f64::sqrt(2.0), 0.0);
+    let wire: Wire = vec![builder::line(&v0, &v1), builder::circle_arc(&v1, &v2, transit)].into();
+    let shell: Shell = builder::tsweep(&wire, Vector3::unit_z());
+
+    let continuities = shell.edge_continuities(8).unwrap();
+    assert_eq!(continuities.len(), 1);
+    let (indices, continuity) = continuities[0];
+    assert_eq!(indices, (0, 1));
+    // G1 but not G2: the curvature jumps from 0 to 1/2.
+    assert!(continuity.max_gap < TOLERANCE);
+    assert!(continuity.max_normal_angle.0 < 1.0e-4, "{:?}", continuity);
+    assert!(f64::abs(continuity.max_curvature_jump - 0.5) < 1.0e-4, "{:?}", continuity);
+}
@@ -0,0 +1,81 @@
+use truck_meshalgo::prelude::*;
+use truck_modeling::builder;
+use truck_topology::shell::ShellCondition;
+
+fn cube_mesh() -> PolygonMesh {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, 2.0 * Vector3::unit_z());
+    let mut mesh = cube.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    mesh
+}
+
+#[test]
+fn lattice_in_cube() {
+    let cube = cube_mesh();
+    let lattice_types = [
+        LatticeType::Gyroid,
+        LatticeType::SchwarzP,
+        LatticeType::Diamond,
+        LatticeType::CubicStrut,
+    ];
+    for lattice_type in lattice_types.iter() {
+        let descriptor = LatticeDescriptor {
+            lattice_type: *lattice_type,
+            cell_size: 0.5,
+            thickness: 0.1,
+            resolution: 6,
+            ..Default::default()
+        };
+        let lattice = cube.lattice_infill(&descriptor);
+        assert!(lattice.faces().len() > 0, "{:?}", lattice_type);
+        assert_eq!(
+            lattice.shell_condition(),
+            ShellCondition::Closed,
+            "{:?}",
+            lattice_type
+        );
+        let bdb = lattice.bounding_box();
+        let spacing = descriptor.cell_size / descriptor.resolution as f64;
+        assert!(bdb.min()[0] > -spacing && bdb.max()[0] < 1.0 + spacing);
+        assert!(bdb.min()[2] > -spacing && bdb.max()[2] < 2.0 + spacing);
+    }
+}
+
+#[test]
+fn lattice_with_skin() {
+    let cube = cube_mesh();
+    let descriptor = LatticeDescriptor {
+        lattice_type: LatticeType::CubicStrut,
+        cell_size: 0.5,
+        thickness: 0.1,
+        skin: 0.1,
+        resolution: 6,
+    };
+    let lattice = cube.lattice_infill(&descriptor);
+    assert_eq!(lattice.shell_condition(), ShellCondition::Closed);
+    // the skin covers the whole boundary of the cube.
+    let bvh = MeshBVH::new(&lattice);
+    let origin = Point3::new(0.3, 0.7, -1.0);
+    let hit = bvh.ray_cast(origin, Vector3::unit_z()).unwrap();
+    assert!(f64::abs(hit.distance - 1.0) < 0.05, "{}", hit.distance);
+}
+
+#[test]
+fn lattice_with_invalid_cell_size() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let mesh = cube_mesh();
+    for cell_size in [0.0, -0.5, f64::NAN, f64::INFINITY].iter() {
+        let descriptor = LatticeDescriptor {
+            cell_size: *cell_size,
+            ..Default::default()
+        };
+        assert!(cube.lattice_infill(&descriptor).positions().is_empty());
+        assert!(mesh.lattice_infill(&descriptor).positions().is_empty());
+    }
+}
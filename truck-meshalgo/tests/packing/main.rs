@@ -0,0 +1,72 @@
+use truck_meshalgo::prelude::*;
+#[path = "../common/mod.rs"]
+mod common;
+
+fn overlap(a: &BoundingBox<Point3>, b: &BoundingBox<Point3>, spacing: f64) -> bool {
+    (0..3).all(|i| a.min()[i] + spacing < b.max()[i] && b.min()[i] + spacing < a.max()[i])
+}
+
+#[test]
+fn pack_spheres() {
+    let meshes: Vec<PolygonMesh> = (0..30)
+        .map(|i| {
+            let radius = 1.0 + (i % 4) as f64 * 0.5;
+            let center = Point3::new(i as f64 * 10.0, -(i as f64), 3.0);
+            common::shapes::sphere(center, radius, 8, 8)
+        })
+        .collect();
+    let descriptor = PackingDescriptor {
+        build_volume: Vector3::new(20.0, 20.0, 10.0),
+        spacing: 0.5,
+        allow_rotation: true,
+    };
+    let placements = pack_meshes(&meshes, &descriptor);
+    let placed: Vec<BoundingBox<Point3>> = meshes
+        .iter()
+        .zip(&placements)
+        .filter_map(|(mesh, trans)| Some(mesh.transformed((*trans)?).bounding_box()))
+        .collect();
+    assert!(placed.len() > 20, "{}", placed.len());
+    for (i, a) in placed.iter().enumerate() {
+        (0..3).for_each(|k| {
+            assert!(a.min()[k] > -TOLERANCE);
+            assert!(a.max()[k] < descriptor.build_volume[k] + TOLERANCE);
+        });
+        for b in &placed[i + 1..] {
+            assert!(
+                !overlap(a, b, descriptor.spacing - TOLERANCE),
+                "{:?} {:?}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn pack_with_rotation() {
+    let bdb = |min: Point3, max: Point3| [min, max].iter().collect::<BoundingBox<Point3>>();
+    let boxes = vec![
+        bdb(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 4.0, 1.0)),
+        bdb(Point3::new(1.0, 2.0, 3.0), Point3::new(5.0, 10.0, 4.0)),
+    ];
+    let mut descriptor = PackingDescriptor {
+        build_volume: Vector3::new(10.0, 6.0, 1.0),
+        spacing: 0.0,
+        allow_rotation: false,
+    };
+    let placements = pack_bounding_boxes(&boxes, &descriptor);
+    assert!(placements[0].is_some());
+    assert!(placements[1].is_none());
+
+    descriptor.build_volume = Vector3::new(10.0, 8.0, 1.0);
+    descriptor.allow_rotation = true;
+    let placements = pack_bounding_boxes(&boxes, &descriptor);
+    let trans = placements[1].unwrap();
+    let placed: BoundingBox<Point3> = [Point3::new(1.0, 2.0, 3.0), Point3::new(5.0, 10.0, 4.0)]
+        .iter()
+        .map(|p| trans.transform_point(*p))
+        .collect();
+    assert!(placed.min().near(&Point3::new(0.0, 4.0, 0.0)));
+    assert!(placed.max().near(&Point3::new(8.0, 8.0, 1.0)));
+}
@@ -0,0 +1,134 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+
+// the torus with the major radius 3 and the minor radius 1 around the z axis,
+// divided into `n` x `m` cells
+fn torus(n: usize, m: usize) -> PolygonMesh {
+    let positions = (0..n)
+        .flat_map(|i| {
+            (0..m).map(move |j| {
+                let u = 2.0 * PI * i as f64 / n as f64;
+                let v = 2.0 * PI * j as f64 / m as f64;
+                let r = 3.0 + f64::cos(v);
+                Point3::new(r * f64::cos(u), r * f64::sin(u), f64::sin(v))
+            })
+        })
+        .collect();
+    let idx = |i: usize, j: usize| (i % n) * m + j % m;
+    let faces = Faces::from_iter((0..n).flat_map(|i| {
+        (0..m).map(move |j| [idx(i, j), idx(i + 1, j), idx(i + 1, j + 1), idx(i, j + 1)])
+    }));
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+// the square [0, 1]^2 divided into `n` x `n` cells with wavy heights
+fn grid(n: usize) -> PolygonMesh {
+    let positions = (0..=n)
+        .flat_map(|i| {
+            (0..=n).map(move |j| {
+                let (x, y) = (i as f64 / n as f64, j as f64 / n as f64);
+                Point3::new(x, y, 0.1 * f64::sin(3.0 * x) * f64::cos(2.0 * y))
+            })
+        })
+        .collect();
+    let idx = |i: usize, j: usize| i * (n + 1) + j;
+    let faces = Faces::from_iter((0..n).flat_map(|i| {
+        (0..n).map(move |j| [idx(i, j), idx(i + 1, j), idx(i + 1, j + 1), idx(i, j + 1)])
+    }));
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+// the triangles as the position triples starting from the smallest index
+fn triangles(positions: &[Point3], faces: &[[usize; 3]]) -> Vec<[[u64; 3]; 3]> {
+    let mut res: Vec<[[u64; 3]; 3]> = faces
+        .iter()
+        .map(|tri| {
+            let mut tri: Vec<[u64; 3]> = tri
+                .iter()
+                .map(|v| {
+                    let p = positions[*v];
+                    [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()]
+                })
+                .collect();
+            let min = (0..3).min_by_key(|i| tri[*i]).unwrap();
+            tri.rotate_left(min);
+            [tri[0], tri[1], tri[2]]
+        })
+        .collect();
+    res.sort();
+    res
+}
+
+fn mesh_triangles(mesh: &PolygonMesh) -> Vec<[[u64; 3]; 3]> {
+    let faces: Vec<[usize; 3]> = mesh
+        .face_iter()
+        .flat_map(|face| (2..face.len()).map(move |i| [face[0].pos, face[i - 1].pos, face[i].pos]))
+        .collect();
+    triangles(mesh.positions(), &faces)
+}
+
+fn check_reconstruction(mesh: &PolygonMesh, min_faces: usize) -> ProgressiveMesh {
+    let progressive = ProgressiveMesh::new(mesh, min_faces);
+    assert!(!progressive.is_empty());
+    let mut decoder = progressive.decoder();
+    let mut faces = decoder.faces().len();
+    for split in progressive.splits() {
+        decoder.apply(split);
+        assert!(decoder.faces().len() > faces);
+        faces = decoder.faces().len();
+    }
+    assert_eq!(decoder.positions().len(), mesh.positions().len());
+    assert_eq!(
+        triangles(decoder.positions(), decoder.faces()),
+        mesh_triangles(mesh),
+    );
+    progressive
+}
+
+#[test]
+fn torus_reconstruction() {
+    let mesh = torus(48, 24);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    let progressive = check_reconstruction(&mesh, 100);
+    assert!(progressive.base_faces().len() <= 100);
+    // the base mesh is still closed
+    let base = progressive.polygon_mesh(0);
+    assert_eq!(base.shell_condition(), ShellCondition::Closed);
+    let lod = progressive.polygon_mesh(progressive.len() / 4);
+    assert_eq!(lod.shell_condition(), ShellCondition::Closed);
+}
+
+#[test]
+fn grid_keeps_boundary() {
+    let mesh = grid(12);
+    let progressive = check_reconstruction(&mesh, 0);
+    // the vertices on the boundary are not removed
+    let base = progressive.polygon_mesh(0);
+    assert_eq!(base.positions().len(), 12 * 4);
+    let bdd = base.extract_boundaries();
+    assert_eq!(bdd.len(), 1);
+    assert_eq!(bdd[0].len(), 12 * 4);
+}
+
+#[test]
+fn serialize_progressive_mesh() {
+    let progressive = ProgressiveMesh::new(&grid(6), 10);
+    let json = serde_json::to_vec(&progressive).unwrap();
+    let decoded: ProgressiveMesh = serde_json::from_slice(&json).unwrap();
+    // the positions may differ in the last bits through the json
+    let near = |a: &[Point3], b: &[Point3]| a.iter().zip(b).all(|(p, q)| p.near(q));
+    assert!(near(progressive.base_positions(), decoded.base_positions()));
+    assert_eq!(progressive.base_faces(), decoded.base_faces());
+    assert_eq!(progressive.len(), decoded.len());
+    progressive
+        .splits()
+        .iter()
+        .zip(decoded.splits())
+        .for_each(|(split0, split1)| {
+            assert_eq!(split0.vertex, split1.vertex);
+            assert!(split0.position.near(&split1.position));
+            assert_eq!(split0.moved_faces, split1.moved_faces);
+            assert_eq!(split0.new_faces, split1.new_faces);
+        });
+}
@@ -0,0 +1,72 @@
+use truck_meshalgo::prelude::*;
+use truck_modeling::builder;
+use truck_topology::shell::ShellCondition;
+
+// the Fibonacci lattice on the sphere
+fn sphere_cloud(center: Point3, radius: f64, n: usize) -> PointCloud {
+    let golden_angle = std::f64::consts::PI * (3.0 - f64::sqrt(5.0));
+    let positions = (0..n)
+        .map(|i| {
+            let z = 1.0 - (2 * i + 1) as f64 / n as f64;
+            let theta = i as f64 * golden_angle;
+            let r = f64::sqrt(1.0 - z * z);
+            center + radius * Vector3::new(r * theta.cos(), r * theta.sin(), z)
+        })
+        .collect();
+    PointCloud::from_positions(positions)
+}
+
+#[test]
+fn estimate_normals_of_spheres() {
+    let center = Point3::new(1.0, -2.0, 0.5);
+    let mut cloud = sphere_cloud(center, 2.0, 1000);
+    cloud.estimate_normals(12);
+    assert_eq!(cloud.normals().len(), cloud.len());
+    for (pt, normal) in cloud.positions().iter().zip(cloud.normals()) {
+        assert!(f64::abs(normal.magnitude() - 1.0) < 1.0e-6);
+        assert!(normal.dot((pt - center).normalize()) > 0.98);
+    }
+
+    // two separated spheres are oriented independently
+    let mut cloud = sphere_cloud(Point3::origin(), 1.0, 500);
+    let other = sphere_cloud(Point3::new(5.0, 0.0, 0.0), 1.0, 500);
+    other.positions().iter().for_each(|pt| cloud.push(*pt, None, None));
+    cloud.estimate_normals(10);
+    for (pt, normal) in cloud.positions().iter().zip(cloud.normals()) {
+        let center = match pt[0] < 2.5 {
+            true => Point3::origin(),
+            false => Point3::new(5.0, 0.0, 0.0),
+        };
+        assert!(normal.dot(pt - center) > 0.95);
+    }
+}
+
+#[test]
+fn reconstruct_sphere() {
+    let cloud = sphere_cloud(Point3::origin(), 1.0, 2000);
+    let mesh = cloud.reconstruct_surface(0.1);
+    assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+    for pt in mesh.positions() {
+        assert!(f64::abs(pt.to_vec().magnitude() - 1.0) < 0.05, "{:?}", pt);
+    }
+    assert!(mesh.neighborhood_include(cloud.positions(), 0.05));
+}
+
+#[test]
+fn reconstruct_cube_with_normals() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let mut mesh = cube.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    let cloud = PointCloud::from(&mesh);
+    assert_eq!(cloud.normals().len(), cloud.len());
+
+    let reconstructed = cloud.reconstruct_surface(0.05);
+    assert_eq!(reconstructed.shell_condition(), ShellCondition::Closed);
+    let bdb = reconstructed.bounding_box();
+    assert!(bdb.min().distance(Point3::origin()) < 0.1);
+    assert!(bdb.max().distance(Point3::new(1.0, 1.0, 1.0)) < 0.1);
+    assert!(PointCloud::default().reconstruct_surface(0.1).positions().is_empty());
+}
@@ -0,0 +1,99 @@
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+// the triangles of the box without the shared positions, as STL files
+fn box_soup(min: Point3, max: Point3) -> Vec<[Point3; 3]> {
+    let corner = |i: usize| {
+        Point3::new(
+            if i & 1 == 0 { min[0] } else { max[0] },
+            if i & 2 == 0 { min[1] } else { max[1] },
+            if i & 4 == 0 { min[2] } else { max[2] },
+        )
+    };
+    let quads = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    quads
+        .iter()
+        .flat_map(|q| {
+            let p = q.map(corner);
+            vec![[p[0], p[1], p[2]], [p[0], p[2], p[3]]]
+        })
+        .collect()
+}
+
+fn soup(triangles: &[[Point3; 3]]) -> PolygonMesh {
+    let positions: Vec<Point3> = triangles.iter().flatten().copied().collect();
+    let faces = Faces::from_iter((0..triangles.len()).map(|i| [3 * i, 3 * i + 1, 3 * i + 2]));
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+#[test]
+fn repair_dirty_box() {
+    let min = Point3::new(-1.0, 0.0, 0.5);
+    let max = Point3::new(1.0, 1.0, 2.0);
+    let mut triangles = box_soup(min, max);
+    // a hole, a duplicated face and a crack
+    triangles.remove(3);
+    triangles.push(triangles[0]);
+    triangles[5][0] += Vector3::new(0.0, 0.0, 1.0e-3);
+    let mesh = soup(&triangles);
+    assert_ne!(mesh.shell_condition(), ShellCondition::Closed);
+
+    let repaired = mesh.make_manifold(0.05);
+    assert_eq!(repaired.shell_condition(), ShellCondition::Closed);
+    assert!(repaired.normals().is_empty());
+    let bdb = repaired.bounding_box();
+    assert!(bdb.min().distance(min) < 0.1);
+    assert!(bdb.max().distance(max) < 0.1);
+    assert!(f64::abs(volume(&repaired) - 3.0) < 0.3);
+}
+
+#[test]
+fn repair_overlapping_boxes() {
+    let mut triangles = box_soup(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 1.0, 1.0));
+    triangles.extend(box_soup(Point3::new(1.0, 0.0, 0.0), Point3::new(3.0, 1.0, 1.0)));
+    let repaired = soup(&triangles).make_manifold(0.05);
+    assert_eq!(repaired.shell_condition(), ShellCondition::Closed);
+    // the union of the boxes, not the doubled volume
+    assert!(f64::abs(volume(&repaired) - 3.0) < 0.3);
+}
+
+#[test]
+fn repair_inverted_and_empty() {
+    let triangles = box_soup(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+    let inverted: Vec<[Point3; 3]> = triangles.iter().map(|t| [t[0], t[2], t[1]]).collect();
+    let repaired = soup(&inverted).make_manifold(0.1);
+    assert_eq!(repaired.shell_condition(), ShellCondition::Closed);
+    let expected = soup(&triangles).make_manifold(0.1);
+    assert!(f64::abs(volume(&repaired) - volume(&expected)) < 1.0e-3);
+    assert!(f64::abs(volume(&repaired) - 1.0) < 0.2);
+
+    assert!(PolygonMesh::default().make_manifold(0.1).positions().is_empty());
+    assert!(soup(&triangles).make_manifold(0.0).positions().is_empty());
+}
+
+#[test]
+fn repair_sphere_and_tiny_spacing() {
+    // many triangles, most of which are approximated by the dipoles
+    let sphere = sphere_mesh(Point3::new(0.5, 0.0, 0.0), 1.0);
+    let repaired = sphere.make_manifold(0.05);
+    assert_eq!(repaired.shell_condition(), ShellCondition::Closed);
+    assert!(f64::abs(volume(&repaired) - volume(&sphere)) < 0.2);
+
+    // The spacing is enlarged to the side of the bounding box divided by `MAX_DIVISIONS`.
+    let triangles = box_soup(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.1, 0.1));
+    let repaired = soup(&triangles).make_manifold(1.0e-9);
+    assert_eq!(repaired.shell_condition(), ShellCondition::Closed);
+    let expected = soup(&triangles).make_manifold(1.0 / MAX_DIVISIONS as f64);
+    assert_eq!(repaired.positions().len(), expected.positions().len());
+    assert!(soup(&triangles).make_manifold(f64::NAN).positions().is_empty());
+}
@@ -0,0 +1,77 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+fn min_distance(cloud: &PointCloud) -> f64 {
+    let positions = cloud.positions();
+    (0..positions.len())
+        .flat_map(|i| (0..i).map(move |j| positions[i].distance(positions[j])))
+        .fold(f64::INFINITY, f64::min)
+}
+
+#[test]
+fn sample_cube_mesh() {
+    let mut mesh = cube_solid(Point3::origin()).triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    let cloud = mesh.poisson_disk_sampling(0.1);
+    assert_eq!(cloud.normals().len(), cloud.len());
+    assert!(min_distance(&cloud) >= 0.1);
+    // about the density of the hexagonal packing
+    let expected = 6.0 / (0.1 * 0.1 * f64::sqrt(3.0) / 2.0);
+    assert!(cloud.len() as f64 > 0.5 * expected);
+    assert!((cloud.len() as f64) < expected);
+    let center = Vector3::new(0.5, 0.5, 0.5);
+    for (p, n) in cloud.positions().iter().zip(cloud.normals()) {
+        // the points are on the faces, and the normals are outward
+        let q = p.to_vec() - center;
+        assert!((0..3).any(|i| q[i].abs().near(&0.5)));
+        assert!((0..3).all(|i| q[i].abs() < 0.5 + TOLERANCE));
+        assert!(n.dot(q) > 0.0);
+    }
+    // every point on the mesh is covered
+    for p in mesh.positions() {
+        let dist = cloud
+            .positions()
+            .iter()
+            .map(|q| p.distance(*q))
+            .fold(f64::INFINITY, f64::min);
+        assert!(dist < 0.2, "{:?} {}", p, dist);
+    }
+    // reproducible
+    assert_eq!(cloud.positions(), mesh.poisson_disk_sampling(0.1).positions());
+}
+
+#[test]
+fn sample_sphere_solid() {
+    let center = Point3::new(1.0, 2.0, -1.0);
+    let solid = sphere_solid(center, 2.0);
+    let cloud = solid.poisson_disk_sampling(0.3);
+    assert!(min_distance(&cloud) >= 0.3 - TOLERANCE);
+    let expected = 16.0 * PI / (0.3 * 0.3 * f64::sqrt(3.0) / 2.0);
+    assert!(cloud.len() as f64 > 0.5 * expected);
+    for (p, n) in cloud.positions().iter().zip(cloud.normals()) {
+        // the points and the normals are on the exact surface
+        assert!((p.distance(center) - 2.0).abs() < 1.0e-6);
+        assert_near!(*n, (p - center) / 2.0);
+    }
+
+    // the samples of the face are the samples of the solid with one face
+    let face = solid.boundaries()[0].face_iter().next().unwrap();
+    let samples = face.poisson_disk_sampling(0.3);
+    assert!(!samples.is_empty());
+    assert!(samples.len() < cloud.len());
+    for (p, n) in samples.positions().iter().zip(samples.normals()) {
+        assert_near!(*n, (p - center) / 2.0);
+    }
+}
+
+#[test]
+fn sample_degenerate_inputs() {
+    assert!(PolygonMesh::default().poisson_disk_sampling(0.1).is_empty());
+    let mesh = cube_solid(Point3::origin()).triangulation(0.01).unwrap().into_polygon();
+    assert!(mesh.poisson_disk_sampling(0.0).is_empty());
+    assert!(cube_solid(Point3::origin()).poisson_disk_sampling(-1.0).is_empty());
+}
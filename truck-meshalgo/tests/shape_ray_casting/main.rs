@@ -0,0 +1,110 @@
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+#[test]
+fn cube_ray_hits() {
+    let cube = cube_solid(Point3::origin());
+    let hits = cube
+        .ray_hits(Point3::new(-1.0, 0.3, 0.6), Vector3::unit_x(), 0.01)
+        .unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_near!(hits[0].point, Point3::new(0.0, 0.3, 0.6));
+    assert_near!(hits[0].normal, -Vector3::unit_x());
+    assert_eq!(hits[0].crossing, RayCrossing::Entering);
+    assert_near!(hits[1].point, Point3::new(1.0, 0.3, 0.6));
+    assert!(hits[1].distance.near(&2.0));
+    assert_eq!(hits[1].crossing, RayCrossing::Exiting);
+    assert_ne!(hits[0].face_index, hits[1].face_index);
+    // the face of the intersection
+    let face = cube.boundaries()[0].face_iter().nth(hits[1].face_index).unwrap();
+    let hit = face
+        .ray_cast(Point3::new(-1.0, 0.3, 0.6), Vector3::unit_x(), 0.01)
+        .unwrap();
+    assert_eq!(hit.face_index, 0);
+    assert_near!(hit.point, hits[1].point);
+
+    // the planes of the faces are hit out of the trimming domains
+    let hits = cube
+        .ray_hits(Point3::new(-1.0, 1.5, 0.5), Vector3::unit_x(), 0.01)
+        .unwrap();
+    assert!(hits.is_empty());
+    // the ray from the inside
+    let hit = cube
+        .ray_cast(Point3::new(0.5, 0.5, 0.5), Vector3::new(0.0, 1.0, 2.0), 0.01)
+        .unwrap();
+    assert_near!(hit.point, Point3::new(0.5, 0.75, 1.0));
+    assert_eq!(hit.crossing, RayCrossing::Exiting);
+}
+
+#[test]
+fn sphere_picking() {
+    let sphere = sphere_solid(Point3::origin(), 1.0);
+    let origin = Point3::new(-5.0, 0.25, 0.25);
+    let hit = sphere.ray_cast(origin, Vector3::unit_x(), 0.05).unwrap();
+    // exact on the sphere, not on the tessellation
+    assert!(f64::abs(hit.point.to_vec().magnitude() - 1.0) < 1.0e-6, "{:?}", hit);
+    assert!(hit.point[0].near(&-f64::sqrt(0.875)), "{:?}", hit);
+    assert_eq!(hit.crossing, RayCrossing::Entering);
+    assert_near!(hit.normal.normalize(), hit.point.to_vec());
+    let hits = sphere.ray_hits(origin, Vector3::unit_x(), 0.05).unwrap();
+    assert_eq!(hits.len(), 2);
+    assert!(hits[1].point[0].near(&f64::sqrt(0.875)), "{:?}", hits);
+    assert_eq!(hits[1].crossing, RayCrossing::Exiting);
+    assert!(sphere.ray_cast(origin, Vector3::unit_y(), 0.05).is_none());
+}
+
+#[test]
+fn point_in_solid() {
+    use PointContainment::*;
+    let cube = cube_solid(Point3::origin());
+    assert_eq!(cube.contains(Point3::new(0.5, 0.5, 0.5), 0.01), Some(Inside));
+    assert_eq!(cube.contains(Point3::new(0.99, 0.01, 0.5), 0.01), Some(Inside));
+    assert_eq!(cube.contains(Point3::new(1.01, 0.5, 0.5), 0.01), Some(Outside));
+    assert_eq!(cube.contains(Point3::new(-3.0, 2.0, 0.5), 0.01), Some(Outside));
+    // the ray from the point to the diagonal direction passes through the edges
+    assert_eq!(cube.contains(Point3::new(0.25, 0.25, 0.25), 0.01), Some(Inside));
+    // on the face, the edge and the vertex
+    assert_eq!(cube.contains(Point3::new(0.5, 0.5, 1.0), 0.01), Some(OnBoundary));
+    assert_eq!(cube.contains(Point3::new(1.0, 0.5, 1.0), 0.01), Some(OnBoundary));
+    assert_eq!(cube.contains(Point3::new(1.0, 1.0, 0.0), 0.01), Some(OnBoundary));
+    // on the plane of the face, but outside
+    assert_eq!(cube.contains(Point3::new(2.0, 0.5, 1.0), 0.01), Some(Outside));
+
+    let sphere = sphere_solid(Point3::origin(), 1.0);
+    for i in 0..10 {
+        let t = i as f64 / 10.0;
+        let p = Point3::new(t, t / 2.0, -t / 3.0);
+        let res = match p.to_vec().magnitude() < 1.0 {
+            true => Inside,
+            false => Outside,
+        };
+        assert_eq!(sphere.contains(p, 0.05), Some(res), "{:?}", p);
+    }
+    let p = Point3::new(0.36, 0.48, 0.8);
+    assert_eq!(sphere.contains(p, 0.05), Some(OnBoundary));
+}
+
+#[test]
+fn solid_classifier() {
+    use PointContainment::*;
+    // the hollow sphere, whose cavity is outside
+    let outer = sphere_solid(Point3::origin(), 1.0).into_boundaries().pop().unwrap();
+    let inner = sphere_solid(Point3::origin(), 0.5).into_boundaries().pop().unwrap();
+    let inner: Shell = inner.face_iter().map(|face| face.inverse()).collect();
+    let hollow = Solid::new(vec![outer, inner]);
+    let classifier = SolidClassifier::new(&hollow, 0.05).unwrap();
+    for i in 0..20 {
+        let t = i as f64 / 20.0;
+        let dir = Vector3::new(1.0, 2.0 * t - 1.0, t).normalize();
+        for (r, res) in [(0.2, Outside), (0.75, Inside), (1.5, Outside)].iter() {
+            assert_eq!(classifier.classify(Point3::from_vec(*r * dir)), Some(*res), "{} {}", i, r);
+        }
+        // between the exact surface and the tessellation
+        assert_eq!(classifier.classify(Point3::from_vec(0.995 * dir)), Some(Inside), "{}", i);
+        assert_eq!(classifier.classify(Point3::from_vec(0.495 * dir)), Some(Outside));
+        assert_eq!(classifier.classify(Point3::from_vec(dir)), Some(OnBoundary));
+    }
+}
@@ -0,0 +1,84 @@
+use truck_meshalgo::prelude::*;
+use truck_modeling::builder;
+
+fn closed_mesh(solid: &truck_modeling::Solid) -> PolygonMesh {
+    let mut mesh = solid.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    mesh
+}
+
+#[test]
+fn skeleton_of_torus() {
+    // the torus with the major radius 3 and the minor radius 1 around the z axis
+    let v = builder::vertex(Point3::new(3.0, 0.0, 1.0));
+    let circle = builder::rsweep(&v, Point3::new(3.0, 0.0, 0.0), Vector3::unit_y(), Rad(7.0));
+    let torus = builder::rsweep(&circle, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let mesh = closed_mesh(&truck_modeling::Solid::new(vec![torus]));
+
+    let skeleton = mesh.curve_skeleton(0.2);
+    assert_eq!(skeleton.nodes.len(), 1);
+    assert_eq!(skeleton.branches.len(), 1);
+    let branch = &skeleton.branches[0];
+    // a loop
+    assert_eq!(branch.ends, (0, 0));
+    let curve = &branch.curve;
+    assert!(curve[0].near(&curve[curve.len() - 1]));
+    // the core circle
+    curve.iter().for_each(|pt| {
+        assert!(f64::abs(f64::hypot(pt[0], pt[1]) - 3.0) < 0.3, "{:?}", pt);
+        assert!(f64::abs(pt[2]) < 0.3, "{:?}", pt);
+    });
+}
+
+#[test]
+fn skeleton_of_cross() {
+    // the plus-shaped plate with the arms of the width 1 and the thickness 1
+    let points = [
+        (0.5, 0.5),
+        (4.0, 0.5),
+        (4.0, -0.5),
+        (0.5, -0.5),
+        (0.5, -4.0),
+        (-0.5, -4.0),
+        (-0.5, -0.5),
+        (-4.0, -0.5),
+        (-4.0, 0.5),
+        (-0.5, 0.5),
+        (-0.5, 4.0),
+        (0.5, 4.0),
+    ];
+    let vertices: Vec<_> = points
+        .iter()
+        .map(|(x, y)| builder::vertex(Point3::new(*x, *y, 0.0)))
+        .collect();
+    let wire: truck_modeling::Wire = (0..12)
+        .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % 12]))
+        .collect();
+    let plate = builder::try_attach_plane(&vec![wire.inverse()]).unwrap();
+    let cross = builder::tsweep(&plate, Vector3::unit_z());
+    let mesh = closed_mesh(&cross);
+
+    let skeleton = mesh.curve_skeleton(0.125);
+    // four arms meeting at the center
+    let tips: Vec<&Point3> = skeleton
+        .nodes
+        .iter()
+        .filter(|pt| pt.to_vec().magnitude() > 2.5)
+        .collect();
+    assert_eq!(tips.len(), 4);
+    let mut arms: Vec<&SkeletonBranch> = skeleton
+        .branches
+        .iter()
+        .filter(|branch| {
+            let (p, q) = (branch.curve[0], branch.curve[branch.curve.len() - 1]);
+            p.to_vec().magnitude() > 2.5 || q.to_vec().magnitude() > 2.5
+        })
+        .collect();
+    assert_eq!(arms.len(), 4);
+    arms.iter_mut().for_each(|branch| {
+        branch.curve.iter().for_each(|pt| {
+            assert!(f64::min(pt[0].abs(), pt[1].abs()) < 0.2, "{:?}", pt);
+            assert!(f64::abs(pt[2] - 0.5) < 0.2, "{:?}", pt);
+        })
+    });
+}
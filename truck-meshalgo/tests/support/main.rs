@@ -0,0 +1,56 @@
+use truck_meshalgo::prelude::*;
+use truck_modeling::builder;
+
+fn cuboid(origin: Point3, size: Vector3) -> PolygonMesh {
+    let v = builder::vertex(origin);
+    let e = builder::tsweep(&v, size[0] * Vector3::unit_x());
+    let f = builder::tsweep(&e, size[1] * Vector3::unit_y());
+    let solid = builder::tsweep(&f, size[2] * Vector3::unit_z());
+    let mut mesh = solid.triangulation(0.01).unwrap().into_polygon();
+    mesh.put_together_same_attrs();
+    mesh
+}
+
+#[test]
+fn support_of_table() {
+    // a slab on a short column
+    let mut table = cuboid(Point3::new(0.0, 0.0, 5.0), Vector3::new(4.0, 4.0, 1.0));
+    table.merge(cuboid(
+        Point3::new(1.5, 1.5, 0.0),
+        Vector3::new(1.0, 1.0, 3.0),
+    ));
+    let descriptor = SupportDescriptor::default();
+
+    let overhangs = table.overhanging_faces(&descriptor);
+    // the bottom of the slab and the bottom of the column
+    assert_eq!(overhangs.len(), 4);
+
+    let support = table.support_structure(&descriptor);
+    // the pillars stand on the grid points from (0, 0) to (4, 4).
+    let bdb = support.bounding_box();
+    let radius = descriptor.radius;
+    assert!(bdb.min().near(&Point3::new(-radius, -radius, 0.0)));
+    assert!(bdb
+        .max()
+        .near(&Point3::new(4.0 + radius, 4.0 + radius, 5.0)));
+    // 3 x 3 pillars
+    assert_eq!(support.faces().len(), 9 * (2 + 2 * descriptor.sides));
+    // the pillar at the center stands on the column.
+    let center: Vec<&Point3> = support
+        .positions()
+        .iter()
+        .filter(|p| Point2::new(p[0], p[1]).distance(Point2::new(2.0, 2.0)) < 0.5)
+        .collect();
+    assert!(!center.is_empty());
+    assert!(center.iter().all(|p| p[2] > 3.0 - TOLERANCE));
+}
+
+#[test]
+fn no_support_for_wall() {
+    let wall = cuboid(Point3::origin(), Vector3::new(10.0, 1.0, 10.0));
+    let descriptor = SupportDescriptor::default();
+    // only the bottom is overhanging, which is on the build plate.
+    assert_eq!(wall.overhanging_faces(&descriptor).len(), 2);
+    let support = wall.support_structure(&descriptor);
+    assert_eq!(support.faces().len(), 0);
+}
@@ -1,4 +1,6 @@
 use super::*;
+use std::f64::consts::PI;
+use truck_polymesh::progress::CancellationToken;
 use truck_topology::shell::ShellCondition;
 
 const SHAPE_JSONS: [&'static [u8]; 3] = [
@@ -27,3 +29,432 @@ fn compare_occt_mesh() {
     assert!(res.is_clung_to_by(ans.positions(), 0.05));
     assert!(ans.is_clung_to_by(res.positions(), 0.05));
 }
+
+#[test]
+fn triangulation_progress() {
+    for json in SHAPE_JSONS.iter() {
+        let solid = Solid::extract(serde_json::from_reader(*json).unwrap()).unwrap();
+        let len: usize = solid.boundaries().iter().map(|shell| shell.len()).sum();
+        let mut counter = 0;
+        solid
+            .triangulation_with_progress(0.01, |i, n| {
+                counter += 1;
+                assert_eq!(i, counter);
+                assert_eq!(n, len);
+                true
+            })
+            .unwrap();
+        assert_eq!(counter, len);
+    }
+}
+
+#[test]
+fn triangulation_cancellation() {
+    for json in SHAPE_JSONS.iter() {
+        let solid = Solid::extract(serde_json::from_reader(*json).unwrap()).unwrap();
+        let mut counter = 0;
+        let res = solid.try_triangulation_with_progress(0.01, |i, _| {
+            counter = i;
+            i < 2
+        });
+        assert!(matches!(res, Err(TessellationError::Cancelled)));
+        assert_eq!(counter, 2);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(solid.triangulation_with_progress(0.01, &token).is_none());
+    }
+}
+
+#[test]
+fn retriangulation_patches_mesh() {
+    for json in SHAPE_JSONS.iter() {
+        let solid = Solid::extract(serde_json::from_reader(*json).unwrap()).unwrap();
+        let mut meshed = solid.triangulation(0.01).unwrap();
+
+        let face = &solid.boundaries()[0][0];
+        face.set_surface(face.get_surface());
+        let edge = solid.edge_iter().last().unwrap();
+        edge.set_curve(edge.get_curve());
+        let expected = solid
+            .face_iter()
+            .filter(|f| {
+                f.id() == face.id() || f.boundaries().iter().flatten().any(|e| e.is_same(&edge))
+            })
+            .count();
+
+        let count = solid.retriangulation(&mut meshed, &[face.id()], &[edge.id()], 0.01);
+        assert_eq!(count, Some(expected));
+        let mut poly = meshed.into_polygon();
+        poly.put_together_same_attrs()
+            .remove_degenerate_faces()
+            .remove_unused_attrs();
+        assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+        let ans = solid.triangulation(0.01).unwrap().into_polygon();
+        let (bdb0, bdb1) = (poly.bounding_box(), ans.bounding_box());
+        assert_near!(bdb0.min(), bdb1.min());
+        assert_near!(bdb0.max(), bdb1.max());
+    }
+}
+
+#[test]
+fn region_retriangulation_stitches_mesh() {
+    for json in SHAPE_JSONS.iter() {
+        let solid = Solid::extract(serde_json::from_reader(*json).unwrap()).unwrap();
+        let mut meshed = solid.triangulation(0.05).unwrap();
+        let coarse = meshed.into_polygon();
+        let bdb = coarse.bounding_box();
+        let (center, diag) = (bdb.center(), bdb.diagonal());
+        let region: BoundingBox<Point3> =
+            [center - diag * 0.5, center + diag * 0.1].iter().collect();
+        let include =
+            |p: &Point3| (0..3).all(|i| region.min()[i] <= p[i] && p[i] <= region.max()[i]);
+
+        let region0 = RemeshRegion::BoundingBox(region.clone());
+        let count = solid.region_retriangulation(&mut meshed, &region0, 0.005);
+        assert!(count.unwrap() > 0);
+        let mut poly = meshed.into_polygon();
+        let inside = |mesh: &PolygonMesh| mesh.positions().iter().filter(|p| include(p)).count();
+        assert!(inside(&poly) > inside(&coarse));
+        let outside = |mesh: &PolygonMesh| mesh.positions().len() - inside(mesh);
+        assert_eq!(outside(&poly), outside(&coarse));
+        poly.put_together_same_attrs()
+            .remove_degenerate_faces()
+            .remove_unused_attrs();
+        assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    }
+}
+
+#[test]
+fn region_retriangulation_near_face() {
+    let solid = Solid::extract(serde_json::from_slice(SHAPE_JSONS[1]).unwrap()).unwrap();
+    let mut meshed = solid.triangulation(0.05).unwrap();
+    let face = &solid.boundaries()[0][0];
+    let region = RemeshRegion::NearFace {
+        face: face.id(),
+        margin: 0.01,
+    };
+    let count = solid.region_retriangulation(&mut meshed, &region, 0.005);
+    // the face, the adjacent faces and the faces touching the enlarged bounding box
+    let adjacent = solid
+        .face_iter()
+        .filter(|f| f.id() == face.id() || f.border_on(face))
+        .count();
+    assert!(adjacent <= count.unwrap());
+    let mut poly = meshed.into_polygon();
+    poly.put_together_same_attrs()
+        .remove_degenerate_faces()
+        .remove_unused_attrs();
+    assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+
+    // the face not in the solid
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let other = builder::tsweep(&e, Vector3::unit_y());
+    let region = RemeshRegion::NearFace {
+        face: other.id(),
+        margin: 0.01,
+    };
+    assert!(solid
+        .region_retriangulation(&mut meshed, &region, 0.005)
+        .is_none());
+}
+
+#[test]
+fn decimation_keeps_boundaries() {
+    for json in SHAPE_JSONS.iter() {
+        let solid = Solid::extract(serde_json::from_reader(*json).unwrap()).unwrap();
+        let meshed = solid.triangulation(0.01).unwrap();
+        let decimated = meshed.decimation(0.2);
+        let (shell0, shell1) = (&meshed.boundaries()[0], &decimated.boundaries()[0]);
+        assert_eq!(shell0.len(), shell1.len());
+        for (face0, face1) in shell0.face_iter().zip(shell1.face_iter()) {
+            assert_eq!(face0.orientation(), face1.orientation());
+            let edges0 = face0.boundaries().into_iter().flatten().map(|edge| edge.id());
+            let edges1 = face1.boundaries().into_iter().flatten().map(|edge| edge.id());
+            assert!(edges0.eq(edges1));
+            let (poly0, poly1) = (face0.get_surface(), face1.get_surface());
+            // planar faces may have no inner vertices
+            assert!(poly1.faces().len() <= poly0.faces().len());
+            let boundary = |poly: &PolygonMesh| {
+                let mut boundary: Vec<[u64; 3]> = poly
+                    .extract_boundaries()
+                    .into_iter()
+                    .flatten()
+                    .map(|i| {
+                        let p = poly.positions()[i];
+                        [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()]
+                    })
+                    .collect();
+                boundary.sort();
+                boundary.dedup();
+                boundary
+            };
+            assert_eq!(boundary(&poly0), boundary(&poly1));
+        }
+        let mut poly = decimated.into_polygon();
+        // the grids of the faces may have no inner vertices
+        assert!(poly.faces().len() <= meshed.into_polygon().faces().len());
+        poly.put_together_same_attrs()
+            .remove_degenerate_faces()
+            .remove_unused_attrs();
+        assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    }
+}
+
+#[test]
+fn triangulation_with_pcurves() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let line = |t0: f64, t1: f64, uv0: Point2, uv1: Point2| {
+        BSplineCurve::new(KnotVec::from(vec![t0, t0, t1, t1]), vec![uv0, uv1])
+    };
+    let mut pcurves = truck_topology::PCurveMap::new();
+    for face in cube.face_iter() {
+        let surface = face.get_surface();
+        for edge in face.absolute_boundaries().iter().flatten() {
+            let curve = edge.get_curve();
+            let (t0, t1) = curve.parameter_range();
+            let uv0 = surface.search_parameter(curve.subs(t0), None, 100).unwrap();
+            let uv1 = surface.search_parameter(curve.subs(t1), None, 100).unwrap();
+            pcurves.insert(edge.id(), face.id(), line(t0, t1, uv0.into(), uv1.into()));
+        }
+    }
+    let shell = &cube.boundaries()[0];
+    assert!(pcurves.desynchronized(shell, 1.0e-6).is_empty());
+    let ans = cube.triangulation(0.01).unwrap().into_polygon();
+    let res = cube.triangulation_with_pcurves(0.01, &pcurves).unwrap().into_polygon();
+    assert!(res.is_clung_to_by(ans.positions(), 1.0e-6));
+    assert!(ans.is_clung_to_by(res.positions(), 1.0e-6));
+
+    // the meshes of the faces follow the pcurves
+    let face = &shell[0];
+    let edge = &face.absolute_boundaries()[0][0];
+    let (t0, t1) = edge.get_curve().parameter_range();
+    let pcurve = pcurves.get(edge.id(), face.id()).unwrap();
+    let shift = Vector2::new(0.1, 0.1);
+    let uv0 = pcurve.subs(t0) + shift;
+    let uv1 = pcurve.subs(t1) + shift;
+    pcurves.insert(edge.id(), face.id(), line(t0, t1, uv0, uv1));
+    assert_eq!(pcurves.desynchronized(shell, 1.0e-6), vec![(edge.id(), face.id())]);
+    let meshed = cube.triangulation_with_pcurves(0.01, &pcurves).unwrap();
+    let polygon = meshed.boundaries()[0][0].get_surface();
+    assert!(!ans.is_clung_to_by(polygon.positions(), 0.05));
+}
+
+#[test]
+fn triangulation_of_degenerate_surfaces() {
+    // the sphere whose poles are the degenerate edges
+    let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    let arc = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(PI));
+    let sphere = builder::rsweep(&arc, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    // the cone whose apex and the center of the bottom are on the axis
+    let v0 = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let v2 = builder::vertex(Point3::origin());
+    let wire: Wire = vec![builder::line(&v0, &v1), builder::line(&v1, &v2)].into();
+    let cone = builder::rsweep(&wire, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+
+    let area = |poly: &PolygonMesh| -> f64 {
+        poly.tri_faces()
+            .iter()
+            .map(|tri| {
+                let [p, q, r] = [0, 1, 2].map(|i| poly.positions()[tri[i].pos]);
+                (q - p).cross(r - p).magnitude() / 2.0
+            })
+            .sum()
+    };
+    let expected = [4.0 * PI, PI * f64::sqrt(2.0) + PI];
+    for (shell, expected) in [sphere, cone].iter().zip(expected) {
+        let meshed = shell.triangulation(0.01).unwrap();
+        for face in meshed.face_iter() {
+            let poly = face.get_surface();
+            assert!(poly.normals().iter().all(|n| n.magnitude().near(&1.0)));
+            assert!(poly.tri_faces().iter().all(|tri| {
+                tri[0].pos != tri[1].pos && tri[1].pos != tri[2].pos && tri[2].pos != tri[0].pos
+            }));
+        }
+        let mut poly = meshed.into_polygon();
+        assert!(f64::abs(area(&poly) - expected) < 0.1);
+        poly.put_together_same_attrs()
+            .remove_degenerate_faces()
+            .remove_unused_attrs();
+        assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+    }
+}
+
+/// the surface whose `search_parameter` fails at the points where `fails` is true
+#[derive(Clone, Debug)]
+struct FlakySurface {
+    surface: Surface,
+    fails: fn(Point3) -> bool,
+}
+
+impl ParametricSurface for FlakySurface {
+    type Point = Point3;
+    type Vector = Vector3;
+    fn subs(&self, u: f64, v: f64) -> Point3 { self.surface.subs(u, v) }
+    fn uder(&self, u: f64, v: f64) -> Vector3 { self.surface.uder(u, v) }
+    fn vder(&self, u: f64, v: f64) -> Vector3 { self.surface.vder(u, v) }
+    fn uuder(&self, u: f64, v: f64) -> Vector3 { self.surface.uuder(u, v) }
+    fn uvder(&self, u: f64, v: f64) -> Vector3 { self.surface.uvder(u, v) }
+    fn vvder(&self, u: f64, v: f64) -> Vector3 { self.surface.vvder(u, v) }
+}
+
+impl ParametricSurface3D for FlakySurface {}
+
+impl Invertible for FlakySurface {
+    fn invert(&mut self) { self.surface.invert() }
+}
+
+impl ParameterDivision2D for FlakySurface {
+    fn parameter_division(
+        &self,
+        range: ((f64, f64), (f64, f64)),
+        tol: f64,
+    ) -> (Vec<f64>, Vec<f64>) {
+        self.surface.parameter_division(range, tol)
+    }
+}
+
+impl SearchParameter for FlakySurface {
+    type Point = Point3;
+    type Parameter = (f64, f64);
+    fn search_parameter(
+        &self,
+        pt: Point3,
+        hint: Option<(f64, f64)>,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        match (self.fails)(pt) {
+            true => None,
+            false => self.surface.search_parameter(pt, hint, trials),
+        }
+    }
+}
+
+#[test]
+fn triangulation_with_failing_search_parameter() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let flaky = |fails: fn(Point3) -> bool| {
+        cube.mapped(
+            |pt| *pt,
+            |curve| curve.clone(),
+            |surface| FlakySurface {
+                surface: surface.clone(),
+                fails,
+            },
+        )
+    };
+
+    // the points around the edge x = y = 1 are projected by the subdivision
+    let solid = flaky(|pt| pt[0] > 0.7 && pt[1] > 0.7);
+    let mut poly = solid.try_triangulation(0.01).unwrap().into_polygon();
+    let ans = cube.triangulation(0.01).unwrap().into_polygon();
+    assert!(poly.is_clung_to_by(ans.positions(), 1.0e-6));
+    assert!(ans.is_clung_to_by(poly.positions(), 1.0e-6));
+    poly.put_together_same_attrs();
+    assert_eq!(poly.shell_condition(), ShellCondition::Closed);
+
+    // no parameters are found on the top face
+    let solid = flaky(|pt| pt[2] > 0.5);
+    let top = solid.boundaries()[0]
+        .face_iter()
+        .position(|face| face.boundaries()[0].vertex_iter().all(|v| v.get_point()[2] > 0.5))
+        .unwrap();
+    match solid.try_triangulation(0.01) {
+        Err(TessellationError::ParameterNotFound {
+            face, wire, point, ..
+        }) => {
+            assert_eq!(face, top);
+            assert_eq!(wire, 0);
+            assert_near!(point[2], 1.0);
+        }
+        _ => panic!("the tessellation must fail on the top face"),
+    }
+    assert!(solid.triangulation(0.01).is_none());
+}
+
+#[test]
+fn triangulation_report() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let (meshed, report) = cube.triangulation_with_report(0.01);
+    let polygon = meshed.unwrap().into_polygon();
+    assert!(report.failures.is_empty());
+    assert_eq!(report.faces.len(), 6);
+    assert_eq!(report.triangles(), polygon.tri_faces().len());
+    assert!(report.max_deviation() < 1.0e-6);
+    assert!(report.min_angle() > PI / 8.0);
+
+    // all failed faces, the top and the bottom, are reported
+    let horizontal = |surface: &Surface| {
+        let normal = surface.normal(0.5, 0.5);
+        normal.cross(Vector3::unit_z()).so_small()
+    };
+    let solid = cube.mapped(
+        |pt| *pt,
+        |curve| curve.clone(),
+        |surface| FlakySurface {
+            surface: surface.clone(),
+            fails: match horizontal(surface) {
+                true => |_| true,
+                false => |_| false,
+            },
+        },
+    );
+    let (meshed, report) = solid.triangulation_with_report(0.01);
+    assert!(meshed.is_none());
+    assert_eq!(report.failures.len(), 2);
+    let failed = report.failed_faces();
+    for (i, face) in cube.boundaries()[0].face_iter().enumerate() {
+        let horizontal = horizontal(&face.get_surface());
+        assert_eq!(failed.contains(&i), horizontal);
+        assert_eq!(report.faces[i].is_none(), horizontal);
+    }
+    assert_eq!(failed.len(), 2);
+}
+
+struct OnlyTriangulation(Option<Solid>);
+
+impl MeshableShape for OnlyTriangulation {
+    type MeshedShape = truck_topology::Solid<Point3, PolylineCurve<Point3>, PolygonMesh>;
+    fn triangulation(&self, tol: f64) -> Option<Self::MeshedShape> {
+        self.0.as_ref()?.triangulation(tol)
+    }
+}
+
+#[test]
+fn default_triangulation_methods() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    let shape = OnlyTriangulation(Some(cube));
+    let mut log = Vec::new();
+    let meshed = shape.triangulation_with_progress(0.01, |i, n| {
+        log.push((i, n));
+        true
+    });
+    assert_eq!(meshed.unwrap().into_polygon().tri_faces().len(), 12);
+    assert!(log.is_empty());
+    let (meshed, report) = shape.triangulation_with_report(0.01);
+    assert!(meshed.is_some() && report.failures.is_empty());
+
+    let shape = OnlyTriangulation(None);
+    assert!(matches!(
+        shape.try_triangulation(0.01),
+        Err(TessellationError::Unknown)
+    ));
+    let (meshed, report) = shape.triangulation_with_report(0.01);
+    assert!(meshed.is_none());
+    assert_eq!(report.failures.len(), 1);
+}
@@ -0,0 +1,75 @@
+use std::f64::consts::PI;
+use truck_meshalgo::prelude::*;
+use truck_modeling::*;
+
+fn cube(origin: Point3, size: f64) -> Solid {
+    let v = builder::vertex(origin);
+    let e = builder::tsweep(&v, size * Vector3::unit_x());
+    let f = builder::tsweep(&e, size * Vector3::unit_y());
+    builder::tsweep(&f, size * Vector3::unit_z())
+}
+
+#[test]
+fn hollow_cube() {
+    let outer = cube(Point3::origin(), 1.0);
+    let inner = cube(Point3::new(0.1, 0.1, 0.1), 0.8);
+    // the cavity is bounded by the inverted faces
+    let cavity: Shell = inner.boundaries()[0].face_iter().map(Face::inverse).collect();
+    let mut boundaries = outer.into_boundaries();
+    boundaries.push(cavity);
+    let hollow = Solid::new(boundaries);
+
+    let thicknesses = hollow.wall_thicknesses(0.01).unwrap();
+    // all sample points have the opposite faces
+    let samples: usize = hollow
+        .triangulation(0.01)
+        .unwrap()
+        .face_iter()
+        .map(|face| face.get_surface().faces().len())
+        .sum();
+    assert_eq!(thicknesses.len(), samples);
+    for thickness in &thicknesses {
+        assert!(thickness.thickness.near(&0.1), "{:?}", thickness);
+        let diff = thickness.opposite_point - thickness.point;
+        assert!(diff.magnitude().near(&thickness.thickness));
+        assert_ne!(thickness.face_index, thickness.opposite_face_index);
+        // the outer faces and the inner faces face each other
+        assert_ne!(thickness.face_index < 6, thickness.opposite_face_index < 6);
+    }
+}
+
+#[test]
+fn thick_tube() {
+    // the tube with the inner radius 0.8, the outer radius 1.0 and the height 1.0
+    let v = builder::vertex(Point3::new(0.8, 0.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::unit_z());
+    let f = builder::tsweep(&e, 0.2 * Vector3::unit_x());
+    let tube = builder::rsweep(&f, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+
+    let thicknesses = tube.wall_thicknesses(0.01).unwrap();
+    assert!(!thicknesses.is_empty());
+    for thickness in &thicknesses {
+        assert!(thickness.thickness > 0.2 - TOLERANCE, "{:?}", thickness);
+        // the points are on the exact surfaces, not on the tessellation
+        let r = thickness.point[0].hypot(thickness.point[1]);
+        let top_or_bottom = thickness.point[2].near(&0.0) || thickness.point[2].near(&1.0);
+        assert!(r.near(&0.8) || r.near(&1.0) || top_or_bottom, "{:?}", thickness);
+    }
+    let min = tube.minimum_wall_thickness(0.01).unwrap();
+    assert!(min.thickness.near(&0.2), "{:?}", min);
+}
+
+#[test]
+fn sphere_diameter() {
+    let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    let wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(PI));
+    let shell = builder::cone(&wire, Vector3::unit_z(), Rad(2.0 * PI));
+    let sphere = Solid::new(vec![shell]);
+
+    // the rays in the inward normals pass through the center.
+    let thicknesses = sphere.wall_thicknesses(0.01).unwrap();
+    assert!(!thicknesses.is_empty());
+    for thickness in &thicknesses {
+        assert!(f64::abs(thickness.thickness - 2.0) < 1.0e-6, "{:?}", thickness);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+use truck_meshalgo::prelude::*;
+use truck_topology::shell::ShellCondition;
+#[path = "../common/mod.rs"]
+mod common;
+use common::shapes::*;
+
+// V - E + F, which is 2 for each component homeomorphic to the sphere
+fn euler_characteristic(mesh: &PolygonMesh) -> isize {
+    let edges: HashSet<[usize; 2]> = mesh
+        .face_iter()
+        .flat_map(|face| {
+            let len = face.len();
+            (0..len).map(move |i| {
+                let (a, b) = (face[i].pos, face[(i + 1) % len].pos);
+                [usize::min(a, b), usize::max(a, b)]
+            })
+        })
+        .collect();
+    let vertices: HashSet<usize> = mesh.face_iter().flatten().map(|v| v.pos).collect();
+    vertices.len() as isize - edges.len() as isize + mesh.faces().len() as isize
+}
+
+#[test]
+fn remesh_sphere() {
+    let sphere = sphere_mesh(Point3::origin(), 1.0);
+    let grid = voxelize(&sphere, 0.1);
+    assert!(f64::abs(grid.volume() - volume(&sphere)) < 0.1);
+    assert!(f64::abs(grid.value_at(Point3::new(0.5, 0.0, 0.0)) + 0.5) < 0.02);
+    assert!(f64::abs(grid.value_at(Point3::new(0.0, 0.5, 0.0)) + 0.5) < 0.02);
+    assert!(f64::abs(grid.value_at(Point3::new(3.0, 0.0, 0.0)) - 2.0) < 0.01);
+
+    let remeshed = grid.to_polygon();
+    assert_eq!(remeshed.shell_condition(), ShellCondition::Closed);
+    assert_eq!(euler_characteristic(&remeshed), 2);
+    assert!(f64::abs(volume(&remeshed) - volume(&sphere)) < 0.05);
+    remeshed.positions().iter().for_each(|pt| {
+        assert!(f64::abs(pt.to_vec().magnitude() - 1.0) < 0.02);
+    });
+}
+
+#[test]
+fn self_intersecting_soup() {
+    // two overlapping cubes
+    let mut soup = cube_mesh(Point3::origin());
+    soup.merge(cube_mesh(Point3::new(0.5, 0.5, 0.5)));
+    let grid = voxelize(&soup, 0.1);
+    assert!(f64::abs(grid.volume() - 1.875) < 0.01);
+
+    let remeshed = grid.to_polygon();
+    assert_eq!(remeshed.shell_condition(), ShellCondition::Closed);
+    assert_eq!(euler_characteristic(&remeshed), 2);
+    assert!(f64::abs(volume(&remeshed) - 1.875) < 0.05);
+
+    // the inverted mesh is also voxelized
+    soup.invert();
+    let inverted = voxelize(&soup, 0.1);
+    assert_eq!(inverted.size(), grid.size());
+    let values = inverted.values().iter().zip(grid.values());
+    values.for_each(|(a, b)| assert!(a.near(b) && (*a < 0.0) == (*b < 0.0)));
+}
+
+#[test]
+fn offset() {
+    let sphere = sphere_mesh(Point3::origin(), 1.0);
+    let grid = voxelize(&sphere, 0.1);
+    for distance in [0.35, -0.35] {
+        let offset = grid.offset(distance);
+        let mesh = offset.to_polygon();
+        assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+        mesh.positions().iter().for_each(|pt| {
+            // the distances out of the original grid are extrapolated
+            assert!(f64::abs(pt.to_vec().magnitude() - 1.0 - distance) < 0.03);
+        });
+    }
+    // the grid is extended for the outward offset
+    let bdb = grid.offset(0.35).bounding_box();
+    assert!(bdb.max()[0] > 1.35 && bdb.min()[0] < -1.35);
+}
+
+#[test]
+fn booleans() {
+    let grid0 = voxelize(&sphere_mesh(Point3::origin(), 1.0), 0.1);
+    let grid1 = voxelize(&sphere_mesh(Point3::new(1.0, 0.25, 0.0), 0.8), 0.1);
+    let union = grid0.union(&grid1);
+    let intersection = grid0.intersection(&grid1);
+    let difference = grid0.difference(&grid1);
+    let (v0, v1) = (grid0.volume(), grid1.volume());
+    // the second grid is not aligned with the first one, and is resampled
+    assert!(f64::abs(union.volume() + intersection.volume() - v0 - v1) < 0.1);
+    assert!(f64::abs(difference.volume() + intersection.volume() - v0) < 0.1);
+    assert!(intersection.volume() > 0.5);
+    for grid in [union, intersection, difference] {
+        let mesh = grid.to_polygon();
+        assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
+        assert!(f64::abs(volume(&mesh) - grid.volume()) < 0.1);
+    }
+}
+
+#[test]
+fn empty_grid() {
+    let grid = voxelize(&cube_mesh(Point3::origin()), 0.0);
+    assert!(grid.is_empty());
+    assert!(grid.bounding_box().is_empty());
+    assert_eq!(grid.volume(), 0.0);
+    assert_eq!(grid.to_polygon().faces().len(), 0);
+    let grid0 = voxelize(&cube_mesh(Point3::origin()), 0.1);
+    assert_eq!(grid.union(&grid0), grid0);
+    assert!(grid.intersection(&grid0).is_empty());
+    assert_eq!(grid0.difference(&grid), grid0);
+}
+
@@ -0,0 +1,269 @@
+use crate::errors::Error;
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+pub use truck_polymesh::PolygonMesh;
+
+/// The shape referred by components of assemblies.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Part {
+    /// solid model
+    Solid(Solid),
+    /// polygon mesh, boxed since it is much larger than a solid
+    Mesh(Box<PolygonMesh>),
+}
+
+/// A node of the tree of assemblies, placing a part and sub-components.
+///
+/// The transform of a component is relative to the parent component.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Component {
+    name: String,
+    transform: Matrix4,
+    part: Option<usize>,
+    metadata: BTreeMap<String, String>,
+    children: Vec<Component>,
+}
+
+/// Assembly, a tree of components placing shared parts.
+///
+/// Components refer parts by the indices in the assembly,
+/// so one part can be placed many times without duplicating its data.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube = builder::tsweep(&f, Vector3::unit_z());
+///
+/// let mut assembly = Assembly::new();
+/// let part = assembly.add_part(cube);
+///
+/// // a sub-assembly with two cubes
+/// let mut pair = Component::new("pair", Matrix4::from_translation(Vector3::new(0.0, 0.0, 5.0)), None);
+/// pair.push_child(Component::new("left", Matrix4::identity(), Some(part)));
+/// pair.push_child(Component::new("right", Matrix4::from_translation(Vector3::unit_x() * 2.0), Some(part)));
+/// assembly.push(pair);
+///
+/// let solids = assembly.flatten_solids();
+/// assert_eq!(solids.len(), 2);
+/// let vertex = &solids[1].boundaries()[0].vertex_iter().next().unwrap();
+/// assert_eq!(vertex.get_point(), Point3::new(2.0, 0.0, 5.0));
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(try_from = "RawAssembly")]
+pub struct Assembly {
+    parts: Vec<Part>,
+    components: Vec<Component>,
+}
+
+#[derive(Deserialize)]
+struct RawAssembly {
+    parts: Vec<Part>,
+    components: Vec<Component>,
+}
+
+impl From<Solid> for Part {
+    #[inline(always)]
+    fn from(solid: Solid) -> Part { Part::Solid(solid) }
+}
+
+impl From<PolygonMesh> for Part {
+    #[inline(always)]
+    fn from(mesh: PolygonMesh) -> Part { Part::Mesh(Box::new(mesh)) }
+}
+
+impl Component {
+    /// Creates a component without children and metadata.
+    #[inline(always)]
+    pub fn new<S: Into<String>>(name: S, transform: Matrix4, part: Option<usize>) -> Component {
+        Component {
+            name: name.into(),
+            transform,
+            part,
+            metadata: BTreeMap::new(),
+            children: Vec::new(),
+        }
+    }
+    /// Returns the name of the component.
+    #[inline(always)]
+    pub fn name(&self) -> &str { &self.name }
+    /// Sets the name of the component.
+    #[inline(always)]
+    pub fn set_name<S: Into<String>>(&mut self, name: S) { self.name = name.into() }
+    /// Returns the transform relative to the parent.
+    #[inline(always)]
+    pub fn transform(&self) -> Matrix4 { self.transform }
+    /// Sets the transform relative to the parent.
+    #[inline(always)]
+    pub fn set_transform(&mut self, transform: Matrix4) { self.transform = transform }
+    /// Returns the index of the placed part.
+    #[inline(always)]
+    pub fn part(&self) -> Option<usize> { self.part }
+    /// Returns the metadata of the component.
+    #[inline(always)]
+    pub fn metadata(&self) -> &BTreeMap<String, String> { &self.metadata }
+    /// Returns the mutable reference to the metadata of the component.
+    #[inline(always)]
+    pub fn metadata_mut(&mut self) -> &mut BTreeMap<String, String> { &mut self.metadata }
+    /// Returns the sub-components.
+    #[inline(always)]
+    pub fn children(&self) -> &Vec<Component> { &self.children }
+    /// Adds a sub-component.
+    #[inline(always)]
+    pub fn push_child(&mut self, child: Component) { self.children.push(child) }
+
+    fn check_parts(&self, len: usize) -> Result<()> {
+        match self.part {
+            Some(idx) if idx >= len => Err(Error::PartIndexOutOfRange(idx, len)),
+            _ => self
+                .children
+                .iter()
+                .try_for_each(|child| child.check_parts(len)),
+        }
+    }
+}
+
+impl Assembly {
+    /// Creates an empty assembly.
+    #[inline(always)]
+    pub fn new() -> Assembly { Assembly::default() }
+
+    /// Creates an assembly by parts and top-level components.
+    /// # Panics
+    /// Panic occurs if a component refers a part out of range.
+    #[inline(always)]
+    pub fn from_parts(parts: Vec<Part>, components: Vec<Component>) -> Assembly {
+        Assembly::try_from_parts(parts, components).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates an assembly by parts and top-level components.
+    /// # Failures
+    /// If a component refers a part out of range, returns `Error::PartIndexOutOfRange`.
+    #[inline(always)]
+    pub fn try_from_parts(parts: Vec<Part>, components: Vec<Component>) -> Result<Assembly> {
+        components
+            .iter()
+            .try_for_each(|component| component.check_parts(parts.len()))?;
+        Ok(Assembly { parts, components })
+    }
+
+    /// Adds a part, and returns its index.
+    #[inline(always)]
+    pub fn add_part<T: Into<Part>>(&mut self, part: T) -> usize {
+        self.parts.push(part.into());
+        self.parts.len() - 1
+    }
+
+    /// Returns the parts.
+    #[inline(always)]
+    pub fn parts(&self) -> &Vec<Part> { &self.parts }
+
+    /// Returns the top-level components.
+    #[inline(always)]
+    pub fn components(&self) -> &Vec<Component> { &self.components }
+
+    /// Adds a top-level component.
+    /// # Panics
+    /// Panic occurs if the component refers a part out of range.
+    #[inline(always)]
+    pub fn push(&mut self, component: Component) {
+        self.try_push(component).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Adds a top-level component.
+    /// # Failures
+    /// If the component refers a part out of range, returns `Error::PartIndexOutOfRange`.
+    #[inline(always)]
+    pub fn try_push(&mut self, component: Component) -> Result<()> {
+        component.check_parts(self.parts.len())?;
+        self.components.push(component);
+        Ok(())
+    }
+
+    /// Returns the depth-first iterator of all components with the global transforms.
+    #[inline(always)]
+    pub fn iter(&self) -> ComponentIter<'_> {
+        ComponentIter {
+            stack: self
+                .components
+                .iter()
+                .rev()
+                .map(|component| (component, Matrix4::identity()))
+                .collect(),
+        }
+    }
+
+    /// Returns the iterator of the placed parts with the global transforms.
+    #[inline(always)]
+    pub fn instances(&self) -> impl Iterator<Item = (&Part, Matrix4)> {
+        self.iter()
+            .filter_map(move |(component, mat)| Some((&self.parts[component.part?], mat)))
+    }
+
+    /// Returns the transformed solids of all placed solid parts. Mesh parts are ignored.
+    pub fn flatten_solids(&self) -> Vec<Solid> {
+        self.instances()
+            .filter_map(|(part, mat)| match part {
+                Part::Solid(solid) => Some(builder::transformed(solid, mat)),
+                Part::Mesh(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the transformed meshes of all placed parts, in the order of [`Assembly::instances`].
+    ///
+    /// Each solid part is meshed only once by `tessellate`, e.g. the triangulation of `truck-meshalgo`.
+    /// The parts placed by no components are not meshed.
+    /// Returns `None` if `tessellate` fails.
+    ///
+    /// [`Assembly::instances`]: ./struct.Assembly.html#method.instances
+    pub fn flatten_meshes<F>(&self, mut tessellate: F) -> Option<Vec<PolygonMesh>>
+    where F: FnMut(&Solid) -> Option<PolygonMesh> {
+        let mut meshed: Vec<Option<PolygonMesh>> = vec![None; self.parts.len()];
+        let mut meshes = Vec::new();
+        for (component, mat) in self.iter() {
+            let idx = match component.part {
+                Some(idx) => idx,
+                None => continue,
+            };
+            let mesh: &PolygonMesh = match (&self.parts[idx], &mut meshed[idx]) {
+                (Part::Mesh(mesh), _) => mesh,
+                (Part::Solid(_), Some(mesh)) => mesh,
+                (Part::Solid(solid), entry) => entry.insert(tessellate(solid)?),
+            };
+            meshes.push(mesh.transformed(mat));
+        }
+        Some(meshes)
+    }
+}
+
+impl TryFrom<RawAssembly> for Assembly {
+    type Error = Error;
+    #[inline(always)]
+    fn try_from(raw: RawAssembly) -> Result<Assembly> {
+        Assembly::try_from_parts(raw.parts, raw.components)
+    }
+}
+
+/// The depth-first iterator of components, created by [`Assembly::iter`].
+///
+/// [`Assembly::iter`]: ./struct.Assembly.html#method.iter
+#[derive(Clone, Debug)]
+pub struct ComponentIter<'a> {
+    stack: Vec<(&'a Component, Matrix4)>,
+}
+
+impl<'a> Iterator for ComponentIter<'a> {
+    type Item = (&'a Component, Matrix4);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (component, parent) = self.stack.pop()?;
+        let mat = parent * component.transform;
+        self.stack
+            .extend(component.children.iter().rev().map(|child| (child, mat)));
+        Some((component, mat))
+    }
+}
@@ -306,6 +306,324 @@ pub fn try_attach_plane(wires: &Vec<Wire>) -> Result<Face> {
     Ok(Face::try_new(wires.clone(), surface)?)
 }
 
+/// Returns the natural boundary of the surface of `face`, the image of the boundary of
+/// the parameter domain, oriented as the boundary of the face.
+///
+/// The parameter domain of a plane is the unit square. The sides of the domain degenerated to
+/// points, e.g. the poles of spheres, are omitted.
+/// # Errors
+/// Returns [`Error::NoNaturalBoundary`] if the natural boundary is not a simple closed wire,
+/// e.g. the surface is a revolution or closed in a parameter direction.
+///
+/// [`Error::NoNaturalBoundary`]: ../errors/enum.Error.html#variant.NoNaturalBoundary
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the square patch trimmed by a triangle
+/// let surface = BSplineSurface::new(
+///     (KnotVec::bezier_knot(1), KnotVec::bezier_knot(1)),
+///     vec![
+///         vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+///         vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+///     ],
+/// );
+/// let v0 = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+/// let v1 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let v2 = builder::vertex(Point3::new(0.0, 1.0, 0.0));
+/// let triangle: Wire = vec![
+///     builder::line(&v0, &v1),
+///     builder::line(&v1, &v2),
+///     builder::line(&v2, &v0),
+/// ].into();
+/// let face = Face::new(vec![triangle], Surface::BSplineSurface(surface));
+///
+/// let boundary = builder::try_natural_boundary(&face).unwrap();
+/// assert_eq!(boundary.len(), 4);
+/// assert!(boundary.is_closed());
+/// assert_eq!(boundary[0].front().get_point(), Point3::new(0.0, 0.0, 0.0));
+/// assert_eq!(boundary[1].front().get_point(), Point3::new(1.0, 0.0, 0.0));
+/// assert_eq!(boundary[2].front().get_point(), Point3::new(1.0, 1.0, 0.0));
+/// ```
+pub fn try_natural_boundary(face: &Face) -> Result<Wire> {
+    let wire = natural_boundary(&face.get_surface())?;
+    Ok(match face.orientation() {
+        true => wire,
+        false => wire.inverse(),
+    })
+}
+
+/// Returns the face of the whole surface of `face`, bounded by the natural boundary,
+/// e.g. for exporting trimmed faces to the formats without trimming.
+/// The orientation of the face is kept, and the vertices and the edges are newly created.
+/// # Errors
+/// Returns [`Error::NoNaturalBoundary`] if the natural boundary is not a simple closed wire.
+/// cf. [`try_natural_boundary`](./fn.try_natural_boundary.html)
+///
+/// [`Error::NoNaturalBoundary`]: ../errors/enum.Error.html#variant.NoNaturalBoundary
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the disk on the plane
+/// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+/// let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+///
+/// let untrimmed = builder::try_untrimmed(&disk).unwrap();
+/// assert_eq!(untrimmed.boundaries().len(), 1);
+/// assert_eq!(untrimmed.boundaries()[0].len(), 4);
+/// assert!(untrimmed.is_geometric_consistent());
+/// let normal = untrimmed.oriented_surface().normal(0.5, 0.5);
+/// assert!(normal.near(&disk.oriented_surface().normal(0.5, 0.5)));
+/// ```
+pub fn try_untrimmed(face: &Face) -> Result<Face> {
+    let surface = face.get_surface();
+    let wire = natural_boundary(&surface)?;
+    let mut untrimmed = Face::try_new(vec![wire], surface)?;
+    if !face.orientation() {
+        untrimmed.invert();
+    }
+    Ok(untrimmed)
+}
+
+/// Returns the wire whose corner at `vertex` is rounded by the circle arc of `radius`.
+/// # Panics
+/// Panic occurs if [`try_fillet_wire`] fails.
+///
+/// [`try_fillet_wire`]: ./fn.try_fillet_wire.html
+#[inline(always)]
+pub fn fillet_wire(wire: &Wire, vertex: &Vertex, radius: f64) -> Wire {
+    try_fillet_wire(wire, vertex, radius).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Returns the wire whose corner at `vertex` is rounded by the circle arc of `radius`.
+///
+/// The corner must be between two lines of the wire. The lines are shortened to the tangent
+/// points of the arc, so a closed wire remains closed and the arc is on the plane of the lines.
+/// A line shorter than the tangent length within `TOLERANCE` is replaced by the arc entirely.
+/// The other edges and vertices of the wire are shared with the original wire.
+/// # Errors
+/// - Returns [`Error::NotCornerOfWire`] if `vertex` is not the end of an edge and the start of
+///   the next edge in the wire.
+/// - Returns [`Error::CornerNotFit`] if the edges at the corner are not lines, are in a straight
+///   line, or are shorter than the tangent length of the arc.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the unit square
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let face = builder::tsweep(&e, Vector3::unit_y());
+/// let square = face.boundaries().pop().unwrap();
+///
+/// let corner = square.front_vertex().unwrap().clone();
+/// let rounded = builder::try_fillet_wire(&square, &corner, 0.25).unwrap();
+/// assert_eq!(rounded.len(), 5);
+/// assert!(rounded.is_closed());
+/// // the arc is tangent to the sides at the distance of the radius from the corner
+/// let arc = rounded
+///     .edge_iter()
+///     .find(|edge| {
+///         let (pt0, pt1) = (edge.front().get_point(), edge.back().get_point());
+///         pt0.to_vec().magnitude().near(&0.25) && pt1.to_vec().magnitude().near(&0.25)
+///     })
+///     .unwrap();
+/// let curve = arc.oriented_curve();
+/// let (t0, t1) = curve.parameter_range();
+/// let center = Point3::new(0.25, 0.25, 0.0);
+/// assert_near!(curve.subs((t0 + t1) / 2.0).distance(center), 0.25);
+/// // still a planar face
+/// assert!(builder::try_attach_plane(&vec![rounded]).is_ok());
+///
+/// // too large radius
+/// assert!(builder::try_fillet_wire(&square, &corner, 2.0).is_err());
+/// ```
+///
+/// [`Error::NotCornerOfWire`]: ../errors/enum.Error.html#variant.NotCornerOfWire
+/// [`Error::CornerNotFit`]: ../errors/enum.Error.html#variant.CornerNotFit
+pub fn try_fillet_wire(wire: &Wire, vertex: &Vertex, radius: f64) -> Result<Wire> {
+    replace_corner(wire, vertex, |corner, dirs| {
+        let cos = dirs[0].dot(dirs[1]);
+        // the half angle of the corner
+        let half = Rad::acos(f64::clamp(cos, -1.0, 1.0)) / 2.0;
+        if half.0.so_small() || (PI / 2.0 - half).0.so_small() || radius < TOLERANCE {
+            return Err(Error::CornerNotFit);
+        }
+        let bisector = (dirs[0] + dirs[1]).normalize();
+        let transit = corner + bisector * (radius / half.sin() - radius);
+        let length = radius / half.tan();
+        Ok((length, Box::new(move |v0, v1| circle_arc(v0, v1, transit))))
+    })
+}
+
+/// Returns the wire whose corner at `vertex` is cut by the line at `distance` from the corner
+/// along the both edges.
+/// # Panics
+/// Panic occurs if [`try_chamfer_wire`] fails.
+///
+/// [`try_chamfer_wire`]: ./fn.try_chamfer_wire.html
+#[inline(always)]
+pub fn chamfer_wire(wire: &Wire, vertex: &Vertex, distance: f64) -> Wire {
+    try_chamfer_wire(wire, vertex, distance).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Returns the wire whose corner at `vertex` is cut by the line at `distance` from the corner
+/// along the both edges.
+///
+/// The corner must be between two lines of the wire, as [`try_fillet_wire`].
+/// # Errors
+/// The same as [`try_fillet_wire`].
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// // the triangle
+/// let v0 = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+/// let v1 = builder::vertex(Point3::new(2.0, 0.0, 0.0));
+/// let v2 = builder::vertex(Point3::new(0.0, 2.0, 0.0));
+/// let triangle: Wire = vec![
+///     builder::line(&v0, &v1),
+///     builder::line(&v1, &v2),
+///     builder::line(&v2, &v0),
+/// ].into();
+///
+/// let chamfered = builder::try_chamfer_wire(&triangle, &v1, 1.0).unwrap();
+/// assert_eq!(chamfered.len(), 4);
+/// assert!(chamfered.is_closed());
+/// assert_near!(chamfered[1].front().get_point(), Point3::new(1.0, 0.0, 0.0));
+/// let point = Point3::new(2.0 - f64::sqrt(0.5), f64::sqrt(0.5), 0.0);
+/// assert_near!(chamfered[1].back().get_point(), point);
+///
+/// // `v1` is a vertex of the wire, but `v0` is not a corner of the open wire.
+/// let open: Wire = vec![triangle[0].clone(), triangle[1].clone()].into();
+/// assert!(builder::try_chamfer_wire(&open, &v1, 0.5).is_ok());
+/// assert!(builder::try_chamfer_wire(&open, &v0, 0.5).is_err());
+/// ```
+///
+/// [`try_fillet_wire`]: ./fn.try_fillet_wire.html
+pub fn try_chamfer_wire(wire: &Wire, vertex: &Vertex, distance: f64) -> Result<Wire> {
+    replace_corner(wire, vertex, |_, dirs| {
+        if dirs[0].dot(dirs[1]).abs().near(&1.0) || distance < TOLERANCE {
+            return Err(Error::CornerNotFit);
+        }
+        Ok((distance, Box::new(line)))
+    })
+}
+
+/// the closure creating the edge of the corner between two vertices
+type CornerEdge<'a> = Box<dyn Fn(&Vertex, &Vertex) -> Edge + 'a>;
+
+/// Replaces the corner at `vertex` by the edge created by `corner_edge`, which returns
+/// the distance of the ends of the edge from the corner, and the edge. `corner_edge` is given
+/// the corner and the unit directions from the corner to the other ends of the lines.
+fn replace_corner<'a, F>(wire: &Wire, vertex: &Vertex, corner_edge: F) -> Result<Wire>
+where F: FnOnce(Point3, [Vector3; 2]) -> Result<(f64, CornerEdge<'a>)> {
+    let len = wire.len();
+    let closed = wire.is_closed();
+    let idx = (0..len)
+        .filter(|i| closed || i + 1 < len)
+        .find(|i| wire[*i].back() == vertex && wire[(i + 1) % len].front() == vertex)
+        .ok_or(Error::NotCornerOfWire)?;
+    let next = (idx + 1) % len;
+    let ends = [wire[idx].front(), wire[next].back()];
+    let corner = vertex.get_point();
+    let is_line = |edge: &Edge| {
+        let (pt0, pt1) = (edge.front().get_point(), edge.back().get_point());
+        let dir = pt1 - pt0;
+        let curve = edge.oriented_curve().lift_up();
+        !dir.so_small()
+            && curve.control_points().iter().all(|pt| {
+                let vec = pt.to_point() - pt0;
+                vec.cross(dir).magnitude() < TOLERANCE * dir.magnitude()
+            })
+    };
+    if !is_line(&wire[idx]) || !is_line(&wire[next]) || ends[0] == ends[1] {
+        return Err(Error::CornerNotFit);
+    }
+    let lengths = [0, 1].map(|i| ends[i].get_point().distance(corner));
+    let dirs = [0, 1].map(|i| (ends[i].get_point() - corner) / lengths[i]);
+    let (distance, corner_edge) = corner_edge(corner, dirs)?;
+    if distance > lengths[0] + TOLERANCE || distance > lengths[1] + TOLERANCE {
+        return Err(Error::CornerNotFit);
+    }
+    // the tangent points, or the other ends of the lines if the lines are consumed
+    let points = [0, 1].map(|i| match distance.near(&lengths[i]) {
+        true => ends[i].clone(),
+        false => Vertex::new(corner + dirs[i] * distance),
+    });
+    let mut edges = Vec::with_capacity(len + 2);
+    for (i, edge) in wire.edge_iter().enumerate() {
+        if i == idx {
+            if points[0] != *ends[0] {
+                edges.push(line(ends[0], &points[0]));
+            }
+            edges.push(corner_edge(&points[0], &points[1]));
+        } else if i == next {
+            if points[1] != *ends[1] {
+                edges.push(line(&points[1], ends[1]));
+            }
+        } else {
+            edges.push(edge.clone());
+        }
+    }
+    Ok(edges.into())
+}
+
+/// the natural boundary oriented counterclockwise in the parameter domain
+fn natural_boundary(surface: &Surface) -> Result<Wire> {
+    let curves = match surface {
+        Surface::Plane(plane) => {
+            let pts = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+                .map(|(u, v)| plane.subs(u, v).to_homogeneous());
+            let curve = |i: usize| {
+                let line = geom_impls::line(pts[i], pts[(i + 1) % 4]);
+                Curve::NURBSCurve(NURBSCurve::new(line))
+            };
+            [curve(0), curve(1), curve(2), curve(3)]
+        }
+        Surface::BSplineSurface(surface) => surface.splitted_boundary().map(Curve::BSplineCurve),
+        Surface::NURBSSurface(surface) => surface.splitted_boundary().map(Curve::NURBSCurve),
+        Surface::RevolutedCurve(_) => return Err(Error::NoNaturalBoundary),
+    };
+    // the corners of the parameter domain, the same vertex for the same point
+    let mut vertices: Vec<Vertex> = Vec::new();
+    let corners: Vec<Vertex> = curves
+        .iter()
+        .map(|curve| {
+            let pt = curve.front();
+            match vertices.iter().find(|v| v.get_point().near(&pt)) {
+                Some(v) => v.clone(),
+                None => {
+                    vertices.push(Vertex::new(pt));
+                    vertices.last().unwrap().clone()
+                }
+            }
+        })
+        .collect();
+    let mut wire = Wire::new();
+    for (i, curve) in curves.iter().enumerate() {
+        let (v0, v1) = (&corners[i], &corners[(i + 1) % 4]);
+        if v0 != v1 {
+            wire.push_back(Edge::new(v0, v1, curve.clone()));
+        } else {
+            let (t0, t1) = curve.parameter_range();
+            let degenerate = (0..=8).all(|j| {
+                let t = t0 + (t1 - t0) * j as f64 / 8.0;
+                curve.subs(t).near(&v0.get_point())
+            });
+            if !degenerate {
+                return Err(Error::NoNaturalBoundary);
+            }
+        }
+    }
+    match wire.len() >= 2 && wire.is_closed() && wire.is_simple() {
+        true => Ok(wire),
+        false => Err(Error::NoNaturalBoundary),
+    }
+}
+
 /// Returns another topology whose points, curves, and surfaces are cloned.
 #[inline(always)]
 pub fn clone<T: Mapped<Point3, Curve, Surface>>(elem: &T) -> T { elem.topological_clone() }
@@ -349,6 +667,34 @@ pub fn scaled<T: Mapped<Point3, Curve, Surface>>(elem: &T, origin: Point3, scala
     transformed(elem, mat2 * mat1 * mat0)
 }
 
+/// Returns a vertex, edge, wire, face, shell or solid modeled in `unit`,
+/// converted into the unit of `context`.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// // a cube with 10 mm edges
+/// let vertex = builder::vertex(Point3::origin());
+/// let line = builder::tsweep(&vertex, Vector3::unit_x() * 10.0);
+/// let square = builder::tsweep(&line, Vector3::unit_y() * 10.0);
+/// let cube = builder::tsweep(&square, Vector3::unit_z() * 10.0);
+///
+/// let context = ModelContext::new(LengthUnit::Meter, 1.0e-9);
+/// let cube = builder::unit_converted(&cube, LengthUnit::Millimeter, &context);
+/// let bdb: BoundingBox<Point3> = cube.boundaries()[0]
+///     .vertex_iter()
+///     .map(|v| v.get_point())
+///     .collect();
+/// assert_near!(bdb.max(), &Point3::new(0.01, 0.01, 0.01));
+/// ```
+#[inline(always)]
+pub fn unit_converted<T: Mapped<Point3, Curve, Surface>>(
+    elem: &T,
+    unit: LengthUnit,
+    context: &ModelContext,
+) -> T {
+    transformed(elem, Matrix4::from_scale(context.scale_from(unit)))
+}
+
 /// Sweeps a vertex, an edge, a wire, a face, or a shell by a vector.
 /// # Examples
 /// ```
@@ -577,6 +923,311 @@ fn whole_rsweep<T: ClosedSweep<Point3, Curve, Surface>>(
     )
 }
 
+/// The frames moving along the path of [`frame_sweep`](./fn.frame_sweep.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SweepFrame {
+    /// The Frenet frame, whose normal directs to the center of the curvature.
+    /// The normal is kept on the straight parts of the path, and flips at the inflection points.
+    Frenet,
+    /// The rotation minimizing frame, which does not rotate around the tangent of the path.
+    RotationMinimizing,
+    /// The frame whose normal is the given up vector projected onto the normal plane of the path.
+    FixedUp(Vector3),
+    /// The frame whose binormal is locked to the given vector, like the section of a road.
+    /// The profile rotates only around the vector, and faces the tangent of the path projected
+    /// onto the plane perpendicular to the vector.
+    Binormal(Vector3),
+}
+
+/// Options of [`frame_sweep`](./fn.frame_sweep.html)
+#[derive(Clone, Copy, Debug)]
+pub struct FrameSweepOptions {
+    /// the frame moving along the path. Default is `SweepFrame::RotationMinimizing`.
+    pub frame: SweepFrame,
+    /// the twist angle around the tangent per unit length of the path, in radians.
+    /// Default is `0.0`.
+    pub twist_rate: f64,
+    /// the number of the steps of the sweep. Default is `16`.
+    pub division: usize,
+}
+
+impl Default for FrameSweepOptions {
+    #[inline(always)]
+    fn default() -> FrameSweepOptions {
+        FrameSweepOptions {
+            frame: SweepFrame::RotationMinimizing,
+            twist_rate: 0.0,
+            division: 16,
+        }
+    }
+}
+
+/// Sweeps a vertex, an edge, a wire, a face, or a shell along the curve of `path`.
+/// # Details
+/// The element is moved by the frame of `options.frame`, twisted around the tangent by
+/// `options.twist_rate`, and keeps its placement relative to the frame at the front of `path`.
+/// The path is divided into `options.division` steps, and each step is connected by a cubic
+/// curve along the path, so the swept surfaces are tangent continuous across the steps and
+/// approximate the exact sweep more closely as the division increases.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use builder::{FrameSweepOptions, SweepFrame};
+/// const PI: Rad<f64> = Rad(std::f64::consts::PI);
+///
+/// // the disk on the plane z = 0
+/// let v = builder::vertex(Point3::new(0.2, 0.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), PI * 2.0);
+/// let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+///
+/// // the path starting at the center of the disk
+/// let path = builder::bezier(
+///     &builder::vertex(Point3::origin()),
+///     &builder::vertex(Point3::new(2.0, 0.0, 2.0)),
+///     vec![Point3::new(0.0, 0.0, 1.0), Point3::new(1.0, 0.0, 2.0)],
+/// );
+/// let options = FrameSweepOptions {
+///     frame: SweepFrame::RotationMinimizing,
+///     twist_rate: 0.5,
+///     division: 8,
+/// };
+/// let pipe: Solid = builder::frame_sweep(&disk, &path, &options);
+/// assert_eq!(pipe.boundaries()[0].shell_condition(), ShellCondition::Closed);
+/// assert!(pipe.is_geometric_consistent());
+/// ```
+pub fn frame_sweep<T: FrameSweep<Point3, Curve, Surface>>(
+    elem: &T,
+    path: &Edge,
+    options: &FrameSweepOptions,
+) -> T::Swept {
+    let division = usize::max(options.division, 1);
+    let curve = path.oriented_curve();
+    let (t0, t1) = curve.parameter_range();
+    let params: Vec<f64> = (0..=division)
+        .map(|k| t0 + (t1 - t0) * k as f64 / division as f64)
+        .collect();
+    let frames = geom_impls::sweep_frames(&curve, options.frame, options.twist_rate, &params);
+    let maps: Vec<[Matrix4; 4]> = frames
+        .windows(2)
+        .map(|frames| geom_impls::hermite_maps(frames[0], frames[1]))
+        .collect();
+    elem.frame_sweep(
+        &|k, pt| maps[k][3].transform_point(*pt),
+        &|k, curve| curve.transformed(maps[k][3]),
+        &|k, surface| surface.transformed(maps[k][3]),
+        &|k, pt, _| {
+            let control_points = maps[k].iter().map(|mat| mat.transform_point(*pt)).collect();
+            Curve::BSplineCurve(BSplineCurve::new(KnotVec::bezier_knot(3), control_points))
+        },
+        &|k, curve, _| {
+            let curve = curve.clone().lift_up();
+            let control_points = curve
+                .control_points()
+                .iter()
+                .map(|pt| maps[k].iter().map(|mat| mat * pt).collect())
+                .collect();
+            let knot_vecs = (curve.knot_vec().clone(), KnotVec::bezier_knot(3));
+            Surface::NURBSSurface(NURBSSurface::new(BSplineSurface::new(
+                knot_vecs,
+                control_points,
+            )))
+        },
+        division,
+    )
+}
+
+/// Sweeps the closed planar `profiles` placed along the curve of `path`, interpolating
+/// the profiles between them.
+/// # Details
+/// Each profile is placed at the nearest point of `path` from its center, and the profiles have to
+/// be in order along `path`. The shapes of the profiles relative to the frames of
+/// `options.frame` at their places are interpolated smoothly, and moved along `path` by
+/// the frames twisted by `options.twist_rate`. The sweep is from the first profile to
+/// the last profile, and each span between adjacent profiles is divided into `options.division`
+/// steps. The profiles are reproduced in the result, and the ends are capped by planes.
+///
+/// The `i`-th edges of the profiles make one side of the result, so the profiles must have
+/// the same number of edges whose vertices correspond to each other.
+/// # Failures
+/// - Returns [`Error::IncompatibleProfiles`] if there are less than two profiles, or the profiles
+///   are not closed, or the numbers of edges of the profiles are different.
+/// - Returns [`Error::ProfilesNotInOrder`] if the profiles are not in order along `path`.
+/// - Returns [`Error::WireNotInOnePlane`] if the first or the last profile is not planar.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use builder::FrameSweepOptions;
+/// const PI: Rad<f64> = Rad(std::f64::consts::PI);
+///
+/// // the duct whose radius changes from 0.5 to 1.0, and to 0.5
+/// let circle = |radius: f64, z: f64| {
+///     let v = builder::vertex(Point3::new(radius, 0.0, z));
+///     builder::rsweep(&v, Point3::new(0.0, 0.0, z), Vector3::unit_z(), PI * 2.0)
+/// };
+/// let profiles = vec![circle(0.5, 0.0), circle(1.0, 1.0), circle(0.5, 2.0)];
+/// let path = builder::line(
+///     &builder::vertex(Point3::origin()),
+///     &builder::vertex(Point3::new(0.0, 0.0, 2.0)),
+/// );
+/// let options = FrameSweepOptions::default();
+/// let duct = builder::try_multi_section_sweep(&profiles, &path, &options).unwrap();
+/// assert_eq!(duct.boundaries()[0].shell_condition(), ShellCondition::Closed);
+/// assert!(duct.is_geometric_consistent());
+/// ```
+///
+/// [`Error::IncompatibleProfiles`]: ../errors/enum.Error.html#variant.IncompatibleProfiles
+/// [`Error::ProfilesNotInOrder`]: ../errors/enum.Error.html#variant.ProfilesNotInOrder
+/// [`Error::WireNotInOnePlane`]: ../errors/enum.Error.html#variant.WireNotInOnePlane
+pub fn try_multi_section_sweep(
+    profiles: &[Wire],
+    path: &Edge,
+    options: &FrameSweepOptions,
+) -> Result<Solid> {
+    let len = profiles.first().map_or(0, |wire| wire.len());
+    let compatible = profiles.len() > 1
+        && profiles.iter().all(|wire| wire.len() == len && wire.is_closed());
+    if !compatible {
+        return Err(Error::IncompatibleProfiles);
+    }
+    let curve = path.oriented_curve();
+    let params: Vec<f64> = profiles
+        .iter()
+        .map(|wire| {
+            let sum = wire
+                .vertex_iter()
+                .fold(Vector3::zero(), |sum, v| sum + v.get_point().to_vec());
+            geom_impls::nearest_parameter(&curve, Point3::from_vec(sum / len as f64))
+        })
+        .collect();
+    if params.windows(2).any(|t| t[0] >= t[1]) {
+        return Err(Error::ProfilesNotInOrder);
+    }
+    let division = usize::max(options.division, 1);
+    let mut steps: Vec<f64> = params
+        .windows(2)
+        .flat_map(|t| (0..division).map(move |k| t[0] + (t[1] - t[0]) * k as f64 / division as f64))
+        .collect();
+    steps.push(params[params.len() - 1]);
+    let frames = geom_impls::sweep_frames(&curve, options.frame, options.twist_rate, &steps);
+
+    // the compatible curves of the profiles in the coordinates of the frames at the profiles
+    let sections: Vec<Vec<BSplineCurve<Vector4>>> = (0..len)
+        .map(|i| {
+            let mut curves: Vec<_> = profiles
+                .iter()
+                .map(|wire| wire[i].oriented_curve().lift_up())
+                .collect();
+            for _ in 0..2 {
+                let (first, others) = curves.split_first_mut().unwrap();
+                others.iter_mut().for_each(|curve| {
+                    first.syncro_degree(curve);
+                    first.syncro_knots(curve);
+                });
+            }
+            curves.iter_mut().enumerate().for_each(|(j, curve)| {
+                let inverse = frames[j * division].1.invert().unwrap();
+                curve.transform_control_points(|pt| *pt = inverse * *pt);
+            });
+            curves
+        })
+        .collect();
+    // the control points of the `i`-th curve at each step and their derivatives
+    let rows: Vec<Vec<Vec<(Vector4, Vector4)>>> = frames
+        .iter()
+        .enumerate()
+        .map(|(k, (t, frame, der))| {
+            let j = usize::min(k / division, params.len() - 2);
+            sections
+                .iter()
+                .map(|curves| {
+                    (0..curves[0].control_points().len())
+                        .map(|q| {
+                            let values: Vec<Vector4> =
+                                curves.iter().map(|curve| *curve.control_point(q)).collect();
+                            let (pt, pt_der) = geom_impls::catmull_rom(&params, &values, j, *t);
+                            (frame * pt, der * pt + frame * pt_der)
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+    // the cubic Bezier control points from the `k`-th step to the next step
+    let bezier = |k: usize, (pt0, der0): (Vector4, Vector4), (pt1, der1): (Vector4, Vector4)| {
+        let h = (frames[k + 1].0 - frames[k].0) / 3.0;
+        vec![pt0, pt0 + der0 * h, pt1 - der1 * h, pt1]
+    };
+
+    let vertices: Vec<Vec<Vertex>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cps| vertex(Point3::from_homogeneous(cps[0].0))).collect())
+        .collect();
+    let edges: Vec<Vec<Edge>> = rows
+        .iter()
+        .zip(&vertices)
+        .map(|(row, vertices)| {
+            row.iter()
+                .enumerate()
+                .map(|(i, cps)| {
+                    let knot_vec = sections[i][0].knot_vec().clone();
+                    let control_points = cps.iter().map(|(pt, _)| *pt).collect();
+                    let curve = NURBSCurve::new(BSplineCurve::new(knot_vec, control_points));
+                    let (v0, v1) = (&vertices[i], &vertices[(i + 1) % len]);
+                    Edge::new(v0, v1, Curve::NURBSCurve(curve))
+                })
+                .collect()
+        })
+        .collect();
+    let lines: Vec<Vec<Edge>> = (0..steps.len() - 1)
+        .map(|k| {
+            (0..len)
+                .map(|i| {
+                    let control_points = bezier(k, rows[k][i][0], rows[k + 1][i][0]);
+                    let curve = BSplineCurve::new(KnotVec::bezier_knot(3), control_points);
+                    let curve = Curve::NURBSCurve(NURBSCurve::new(curve));
+                    Edge::new(&vertices[k][i], &vertices[k + 1][i], curve)
+                })
+                .collect()
+        })
+        .collect();
+
+    let first: Wire = edges[0].iter().cloned().collect();
+    let last: Wire = edges[edges.len() - 1].iter().cloned().collect();
+    let mut shell: Shell = vec![try_attach_plane(&vec![first])?.inverse()].into();
+    for k in 0..steps.len() - 1 {
+        for i in 0..len {
+            let control_points = rows[k][i]
+                .iter()
+                .zip(&rows[k + 1][i])
+                .map(|(pt0, pt1)| bezier(k, *pt0, *pt1))
+                .collect();
+            let knot_vecs = (sections[i][0].knot_vec().clone(), KnotVec::bezier_knot(3));
+            let surface = BSplineSurface::new(knot_vecs, control_points);
+            let wire: Wire = vec![
+                edges[k][i].clone(),
+                lines[k][(i + 1) % len].clone(),
+                edges[k + 1][i].inverse(),
+                lines[k][i].inverse(),
+            ]
+            .into();
+            let surface = Surface::NURBSSurface(NURBSSurface::new(surface));
+            shell.push(Face::new(vec![wire], surface));
+        }
+    }
+    shell.push(try_attach_plane(&vec![last])?);
+    // the solid is inverted if the first profile faces the opposite of the path
+    let normal = match shell[0].oriented_surface() {
+        Surface::Plane(plane) => plane.normal(),
+        _ => unreachable!(),
+    };
+    if normal.dot(frames[0].1[2].truncate()) > 0.0 {
+        shell.iter_mut().for_each(|face| {
+            face.invert();
+        });
+    }
+    Ok(Solid::try_new(vec![shell])?)
+}
+
 #[test]
 fn partial_torus() {
     let v = vertex(Point3::new(0.5, 0.0, 0.0));
@@ -11,6 +11,61 @@ pub enum Error {
     /// cf. [`builder::try_attach_plane`](../builder/fn.try_attach_plane.html)
     #[error("cannot attach a plane to a wire that is not on one plane.")]
     WireNotInOnePlane,
+    /// a component of assemblies refers a part out of range.
+    /// cf. [`Assembly::try_push`](../assembly/struct.Assembly.html#method.try_push)
+    #[error("the index {0} of the part is out of range of {1} parts.")]
+    PartIndexOutOfRange(usize, usize),
+    /// the face to be marked is not a face of the solid.
+    /// cf. [`marking::emboss`](../marking/fn.emboss.html)
+    #[error("the face is not a face of the solid.")]
+    FaceNotInSolid,
+    /// tried to mark a face whose surface is not a plane.
+    /// cf. [`marking::emboss`](../marking/fn.emboss.html)
+    #[error("cannot mark a face that is not planar.")]
+    NotPlanarFace,
+    /// the profiles of a sweep are less than two, not closed, or have different numbers of edges.
+    /// cf. [`builder::try_multi_section_sweep`](../builder/fn.try_multi_section_sweep.html)
+    #[error("the profiles must be at least two closed wires with the same number of edges.")]
+    IncompatibleProfiles,
+    /// the profiles of a sweep are not placed in order along the path.
+    /// cf. [`builder::try_multi_section_sweep`](../builder/fn.try_multi_section_sweep.html)
+    #[error("the profiles are not placed in order along the path.")]
+    ProfilesNotInOrder,
+    /// the natural boundary of the surface is not a simple closed wire, e.g. the surface is
+    /// closed in a parameter direction.
+    /// cf. [`builder::try_natural_boundary`](../builder/fn.try_natural_boundary.html)
+    #[error("the natural boundary of the surface is not a simple closed wire.")]
+    NoNaturalBoundary,
+    /// the boundaries of the result of a boolean operation of planar regions are not closed.
+    /// cf. [`planar_boolean::union`](../planar_boolean/fn.union.html)
+    #[error("the boundaries of the result of the planar boolean operation are not closed.")]
+    PlanarBooleanFailed,
+    /// the vertex is not a corner between two edges of the wire.
+    /// cf. [`builder::try_fillet_wire`](../builder/fn.try_fillet_wire.html)
+    #[error("the vertex is not a corner between two edges of the wire.")]
+    NotCornerOfWire,
+    /// the edges at the corner are not lines, are in a straight line, or are too short for
+    /// the fillet or the chamfer.
+    /// cf. [`builder::try_fillet_wire`](../builder/fn.try_fillet_wire.html)
+    #[error("the fillet or the chamfer does not fit the corner.")]
+    CornerNotFit,
+    /// the profile of the base flange of a sheet metal is not a closed wire of lines.
+    /// cf. [`SheetMetal::try_base_flange`](../sheet_metal/struct.SheetMetal.html#method.try_base_flange)
+    #[error("the profile of the sheet metal is not a closed wire of lines.")]
+    NotSheetProfile,
+    /// the edge is not an edge of the profile of the base flange, or is already bent.
+    /// cf. [`SheetMetal::try_add_bend`](../sheet_metal/struct.SheetMetal.html#method.try_add_bend)
+    #[error("the edge is not a free edge of the profile of the sheet metal.")]
+    EdgeNotBendable,
+    /// the thickness or the parameters of a bend of a sheet metal are out of range.
+    /// cf. [`Bend`](../sheet_metal/struct.Bend.html)
+    #[error("the thickness or the parameters of the bend are out of range.")]
+    InvalidSheetParameter,
+    /// the polygon mesh is not a closed and consistently oriented manifold, or a merged face
+    /// has a degenerate boundary.
+    /// cf. [`faceted::try_solid_from_mesh`](../faceted/fn.try_solid_from_mesh.html)
+    #[error("the polygon mesh is not a closed and oriented manifold of planar facets.")]
+    NotFacetedSolid,
 }
 
 #[test]
@@ -19,5 +74,18 @@ fn print_messages() {
     writeln!(&mut std::io::stderr(), "****** test of the expressions of error messages ******\n").unwrap();
     writeln!(&mut std::io::stderr(), "{}\n", Error::FromTopology(truck_topology::errors::Error::SameVertex)).unwrap();
     writeln!(&mut std::io::stderr(), "{}\n", Error::WireNotInOnePlane).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::PartIndexOutOfRange(3, 2)).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::FaceNotInSolid).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::NotPlanarFace).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::IncompatibleProfiles).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::ProfilesNotInOrder).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::NoNaturalBoundary).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::PlanarBooleanFailed).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::NotCornerOfWire).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::CornerNotFit).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::NotSheetProfile).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::EdgeNotBendable).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::InvalidSheetParameter).unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::NotFacetedSolid).unwrap();
     writeln!(&mut std::io::stderr(), "*******************************************************").unwrap();
 }
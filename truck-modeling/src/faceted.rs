@@ -0,0 +1,350 @@
+use crate::errors::Error;
+use crate::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use truck_polymesh::PolygonMesh;
+
+/// Converts a polygon mesh of planar facets into a solid with planar faces and straight edges.
+///
+/// The positions closer than `tolerance` are merged, and the polygons are divided into
+/// triangles. The adjacent triangles whose normals have the same direction and whose vertices
+/// are within `tolerance` from a common plane are merged into one planar face, and the vertices
+/// of the boundaries between two faces lying on a straight line are removed, so that each
+/// straight edge of the model becomes one line. The faces share the vertices and the edges,
+/// and each connected component of the mesh becomes a boundary shell of the solid.
+///
+/// The mesh must be closed and consistently oriented by the outward normals, as STL files of
+/// prismatic parts. The attributes other than the positions are ignored.
+/// # Errors
+/// Returns [`Error::NotFacetedSolid`] if an edge of the triangles is not shared by exactly two
+/// triangles with the opposite directions, or a merged face has a degenerate boundary.
+/// Returns [`Error::FromTopology`] if the faces do not make closed manifold shells.
+///
+/// [`Error::NotFacetedSolid`]: ../errors/enum.Error.html#variant.NotFacetedSolid
+/// [`Error::FromTopology`]: ../errors/enum.Error.html#variant.FromTopology
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_polymesh::Faces;
+///
+/// // the unit cube whose sides are divided into two triangles
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(1.0, 0.0, 1.0),
+///     Point3::new(1.0, 1.0, 1.0),
+///     Point3::new(0.0, 1.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[
+///     [0, 2, 1], [0, 3, 2], [4, 5, 6], [4, 6, 7],
+///     [0, 1, 5], [0, 5, 4], [1, 2, 6], [1, 6, 5],
+///     [2, 3, 7], [2, 7, 6], [3, 0, 4], [3, 4, 7],
+/// ]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let solid = faceted::try_solid_from_mesh(&mesh, 1.0e-6).unwrap();
+/// let shell = &solid.boundaries()[0];
+/// assert_eq!(shell.len(), 6);
+/// assert_eq!(shell.edge_iter().count(), 24);
+/// assert_eq!(shell.vertex_iter().count(), 24);
+/// assert!(shell
+///     .face_iter()
+///     .all(|face| matches!(face.get_surface(), Surface::Plane(_))));
+/// ```
+pub fn try_solid_from_mesh(mesh: &PolygonMesh, tolerance: f64) -> Result<Solid> {
+    let (points, triangles) = merged_triangles(mesh, tolerance);
+    let mut directed = HashMap::new();
+    for (i, tri) in triangles.iter().enumerate() {
+        for j in 0..3 {
+            if directed.insert((tri[j], tri[(j + 1) % 3]), i).is_some() {
+                return Err(Error::NotFacetedSolid);
+            }
+        }
+    }
+    if directed.keys().any(|(a, b)| !directed.contains_key(&(*b, *a))) {
+        return Err(Error::NotFacetedSolid);
+    }
+    let regions = coplanar_regions(&points, &triangles, &directed, tolerance);
+    let corners = corner_vertices(&points, &directed, &regions, tolerance);
+    let vertices: HashMap<usize, Vertex> = corners
+        .iter()
+        .map(|i| (*i, builder::vertex(points[*i])))
+        .collect();
+    let mut edges = HashMap::new();
+    let mut faces = Vec::with_capacity(regions.count);
+    for region in 0..regions.count {
+        let loops = boundary_loops(&triangles, &directed, &regions, region)?;
+        let normal = regions.normals[region];
+        let mut wires = Vec::with_capacity(loops.len());
+        for lp in loops {
+            wires.push(loop_wire(&lp, &corners, &vertices, &mut edges)?);
+        }
+        wires.sort_by(|wire0, wire1| {
+            let area0 = wire_area(wire0, normal);
+            let area1 = wire_area(wire1, normal);
+            area1.partial_cmp(&area0).unwrap()
+        });
+        let plane = fitting_plane(&wires, normal);
+        faces.push(Face::try_new(wires, Surface::Plane(plane))?);
+    }
+    let shell: Shell = faces.into();
+    Ok(Solid::try_new(shell.connected_components())?)
+}
+
+/// the merged positions and the triangles of the polygons without repeated vertices
+fn merged_triangles(mesh: &PolygonMesh, tolerance: f64) -> (Vec<Point3>, Vec<[usize; 3]>) {
+    let cell = |pt: Point3| {
+        let cell = pt / tolerance;
+        [
+            cell[0].floor() as i64,
+            cell[1].floor() as i64,
+            cell[2].floor() as i64,
+        ]
+    };
+    let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    let mut points: Vec<Point3> = Vec::new();
+    let index: Vec<usize> = mesh
+        .positions()
+        .iter()
+        .map(|pt| {
+            let c = cell(*pt);
+            let near = (0..27).find_map(|k| {
+                let key = [c[0] + k % 3 - 1, c[1] + k / 3 % 3 - 1, c[2] + k / 9 - 1];
+                grid.get(&key)?
+                    .iter()
+                    .find(|i| points[**i].distance(*pt) < tolerance)
+                    .copied()
+            });
+            near.unwrap_or_else(|| {
+                grid.entry(c).or_default().push(points.len());
+                points.push(*pt);
+                points.len() - 1
+            })
+        })
+        .collect();
+    let triangles = mesh
+        .face_iter()
+        .flat_map(|face| {
+            let face: Vec<usize> = face.iter().map(|v| index[v.pos]).collect();
+            (2..face.len())
+                .map(move |i| [face[0], face[i - 1], face[i]])
+                .collect::<Vec<_>>()
+        })
+        .filter(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0])
+        .collect();
+    (points, triangles)
+}
+
+fn triangle_normal(points: &[Point3], tri: &[usize; 3]) -> Vector3 {
+    (points[tri[1]] - points[tri[0]]).cross(points[tri[2]] - points[tri[0]])
+}
+
+struct Regions {
+    count: usize,
+    /// the index of the region of each triangle
+    index: Vec<usize>,
+    /// the unit normals of the regions
+    normals: Vec<Vector3>,
+}
+
+/// Divides the triangles into the connected regions on the planes.
+fn coplanar_regions(
+    points: &[Point3],
+    triangles: &[[usize; 3]],
+    directed: &HashMap<(usize, usize), usize>,
+    tolerance: f64,
+) -> Regions {
+    let normals: Vec<Vector3> = triangles
+        .iter()
+        .map(|tri| triangle_normal(points, tri))
+        .collect();
+    let mut seeds: Vec<usize> = (0..triangles.len()).collect();
+    seeds.sort_by(|i, j| {
+        let (a, b) = (normals[*i].magnitude2(), normals[*j].magnitude2());
+        b.partial_cmp(&a).unwrap()
+    });
+    let mut index = vec![usize::MAX; triangles.len()];
+    let mut region_normals = Vec::new();
+    for seed in seeds {
+        if index[seed] != usize::MAX {
+            continue;
+        }
+        let region = region_normals.len();
+        let (origin, normal) = (points[triangles[seed][0]], normals[seed].normalize());
+        let mut sum = Vector3::zero();
+        let mut queue: VecDeque<usize> = vec![seed].into();
+        index[seed] = region;
+        while let Some(i) = queue.pop_front() {
+            sum += normals[i];
+            let tri = &triangles[i];
+            for j in 0..3 {
+                let k = directed[&(tri[(j + 1) % 3], tri[j])];
+                let on_plane = triangles[k]
+                    .iter()
+                    .all(|v| (points[*v] - origin).dot(normal).abs() < tolerance);
+                let same_side = normals[k].magnitude() < tolerance * tolerance
+                    || normals[k].dot(normal) > 0.0;
+                if index[k] == usize::MAX && on_plane && same_side {
+                    index[k] = region;
+                    queue.push_back(k);
+                }
+            }
+        }
+        region_normals.push(match sum.so_small() {
+            true => normal,
+            false => sum.normalize(),
+        });
+    }
+    Regions {
+        count: region_normals.len(),
+        index,
+        normals: region_normals,
+    }
+}
+
+/// Returns the vertices of the boundaries of regions which are not in the middle of
+/// straight boundaries between two regions.
+fn corner_vertices(
+    points: &[Point3],
+    directed: &HashMap<(usize, usize), usize>,
+    regions: &Regions,
+    tolerance: f64,
+) -> HashSet<usize> {
+    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    directed.iter().for_each(|((a, b), i)| {
+        let j = directed[&(*b, *a)];
+        if a < b && regions.index[*i] != regions.index[j] {
+            adjacency.entry(*a).or_default().push(*b);
+            adjacency.entry(*b).or_default().push(*a);
+        }
+    });
+    adjacency
+        .into_iter()
+        .filter(|(v, adjacent)| {
+            if adjacent.len() != 2 {
+                return true;
+            }
+            let (p, a, b) = (points[*v], points[adjacent[0]], points[adjacent[1]]);
+            let dir = b - a;
+            let distance = (p - a).cross(dir).magnitude() / dir.magnitude();
+            distance >= tolerance || (a - p).dot(b - p) >= 0.0
+        })
+        .map(|(v, _)| v)
+        .collect()
+}
+
+/// the closed loops of the indices of the points on the boundary of the region
+fn boundary_loops(
+    triangles: &[[usize; 3]],
+    directed: &HashMap<(usize, usize), usize>,
+    regions: &Regions,
+    region: usize,
+) -> Result<Vec<Vec<usize>>> {
+    let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+    triangles
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| regions.index[*i] == region)
+        .for_each(|(_, tri)| {
+            for j in 0..3 {
+                let (a, b) = (tri[j], tri[(j + 1) % 3]);
+                if regions.index[directed[&(b, a)]] != region {
+                    next.entry(a).or_default().push(b);
+                }
+            }
+        });
+    let mut starts: Vec<usize> = next.keys().copied().collect();
+    starts.sort_unstable();
+    let mut loops = Vec::new();
+    for start in starts {
+        while matches!(next.get(&start), Some(vec) if !vec.is_empty()) {
+            let mut lp = vec![start];
+            let mut current = start;
+            loop {
+                current = match next.get_mut(&current).and_then(Vec::pop) {
+                    Some(got) => got,
+                    None => return Err(Error::NotFacetedSolid),
+                };
+                if current == start {
+                    break;
+                }
+                lp.push(current);
+            }
+            loops.push(lp);
+        }
+    }
+    Ok(loops)
+}
+
+/// Returns the wire of the loop whose vertices and edges are shared with the other faces.
+fn loop_wire(
+    lp: &[usize],
+    corners: &HashSet<usize>,
+    vertices: &HashMap<usize, Vertex>,
+    edges: &mut HashMap<(usize, usize), Edge>,
+) -> Result<Wire> {
+    let first = lp
+        .iter()
+        .position(|v| corners.contains(v))
+        .ok_or(Error::NotFacetedSolid)?;
+    let lp: Vec<usize> = lp[first..].iter().chain(&lp[..first]).copied().collect();
+    let mut splits: Vec<usize> = (0..lp.len()).filter(|i| corners.contains(&lp[*i])).collect();
+    if splits.len() < 3 {
+        return Err(Error::NotFacetedSolid);
+    }
+    splits.push(lp.len());
+    let mut wire = Wire::with_capacity(splits.len() - 1);
+    for split in splits.windows(2) {
+        let (start, end) = (lp[split[0]], lp[split[1] % lp.len()]);
+        let (second, last) = (lp[(split[0] + 1) % lp.len()], lp[split[1] - 1]);
+        // the edges are keyed by the first steps along the boundaries of the triangles
+        let edge = match edges.get(&(start, second)) {
+            Some(edge) => edge.clone(),
+            None => {
+                let edge = builder::line(&vertices[&start], &vertices[&end]);
+                edges.insert((end, last), edge.inverse());
+                edge
+            }
+        };
+        wire.push_back(edge);
+    }
+    Ok(wire)
+}
+
+/// twice the signed area of the wire projected to the plane of `normal`
+fn wire_area(wire: &Wire, normal: Vector3) -> f64 {
+    let pts: Vec<Point3> = wire.vertex_iter().map(|v| v.get_point()).collect();
+    (0..pts.len()).fold(0.0, |sum, i| {
+        let (p, q) = (pts[i], pts[(i + 1) % pts.len()]);
+        sum + p.to_vec().cross(q.to_vec()).dot(normal)
+    })
+}
+
+/// Returns the plane whose normal is `normal` and whose parameters of the vertices of `wires`
+/// are in the unit square.
+fn fitting_plane(wires: &[Wire], normal: Vector3) -> Plane {
+    let a = match (normal[2].abs() - 1.0).so_small() {
+        true => Vector3::new(0.0, normal[2], -normal[1]).normalize(),
+        false => Vector3::new(normal[1], -normal[0], 0.0).normalize(),
+    };
+    let b = normal.cross(a);
+    let pts: Vec<Point3> = wires
+        .iter()
+        .flat_map(Wire::vertex_iter)
+        .map(|v| v.get_point())
+        .collect();
+    let height = pts.iter().map(|pt| pt.to_vec().dot(normal)).sum::<f64>() / pts.len() as f64;
+    let bnd_box: BoundingBox<Point2> = pts
+        .iter()
+        .map(|pt| Point2::new(pt.to_vec().dot(a), pt.to_vec().dot(b)))
+        .collect();
+    let (min, max) = (bnd_box.min(), bnd_box.max());
+    let origin = Point3::from_vec(a * min[0] + b * min[1] + normal * height);
+    Plane::new(
+        origin,
+        origin + a * (max[0] - min[0]),
+        origin + b * (max[1] - min[1]),
+    )
+}
@@ -0,0 +1,192 @@
+use crate::topo_impls::*;
+use crate::topo_traits::*;
+use truck_topology::*;
+
+impl<P: Clone, C: Clone, S: Clone> FrameSweep<P, C, S> for Vertex<P> {
+    type Swept = Wire<P, C>;
+    fn frame_sweep<
+        FP: Fn(usize, &P) -> P,
+        FC: Fn(usize, &C) -> C,
+        FS: Fn(usize, &S) -> S,
+        CP: Fn(usize, &P, &P) -> C,
+        CE: Fn(usize, &C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        _: &FC,
+        _: &FS,
+        connect_points: &CP,
+        _: &CE,
+        division: usize,
+    ) -> Self::Swept {
+        let mut wire = Wire::new();
+        let mut vertex = self.clone();
+        for k in 0..division {
+            let new_vertex = vertex.mapped(&|pt: &P| point_mapping(k, pt));
+            wire.push_back(connect_vertices(
+                &vertex,
+                &new_vertex,
+                &|pt0: &P, pt1: &P| connect_points(k, pt0, pt1),
+            ));
+            vertex = new_vertex;
+        }
+        wire
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> FrameSweep<P, C, S> for Edge<P, C> {
+    type Swept = Shell<P, C, S>;
+    fn frame_sweep<
+        FP: Fn(usize, &P) -> P,
+        FC: Fn(usize, &C) -> C,
+        FS: Fn(usize, &S) -> S,
+        CP: Fn(usize, &P, &P) -> C,
+        CE: Fn(usize, &C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        _: &FS,
+        connect_points: &CP,
+        connect_curves: &CE,
+        division: usize,
+    ) -> Self::Swept {
+        let mut shell = Shell::new();
+        let mut edge = self.clone();
+        for k in 0..division {
+            let new_edge =
+                edge.mapped(&|pt: &P| point_mapping(k, pt), &|c: &C| curve_mapping(k, c));
+            shell.push(connect_edges(
+                &edge,
+                &new_edge,
+                &|pt0: &P, pt1: &P| connect_points(k, pt0, pt1),
+                &|c0: &C, c1: &C| connect_curves(k, c0, c1),
+            ));
+            edge = new_edge;
+        }
+        shell
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> FrameSweep<P, C, S> for Wire<P, C> {
+    type Swept = Shell<P, C, S>;
+    fn frame_sweep<
+        FP: Fn(usize, &P) -> P,
+        FC: Fn(usize, &C) -> C,
+        FS: Fn(usize, &S) -> S,
+        CP: Fn(usize, &P, &P) -> C,
+        CE: Fn(usize, &C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        _: &FS,
+        connect_points: &CP,
+        connect_curves: &CE,
+        division: usize,
+    ) -> Self::Swept {
+        let mut shell = Shell::new();
+        let mut wire = self.clone();
+        for k in 0..division {
+            let new_wire =
+                wire.mapped(&|pt: &P| point_mapping(k, pt), &|c: &C| curve_mapping(k, c));
+            shell.extend(connect_wires(
+                &wire,
+                &new_wire,
+                &|pt0: &P, pt1: &P| connect_points(k, pt0, pt1),
+                &|c0: &C, c1: &C| connect_curves(k, c0, c1),
+            ));
+            wire = new_wire;
+        }
+        shell
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> FrameSweep<P, C, S> for Face<P, C, S> {
+    type Swept = Solid<P, C, S>;
+    fn frame_sweep<
+        FP: Fn(usize, &P) -> P,
+        FC: Fn(usize, &C) -> C,
+        FS: Fn(usize, &S) -> S,
+        CP: Fn(usize, &P, &P) -> C,
+        CE: Fn(usize, &C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        surface_mapping: &FS,
+        connect_points: &CP,
+        connect_curves: &CE,
+        division: usize,
+    ) -> Self::Swept {
+        let mut shell = Shell::new();
+        shell.push(self.inverse());
+        let mut face_cursor = self.clone();
+        for k in 0..division {
+            let seiling = face_cursor.mapped(
+                &|pt: &P| point_mapping(k, pt),
+                &|c: &C| curve_mapping(k, c),
+                &|s: &S| surface_mapping(k, s),
+            );
+            let biter0 = face_cursor.boundary_iters().into_iter().flatten();
+            let biter1 = seiling.boundary_iters().into_iter().flatten();
+            shell.extend(connect_raw_wires(
+                biter0,
+                biter1,
+                &|pt0: &P, pt1: &P| connect_points(k, pt0, pt1),
+                &|c0: &C, c1: &C| connect_curves(k, c0, c1),
+            ));
+            face_cursor = seiling;
+        }
+        shell.push(face_cursor);
+        Solid::debug_new(vec![shell])
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> FrameSweep<P, C, S> for Shell<P, C, S> {
+    type Swept = Vec<Result<Solid<P, C, S>>>;
+    fn frame_sweep<
+        FP: Fn(usize, &P) -> P,
+        FC: Fn(usize, &C) -> C,
+        FS: Fn(usize, &S) -> S,
+        CP: Fn(usize, &P, &P) -> C,
+        CE: Fn(usize, &C, &C) -> S,
+    >(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        surface_mapping: &FS,
+        connect_points: &CP,
+        connect_curves: &CE,
+        division: usize,
+    ) -> Self::Swept {
+        self.connected_components()
+            .into_iter()
+            .map(move |shell| {
+                let mut bdry = Shell::new();
+                bdry.extend(shell.face_iter().map(|face| face.inverse()));
+                let mut shell_cursor = shell.clone();
+                for k in 0..division {
+                    let seiling = shell_cursor.mapped(
+                        &|pt: &P| point_mapping(k, pt),
+                        &|c: &C| curve_mapping(k, c),
+                        &|s: &S| surface_mapping(k, s),
+                    );
+                    let bdries0 = shell_cursor.extract_boundaries();
+                    let bdries1 = seiling.extract_boundaries();
+                    let biter0 = bdries0.iter().flat_map(Wire::edge_iter);
+                    let biter1 = bdries1.iter().flat_map(Wire::edge_iter);
+                    bdry.extend(connect_wires(
+                        biter0,
+                        biter1,
+                        &|pt0: &P, pt1: &P| connect_points(k, pt0, pt1),
+                        &|c0: &C, c1: &C| connect_curves(k, c0, c1),
+                    ));
+                    shell_cursor = seiling;
+                }
+                bdry.append(&mut shell_cursor);
+                Solid::try_new(vec![bdry])
+            })
+            .collect()
+    }
+}
@@ -71,6 +71,185 @@ pub(super) fn circle_arc(
     curve
 }
 
+/// the number of the sub-steps of each step for computing the frames of sweeps
+const FRAME_SUB_DIVISION: usize = 16;
+
+/// The frame `(t, F(t), F'(t))` of the sweep along `curve` at each parameter `t` of `params`,
+/// which is increasing. The columns of `F(t)` are the normal, the binormal, the tangent and
+/// the point of the curve. The frames are traced by the sub-steps, and the derivatives are
+/// the central differences of the sub-steps. The twist starts at the first parameter.
+pub(super) fn sweep_frames(
+    curve: &Curve,
+    frame: builder::SweepFrame,
+    twist_rate: f64,
+    params: &[f64],
+) -> Vec<(f64, Matrix4, Matrix4)> {
+    use builder::SweepFrame;
+    let n = (params.len() - 1) * FRAME_SUB_DIVISION;
+    let mut ts: Vec<f64> = params
+        .windows(2)
+        .flat_map(|t| {
+            let dt = (t[1] - t[0]) / FRAME_SUB_DIVISION as f64;
+            (0..FRAME_SUB_DIVISION).map(move |i| t[0] + dt * i as f64)
+        })
+        .collect();
+    ts.push(params[params.len() - 1]);
+    let points: Vec<Point3> = ts.iter().map(|t| curve.subs(*t)).collect();
+    let mut tangents: Vec<Vector3> = ts.iter().map(|t| curve.der(*t).normalize()).collect();
+    let curvature = |i: usize, tangent: Vector3| perpendicular(curve.der2(ts[i]), tangent);
+    let mut normals = Vec::<Vector3>::with_capacity(n + 1);
+    for i in 0..=n {
+        let prev = normals.last().copied();
+        let normal = match frame {
+            SweepFrame::Frenet => curvature(i, tangents[i]).or(prev),
+            SweepFrame::RotationMinimizing => match prev {
+                Some(normal) => Some(double_reflection(
+                    (points[i - 1], tangents[i - 1], normal),
+                    (points[i], tangents[i]),
+                )),
+                None => curvature(i, tangents[i]),
+            },
+            SweepFrame::FixedUp(up) => perpendicular(up, tangents[i]).or(prev),
+            SweepFrame::Binormal(axis) => {
+                let axis = axis.normalize();
+                tangents[i] = perpendicular(tangents[i], axis)
+                    .or_else(|| Some(prev?.cross(axis)))
+                    .unwrap_or_else(|| any_perpendicular(axis));
+                Some(axis.cross(tangents[i]))
+            }
+        };
+        normals.push(normal.unwrap_or_else(|| any_perpendicular(tangents[i])));
+    }
+    let mut length = 0.0;
+    let frames: Vec<Matrix4> = (0..=n)
+        .map(|i| {
+            if i > 0 {
+                length += points[i].distance(points[i - 1]);
+            }
+            let (tangent, normal) = (tangents[i], normals[i]);
+            let binormal = tangent.cross(normal);
+            let (sin, cos) = (twist_rate * length).sin_cos();
+            let normal = normal * cos + binormal * sin;
+            let binormal = tangent.cross(normal);
+            Matrix4::from_cols(
+                normal.extend(0.0),
+                binormal.extend(0.0),
+                tangent.extend(0.0),
+                points[i].to_homogeneous(),
+            )
+        })
+        .collect();
+    (0..params.len())
+        .map(|k| {
+            let i = k * FRAME_SUB_DIVISION;
+            let (i0, i1) = (i.saturating_sub(1), usize::min(i + 1, n));
+            let der = (frames[i1] - frames[i0]) / (ts[i1] - ts[i0]);
+            (ts[i], frames[i], der)
+        })
+        .collect()
+}
+
+/// The affine maps `[A0, A1, A2, A3]` of a step of the sweep from `frame0` to `frame1`.
+/// The cubic Bezier curve whose control points are `Aj p` is the Hermite interpolation of
+/// the trajectory `F(t) F(t0)^{-1} p` of the point `p`, and `A3` is the map of the step.
+pub(super) fn hermite_maps(
+    (t0, frame0, der0): (f64, Matrix4, Matrix4),
+    (t1, frame1, der1): (f64, Matrix4, Matrix4),
+) -> [Matrix4; 4] {
+    let inverse = frame0.invert().unwrap();
+    let h = (t1 - t0) / 3.0;
+    let map = frame1 * inverse;
+    [
+        Matrix4::identity(),
+        Matrix4::identity() + der0 * inverse * h,
+        map - der1 * inverse * h,
+        map,
+    ]
+}
+
+/// the parameter of the nearest point of `curve` from `point`
+pub(super) fn nearest_parameter(curve: &Curve, point: Point3) -> f64 {
+    const DIVISION: usize = 100;
+    let (t0, t1) = curve.parameter_range();
+    let hint = (0..=DIVISION)
+        .map(|i| t0 + (t1 - t0) * i as f64 / DIVISION as f64)
+        .min_by(|s, t| {
+            let (ds, dt) = (curve.subs(*s).distance2(point), curve.subs(*t).distance2(point));
+            ds.partial_cmp(&dt).unwrap()
+        })
+        .unwrap();
+    let t = curve.search_nearest_parameter(point, Some(hint), 100);
+    t.unwrap_or(hint).clamp(t0, t1)
+}
+
+/// The value and the derivative at `t` in the `j`-th span of the cubic Hermite interpolation
+/// of `values` at `params` by the Catmull-Rom tangents.
+pub(super) fn catmull_rom(
+    params: &[f64],
+    values: &[Vector4],
+    j: usize,
+    t: f64,
+) -> (Vector4, Vector4) {
+    let last = params.len() - 1;
+    let tangent = |j: usize| {
+        let (j0, j1) = (j.saturating_sub(1), usize::min(j + 1, last));
+        (values[j1] - values[j0]) / (params[j1] - params[j0])
+    };
+    let h = params[j + 1] - params[j];
+    let r = (t - params[j]) / h;
+    let (r2, r3) = (r * r, r * r * r);
+    let (v0, v1) = (values[j], values[j + 1]);
+    let (m0, m1) = (tangent(j) * h, tangent(j + 1) * h);
+    let value = v0 * (2.0 * r3 - 3.0 * r2 + 1.0)
+        + m0 * (r3 - 2.0 * r2 + r)
+        + v1 * (3.0 * r2 - 2.0 * r3)
+        + m1 * (r3 - r2);
+    let der = v0 * (6.0 * r2 - 6.0 * r)
+        + m0 * (3.0 * r2 - 4.0 * r + 1.0)
+        + v1 * (6.0 * r - 6.0 * r2)
+        + m1 * (3.0 * r2 - 2.0 * r);
+    (value, der / h)
+}
+
+/// the normalized component of `vector` perpendicular to the unit vector `axis`,
+/// or `None` if `vector` is parallel to `axis`
+fn perpendicular(vector: Vector3, axis: Vector3) -> Option<Vector3> {
+    let vector = vector - vector.dot(axis) * axis;
+    match vector.magnitude() > TOLERANCE {
+        true => Some(vector.normalize()),
+        false => None,
+    }
+}
+
+/// a unit vector perpendicular to the unit vector `axis`
+fn any_perpendicular(axis: Vector3) -> Vector3 {
+    let i = (0..3)
+        .min_by(|i, j| axis[*i].abs().partial_cmp(&axis[*j].abs()).unwrap())
+        .unwrap();
+    let mut vector = Vector3::zero();
+    vector[i] = 1.0;
+    perpendicular(vector, axis).unwrap()
+}
+
+/// the normal of the rotation minimizing frame at the next point by the double reflection method
+fn double_reflection(
+    (pt0, tangent0, normal0): (Point3, Vector3, Vector3),
+    (pt1, tangent1): (Point3, Vector3),
+) -> Vector3 {
+    let reflect = |vector: Vector3, mirror: Vector3| {
+        let c = mirror.dot(mirror);
+        match c > TOLERANCE2 {
+            true => vector - (2.0 / c) * mirror.dot(vector) * mirror,
+            false => vector,
+        }
+    };
+    let mirror0 = pt1 - pt0;
+    let normal = reflect(normal0, mirror0);
+    let tangent = reflect(tangent0, mirror0);
+    let normal = reflect(normal, tangent1 - tangent);
+    perpendicular(normal, tangent1).unwrap_or(normal0)
+}
+
 fn closed_polyline_orientation(pts: &Vec<Point3>) -> bool {
     pts.windows(2).fold(0.0, |sum, pt| {
         sum + (pt[1][0] + pt[0][0]) * (pt[1][1] - pt[0][1])
@@ -63,6 +63,31 @@ impl ParameterDivision1D for Curve {
     }
 }
 
+impl RoughlyBounded<Point3> for Curve {
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+        derive_curve_method!(self, RoughlyBounded::roughly_bounding_box,)
+    }
+}
+
+impl SearchNearestParameter for Curve {
+    type Point = Point3;
+    type Parameter = f64;
+    fn search_nearest_parameter(
+        &self,
+        point: Point3,
+        hint: Option<f64>,
+        trials: usize,
+    ) -> Option<f64> {
+        derive_curve_method!(
+            self,
+            SearchNearestParameter::search_nearest_parameter,
+            point,
+            hint,
+            trials
+        )
+    }
+}
+
 impl Curve {
     #[inline(always)]
     pub(super) fn knot_vec(&self) -> &KnotVec {
@@ -223,6 +248,12 @@ impl IncludeCurve<Curve> for Surface {
     }
 }
 
+impl RoughlyBounded<Point3> for Surface {
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> {
+        derive_surface_method!(self, RoughlyBounded::roughly_bounding_box,)
+    }
+}
+
 impl SearchParameter for Surface {
     type Point = Point3;
     type Parameter = (f64, f64);
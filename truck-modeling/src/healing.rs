@@ -0,0 +1,195 @@
+use crate::*;
+use std::collections::HashSet;
+
+/// the number of the divisions of curves for sampling points
+const DIVISION: usize = 8;
+
+/// Options of [`heal`](./fn.heal.html)
+#[derive(Clone, Copy, Debug)]
+pub struct HealingOptions {
+    /// the tolerance of the gaps between faces, the lengths of small edges and
+    /// the widths of sliver faces. Default is `1.0e-4`.
+    pub tolerance: f64,
+    /// whether the sliver faces are removed or not. Default is `true`.
+    pub remove_sliver_faces: bool,
+    /// whether the curves of edges are modified so that their ends are on the vertices or not.
+    /// Default is `true`.
+    pub resynchronize_curves: bool,
+}
+
+impl Default for HealingOptions {
+    #[inline(always)]
+    fn default() -> HealingOptions {
+        HealingOptions {
+            tolerance: 1.0e-4,
+            remove_sliver_faces: true,
+            resynchronize_curves: true,
+        }
+    }
+}
+
+/// The fixes done by [`heal`](./fn.heal.html)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HealingReport {
+    /// the number of the removed edges shorter than the tolerance
+    pub small_edges: usize,
+    /// the number of the removed faces narrower than the tolerance
+    pub sliver_faces: usize,
+    /// the number of the edges glued to the other edges across the gaps
+    pub sewn_edges: usize,
+    /// the number of the edges whose curves are modified so that their ends are on the vertices
+    pub resynchronized_curves: usize,
+}
+
+/// Heals `solid` so that it can be meshed and used in boolean operations.
+///
+/// The healing consists of the following steps.
+/// 1. The faces whose boundaries are within the tolerance from themselves, i.e.
+///    the sliver faces, are removed.
+/// 2. The faces are sewn by [`sewing::sew`], which merges the vertices closer than
+///    the tolerance, removes the edges shorter than the tolerance and glues the coincident
+///    edges across the gaps.
+/// 3. The ends of the curves of edges are moved onto the merged vertices.
+///
+/// Returns the healed solid and the report of the fixes.
+/// # Failures
+/// Returns an error if a healed shell is not closed, e.g. the gaps are wider than the tolerance.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use healing::HealingOptions;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the faces with the small gaps, as imported from a file
+/// let faces: Shell = cube.boundaries()[0]
+///     .face_iter()
+///     .enumerate()
+///     .map(|(i, face)| builder::translated(face, Vector3::new(0.0, 0.0, 1.0e-5 * i as f64)))
+///     .collect();
+/// let imported = Solid::new_unchecked(vec![faces]);
+///
+/// let (solid, report) = healing::heal(&imported, &HealingOptions::default()).unwrap();
+/// assert_eq!(solid.boundaries()[0].shell_condition(), ShellCondition::Closed);
+/// assert_eq!(report.sewn_edges, 12);
+/// assert!(report.resynchronized_curves > 0);
+/// ```
+///
+/// [`sewing::sew`]: ../sewing/fn.sew.html
+pub fn heal(solid: &Solid, options: &HealingOptions) -> Result<(Solid, HealingReport)> {
+    let tolerance = options.tolerance;
+    let small_edges = solid
+        .edge_iter()
+        .filter(|edge| {
+            let tolerance = edge.tolerance().map_or(tolerance, |tol| tol.max(tolerance));
+            is_small(&edge.get_curve(), tolerance)
+        })
+        .map(|edge| edge.id())
+        .collect::<HashSet<_>>()
+        .len();
+    let mut faces: Vec<Face> = solid.face_iter().cloned().collect();
+    let len = faces.len();
+    if options.remove_sliver_faces {
+        faces.retain(|face| !is_sliver(face, tolerance));
+    }
+    let sliver_faces = len - faces.len();
+    let edges = edge_count(faces.iter());
+    let sewed = sewing::sew(faces, tolerance);
+    let sewed_edges = edge_count(sewed.shells.iter().flat_map(Shell::face_iter));
+    let sewn_edges = edges.saturating_sub(small_edges + sewed_edges);
+    let mut resynchronized_curves = 0;
+    if options.resynchronize_curves {
+        let mut edges = HashSet::new();
+        resynchronized_curves = sewed
+            .shells
+            .iter()
+            .flat_map(Shell::edge_iter)
+            .filter(|edge| edges.insert(edge.id()))
+            .filter(resynchronize)
+            .count();
+    }
+    let report = HealingReport {
+        small_edges,
+        sliver_faces,
+        sewn_edges,
+        resynchronized_curves,
+    };
+    let solid = Solid::try_new(sewed.shells)?;
+    Ok((solid, report))
+}
+
+/// the number of the edges in the boundaries of `faces`, without duplications
+fn edge_count<'a>(faces: impl Iterator<Item = &'a Face>) -> usize {
+    faces
+        .flat_map(|face| face.absolute_boundaries().iter().flatten())
+        .map(|edge| edge.id())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// the points of `curve` at the parameters dividing the parameter range into `DIVISION` parts
+fn sample_points(curve: &Curve) -> impl Iterator<Item = Point3> + '_ {
+    let (t0, t1) = curve.parameter_range();
+    (0..=DIVISION).map(move |i| curve.subs(t0 + (t1 - t0) * i as f64 / DIVISION as f64))
+}
+
+/// Whether all the points of `curve` are within `tolerance` from its front.
+fn is_small(curve: &Curve, tolerance: f64) -> bool {
+    let front = curve.subs(curve.parameter_range().0);
+    sample_points(curve).all(|pt| pt.distance(front) <= tolerance)
+}
+
+/// Whether each edge of the boundaries of `face` is within `tolerance` from the other edges.
+fn is_sliver(face: &Face, tolerance: f64) -> bool {
+    let curves: Vec<Curve> = face
+        .absolute_boundaries()
+        .iter()
+        .flatten()
+        .map(|edge| edge.get_curve())
+        .collect();
+    let on_curve = |pt: Point3, curve: &Curve| {
+        curve
+            .search_nearest_parameter(pt, None, 100)
+            .map(|t| curve.subs(t).distance(pt) <= tolerance)
+            .unwrap_or(false)
+    };
+    curves.len() > 1
+        && curves.iter().enumerate().all(|(i, curve)| {
+            sample_points(curve).all(|pt| {
+                curves
+                    .iter()
+                    .enumerate()
+                    .any(|(j, other)| i != j && on_curve(pt, other))
+            })
+        })
+}
+
+/// Moves the ends of the curve of `edge` onto the vertices, by moving the first and the last
+/// control points. Returns `true` if the curve is modified.
+fn resynchronize(edge: &Edge) -> bool {
+    let (p0, p1) = (edge.absolute_front().get_point(), edge.absolute_back().get_point());
+    let mut curve = edge.get_curve();
+    let (t0, t1) = curve.parameter_range();
+    let (d0, d1) = (p0 - curve.subs(t0), p1 - curve.subs(t1));
+    if d0.so_small() && d1.so_small() {
+        return false;
+    }
+    match &mut curve {
+        Curve::BSplineCurve(curve) => {
+            let n = curve.control_points().len();
+            *curve.control_point_mut(0) += d0;
+            *curve.control_point_mut(n - 1) += d1;
+        }
+        Curve::NURBSCurve(curve) => {
+            let n = curve.control_points().len();
+            let moved = |pt: Vector4, d: Vector3| (pt.to_point() + d).to_vec().extend(1.0) * pt[3];
+            *curve.control_point_mut(0) = moved(*curve.control_point(0), d0);
+            *curve.control_point_mut(n - 1) = moved(*curve.control_point(n - 1), d1);
+        }
+    }
+    edge.set_curve(curve);
+    true
+}
@@ -15,7 +15,9 @@
 
 /// re-export `truck_base`.
 pub mod base {
-    pub use truck_base::{assert_near, assert_near2, bounding_box::*, cgmath64::*, tolerance::*};
+    pub use truck_base::{
+        assert_near, assert_near2, bounding_box::*, cgmath64::*, context::*, tolerance::*,
+    };
     pub use truck_geotrait::*;
 }
 pub use base::*;
@@ -47,15 +49,20 @@ pub mod topology {
     /// The id that does not depend on the direction of the face.
     pub type FaceID = truck_topology::FaceID<Surface>;
 
+    /// Metadata attached to a solid, and its faces and edges.
+    pub type MetadataMap = truck_topology::MetadataMap<Curve, Surface>;
+    pub use truck_topology::Metadata;
+
     pub use truck_topology::shell::ShellCondition;
 }
 pub use topology::*;
 
-/// topological utility: [`Mapped`], [`Sweep`], and [`ClosedSweep`].
+/// topological utility: [`Mapped`], [`Sweep`], [`ClosedSweep`], and [`FrameSweep`].
 ///
 /// [`Mapped`]: ./topo_traits/trait.Mapped.html
 /// [`Sweep`]: ./topo_traits/trait.Sweep.html
 /// [`ClosedSweep`]: ./topo_traits/trait.ClosedSweep.html
+/// [`FrameSweep`]: ./topo_traits/trait.FrameSweep.html
 pub mod topo_traits {
     /// Mapping, duplicates and moves a topological element.
     pub trait Mapped<P, C, S>: Sized {
@@ -142,19 +149,63 @@ pub mod topo_traits {
             division: usize,
         ) -> Self::Swept;
     }
+
+    /// Sweeping step by step, builds a pipe along a path with moving frames, and so on.
+    ///
+    /// Unlike [`MultiSweep`], each closure takes the index of the step from `0` to
+    /// `division - 1`, so the mappings and the connections can depend on the frame of the path
+    /// at the step, e.g. a Frenet frame or a twist.
+    ///
+    /// [`MultiSweep`]: ./trait.MultiSweep.html
+    pub trait FrameSweep<P, C, S> {
+        /// The struct of sweeped topology.
+        type Swept;
+        /// Transform topologies and connect vertices and edges in boundaries at each step.
+        fn frame_sweep<
+            FP: Fn(usize, &P) -> P,
+            FC: Fn(usize, &C) -> C,
+            FS: Fn(usize, &S) -> S,
+            CP: Fn(usize, &P, &P) -> C,
+            CE: Fn(usize, &C, &C) -> S,
+        >(
+            &self,
+            point_mapping: &FP,
+            curve_mapping: &FC,
+            surface_mapping: &FS,
+            connect_points: &CP,
+            connect_curves: &CE,
+            division: usize,
+        ) -> Self::Swept;
+    }
 }
 pub use topo_traits::*;
 
 /// `Result` with crate's errors.
 pub type Result<T> = std::result::Result<T, errors::Error>;
 
+/// assemblies of parts placed by transforms
+pub mod assembly;
+pub use assembly::*;
 /// the building model utility API
 pub mod builder;
 mod closed_sweep;
 /// declare errors
 pub mod errors;
+/// converting polygon meshes of planar facets into solids
+pub mod faceted;
+mod frame_sweep;
 mod geom_impls;
+/// healing imported solids for meshing and boolean operations
+pub mod healing;
 mod mapped;
+/// embossing and engraving marks on the faces of solids
+pub mod marking;
 mod multi_sweep;
+/// boolean operations of regions bounded by coplanar closed wires
+pub mod planar_boolean;
+/// sewing faces into shells
+pub mod sewing;
+/// sheet metal parts of base flanges and bends, folded and flattened
+pub mod sheet_metal;
 mod sweep;
 mod topo_impls;
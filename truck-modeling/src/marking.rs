@@ -0,0 +1,141 @@
+use crate::errors::Error;
+use crate::*;
+
+/// Embosses the regions bounded by `outlines` on the planar `face` of `solid` by `depth`.
+///
+/// Each element of `outlines` is the boundary of one region, e.g. a glyph of text converted
+/// into wires: the first wire is the outer boundary and the others are the holes of the region.
+/// The outlines have to be on the plane of `face`, in the interior of `face`, and must not
+/// intersect each other. The regions are extruded along the normal of `face`, and the side and
+/// top faces of the extrusions are added to the shell of `face`.
+/// # Failures
+/// - Returns [`Error::FaceNotInSolid`] if `face` is not a face of `solid`.
+/// - Returns [`Error::NotPlanarFace`] if `face` is not planar.
+/// - Returns [`Error::WireNotInOnePlane`] if the outlines are not on the plane of `face`.
+/// - Returns the error of [`builder::try_attach_plane`] if an outline is not closed.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube = builder::tsweep(&f, Vector3::unit_z());
+/// // the face on the plane z = 1
+/// let top = cube.boundaries()[0].last().unwrap().clone();
+///
+/// // the square mark
+/// let v = builder::vertex(Point3::new(0.25, 0.25, 1.0));
+/// let e = builder::tsweep(&v, Vector3::new(0.5, 0.0, 0.0));
+/// let square = builder::tsweep(&e, Vector3::new(0.0, 0.5, 0.0));
+/// let outlines = vec![square.boundaries()];
+///
+/// let solid = marking::emboss(&cube, &top, &outlines, 0.1).unwrap();
+/// let shell = &solid.boundaries()[0];
+/// // the four sides and the top of the mark are added
+/// assert_eq!(shell.len(), 11);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// ```
+///
+/// [`Error::FaceNotInSolid`]: ../errors/enum.Error.html#variant.FaceNotInSolid
+/// [`Error::NotPlanarFace`]: ../errors/enum.Error.html#variant.NotPlanarFace
+/// [`Error::WireNotInOnePlane`]: ../errors/enum.Error.html#variant.WireNotInOnePlane
+/// [`builder::try_attach_plane`]: ../builder/fn.try_attach_plane.html
+#[inline(always)]
+pub fn emboss(solid: &Solid, face: &Face, outlines: &[Vec<Wire>], depth: f64) -> Result<Solid> {
+    mark(solid, face, outlines, depth)
+}
+
+/// Engraves the regions bounded by `outlines` on the planar `face` of `solid` by `depth`.
+///
+/// The outlines are the same as the ones of [`emboss`]. The regions are dug along
+/// the opposite of the normal of `face`, and the side and bottom faces of the pockets are
+/// added to the shell of `face`. The pockets must not reach the other faces of `solid`.
+/// # Failures
+/// The same as [`emboss`].
+///
+/// [`emboss`]: ./fn.emboss.html
+#[inline(always)]
+pub fn engrave(solid: &Solid, face: &Face, outlines: &[Vec<Wire>], depth: f64) -> Result<Solid> {
+    mark(solid, face, outlines, -depth)
+}
+
+/// the normal of the plane of `face`, or `None` if `face` is not planar
+fn plane_normal(face: &Face) -> Option<Vector3> {
+    let surface = face.oriented_surface();
+    if let Surface::Plane(plane) = &surface {
+        return Some(plane.normal());
+    }
+    // the surfaces created by sweeping lines are not `Plane`, even if they are flat.
+    let boundaries = face.boundaries();
+    let normal = match builder::try_attach_plane(&boundaries).ok()?.get_surface() {
+        Surface::Plane(plane) => plane.normal(),
+        _ => return None,
+    };
+    let flat = boundaries
+        .iter()
+        .flatten()
+        .flat_map(|edge| {
+            let curve = edge.get_curve();
+            let (t0, t1) = curve.parameter_range();
+            (0..4).map(move |i| curve.subs(t0 + (t1 - t0) * i as f64 / 4.0))
+        })
+        .all(|pt| {
+            surface
+                .search_parameter(pt, None, 100)
+                .is_some_and(|(u, v)| surface.normal(u, v).near(&normal))
+        });
+    match flat {
+        true => Some(normal),
+        false => None,
+    }
+}
+
+/// Extrudes the regions by `height` along the normal of `face`, embossing if `height` is
+/// positive and engraving if `height` is negative.
+fn mark(solid: &Solid, face: &Face, outlines: &[Vec<Wire>], height: f64) -> Result<Solid> {
+    let mut shells = solid.boundaries().clone();
+    let (k, i) = shells
+        .iter()
+        .enumerate()
+        .find_map(|(k, shell)| Some((k, shell.iter().position(|f| f.id() == face.id())?)))
+        .ok_or(Error::FaceNotInSolid)?;
+    let face = &shells[k][i];
+    let normal = plane_normal(face).ok_or(Error::NotPlanarFace)?;
+    let origin = face.absolute_boundaries()[0].front_vertex().unwrap().get_point();
+    let mut holes = Vec::new();
+    let mut faces = Vec::new();
+    for wires in outlines {
+        let on_plane = wires
+            .iter()
+            .flat_map(Wire::vertex_iter)
+            .all(|v| (v.get_point() - origin).dot(normal).so_small());
+        if !on_plane {
+            return Err(Error::WireNotInOnePlane);
+        }
+        let mut region = builder::try_attach_plane(wires)?;
+        // the region is inverted by the sweep, so it has to face the direction of the sweep
+        if plane_normal(&region).is_some_and(|n| n.dot(normal) * height < 0.0) {
+            region.invert();
+        }
+        let tool = builder::tsweep(&region, normal * height);
+        let shell = &tool.boundaries()[0];
+        // the first face of the shell is the inverted region, which is replaced by `face`
+        let cap = shell[0].boundaries();
+        match height > 0.0 {
+            true => {
+                holes.extend(cap);
+                faces.extend(shell.iter().skip(1).cloned());
+            }
+            false => {
+                holes.extend(cap.iter().map(Wire::inverse));
+                faces.extend(shell.iter().skip(1).map(Face::inverse));
+            }
+        }
+    }
+    let mut wires = face.boundaries();
+    wires.extend(holes);
+    shells[k][i] = Face::try_new(wires, face.oriented_surface())?;
+    shells[k].extend(faces);
+    Ok(Solid::try_new(shells)?)
+}
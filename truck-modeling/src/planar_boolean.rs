@@ -0,0 +1,527 @@
+use crate::errors::Error;
+use crate::*;
+
+/// Returns the union of the regions bounded by the coplanar closed wires.
+///
+/// The region of each operand is the set of points enclosed by its wires in the even-odd rule,
+/// so the outer boundaries and the holes can be given in any orientation and order.
+/// The edges are split at their intersections on the exact curves, where the intersections
+/// are searched by the polylines with tolerance `tol`.
+///
+/// Each element of the result is the boundary of one connected region: the first wire is
+/// the outer boundary and the others are the holes. The outer boundaries turn counterclockwise
+/// and the holes turn clockwise around the normal of the first wire of `wires0`, so each element
+/// can be passed to [`builder::try_attach_plane`].
+/// # Failures
+/// - Returns [`Error::WireNotInOnePlane`] if the wires are not on one plane.
+/// - Returns the topological error `NotClosedWire` if some wire is not closed.
+/// - Returns [`Error::PlanarBooleanFailed`] if the boundaries of the result are not closed.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// fn square(origin: Point3, size: f64) -> Wire {
+///     let v = builder::vertex(origin);
+///     let e = builder::tsweep(&v, Vector3::new(size, 0.0, 0.0));
+///     let face = builder::tsweep(&e, Vector3::new(0.0, size, 0.0));
+///     face.boundaries().pop().unwrap()
+/// }
+///
+/// let wire0 = square(Point3::origin(), 2.0);
+/// let wire1 = square(Point3::new(1.0, 1.0, 0.0), 2.0);
+/// let regions = planar_boolean::union(&[wire0], &[wire1], 0.01).unwrap();
+/// // one region without holes, whose boundary is the octagon with two reflex corners
+/// assert_eq!(regions.len(), 1);
+/// assert_eq!(regions[0].len(), 1);
+/// assert_eq!(regions[0][0].len(), 8);
+/// let face = builder::try_attach_plane(&regions[0]).unwrap();
+/// ```
+///
+/// [`builder::try_attach_plane`]: ../builder/fn.try_attach_plane.html
+/// [`Error::WireNotInOnePlane`]: ../errors/enum.Error.html#variant.WireNotInOnePlane
+/// [`Error::PlanarBooleanFailed`]: ../errors/enum.Error.html#variant.PlanarBooleanFailed
+#[inline(always)]
+pub fn union(wires0: &[Wire], wires1: &[Wire], tol: f64) -> Result<Vec<Vec<Wire>>> {
+    boolean(wires0, wires1, tol, |a, b| a || b)
+}
+
+/// Returns the intersection of the regions bounded by the coplanar closed wires.
+///
+/// The operands and the result are the same as the ones of [`union`].
+/// # Failures
+/// The same as [`union`].
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// fn square(origin: Point3, size: f64) -> Wire {
+///     let v = builder::vertex(origin);
+///     let e = builder::tsweep(&v, Vector3::new(size, 0.0, 0.0));
+///     let face = builder::tsweep(&e, Vector3::new(0.0, size, 0.0));
+///     face.boundaries().pop().unwrap()
+/// }
+///
+/// let wire0 = square(Point3::origin(), 2.0);
+/// let wire1 = square(Point3::new(1.0, 1.0, 0.0), 2.0);
+/// let regions = planar_boolean::intersection(&[wire0], &[wire1], 0.01).unwrap();
+/// // the unit square [1, 2] x [1, 2]
+/// assert_eq!(regions.len(), 1);
+/// let wire = &regions[0][0];
+/// assert_eq!(wire.len(), 4);
+/// assert!(wire.vertex_iter().all(|v| {
+///     let p = v.get_point();
+///     (p[0].near(&1.0) || p[0].near(&2.0)) && (p[1].near(&1.0) || p[1].near(&2.0))
+/// }));
+/// ```
+///
+/// [`union`]: ./fn.union.html
+#[inline(always)]
+pub fn intersection(wires0: &[Wire], wires1: &[Wire], tol: f64) -> Result<Vec<Vec<Wire>>> {
+    boolean(wires0, wires1, tol, |a, b| a && b)
+}
+
+/// Returns the difference of the region bounded by `wires1` from the region bounded by `wires0`.
+///
+/// The operands and the result are the same as the ones of [`union`].
+/// # Failures
+/// The same as [`union`].
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// fn square(origin: Point3, size: f64) -> Wire {
+///     let v = builder::vertex(origin);
+///     let e = builder::tsweep(&v, Vector3::new(size, 0.0, 0.0));
+///     let face = builder::tsweep(&e, Vector3::new(0.0, size, 0.0));
+///     face.boundaries().pop().unwrap()
+/// }
+///
+/// let wire0 = square(Point3::origin(), 3.0);
+/// let wire1 = square(Point3::new(1.0, 1.0, 0.0), 1.0);
+/// let regions = planar_boolean::difference(&[wire0], &[wire1], 0.01).unwrap();
+/// // the square with the square hole
+/// assert_eq!(regions.len(), 1);
+/// assert_eq!(regions[0].len(), 2);
+/// let face = builder::try_attach_plane(&regions[0]).unwrap();
+/// ```
+///
+/// [`union`]: ./fn.union.html
+#[inline(always)]
+pub fn difference(wires0: &[Wire], wires1: &[Wire], tol: f64) -> Result<Vec<Vec<Wire>>> {
+    boolean(wires0, wires1, tol, |a, b| a && !b)
+}
+
+/// the plane of the wires with its orthonormal frame
+struct Frame {
+    origin: Point3,
+    axes: [Vector3; 2],
+}
+
+impl Frame {
+    /// the coordinates of `point` in the plane
+    #[inline(always)]
+    fn point(&self, point: Point3) -> Point2 {
+        let vec = self.vector(point - self.origin);
+        Point2::new(vec[0], vec[1])
+    }
+    /// the coordinates of `vector` in the plane
+    #[inline(always)]
+    fn vector(&self, vector: Vector3) -> Vector2 {
+        Vector2::new(vector.dot(self.axes[0]), vector.dot(self.axes[1]))
+    }
+}
+
+/// the curve with the division points in the plane
+struct PlanarCurve {
+    curve: Curve,
+    params: Vec<f64>,
+    points: Vec<Point2>,
+}
+
+impl PlanarCurve {
+    fn new(curve: Curve, frame: &Frame, tol: f64) -> PlanarCurve {
+        let params = curve.parameter_division(curve.parameter_range(), tol);
+        let points = params.iter().map(|t| frame.point(curve.subs(*t))).collect();
+        PlanarCurve {
+            curve,
+            params,
+            points,
+        }
+    }
+    fn segments(&self) -> impl Iterator<Item = (usize, Point2, Point2)> + '_ {
+        (1..self.points.len()).map(move |i| (i, self.points[i - 1], self.points[i]))
+    }
+    /// the parameter at the ratio `s` of the `i`th segment
+    #[inline(always)]
+    fn parameter(&self, i: usize, s: f64) -> f64 {
+        self.params[i - 1] + (self.params[i] - self.params[i - 1]) * s
+    }
+}
+
+#[inline(always)]
+fn cross(a: Vector2, b: Vector2) -> f64 { a[0] * b[1] - a[1] * b[0] }
+
+/// Returns the curves of the edges of the closed wires.
+fn wire_curves(wires: &[Wire]) -> Result<Vec<Curve>> {
+    wires
+        .iter()
+        .map(|wire| match wire.is_closed() {
+            true => Ok(wire.edge_iter().map(Edge::oriented_curve)),
+            false => Err(truck_topology::errors::Error::NotClosedWire.into()),
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|curves| curves.into_iter().flatten().collect())
+}
+
+/// Returns the frame of the plane of the first wire.
+fn frame(wire: &Wire, tol: f64) -> Option<Frame> {
+    let points: Vec<Point3> = wire
+        .edge_iter()
+        .flat_map(|edge| {
+            let curve = edge.oriented_curve();
+            let mut params = curve.parameter_division(curve.parameter_range(), tol);
+            params.pop();
+            params.into_iter().map(move |t| curve.subs(t))
+        })
+        .collect();
+    let origin = *points.first()?;
+    // Newell's method
+    let normal = (0..points.len()).fold(Vector3::zero(), |sum, i| {
+        let (p, q) = (points[i], points[(i + 1) % points.len()]);
+        sum + (p - origin).cross(q - origin)
+    });
+    if normal.so_small() {
+        return None;
+    }
+    let normal = normal.normalize();
+    let axis = match normal[0].abs() < 0.9 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let axis0 = axis.cross(normal).normalize();
+    Some(Frame {
+        origin,
+        axes: [axis0, normal.cross(axis0)],
+    })
+}
+
+/// whether `point` is enclosed by the curves in the even-odd rule
+fn enclosed(curves: &[PlanarCurve], point: Point2) -> bool {
+    curves
+        .iter()
+        .flat_map(PlanarCurve::segments)
+        .filter(|(_, p, q)| {
+            (p[1] > point[1]) != (q[1] > point[1])
+                && point[0] < p[0] + (q[0] - p[0]) * (point[1] - p[1]) / (q[1] - p[1])
+        })
+        .count()
+        % 2
+        == 1
+}
+
+/// Solves `curve0.subs(t0) == curve1.subs(t1)` in the plane by Newton's method from `hint`.
+fn intersection_parameters(
+    curve0: &Curve,
+    curve1: &Curve,
+    frame: &Frame,
+    hint: (f64, f64),
+) -> Option<(f64, f64)> {
+    let (mut t0, mut t1) = hint;
+    for _ in 0..20 {
+        let diff = frame.point(curve0.subs(t0)) - frame.point(curve1.subs(t1));
+        if diff.so_small() {
+            return Some((t0, t1));
+        }
+        let mat = Matrix2::from_cols(frame.vector(curve0.der(t0)), -frame.vector(curve1.der(t1)));
+        let delta = mat.invert()? * diff;
+        t0 -= delta[0];
+        t1 -= delta[1];
+    }
+    None
+}
+
+/// Adds the parameters of the intersections of `curve0` and `curve1` to `splits`.
+fn add_intersections(
+    curve0: &PlanarCurve,
+    curve1: &PlanarCurve,
+    frame: &Frame,
+    splits: (&mut Vec<f64>, &mut Vec<f64>),
+) {
+    for (i, p0, q0) in curve0.segments() {
+        for (j, p1, q1) in curve1.segments() {
+            let (d0, d1) = (q0 - p0, q1 - p1);
+            let det = cross(d0, d1);
+            if det.so_small() {
+                continue;
+            }
+            let s0 = cross(p1 - p0, d1) / det;
+            let s1 = cross(p1 - p0, d0) / det;
+            if !(0.0..=1.0).contains(&s0) || !(0.0..=1.0).contains(&s1) {
+                continue;
+            }
+            let hint = (curve0.parameter(i, s0), curve1.parameter(j, s1));
+            let (t0, t1) = intersection_parameters(&curve0.curve, &curve1.curve, frame, hint)
+                .unwrap_or(hint);
+            splits.0.push(t0);
+            splits.1.push(t1);
+        }
+    }
+}
+
+/// Adds the parameters of the nearest points on `curve` to the end points of `other`, which are
+/// on `curve`, to `splits`. The overlapping curves are split at the ends of each other.
+fn add_touching_points(curve: &PlanarCurve, other: &PlanarCurve, splits: &mut Vec<f64>, tol: f64) {
+    let ends = [other.points[0], other.points[other.points.len() - 1]];
+    for end in ends.iter() {
+        let nearest = curve
+            .segments()
+            .map(|(i, p, q)| {
+                let d = q - p;
+                let s = match d.so_small() {
+                    true => 0.0,
+                    false => f64::clamp((end - p).dot(d) / d.magnitude2(), 0.0, 1.0),
+                };
+                (end.distance(p + s * d), curve.parameter(i, s))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        if let Some((distance, hint)) = nearest {
+            if distance < tol {
+                let point = other.curve.subs(match end == &other.points[0] {
+                    true => other.params[0],
+                    false => other.params[other.params.len() - 1],
+                });
+                let t = curve
+                    .curve
+                    .search_nearest_parameter(point, Some(hint), 100)
+                    .unwrap_or(hint);
+                splits.push(t);
+            }
+        }
+    }
+}
+
+/// Splits `curve` at the parameters `splits`, omitting the ones near the ends or each other.
+fn split(mut curve: Curve, mut splits: Vec<f64>, tol: f64) -> Vec<Curve> {
+    let (t0, t1) = curve.parameter_range();
+    splits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut last = curve.subs(t0);
+    let end = curve.subs(t1);
+    let params: Vec<f64> = splits
+        .into_iter()
+        .filter(|t| t0 < *t && *t < t1)
+        .filter(|t| {
+            let point = curve.subs(*t);
+            let far = point.distance(last) > tol && point.distance(end) > tol;
+            if far {
+                last = point;
+            }
+            far
+        })
+        .collect();
+    let mut pieces: Vec<Curve> = params.into_iter().rev().map(|t| curve.cut(t)).collect();
+    pieces.push(curve);
+    pieces.reverse();
+    pieces
+}
+
+/// the piece of the boundary of the result, whose left side is in the result
+struct Piece {
+    curve: Curve,
+    points: Vec<Point2>,
+    vertices: [usize; 2],
+}
+
+/// Returns the signed area of the closed polygon.
+fn signed_area(points: &[Point2]) -> f64 {
+    (0..points.len()).fold(0.0, |sum, i| {
+        let (p, q) = (points[i], points[(i + 1) % points.len()]);
+        sum + cross(p.to_vec(), q.to_vec())
+    }) / 2.0
+}
+
+fn boolean<F: Fn(bool, bool) -> bool>(
+    wires0: &[Wire],
+    wires1: &[Wire],
+    tol: f64,
+    operation: F,
+) -> Result<Vec<Vec<Wire>>> {
+    let curves0 = wire_curves(wires0)?;
+    let curves1 = wire_curves(wires1)?;
+    let frame = match wires0.first() {
+        Some(wire) => frame(wire, tol).ok_or(Error::WireNotInOnePlane)?,
+        None => return Ok(Vec::new()),
+    };
+    let normal = frame.axes[0].cross(frame.axes[1]);
+    let planar = curves0.iter().chain(&curves1).all(|curve| {
+        let params = curve.parameter_division(curve.parameter_range(), tol);
+        params
+            .into_iter()
+            .all(|t| (curve.subs(t) - frame.origin).dot(normal).abs() < tol)
+    });
+    if !planar {
+        return Err(Error::WireNotInOnePlane);
+    }
+    let curves0: Vec<_> = curves0
+        .into_iter()
+        .map(|curve| PlanarCurve::new(curve, &frame, tol))
+        .collect();
+    let curves1: Vec<_> = curves1
+        .into_iter()
+        .map(|curve| PlanarCurve::new(curve, &frame, tol))
+        .collect();
+
+    // splits the curves at the intersections
+    let mut splits0 = vec![Vec::new(); curves0.len()];
+    let mut splits1 = vec![Vec::new(); curves1.len()];
+    for (curve0, splits0) in curves0.iter().zip(&mut splits0) {
+        for (curve1, splits1) in curves1.iter().zip(&mut splits1) {
+            add_intersections(curve0, curve1, &frame, (splits0, splits1));
+            add_touching_points(curve0, curve1, splits0, tol);
+            add_touching_points(curve1, curve0, splits1, tol);
+        }
+    }
+    let pieces = curves0
+        .iter()
+        .zip(splits0)
+        .chain(curves1.iter().zip(splits1))
+        .flat_map(|(curve, splits)| split(curve.curve.clone(), splits, tol));
+
+    // selects the pieces between the inside and the outside of the result
+    let eps = 2.0 * tol;
+    let mut points = Vec::<Point3>::new();
+    let mut vertex = |point: Point3| match points.iter().position(|p| p.distance(point) < tol) {
+        Some(idx) => idx,
+        None => {
+            points.push(point);
+            points.len() - 1
+        }
+    };
+    let mut selected = Vec::<Piece>::new();
+    for mut curve in pieces {
+        let (t0, t1) = curve.parameter_range();
+        let t = (t0 + t1) / 2.0;
+        let (middle, tangent) = (frame.point(curve.subs(t)), frame.vector(curve.der(t)));
+        if tangent.so_small() {
+            continue;
+        }
+        let left = Vector2::new(-tangent[1], tangent[0]).normalize() * eps;
+        let classify = |point: Point2| {
+            operation(enclosed(&curves0, point), enclosed(&curves1, point))
+        };
+        let (left_in, right_in) = (classify(middle + left), classify(middle - left));
+        if left_in == right_in {
+            continue;
+        }
+        if right_in {
+            curve.invert();
+        }
+        let (t0, t1) = curve.parameter_range();
+        let vertices = [vertex(curve.subs(t0)), vertex(curve.subs(t1))];
+        let middle = curve.subs((t0 + t1) / 2.0);
+        // the pieces shared by both operands
+        let duplicated = selected.iter().any(|piece| {
+            let (s0, s1) = piece.curve.parameter_range();
+            piece.vertices == vertices && piece.curve.subs((s0 + s1) / 2.0).distance(middle) < tol
+        });
+        if vertices[0] == vertices[1] || duplicated {
+            continue;
+        }
+        let mut params = curve.parameter_division((t0, t1), tol);
+        params.pop();
+        let points = params.iter().map(|t| frame.point(curve.subs(*t))).collect();
+        selected.push(Piece {
+            curve,
+            points,
+            vertices,
+        });
+    }
+
+    // traces the loops, turning left as much as possible at the branches
+    let mut used = vec![false; selected.len()];
+    let mut loops = Vec::<Vec<usize>>::new();
+    while let Some(first) = used.iter().position(|used| !used) {
+        used[first] = true;
+        let mut indices = vec![first];
+        let start = selected[first].vertices[0];
+        let mut last = first;
+        while selected[last].vertices[1] != start {
+            let curve = &selected[last].curve;
+            let incoming = frame.vector(curve.der(curve.parameter_range().1));
+            let next = (0..selected.len())
+                .filter(|i| !used[*i] && selected[*i].vertices[0] == selected[last].vertices[1])
+                .max_by(|i, j| {
+                    let turn = |k: &usize| {
+                        let curve = &selected[*k].curve;
+                        let outgoing = frame.vector(curve.der(curve.parameter_range().0));
+                        f64::atan2(cross(incoming, outgoing), incoming.dot(outgoing))
+                    };
+                    turn(i).partial_cmp(&turn(j)).unwrap()
+                })
+                .ok_or(Error::PlanarBooleanFailed)?;
+            used[next] = true;
+            indices.push(next);
+            last = next;
+        }
+        loops.push(indices);
+    }
+
+    // gathers the holes into the outer boundaries
+    let vertices: Vec<Vertex> = points.into_iter().map(Vertex::new).collect();
+    let polygons: Vec<Vec<Point2>> = loops
+        .iter()
+        .map(|indices| {
+            indices
+                .iter()
+                .flat_map(|i| selected[*i].points.iter().cloned())
+                .collect()
+        })
+        .collect();
+    let areas: Vec<f64> = polygons.iter().map(|polygon| signed_area(polygon)).collect();
+    let mut regions: Vec<(usize, Vec<usize>)> = (0..loops.len())
+        .filter(|i| areas[*i] > 0.0)
+        .map(|i| (i, Vec::new()))
+        .collect();
+    for hole in (0..loops.len()).filter(|i| areas[*i] <= 0.0) {
+        let piece = &selected[loops[hole][0]];
+        let (t0, t1) = piece.curve.parameter_range();
+        let point = frame.point(piece.curve.subs((t0 + t1) / 2.0));
+        let polygon_contains = |polygon: &[Point2]| {
+            let len = polygon.len();
+            (0..len)
+                .filter(|i| {
+                    let (p, q) = (polygon[*i], polygon[(*i + 1) % len]);
+                    (p[1] > point[1]) != (q[1] > point[1])
+                        && point[0] < p[0] + (q[0] - p[0]) * (point[1] - p[1]) / (q[1] - p[1])
+                })
+                .count()
+                % 2
+                == 1
+        };
+        let (_, holes) = regions
+            .iter_mut()
+            .filter(|(outer, _)| polygon_contains(&polygons[*outer]))
+            .min_by(|(i, _), (j, _)| areas[*i].partial_cmp(&areas[*j]).unwrap())
+            .ok_or(Error::PlanarBooleanFailed)?;
+        holes.push(hole);
+    }
+    let wire = |indices: &[usize]| -> Wire {
+        indices
+            .iter()
+            .map(|i| {
+                let piece = &selected[*i];
+                let [v0, v1] = piece.vertices;
+                Edge::new(&vertices[v0], &vertices[v1], piece.curve.clone())
+            })
+            .collect()
+    };
+    let res = regions
+        .into_iter()
+        .map(|(outer, holes)| {
+            std::iter::once(outer)
+                .chain(holes)
+                .map(|i| wire(&loops[i]))
+                .collect()
+        })
+        .collect();
+    Ok(res)
+}
@@ -0,0 +1,315 @@
+use crate::*;
+use std::collections::{HashMap, VecDeque};
+
+/// The shells sewn by [`sew`](./fn.sew.html).
+#[derive(Clone, Debug)]
+pub struct SewedShells {
+    /// the connected shells, whose faces are oriented consistently if possible
+    pub shells: Vec<Shell>,
+    /// the edges of the shells not matched to the edges of the other faces
+    pub free_edges: Vec<Edge>,
+}
+
+/// Sews faces into shells by matching the coincident boundary edges geometrically.
+///
+/// The vertices closer than `tolerance` are merged, and two edges are glued if their ends are
+/// merged and the points of one curve are within `tolerance` from the other curve.
+/// The faces connected by the glued edges make one shell, and the faces are inverted so that
+/// adjacent faces have the compatible orientations, as far as the shell is orientable.
+/// The edges not glued to any other edges are returned as the free edges, which are
+/// the boundaries of the open shells.
+///
+/// If a vertex or an edge has its own tolerance larger than `tolerance`, it is merged or glued
+/// by its own tolerance, as the imprecise vertices and edges of imported data.
+///
+/// Each glued edge takes the curve of one of the original edges, and each merged vertex
+/// takes the point of one of the original vertices. The merged vertices and the glued edges
+/// take the largest tolerances of the original ones. The edges whose both ends are merged
+/// into one vertex are removed from the boundaries.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+///
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the faces not sharing vertices and edges, as imported from a file
+/// let faces: Vec<Face> = cube.boundaries()[0]
+///     .face_iter()
+///     .map(|face| face.topological_clone())
+///     .collect();
+///
+/// let sewed = sewing::sew(faces, 1.0e-6);
+/// assert_eq!(sewed.shells.len(), 1);
+/// assert!(sewed.free_edges.is_empty());
+/// assert_eq!(sewed.shells[0].shell_condition(), ShellCondition::Closed);
+/// let solid = Solid::new(sewed.shells);
+/// ```
+pub fn sew(faces: Vec<Face>, tolerance: f64) -> SewedShells {
+    let edges = boundary_edges(&faces);
+    let vertices = merged_vertices(&edges, tolerance);
+    let ends: Vec<(usize, usize)> = edges
+        .iter()
+        .map(|edge| {
+            let (v0, v1) = edge.absolute_ends();
+            (vertices.index[&v0.id()], vertices.index[&v1.id()])
+        })
+        .collect();
+    let glued = glued_edges(&edges, &ends, tolerance);
+    let mut edge_tolerances = vec![None; edges.len()];
+    glued.iter().zip(&edges).for_each(|((rep, _), edge)| {
+        edge_tolerances[*rep] = max_tolerance(edge_tolerances[*rep], edge.tolerance());
+    });
+    let edge_index: HashMap<EdgeID, usize> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, edge)| (edge.id(), i))
+        .collect();
+    let new_edges: Vec<Option<Edge>> = (0..edges.len())
+        .map(|i| {
+            let (v0, v1) = ends[i];
+            match glued[i].0 == i && v0 != v1 {
+                true => {
+                    let (v0, v1) = (&vertices.vertices[v0], &vertices.vertices[v1]);
+                    let edge = Edge::new(v0, v1, edges[i].get_curve());
+                    edge.set_tolerance(edge_tolerances[i]);
+                    Some(edge)
+                }
+                false => None,
+            }
+        })
+        .collect();
+    let mut new_faces: Vec<Face> = faces
+        .iter()
+        .filter_map(|face| {
+            let wires: Vec<Wire> = face
+                .absolute_boundaries()
+                .iter()
+                .map(|wire| {
+                    wire.iter()
+                        .filter_map(|edge| {
+                            let (rep, same) = glued[edge_index[&edge.id()]];
+                            let new_edge = new_edges[rep].as_ref()?;
+                            match edge.orientation() == same {
+                                true => Some(new_edge.clone()),
+                                false => Some(new_edge.inverse()),
+                            }
+                        })
+                        .collect::<Wire>()
+                })
+                .filter(|wire| !wire.is_empty())
+                .collect();
+            if wires.is_empty() {
+                return None;
+            }
+            let mut new_face = Face::debug_new(wires, face.get_surface());
+            if !face.orientation() {
+                new_face.invert();
+            }
+            Some(new_face)
+        })
+        .collect();
+    let (shells, free_edges) = connected_shells(&mut new_faces);
+    SewedShells { shells, free_edges }
+}
+
+/// the edges of the boundaries of faces, without duplications
+fn boundary_edges(faces: &[Face]) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    let mut ids = HashMap::new();
+    faces
+        .iter()
+        .flat_map(|face| face.absolute_boundaries().iter().flatten())
+        .for_each(|edge| {
+            ids.entry(edge.id()).or_insert_with(|| edges.push(edge.clone()));
+        });
+    edges
+}
+
+struct MergedVertices {
+    /// the indices of the merged vertices of the original vertices
+    index: HashMap<VertexID, usize>,
+    vertices: Vec<Vertex>,
+}
+
+/// Merges the vertices of `edges` closer than `tolerance`.
+fn merged_vertices(edges: &[Edge], tolerance: f64) -> MergedVertices {
+    let mut ids = HashMap::new();
+    let mut points = Vec::new();
+    let mut tolerances = Vec::new();
+    edges
+        .iter()
+        .flat_map(|edge| vec![edge.absolute_front(), edge.absolute_back()])
+        .for_each(|vertex| {
+            ids.entry(vertex.id()).or_insert_with(|| {
+                points.push(vertex.get_point());
+                tolerances.push(vertex.tolerance());
+                points.len() - 1
+            });
+        });
+    // the tolerance for merging the i-th and the j-th vertices
+    let pair_tolerance = |i: usize, j: usize| {
+        max_tolerance(tolerances[i], tolerances[j]).map_or(tolerance, |tol| tol.max(tolerance))
+    };
+    let max_tol = tolerances.iter().flatten().fold(tolerance, |tol, x| f64::max(tol, *x));
+    let mut parents: Vec<usize> = (0..points.len()).collect();
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|i, j| points[*i][0].partial_cmp(&points[*j][0]).unwrap());
+    for (k, i) in order.iter().enumerate() {
+        for j in &order[k + 1..] {
+            if points[*j][0] - points[*i][0] > max_tol {
+                break;
+            }
+            if points[*i].distance(points[*j]) <= pair_tolerance(*i, *j) {
+                union(&mut parents, *i, *j);
+            }
+        }
+    }
+    let mut vertices = Vec::<Vertex>::new();
+    let mut roots = HashMap::new();
+    let merged: Vec<usize> = (0..points.len())
+        .map(|i| {
+            let root = find(&mut parents, i);
+            let idx = *roots.entry(root).or_insert_with(|| {
+                vertices.push(Vertex::new(points[root]));
+                vertices.len() - 1
+            });
+            let vertex = &vertices[idx];
+            vertex.set_tolerance(max_tolerance(vertex.tolerance(), tolerances[i]));
+            idx
+        })
+        .collect();
+    let index = ids.into_iter().map(|(id, i)| (id, merged[i])).collect();
+    MergedVertices { index, vertices }
+}
+
+/// Returns the index of the representative edge of each edge, and whether the edge has
+/// the same direction as the representative or not.
+fn glued_edges(edges: &[Edge], ends: &[(usize, usize)], tolerance: f64) -> Vec<(usize, bool)> {
+    let mut groups = HashMap::<(usize, usize), Vec<usize>>::new();
+    ends.iter().enumerate().for_each(|(i, (v0, v1))| {
+        groups.entry((*v0.min(v1), *v0.max(v1))).or_default().push(i)
+    });
+    let mut glued: Vec<(usize, bool)> = (0..edges.len()).map(|i| (i, true)).collect();
+    let mut matched = vec![false; edges.len()];
+    let mut keys: Vec<_> = groups.keys().copied().collect();
+    keys.sort_unstable();
+    for key in keys {
+        let group = &groups[&key];
+        for (k, i) in group.iter().enumerate() {
+            if matched[*i] {
+                continue;
+            }
+            let curve = edges[*i].get_curve();
+            let partner = group[k + 1..].iter().find(|j| {
+                let tol = max_tolerance(edges[*i].tolerance(), edges[**j].tolerance())
+                    .map_or(tolerance, |tol| tol.max(tolerance));
+                !matched[**j] && coincide(&curve, &edges[**j].get_curve(), tol)
+            });
+            if let Some(j) = partner {
+                matched[*i] = true;
+                matched[*j] = true;
+                glued[*j] = (*i, ends[*i].0 == ends[*j].0);
+            }
+        }
+    }
+    glued
+}
+
+/// Whether the sample points of `curve0` are on `curve1` and vice versa.
+fn coincide(curve0: &Curve, curve1: &Curve, tolerance: f64) -> bool {
+    const DIVISION: usize = 8;
+    let on = |curve0: &Curve, curve1: &Curve| {
+        let (t0, t1) = curve0.parameter_range();
+        (1..DIVISION).all(|i| {
+            let pt = curve0.subs(t0 + (t1 - t0) * i as f64 / DIVISION as f64);
+            curve1
+                .search_nearest_parameter(pt, None, 100)
+                .map(|t| curve1.subs(t).distance(pt) <= tolerance)
+                .unwrap_or(false)
+        })
+    };
+    on(curve0, curve1) && on(curve1, curve0)
+}
+
+/// Divides `faces` into connected shells, inverting the faces for consistent orientations.
+/// Returns the shells and the free edges.
+fn connected_shells(faces: &mut [Face]) -> (Vec<Shell>, Vec<Edge>) {
+    let mut edge_faces = HashMap::<EdgeID, Vec<(usize, bool)>>::new();
+    let mut edges = Vec::new();
+    faces.iter().enumerate().for_each(|(i, face)| {
+        face.boundaries().iter().flatten().for_each(|edge| {
+            let uses = edge_faces.entry(edge.id()).or_insert_with(|| {
+                edges.push(edge.clone());
+                Vec::new()
+            });
+            uses.push((i, edge.orientation()));
+        })
+    });
+    let mut component = vec![None; faces.len()];
+    let mut inverted = vec![false; faces.len()];
+    let mut shells = Vec::new();
+    for start in 0..faces.len() {
+        if component[start].is_some() {
+            continue;
+        }
+        component[start] = Some(shells.len());
+        let mut shell = Vec::new();
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(i) = queue.pop_front() {
+            shell.push(i);
+            for edge in faces[i].boundaries().iter().flatten() {
+                let uses = &edge_faces[&edge.id()];
+                let orientation = edge.orientation() != inverted[i];
+                for (j, other) in uses {
+                    if component[*j].is_none() {
+                        component[*j] = Some(shells.len());
+                        // the faces sharing an edge traverse it in the opposite directions
+                        inverted[*j] = uses.len() == 2 && *other == orientation;
+                        queue.push_back(*j);
+                    }
+                }
+            }
+        }
+        shells.push(shell);
+    }
+    faces
+        .iter_mut()
+        .zip(inverted)
+        .filter(|(_, inverted)| *inverted)
+        .for_each(|(face, _)| {
+            face.invert();
+        });
+    let shells = shells
+        .into_iter()
+        .map(|shell| shell.into_iter().map(|i| faces[i].clone()).collect())
+        .collect();
+    let free_edges = edges
+        .into_iter()
+        .filter(|edge| edge_faces[&edge.id()].len() == 1)
+        .collect();
+    (shells, free_edges)
+}
+
+/// the larger tolerance, or the given one if the other is `None`
+fn max_tolerance(tol0: Option<f64>, tol1: Option<f64>) -> Option<f64> {
+    match (tol0, tol1) {
+        (Some(tol0), Some(tol1)) => Some(f64::max(tol0, tol1)),
+        (tol0, tol1) => tol0.or(tol1),
+    }
+}
+
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn union(parents: &mut [usize], i: usize, j: usize) {
+    let (i, j) = (find(parents, i), find(parents, j));
+    parents[i.max(j)] = i.min(j);
+}
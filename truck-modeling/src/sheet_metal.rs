@@ -0,0 +1,252 @@
+use crate::errors::Error;
+use crate::*;
+use std::f64::consts::PI;
+
+/// A bend along an edge of the base flange, followed by a flat flange.
+///
+/// The angle is the one between the base flange and the flange. A positive angle bends
+/// the flange toward the normal of the base flange, and a negative one bends it to the
+/// opposite side.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bend {
+    /// the bend angle, whose absolute value is in `(0, π)`
+    pub angle: Rad<f64>,
+    /// the inner radius of the bend, which is positive
+    pub radius: f64,
+    /// the ratio of the distance from the inner surface to the neutral fiber to the thickness,
+    /// which is in `[0, 1]`
+    pub k_factor: f64,
+    /// the length of the flat flange after the bend, which is not negative
+    pub length: f64,
+}
+
+impl Bend {
+    /// Returns the length of the neutral fiber of the bend of a sheet of `thickness`,
+    /// the bend allowance `|angle| * (radius + k_factor * thickness)`.
+    #[inline(always)]
+    pub fn allowance(&self, thickness: f64) -> f64 {
+        self.angle.0.abs() * (self.radius + self.k_factor * thickness)
+    }
+
+    fn is_valid(&self) -> bool {
+        0.0 < self.angle.0.abs()
+            && self.angle.0.abs() < PI
+            && self.radius > 0.0
+            && (0.0..=1.0).contains(&self.k_factor)
+            && self.length >= 0.0
+    }
+}
+
+/// Sheet metal part, a base flange and the flanges bent along its edges.
+///
+/// The part is kept as the parameters of the features, from which both the folded solid and
+/// the flat pattern are produced.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::sheet_metal::*;
+///
+/// // the square [0, 1] x [0, 1] turning counterclockwise, whose normal is +z
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let square = builder::tsweep(&e, Vector3::unit_y());
+/// let profile = square.boundaries()[0].clone();
+///
+/// let mut part = SheetMetal::try_base_flange(&profile, 0.1).unwrap();
+/// let bend = Bend {
+///     angle: Rad(std::f64::consts::PI / 2.0),
+///     radius: 0.2,
+///     k_factor: 0.5,
+///     length: 0.5,
+/// };
+/// part.try_add_bend(&profile[0], bend).unwrap();
+///
+/// // the flange along the edge on y = 0 stands up
+/// let folded = part.folded().unwrap();
+/// assert_eq!(folded.boundaries()[0].shell_condition(), ShellCondition::Closed);
+/// let top = folded.boundaries()[0]
+///     .vertex_iter()
+///     .map(|v| v.get_point()[2])
+///     .fold(f64::NEG_INFINITY, f64::max);
+/// assert_near!(top, 0.3 + 0.5);
+///
+/// // the flange is unrolled in the flat pattern
+/// let flat = part.flat_pattern().unwrap();
+/// let bottom = flat.boundaries()[0]
+///     .vertex_iter()
+///     .map(|v| v.get_point()[1])
+///     .fold(f64::INFINITY, f64::min);
+/// assert_near!(bottom, -(bend.allowance(0.1) + 0.5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SheetMetal {
+    base: Face,
+    thickness: f64,
+    bends: Vec<(usize, Bend)>,
+}
+
+impl SheetMetal {
+    /// Creates the base flange from the closed `profile` and `thickness`.
+    ///
+    /// The profile has to be a closed wire of lines on one plane. The sheet is thickened along
+    /// the normal of the plane attached by [`builder::try_attach_plane`], i.e. toward the side
+    /// from which the profile is seen counterclockwise.
+    /// # Failures
+    /// - Returns [`Error::NotSheetProfile`] if `profile` is not a closed wire of lines.
+    /// - Returns [`Error::InvalidSheetParameter`] if `thickness` is not positive.
+    /// - Returns the error of [`builder::try_attach_plane`] if `profile` is not on one plane.
+    ///
+    /// [`builder::try_attach_plane`]: ../builder/fn.try_attach_plane.html
+    /// [`Error::NotSheetProfile`]: ../errors/enum.Error.html#variant.NotSheetProfile
+    /// [`Error::InvalidSheetParameter`]: ../errors/enum.Error.html#variant.InvalidSheetParameter
+    pub fn try_base_flange(profile: &Wire, thickness: f64) -> Result<SheetMetal> {
+        if !profile.is_closed() || !profile.iter().all(is_line) {
+            return Err(Error::NotSheetProfile);
+        }
+        if thickness <= 0.0 {
+            return Err(Error::InvalidSheetParameter);
+        }
+        let base = builder::try_attach_plane(&vec![profile.clone()])?;
+        Ok(SheetMetal {
+            base,
+            thickness,
+            bends: Vec::new(),
+        })
+    }
+
+    /// Returns the thickness of the sheet.
+    #[inline(always)]
+    pub fn thickness(&self) -> f64 { self.thickness }
+
+    /// Returns the face of the base flange, whose normal is the direction of the thickness.
+    #[inline(always)]
+    pub fn base_face(&self) -> &Face { &self.base }
+
+    /// Returns the bends with the indices of the edges in the profile.
+    #[inline(always)]
+    pub fn bends(&self) -> &[(usize, Bend)] { &self.bends }
+
+    /// Adds `bend` along `edge` of the profile of the base flange.
+    ///
+    /// The bends on the edges around a convex corner do not interfere with each other. The
+    /// flanges must not reach the other parts of the sheet.
+    /// # Failures
+    /// - Returns [`Error::EdgeNotBendable`] if `edge` is not an edge of the profile or is
+    ///   already bent.
+    /// - Returns [`Error::InvalidSheetParameter`] if the parameters of `bend` are out of range.
+    ///
+    /// [`Error::EdgeNotBendable`]: ../errors/enum.Error.html#variant.EdgeNotBendable
+    /// [`Error::InvalidSheetParameter`]: ../errors/enum.Error.html#variant.InvalidSheetParameter
+    pub fn try_add_bend(&mut self, edge: &Edge, bend: Bend) -> Result<()> {
+        let idx = self.base.boundaries()[0]
+            .iter()
+            .position(|e| e.id() == edge.id())
+            .ok_or(Error::EdgeNotBendable)?;
+        if self.bends.iter().any(|(i, _)| *i == idx) {
+            return Err(Error::EdgeNotBendable);
+        }
+        if !bend.is_valid() {
+            return Err(Error::InvalidSheetParameter);
+        }
+        self.bends.push((idx, bend));
+        Ok(())
+    }
+
+    /// the normal of the base flange
+    fn normal(&self) -> Vector3 {
+        match self.base.oriented_surface() {
+            Surface::Plane(plane) => plane.normal(),
+            _ => unreachable!("the base flange is attached to a plane"),
+        }
+    }
+
+    /// the front point, the unit direction and the outer normal in the plane of the edge
+    fn edge_frame(&self, idx: usize) -> (Point3, Vector3, Vector3) {
+        let edge = &self.base.boundaries()[0][idx];
+        let (p, q) = (edge.front().get_point(), edge.back().get_point());
+        let dir = (q - p).normalize();
+        (p, dir, dir.cross(self.normal()))
+    }
+
+    /// Returns the folded solid of the sheet metal.
+    ///
+    /// Each bend is the revolution of the side face of the base flange along the edge, and
+    /// the flange is the extrusion of the end face of the bend.
+    pub fn folded(&self) -> Result<Solid> {
+        let normal = self.normal();
+        let plate = builder::tsweep(&self.base, normal * self.thickness);
+        let mut plate_shell = plate.into_boundaries().pop().unwrap();
+        let mut faces = Vec::new();
+        for (idx, bend) in &self.bends {
+            let (p, dir, outer) = self.edge_frame(*idx);
+            // the faces of the plate are the inverted base, the sides, and the top.
+            let side = &plate_shell[idx + 1];
+            let (origin, axis) = match bend.angle.0 > 0.0 {
+                true => (p + normal * (self.thickness + bend.radius), -dir),
+                false => (p - normal * bend.radius, dir),
+            };
+            let arc = builder::rsweep(side, origin, axis, Rad(bend.angle.0.abs()));
+            let mut arc_shell = arc.into_boundaries().pop().unwrap();
+            let end = arc_shell.pop().unwrap();
+            faces.extend(arc_shell.into_iter().skip(1));
+            match bend.length > 0.0 {
+                true => {
+                    let (sin, cos) = bend.angle.0.sin_cos();
+                    let flange_dir = outer * cos + normal * sin;
+                    let flange = builder::tsweep(&end, flange_dir * bend.length);
+                    faces.extend(flange.into_boundaries().pop().unwrap().into_iter().skip(1));
+                }
+                false => faces.push(end),
+            }
+        }
+        let mut bent = self
+            .bends
+            .iter()
+            .map(|(idx, _)| idx + 1)
+            .collect::<Vec<_>>();
+        bent.sort_unstable();
+        bent.into_iter().rev().for_each(|i| {
+            plate_shell.remove(i);
+        });
+        plate_shell.extend(faces);
+        Ok(Solid::try_new(vec![plate_shell])?)
+    }
+
+    /// Returns the flat pattern of the sheet metal, the face on the plane of the base flange.
+    ///
+    /// Each bent edge is pushed outward by the bend allowance and the length of the flange.
+    /// The flat solid is given by sweeping the face by the thickness along its normal.
+    pub fn flat_pattern(&self) -> Result<Face> {
+        let boundary = &self.base.boundaries()[0];
+        let mut points = Vec::new();
+        for (idx, edge) in boundary.iter().enumerate() {
+            points.push(edge.front().get_point());
+            if let Some((_, bend)) = self.bends.iter().find(|(i, _)| *i == idx) {
+                let (_, _, outer) = self.edge_frame(idx);
+                let offset = outer * (bend.allowance(self.thickness) + bend.length);
+                points.push(edge.front().get_point() + offset);
+                points.push(edge.back().get_point() + offset);
+            }
+        }
+        let vertices = points.into_iter().map(builder::vertex).collect::<Vec<_>>();
+        let wire: Wire = (0..vertices.len())
+            .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % vertices.len()]))
+            .collect();
+        builder::try_attach_plane(&vec![wire])
+    }
+}
+
+/// whether the curve of `edge` is a line segment
+fn is_line(edge: &Edge) -> bool {
+    let (p, q) = (edge.front().get_point(), edge.back().get_point());
+    if p.near(&q) {
+        return false;
+    }
+    let dir = (q - p).normalize();
+    edge.oriented_curve()
+        .lift_up()
+        .control_points()
+        .iter()
+        .map(|pt| pt.to_point())
+        .all(|pt| (pt - p).cross(dir).so_small())
+}
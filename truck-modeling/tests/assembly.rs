@@ -0,0 +1,121 @@
+use truck_modeling::*;
+use truck_polymesh::Faces;
+mod common;
+use common::cube;
+
+fn triangle() -> PolygonMesh {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    PolygonMesh::new(
+        positions,
+        Vec::new(),
+        Vec::new(),
+        Faces::from_iter([[0, 1, 2]]),
+    )
+}
+
+fn sample_assembly() -> Assembly {
+    let mut assembly = Assembly::new();
+    let cube = assembly.add_part(cube());
+    let triangle = assembly.add_part(triangle());
+    let mut root = Component::new("root", Matrix4::from_translation(Vector3::unit_z()), None);
+    root.metadata_mut()
+        .insert("material".to_string(), "steel".to_string());
+    let mut arm = Component::new("arm", Matrix4::from_scale(2.0), Some(cube));
+    arm.push_child(Component::new(
+        "plate",
+        Matrix4::from_translation(Vector3::unit_x()),
+        Some(triangle),
+    ));
+    root.push_child(arm);
+    assembly.push(root);
+    assembly.push(Component::new("base", Matrix4::identity(), Some(cube)));
+    assembly
+}
+
+#[test]
+fn iterate_and_flatten() {
+    let assembly = sample_assembly();
+    let names: Vec<&str> = assembly.iter().map(|(c, _)| c.name()).collect();
+    assert_eq!(names, vec!["root", "arm", "plate", "base"]);
+    let (_, mat) = assembly.iter().nth(2).unwrap();
+    assert_near!(
+        mat.transform_point(Point3::origin()),
+        Point3::new(2.0, 0.0, 1.0)
+    );
+    assert_eq!(assembly.instances().count(), 3);
+
+    let solids = assembly.flatten_solids();
+    assert_eq!(solids.len(), 2);
+    let bdb: BoundingBox<Point3> = solids[0].boundaries()[0]
+        .vertex_iter()
+        .map(|v| v.get_point())
+        .collect();
+    assert_near!(*bdb.min(), Point3::new(0.0, 0.0, 1.0));
+    assert_near!(*bdb.max(), Point3::new(2.0, 2.0, 3.0));
+
+    let mut count = 0;
+    let meshes = assembly
+        .flatten_meshes(|_| {
+            count += 1;
+            Some(triangle())
+        })
+        .unwrap();
+    // the shared cube is tessellated only once.
+    assert_eq!(count, 1);
+    assert_eq!(meshes.len(), 3);
+    assert_near!(meshes[1].positions()[1], Point3::new(4.0, 0.0, 1.0));
+    assert!(assembly.flatten_meshes(|_| None).is_none());
+
+    // the part placed by no components is not tessellated.
+    let mut assembly = sample_assembly();
+    assembly.add_part(cube());
+    let mut count = 0;
+    let meshes = assembly.flatten_meshes(|_| {
+        count += 1;
+        Some(triangle())
+    });
+    assert_eq!(meshes.unwrap().len(), 3);
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn invalid_part_index() {
+    let mut assembly = Assembly::new();
+    assembly.add_part(cube());
+    let mut component = Component::new("parent", Matrix4::identity(), None);
+    component.push_child(Component::new("child", Matrix4::identity(), Some(1)));
+    assert_eq!(
+        assembly.try_push(component.clone()),
+        Err(errors::Error::PartIndexOutOfRange(1, 1))
+    );
+    assert!(Assembly::try_from_parts(Vec::new(), vec![component]).is_err());
+}
+
+#[test]
+fn serialize_assembly() {
+    let assembly = sample_assembly();
+    let json = serde_json::to_string(&assembly).unwrap();
+    let assembly0: Assembly = serde_json::from_str(&json).unwrap();
+    assert_eq!(assembly0.parts().len(), 2);
+    assembly
+        .iter()
+        .zip(assembly0.iter())
+        .for_each(|((c, mat), (c0, mat0))| {
+            assert_eq!(c.name(), c0.name());
+            assert_eq!(c.part(), c0.part());
+            assert_eq!(c.metadata(), c0.metadata());
+            assert_near!(mat, mat0);
+        });
+    match &assembly0.parts()[0] {
+        Part::Solid(solid) => assert_eq!(solid.boundaries()[0].len(), 6),
+        _ => panic!("the part is not a solid"),
+    }
+
+    // the deserializer checks the indices of parts.
+    let json = json.replace("\"part\":1", "\"part\":2");
+    assert!(serde_json::from_str::<Assembly>(&json).is_err());
+}
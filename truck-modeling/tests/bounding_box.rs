@@ -0,0 +1,105 @@
+use std::f64::consts::PI;
+use truck_modeling::*;
+
+fn include(bdb: &BoundingBox<Point3>, pt: Point3) -> bool {
+    (0..3).all(|i| bdb.min()[i] - TOLERANCE < pt[i] && pt[i] < bdb.max()[i] + TOLERANCE)
+}
+
+// checks that the boxes include the sampled points of the curves and the bounded surfaces
+fn assert_conservative(solid: &Solid) {
+    let bdb = solid.bounding_box();
+    for face in solid.face_iter() {
+        let face_bdb = face.bounding_box();
+        assert!(include(&bdb, *face_bdb.min()) && include(&bdb, *face_bdb.max()));
+        for edge in face.boundaries().iter().flatten() {
+            let curve = edge.read_curve();
+            let (t0, t1) = curve.parameter_range();
+            for i in 0..=10 {
+                let pt = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+                assert!(include(&face_bdb, pt), "{:?} {:?}", face_bdb, pt);
+            }
+        }
+        let surface = face.read_surface();
+        let ((u0, u1), (v0, v1)) = match &*surface {
+            Surface::Plane(_) => continue,
+            Surface::BSplineSurface(surface) => surface.parameter_range(),
+            Surface::NURBSSurface(surface) => surface.parameter_range(),
+            Surface::RevolutedCurve(surface) => surface.parameter_range(),
+        };
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let u = u0 + (u1 - u0) * i as f64 / 10.0;
+                let v = v0 + (v1 - v0) * j as f64 / 10.0;
+                assert!(include(&face_bdb, surface.subs(u, v)));
+            }
+        }
+    }
+}
+
+#[test]
+fn bounding_box_of_cube() {
+    let v = builder::vertex(Point3::new(-1.0, 0.0, 2.0));
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, 2.0 * Vector3::unit_y());
+    let cube = builder::tsweep(&f, 3.0 * Vector3::unit_z());
+    // the planar faces are bounded by the boundaries
+    let bdb = cube.bounding_box();
+    assert_near!(*bdb.min(), Point3::new(-1.0, 0.0, 2.0));
+    assert_near!(*bdb.max(), Point3::new(0.0, 2.0, 5.0));
+    assert_conservative(&cube);
+    assert_eq!(cube.boundaries()[0].bounding_box(), bdb);
+    let top = cube.face_iter().last().unwrap();
+    assert!(top.bounding_box().size().near(&2.0));
+}
+
+#[test]
+fn bounding_box_of_revolutions() {
+    // the cylinder with radius 1 and height 1
+    let v = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_z());
+    let cylinder = builder::rsweep(&f, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+    let bdb = cylinder.bounding_box();
+    assert_near!(*bdb.min(), Point3::new(-1.0, -1.0, 0.0));
+    assert_near!(*bdb.max(), Point3::new(1.0, 1.0, 1.0));
+    assert_conservative(&cylinder);
+
+    // the unit sphere
+    let v = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    let wire = builder::rsweep(&v, Point3::origin(), Vector3::unit_y(), Rad(PI));
+    let shell = builder::cone(&wire, Vector3::unit_z(), Rad(2.0 * PI));
+    let sphere = Solid::new(vec![shell]);
+    let bdb = sphere.bounding_box();
+    assert!(include(&bdb, Point3::new(-1.0, -1.0, -1.0)));
+    assert!(include(&bdb, Point3::new(1.0, 1.0, 1.0)));
+    assert!(bdb.size() < 2.0 + TOLERANCE, "{:?}", bdb);
+    assert_conservative(&sphere);
+
+    // the boxes of the transformed surfaces are conservative
+    let rotated = builder::rotated(&cylinder, Point3::origin(), Vector3::unit_x(), Rad(PI / 4.0));
+    assert_conservative(&rotated);
+    let bdb = rotated.bounding_box();
+    assert!(bdb.size() < 3.0, "{:?}", bdb);
+}
+
+#[test]
+fn bounding_box_of_bspline_face() {
+    let surface = BSplineSurface::new(
+        (KnotVec::bezier_knot(2), KnotVec::bezier_knot(1)),
+        vec![
+            vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+            vec![Point3::new(0.5, 0.0, 1.0), Point3::new(0.5, 1.0, 1.0)],
+            vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+        ],
+    );
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let face = builder::tsweep(&e, Vector3::unit_y());
+    let bdb = face.bounding_box();
+    assert_near!(*bdb.min(), Point3::origin());
+    assert_near!(*bdb.max(), Point3::new(1.0, 1.0, 0.0));
+    // the box of the control points, higher than the surface whose height is 0.5
+    let face = Face::new(face.boundaries(), Surface::BSplineSurface(surface));
+    let bdb = face.bounding_box();
+    assert_near!(*bdb.max(), Point3::new(1.0, 1.0, 1.0));
+}
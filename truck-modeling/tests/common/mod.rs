@@ -0,0 +1,9 @@
+use truck_modeling::*;
+
+// the unit cube with the corner at the origin
+pub fn cube() -> Solid {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    builder::tsweep(&f, Vector3::unit_z())
+}
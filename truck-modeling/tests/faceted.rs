@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use truck_modeling::*;
+use truck_polymesh::Faces;
+
+// the triangles of the box whose sides are divided into `n` x `n` squares,
+// without the shared positions as STL files
+fn box_triangles(min: Point3, max: Point3, n: usize, inverted: bool) -> Vec<[Point3; 3]> {
+    let size = max - min;
+    let mut triangles = Vec::new();
+    for axis in 0..3 {
+        let (i, j) = ((axis + 1) % 3, (axis + 2) % 3);
+        for &side in &[0.0, 1.0] {
+            let point = |s: usize, t: usize| {
+                let mut pt = min;
+                pt[axis] += side * size[axis];
+                pt[i] += s as f64 / n as f64 * size[i];
+                pt[j] += t as f64 / n as f64 * size[j];
+                pt
+            };
+            for s in 0..n {
+                for t in 0..n {
+                    let quad = [
+                        point(s, t),
+                        point(s + 1, t),
+                        point(s + 1, t + 1),
+                        point(s, t + 1),
+                    ];
+                    let mut tris = [[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]];
+                    if (side == 0.0) != inverted {
+                        tris.iter_mut().for_each(|tri| tri.swap(1, 2));
+                    }
+                    triangles.extend_from_slice(&tris);
+                }
+            }
+        }
+    }
+    triangles
+}
+
+fn soup(triangles: &[[Point3; 3]]) -> PolygonMesh {
+    let positions: Vec<Point3> = triangles.iter().flatten().copied().collect();
+    let faces = Faces::from_iter((0..triangles.len()).map(|i| [3 * i, 3 * i + 1, 3 * i + 2]));
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+fn count_edges(shell: &Shell) -> usize {
+    shell.edge_iter().map(|edge| edge.id()).collect::<HashSet<_>>().len()
+}
+
+fn count_vertices(shell: &Shell) -> usize {
+    shell.vertex_iter().map(|v| v.id()).collect::<HashSet<_>>().len()
+}
+
+#[test]
+fn subdivided_box() {
+    let triangles = box_triangles(Point3::origin(), Point3::new(1.0, 2.0, 3.0), 3, false);
+    let solid = faceted::try_solid_from_mesh(&soup(&triangles), 1.0e-6).unwrap();
+    assert_eq!(solid.boundaries().len(), 1);
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 6);
+    assert_eq!(count_edges(shell), 12);
+    assert_eq!(count_vertices(shell), 8);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    for face in shell.face_iter() {
+        let normal = match face.oriented_surface() {
+            Surface::Plane(plane) => plane.normal(),
+            _ => panic!("the face is not planar"),
+        };
+        let center = face.boundaries()[0]
+            .vertex_iter()
+            .fold(Vector3::zero(), |sum, v| sum + v.get_point().to_vec())
+            / 4.0;
+        // the normals are outward
+        assert!(normal.dot(center - Vector3::new(0.5, 1.0, 1.5)) > 0.0);
+    }
+}
+
+#[test]
+fn box_with_cavity() {
+    let mut triangles = box_triangles(Point3::origin(), Point3::new(3.0, 3.0, 3.0), 2, false);
+    let cavity = box_triangles(Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 2.0, 2.0), 1, true);
+    triangles.extend(cavity);
+    let solid = faceted::try_solid_from_mesh(&soup(&triangles), 1.0e-6).unwrap();
+    assert_eq!(solid.boundaries().len(), 2);
+    for shell in solid.boundaries() {
+        assert_eq!(shell.len(), 6);
+        assert_eq!(count_edges(shell), 12);
+        assert_eq!(count_vertices(shell), 8);
+    }
+}
+
+#[test]
+fn perturbed_positions() {
+    let triangles = box_triangles(Point3::origin(), Point3::new(1.0, 1.0, 1.0), 2, false);
+    let triangles: Vec<[Point3; 3]> = triangles
+        .into_iter()
+        .enumerate()
+        .map(|(i, tri)| {
+            let delta = Vector3::new(1.0, -1.0, 1.0) * 1.0e-8 * (i % 3) as f64;
+            [tri[0] + delta, tri[1] - delta, tri[2] + delta]
+        })
+        .collect();
+    let solid = faceted::try_solid_from_mesh(&soup(&triangles), 1.0e-6).unwrap();
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 6);
+    assert_eq!(count_vertices(shell), 8);
+}
+
+#[test]
+fn open_mesh() {
+    let mut triangles = box_triangles(Point3::origin(), Point3::new(1.0, 1.0, 1.0), 1, false);
+    triangles.pop();
+    assert_eq!(
+        faceted::try_solid_from_mesh(&soup(&triangles), 1.0e-6).unwrap_err(),
+        errors::Error::NotFacetedSolid,
+    );
+}
@@ -0,0 +1,123 @@
+use builder::{FrameSweepOptions, SweepFrame};
+use std::f64::consts::PI;
+use truck_modeling::*;
+
+fn options(frame: SweepFrame, twist_rate: f64) -> FrameSweepOptions {
+    FrameSweepOptions {
+        frame,
+        twist_rate,
+        ..Default::default()
+    }
+}
+
+// the quarter of the unit circle on the plane z = 0, from (1, 0, 0) to (0, 1, 0)
+fn quarter_circle() -> Edge {
+    builder::circle_arc(
+        &builder::vertex(Point3::new(1.0, 0.0, 0.0)),
+        &builder::vertex(Point3::new(0.0, 1.0, 0.0)),
+        Point3::new(f64::sqrt(0.5), f64::sqrt(0.5), 0.0),
+    )
+}
+
+// the points on the curves of the edges of `wire`
+fn sample_points(wire: &Wire) -> Vec<Point3> {
+    wire.edge_iter()
+        .flat_map(|edge| {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            (0..=10).map(move |i| curve.subs(t0 + (t1 - t0) * i as f64 / 10.0))
+        })
+        .collect()
+}
+
+#[test]
+fn sweep_vertex_along_circle() {
+    let path = quarter_circle();
+    // the point above the path
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.3));
+    for frame in [
+        SweepFrame::RotationMinimizing,
+        SweepFrame::FixedUp(Vector3::unit_z()),
+        SweepFrame::Binormal(Vector3::unit_z()),
+    ] {
+        let wire = builder::frame_sweep(&v, &path, &options(frame, 0.0));
+        assert_eq!(wire.len(), 16);
+        let end = wire.back_vertex().unwrap().get_point();
+        assert_near!(end, Point3::new(0.0, 1.0, 0.3));
+        for pt in sample_points(&wire) {
+            assert!(f64::abs(Vector2::new(pt[0], pt[1]).magnitude() - 1.0) < 1.0e-4);
+            assert!(f64::abs(pt[2] - 0.3) < 1.0e-6);
+        }
+    }
+    // the point inside the circle, which is on the normal of the Frenet frame
+    let v = builder::vertex(Point3::new(0.8, 0.0, 0.0));
+    let wire = builder::frame_sweep(&v, &path, &options(SweepFrame::Frenet, 0.0));
+    let end = wire.back_vertex().unwrap().get_point();
+    assert_near!(end, Point3::new(0.0, 0.8, 0.0));
+}
+
+#[test]
+fn twisted_rectangle() {
+    let v = builder::vertex(Point3::new(-0.5, -0.25, 0.0));
+    let e = builder::tsweep(&v, Vector3::new(1.0, 0.0, 0.0));
+    let rect = builder::tsweep(&e, Vector3::new(0.0, 0.5, 0.0));
+    let path = builder::line(
+        &builder::vertex(Point3::origin()),
+        &builder::vertex(Point3::new(0.0, 0.0, 1.0)),
+    );
+    // a quarter turn along the path
+    let solid = builder::frame_sweep(&rect, &path, &options(SweepFrame::Frenet, PI / 2.0));
+    assert!(solid.is_geometric_consistent());
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell.len(), 4 * 16 + 2);
+    let top = shell.last().unwrap();
+    for v in top.boundaries()[0].vertex_iter() {
+        let pt = v.get_point();
+        assert_near!(pt[0].abs(), 0.25);
+        assert_near!(pt[1].abs(), 0.5);
+        assert_near!(pt[2], 1.0);
+    }
+}
+
+#[test]
+fn binormal_lock_along_helix() {
+    let path = builder::bezier(
+        &builder::vertex(Point3::origin()),
+        &builder::vertex(Point3::new(0.0, 2.0, 1.0)),
+        vec![Point3::new(2.0, 0.0, 0.3), Point3::new(2.0, 2.0, 0.6)],
+    );
+    // the point above the path keeps above the path
+    let v = builder::vertex(Point3::new(0.0, 0.0, 0.5));
+    let options = options(SweepFrame::Binormal(Vector3::unit_z()), 0.0);
+    let wire = builder::frame_sweep(&v, &path, &options);
+    let curve = path.oriented_curve();
+    let (t0, t1) = curve.parameter_range();
+    let vertices: Vec<Vertex> = wire.vertex_iter().collect();
+    for (i, v) in vertices.iter().enumerate() {
+        let pt = curve.subs(t0 + (t1 - t0) * i as f64 / 16.0);
+        assert_near!(v.get_point(), pt + Vector3::new(0.0, 0.0, 0.5));
+    }
+}
+
+#[test]
+fn closed_pipes() {
+    let v = builder::vertex(Point3::new(0.2, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+    let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+    let path = builder::bezier(
+        &builder::vertex(Point3::origin()),
+        &builder::vertex(Point3::new(2.0, 1.0, 2.0)),
+        vec![Point3::new(0.0, 0.0, 1.0), Point3::new(1.0, 2.0, 1.0)],
+    );
+    for frame in [
+        SweepFrame::Frenet,
+        SweepFrame::RotationMinimizing,
+        SweepFrame::FixedUp(Vector3::unit_x()),
+        SweepFrame::Binormal(Vector3::unit_x()),
+    ] {
+        let solid: Solid = builder::frame_sweep(&disk, &path, &options(frame, 1.0));
+        assert_eq!(solid.boundaries()[0].shell_condition(), ShellCondition::Closed);
+        assert!(solid.is_geometric_consistent());
+    }
+}
@@ -0,0 +1,90 @@
+use healing::{HealingOptions, HealingReport};
+use truck_modeling::*;
+mod common;
+use common::cube;
+
+// Checks that the ends of the curves are on the vertices.
+fn synchronized(solid: &Solid) -> bool {
+    solid.edge_iter().all(|edge| {
+        let curve = edge.get_curve();
+        let (t0, t1) = curve.parameter_range();
+        curve.subs(t0).near(&edge.absolute_front().get_point())
+            && curve.subs(t1).near(&edge.absolute_back().get_point())
+    })
+}
+
+#[test]
+fn heal_valid_solid() {
+    let cube = cube();
+    let (solid, report) = healing::heal(&cube, &HealingOptions::default()).unwrap();
+    assert_eq!(report, HealingReport::default());
+    assert_eq!(solid.face_iter().count(), 6);
+    assert_eq!(solid.edge_iter().count(), cube.edge_iter().count());
+}
+
+#[test]
+fn heal_gaps() {
+    let faces: Shell = cube().boundaries()[0]
+        .face_iter()
+        .enumerate()
+        .map(|(i, face)| {
+            let vector = Vector3::new(1.0, -2.0, 3.0).normalize() * 1.0e-5 * i as f64;
+            builder::translated(face, vector)
+        })
+        .collect();
+    let imported = Solid::new_unchecked(vec![faces]);
+    let condition = imported.boundaries()[0].shell_condition();
+    assert_ne!(condition, ShellCondition::Closed);
+    let (solid, report) = healing::heal(&imported, &HealingOptions::default()).unwrap();
+    assert_eq!(report.small_edges, 0);
+    assert_eq!(report.sliver_faces, 0);
+    assert_eq!(report.sewn_edges, 12);
+    assert!(report.resynchronized_curves > 0);
+    assert!(synchronized(&solid));
+
+    // the gaps wider than the tolerance
+    let options = HealingOptions {
+        tolerance: 1.0e-6,
+        ..Default::default()
+    };
+    assert!(healing::heal(&imported, &options).is_err());
+}
+
+#[test]
+fn heal_small_edge_and_sliver_face() {
+    // the pentagonal prism with a small edge, whose side face on the small edge is a sliver
+    let positions = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(1.0e-5, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let vertices: Vec<Vertex> = positions.iter().map(|pt| builder::vertex(*pt)).collect();
+    let wire: Wire = (0..5)
+        .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % 5]))
+        .collect();
+    let face = builder::try_attach_plane(&vec![wire]).unwrap();
+    let prism = builder::tsweep(&face, Vector3::unit_z());
+    assert_eq!(prism.face_iter().count(), 7);
+
+    let (solid, report) = healing::heal(&prism, &HealingOptions::default()).unwrap();
+    assert_eq!(report.small_edges, 2);
+    assert_eq!(report.sliver_faces, 1);
+    assert_eq!(report.sewn_edges, 1);
+    assert!(report.resynchronized_curves > 0);
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell.len(), 6);
+    let cube = cube();
+    assert_eq!(solid.edge_iter().count(), cube.edge_iter().count());
+    assert_eq!(solid.vertex_iter().count(), cube.vertex_iter().count());
+    assert!(synchronized(&solid));
+
+    // the shell is not closed if the sliver face is kept
+    let options = HealingOptions {
+        remove_sliver_faces: false,
+        ..Default::default()
+    };
+    assert!(healing::heal(&prism, &options).is_err());
+}
@@ -0,0 +1,81 @@
+use truck_modeling::*;
+mod common;
+use common::cube;
+
+// the square on the plane z = `z`, whose orientation is determined by `ccw`
+fn square(min: f64, max: f64, z: f64, ccw: bool) -> Wire {
+    let v = builder::vertex(Point3::new(min, min, z));
+    let e = builder::tsweep(&v, Vector3::new(max - min, 0.0, 0.0));
+    let face = builder::tsweep(&e, Vector3::new(0.0, max - min, 0.0));
+    match ccw {
+        true => face.boundaries()[0].clone(),
+        false => face.boundaries()[0].inverse(),
+    }
+}
+
+// the square frame, the letter "O"
+fn frame(z: f64) -> Vec<Wire> { vec![square(0.2, 0.8, z, true), square(0.4, 0.6, z, false)] }
+
+fn heights(solid: &Solid) -> (f64, f64) {
+    solid.boundaries()[0]
+        .vertex_iter()
+        .map(|v| v.get_point()[2])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), z| {
+            (f64::min(min, z), f64::max(max, z))
+        })
+}
+
+#[test]
+fn emboss_top() {
+    let cube = cube();
+    let top = cube.boundaries()[0].last().unwrap().clone();
+    let solid = marking::emboss(&cube, &top, &[frame(1.0)], 0.1).unwrap();
+    let shell = &solid.boundaries()[0];
+    // the eight sides and the top of the frame are added
+    assert_eq!(shell.len(), 15);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(solid.is_geometric_consistent());
+    assert_near!(heights(&solid).1, 1.1);
+}
+
+#[test]
+fn engrave_top_and_bottom() {
+    let cube = cube();
+    let top = cube.boundaries()[0].last().unwrap().clone();
+    let solid = marking::engrave(&cube, &top, &[frame(1.0)], 0.1).unwrap();
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 15);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(solid.is_geometric_consistent());
+    assert_eq!(heights(&solid), (0.0, 1.0));
+    let depth = shell.vertex_iter().filter(|v| v.get_point()[2] > 0.5);
+    assert!(depth.map(|v| v.get_point()[2]).any(|z| z.near(&0.9)));
+
+    // the outlines in the opposite orientation on the face with the normal -z
+    let bottom = cube.boundaries()[0][0].clone();
+    let outlines = [vec![square(0.2, 0.4, 0.0, false)], vec![square(0.6, 0.8, 0.0, true)]];
+    let solid = marking::engrave(&cube, &bottom, &outlines, 0.2).unwrap();
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 16);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(heights(&solid), (0.0, 1.0));
+}
+
+#[test]
+fn marking_errors() {
+    let cube = cube();
+    let top = cube.boundaries()[0].last().unwrap().clone();
+    let other = builder::translated(&top, Vector3::unit_x());
+    let res = marking::emboss(&cube, &other, &[frame(1.0)], 0.1);
+    assert_eq!(res.unwrap_err(), errors::Error::FaceNotInSolid);
+    let res = marking::emboss(&cube, &top, &[frame(1.5)], 0.1);
+    assert_eq!(res.unwrap_err(), errors::Error::WireNotInOnePlane);
+
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+    let cylinder = builder::tsweep(&disk, Vector3::unit_z());
+    let side = cylinder.boundaries()[0][1].clone();
+    let res = marking::engrave(&cylinder, &side, &[frame(1.0)], 0.1);
+    assert_eq!(res.unwrap_err(), errors::Error::NotPlanarFace);
+}
@@ -0,0 +1,107 @@
+use builder::{FrameSweepOptions, SweepFrame};
+use errors::Error;
+use std::f64::consts::PI;
+use truck_modeling::*;
+
+fn circle(radius: f64, z: f64) -> Wire {
+    let v = builder::vertex(Point3::new(radius, 0.0, z));
+    builder::rsweep(&v, Point3::new(0.0, 0.0, z), Vector3::unit_z(), Rad(2.0 * PI))
+}
+
+// the rectangle centered at `center` on the plane perpendicular to the z-axis
+fn rectangle(center: Point3, width: f64, height: f64) -> Wire {
+    let v = builder::vertex(center - Vector3::new(width / 2.0, height / 2.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::new(width, 0.0, 0.0));
+    let face = builder::tsweep(&e, Vector3::new(0.0, height, 0.0));
+    face.boundaries()[0].clone()
+}
+
+fn line(z: f64) -> Edge {
+    builder::line(
+        &builder::vertex(Point3::origin()),
+        &builder::vertex(Point3::new(0.0, 0.0, z)),
+    )
+}
+
+#[test]
+fn duct_through_profiles() {
+    let profiles = vec![circle(0.5, 0.0), circle(1.0, 1.0), circle(0.5, 2.0)];
+    let options = FrameSweepOptions {
+        division: 4,
+        ..Default::default()
+    };
+    let duct = builder::try_multi_section_sweep(&profiles, &line(2.0), &options).unwrap();
+    assert!(duct.is_geometric_consistent());
+    let shell = &duct.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell.len(), 2 * 2 * 4 + 2);
+    // the profiles are reproduced
+    for v in shell.vertex_iter() {
+        let pt = v.get_point();
+        let radius = Vector2::new(pt[0], pt[1]).magnitude();
+        match pt[2] {
+            z if z.near(&1.0) => assert_near!(radius, 1.0),
+            z if z.near(&0.0) || z.near(&2.0) => assert_near!(radius, 0.5),
+            _ => assert!(0.5 < radius && radius < 1.0),
+        }
+    }
+    // the first cap faces the opposite of the path
+    let normal = shell[0].oriented_surface().normal(0.5, 0.5);
+    assert!(normal[2] < 0.0);
+}
+
+#[test]
+fn inverted_profiles() {
+    let profiles = vec![circle(0.5, 0.0).inverse(), circle(1.0, 1.0).inverse()];
+    let options = FrameSweepOptions::default();
+    let duct = builder::try_multi_section_sweep(&profiles, &line(1.0), &options).unwrap();
+    assert!(duct.is_geometric_consistent());
+    let shell = &duct.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    let normal = shell[0].oriented_surface().normal(0.5, 0.5);
+    assert!(normal[2] < 0.0);
+}
+
+#[test]
+fn twisted_rectangles_along_curve() {
+    let path = builder::bezier(
+        &builder::vertex(Point3::origin()),
+        &builder::vertex(Point3::new(2.0, 0.0, 2.0)),
+        vec![Point3::new(0.0, 0.0, 1.0), Point3::new(1.0, 0.0, 2.0)],
+    );
+    // the profiles perpendicular to the path at its ends
+    let end = builder::rotated(
+        &rectangle(Point3::new(0.0, 0.0, 0.0), 0.4, 0.4),
+        Point3::origin(),
+        Vector3::unit_y(),
+        Rad(PI / 2.0),
+    );
+    let end = builder::translated(&end, Vector3::new(2.0, 0.0, 2.0));
+    let profiles = vec![rectangle(Point3::origin(), 1.0, 0.5), end];
+    for frame in [SweepFrame::Frenet, SweepFrame::RotationMinimizing] {
+        let options = FrameSweepOptions {
+            frame,
+            twist_rate: 0.3,
+            division: 8,
+        };
+        let solid = builder::try_multi_section_sweep(&profiles, &path, &options).unwrap();
+        assert!(solid.is_geometric_consistent());
+        let shell = &solid.boundaries()[0];
+        assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+        assert_eq!(shell.len(), 4 * 8 + 2);
+    }
+}
+
+#[test]
+fn invalid_profiles() {
+    let options = FrameSweepOptions::default();
+    let profiles = vec![circle(0.5, 0.0)];
+    let res = builder::try_multi_section_sweep(&profiles, &line(1.0), &options);
+    assert_eq!(res.unwrap_err(), Error::IncompatibleProfiles);
+    let profiles = vec![circle(0.5, 0.0), rectangle(Point3::new(0.0, 0.0, 1.0), 1.0, 1.0)];
+    let res = builder::try_multi_section_sweep(&profiles, &line(1.0), &options);
+    assert_eq!(res.unwrap_err(), Error::IncompatibleProfiles);
+    let profiles = vec![circle(0.5, 1.0), circle(1.0, 0.0)];
+    let res = builder::try_multi_section_sweep(&profiles, &line(1.0), &options);
+    assert_eq!(res.unwrap_err(), Error::ProfilesNotInOrder);
+}
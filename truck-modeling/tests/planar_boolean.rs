@@ -0,0 +1,203 @@
+use std::f64::consts::PI;
+use truck_modeling::*;
+
+// the rectangle [x0, x1] x [y0, y1] on the xy-plane, whose orientation is determined by `ccw`
+fn rectangle(x0: f64, y0: f64, x1: f64, y1: f64, ccw: bool) -> Wire {
+    let v = builder::vertex(Point3::new(x0, y0, 0.0));
+    let e = builder::tsweep(&v, Vector3::new(x1 - x0, 0.0, 0.0));
+    let face = builder::tsweep(&e, Vector3::new(0.0, y1 - y0, 0.0));
+    match ccw {
+        true => face.boundaries()[0].clone(),
+        false => face.boundaries()[0].inverse(),
+    }
+}
+
+fn circle(center: Point3, radius: f64) -> Wire {
+    let v = builder::vertex(center + Vector3::new(radius, 0.0, 0.0));
+    builder::rsweep(&v, center, Vector3::unit_z(), Rad(2.0 * PI))
+}
+
+// the signed area of the wire on the xy-plane
+fn area(wire: &Wire) -> f64 {
+    let points: Vec<Point3> = wire
+        .edge_iter()
+        .flat_map(|edge| {
+            let curve = edge.oriented_curve();
+            let mut params = curve.parameter_division(curve.parameter_range(), 0.001);
+            params.pop();
+            params.into_iter().map(move |t| curve.subs(t))
+        })
+        .collect();
+    (0..points.len()).fold(0.0, |sum, i| {
+        let (p, q) = (points[i], points[(i + 1) % points.len()]);
+        sum + (p[0] * q[1] - p[1] * q[0]) / 2.0
+    })
+}
+
+// the total area of the regions
+fn total_area(regions: &[Vec<Wire>]) -> f64 {
+    regions.iter().flatten().map(area).sum()
+}
+
+// the regions are the boundaries of planar faces
+fn check_regions(regions: &[Vec<Wire>]) {
+    for region in regions {
+        assert!(area(&region[0]) > 0.0);
+        assert!(region[1..].iter().all(|hole| area(hole) < 0.0));
+        assert!(region.iter().all(Wire::is_closed));
+        builder::try_attach_plane(region).unwrap();
+    }
+}
+
+#[test]
+fn overlapping_squares() {
+    let wire0 = [rectangle(0.0, 0.0, 2.0, 2.0, true)];
+    let wire1 = [rectangle(1.0, 1.0, 3.0, 3.0, false)];
+
+    let regions = planar_boolean::union(&wire0, &wire1, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_near!(total_area(&regions), 7.0);
+
+    let regions = planar_boolean::intersection(&wire0, &wire1, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0][0].len(), 4);
+    assert_near!(total_area(&regions), 1.0);
+
+    let regions = planar_boolean::difference(&wire0, &wire1, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0][0].len(), 6);
+    assert_near!(total_area(&regions), 3.0);
+
+    // the disjoint squares
+    let wire2 = [rectangle(5.0, 0.0, 6.0, 1.0, true)];
+    let regions = planar_boolean::union(&wire0, &wire2, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 2);
+    assert_near!(total_area(&regions), 5.0);
+    let regions = planar_boolean::intersection(&wire0, &wire2, 0.01).unwrap();
+    assert!(regions.is_empty());
+}
+
+#[test]
+fn regions_with_holes() {
+    // the square frame
+    let frame = [
+        rectangle(0.0, 0.0, 3.0, 3.0, true),
+        rectangle(1.0, 1.0, 2.0, 2.0, false),
+    ];
+    // the bar crossing the frame
+    let bar = [rectangle(-1.0, 1.25, 4.0, 1.75, true)];
+
+    let regions = planar_boolean::union(&frame, &bar, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].len(), 3);
+    assert_near!(total_area(&regions), 8.0 + 1.0 + 0.5);
+
+    let regions = planar_boolean::intersection(&frame, &bar, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 2);
+    assert_near!(total_area(&regions), 1.0);
+
+    let regions = planar_boolean::difference(&frame, &bar, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 2);
+    assert!(regions.iter().all(|region| region.len() == 1));
+    assert_near!(total_area(&regions), 7.0);
+
+    // the hole in the middle of the square
+    let square = [rectangle(0.0, 0.0, 3.0, 3.0, true)];
+    let inner = [rectangle(1.0, 1.0, 2.0, 2.0, true)];
+    let regions = planar_boolean::difference(&square, &inner, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].len(), 2);
+    let regions = planar_boolean::union(&square, &inner, 0.01).unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].len(), 1);
+    assert_near!(total_area(&regions), 9.0);
+}
+
+#[test]
+fn sharing_edges() {
+    let wire0 = [rectangle(0.0, 0.0, 1.0, 1.0, true)];
+    let wire1 = [rectangle(1.0, 0.0, 2.0, 1.0, true)];
+    let regions = planar_boolean::union(&wire0, &wire1, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].len(), 1);
+    assert_near!(total_area(&regions), 2.0);
+    let regions = planar_boolean::difference(&wire0, &wire1, 0.01).unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_near!(total_area(&regions), 1.0);
+
+    // the rectangle sharing a part of an edge
+    let wire2 = [rectangle(0.5, -1.0, 1.5, 0.0, true)];
+    let regions = planar_boolean::union(&wire0, &wire2, 0.01).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0][0].len(), 8);
+    assert_near!(total_area(&regions), 2.0);
+
+    // the identical squares
+    let regions = planar_boolean::union(&wire0, &wire0, 0.01).unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0][0].len(), 4);
+    let regions = planar_boolean::difference(&wire0, &wire0, 0.01).unwrap();
+    assert!(regions.is_empty());
+}
+
+#[test]
+fn circles() {
+    let square = [rectangle(0.0, 0.0, 2.0, 2.0, true)];
+    let disk = [circle(Point3::new(2.0, 2.0, 0.0), 1.0)];
+
+    let regions = planar_boolean::intersection(&square, &disk, 0.001).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert!(f64::abs(total_area(&regions) - PI / 4.0) < 1.0e-2);
+    // the arc is on the exact circle
+    let center = Point3::new(2.0, 2.0, 0.0);
+    let arcs = regions[0][0]
+        .edge_iter()
+        .filter(|edge| {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.parameter_range();
+            curve.subs((t0 + t1) / 2.0).distance(center).near(&1.0)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(arcs.len(), 1);
+    assert_near!(arcs[0].front().get_point(), Point3::new(1.0, 2.0, 0.0));
+    assert_near!(arcs[0].back().get_point(), Point3::new(2.0, 1.0, 0.0));
+
+    let regions = planar_boolean::difference(&square, &disk, 0.001).unwrap();
+    check_regions(&regions);
+    assert!(f64::abs(total_area(&regions) - (4.0 - PI / 4.0)) < 1.0e-2);
+
+    // the disk in the square
+    let disk = [circle(Point3::new(1.0, 1.0, 0.0), 0.5)];
+    let regions = planar_boolean::difference(&square, &disk, 0.001).unwrap();
+    check_regions(&regions);
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].len(), 2);
+    assert!(f64::abs(total_area(&regions) - (4.0 - PI / 4.0)) < 1.0e-2);
+}
+
+#[test]
+fn invalid_wires() {
+    let square = [rectangle(0.0, 0.0, 1.0, 1.0, true)];
+    let lifted = [builder::translated(
+        &rectangle(0.0, 0.0, 1.0, 1.0, true),
+        Vector3::new(0.0, 0.0, 1.0),
+    )];
+    assert!(matches!(
+        planar_boolean::union(&square, &lifted, 0.01),
+        Err(errors::Error::WireNotInOnePlane),
+    ));
+    let mut open = rectangle(0.0, 0.0, 1.0, 1.0, true);
+    open.pop_back();
+    assert!(planar_boolean::union(&square, &[open], 0.01).is_err());
+}
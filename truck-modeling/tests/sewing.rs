@@ -0,0 +1,99 @@
+use truck_modeling::*;
+mod common;
+use common::cube;
+
+// the cylinder whose side is divided by the seam
+fn cylinder() -> Solid {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+    builder::tsweep(&disk, Vector3::new(0.0, 0.0, 2.0))
+}
+
+// Separates the faces of `solid` with the small gaps and the random orientations.
+fn separated_faces(solid: &Solid, gap: f64) -> Vec<Face> {
+    solid.boundaries()[0]
+        .face_iter()
+        .enumerate()
+        .map(|(i, face)| {
+            let vector = Vector3::new(1.0, -2.0, 3.0).normalize() * gap * i as f64;
+            let mut face = builder::translated(face, vector);
+            if i % 2 == 1 {
+                face.invert();
+            }
+            face
+        })
+        .rev()
+        .collect()
+}
+
+#[test]
+fn sew_closed_shells() {
+    for solid in &[cube(), cylinder()] {
+        let faces = separated_faces(solid, 1.0e-5);
+        let sewed = sewing::sew(faces, 1.0e-4);
+        assert_eq!(sewed.shells.len(), 1);
+        assert!(sewed.free_edges.is_empty());
+        let shell = &sewed.shells[0];
+        assert_eq!(shell.len(), solid.boundaries()[0].len());
+        assert_eq!(shell.edge_iter().count(), solid.boundaries()[0].edge_iter().count());
+        assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+        Solid::try_new(sewed.shells).unwrap();
+    }
+}
+
+#[test]
+fn sew_open_shell() {
+    let mut faces = separated_faces(&cube(), 1.0e-5);
+    faces.pop();
+    let sewed = sewing::sew(faces, 1.0e-4);
+    assert_eq!(sewed.shells.len(), 1);
+    assert_eq!(sewed.shells[0].shell_condition(), ShellCondition::Oriented);
+    assert_eq!(sewed.free_edges.len(), 4);
+    let boundaries = sewed.shells[0].extract_boundaries();
+    assert_eq!(boundaries.len(), 1);
+    assert_eq!(boundaries[0].len(), 4);
+}
+
+#[test]
+fn sew_separated_shells() {
+    let mut faces = separated_faces(&cube(), 0.0);
+    let other = builder::translated(&cube(), Vector3::new(3.0, 0.0, 0.0));
+    faces.extend(separated_faces(&other, 0.0));
+    // the gaps larger than the tolerance are not sewn
+    let far = builder::translated(&cube(), Vector3::new(0.0, 3.0, 0.0));
+    faces.extend(separated_faces(&far, 1.0e-2));
+    let sewed = sewing::sew(faces, 1.0e-4);
+    let mut lens: Vec<usize> = sewed.shells.iter().map(|shell| shell.len()).collect();
+    lens.sort_unstable();
+    assert_eq!(lens, vec![1, 1, 1, 1, 1, 1, 6, 6]);
+    assert_eq!(sewed.free_edges.len(), 24);
+}
+
+#[test]
+fn sew_tolerant_faces() {
+    let faces = separated_faces(&cube(), 1.0e-2);
+    // the gaps are wider than the global tolerance, but within the tolerances of the data
+    faces
+        .iter()
+        .flat_map(|face| face.absolute_boundaries().iter().flatten())
+        .for_each(|edge| {
+            edge.set_tolerance(Some(0.1));
+            edge.front().set_tolerance(Some(0.1));
+            edge.back().set_tolerance(Some(0.1));
+        });
+    let sewed = sewing::sew(faces, 1.0e-4);
+    assert_eq!(sewed.shells.len(), 1);
+    assert!(sewed.free_edges.is_empty());
+    let shell = &sewed.shells[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(shell.vertex_iter().all(|vertex| vertex.tolerance() == Some(0.1)));
+    assert!(shell.edge_iter().all(|edge| edge.tolerance() == Some(0.1)));
+    assert!(shell.edge_iter().all(|edge| edge.is_geometric_consistent()));
+    shell.edge_iter().for_each(|edge| {
+        edge.set_tolerance(None);
+        edge.front().set_tolerance(None);
+        edge.back().set_tolerance(None);
+    });
+    assert!(!shell.edge_iter().all(|edge| edge.is_geometric_consistent()));
+}
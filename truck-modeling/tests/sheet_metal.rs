@@ -0,0 +1,122 @@
+use std::f64::consts::PI;
+use truck_modeling::sheet_metal::*;
+use truck_modeling::*;
+
+// the rectangle [0, x] x [0, y] on the plane z = 0 turning counterclockwise
+fn rectangle(x: f64, y: f64) -> Wire {
+    let v = [(0.0, 0.0), (x, 0.0), (x, y), (0.0, y)]
+        .iter()
+        .map(|(x, y)| builder::vertex(Point3::new(*x, *y, 0.0)))
+        .collect::<Vec<_>>();
+    (0..4)
+        .map(|i| builder::line(&v[i], &v[(i + 1) % 4]))
+        .collect()
+}
+
+fn bend(angle: f64, length: f64) -> Bend {
+    Bend {
+        angle: Rad(angle),
+        radius: 0.2,
+        k_factor: 0.4,
+        length,
+    }
+}
+
+fn bounding_box(shape: impl Iterator<Item = Vertex>) -> BoundingBox<Point3> {
+    shape.map(|v| v.get_point()).collect()
+}
+
+#[test]
+fn folded_box_tray() {
+    let profile = rectangle(2.0, 1.0);
+    let mut part = SheetMetal::try_base_flange(&profile, 0.1).unwrap();
+    // the flanges along the four edges make a tray
+    for edge in profile.iter() {
+        part.try_add_bend(edge, bend(PI / 2.0, 0.5)).unwrap();
+    }
+    let folded = part.folded().unwrap();
+    let shell = &folded.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert!(folded.is_geometric_consistent());
+    // the base, the top, and the four faces of the bend and the five faces of the flange for each edge
+    assert_eq!(shell.len(), 2 + 4 * 9);
+    let bdd = bounding_box(shell.vertex_iter());
+    assert_near!(bdd.min(), Point3::new(-0.3, -0.3, 0.0));
+    assert_near!(bdd.max(), Point3::new(2.3, 1.3, 0.8));
+
+    let flat = part.flat_pattern().unwrap();
+    assert_eq!(flat.boundaries()[0].len(), 12);
+    let d = bend(PI / 2.0, 0.5).allowance(0.1) + 0.5;
+    let bdd = bounding_box(flat.boundaries()[0].vertex_iter());
+    assert_near!(bdd.min(), Point3::new(-d, -d, 0.0));
+    assert_near!(bdd.max(), Point3::new(2.0 + d, 1.0 + d, 0.0));
+    assert!(flat
+        .oriented_surface()
+        .normal(0.5, 0.5)
+        .near(&Vector3::unit_z()));
+    let solid = builder::tsweep(&flat, Vector3::unit_z() * part.thickness());
+    assert_eq!(
+        solid.boundaries()[0].shell_condition(),
+        ShellCondition::Closed
+    );
+}
+
+#[test]
+fn downward_bend_without_flange() {
+    let profile = rectangle(1.0, 1.0);
+    let mut part = SheetMetal::try_base_flange(&profile, 0.1).unwrap();
+    part.try_add_bend(&profile[1], bend(-PI / 3.0, 0.0))
+        .unwrap();
+    let folded = part.folded().unwrap();
+    let shell = &folded.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    // the fibers end at the angle of π/3 around the axis at x = 1, z = -0.2
+    let bdd = bounding_box(shell.vertex_iter());
+    assert_near!(bdd.max()[0], 1.0 + 0.3 * f64::sin(PI / 3.0));
+    assert_near!(bdd.min()[2], -0.2 + 0.2 * f64::cos(PI / 3.0));
+
+    let flat = part.flat_pattern().unwrap();
+    let bdd = bounding_box(flat.boundaries()[0].vertex_iter());
+    assert_near!(bdd.max()[0], 1.0 + PI / 3.0 * (0.2 + 0.4 * 0.1));
+}
+
+#[test]
+fn sheet_metal_failures() {
+    let profile = rectangle(1.0, 1.0);
+    assert_eq!(
+        SheetMetal::try_base_flange(&profile, 0.0).unwrap_err(),
+        errors::Error::InvalidSheetParameter,
+    );
+    let mut open = profile.clone();
+    open.pop_back();
+    assert_eq!(
+        SheetMetal::try_base_flange(&open, 0.1).unwrap_err(),
+        errors::Error::NotSheetProfile,
+    );
+    let v = profile.front_vertex().unwrap();
+    let arc = builder::circle_arc(profile[0].back(), v, Point3::new(0.5, -0.5, 0.0));
+    let lens: Wire = vec![profile[0].clone(), arc].into();
+    assert_eq!(
+        SheetMetal::try_base_flange(&lens, 0.1).unwrap_err(),
+        errors::Error::NotSheetProfile,
+    );
+
+    let mut part = SheetMetal::try_base_flange(&profile, 0.1).unwrap();
+    let other = rectangle(1.0, 1.0);
+    assert_eq!(
+        part.try_add_bend(&other[0], bend(PI / 2.0, 0.5))
+            .unwrap_err(),
+        errors::Error::EdgeNotBendable,
+    );
+    assert_eq!(
+        part.try_add_bend(&profile[0], bend(PI, 0.5)).unwrap_err(),
+        errors::Error::InvalidSheetParameter,
+    );
+    part.try_add_bend(&profile[0], bend(PI / 2.0, 0.5)).unwrap();
+    assert_eq!(
+        part.try_add_bend(&profile[0], bend(PI / 2.0, 0.5))
+            .unwrap_err(),
+        errors::Error::EdgeNotBendable,
+    );
+    assert_eq!(part.bends().len(), 1);
+}
@@ -0,0 +1,106 @@
+use errors::Error;
+use std::f64::consts::PI;
+use truck_modeling::*;
+
+fn bspline_face(control_points: Vec<Vec<Point3>>) -> Face {
+    let knot_vecs = (
+        KnotVec::bezier_knot(control_points.len() - 1),
+        KnotVec::bezier_knot(control_points[0].len() - 1),
+    );
+    let surface = BSplineSurface::new(knot_vecs, control_points);
+    // the boundary is irrelevant to the natural boundary
+    let v = builder::vertex(Point3::new(0.2, 0.2, 0.0));
+    let circle = builder::rsweep(&v, Point3::new(0.3, 0.3, 0.0), Vector3::unit_z(), Rad(7.0));
+    Face::new(vec![circle], Surface::BSplineSurface(surface))
+}
+
+#[test]
+fn untrim_disks() {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI));
+    let disk = builder::try_attach_plane(&vec![circle]).unwrap();
+    for face in [disk.clone(), disk.inverse()] {
+        let untrimmed = builder::try_untrimmed(&face).unwrap();
+        assert!(untrimmed.is_geometric_consistent());
+        assert_eq!(untrimmed.orientation(), face.orientation());
+        let normal0 = untrimmed.oriented_surface().normal(0.5, 0.5);
+        let normal1 = face.oriented_surface().normal(0.5, 0.5);
+        assert_near!(normal0, normal1);
+        let boundary = builder::try_natural_boundary(&face).unwrap();
+        let points0: Vec<Point3> = boundary.vertex_iter().map(|v| v.get_point()).collect();
+        let points1: Vec<Point3> = untrimmed.boundaries()[0]
+            .vertex_iter()
+            .map(|v| v.get_point())
+            .collect();
+        assert_eq!(points0, points1);
+    }
+}
+
+#[test]
+fn untrim_the_faces_of_cube() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    for face in cube.face_iter() {
+        let untrimmed = builder::try_untrimmed(face).unwrap();
+        assert!(untrimmed.is_geometric_consistent());
+        // the faces of the cube are not trimmed
+        let mut points0: Vec<[i64; 3]> = face.boundaries()[0]
+            .vertex_iter()
+            .map(|v| v.get_point().map(|x| x.round() as i64).into())
+            .collect();
+        let mut points1: Vec<[i64; 3]> = untrimmed.boundaries()[0]
+            .vertex_iter()
+            .map(|v| v.get_point().map(|x| x.round() as i64).into())
+            .collect();
+        points0.sort();
+        points1.sort();
+        assert_eq!(points0, points1);
+    }
+}
+
+#[test]
+fn degenerate_side() {
+    // the triangular patch, whose side u = 0 is degenerated to the point
+    let face = bspline_face(vec![
+        vec![Point3::new(0.0, 0.0, 0.0); 3],
+        vec![
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 0.5, 0.5),
+            Point3::new(1.0, 1.0, 0.0),
+        ],
+    ]);
+    let boundary = builder::try_natural_boundary(&face).unwrap();
+    assert_eq!(boundary.len(), 3);
+    assert!(boundary.is_closed() && boundary.is_simple());
+    let points: Vec<Point3> = boundary.vertex_iter().map(|v| v.get_point()).collect();
+    let expected = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+    ];
+    assert_eq!(points, expected);
+    let untrimmed = builder::try_untrimmed(&face).unwrap();
+    assert_eq!(untrimmed.boundaries()[0].len(), 3);
+}
+
+#[test]
+fn no_natural_boundary() {
+    // the tube closed in the direction of u
+    let face = bspline_face(vec![
+        vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 1.0)],
+        vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 1.0)],
+        vec![Point3::new(1.0, 1.0, 0.0), Point3::new(1.0, 1.0, 1.0)],
+        vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 1.0)],
+    ]);
+    let res = builder::try_natural_boundary(&face);
+    assert_eq!(res.unwrap_err(), Error::NoNaturalBoundary);
+
+    // the surface of revolution
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let e = builder::tsweep(&v, Vector3::unit_z());
+    let shell = builder::rsweep(&e, Point3::origin(), Vector3::unit_z(), Rad(PI));
+    let res = builder::try_untrimmed(&shell[0]);
+    assert_eq!(res.unwrap_err(), Error::NoNaturalBoundary);
+}
@@ -0,0 +1,104 @@
+use truck_modeling::*;
+
+// the rectangle [0, x] x [0, y] turning counterclockwise
+fn rectangle(x: f64, y: f64) -> (Vec<Vertex>, Wire) {
+    let v = [(0.0, 0.0), (x, 0.0), (x, y), (0.0, y)]
+        .iter()
+        .map(|(x, y)| builder::vertex(Point3::new(*x, *y, 0.0)))
+        .collect::<Vec<_>>();
+    let wire = (0..4).map(|i| builder::line(&v[i], &v[(i + 1) % 4])).collect();
+    (v, wire)
+}
+
+#[test]
+fn fillet_all_corners() {
+    let (v, mut wire) = rectangle(3.0, 2.0);
+    for vertex in &v {
+        wire = builder::try_fillet_wire(&wire, vertex, 0.5).unwrap();
+    }
+    assert_eq!(wire.len(), 8);
+    assert!(wire.is_closed() && wire.is_simple());
+    assert!(wire.vertex_iter().all(|vertex| !v.contains(&vertex)));
+    let face = builder::try_attach_plane(&vec![wire]).unwrap();
+    assert!(face.oriented_surface().normal(0.5, 0.5).near(&Vector3::unit_z()));
+    let solid: Solid = builder::tsweep(&face, Vector3::unit_z());
+    assert_eq!(solid.boundaries()[0].shell_condition(), ShellCondition::Closed);
+}
+
+#[test]
+fn fillet_consuming_lines() {
+    // the stadium: the short sides are replaced by semicircles
+    let (v, mut wire) = rectangle(3.0, 1.0);
+    for vertex in &v {
+        wire = builder::try_fillet_wire(&wire, vertex, 0.5).unwrap();
+    }
+    assert_eq!(wire.len(), 6);
+    assert!(wire.is_closed());
+    // the arcs of the corners on the same side meet at the middle of the side
+    let middle = Point3::new(3.0, 0.5, 0.0);
+    assert!(wire.vertex_iter().any(|vertex| vertex.get_point().near(&middle)));
+    let corner = wire
+        .vertex_iter()
+        .find(|vertex| vertex.get_point().near(&middle))
+        .unwrap();
+    // the corner between the tangent arcs
+    assert!(matches!(
+        builder::try_fillet_wire(&wire, &corner, 0.1),
+        Err(errors::Error::CornerNotFit),
+    ));
+    wire.invert();
+    assert!(builder::try_attach_plane(&vec![wire]).is_ok());
+}
+
+#[test]
+fn chamfer_corners() {
+    let (v, wire) = rectangle(2.0, 1.0);
+    let chamfered = builder::try_chamfer_wire(&wire, &v[2], 0.25).unwrap();
+    let chamfered = builder::try_chamfer_wire(&chamfered, &v[0], 1.0).unwrap();
+    assert_eq!(chamfered.len(), 5);
+    assert!(chamfered.is_closed());
+    // the chamfer consumes the short side
+    let points: Vec<Point3> = chamfered.vertex_iter().map(|v| v.get_point()).collect();
+    assert!(points.contains(&Point3::new(0.0, 1.0, 0.0)));
+    assert!(points.contains(&Point3::new(1.0, 0.0, 0.0)));
+    assert!(points.iter().any(|p| p.near(&Point3::new(1.75, 1.0, 0.0))));
+    assert!(points.iter().any(|p| p.near(&Point3::new(2.0, 0.75, 0.0))));
+    let face = builder::try_attach_plane(&vec![chamfered]).unwrap();
+    let area = {
+        let pts: Vec<Point3> = face.boundaries()[0]
+            .vertex_iter()
+            .map(|v| v.get_point())
+            .collect();
+        (0..pts.len()).fold(0.0, |sum, i| {
+            let (p, q) = (pts[i], pts[(i + 1) % pts.len()]);
+            sum + (p[0] * q[1] - p[1] * q[0]) / 2.0
+        })
+    };
+    assert_near!(area, 2.0 - 0.5 - 0.25 * 0.25 / 2.0);
+}
+
+#[test]
+fn invalid_corners() {
+    let (v, wire) = rectangle(2.0, 1.0);
+    let other = builder::vertex(Point3::new(5.0, 5.0, 0.0));
+    assert!(matches!(
+        builder::try_fillet_wire(&wire, &other, 0.1),
+        Err(errors::Error::NotCornerOfWire),
+    ));
+    // too long distance
+    assert!(matches!(
+        builder::try_chamfer_wire(&wire, &v[1], 1.5),
+        Err(errors::Error::CornerNotFit),
+    ));
+    // the corner of a straight line
+    let v3 = builder::vertex(Point3::new(4.0, 0.0, 0.0));
+    let straight: Wire = vec![builder::line(&v[0], &v[1]), builder::line(&v[1], &v3)].into();
+    assert!(builder::try_chamfer_wire(&straight, &v[1], 0.5).is_err());
+    // the corner of the arc
+    let arc = builder::circle_arc(&v[1], &v[2], Point3::new(2.5, 0.5, 0.0));
+    let curved: Wire = vec![builder::line(&v[0], &v[1]), arc].into();
+    assert!(matches!(
+        builder::try_fillet_wire(&curved, &v[1], 0.1),
+        Err(errors::Error::CornerNotFit),
+    ));
+}
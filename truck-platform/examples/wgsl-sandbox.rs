@@ -37,8 +37,9 @@ mod plane {
     use super::*;
 
     /// Canvas to draw by fragment shader.
+    #[derive(Clone)]
     pub struct Plane {
-        module: ShaderModule,
+        pub shader: ShaderHandle,
         pub mouse: [f32; 4],
         id: RenderID,
     }
@@ -175,13 +176,14 @@ fn fs_main([[builtin(position)]] position: vec4<f32>) -> [[location(0)]] vec4<f3
             sample_count: u32,
         ) -> Arc<RenderPipeline> {
             let config = handler.config();
+            let module = self.shader.module();
             Arc::new(
                 handler
                     .device()
                     .create_render_pipeline(&RenderPipelineDescriptor {
                         layout: Some(layout),
                         vertex: VertexState {
-                            module: &self.module,
+                            module: &module,
                             entry_point: "vs_main",
                             buffers: &[VertexBufferLayout {
                                 array_stride: std::mem::size_of::<u32>() as BufferAddress,
@@ -194,7 +196,7 @@ fn fs_main([[builtin(position)]] position: vec4<f32>) -> [[location(0)]] vec4<f3
                             }],
                         },
                         fragment: Some(FragmentState {
-                            module: &self.module,
+                            module: &module,
                             entry_point: "fs_main",
                             targets: &[ColorTargetState {
                                 format: config.format,
@@ -231,44 +233,32 @@ fn fs_main([[builtin(position)]] position: vec4<f32>) -> [[location(0)]] vec4<f3
     impl Plane {
         /// constructor
         /// # Arguments
-        /// - device: Device, provided by wgpu.
+        /// - scene: the scene in which the shader is registered.
         /// - shader: the inputed fragment shader
-        pub fn new(device: &Device, shader: &str) -> Plane {
-            let module = create_module(device, shader).expect("Default shader is invalid");
+        pub fn new(scene: &mut Scene, shader: &str) -> Plane {
+            let shader = scene
+                .create_shader(&whole_source(shader))
+                .expect("Default shader is invalid");
             Plane {
-                module,
+                shader,
                 mouse: [0.0; 4],
                 id: RenderID::gen(),
             }
         }
 
-        pub fn set_shader(&mut self, device: &Device, shader: &str) {
-            if let Some(module) = create_module(device, shader) {
-                self.module = module;
+        /// Replaces the fragment shader. The pipeline is rebuilt by the scene.
+        pub fn set_shader(&self, scene: &mut Scene, shader: &str) {
+            if let Err(error) = scene.replace_shader(&self.shader, &whole_source(shader)) {
+                println!("{}", error);
             }
         }
     }
 
-    fn create_module(device: &Device, shader: &str) -> Option<ShaderModule> {
-        use naga::{front::wgsl::Parser, valid::*};
+    fn whole_source(shader: &str) -> String {
         let mut source = BASE_PREFIX.to_string();
         source += shader;
         source += BASE_SHADER;
-
-        Validator::new(ValidationFlags::all(), Capabilities::empty())
-            .validate(
-                &Parser::new()
-                    .parse(&source)
-                    .map_err(|error| println!("WGSL Parse Error: {}", error))
-                    .ok()?,
-            )
-            .map_err(|error| println!("WGSL Validation Error: {}", error))
-            .ok()?;
-
-        Some(device.create_shader_module(&ShaderModuleDescriptor {
-            source: ShaderSource::Wgsl(source.into()),
-            label: None,
-        }))
+        source
     }
 }
 use plane::Plane;
@@ -333,9 +323,11 @@ fn main() {
     } else {
         include_str!("newton-cuberoot.wgsl").to_string()
     };
-    let mut plane = Plane::new(handler.device(), &source);
+    let mut plane = Plane::new(&mut scene, &source);
     // Adds a plane to the scene!
-    scene.add_object(&mut plane);
+    scene.add_object(&plane);
+    // The pipeline of the plane is rebuilt each time the shader is replaced.
+    scene.link_shader(&plane, &[&plane.shader]);
 
     let mut dragging = false;
     let mut clicked = false;
@@ -379,10 +371,7 @@ fn main() {
                 WindowEvent::CloseRequested => ControlFlow::Exit,
                 WindowEvent::DroppedFile(path) => {
                     match std::fs::read_to_string(path) {
-                        Ok(code) => {
-                            plane.set_shader(handler.device(), &code);
-                            scene.update_pipeline(&plane);
-                        }
+                        Ok(code) => plane.set_shader(&mut scene, &code),
                         Err(error) => println!("{:?}", error),
                     }
                     ControlFlow::Poll
@@ -230,6 +230,48 @@ impl Camera {
             * self.matrix.invert().unwrap()
     }
 
+    /// Returns the length in the world space of one pixel at `point` on the screen
+    /// whose height is `screen_height` pixels.
+    ///
+    /// For the perspective camera, the length is proportional to the depth of `point`,
+    /// and is zero if `point` is behind the camera.
+    /// # Examples
+    /// ```
+    /// use truck_base::{cgmath64::*, tolerance::*};
+    /// use truck_platform::*;
+    /// let matrix = Matrix4::look_at_rh(
+    ///     Point3::new(0.0, 0.0, 10.0),
+    ///     Point3::origin(),
+    ///     Vector3::new(0.0, 1.0, 0.0),
+    /// );
+    /// // the screen shows the height 2 tan(pi / 8) * 10 at the origin.
+    /// let camera = Camera::perspective_camera(
+    ///     matrix.invert().unwrap(),
+    ///     Deg(45.0),
+    ///     0.1,
+    ///     100.0,
+    /// );
+    /// let size = camera.pixel_size(Point3::origin(), 800);
+    /// let height = 2.0 * f64::tan(std::f64::consts::PI / 8.0) * 10.0;
+    /// assert!(size.near(&(height / 800.0)));
+    ///
+    /// // the parallel camera shows the height 3 at any depth.
+    /// let camera = Camera::parallel_camera(matrix.invert().unwrap(), 3.0, 0.1, 100.0);
+    /// let size = camera.pixel_size(Point3::new(1.0, 2.0, 3.0), 600);
+    /// assert!(size.near(&0.005));
+    /// ```
+    #[inline(always)]
+    pub fn pixel_size(&self, point: Point3, screen_height: u32) -> f64 {
+        let height = 2.0 / self.projection[1][1];
+        let depth = match self.projection_type {
+            ProjectionType::Perspective => {
+                f64::max((point - self.position()).dot(self.eye_direction()), 0.0)
+            }
+            ProjectionType::Parallel => 1.0,
+        };
+        height * depth / screen_height as f64
+    }
+
     fn camera_info(&self, as_rat: f64) -> CameraInfo {
         CameraInfo {
             camera_matrix: (&self.matrix).cast().unwrap().into(),
@@ -44,9 +44,10 @@ extern crate bytemuck;
 extern crate truck_base;
 pub extern crate wgpu;
 use bytemuck::{Pod, Zeroable};
+use std::any::Any;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use truck_base::cgmath64::*;
+use truck_base::{bounding_box::BoundingBox, cgmath64::*};
 use wgpu::util::{BufferInitDescriptor, DeviceExt};
 use wgpu::*;
 
@@ -154,6 +155,8 @@ pub struct RenderObject {
     pipeline: Arc<RenderPipeline>,
     bind_group_layout: Arc<BindGroupLayout>,
     bind_group: Arc<BindGroup>,
+    bounding_box: Option<BoundingBox<Point3>>,
+    offline_data: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 /// the projection type of camera
@@ -287,6 +290,49 @@ pub struct SceneDescriptor {
     pub sample_count: u32,
 }
 
+/// WGSL shader registered in [`Scene`](./struct.Scene.html), which can be replaced at runtime.
+///
+/// The shader module is validated by [`naga`](https://crates.io/crates/naga) before being
+/// created. If the shader is replaced by [`Scene::replace_shader`] or [`Scene::reload_shaders`],
+/// the render pipelines of the objects linked by [`Scene::link_shader`] are rebuilt automatically.
+///
+/// [`Scene::replace_shader`]: ./struct.Scene.html#method.replace_shader
+/// [`Scene::reload_shaders`]: ./struct.Scene.html#method.reload_shaders
+/// [`Scene::link_shader`]: ./struct.Scene.html#method.link_shader
+#[derive(Debug, Clone)]
+pub struct ShaderHandle(Arc<Mutex<ShaderEntry>>);
+
+#[derive(Debug)]
+struct ShaderEntry {
+    module: Arc<ShaderModule>,
+    source: String,
+    path: Option<std::path::PathBuf>,
+    modified: Option<std::time::SystemTime>,
+}
+
+type PipelineBuilder =
+    dyn Fn(&DeviceHandler, &PipelineLayout, u32) -> Arc<RenderPipeline> + Send + Sync;
+
+#[derive(Clone)]
+struct ShaderLink {
+    shaders: Vec<ShaderHandle>,
+    builder: Arc<PipelineBuilder>,
+}
+
+/// Errors occurred in registering or replacing shaders.
+#[derive(Debug, thiserror::Error)]
+pub enum ShaderError {
+    /// failed to read the shader file.
+    #[error("failed to read the shader file: {0}")]
+    Io(#[from] std::io::Error),
+    /// failed to parse WGSL.
+    #[error("WGSL parse error: {0}")]
+    Parse(String),
+    /// failed to validate the shader module.
+    #[error("WGSL validation error: {0}")]
+    Validation(String),
+}
+
 /// Wraps `wgpu` and provides an intuitive graphics API.
 ///
 /// `Scene` is the most important in `truck-platform`.
@@ -303,6 +349,8 @@ pub struct Scene {
     previous_sample_count: u32,
     clock: std::time::Instant,
     scene_desc: SceneDescriptor,
+    shaders: Vec<ShaderHandle>,
+    shader_links: HashMap<RenderID, ShaderLink>,
 }
 
 /// Rendered objects in the scene.
@@ -332,6 +380,22 @@ pub trait Rendered {
         layout: &PipelineLayout,
         sample_count: u32,
     ) -> Arc<RenderPipeline>;
+    /// Returns the bounding box in the world coordinates, or `None` if it is unknown.
+    ///
+    /// The objects returning `None` are ignored in [`Scene::world_bounding_box`].
+    /// Default returns `None`.
+    ///
+    /// [`Scene::world_bounding_box`]: ./struct.Scene.html#method.world_bounding_box
+    #[inline(always)]
+    fn bounding_box(&self) -> Option<BoundingBox<Point3>> { None }
+    /// Returns the data for the renderers other than the rasterization of [`Scene`],
+    /// e.g. the meshes and the materials for ray tracers, or `None` if there is no such data.
+    ///
+    /// The data are collected by [`Scene::offline_data`]. Default returns `None`.
+    ///
+    /// [`Scene::offline_data`]: ./struct.Scene.html#method.offline_data
+    #[inline(always)]
+    fn offline_data(&self) -> Option<Arc<dyn Any + Send + Sync>> { None }
     #[doc(hidden)]
     fn render_object(&self, scene: &Scene) -> RenderObject {
         let (vertex_buffer, index_buffer) = self.vertex_buffer(scene.device_handler());
@@ -355,6 +419,8 @@ pub trait Rendered {
             bind_group_layout,
             bind_group,
             pipeline,
+            bounding_box: self.bounding_box(),
+            offline_data: self.offline_data(),
         }
     }
 }
@@ -365,6 +431,7 @@ mod light;
 #[doc(hidden)]
 pub mod rendered_macros;
 mod scene;
+mod shader;
 
 #[doc(hidden)]
 pub mod bind_group_util {
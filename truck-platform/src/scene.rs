@@ -222,6 +222,8 @@ impl Scene {
             previous_sample_count: scene_desc.sample_count,
             clock: std::time::Instant::now(),
             scene_desc: scene_desc.clone(),
+            shaders: Vec::new(),
+            shader_links: HashMap::new(),
             device_handler,
         }
     }
@@ -400,6 +402,7 @@ impl Scene {
     /// If there does not exist the render object in the scene, does nothing and returns false.
     #[inline(always)]
     pub fn remove_object<R: Rendered>(&mut self, object: &R) -> bool {
+        self.shader_links.remove(&object.render_id());
         self.objects.remove(&object.render_id()).is_some()
     }
     /// Removes render objects from the scene.
@@ -418,6 +421,7 @@ impl Scene {
     /// Removes all render objects from the scene.
     #[inline(always)]
     pub fn clear_objects(&mut self) {
+        self.shader_links.clear();
         self.objects.clear()
     }
 
@@ -427,6 +431,35 @@ impl Scene {
         self.objects.len()
     }
 
+    /// Returns the union of the bounding boxes of the render objects in the world coordinates,
+    /// for fitting the camera to the contents.
+    ///
+    /// The bounding boxes are taken at adding the objects and at updating their vertex buffers
+    /// or bind groups. The objects whose [`Rendered::bounding_box`] are `None` are ignored.
+    ///
+    /// [`Rendered::bounding_box`]: ./trait.Rendered.html#method.bounding_box
+    #[inline(always)]
+    pub fn world_bounding_box(&self) -> BoundingBox<Point3> {
+        self.objects
+            .values()
+            .filter_map(|object| object.bounding_box.as_ref())
+            .fold(BoundingBox::new(), |res, bdd| res + bdd)
+    }
+
+    /// Returns the iterator over the data of the render objects for the renderers other than
+    /// the rasterization, e.g. ray tracers.
+    ///
+    /// The data are taken at adding the objects and at updating their vertex buffers
+    /// or bind groups. The objects whose [`Rendered::offline_data`] are `None` are skipped.
+    ///
+    /// [`Rendered::offline_data`]: ./trait.Rendered.html#method.offline_data
+    #[inline(always)]
+    pub fn offline_data(&self) -> impl Iterator<Item = &Arc<dyn Any + Send + Sync>> {
+        self.objects
+            .values()
+            .filter_map(|object| object.offline_data.as_ref())
+    }
+
     /// Syncronizes the information of vertices of `object` in the CPU memory
     /// and that in the GPU memory.
     ///
@@ -440,6 +473,8 @@ impl Scene {
                 let (vb, ib) = object.vertex_buffer(handler);
                 render_object.vertex_buffer = vb;
                 render_object.index_buffer = ib;
+                render_object.bounding_box = object.bounding_box();
+                render_object.offline_data = object.offline_data();
                 true
             }
         }
@@ -470,6 +505,8 @@ impl Scene {
             Some(render_object) => {
                 let bind_group = object.bind_group(handler, &render_object.bind_group_layout);
                 render_object.bind_group = bind_group;
+                render_object.bounding_box = object.bounding_box();
+                render_object.offline_data = object.offline_data();
                 true
             }
             _ => false,
@@ -497,15 +534,8 @@ impl Scene {
         let (handler, objects) = (&self.device_handler, &mut self.objects);
         match objects.get_mut(&object.render_id()) {
             Some(render_object) => {
-                let device = handler.device();
-                let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
-                    bind_group_layouts: &[
-                        &self.bind_group_layout,
-                        &render_object.bind_group_layout,
-                    ],
-                    push_constant_ranges: &[],
-                    label: None,
-                });
+                let pipeline_layout =
+                    Self::pipeline_layout(handler, &self.bind_group_layout, render_object);
                 render_object.pipeline =
                     object.pipeline(handler, &pipeline_layout, self.scene_desc.sample_count);
                 true
@@ -527,6 +557,20 @@ impl Scene {
         objects.into_iter().fold(true, closure)
     }
     #[inline(always)]
+    pub(super) fn pipeline_layout(
+        handler: &DeviceHandler,
+        scene_layout: &BindGroupLayout,
+        render_object: &RenderObject,
+    ) -> PipelineLayout {
+        handler
+            .device()
+            .create_pipeline_layout(&PipelineLayoutDescriptor {
+                bind_group_layouts: &[scene_layout, &render_object.bind_group_layout],
+                push_constant_ranges: &[],
+                label: None,
+            })
+    }
+    #[inline(always)]
     fn depth_stencil_attachment_descriptor(
         depth_view: &TextureView,
     ) -> RenderPassDepthStencilAttachment {
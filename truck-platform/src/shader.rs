@@ -0,0 +1,217 @@
+use crate::*;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+fn validate(source: &str) -> Result<(), ShaderError> {
+    use naga::{front::wgsl::Parser, valid::*};
+    let module = Parser::new()
+        .parse(source)
+        .map_err(|error| ShaderError::Parse(error.to_string()))?;
+    Validator::new(ValidationFlags::all(), Capabilities::empty())
+        .validate(&module)
+        .map_err(|error| ShaderError::Validation(error.to_string()))?;
+    Ok(())
+}
+
+fn create_module(device: &Device, source: &str) -> Result<Arc<ShaderModule>, ShaderError> {
+    validate(source)?;
+    Ok(Arc::new(device.create_shader_module(
+        &ShaderModuleDescriptor {
+            source: ShaderSource::Wgsl(source.into()),
+            label: None,
+        },
+    )))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+impl ShaderHandle {
+    /// Returns the current shader module.
+    ///
+    /// The render pipelines should be created by the module returned by this method
+    /// in [`Rendered::pipeline`](./trait.Rendered.html#tymethod.pipeline).
+    #[inline(always)]
+    pub fn module(&self) -> Arc<ShaderModule> { Arc::clone(&self.0.lock().unwrap().module) }
+    /// Returns the current WGSL source.
+    #[inline(always)]
+    pub fn source(&self) -> String { self.0.lock().unwrap().source.clone() }
+    /// Returns the path of the shader file, or `None` if the shader is created from a string.
+    #[inline(always)]
+    pub fn path(&self) -> Option<PathBuf> { self.0.lock().unwrap().path.clone() }
+    /// Returns whether `self` and `other` are the same shader or not.
+    #[inline(always)]
+    pub fn is_same(&self, other: &ShaderHandle) -> bool { Arc::ptr_eq(&self.0, &other.0) }
+}
+
+impl std::fmt::Debug for ShaderLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ShaderLink")
+            .field("shaders", &self.shaders)
+            .finish()
+    }
+}
+
+impl Scene {
+    /// Registers the WGSL shader `source` to the scene.
+    ///
+    /// Returns an error if `source` is not a valid WGSL.
+    pub fn create_shader(&mut self, source: &str) -> Result<ShaderHandle, ShaderError> {
+        let module = create_module(self.device(), source)?;
+        let shader = ShaderHandle(Arc::new(Mutex::new(ShaderEntry {
+            module,
+            source: source.to_string(),
+            path: None,
+            modified: None,
+        })));
+        self.shaders.push(shader.clone());
+        Ok(shader)
+    }
+
+    /// Registers the WGSL shader file to the scene.
+    ///
+    /// The file is watched by [`Scene::reload_shaders`](./struct.Scene.html#method.reload_shaders).
+    pub fn create_shader_from_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<ShaderHandle, ShaderError> {
+        let path = path.as_ref();
+        let modified = modified_time(path);
+        let source = std::fs::read_to_string(path)?;
+        let module = create_module(self.device(), &source)?;
+        let shader = ShaderHandle(Arc::new(Mutex::new(ShaderEntry {
+            module,
+            source,
+            path: Some(path.to_path_buf()),
+            modified,
+        })));
+        self.shaders.push(shader.clone());
+        Ok(shader)
+    }
+
+    /// Unregisters the shader from the scene, and unlinks the objects depending on the shader.
+    ///
+    /// The shader module is kept alive while the handle or the pipelines refer it.
+    pub fn remove_shader(&mut self, shader: &ShaderHandle) -> bool {
+        let len = self.shaders.len();
+        self.shaders.retain(|s| !s.is_same(shader));
+        self.shader_links
+            .retain(|_, link| !link.shaders.iter().any(|s| s.is_same(shader)));
+        len != self.shaders.len()
+    }
+
+    /// Returns the number of the registered shaders.
+    #[inline(always)]
+    pub fn number_of_shaders(&self) -> usize { self.shaders.len() }
+
+    /// Links `object` to `shaders`, so that the render pipeline of `object` is rebuilt
+    /// when one of `shaders` is replaced.
+    ///
+    /// `object` is cloned at the time of linking, and the pipeline is rebuilt by the clone.
+    /// The clone is kept until unlinking, and the changes of `object` after linking, e.g. of its
+    /// instance state, are not reflected to the rebuilt pipeline. In that case, link again.
+    /// If `object` is not in the scene, does nothing and returns false.
+    pub fn link_shader<R>(&mut self, object: &R, shaders: &[&ShaderHandle]) -> bool
+    where R: Rendered + Clone + Send + Sync + 'static {
+        let id = object.render_id();
+        if !self.objects.contains_key(&id) {
+            return false;
+        }
+        let object = object.clone();
+        let builder = Arc::new(
+            move |handler: &DeviceHandler, layout: &PipelineLayout, sample_count: u32| {
+                object.pipeline(handler, layout, sample_count)
+            },
+        );
+        let link = ShaderLink {
+            shaders: shaders.iter().map(|shader| (*shader).clone()).collect(),
+            builder,
+        };
+        self.shader_links.insert(id, link);
+        true
+    }
+
+    /// Unlinks `object` from all shaders.
+    ///
+    /// If `object` is not linked, does nothing and returns false.
+    #[inline(always)]
+    pub fn unlink_shader<R: Rendered>(&mut self, object: &R) -> bool {
+        self.shader_links.remove(&object.render_id()).is_some()
+    }
+
+    /// Replaces the source of `shader` and rebuilds the render pipelines of the linked objects.
+    ///
+    /// If `source` is not a valid WGSL, returns an error and keeps the current shader.
+    /// Returns the number of the rebuilt pipelines.
+    pub fn replace_shader(
+        &mut self,
+        shader: &ShaderHandle,
+        source: &str,
+    ) -> Result<usize, ShaderError> {
+        let module = create_module(self.device(), source)?;
+        {
+            let mut entry = shader.0.lock().unwrap();
+            entry.module = module;
+            entry.source = source.to_string();
+        }
+        Ok(self.rebuild_pipelines(std::slice::from_ref(shader)))
+    }
+
+    /// Reloads the shader files modified since the last loading,
+    /// and rebuilds the render pipelines of the linked objects.
+    ///
+    /// Returns the number of the rebuilt pipelines and the errors of the shaders failed to reload.
+    /// The failed shaders keep the current modules, and are retried after the next modification.
+    pub fn reload_shaders(&mut self) -> (usize, Vec<(PathBuf, ShaderError)>) {
+        let mut reloaded = Vec::new();
+        let mut errors = Vec::new();
+        for shader in &self.shaders {
+            let mut entry = shader.0.lock().unwrap();
+            let path = match &entry.path {
+                Some(path) => path.clone(),
+                None => continue,
+            };
+            let modified = modified_time(&path);
+            if modified.is_none() || modified == entry.modified {
+                continue;
+            }
+            entry.modified = modified;
+            let res = std::fs::read_to_string(&path)
+                .map_err(ShaderError::from)
+                .and_then(|source| {
+                    create_module(self.device(), &source).map(|module| (module, source))
+                });
+            match res {
+                Ok((module, source)) => {
+                    entry.module = module;
+                    entry.source = source;
+                    reloaded.push(shader.clone());
+                }
+                Err(error) => errors.push((path, error)),
+            }
+        }
+        (self.rebuild_pipelines(&reloaded), errors)
+    }
+
+    fn rebuild_pipelines(&mut self, shaders: &[ShaderHandle]) -> usize {
+        let (handler, objects) = (&self.device_handler, &mut self.objects);
+        let (scene_layout, sample_count) = (&self.bind_group_layout, self.scene_desc.sample_count);
+        self.shader_links
+            .iter()
+            .filter(|(_, link)| {
+                link.shaders
+                    .iter()
+                    .any(|s0| shaders.iter().any(|s1| s0.is_same(s1)))
+            })
+            .filter_map(|(id, link)| {
+                let render_object = objects.get_mut(id)?;
+                let layout = Self::pipeline_layout(handler, scene_layout, render_object);
+                render_object.pipeline = (link.builder)(handler, &layout, sample_count);
+                Some(())
+            })
+            .count()
+    }
+}
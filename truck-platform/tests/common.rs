@@ -63,65 +63,85 @@ impl<'a> Rendered for Plane<'a> {
         sample_count: u32,
     ) -> Arc<RenderPipeline> {
         writeln!(&mut std::io::stderr(), "create pipeline").unwrap();
-        let (device, config) = (handler.device(), handler.config());
         let source = ShaderSource::Wgsl(self.shader.into());
-        let module = device.create_shader_module(&ShaderModuleDescriptor {
-            label: None,
-            source,
-        });
-        Arc::new(
-            handler
-                .device()
-                .create_render_pipeline(&RenderPipelineDescriptor {
-                    layout: Some(layout),
-                    vertex: VertexState {
-                        module: &module,
-                        entry_point: self.vs_entpt,
-                        buffers: &[VertexBufferLayout {
-                            array_stride: std::mem::size_of::<u32>() as BufferAddress,
-                            step_mode: VertexStepMode::Vertex,
-                            attributes: &[VertexAttribute {
-                                format: VertexFormat::Uint32,
-                                offset: 0,
-                                shader_location: 0,
-                            }],
-                        }],
-                    },
-                    fragment: Some(FragmentState {
-                        module: &module,
-                        entry_point: self.fs_entpt,
-                        targets: &[ColorTargetState {
-                            format: config.format,
-                            blend: Some(BlendState::REPLACE),
-                            write_mask: ColorWrites::ALL,
-                        }],
-                    }),
-                    primitive: PrimitiveState {
-                        topology: PrimitiveTopology::TriangleList,
-                        front_face: FrontFace::Ccw,
-                        cull_mode: Some(Face::Back),
-                        polygon_mode: PolygonMode::Fill,
-                        clamp_depth: false,
-                        ..Default::default()
-                    },
-                    depth_stencil: Some(DepthStencilState {
-                        format: TextureFormat::Depth32Float,
-                        depth_write_enabled: true,
-                        depth_compare: wgpu::CompareFunction::Less,
-                        stencil: Default::default(),
-                        bias: Default::default(),
-                    }),
-                    multisample: MultisampleState {
-                        count: sample_count,
-                        mask: !0,
-                        alpha_to_coverage_enabled: false,
-                    },
-                    label: None,
-                }),
+        let module = handler
+            .device()
+            .create_shader_module(&ShaderModuleDescriptor {
+                label: None,
+                source,
+            });
+        plane_pipeline(
+            handler,
+            layout,
+            sample_count,
+            &module,
+            self.vs_entpt,
+            self.fs_entpt,
         )
     }
 }
 
+pub fn plane_pipeline(
+    handler: &DeviceHandler,
+    layout: &PipelineLayout,
+    sample_count: u32,
+    module: &ShaderModule,
+    vs_entpt: &str,
+    fs_entpt: &str,
+) -> Arc<RenderPipeline> {
+    let config = handler.config();
+    Arc::new(
+        handler
+            .device()
+            .create_render_pipeline(&RenderPipelineDescriptor {
+                layout: Some(layout),
+                vertex: VertexState {
+                    module,
+                    entry_point: vs_entpt,
+                    buffers: &[VertexBufferLayout {
+                        array_stride: std::mem::size_of::<u32>() as BufferAddress,
+                        step_mode: VertexStepMode::Vertex,
+                        attributes: &[VertexAttribute {
+                            format: VertexFormat::Uint32,
+                            offset: 0,
+                            shader_location: 0,
+                        }],
+                    }],
+                },
+                fragment: Some(FragmentState {
+                    module,
+                    entry_point: fs_entpt,
+                    targets: &[ColorTargetState {
+                        format: config.format,
+                        blend: Some(BlendState::REPLACE),
+                        write_mask: ColorWrites::ALL,
+                    }],
+                }),
+                primitive: PrimitiveState {
+                    topology: PrimitiveTopology::TriangleList,
+                    front_face: FrontFace::Ccw,
+                    cull_mode: Some(Face::Back),
+                    polygon_mode: PolygonMode::Fill,
+                    clamp_depth: false,
+                    ..Default::default()
+                },
+                depth_stencil: Some(DepthStencilState {
+                    format: TextureFormat::Depth32Float,
+                    depth_write_enabled: true,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: Default::default(),
+                    bias: Default::default(),
+                }),
+                multisample: MultisampleState {
+                    count: sample_count,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                label: None,
+            }),
+    )
+}
+
 pub fn init_device(instance: &Instance) -> (Arc<Device>, Arc<Queue>) {
     futures::executor::block_on(async {
         let adapter = instance
@@ -0,0 +1,102 @@
+mod common;
+use common::Plane;
+use std::sync::{Arc, Mutex};
+use truck_platform::*;
+use wgpu::*;
+
+const PICTURE_WIDTH: u32 = 256;
+const PICTURE_HEIGHT: u32 = 256;
+
+#[derive(Clone, Debug)]
+struct ShaderPlane {
+    plane: Plane<'static>,
+    shader: ShaderHandle,
+}
+
+impl Rendered for ShaderPlane {
+    fn render_id(&self) -> RenderID {
+        self.plane.render_id()
+    }
+    fn vertex_buffer(
+        &self,
+        handler: &DeviceHandler,
+    ) -> (Arc<BufferHandler>, Option<Arc<BufferHandler>>) {
+        self.plane.vertex_buffer(handler)
+    }
+    fn bind_group_layout(&self, handler: &DeviceHandler) -> Arc<BindGroupLayout> {
+        self.plane.bind_group_layout(handler)
+    }
+    fn bind_group(&self, handler: &DeviceHandler, layout: &BindGroupLayout) -> Arc<BindGroup> {
+        self.plane.bind_group(handler, layout)
+    }
+    fn pipeline(
+        &self,
+        handler: &DeviceHandler,
+        layout: &PipelineLayout,
+        sample_count: u32,
+    ) -> Arc<RenderPipeline> {
+        common::plane_pipeline(
+            handler,
+            layout,
+            sample_count,
+            &self.shader.module(),
+            "vs_main",
+            "fs_main",
+        )
+    }
+}
+
+fn render(scene: &mut Scene, texture: &Texture) -> Vec<u8> {
+    scene.render_scene(&texture.create_view(&Default::default()));
+    common::read_texture(scene.device_handler(), texture)
+}
+
+#[test]
+fn shader_hot_reload() {
+    let _ = env_logger::try_init();
+    let instance = Instance::new(Backends::PRIMARY);
+    let (device, queue) = common::init_device(&instance);
+    let config = SurfaceConfiguration {
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        format: TextureFormat::Rgba8UnormSrgb,
+        width: PICTURE_WIDTH,
+        height: PICTURE_HEIGHT,
+        present_mode: PresentMode::Mailbox,
+    };
+    let texture = device.create_texture(&common::texture_descriptor(&config));
+    let handler = DeviceHandler::new(device, queue, Arc::new(Mutex::new(config)));
+    let mut scene = Scene::new(handler, &Default::default());
+
+    match scene.create_shader("this is not wgsl") {
+        Err(ShaderError::Parse(_)) => {}
+        _ => panic!("invalid shader is accepted"),
+    }
+    assert_eq!(scene.number_of_shaders(), 0);
+
+    let shader = scene
+        .create_shader(include_str!("shaders/unicolor.wgsl"))
+        .unwrap();
+    let plane = ShaderPlane {
+        plane: new_plane!("shaders/unicolor.wgsl", "vs_main", "fs_main"),
+        shader: shader.clone(),
+    };
+    scene.add_object(&plane);
+    assert!(scene.link_shader(&plane, &[&shader]));
+    let buffer0 = render(&mut scene, &texture);
+
+    // the shader is kept if the new source is invalid.
+    assert!(scene.replace_shader(&shader, "fn fs_main() {").is_err());
+    assert_eq!(shader.source(), include_str!("shaders/unicolor.wgsl"));
+    let buffer1 = render(&mut scene, &texture);
+    assert!(common::same_buffer(&buffer0, &buffer1));
+
+    // the pipeline is rebuilt without calling `Scene::update_pipeline`.
+    let source = include_str!("shaders/unicolor.wgsl").replace("0.2, 0.4, 0.6", "0.6, 0.4, 0.2");
+    assert_eq!(scene.replace_shader(&shader, &source).unwrap(), 1);
+    let buffer2 = render(&mut scene, &texture);
+    assert!(!common::same_buffer(&buffer0, &buffer2));
+
+    assert!(scene.remove_shader(&shader));
+    assert_eq!(scene.number_of_shaders(), 0);
+    assert_eq!(scene.replace_shader(&shader, &source).unwrap(), 0);
+}
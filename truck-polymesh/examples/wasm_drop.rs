@@ -0,0 +1,72 @@
+//! Loads the mesh from the bytes of a file dropped on a web page.
+//!
+//! The I/O modules read from `&[u8]` and never touch the file system, so this example is
+//! built for WebAssembly as it is:
+//!
+//! ```bash
+//! cargo build --example wasm_drop --target wasm32-unknown-unknown
+//! ```
+//!
+//! JavaScript copies the bytes of the dropped file into the buffer of the module:
+//!
+//! ```js
+//! const { instance } = await WebAssembly.instantiateStreaming(fetch("wasm_drop.wasm"));
+//! const { memory, input_buffer, load_mesh } = instance.exports;
+//! window.ondragover = (e) => e.preventDefault();
+//! window.ondrop = async (e) => {
+//!     e.preventDefault();
+//!     const file = e.dataTransfer.files[0];
+//!     const bytes = new Uint8Array(await file.arrayBuffer());
+//!     const ptr = input_buffer(bytes.length);
+//!     new Uint8Array(memory.buffer, ptr, bytes.length).set(bytes);
+//!     const format = file.name.toLowerCase().endsWith(".stl") ? 1 : 0;
+//!     console.log(`${load_mesh(format)} faces`);
+//! };
+//! ```
+
+use std::cell::RefCell;
+use truck_polymesh::*;
+
+thread_local! {
+    static INPUT: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reads the mesh from `bytes` of obj (`format == 0`) or STL (`format == 1`).
+fn load(bytes: &[u8], format: u32) -> Result<PolygonMesh> {
+    match format {
+        1 => stl::read(bytes, stl::STLType::Automatic),
+        _ => obj::read(bytes),
+    }
+}
+
+/// Resizes the input buffer to `len` bytes and returns its address, to which JavaScript
+/// writes the bytes of the file.
+#[no_mangle]
+pub extern "C" fn input_buffer(len: usize) -> *mut u8 {
+    INPUT.with(|input| {
+        let mut input = input.borrow_mut();
+        input.resize(len, 0);
+        input.as_mut_ptr()
+    })
+}
+
+/// Reads the mesh from the input buffer and returns the number of the faces,
+/// or `-1` if the bytes are not a mesh.
+#[no_mangle]
+pub extern "C" fn load_mesh(format: u32) -> i32 {
+    INPUT.with(|input| match load(&input.borrow(), format) {
+        Ok(mesh) => mesh.faces().len() as i32,
+        Err(_) => -1,
+    })
+}
+
+fn main() {
+    // simulates the drop of the file on native targets
+    let bytes = include_bytes!("../tests/data/teapot-position.obj");
+    let ptr = input_buffer(bytes.len());
+    INPUT.with(|input| {
+        assert_eq!(input.borrow_mut().as_mut_ptr(), ptr);
+        input.borrow_mut().copy_from_slice(bytes);
+    });
+    println!("{} faces", load_mesh(0));
+}
@@ -0,0 +1,310 @@
+use crate::*;
+use std::io::{BufWriter, Write};
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+/// Mesh object with its metadata
+#[derive(Clone, Debug, PartialEq)]
+pub struct AMFObject<'a> {
+    /// the mesh of the object
+    pub mesh: &'a PolygonMesh,
+    /// the pairs of the types and the values of the metadata, e.g. `("name", "teapot")`.
+    pub metadata: Vec<(String, String)>,
+}
+
+impl<'a> AMFObject<'a> {
+    /// Creates the object with the metadata of its name.
+    #[inline(always)]
+    pub fn new(name: &str, mesh: &'a PolygonMesh) -> AMFObject<'a> {
+        AMFObject {
+            mesh,
+            metadata: vec![("name".to_string(), name.to_string())],
+        }
+    }
+}
+
+/// Placement of an object or a constellation in a constellation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AMFInstance {
+    /// the index of the placed object. The objects are indexed from `0`, and the
+    /// constellations [`AMFDescriptor::constellations`] follow the objects.
+    ///
+    /// [`AMFDescriptor::constellations`]: ./struct.AMFDescriptor.html#structfield.constellations
+    pub object: usize,
+    /// the translation in the unit of the descriptor
+    pub translation: Vector3,
+    /// the rotation angles in degrees around the x-, y- and z-axes, applied in this order.
+    pub rotation: Vector3,
+}
+
+impl AMFInstance {
+    /// Returns the instance placed without transforms.
+    #[inline(always)]
+    pub fn new(object: usize) -> AMFInstance {
+        AMFInstance {
+            object,
+            translation: Vector3::zero(),
+            rotation: Vector3::zero(),
+        }
+    }
+
+    /// Returns the affine transform of the instance.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use amf::AMFInstance;
+    /// let instance = AMFInstance {
+    ///     object: 0,
+    ///     translation: Vector3::new(1.0, 0.0, 0.0),
+    ///     rotation: Vector3::new(0.0, 0.0, 90.0),
+    /// };
+    /// let pt = instance.transform().transform_point(Point3::new(1.0, 0.0, 0.0));
+    /// assert!(pt.near(&Point3::new(1.0, 1.0, 0.0)));
+    /// ```
+    #[inline(always)]
+    pub fn transform(&self) -> Matrix4 {
+        Matrix4::from_translation(self.translation)
+            * Matrix4::from_angle_z(Deg(self.rotation[2]))
+            * Matrix4::from_angle_y(Deg(self.rotation[1]))
+            * Matrix4::from_angle_x(Deg(self.rotation[0]))
+    }
+}
+
+/// Constellation, the assembly of placed objects and constellations
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AMFConstellation {
+    /// the pairs of the types and the values of the metadata
+    pub metadata: Vec<(String, String)>,
+    /// the instances, whose objects are the indices of objects or of the preceding
+    /// constellations
+    pub instances: Vec<AMFInstance>,
+}
+
+/// Configures of AMF files
+#[derive(Clone, Debug, PartialEq)]
+pub struct AMFDescriptor {
+    /// the unit of the lengths of meshes. Default is `LengthUnit::Millimeter`.
+    ///
+    /// AMF has no centimeter, so the lengths in centimeters are written in millimeters.
+    pub unit: LengthUnit,
+    /// the metadata of the whole file. Default is empty.
+    pub metadata: Vec<(String, String)>,
+    /// the constellations, following the objects. Default is empty.
+    pub constellations: Vec<AMFConstellation>,
+}
+
+impl Default for AMFDescriptor {
+    #[inline(always)]
+    fn default() -> AMFDescriptor {
+        AMFDescriptor {
+            unit: LengthUnit::Millimeter,
+            metadata: Vec::new(),
+            constellations: Vec::new(),
+        }
+    }
+}
+
+/// the unit attribute of AMF and the scale of lengths into it
+fn unit_attribute(unit: LengthUnit) -> (&'static str, f64) {
+    match unit {
+        LengthUnit::Micrometer => ("micron", 1.0),
+        LengthUnit::Millimeter => ("millimeter", 1.0),
+        LengthUnit::Centimeter => ("millimeter", unit.factor_to(LengthUnit::Millimeter)),
+        LengthUnit::Meter => ("meter", 1.0),
+        LengthUnit::Inch => ("inch", 1.0),
+        LengthUnit::Foot => ("feet", 1.0),
+    }
+}
+
+fn escape(string: &str) -> String {
+    string
+        .chars()
+        .fold(String::with_capacity(string.len()), |mut res, c| {
+            match c {
+                '&' => res.push_str("&amp;"),
+                '<' => res.push_str("&lt;"),
+                '>' => res.push_str("&gt;"),
+                '"' => res.push_str("&quot;"),
+                '\'' => res.push_str("&apos;"),
+                _ => res.push(c),
+            }
+            res
+        })
+}
+
+fn write_metadata<W: Write>(
+    metadata: &[(String, String)],
+    indent: &str,
+    writer: &mut W,
+) -> Result<()> {
+    metadata.iter().try_for_each(|(key, value)| {
+        writer.write_fmt(format_args!(
+            "{}<metadata type=\"{}\">{}</metadata>\n",
+            indent,
+            escape(key),
+            escape(value),
+        ))
+    })?;
+    Ok(())
+}
+
+/// Writes mesh objects and constellations into an AMF file.
+///
+/// The faces of each mesh are triangulated as fans and written as one volume.
+/// The degenerate triangles are removed, while the other defects such as holes and
+/// non-manifold edges are not repaired. Only positions are written.
+///
+/// The constellations and the unit are configured by `desc`.
+/// Writing a mesh without faces returns an error.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use amf::{AMFConstellation, AMFDescriptor, AMFInstance, AMFObject};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let square = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // two squares, the second one is shifted and turned
+/// let pair = AMFConstellation {
+///     metadata: vec![("name".to_string(), "pair".to_string())],
+///     instances: vec![
+///         AMFInstance::new(0),
+///         AMFInstance {
+///             object: 0,
+///             translation: Vector3::new(0.0, 0.0, 1.0),
+///             rotation: Vector3::new(0.0, 0.0, 45.0),
+///         },
+///     ],
+/// };
+/// let desc = AMFDescriptor {
+///     unit: LengthUnit::Inch,
+///     constellations: vec![pair],
+///     ..Default::default()
+/// };
+///
+/// let mut bytes = Vec::new();
+/// amf::write(&[AMFObject::new("square", &square)], &mut bytes, &desc).unwrap();
+/// let amf = String::from_utf8(bytes).unwrap();
+/// assert!(amf.contains("<amf unit=\"inch\" version=\"1.1\">"));
+/// // the quadrangle is triangulated
+/// assert_eq!(amf.matches("<triangle>").count(), 2);
+/// assert_eq!(amf.matches("<instance objectid=\"0\">").count(), 2);
+/// ```
+pub fn write<W: Write>(objects: &[AMFObject], writer: W, desc: &AMFDescriptor) -> Result<()> {
+    sub_write(objects, writer, desc, 1.0)
+}
+
+/// Writes mesh objects and constellations into an AMF file whose lengths, the positions of
+/// meshes and the translations of instances, are converted from the unit of `context` into
+/// `desc.unit`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use amf::{AMFDescriptor, AMFObject};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2]]);
+/// let triangle = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // the model is in meters, and the file is in millimeters.
+/// let context = ModelContext::new(LengthUnit::Meter, 1.0e-9);
+/// let objects = [AMFObject::new("triangle", &triangle)];
+/// let mut bytes = Vec::new();
+/// amf::write_with_context(&objects, &mut bytes, &AMFDescriptor::default(), &context).unwrap();
+/// let amf = String::from_utf8(bytes).unwrap();
+/// assert!(amf.contains("<x>1000</x><y>1000</y><z>0</z>"));
+/// ```
+pub fn write_with_context<W: Write>(
+    objects: &[AMFObject],
+    writer: W,
+    desc: &AMFDescriptor,
+    context: &ModelContext,
+) -> Result<()> {
+    sub_write(objects, writer, desc, context.scale_to(desc.unit))
+}
+
+/// `scale` converts the lengths of `objects` and `desc` into `desc.unit`.
+fn sub_write<W: Write>(
+    objects: &[AMFObject],
+    writer: W,
+    desc: &AMFDescriptor,
+    scale: f64,
+) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    desc.constellations
+        .iter()
+        .enumerate()
+        .try_for_each(|(i, constellation)| {
+            let len = objects.len() + i;
+            constellation.instances.iter().try_for_each(|instance| {
+                match instance.object < len {
+                    true => Ok(()),
+                    false => Err(errors::Error::OutOfRange("objects", len, instance.object)),
+                }
+            })
+        })?;
+    let (unit, unit_scale) = unit_attribute(desc.unit);
+    let scale = scale * unit_scale;
+    writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+    writer.write_fmt(format_args!("<amf unit=\"{}\" version=\"1.1\">\n", unit))?;
+    writer.write_all(b" <metadata type=\"producer\">truck-polymesh</metadata>\n")?;
+    write_metadata(&desc.metadata, " ", &mut writer)?;
+    for (i, object) in objects.iter().enumerate() {
+        let triangles = object.mesh.position_triangles();
+        if triangles.is_empty() {
+            return Err(invalid_input("AMF cannot store meshes without faces"));
+        }
+        writer.write_fmt(format_args!(" <object id=\"{}\">\n", i))?;
+        write_metadata(&object.metadata, "  ", &mut writer)?;
+        writer.write_all(b"  <mesh>\n   <vertices>\n")?;
+        for p in object.mesh.positions() {
+            let p = p * scale;
+            writer.write_fmt(format_args!(
+                "    <vertex><coordinates><x>{}</x><y>{}</y><z>{}</z></coordinates></vertex>\n",
+                p[0], p[1], p[2],
+            ))?;
+        }
+        writer.write_all(b"   </vertices>\n   <volume>\n")?;
+        for [v0, v1, v2] in triangles {
+            writer.write_fmt(format_args!(
+                "    <triangle><v1>{}</v1><v2>{}</v2><v3>{}</v3></triangle>\n",
+                v0, v1, v2,
+            ))?;
+        }
+        writer.write_all(b"   </volume>\n  </mesh>\n </object>\n")?;
+    }
+    for (i, constellation) in desc.constellations.iter().enumerate() {
+        let id = objects.len() + i;
+        writer.write_fmt(format_args!(" <constellation id=\"{}\">\n", id))?;
+        write_metadata(&constellation.metadata, "  ", &mut writer)?;
+        for instance in &constellation.instances {
+            let delta = instance.translation * scale;
+            let rotation = instance.rotation;
+            writer.write_fmt(format_args!(
+                "  <instance objectid=\"{}\"><deltax>{}</deltax><deltay>{}</deltay>\
+                 <deltaz>{}</deltaz><rx>{}</rx><ry>{}</ry><rz>{}</rz></instance>\n",
+                instance.object,
+                delta[0],
+                delta[1],
+                delta[2],
+                rotation[0],
+                rotation[1],
+                rotation[2],
+            ))?;
+        }
+        writer.write_all(b" </constellation>\n")?;
+    }
+    writer.write_all(b"</amf>\n")?;
+    writer.flush()?;
+    Ok(())
+}
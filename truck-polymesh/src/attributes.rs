@@ -0,0 +1,179 @@
+use crate::*;
+use std::collections::BTreeMap;
+
+/// Named arrays of the values of type `T`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attributes<T>(BTreeMap<String, Vec<T>>);
+
+impl<T> Default for Attributes<T> {
+    #[inline(always)]
+    fn default() -> Self { Attributes(BTreeMap::new()) }
+}
+
+impl<T: Clone> Attributes<T> {
+    /// Returns the values of the attribute `name`.
+    #[inline(always)]
+    pub fn get(&self, name: &str) -> Option<&Vec<T>> { self.0.get(name) }
+    /// Returns the mutable slice of the values of the attribute `name`.
+    #[inline(always)]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut [T]> {
+        self.0.get_mut(name).map(Vec::as_mut_slice)
+    }
+    /// Returns the iterator over the names and the values of the attributes.
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<T>)> { self.0.iter() }
+    /// Returns the number of the attributes.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.0.len() }
+    /// Returns whether there are no attributes or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+    #[inline(always)]
+    fn insert(&mut self, name: String, values: Vec<T>) -> Option<Vec<T>> {
+        self.0.insert(name, values)
+    }
+    #[inline(always)]
+    fn remove(&mut self, name: &str) -> Option<Vec<T>> { self.0.remove(name) }
+    fn reindex(&mut self, map: &[usize]) {
+        self.0.values_mut().for_each(|values| {
+            *values = map.iter().map(|i| values[*i].clone()).collect();
+        })
+    }
+    fn append(&mut self, mut other: Self, len: usize, other_len: usize, padding: T) {
+        let names: Vec<String> = other.0.keys().cloned().collect();
+        for name in names {
+            self.0.entry(name).or_insert_with(|| vec![padding.clone(); len]);
+        }
+        for (name, values) in &mut self.0 {
+            match other.0.remove(name) {
+                Some(other_values) => values.extend(other_values),
+                None => values.extend(std::iter::repeat_n(padding.clone(), other_len)),
+            }
+        }
+    }
+}
+
+/// The types of the values of attributes held in [`MeshAttributes`].
+///
+/// [`MeshAttributes`]: ./struct.MeshAttributes.html
+pub trait AttributeType: Clone + Zero {
+    /// Returns the attributes of this type.
+    fn attributes(attributes: &MeshAttributes) -> &Attributes<Self>;
+    /// Returns the mutable reference to the attributes of this type.
+    fn attributes_mut(attributes: &mut MeshAttributes) -> &mut Attributes<Self>;
+}
+
+/// The named attributes of the vertices or the faces of [`PolygonMesh`], e.g. scalar fields,
+/// labels and weights. The `i`-th value of each attribute belongs to the `i`-th element.
+///
+/// The values are `f64` as scalars, `usize` as labels, or `Vector3` as vectors.
+/// The named selection sets of the elements are also held as the flags, which are `true` for
+/// the selected elements, so that the filters can be limited to the selected regions.
+/// The attributes are kept by the filters which reorder, merge or split the elements.
+///
+/// [`PolygonMesh`]: ../struct.PolygonMesh.html
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeshAttributes {
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    scalars: Attributes<f64>,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    labels: Attributes<usize>,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    vectors: Attributes<Vector3>,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    selections: Attributes<bool>,
+}
+
+macro_rules! impl_attribute_type {
+    ($type: ty, $field: ident) => {
+        impl AttributeType for $type {
+            #[inline(always)]
+            fn attributes(attributes: &MeshAttributes) -> &Attributes<Self> { &attributes.$field }
+            #[inline(always)]
+            fn attributes_mut(attributes: &mut MeshAttributes) -> &mut Attributes<Self> {
+                &mut attributes.$field
+            }
+        }
+    };
+}
+impl_attribute_type!(f64, scalars);
+impl_attribute_type!(usize, labels);
+impl_attribute_type!(Vector3, vectors);
+
+impl MeshAttributes {
+    /// Returns the values of the attribute `name` of type `T`.
+    #[inline(always)]
+    pub fn get<T: AttributeType>(&self, name: &str) -> Option<&Vec<T>> {
+        T::attributes(self).get(name)
+    }
+    /// Returns the mutable slice of the values of the attribute `name` of type `T`.
+    #[inline(always)]
+    pub fn get_mut<T: AttributeType>(&mut self, name: &str) -> Option<&mut [T]> {
+        T::attributes_mut(self).get_mut(name)
+    }
+    /// Returns the attributes of type `T`.
+    #[inline(always)]
+    pub fn attributes<T: AttributeType>(&self) -> &Attributes<T> { T::attributes(self) }
+    /// Removes the attribute `name` of type `T`, and returns its values.
+    #[inline(always)]
+    pub fn remove<T: AttributeType>(&mut self, name: &str) -> Option<Vec<T>> {
+        T::attributes_mut(self).remove(name)
+    }
+    /// Returns the flags of the selection `name`, which are `true` for the selected elements.
+    #[inline(always)]
+    pub fn selection(&self, name: &str) -> Option<&Vec<bool>> { self.selections.get(name) }
+    /// Returns the mutable slice of the flags of the selection `name`.
+    #[inline(always)]
+    pub fn selection_mut(&mut self, name: &str) -> Option<&mut [bool]> {
+        self.selections.get_mut(name)
+    }
+    /// Returns the selections.
+    #[inline(always)]
+    pub fn selections(&self) -> &Attributes<bool> { &self.selections }
+    /// Removes the selection `name`, and returns its flags.
+    #[inline(always)]
+    pub fn remove_selection(&mut self, name: &str) -> Option<Vec<bool>> {
+        self.selections.remove(name)
+    }
+    /// Returns whether there are no attributes and no selections or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.scalars.is_empty()
+            && self.labels.is_empty()
+            && self.vectors.is_empty()
+            && self.selections.is_empty()
+    }
+    /// Replaces the values of all the attributes so that the `i`-th new value is
+    /// the `map[i]`-th old value. Used when the elements are reordered, removed or duplicated.
+    pub fn reindex(&mut self, map: &[usize]) {
+        self.scalars.reindex(map);
+        self.labels.reindex(map);
+        self.vectors.reindex(map);
+        self.selections.reindex(map);
+    }
+    #[inline(always)]
+    pub(crate) fn insert<T: AttributeType>(
+        &mut self,
+        name: String,
+        values: Vec<T>,
+    ) -> Option<Vec<T>> {
+        T::attributes_mut(self).insert(name, values)
+    }
+    #[inline(always)]
+    pub(crate) fn insert_selection(
+        &mut self,
+        name: String,
+        flags: Vec<bool>,
+    ) -> Option<Vec<bool>> {
+        self.selections.insert(name, flags)
+    }
+    /// Appends the attributes of `other` whose elements follow the `len` elements of `self`.
+    /// The values of the attributes only in either of them are padded by zeros, and
+    /// the elements are not selected by the selections only in either of them.
+    pub(crate) fn append(&mut self, other: MeshAttributes, len: usize, other_len: usize) {
+        self.scalars.append(other.scalars, len, other_len, f64::zero());
+        self.labels.append(other.labels, len, other_len, usize::zero());
+        self.vectors.append(other.vectors, len, other_len, Vector3::zero());
+        self.selections.append(other.selections, len, other_len, false);
+    }
+}
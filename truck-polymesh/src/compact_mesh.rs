@@ -0,0 +1,168 @@
+use crate::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+impl CompactMesh {
+    /// Returns the vector of all positions.
+    #[inline(always)]
+    pub fn positions(&self) -> &Vec<Point3> { &self.positions }
+
+    /// Returns the vector of all uv (texture) coordinates.
+    /// The vector is empty or has the same length as the positions.
+    #[inline(always)]
+    pub fn uv_coords(&self) -> &Vec<Vector2> { &self.uv_coords }
+
+    /// Returns the vector of all normals.
+    /// The vector is empty or has the same length as the positions.
+    #[inline(always)]
+    pub fn normals(&self) -> &Vec<Vector3> { &self.normals }
+
+    /// Returns the vector of all colors.
+    /// The vector is empty or has the same length as the positions.
+    #[inline(always)]
+    pub fn colors(&self) -> &Vec<Vector4> { &self.colors }
+
+    /// Returns the flat index buffer of all faces.
+    #[inline(always)]
+    pub fn indices(&self) -> &[u32] { &self.indices }
+
+    /// Returns the end offsets of faces in the index buffer.
+    #[inline(always)]
+    pub fn offsets(&self) -> &[u32] { &self.offsets }
+
+    /// Returns the number of faces.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.offsets.len() }
+
+    /// Returns true if there are no faces.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.offsets.is_empty() }
+
+    /// Returns the indices of the `idx`th face.
+    /// # Panics
+    /// Panic occurs if `idx` is out of range.
+    #[inline(always)]
+    pub fn face(&self, idx: usize) -> &[u32] {
+        let start = match idx {
+            0 => 0,
+            _ => self.offsets[idx - 1] as usize,
+        };
+        &self.indices[start..self.offsets[idx] as usize]
+    }
+
+    /// Returns the iterator of the indices of faces, in the order of
+    /// [`Faces::face_iter`](./struct.Faces.html#method.face_iter) of the original mesh.
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = &[u32]> {
+        (0..self.len()).map(move |i| self.face(i))
+    }
+
+    /// Returns the bounding box of the positions.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> { self.positions.iter().collect() }
+}
+
+#[inline(always)]
+fn into_u32(idx: usize) -> u32 {
+    u32::try_from(idx).unwrap_or_else(|_| panic!("the index {} exceeds u32", idx))
+}
+
+// the attribute of `idx` or zero if missing, only when some corners have the attribute
+#[inline(always)]
+fn gather<T: Copy + Zero>(attrs: &mut Vec<T>, array: &[T], idx: Option<usize>, used: bool) {
+    if used {
+        attrs.push(idx.map(|i| array[i]).unwrap_or_else(T::zero))
+    }
+}
+
+impl<F: FaceStorage> From<PolygonMeshSlice<'_, F>> for CompactMesh {
+    /// Deduplicates the corners.
+    ///
+    /// The uv coordinates, the normals and the colors are held if at least one corner has them,
+    /// and the missing ones at the other corners are filled with zero.
+    /// # Panics
+    /// Panic occurs if the number of distinct corners or the number of all corners
+    /// exceeds `u32::MAX`.
+    fn from(mesh: PolygonMeshSlice<'_, F>) -> CompactMesh {
+        let corners = || mesh.face_iter().flatten();
+        let has_uv = corners().any(|v| v.uv.is_some());
+        let has_nor = corners().any(|v| v.nor.is_some());
+        let has_col = corners().any(|v| v.col.is_some());
+        let mut res = CompactMesh::default();
+        let mut map = HashMap::<Vertex, u32>::new();
+        for face in mesh.face_iter() {
+            for v in face {
+                let idx = *map.entry(*v).or_insert_with(|| {
+                    res.positions.push(mesh.positions()[v.pos]);
+                    gather(&mut res.uv_coords, mesh.uv_coords(), v.uv, has_uv);
+                    gather(&mut res.normals, mesh.normals(), v.nor, has_nor);
+                    gather(&mut res.colors, mesh.colors(), v.col, has_col);
+                    into_u32(res.positions.len() - 1)
+                });
+                res.indices.push(idx);
+            }
+            res.offsets.push(into_u32(res.indices.len()));
+        }
+        res
+    }
+}
+
+impl<F: FaceStorage> From<&PolygonMesh<F>> for CompactMesh {
+    /// Deduplicates the corners. The named attributes are dropped.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let normals = vec![Vector3::unit_z(), -Vector3::unit_z()];
+    /// let faces = Faces::from_iter(&[
+    ///     [(0, None, Some(0)), (1, None, Some(0)), (2, None, Some(0))],
+    ///     [(0, None, Some(0)), (2, None, Some(0)), (3, None, Some(0))],
+    ///     [(0, None, Some(1)), (3, None, Some(1)), (2, None, Some(1))],
+    /// ]);
+    /// let mesh = PolygonMesh::new(positions, vec![], normals, faces);
+    /// let compact = CompactMesh::from(&mesh);
+    /// // the corners of the back face are distinct from the ones of the front faces
+    /// assert_eq!(compact.positions().len(), 7);
+    /// assert_eq!(compact.normals().len(), 7);
+    /// assert!(compact.uv_coords().is_empty());
+    /// assert_eq!(compact.indices(), &[0, 1, 2, 0, 2, 3, 4, 5, 6]);
+    /// assert_eq!(compact.face(1), &[0, 2, 3]);
+    /// ```
+    #[inline(always)]
+    fn from(mesh: &PolygonMesh<F>) -> CompactMesh { mesh.as_slice().into() }
+}
+
+impl From<&CompactMesh> for PolygonMesh {
+    /// Each vertex of the compact mesh refers to the position, the uv coordinate, the normal and
+    /// the color with the same index.
+    fn from(mesh: &CompactMesh) -> PolygonMesh {
+        let vertex = |idx: &u32| {
+            let idx = *idx as usize;
+            let attr = |attrs_len: usize| match attrs_len {
+                0 => None,
+                _ => Some(idx),
+            };
+            Vertex {
+                pos: idx,
+                uv: attr(mesh.uv_coords.len()),
+                nor: attr(mesh.normals.len()),
+                col: attr(mesh.colors.len()),
+            }
+        };
+        let faces = Faces::from_iter(
+            mesh.face_iter().map(|face| face.iter().map(vertex).collect::<Vec<_>>()),
+        );
+        PolygonMesh::from_raw_parts(
+            mesh.positions.clone(),
+            mesh.uv_coords.clone(),
+            mesh.normals.clone(),
+            mesh.colors.clone(),
+            faces,
+        )
+    }
+}
@@ -75,6 +75,33 @@ pub enum Error {
     /// ``` 
     #[error("This division vector is unsorted.")]
     UnsortedDivision,
+    /// The heightmap image has less than two pixels in width or height.
+    #[error("The heightmap image must have at least 2 x 2 pixels.")]
+    TooSmallHeightmap,
+    /// The range of x or y of the heightmap has no width.
+    #[error("The ranges of the heightmap must have nonzero widths.")]
+    EmptyHeightmapRange,
+    /// The number of the values of an attribute is not the number of the vertices or the faces.
+    #[error("The attribute {0} requires {1} values, but {2} values are given.")]
+    AttributeLength(String, usize, usize),
+    /// The end offsets of [`FlatFaces`](../struct.FlatFaces.html) do not increase by at least
+    /// three, or the last offset is not the length of the index buffer.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use errors::Error;
+    ///
+    /// let indices: Vec<Vertex> = (0..5).map(Vertex::from).collect();
+    /// match FlatFaces::try_from_raw_parts(indices, vec![3, 4]) {
+    ///     Err(Error::InvalidFaceOffsets(len)) => assert_eq!(len, 5),
+    ///     _ => panic!("wrong result!"),
+    /// }
+    /// ```
+    #[error("The end offsets of faces are invalid for the index buffer with the length {0}.")]
+    InvalidFaceOffsets(usize),
+    /// The work is cancelled by [`Progress`](../progress/trait.Progress.html).
+    #[error("The work is cancelled.")]
+    Cancelled,
     /// Errors caused by obj files I/O.
     #[error(transparent)]
     FromIO(#[from] std::io::Error),
@@ -0,0 +1,309 @@
+use crate::*;
+use errors::Error;
+
+impl FlatFaces {
+    /// Creates faces of a polygon by iterator of slice.
+    ///
+    /// If `face.len() < 3`, the face is ignored.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let slice: &[&[[usize; 3]]] = &[
+    ///     &[[0, 0, 0], [1, 1, 1], [2, 2, 2]],
+    ///     &[[0, 0, 0], [4, 4, 4], [5, 5, 5], [1, 1, 1]],
+    ///     &[[0, 0, 0], [2, 2, 2], [3, 3, 3]],
+    /// ];
+    /// let faces = FlatFaces::from_iter(slice);
+    /// assert_eq!(faces.len(), 3);
+    /// assert_eq!(faces.indices().len(), 10);
+    /// assert_eq!(faces.offsets(), &[3, 7, 10]);
+    /// ```
+    #[inline(always)]
+    pub fn from_iter<V: Copy + Into<Vertex>, T: AsRef<[V]>, I: IntoIterator<Item = T>>(
+        iter: I,
+    ) -> FlatFaces {
+        let mut faces = FlatFaces::default();
+        faces.extend(iter);
+        faces
+    }
+
+    /// Creates faces by the flat index buffer and the end offsets of faces.
+    /// # Errors
+    /// Returns [`Error::InvalidFaceOffsets`] if a face has less than three vertices,
+    /// i.e. `offsets` does not increase by at least three, or the last offset is not
+    /// `indices.len()`.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let indices: Vec<Vertex> = [0, 1, 2, 0, 2, 3].iter().map(Vertex::from).collect();
+    /// let faces = FlatFaces::try_from_raw_parts(indices.clone(), vec![3, 6]).unwrap();
+    /// assert_eq!(faces.len(), 2);
+    /// assert_eq!(faces[1][2].pos, 3);
+    ///
+    /// assert!(FlatFaces::try_from_raw_parts(indices.clone(), vec![3, 5]).is_err());
+    /// assert!(FlatFaces::try_from_raw_parts(indices, vec![2, 6]).is_err());
+    /// ```
+    ///
+    /// [`Error::InvalidFaceOffsets`]: ./errors/enum.Error.html#variant.InvalidFaceOffsets
+    pub fn try_from_raw_parts(indices: Vec<Vertex>, offsets: Vec<usize>) -> Result<FlatFaces> {
+        let mut start = 0;
+        let increasing = offsets.iter().all(|&end| {
+            let valid = end >= start + 3;
+            start = end;
+            valid
+        });
+        match increasing && start == indices.len() {
+            true => Ok(FlatFaces { indices, offsets }),
+            false => Err(Error::InvalidFaceOffsets(indices.len())),
+        }
+    }
+
+    /// Extends faces by an iterator.
+    #[inline(always)]
+    pub fn extend<V: Copy + Into<Vertex>, T: AsRef<[V]>, I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) {
+        iter.into_iter().for_each(|face| self.push(face))
+    }
+
+    /// Push a face to the faces.
+    ///
+    /// If `face.len() < 3`, the face is ignored.
+    #[inline(always)]
+    pub fn push<V: Copy + Into<Vertex>, T: AsRef<[V]>>(&mut self, face: T) {
+        let face = face.as_ref();
+        if face.len() >= 3 {
+            self.indices.extend(face.iter().map(|v| (*v).into()));
+            self.offsets.push(self.indices.len());
+        }
+    }
+
+    /// Returns the flat index buffer of all faces.
+    #[inline(always)]
+    pub fn indices(&self) -> &[Vertex] { &self.indices }
+
+    /// Returns the mutable flat index buffer of all faces.
+    #[inline(always)]
+    pub fn indices_mut(&mut self) -> &mut [Vertex] { &mut self.indices }
+
+    /// Returns the end offsets of faces in the index buffer.
+    #[inline(always)]
+    pub fn offsets(&self) -> &[usize] { &self.offsets }
+
+    /// Returns the range of the `idx`th face in the index buffer.
+    #[inline(always)]
+    fn range(&self, idx: usize) -> std::ops::Range<usize> {
+        let start = match idx {
+            0 => 0,
+            _ => self.offsets[idx - 1],
+        };
+        start..self.offsets[idx]
+    }
+
+    /// Returns the iterator of the slice.
+    ///
+    /// Unlike [`Faces::face_iter`](./struct.Faces.html#method.face_iter),
+    /// faces are iterated in the order in which they are registered.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let slice: &[&[usize]] = &[&[0, 4, 5, 1], &[0, 1, 2]];
+    /// let faces = FlatFaces::from_iter(slice);
+    /// let mut iter = faces.face_iter();
+    /// assert_eq!(iter.next().unwrap().len(), 4);
+    /// assert_eq!(iter.next().unwrap().len(), 3);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl ExactSizeIterator<Item = &[Vertex]> {
+        let indices = &self.indices;
+        let mut start = 0;
+        self.offsets.iter().map(move |&end| {
+            let face = &indices[start..end];
+            start = end;
+            face
+        })
+    }
+
+    /// Returns the mutable iterator of the slice.
+    ///
+    /// cf: [`FlatFaces::face_iter`](./struct.FlatFaces.html#method.face_iter)
+    #[inline(always)]
+    pub fn face_iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [Vertex]> {
+        let mut rest = self.indices.as_mut_slice();
+        let mut start = 0;
+        self.offsets.iter().map(move |&end| {
+            let (face, tail) = std::mem::take(&mut rest).split_at_mut(end - start);
+            rest = tail;
+            start = end;
+            face
+        })
+    }
+
+    /// Returns the number of faces.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.offsets.len() }
+
+    /// Returns whether there are no faces or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.offsets.is_empty() }
+
+    /// Merges `other` into `self`.
+    #[inline(always)]
+    pub fn naive_concat(&mut self, other: Self) {
+        let len = self.indices.len();
+        self.indices.extend(other.indices);
+        self.offsets
+            .extend(other.offsets.into_iter().map(|end| end + len));
+    }
+}
+
+impl FaceStorage for FlatFaces {
+    #[inline(always)]
+    fn face_iter(&self) -> impl Iterator<Item = &[Vertex]> { FlatFaces::face_iter(self) }
+    #[inline(always)]
+    fn face_iter_mut(&mut self) -> impl Iterator<Item = &mut [Vertex]> {
+        FlatFaces::face_iter_mut(self)
+    }
+    #[inline(always)]
+    fn len(&self) -> usize { self.len() }
+}
+
+impl FaceStorage for Faces {
+    #[inline(always)]
+    fn face_iter(&self) -> impl Iterator<Item = &[Vertex]> { Faces::face_iter(self) }
+    #[inline(always)]
+    fn face_iter_mut(&mut self) -> impl Iterator<Item = &mut [Vertex]> {
+        Faces::face_iter_mut(self)
+    }
+    #[inline(always)]
+    fn len(&self) -> usize { self.len() }
+}
+
+impl std::ops::Index<usize> for FlatFaces {
+    type Output = [Vertex];
+    #[inline(always)]
+    fn index(&self, idx: usize) -> &Self::Output { &self.indices[self.range(idx)] }
+}
+
+impl std::ops::IndexMut<usize> for FlatFaces {
+    #[inline(always)]
+    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+        let range = self.range(idx);
+        &mut self.indices[range]
+    }
+}
+
+impl Invertible for FlatFaces {
+    #[inline(always)]
+    fn invert(&mut self) { self.face_iter_mut().for_each(|f| f.reverse()); }
+    #[inline(always)]
+    fn inverse(&self) -> Self {
+        let mut faces = self.clone();
+        faces.invert();
+        faces
+    }
+}
+
+impl From<&Faces> for FlatFaces {
+    /// Converts in the order of `Faces::face_iter`.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let slice: &[&[usize]] = &[&[0, 4, 5, 1], &[0, 1, 2], &[1, 2, 6, 7, 8]];
+    /// let faces = Faces::from_iter(slice);
+    /// let flat_faces = FlatFaces::from(&faces);
+    /// assert!(faces.face_iter().eq(flat_faces.face_iter()));
+    /// assert_eq!(Faces::from(&flat_faces), faces);
+    /// ```
+    #[inline(always)]
+    fn from(faces: &Faces) -> FlatFaces {
+        let mut offsets = Vec::with_capacity(faces.len());
+        let mut indices = Vec::new();
+        faces.face_iter().for_each(|face| {
+            indices.extend_from_slice(face);
+            offsets.push(indices.len());
+        });
+        FlatFaces { indices, offsets }
+    }
+}
+
+impl From<&FlatFaces> for Faces {
+    #[inline(always)]
+    fn from(faces: &FlatFaces) -> Faces { Faces::from_iter(faces.face_iter()) }
+}
+
+impl From<&PolygonMesh> for PolygonMesh<FlatFaces> {
+    /// Converts the faces in the order of `Faces::face_iter`, which is the order of
+    /// the attributes of the faces. The attributes are kept.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.5, 2.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[&[0, 1, 2, 3][..], &[3, 2, 4]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// mesh.set_face_attribute("label", vec![3_usize, 4]).unwrap();
+    ///
+    /// let flat = PolygonMesh::<FlatFaces>::from(&mesh);
+    /// assert!(mesh.face_iter().eq(flat.face_iter()));
+    /// assert_eq!(flat.faces().indices().len(), 7);
+    /// assert_eq!(flat.face_attributes(), mesh.face_attributes());
+    /// assert_eq!(PolygonMesh::from(&flat), mesh);
+    /// ```
+    fn from(mesh: &PolygonMesh) -> PolygonMesh<FlatFaces> {
+        PolygonMesh {
+            positions: mesh.positions.clone(),
+            uv_coords: mesh.uv_coords.clone(),
+            normals: mesh.normals.clone(),
+            colors: mesh.colors.clone(),
+            faces: FlatFaces::from(&mesh.faces),
+            vertex_attributes: mesh.vertex_attributes.clone(),
+            face_attributes: mesh.face_attributes.clone(),
+        }
+    }
+}
+
+impl From<&PolygonMesh<FlatFaces>> for PolygonMesh {
+    /// Sorts the faces into triangles, quadrangles and the others,
+    /// and reorders the attributes of the faces in the same way.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.5, 2.0, 0.0),
+    /// ];
+    /// let faces = FlatFaces::from_iter(&[&[0, 1, 2, 3][..], &[3, 2, 4]]);
+    /// let mut flat = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// flat.set_face_attribute("label", vec![4_usize, 3]).unwrap();
+    ///
+    /// let mesh = PolygonMesh::from(&flat);
+    /// assert_eq!(mesh.faces()[0].len(), 3);
+    /// assert_eq!(mesh.face_attributes().get::<usize>("label").unwrap(), &vec![3, 4]);
+    /// ```
+    fn from(mesh: &PolygonMesh<FlatFaces>) -> PolygonMesh {
+        let mut map: Vec<usize> = (0..mesh.faces.len()).collect();
+        map.sort_by_key(|i| usize::min(mesh.faces[*i].len(), 5));
+        let mut face_attributes = mesh.face_attributes.clone();
+        face_attributes.reindex(&map);
+        PolygonMesh {
+            positions: mesh.positions.clone(),
+            uv_coords: mesh.uv_coords.clone(),
+            normals: mesh.normals.clone(),
+            colors: mesh.colors.clone(),
+            faces: Faces::from(&mesh.faces),
+            vertex_attributes: mesh.vertex_attributes.clone(),
+            face_attributes,
+        }
+    }
+}
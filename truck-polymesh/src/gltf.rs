@@ -0,0 +1,697 @@
+use crate::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufWriter, Read, Write};
+
+/// the magic number of glb, "glTF" in little endian
+const GLB_MAGIC: u32 = 0x4654_6C67;
+/// the type of the json chunk of glb, "JSON" in little endian
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+/// the type of the binary chunk of glb, "BIN\0" in little endian
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+// the constants of glTF derived from OpenGL
+const BYTE: u32 = 5120;
+const UNSIGNED_BYTE: u32 = 5121;
+const SHORT: u32 = 5122;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const FLOAT: u32 = 5126;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const LINEAR: u32 = 9729;
+const LINEAR_MIPMAP_LINEAR: u32 = 9987;
+const REPEAT: u32 = 10497;
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+fn invalid_data(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message).into()
+}
+
+/// glTF format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GLTFFormat {
+    /// binary glTF, ".glb", with the binary buffer in the same file
+    Binary,
+    /// json glTF, ".gltf", with the binary buffer embedded as a base64 data uri
+    Embedded,
+}
+
+impl Default for GLTFFormat {
+    /// the binary glTF
+    #[inline(always)]
+    fn default() -> GLTFFormat { GLTFFormat::Binary }
+}
+
+/// the mime types of the images of textures
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ImageMimeType {
+    /// "image/png"
+    PNG,
+    /// "image/jpeg"
+    JPEG,
+}
+
+impl ImageMimeType {
+    fn as_str(self) -> &'static str {
+        match self {
+            ImageMimeType::PNG => "image/png",
+            ImageMimeType::JPEG => "image/jpeg",
+        }
+    }
+}
+
+/// Encoded image file embedded in glTF as a texture
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GLTFImage {
+    /// the mime type of `data`
+    pub mime_type: ImageMimeType,
+    /// the contents of png or jpeg file
+    pub data: Vec<u8>,
+}
+
+/// PBR material of metallic-roughness model in glTF
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GLTFMaterial {
+    /// the linear RGBA base color. Default is `[1.0, 1.0, 1.0, 1.0]`.
+    pub base_color_factor: [f64; 4],
+    /// the image of base color in sRGB, multiplied by `base_color_factor`
+    /// and mapped by the uv coordinates of the mesh. Default is `None`.
+    pub base_color_texture: Option<GLTFImage>,
+    /// the metalness in `[0, 1]`. Default is `1.0`.
+    pub metallic_factor: f64,
+    /// the roughness in `[0, 1]`. Default is `1.0`.
+    pub roughness_factor: f64,
+    /// the linear RGB emissive color. Default is `[0.0, 0.0, 0.0]`.
+    pub emissive_factor: [f64; 3],
+    /// If `true`, the back faces are not culled. Default is `false`.
+    pub double_sided: bool,
+}
+
+impl Default for GLTFMaterial {
+    /// the defaults of glTF
+    #[inline(always)]
+    fn default() -> GLTFMaterial {
+        GLTFMaterial {
+            base_color_factor: [1.0, 1.0, 1.0, 1.0],
+            base_color_texture: None,
+            metallic_factor: 1.0,
+            roughness_factor: 1.0,
+            emissive_factor: [0.0, 0.0, 0.0],
+            double_sided: false,
+        }
+    }
+}
+
+/// the binary buffer and its views
+#[derive(Clone, Debug, Default)]
+struct BufferBuilder {
+    bytes: Vec<u8>,
+    views: Vec<Value>,
+}
+
+impl BufferBuilder {
+    /// Appends `data` as a new buffer view aligned to four bytes, and returns its index.
+    fn push_view(&mut self, data: &[u8], target: Option<u32>) -> usize {
+        let mut view = json!({
+            "buffer": 0,
+            "byteOffset": self.bytes.len(),
+            "byteLength": data.len(),
+        });
+        if let Some(target) = target {
+            view["target"] = json!(target);
+        }
+        self.bytes.extend_from_slice(data);
+        self.bytes.resize(self.bytes.len().div_ceil(4) * 4, 0);
+        self.views.push(view);
+        self.views.len() - 1
+    }
+}
+
+fn f32_bytes<'a, I: IntoIterator<Item = &'a [f32]>>(iter: I) -> Vec<u8> {
+    iter.into_iter()
+        .flatten()
+        .flat_map(|x| x.to_le_bytes().to_vec())
+        .collect()
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut res = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    bytes.chunks(3).for_each(|chunk| {
+        let mut block = [0u8; 3];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let n = (block[0] as u32) << 16 | (block[1] as u32) << 8 | block[2] as u32;
+        (0..4).for_each(|i| match i <= chunk.len() {
+            true => res.push(TABLE[(n >> (18 - 6 * i) & 63) as usize] as char),
+            false => res.push('='),
+        });
+    });
+    res
+}
+
+fn base64_decode(string: &str) -> Result<Vec<u8>> {
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Ok(c - b'A'),
+        b'a'..=b'z' => Ok(c - b'a' + 26),
+        b'0'..=b'9' => Ok(c - b'0' + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(invalid_data("invalid base64 character")),
+    };
+    let bytes = string.trim_end_matches('=').as_bytes();
+    let mut res = Vec::with_capacity(bytes.len() * 3 / 4);
+    for chunk in bytes.chunks(4) {
+        if chunk.len() == 1 {
+            return Err(invalid_data("invalid length of base64"));
+        }
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            n |= (value(*c)? as u32) << (18 - 6 * i);
+        }
+        (0..chunk.len() - 1).for_each(|i| res.push((n >> (16 - 8 * i)) as u8));
+    }
+    Ok(res)
+}
+
+/// Writes a polygon mesh, with an optional material, to glTF 2.0 in `format`.
+///
+/// The faces are triangulated as fans, and the face vertices with the same indices of positions,
+/// uv coordinates and normals are written as one vertex. The uv coordinates and normals are
+/// written only if all the face vertices have them. The normals are normalized, and the
+/// v-coordinates are flipped into the convention of glTF, whose origin is the upper left corner.
+///
+/// The positions are written as is, while the unit of glTF is meter.
+/// Use [`write_with_context`] to convert the lengths.
+/// Writing a mesh without faces returns an error.
+///
+/// [`write_with_context`]: ./fn.write_with_context.html
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use gltf::{GLTFFormat, GLTFMaterial};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+/// let material = GLTFMaterial {
+///     base_color_factor: [0.8, 0.2, 0.2, 1.0],
+///     metallic_factor: 0.0,
+///     roughness_factor: 0.5,
+///     ..Default::default()
+/// };
+///
+/// let mut bytes = Vec::new();
+/// gltf::write(&mesh, &mut bytes, GLTFFormat::Binary, Some(&material)).unwrap();
+/// assert_eq!(&bytes[0..4], b"glTF");
+/// assert_eq!(bytes.len() % 4, 0);
+/// ```
+pub fn write<W: Write>(
+    mesh: &PolygonMesh,
+    writer: W,
+    format: GLTFFormat,
+    material: Option<&GLTFMaterial>,
+) -> Result<()> {
+    let has_uv = mesh.face_iter().flatten().all(|v| v.uv.is_some());
+    let has_normal = mesh.face_iter().flatten().all(|v| v.nor.is_some());
+    let mut indices = HashMap::new();
+    let mut vertices = Vec::new();
+    let triangles: Vec<u32> = mesh
+        .face_iter()
+        .flat_map(|face| {
+            let face: Vec<u32> = face
+                .iter()
+                .map(|v| {
+                    let key = (v.pos, v.uv.filter(|_| has_uv), v.nor.filter(|_| has_normal));
+                    *indices.entry(key).or_insert_with(|| {
+                        vertices.push(key);
+                        vertices.len() as u32 - 1
+                    })
+                })
+                .collect();
+            (2..face.len()).flat_map(move |i| vec![face[0], face[i - 1], face[i]])
+        })
+        .collect();
+    if triangles.is_empty() {
+        return Err(invalid_input("glTF cannot store meshes without faces"));
+    }
+
+    let mut buffer = BufferBuilder::default();
+    let mut accessors = Vec::new();
+    let mut attributes = serde_json::Map::new();
+    let positions: Vec<[f32; 3]> = vertices
+        .iter()
+        .map(|(pos, _, _)| mesh.positions()[*pos].cast().unwrap().into())
+        .collect();
+    let (min, max) = positions.iter().fold(
+        ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+        |(mut min, mut max), p| {
+            (0..3).for_each(|i| {
+                min[i] = f32::min(min[i], p[i]);
+                max[i] = f32::max(max[i], p[i]);
+            });
+            (min, max)
+        },
+    );
+    let view = buffer.push_view(
+        &f32_bytes(positions.iter().map(|p| &p[..])),
+        Some(ARRAY_BUFFER),
+    );
+    attributes.insert("POSITION".into(), json!(accessors.len()));
+    accessors.push(json!({
+        "bufferView": view,
+        "componentType": FLOAT,
+        "count": positions.len(),
+        "type": "VEC3",
+        "min": min,
+        "max": max,
+    }));
+    if has_normal {
+        let normals: Vec<[f32; 3]> = vertices
+            .iter()
+            .map(|(_, _, nor)| {
+                let normal = mesh.normals()[nor.unwrap()];
+                let normal = match normal.magnitude2() > 0.0 {
+                    true => normal.normalize(),
+                    false => normal,
+                };
+                normal.cast().unwrap().into()
+            })
+            .collect();
+        let view = buffer.push_view(
+            &f32_bytes(normals.iter().map(|n| &n[..])),
+            Some(ARRAY_BUFFER),
+        );
+        attributes.insert("NORMAL".into(), json!(accessors.len()));
+        accessors.push(json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": normals.len(),
+            "type": "VEC3",
+        }));
+    }
+    if has_uv {
+        let uv_coords: Vec<[f32; 2]> = vertices
+            .iter()
+            .map(|(_, uv, _)| {
+                let uv = mesh.uv_coords()[uv.unwrap()];
+                [uv[0] as f32, 1.0 - uv[1] as f32]
+            })
+            .collect();
+        let view = buffer.push_view(
+            &f32_bytes(uv_coords.iter().map(|uv| &uv[..])),
+            Some(ARRAY_BUFFER),
+        );
+        attributes.insert("TEXCOORD_0".into(), json!(accessors.len()));
+        accessors.push(json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": uv_coords.len(),
+            "type": "VEC2",
+        }));
+    }
+    let index_bytes: Vec<u8> = triangles
+        .iter()
+        .flat_map(|i| i.to_le_bytes().to_vec())
+        .collect();
+    let view = buffer.push_view(&index_bytes, Some(ELEMENT_ARRAY_BUFFER));
+    let mut primitive = json!({
+        "attributes": attributes,
+        "indices": accessors.len(),
+    });
+    accessors.push(json!({
+        "bufferView": view,
+        "componentType": UNSIGNED_INT,
+        "count": triangles.len(),
+        "type": "SCALAR",
+    }));
+
+    let mut root = json!({
+        "asset": { "version": "2.0", "generator": "truck-polymesh" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0 }],
+    });
+    if let Some(material) = material {
+        let mut pbr = json!({
+            "baseColorFactor": material.base_color_factor,
+            "metallicFactor": material.metallic_factor,
+            "roughnessFactor": material.roughness_factor,
+        });
+        if let Some(image) = &material.base_color_texture {
+            let view = buffer.push_view(&image.data, None);
+            root["images"] = json!([{ "bufferView": view, "mimeType": image.mime_type.as_str() }]);
+            root["samplers"] = json!([{
+                "magFilter": LINEAR,
+                "minFilter": LINEAR_MIPMAP_LINEAR,
+                "wrapS": REPEAT,
+                "wrapT": REPEAT,
+            }]);
+            root["textures"] = json!([{ "sampler": 0, "source": 0 }]);
+            pbr["baseColorTexture"] = json!({ "index": 0 });
+        }
+        root["materials"] = json!([{
+            "pbrMetallicRoughness": pbr,
+            "emissiveFactor": material.emissive_factor,
+            "doubleSided": material.double_sided,
+        }]);
+        primitive["material"] = json!(0);
+    }
+    root["meshes"] = json!([{ "primitives": [primitive] }]);
+    root["accessors"] = Value::Array(accessors);
+    root["bufferViews"] = Value::Array(buffer.views);
+    root["buffers"] = match format {
+        GLTFFormat::Binary => json!([{ "byteLength": buffer.bytes.len() }]),
+        GLTFFormat::Embedded => json!([{
+            "byteLength": buffer.bytes.len(),
+            "uri": format!("data:application/octet-stream;base64,{}", base64_encode(&buffer.bytes)),
+        }]),
+    };
+
+    let mut writer = BufWriter::new(writer);
+    match format {
+        GLTFFormat::Binary => {
+            let mut json = serde_json::to_vec(&root).map_err(std::io::Error::from)?;
+            json.resize(json.len().div_ceil(4) * 4, b' ');
+            let length = 12 + 8 + json.len() + 8 + buffer.bytes.len();
+            [GLB_MAGIC, 2, length as u32, json.len() as u32, CHUNK_JSON]
+                .iter()
+                .try_for_each(|x| writer.write_all(&x.to_le_bytes()))?;
+            writer.write_all(&json)?;
+            writer.write_all(&(buffer.bytes.len() as u32).to_le_bytes())?;
+            writer.write_all(&CHUNK_BIN.to_le_bytes())?;
+            writer.write_all(&buffer.bytes)?;
+        }
+        GLTFFormat::Embedded => {
+            serde_json::to_writer_pretty(&mut writer, &root).map_err(std::io::Error::from)?
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes mesh data whose lengths are converted from the unit of `context` into meter,
+/// the unit of glTF.
+pub fn write_with_context<W: Write>(
+    mesh: &PolygonMesh,
+    writer: W,
+    format: GLTFFormat,
+    material: Option<&GLTFMaterial>,
+    context: &ModelContext,
+) -> Result<()> {
+    match context.length_unit == LengthUnit::Meter {
+        true => write(mesh, writer, format, material),
+        false => write(
+            &mesh.transformed(Matrix4::from_scale(context.scale_to(LengthUnit::Meter))),
+            writer,
+            format,
+            material,
+        ),
+    }
+}
+
+/// the json and the binary buffer of glb
+fn split_glb(bytes: &[u8]) -> Result<(Value, Option<Vec<u8>>)> {
+    let u32_at = |offset: usize| -> Result<usize> {
+        match bytes.get(offset..offset + 4) {
+            Some(x) => Ok(u32::from_le_bytes([x[0], x[1], x[2], x[3]]) as usize),
+            None => Err(invalid_data("unexpected end of glb")),
+        }
+    };
+    if u32_at(4)? != 2 {
+        return Err(invalid_data("unsupported version of glb"));
+    }
+    let length = usize::min(u32_at(8)?, bytes.len());
+    let (mut json, mut bin) = (None, None);
+    let mut offset = 12;
+    while offset + 8 <= length {
+        let chunk_length = u32_at(offset)?;
+        let chunk = bytes
+            .get(offset + 8..offset + 8 + chunk_length)
+            .ok_or_else(|| invalid_data("unexpected end of glb"))?;
+        match u32_at(offset + 4)? as u32 {
+            CHUNK_JSON if json.is_none() => {
+                json = Some(serde_json::from_slice(chunk).map_err(std::io::Error::from)?)
+            }
+            CHUNK_BIN if bin.is_none() => bin = Some(chunk.to_vec()),
+            _ => {}
+        }
+        offset += 8 + chunk_length;
+    }
+    match json {
+        Some(json) => Ok((json, bin)),
+        None => Err(invalid_data("glb has no json chunk")),
+    }
+}
+
+fn get_index(value: &Value, key: &str) -> Option<usize> {
+    value.get(key).and_then(Value::as_u64).map(|x| x as usize)
+}
+
+/// Reads the elements of the accessor `index` as the vectors of `f64`.
+fn read_accessor(json: &Value, buffers: &[Vec<u8>], index: usize) -> Result<Vec<Vec<f64>>> {
+    let accessor = json["accessors"]
+        .get(index)
+        .ok_or_else(|| invalid_data("accessor index out of range"))?;
+    if accessor.get("sparse").is_some() {
+        return Err(invalid_data("sparse accessors are not supported"));
+    }
+    let count =
+        get_index(accessor, "count").ok_or_else(|| invalid_data("accessor without count"))?;
+    let dim = match accessor["type"].as_str() {
+        Some("SCALAR") => 1,
+        Some("VEC2") => 2,
+        Some("VEC3") => 3,
+        Some("VEC4") => 4,
+        _ => return Err(invalid_data("unsupported type of accessor")),
+    };
+    let component_type = accessor["componentType"].as_u64().unwrap_or(0) as u32;
+    let component_size = match component_type {
+        BYTE | UNSIGNED_BYTE => 1,
+        SHORT | UNSIGNED_SHORT => 2,
+        UNSIGNED_INT | FLOAT => 4,
+        _ => return Err(invalid_data("unknown component type")),
+    };
+    let normalized = accessor["normalized"].as_bool().unwrap_or(false);
+    let view_index = match get_index(accessor, "bufferView") {
+        Some(view_index) => view_index,
+        // the accessor without buffer view is filled with zeros
+        None => return Ok(vec![vec![0.0; dim]; count]),
+    };
+    let view = json["bufferViews"]
+        .get(view_index)
+        .ok_or_else(|| invalid_data("buffer view index out of range"))?;
+    let buffer = get_index(view, "buffer")
+        .and_then(|i| buffers.get(i))
+        .ok_or_else(|| invalid_data("buffer index out of range"))?;
+    let view_offset = get_index(view, "byteOffset").unwrap_or(0);
+    let view_length = get_index(view, "byteLength").unwrap_or(0);
+    let bytes = buffer
+        .get(view_offset..view_offset + view_length)
+        .ok_or_else(|| invalid_data("buffer view out of range"))?;
+    let offset = get_index(accessor, "byteOffset").unwrap_or(0);
+    let element_size = component_size * dim;
+    let stride = get_index(view, "byteStride").unwrap_or(element_size);
+    if count > 0 && offset + stride * (count - 1) + element_size > bytes.len() {
+        return Err(invalid_data("accessor out of range of buffer view"));
+    }
+    let component = |x: &[u8]| match component_type {
+        BYTE if normalized => f64::max(x[0] as i8 as f64 / 127.0, -1.0),
+        BYTE => x[0] as i8 as f64,
+        UNSIGNED_BYTE if normalized => x[0] as f64 / 255.0,
+        UNSIGNED_BYTE => x[0] as f64,
+        SHORT if normalized => f64::max(i16::from_le_bytes([x[0], x[1]]) as f64 / 32767.0, -1.0),
+        SHORT => i16::from_le_bytes([x[0], x[1]]) as f64,
+        UNSIGNED_SHORT if normalized => u16::from_le_bytes([x[0], x[1]]) as f64 / 65535.0,
+        UNSIGNED_SHORT => u16::from_le_bytes([x[0], x[1]]) as f64,
+        UNSIGNED_INT => u32::from_le_bytes([x[0], x[1], x[2], x[3]]) as f64,
+        _ => f32::from_le_bytes([x[0], x[1], x[2], x[3]]) as f64,
+    };
+    Ok((0..count)
+        .map(|i| {
+            let element = &bytes[offset + stride * i..offset + stride * i + element_size];
+            element.chunks(component_size).map(component).collect()
+        })
+        .collect())
+}
+
+/// Reads a primitive as a polygon mesh.
+/// Returns `None` if the primitive consists of points or lines.
+fn read_primitive(
+    json: &Value,
+    buffers: &[Vec<u8>],
+    primitive: &Value,
+) -> Result<Option<PolygonMesh>> {
+    let attributes = &primitive["attributes"];
+    let position_index = get_index(attributes, "POSITION")
+        .ok_or_else(|| invalid_data("primitive without positions"))?;
+    let positions: Vec<Point3> = read_accessor(json, buffers, position_index)?
+        .into_iter()
+        .map(|p| Point3::new(p[0], p[1], p[2]))
+        .collect();
+    let len = positions.len();
+    let normals: Vec<Vector3> = match get_index(attributes, "NORMAL") {
+        Some(index) => read_accessor(json, buffers, index)?
+            .into_iter()
+            .map(|n| Vector3::new(n[0], n[1], n[2]))
+            .collect(),
+        None => Vec::new(),
+    };
+    let uv_coords: Vec<Vector2> = match get_index(attributes, "TEXCOORD_0") {
+        Some(index) => read_accessor(json, buffers, index)?
+            .into_iter()
+            .map(|uv| Vector2::new(uv[0], 1.0 - uv[1]))
+            .collect(),
+        None => Vec::new(),
+    };
+    if (!normals.is_empty() && normals.len() != len)
+        || (!uv_coords.is_empty() && uv_coords.len() != len)
+    {
+        return Err(invalid_data("the attributes of a primitive have different counts"));
+    }
+    let indices: Vec<usize> = match get_index(primitive, "indices") {
+        Some(index) => read_accessor(json, buffers, index)?
+            .into_iter()
+            .map(|i| i[0] as usize)
+            .collect(),
+        None => (0..len).collect(),
+    };
+    if let Some(i) = indices.iter().find(|i| **i >= len) {
+        return Err(errors::Error::OutOfRange("positions", len, *i));
+    }
+    let triangles: Vec<[usize; 3]> = match get_index(primitive, "mode").unwrap_or(4) {
+        4 => indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect(),
+        5 => (2..indices.len())
+            .map(|i| match i % 2 {
+                0 => [indices[i - 2], indices[i - 1], indices[i]],
+                _ => [indices[i - 1], indices[i - 2], indices[i]],
+            })
+            .collect(),
+        6 => (2..indices.len())
+            .map(|i| [indices[0], indices[i - 1], indices[i]])
+            .collect(),
+        _ => return Ok(None),
+    };
+    let vertex = |i: usize| Vertex {
+        pos: i,
+        uv: Some(i).filter(|_| !uv_coords.is_empty()),
+        nor: Some(i).filter(|_| !normals.is_empty()),
+        col: None,
+    };
+    let faces = Faces::from_iter(
+        triangles
+            .into_iter()
+            .map(|[i, j, k]| [vertex(i), vertex(j), vertex(k)]),
+    );
+    Ok(Some(PolygonMesh::new(positions, uv_coords, normals, faces)))
+}
+
+/// Reads the meshes of glTF 2.0, in either of `.glb` and `.gltf` with embedded buffers.
+///
+/// Each primitive of triangles, triangle strips or triangle fans is read as a polygon mesh
+/// with the name of its mesh, or `"mesh{i}"` if the `i`th mesh has no name. The primitives of
+/// points and lines are skipped. The attributes `POSITION`, `NORMAL` and `TEXCOORD_0` are read
+/// from indexed or non-indexed, and tightly packed or interleaved buffer views, and the
+/// v-coordinates are flipped into the convention whose origin is the lower left corner.
+///
+/// The meshes are read in their local coordinates: the transforms of nodes are not applied.
+/// Materials, sparse accessors and buffers in external files are not supported.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use gltf::GLTFFormat;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut bytes = Vec::new();
+/// gltf::write(&mesh, &mut bytes, GLTFFormat::Embedded, None).unwrap();
+/// let meshes = gltf::read(bytes.as_slice()).unwrap();
+/// assert_eq!(meshes.len(), 1);
+/// assert_eq!(meshes[0].1.positions(), mesh.positions());
+/// // the quadrangle is triangulated
+/// assert_eq!(meshes[0].1.tri_faces().len(), 2);
+/// ```
+pub fn read<R: Read>(mut reader: R) -> Result<Vec<(String, PolygonMesh)>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let (json, bin) = match bytes.starts_with(b"glTF") {
+        true => split_glb(&bytes)?,
+        false => {
+            let json = serde_json::from_slice(&bytes).map_err(std::io::Error::from)?;
+            (json, None)
+        }
+    };
+    let buffers = json["buffers"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, buffer)| match buffer["uri"].as_str() {
+            Some(uri) => match uri.starts_with("data:") {
+                true => match uri.find(";base64,") {
+                    Some(idx) => base64_decode(&uri[idx + 8..]),
+                    None => Err(invalid_data("data uri is not encoded by base64")),
+                },
+                false => Err(invalid_data("buffers in external files are not supported")),
+            },
+            None => match (i, &bin) {
+                (0, Some(bin)) => Ok(bin.clone()),
+                _ => Err(invalid_data("buffer without data")),
+            },
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut res = Vec::new();
+    let meshes = json["meshes"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for (i, mesh) in meshes.iter().enumerate() {
+        let name = match mesh["name"].as_str() {
+            Some(name) => name.to_string(),
+            None => format!("mesh{}", i),
+        };
+        let primitives = mesh["primitives"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for primitive in primitives {
+            if let Some(polygon) = read_primitive(&json, &buffers, primitive)? {
+                res.push((name.clone(), polygon));
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Reads meshes of glTF whose lengths are in meter, and converts the lengths into the unit
+/// of `context`.
+pub fn read_with_context<R: Read>(
+    reader: R,
+    context: &ModelContext,
+) -> Result<Vec<(String, PolygonMesh)>> {
+    let mut meshes = read(reader)?;
+    if context.length_unit != LengthUnit::Meter {
+        let matrix = Matrix4::from_scale(context.scale_from(LengthUnit::Meter));
+        meshes
+            .iter_mut()
+            .for_each(|(_, mesh)| mesh.transform_by(matrix));
+    }
+    Ok(meshes)
+}
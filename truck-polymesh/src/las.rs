@@ -0,0 +1,234 @@
+use crate::*;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+fn syntax_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "syntax error")
+}
+
+/// the size of the header of LAS 1.2
+const HEADER_SIZE: usize = 227;
+
+#[inline(always)]
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[inline(always)]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut buf = [0; 4];
+    buf.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(buf)
+}
+
+#[inline(always)]
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(buf)
+}
+
+#[inline(always)]
+fn f64_at(bytes: &[u8], offset: usize) -> f64 { f64::from_bits(u64_at(bytes, offset)) }
+
+/// Returns the offset of the RGB values in the point records of `format`.
+fn color_offset(format: u8) -> Option<usize> {
+    match format {
+        2 => Some(20),
+        3 | 5 => Some(28),
+        7 | 8 | 10 => Some(30),
+        _ => None,
+    }
+}
+
+/// reads the next point record into the buffer
+type RecordReader<'a> = Box<dyn FnMut(&mut [u8]) -> std::io::Result<()> + 'a>;
+
+/// Writes point cloud to output stream in LAS 1.2
+///
+/// The points are written in the point data record format 0, or 2 if the point cloud
+/// has colors. The coordinates are quantized by the scale factors chosen from the bounding box,
+/// and the normals and the alpha of the colors are not written.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let positions = vec![Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 20.0, 30.0)];
+/// let cloud = PointCloud::from_positions(positions);
+///
+/// let mut bytes = Vec::new();
+/// las::write(&cloud, &mut bytes).unwrap();
+/// let cloud0 = las::read(bytes.as_slice()).unwrap();
+/// assert_eq!(cloud0.len(), 2);
+/// assert!(cloud0.positions()[1].near(&Point3::new(10.0, 20.0, 30.0)));
+/// ```
+pub fn write<W: Write>(cloud: &PointCloud, writer: W) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let bdb = cloud.bounding_box();
+    let (min, max) = match bdb.is_empty() {
+        true => (Point3::origin(), Point3::origin()),
+        false => (*bdb.min(), *bdb.max()),
+    };
+    // the scales such that the coordinates relative to the minimum fit in `i32`
+    let scales = (0..3).map(|i| {
+        let scale = 10.0_f64.powf(f64::log10((max[i] - min[i]) / 1.0e9).ceil());
+        f64::max(scale, 1.0e-9)
+    });
+    let scales: Vec<f64> = scales.collect();
+    let has_colors = !cloud.colors().is_empty();
+    let (format, record_size) = match has_colors {
+        true => (2_u8, 26_u16),
+        false => (0, 20),
+    };
+
+    let mut header = [0_u8; HEADER_SIZE];
+    header[0..4].copy_from_slice(b"LASF");
+    header[24] = 1;
+    header[25] = 2;
+    let software = b"truck-polymesh";
+    header[58..58 + software.len()].copy_from_slice(software);
+    header[94..96].copy_from_slice(&(HEADER_SIZE as u16).to_le_bytes());
+    header[96..100].copy_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+    header[104] = format;
+    header[105..107].copy_from_slice(&record_size.to_le_bytes());
+    if cloud.len() > u32::MAX as usize {
+        let message = "LAS 1.2 cannot contain more than 2^32 - 1 points.";
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into());
+    }
+    let n = cloud.len() as u32;
+    header[107..111].copy_from_slice(&n.to_le_bytes());
+    header[111..115].copy_from_slice(&n.to_le_bytes());
+    for i in 0..3 {
+        header[131 + 8 * i..139 + 8 * i].copy_from_slice(&scales[i].to_le_bytes());
+        header[155 + 8 * i..163 + 8 * i].copy_from_slice(&min[i].to_le_bytes());
+        header[179 + 16 * i..187 + 16 * i].copy_from_slice(&max[i].to_le_bytes());
+        header[187 + 16 * i..195 + 16 * i].copy_from_slice(&min[i].to_le_bytes());
+    }
+    writer.write_all(&header)?;
+
+    for (i, p) in cloud.positions().iter().enumerate() {
+        for j in 0..3 {
+            let x = ((p[j] - min[j]) / scales[j]).round() as i32;
+            writer.write_all(&x.to_le_bytes())?;
+        }
+        // intensity, return numbers, classification, scan angle, user data and source id
+        writer.write_all(&[0, 0, 1 << 3 | 1, 0, 0, 0, 0, 0])?;
+        if has_colors {
+            let c = cloud.colors()[i];
+            for j in 0..3 {
+                let x = (f64::clamp(c[j], 0.0, 1.0) * 65535.0).round() as u16;
+                writer.write_all(&x.to_le_bytes())?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads point cloud from LAS file.
+///
+/// LAS 1.0 to 1.4 with all the point data record formats are supported, and the colors
+/// are read if the format has RGB values. The compressed files, LAZ, are decompressed
+/// if the feature `laz` is enabled, and are rejected by `ErrorKind::Unsupported` otherwise.
+pub fn read<R: Read>(reader: R) -> Result<PointCloud> {
+    let mut reader = BufReader::new(reader);
+    let mut header = vec![0_u8; HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+    if &header[0..4] != b"LASF" {
+        return Err(syntax_error().into());
+    }
+    let minor_version = header[25];
+    let header_size = u16_at(&header, 94) as usize;
+    let offset = u32_at(&header, 96) as usize;
+    if header_size < HEADER_SIZE || offset < header_size {
+        return Err(syntax_error().into());
+    }
+    header.resize(header_size, 0);
+    reader.read_exact(&mut header[HEADER_SIZE..])?;
+    // the compressed formats, LAZ, have the bit 7 and possibly the bit 6
+    let compressed = header[104] & 0xC0 != 0;
+    let format = header[104] & 0x3F;
+    let record_size = u16_at(&header, 105) as usize;
+    let mut points = u32_at(&header, 107) as u64;
+    if minor_version >= 4 && header_size >= 255 && points == 0 {
+        points = u64_at(&header, 247);
+    }
+    let scales = [131, 139, 147].map(|offset| f64_at(&header, offset));
+    let offsets = [155, 163, 171].map(|offset| f64_at(&header, offset));
+    let color_offset = color_offset(format);
+    if record_size < color_offset.map_or(12, |offset| offset + 6) {
+        return Err(syntax_error().into());
+    }
+
+    let mut vlrs = vec![0_u8; offset - header_size];
+    reader.read_exact(&mut vlrs)?;
+    let mut next_record: RecordReader = match compressed {
+        true => laz_records(&header, &vlrs, reader)?,
+        false => Box::new(move |record| reader.read_exact(record)),
+    };
+    let mut cloud = PointCloud::default();
+    let mut record = vec![0_u8; record_size];
+    for _ in 0..points {
+        next_record(&mut record)?;
+        let position = Point3::from([0, 1, 2].map(|i| {
+            let x = u32_at(&record, 4 * i) as i32;
+            x as f64 * scales[i] + offsets[i]
+        }));
+        let color = color_offset.map(|offset| {
+            let c = [0, 1, 2].map(|i| u16_at(&record, offset + 2 * i) as f64 / 65535.0);
+            Vector4::new(c[0], c[1], c[2], 1.0)
+        });
+        cloud.push(position, None, color);
+    }
+    Ok(cloud)
+}
+
+/// the size of the header of a variable length record
+#[cfg(feature = "laz")]
+const VLR_HEADER_SIZE: usize = 54;
+
+/// Returns the decompressor of the point records of LAZ following `header` and `vlrs`.
+#[cfg(feature = "laz")]
+fn laz_records<'a, R: Read + 'a>(
+    header: &[u8],
+    vlrs: &[u8],
+    mut reader: R,
+) -> Result<RecordReader<'a>> {
+    use laz_rs::{LasZipDecompressor, LazVlr};
+    let laz_error = |e| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+    let mut vlr = None;
+    let mut rest = vlrs;
+    for _ in 0..u32_at(header, 100) {
+        if rest.len() < VLR_HEADER_SIZE {
+            return Err(syntax_error().into());
+        }
+        let user_id = &rest[2..18];
+        let record_id = u16_at(rest, 18);
+        let end = VLR_HEADER_SIZE + u16_at(rest, 20) as usize;
+        if rest.len() < end {
+            return Err(syntax_error().into());
+        }
+        let user_id = user_id.split(|c| *c == 0).next().unwrap_or_default();
+        if user_id == LazVlr::USER_ID.as_bytes() && record_id == LazVlr::RECORD_ID {
+            vlr = Some(LazVlr::from_buffer(&rest[VLR_HEADER_SIZE..end]).map_err(laz_error)?);
+        }
+        rest = &rest[end..];
+    }
+    let vlr = vlr.ok_or_else(syntax_error)?;
+    // the offset to the chunk table is measured from the beginning of the file
+    let mut bytes = [header, vlrs].concat();
+    reader.read_to_end(&mut bytes)?;
+    let mut source = std::io::Cursor::new(bytes);
+    source.set_position((header.len() + vlrs.len()) as u64);
+    let mut decompressor = LasZipDecompressor::new(source, vlr).map_err(laz_error)?;
+    Ok(Box::new(move |record| decompressor.decompress_one(record)))
+}
+
+#[cfg(not(feature = "laz"))]
+fn laz_records<'a, R: Read + 'a>(
+    _: &[u8],
+    _: &[u8],
+    _: R,
+) -> Result<RecordReader<'a>> {
+    let message = "The compressed LAS, LAZ, needs the feature `laz`.";
+    let error = std::io::Error::new(std::io::ErrorKind::Unsupported, message);
+    Err(error.into())
+}
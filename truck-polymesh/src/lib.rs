@@ -11,11 +11,12 @@
     unused_qualifications
 )]
 
+use attributes::MeshAttributes;
 use serde::{Deserialize, Serialize};
 
 /// re-export `truck_base`.
 pub mod base {
-    pub use truck_base::{bounding_box::*, cgmath64::*, tolerance::*};
+    pub use truck_base::{bounding_box::*, cgmath64::*, context::*, tolerance::*};
     pub use truck_geotrait::*;
 }
 pub use base::*;
@@ -29,6 +30,9 @@ pub struct Vertex {
     pub uv: Option<usize>,
     /// index of vertex's normal
     pub nor: Option<usize>,
+    /// index of vertex's color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub col: Option<usize>,
 }
 
 /// Faces of polygon mesh
@@ -42,19 +46,135 @@ pub struct Faces {
     other_faces: Vec<Vec<Vertex>>,
 }
 
+/// Faces of polygon mesh stored in a flat buffer
+///
+/// The vertices of all faces are stored in one flat index buffer, and each face is specified by
+/// the end offset of its vertices in the buffer. Unlike [`Faces`](./struct.Faces.html),
+/// faces are held in the order in which they are registered, and a mesh of any polygons is
+/// iterated over one contiguous buffer, which is faster for uploading to GPU or for
+/// parallel iteration over millions of faces.
+///
+/// `FlatFaces` is an alternative backing of [`PolygonMesh`] through [`FaceStorage`].
+///
+/// [`PolygonMesh`]: ./struct.PolygonMesh.html
+/// [`FaceStorage`]: ./trait.FaceStorage.html
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlatFaces {
+    indices: Vec<Vertex>,
+    offsets: Vec<usize>,
+}
+
+/// The storage of the faces of [`PolygonMesh`], implemented by [`Faces`] and [`FlatFaces`].
+///
+/// [`PolygonMesh`]: ./struct.PolygonMesh.html
+/// [`Faces`]: ./struct.Faces.html
+/// [`FlatFaces`]: ./struct.FlatFaces.html
+pub trait FaceStorage: Clone + Default + std::fmt::Debug + PartialEq {
+    /// Returns the iterator of the faces.
+    fn face_iter(&self) -> impl Iterator<Item = &[Vertex]>;
+    /// Returns the mutable iterator of the faces, in the same order as `face_iter`.
+    fn face_iter_mut(&mut self) -> impl Iterator<Item = &mut [Vertex]>;
+    /// Returns the number of faces.
+    fn len(&self) -> usize;
+    /// Returns whether there are no faces or not.
+    #[inline(always)]
+    fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
 /// Polygon mesh
 ///
 /// The polygon data is held in a method compliant with wavefront obj.
 /// Position, uv (texture) coordinates, and normal vectors are held in separate arrays,
 /// and each face vertex accesses those values by an indices triple.
+/// The optional RGBA colors are held in another array, accessed by the fourth index `col`.
+/// The named attributes of the positions and the faces are held in [`MeshAttributes`].
+///
+/// The faces are held in [`Faces`] by default, or in [`FlatFaces`] by `PolygonMesh<FlatFaces>`.
+///
+/// [`MeshAttributes`]: ./attributes/struct.MeshAttributes.html
+/// [`Faces`]: ./struct.Faces.html
+/// [`FlatFaces`]: ./struct.FlatFaces.html
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
-pub struct PolygonMesh {
+pub struct PolygonMesh<F = Faces> {
     positions: Vec<Point3>,
     uv_coords: Vec<Vector2>,
     normals: Vec<Vector3>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    colors: Vec<Vector4>,
+    faces: F,
+    #[serde(default, skip_serializing_if = "MeshAttributes::is_empty")]
+    vertex_attributes: MeshAttributes,
+    #[serde(default, skip_serializing_if = "MeshAttributes::is_empty")]
+    face_attributes: MeshAttributes,
+}
+
+/// Borrowed view of polygon mesh
+///
+/// The arrays of positions, uv coordinates, normals and colors, and the faces are borrowed from
+/// [`PolygonMesh`] or from the buffers owned by others, e.g. memory-mapped files, so that
+/// the algorithms and the renderer can consume very large meshes without copying them into
+/// owned vectors. The named attributes of [`PolygonMesh`] are not included.
+///
+/// [`PolygonMesh`]: ./struct.PolygonMesh.html
+#[derive(Debug)]
+pub struct PolygonMeshSlice<'a, F = Faces> {
+    positions: &'a [Point3],
+    uv_coords: &'a [Vector2],
+    normals: &'a [Vector3],
+    colors: &'a [Vector4],
+    faces: &'a F,
+}
+
+/// Polygon mesh stored in single precision
+///
+/// The positions, uv coordinates, normals and colors are held in `f32` arrays, which halves
+/// the memory of [`PolygonMesh`] and can be uploaded to GPU without conversion. The faces are
+/// the same as [`PolygonMesh`]. The named attributes of [`PolygonMesh`] are not included.
+///
+/// [`PolygonMesh`]: ./struct.PolygonMesh.html
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolygonMeshF32 {
+    positions: Vec<[f32; 3]>,
+    uv_coords: Vec<[f32; 2]>,
+    normals: Vec<[f32; 3]>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    colors: Vec<[f32; 4]>,
     faces: Faces,
 }
 
+/// Frozen polygon mesh with a single `u32` index per corner
+///
+/// Each distinct combination of the position, the uv coordinate, the normal and the color of
+/// the corners of [`PolygonMesh`] is stored once in the struct-of-arrays, and the faces are
+/// held in the flat buffer of `u32` indices into the arrays with the end offsets of faces.
+/// A corner costs a single `u32` instead of the four `usize` indices of [`Vertex`].
+///
+/// [`PolygonMesh`]: ./struct.PolygonMesh.html
+/// [`Vertex`]: ./struct.Vertex.html
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompactMesh {
+    positions: Vec<Point3>,
+    uv_coords: Vec<Vector2>,
+    normals: Vec<Vector3>,
+    colors: Vec<Vector4>,
+    indices: Vec<u32>,
+    offsets: Vec<u32>,
+}
+
+/// Point cloud
+///
+/// The positions of points with the optional normals and RGBA colors. The arrays of
+/// normals and colors are empty or have the same length as the array of positions,
+/// i.e. the `i`-th normal and color belong to the `i`-th position.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PointCloud {
+    positions: Vec<Point3>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    normals: Vec<Vector3>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    colors: Vec<Vector4>,
+}
+
 /// structured quadrangle mesh
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StructuredMesh {
@@ -67,18 +187,67 @@ pub struct StructuredMesh {
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct PolylineCurve<P>(pub Vec<P>);
 
+/// Configures of the auto-orientation for 3D printing, [`PolygonMesh::auto_orient`].
+///
+/// [`PolygonMesh::auto_orient`]: ./struct.PolygonMesh.html#method.auto_orient
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientationDescriptor {
+    /// the maximum angle of overhangs from the vertical printable without supports.
+    /// Default is 45 degrees.
+    pub overhang_angle: Rad<f64>,
+    /// the number of candidate directions uniformly distributed on the sphere, in addition to
+    /// the axes and the normals of the largest faces. Default is `64`.
+    pub samples: usize,
+}
+
 /// Error handler for [`Error`](./errors/enum.Error.html)
 pub type Result<T> = std::result::Result<T, errors::Error>;
 
+/// Export of AMF
+pub mod amf;
+/// Defines the named attributes of the vertices and the faces.
+pub mod attributes;
+mod compact_mesh;
 /// Defines errors
 pub mod errors;
+mod flat_faces;
+/// I/O of glTF 2.0
+pub mod gltf;
+/// I/O of LAS point clouds
+pub mod las;
 mod meshing_shape;
 /// I/O of wavefront obj
 pub mod obj;
+/// I/O of OFF
+pub mod off;
+mod orientation;
+/// I/O of PCD point clouds
+pub mod pcd;
+/// I/O of PLY
+pub mod ply;
+mod point_cloud;
 /// Defines [`PolygonMeshEditor`](./polygon_mesh/struct.PolygonMeshEditor.html).
 pub mod polygon_mesh;
+mod polygon_mesh_f32;
+mod polygon_mesh_slice;
 /// Defines generalized polyline curve.
 pub mod polyline_curve;
+/// Reports the progress of long I/O and filters, and cancels them.
+pub mod progress;
+/// Export of STEP AP242 tessellated geometry
+pub mod step;
 /// I/O of STL
 pub mod stl;
 mod structured_mesh;
+/// Export of 3MF
+pub mod threemf;
+/// I/O of TMF, the native binary format of polygon meshes
+pub mod tmf;
+/// Validates the raw data of meshes in importing, and reports the problems.
+pub mod validation;
+/// Export of VTK legacy and XML files
+pub mod vtk;
+/// Export of X3D and VRML97
+pub mod x3d;
+/// I/O of plain XYZ point clouds
+pub mod xyz;
@@ -1,5 +1,46 @@
+use crate::progress::{Progress, ProgressCounter, ProgressReader};
+use crate::validation::{FixPolicy, Validated};
 use crate::*;
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::Path;
+#[cfg(feature = "async")]
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+fn invalid_data(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message).into()
+}
+
+/// Material of wavefront obj, defined in MTL file
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OBJMaterial {
+    /// the name of the material
+    pub name: String,
+    /// the diffuse color `Kd`
+    pub diffuse: Option<[f64; 3]>,
+    /// the specular color `Ks`
+    pub specular: Option<[f64; 3]>,
+    /// the specular exponent `Ns`
+    pub shininess: Option<f64>,
+    /// the opacity `d`, or `1 - Tr`
+    pub dissolve: Option<f64>,
+    /// the path of the diffuse texture `map_Kd`, as written in MTL file
+    pub diffuse_texture: Option<String>,
+}
+
+/// Mesh of wavefront obj with its materials
+#[derive(Clone, Debug)]
+pub struct OBJModel {
+    /// the polygon mesh
+    pub mesh: PolygonMesh,
+    /// the materials defined in the MTL files, followed by the materials used by
+    /// `usemtl` but not defined in the MTL files
+    pub materials: Vec<OBJMaterial>,
+    /// the index of the material of each face in the order of `mesh.face_iter()`,
+    /// `None` if no material is assigned to the face
+    pub face_materials: Vec<Option<usize>>,
+}
 
 /// Writes obj data to output stream
 /// # Examples
@@ -41,86 +82,746 @@ use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 /// obj::write(&mesh, std::fs::File::create("meshdata.obj").unwrap());
 /// ```
 pub fn write<W: Write>(mesh: &PolygonMesh, writer: W) -> Result<()> {
-    sub_write(mesh, &mut BufWriter::new(writer))
+    write_with_options(mesh, writer, &OBJWriteOptions::default())
 }
 
-/// Writes obj data to output stream
-pub fn write_vec<W: Write>(mesh: &Vec<PolygonMesh>, writer: W) -> Result<()> {
-    let mut writer = BufWriter::new(writer);
-    for (i, mesh) in mesh.iter().enumerate() {
-        writer.write_fmt(format_args!("g {}\n", i))?;
-        sub_write(mesh, &mut writer)?;
+/// The notation of the coordinates in writing obj
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OBJFloatFormat {
+    /// the scientific notation with the digits after the decimal point, e.g. `1.250e-1`
+    Scientific(usize),
+    /// the decimal notation with the digits after the decimal point, e.g. `0.125`
+    Decimal(usize),
+    /// the shortest decimal notation which is read back to the same `f64`
+    Shortest,
+}
+
+/// The line endings in writing obj
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OBJLineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl OBJLineEnding {
+    #[inline(always)]
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            OBJLineEnding::Lf => b"\n",
+            OBJLineEnding::CrLf => b"\r\n",
+        }
     }
-    Ok(())
 }
 
-fn write2vec<V: std::ops::Index<usize, Output = f64>, W: Write>(
-    writer: &mut BufWriter<W>,
-    vecs: &[V],
-    prefix: &str,
+/// Options of [`write_with_options`](./fn.write_with_options.html)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OBJWriteOptions {
+    /// the notation of the coordinates. Default is `OBJFloatFormat::Scientific(10)`.
+    pub float_format: OBJFloatFormat,
+    /// whether the uv coordinates are written or not. Default is `true`.
+    pub write_uv_coords: bool,
+    /// whether the normals are written or not. Default is `true`.
+    pub write_normals: bool,
+    /// whether the vertex colors are written as the extension `v x y z r g b` or not.
+    /// Default is `true`.
+    pub write_colors: bool,
+    /// whether the indices of the faces are written as the negative indices relative to
+    /// the ends of the arrays, e.g. `f -3 -2 -1`, or not. Default is `false`.
+    pub relative_indices: bool,
+    /// the comment written at the top of the file, each line of which is prefixed by `# `.
+    /// Default is `None`.
+    pub header: Option<String>,
+    /// the line endings. Default is `OBJLineEnding::Lf`.
+    pub line_ending: OBJLineEnding,
+}
+
+impl Default for OBJWriteOptions {
+    #[inline(always)]
+    fn default() -> OBJWriteOptions {
+        OBJWriteOptions {
+            float_format: OBJFloatFormat::Scientific(10),
+            write_uv_coords: true,
+            write_normals: true,
+            write_colors: true,
+            relative_indices: false,
+            header: None,
+            line_ending: OBJLineEnding::Lf,
+        }
+    }
+}
+
+impl OBJWriteOptions {
+    fn write_header<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if let Some(header) = &self.header {
+            for line in header.lines() {
+                match line.is_empty() {
+                    true => writer.write_all(b"#")?,
+                    false => writer.write_fmt(format_args!("# {}", line))?,
+                }
+                writer.write_all(self.line_ending.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// writes the line `prefix x y z ...`
+    fn write_line<W: Write>(&self, writer: &mut W, prefix: &str, coords: &[f64]) -> Result<()> {
+        writer.write_all(prefix.as_bytes())?;
+        for x in coords {
+            match self.float_format {
+                OBJFloatFormat::Scientific(digits) => write!(writer, " {:.*e}", digits, x)?,
+                OBJFloatFormat::Decimal(digits) => write!(writer, " {:.*}", digits, x)?,
+                OBJFloatFormat::Shortest => write!(writer, " {}", x)?,
+            }
+        }
+        writer.write_all(self.line_ending.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Writes obj data to output stream with the options of the notation, the attributes and
+/// the layout of the file.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::obj::*;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 0.5, 0.0),
+/// ];
+/// let normals = vec![Vector3::unit_z()];
+/// let faces = Faces::from_iter(&[[(0, None, Some(0)), (1, None, Some(0)), (2, None, Some(0))]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), normals, faces);
+///
+/// let options = OBJWriteOptions {
+///     float_format: OBJFloatFormat::Decimal(3),
+///     write_normals: false,
+///     relative_indices: true,
+///     header: Some("generated by truck".to_string()),
+///     line_ending: OBJLineEnding::CrLf,
+///     ..Default::default()
+/// };
+/// let mut obj = Vec::new();
+/// obj::write_with_options(&mesh, &mut obj, &options).unwrap();
+/// let expected = "# generated by truck\r\n\
+///                 v 0.000 0.000 0.000\r\n\
+///                 v 1.000 0.000 0.000\r\n\
+///                 v 0.000 0.500 0.000\r\n\
+///                 f -3 -2 -1\r\n";
+/// assert_eq!(String::from_utf8(obj.clone()).unwrap(), expected);
+///
+/// let read = obj::read(obj.as_slice()).unwrap();
+/// assert_eq!(read.positions(), mesh.positions());
+/// assert!(read.normals().is_empty());
+/// assert_eq!(read.tri_faces()[0], [0, 1, 2].map(Vertex::from));
+/// ```
+#[inline(always)]
+pub fn write_with_options<W: Write>(
+    mesh: &PolygonMesh,
+    writer: W,
+    options: &OBJWriteOptions,
 ) -> Result<()> {
-    for vec in vecs {
-        writer.write_fmt(format_args!("{} {:.10e} {:.10e}\n", prefix, vec[0], vec[1]))?;
+    write_with_progress(mesh, writer, options, |_, _| true)
+}
+
+/// Writes obj data to output stream with the options, reporting the number of the written
+/// lines of the elements to `progress`.
+///
+/// If the writing is cancelled, returns [`Error::Cancelled`] and the output is incomplete.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut log = Vec::new();
+/// let options = obj::OBJWriteOptions::default();
+/// let progress = |done, total| {
+///     log.push((done, total));
+///     true
+/// };
+/// obj::write_with_progress(&mesh, Vec::new(), &options, progress).unwrap();
+/// assert_eq!(log, vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+///
+/// // cancels after writing the positions
+/// let progress = |done, _| done < 3;
+/// let res = obj::write_with_progress(&mesh, Vec::new(), &options, progress);
+/// assert!(matches!(res, Err(errors::Error::Cancelled)));
+/// ```
+///
+/// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+pub fn write_with_progress<W: Write, P: Progress>(
+    mesh: &PolygonMesh,
+    writer: W,
+    options: &OBJWriteOptions,
+    progress: P,
+) -> Result<()> {
+    let mut lines = mesh.positions().len() + mesh.faces().len();
+    if options.write_uv_coords {
+        lines += mesh.uv_coords().len();
+    }
+    if options.write_normals {
+        lines += mesh.normals().len();
+    }
+    let mut counter = ProgressCounter::new(lines, progress);
+    let mut writer = BufWriter::new(writer);
+    options.write_header(&mut writer)?;
+    write_mesh(&mut writer, mesh, [0; 3], options, &mut counter)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes obj data to output stream
+pub fn write_vec<W: Write>(mesh: &Vec<PolygonMesh>, writer: W) -> Result<()> {
+    let meshes: Vec<_> = mesh.iter().enumerate().map(|(i, mesh)| (i.to_string(), mesh)).collect();
+    sub_write_grouped(&meshes, writer)
+}
+
+/// Writes the named meshes to output stream as the groups of obj, e.g. the parts of an assembly.
+/// The meshes are restored by [`read_grouped`].
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+/// let meshes = vec![("base".to_string(), mesh.clone()), ("lid".to_string(), mesh)];
+/// let mut obj = Vec::new();
+/// obj::write_grouped(&meshes, &mut obj).unwrap();
+/// let read = obj::read_grouped(obj.as_slice()).unwrap();
+/// assert_eq!(read, meshes);
+/// ```
+///
+/// [`read_grouped`]: ./fn.read_grouped.html
+pub fn write_grouped<W: Write>(meshes: &[(String, PolygonMesh)], writer: W) -> Result<()> {
+    let meshes: Vec<_> = meshes.iter().map(|(name, mesh)| (name.clone(), mesh)).collect();
+    sub_write_grouped(&meshes, writer)
+}
+
+fn sub_write_grouped<W: Write>(meshes: &[(String, &PolygonMesh)], writer: W) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let options = OBJWriteOptions::default();
+    let mut counter = ProgressCounter::new(0, |_, _| true);
+    let mut offsets = [0; 3];
+    for (name, mesh) in meshes {
+        writer.write_fmt(format_args!("g {}\n", name))?;
+        write_mesh(&mut writer, mesh, offsets, &options, &mut counter)?;
+        offsets[0] += mesh.positions().len();
+        offsets[1] += mesh.uv_coords().len();
+        offsets[2] += mesh.normals().len();
     }
+    writer.flush()?;
     Ok(())
 }
 
-fn write3vec<V: std::ops::Index<usize, Output = f64>, W: Write>(
+/// Writes the positions with the vertex colors as the extension `v x y z r g b`.
+fn write_positions<W: Write, P: Progress>(
     writer: &mut BufWriter<W>,
-    vecs: &[V],
-    prefix: &str,
+    mesh: &PolygonMesh,
+    options: &OBJWriteOptions,
+    counter: &mut ProgressCounter<P>,
 ) -> Result<()> {
-    for vec in vecs {
-        writer.write_fmt(format_args!(
-            "{} {:.10e} {:.10e} {:.10e}\n",
-            prefix, vec[0], vec[1], vec[2]
-        ))?;
+    let colors = mesh.position_colors().filter(|_| options.write_colors);
+    for (i, pos) in mesh.positions().iter().enumerate() {
+        match &colors {
+            Some(colors) => {
+                let col = colors[i];
+                options.write_line(writer, "v", &[pos[0], pos[1], pos[2], col[0], col[1], col[2]])?
+            }
+            None => options.write_line(writer, "v", &[pos[0], pos[1], pos[2]])?,
+        }
+        counter.try_advance(1)?;
     }
     Ok(())
 }
 
 impl Vertex {
-    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        match (self.uv, self.nor) {
-            (None, None) => writer.write_fmt(format_args!("{}", self.pos + 1)),
-            (Some(uv), None) => writer.write_fmt(format_args!("{}/{}", self.pos + 1, uv + 1)),
-            (None, Some(nor)) => writer.write_fmt(format_args!("{}//{}", self.pos + 1, nor + 1)),
-            (Some(uv), Some(nor)) => {
-                writer.write_fmt(format_args!("{}/{}/{}", self.pos + 1, uv + 1, nor + 1))
-            }
+    /// writes the vertex whose indices are converted by `index(array, idx)`
+    fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        index: impl Fn(usize, usize) -> isize,
+    ) -> std::io::Result<()> {
+        let pos = index(0, self.pos);
+        let uv = self.uv.map(|uv| index(1, uv));
+        let nor = self.nor.map(|nor| index(2, nor));
+        match (uv, nor) {
+            (None, None) => writer.write_fmt(format_args!("{}", pos)),
+            (Some(uv), None) => writer.write_fmt(format_args!("{}/{}", pos, uv)),
+            (None, Some(nor)) => writer.write_fmt(format_args!("{}//{}", pos, nor)),
+            (Some(uv), Some(nor)) => writer.write_fmt(format_args!("{}/{}/{}", pos, uv, nor)),
         }
     }
 }
 
 impl Faces {
-    fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+    /// writes the faces whose indices are shifted by `offsets`, or are relative to `lens`,
+    /// the lengths of the arrays of the mesh
+    fn write<W: Write, P: Progress>(
+        &self,
+        writer: &mut W,
+        offsets: [usize; 3],
+        lens: [usize; 3],
+        options: &OBJWriteOptions,
+        counter: &mut ProgressCounter<P>,
+    ) -> Result<()> {
+        let index = |array: usize, idx: usize| match options.relative_indices {
+            true => idx as isize - lens[array] as isize,
+            false => (idx + offsets[array] + 1) as isize,
+        };
         for face in self.face_iter() {
-            writer.write(b"f")?;
+            writer.write_all(b"f")?;
             for v in face {
-                writer.write(b" ")?;
-                v.write(writer)?;
+                let v = Vertex {
+                    uv: v.uv.filter(|_| options.write_uv_coords),
+                    nor: v.nor.filter(|_| options.write_normals),
+                    ..*v
+                };
+                writer.write_all(b" ")?;
+                v.write(writer, index)?;
             }
-            writer.write(b"\n")?;
+            writer.write_all(options.line_ending.as_bytes())?;
+            counter.try_advance(1)?;
         }
         Ok(())
     }
 }
 
-fn sub_write<W: Write>(mesh: &PolygonMesh, writer: &mut BufWriter<W>) -> Result<()> {
-    write3vec(writer, mesh.positions(), "v")?;
-    write2vec(writer, mesh.uv_coords(), "vt")?;
-    write3vec(writer, mesh.normals(), "vn")?;
-    mesh.faces.write(writer)
+fn write_mesh<W: Write, P: Progress>(
+    writer: &mut BufWriter<W>,
+    mesh: &PolygonMesh,
+    offsets: [usize; 3],
+    options: &OBJWriteOptions,
+    counter: &mut ProgressCounter<P>,
+) -> Result<()> {
+    write_positions(writer, mesh, options, counter)?;
+    if options.write_uv_coords {
+        for uv in mesh.uv_coords() {
+            options.write_line(writer, "vt", &[uv[0], uv[1]])?;
+            counter.try_advance(1)?;
+        }
+    }
+    if options.write_normals {
+        for nor in mesh.normals() {
+            options.write_line(writer, "vn", &[nor[0], nor[1], nor[2]])?;
+            counter.try_advance(1)?;
+        }
+    }
+    let lens = [mesh.positions().len(), mesh.uv_coords().len(), mesh.normals().len()];
+    mesh.faces.write(writer, offsets, lens, options, counter)
 }
 
 /// Reads mesh data from wavefront obj file.
-pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> {
+///
+/// The materials are ignored. Use [`read_with_materials`] for reading the materials.
+///
+/// [`read_with_materials`]: ./fn.read_with_materials.html
+#[inline(always)]
+pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> { sub_read(reader).map(|res| res.mesh) }
+
+/// Reads mesh data from wavefront obj file, reporting the number of the read bytes of
+/// `len` bytes to `progress`, e.g. `len` is the size of the file.
+///
+/// If the reading is cancelled, returns [`Error::Cancelled`].
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let obj = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let mut read = 0;
+/// let progress = |done, _| {
+///     read = done;
+///     true
+/// };
+/// let mesh = obj::read_with_progress(obj.as_ref(), obj.len(), progress).unwrap();
+/// assert_eq!(mesh.faces().len(), 1);
+/// assert_eq!(read, obj.len());
+/// ```
+///
+/// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+pub fn read_with_progress<R: Read, P: Progress>(
+    reader: R,
+    len: usize,
+    progress: P,
+) -> Result<PolygonMesh> {
+    let mut reader = ProgressReader::new(reader, len, progress);
+    let res = read(&mut reader);
+    reader.check(res)
+}
+
+/// Reads mesh data from wavefront obj file, with validating the indices, the coordinates and
+/// the faces instead of returning the error, and fixes the problems by `policy`.
+///
+/// The indices of the faces in the report are in the order of the `f` statements.
+/// The syntax errors are still returned as the errors.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::validation::FixPolicy;
+/// let obj = b"v 0 0 0
+/// v 1 0 0
+/// v 0 1 0
+/// v nan 0 0
+/// f 1 2 3
+/// f 1 2 5
+/// f 1 2 4
+/// f 1 2 2
+/// ";
+/// // `obj::read` returns the error by the index out of range
+/// assert!(obj::read(obj.as_ref()).is_err());
+///
+/// let validated = obj::read_validated(obj.as_ref(), FixPolicy::Drop).unwrap();
+/// assert_eq!(validated.value.faces().len(), 1);
+/// let report = &validated.report;
+/// assert_eq!(report.out_of_range_count(), 1);
+/// assert_eq!(report.non_finite_count(), 1);
+/// assert_eq!(report.degenerate_face_count(), 1);
+/// assert_eq!(report.dropped_faces, vec![1, 2, 3]);
+/// ```
+pub fn read_validated<R: Read>(reader: R, policy: FixPolicy) -> Result<Validated<PolygonMesh>> {
+    let raw = read_raw(reader)?;
+    let mut validated = PolygonMesh::validate_raw_parts(
+        raw.positions,
+        raw.uv_coords,
+        raw.normals,
+        Vec::new(),
+        raw.faces,
+        policy,
+    );
+    // the colors are ignored unless all the positions have colors
+    let mesh = &mut validated.value;
+    if !raw.colors.is_empty() && raw.colors.len() == mesh.positions().len() {
+        mesh.set_position_colors(raw.colors)?;
+    }
+    Ok(validated)
+}
+
+/// Reads mesh data and the materials from wavefront obj file.
+///
+/// `open` is called with the path of each MTL file in `mtllib`, as written in the obj file,
+/// and returns the reader of the MTL file.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let obj = b"mtllib cube.mtl
+/// v 0 0 0
+/// v 1 0 0
+/// v 0 1 0
+/// v 1 1 0
+/// f 1 2 3
+/// usemtl red
+/// f 3 2 4
+/// ";
+/// let mtl = b"newmtl red
+/// Kd 1.0 0.0 0.0
+/// map_Kd -s 2 2 1 red.png
+/// ";
+/// let model = obj::read_with_materials(obj.as_ref(), |path| {
+///     assert_eq!(path, "cube.mtl");
+///     Ok(mtl.as_ref())
+/// })
+/// .unwrap();
+/// assert_eq!(model.mesh.faces().len(), 2);
+/// assert_eq!(model.materials.len(), 1);
+/// assert_eq!(model.materials[0].diffuse, Some([1.0, 0.0, 0.0]));
+/// assert_eq!(model.materials[0].diffuse_texture.as_deref(), Some("red.png"));
+/// assert_eq!(model.face_materials, vec![None, Some(0)]);
+/// ```
+pub fn read_with_materials<R, M, F>(reader: R, mut open: F) -> Result<OBJModel>
+where
+    R: Read,
+    M: Read,
+    F: FnMut(&str) -> std::io::Result<M>,
+{
+    let obj = sub_read(reader)?;
+    let mut materials = Vec::new();
+    for path in &obj.libraries {
+        materials.extend(read_mtl(open(path)?)?);
+    }
+    let mut indices = HashMap::new();
+    materials.iter().enumerate().for_each(|(i, material)| {
+        indices.entry(material.name.clone()).or_insert(i);
+    });
+    let used: Vec<usize> = obj
+        .material_names
+        .into_iter()
+        .map(|name| {
+            *indices.entry(name.clone()).or_insert_with(|| {
+                materials.push(OBJMaterial {
+                    name,
+                    ..Default::default()
+                });
+                materials.len() - 1
+            })
+        })
+        .collect();
+    let face_materials = obj
+        .face_materials
+        .into_iter()
+        .map(|idx| idx.map(|idx| used[idx]))
+        .collect();
+    Ok(OBJModel {
+        mesh: obj.mesh,
+        materials,
+        face_materials,
+    })
+}
+
+/// Reads mesh data and the materials from wavefront obj file at `path`.
+/// The paths of MTL files are relative to the directory of the obj file.
+///
+/// This is the only function using the file system, and is not available on
+/// `wasm32-unknown-unknown`, which has no file system. There, use [`read_with_materials`]
+/// with the bytes of the MTL files instead.
+///
+/// [`read_with_materials`]: ./fn.read_with_materials.html
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn read_file_with_materials<P: AsRef<Path>>(path: P) -> Result<OBJModel> {
+    let path = path.as_ref();
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let file = std::fs::File::open(path)?;
+    read_with_materials(file, |mtl| std::fs::File::open(dir.join(mtl)))
+}
+
+/// Reads mesh data from wavefront obj file as the named meshes of the groups, in the order of
+/// the first appearance of the groups.
+///
+/// Each `g` or `o` statement starts the group named by the rest of the line, and the faces
+/// before the first statement belong to the group `default`. The faces of the groups with the
+/// same name are collected into one mesh, which has only the attributes used by its faces.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let obj = b"v 0 0 0
+/// v 1 0 0
+/// v 0 1 0
+/// v 1 1 0
+/// o body
+/// f 1 2 3
+/// g wheel
+/// f 3 2 4
+/// ";
+/// let meshes = obj::read_grouped(obj.as_ref()).unwrap();
+/// assert_eq!(meshes.len(), 2);
+/// assert_eq!(meshes[0].0, "body");
+/// assert_eq!(meshes[1].0, "wheel");
+/// assert_eq!(meshes[1].1.positions().len(), 3);
+/// assert_eq!(meshes[1].1.faces().len(), 1);
+/// ```
+pub fn read_grouped<R: Read>(reader: R) -> Result<Vec<(String, PolygonMesh)>> {
+    let OBJData {
+        mesh,
+        group_names,
+        face_groups,
+        ..
+    } = sub_read(reader)?;
+    let mut group_faces = vec![Vec::new(); group_names.len()];
+    mesh.face_iter()
+        .zip(face_groups)
+        .for_each(|(face, group)| group_faces[group].push(face));
+    Ok(group_names
+        .into_iter()
+        .zip(group_faces)
+        .filter(|(_, faces)| !faces.is_empty())
+        .map(|(name, faces)| (name, sub_mesh(&mesh, &faces)))
+        .collect())
+}
+
+/// the mesh consisting of `faces` of `mesh` and the attributes used by them,
+/// in the original order
+fn sub_mesh(mesh: &PolygonMesh, faces: &[&[Vertex]]) -> PolygonMesh {
+    fn compress<T: Copy>(src: &[T], used: impl Iterator<Item = usize>) -> (Vec<T>, Vec<usize>) {
+        let mut is_used = vec![false; src.len()];
+        used.for_each(|idx| is_used[idx] = true);
+        let mut dst = Vec::new();
+        let map = src
+            .iter()
+            .zip(is_used)
+            .map(|(x, is_used)| {
+                if is_used {
+                    dst.push(*x);
+                }
+                dst.len().wrapping_sub(1)
+            })
+            .collect();
+        (dst, map)
+    }
+    let vertices = || faces.iter().flat_map(|face| face.iter());
+    let (positions, pos_map) = compress(mesh.positions(), vertices().map(|v| v.pos));
+    let (uv_coords, uv_map) = compress(mesh.uv_coords(), vertices().filter_map(|v| v.uv));
+    let (normals, nor_map) = compress(mesh.normals(), vertices().filter_map(|v| v.nor));
+    let (colors, col_map) = compress(mesh.colors(), vertices().filter_map(|v| v.col));
+    let mut new_faces = Faces::default();
+    for face in faces {
+        let face: Vec<Vertex> = face
+            .iter()
+            .map(|v| Vertex {
+                pos: pos_map[v.pos],
+                uv: v.uv.map(|uv| uv_map[uv]),
+                nor: v.nor.map(|nor| nor_map[nor]),
+                col: v.col.map(|col| col_map[col]),
+            })
+            .collect();
+        new_faces.push(face);
+    }
+    let mut new_mesh = PolygonMesh::new(positions, uv_coords, normals, new_faces);
+    new_mesh.extend_colors(colors);
+    new_mesh
+}
+
+/// Reads the materials from MTL file.
+pub fn read_mtl<R: Read>(reader: R) -> Result<Vec<OBJMaterial>> {
+    let mut materials = Vec::<OBJMaterial>::new();
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let mut args = line.split_whitespace();
+        let first_str = match args.next() {
+            Some(first_str) => first_str,
+            None => continue,
+        };
+        if first_str == "newmtl" {
+            let name = line[6..].trim().to_string();
+            materials.push(OBJMaterial {
+                name,
+                ..Default::default()
+            });
+            continue;
+        }
+        let material = match materials.last_mut() {
+            Some(material) => material,
+            None => return Err(invalid_data("material property before newmtl")),
+        };
+        let mut number = || -> Result<f64> {
+            let arg = args.next().ok_or_else(|| invalid_data("missing value"))?;
+            Ok(arg.parse::<f64>()?)
+        };
+        match first_str {
+            "Kd" => material.diffuse = Some([number()?, number()?, number()?]),
+            "Ks" => material.specular = Some([number()?, number()?, number()?]),
+            "Ns" => material.shininess = Some(number()?),
+            "d" => material.dissolve = Some(number()?),
+            "Tr" => material.dissolve = Some(1.0 - number()?),
+            // the options of the texture map precede the file name
+            "map_Kd" => match line.split_whitespace().last() {
+                Some(path) if path != "map_Kd" => material.diffuse_texture = Some(path.to_string()),
+                _ => return Err(invalid_data("missing texture path")),
+            },
+            _ => {}
+        }
+    }
+    Ok(materials)
+}
+
+struct OBJData {
+    mesh: PolygonMesh,
+    libraries: Vec<String>,
+    material_names: Vec<String>,
+    /// the indices of `material_names` in the order of `mesh.face_iter()`
+    face_materials: Vec<Option<usize>>,
+    group_names: Vec<String>,
+    /// the indices of `group_names` in the order of `mesh.face_iter()`
+    face_groups: Vec<usize>,
+}
+
+/// the data read from obj file, before building the mesh
+struct OBJRawData {
+    positions: Vec<Point3>,
+    colors: Vec<Vector4>,
+    uv_coords: Vec<Vector2>,
+    normals: Vec<Vector3>,
+    /// the faces in the order of reading
+    faces: Vec<Vec<Vertex>>,
+    libraries: Vec<String>,
+    material_names: Vec<String>,
+    /// the indices of `material_names` in the order of `faces`
+    face_materials: Vec<Option<usize>>,
+    group_names: Vec<String>,
+    /// the indices of `group_names` in the order of `faces`
+    face_groups: Vec<usize>,
+}
+
+fn sub_read<R: Read>(reader: R) -> Result<OBJData> {
+    let OBJRawData {
+        positions,
+        colors,
+        uv_coords,
+        normals,
+        faces: raw_faces,
+        libraries,
+        material_names,
+        face_materials: raw_materials,
+        group_names,
+        face_groups: raw_groups,
+    } = read_raw(reader)?;
+    let mut faces = Faces::default();
+    // the materials and the groups of triangles, quadrangles and the other polygons
+    let mut face_materials = (Vec::new(), Vec::new(), Vec::new());
+    let mut face_groups = (Vec::new(), Vec::new(), Vec::new());
+    let attrs = raw_materials.into_iter().zip(raw_groups);
+    for (face, (material, group)) in raw_faces.into_iter().zip(attrs) {
+        match face.len() {
+            0..=2 => {}
+            3 => {
+                face_materials.0.push(material);
+                face_groups.0.push(group);
+            }
+            4 => {
+                face_materials.1.push(material);
+                face_groups.1.push(group);
+            }
+            _ => {
+                face_materials.2.push(material);
+                face_groups.2.push(group);
+            }
+        }
+        faces.push(face);
+    }
+    let (tri, quad, other) = face_materials;
+    let face_materials = tri.into_iter().chain(quad).chain(other).collect();
+    let (tri, quad, other) = face_groups;
+    let mut mesh = PolygonMesh::try_new(positions, uv_coords, normals, faces)?;
+    // the colors are ignored unless all the positions have colors
+    if !colors.is_empty() && colors.len() == mesh.positions().len() {
+        mesh.set_position_colors(colors)?;
+    }
+    Ok(OBJData {
+        mesh,
+        libraries,
+        material_names,
+        face_materials,
+        group_names,
+        face_groups: tri.into_iter().chain(quad).chain(other).collect(),
+    })
+}
+
+fn read_raw<R: Read>(reader: R) -> Result<OBJRawData> {
     let mut positions = Vec::new();
+    let mut colors = Vec::new();
     let mut uv_coords = Vec::new();
     let mut normals = Vec::new();
-    let mut faces = Faces::default();
+    let mut faces = Vec::new();
+    let mut libraries = Vec::new();
+    let mut material_names = Vec::<String>::new();
+    let mut current_material = None;
+    let mut group_names = vec!["default".to_string()];
+    let mut current_group = 0;
+    let mut face_materials = Vec::new();
+    let mut face_groups = Vec::new();
     let reader = BufReader::new(reader);
-    for line in reader.lines().map(|s| s.unwrap()) {
+    for line in reader.lines() {
+        let line = line?;
         let mut args = line.split_whitespace();
         if let Some(first_str) = args.next() {
             if first_str == "v" {
@@ -128,6 +829,11 @@ pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> {
                 let y = args.next().unwrap().parse::<f64>()?;
                 let z = args.next().unwrap().parse::<f64>()?;
                 positions.push(Point3::new(x, y, z));
+                // the extension of vertex colors: `v x y z r g b`
+                let rgb: Vec<f64> = args.take(3).filter_map(|x| x.parse().ok()).collect();
+                if rgb.len() == 3 {
+                    colors.push(Vector4::new(rgb[0], rgb[1], rgb[2], 1.0));
+                }
             } else if first_str == "vt" {
                 let u = args.next().unwrap().parse::<f64>()?;
                 let v = args.next().unwrap().parse::<f64>()?;
@@ -144,27 +850,152 @@ pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> {
                         break;
                     }
                     let mut iter = vert_str.split("/");
-                    let pos = iter
-                        .next()
-                        .map(|val| val.parse::<usize>().map(|i| i - 1).ok())
-                        .unwrap_or(None);
-                    let uv = iter
-                        .next()
-                        .map(|val| val.parse::<usize>().map(|i| i - 1).ok())
-                        .unwrap_or(None);
-                    let nor = iter
-                        .next()
-                        .map(|val| val.parse::<usize>().map(|i| i - 1).ok())
-                        .unwrap_or(None);
+                    let pos = iter.next().and_then(|val| parse_index(val, positions.len()));
+                    let uv = iter.next().and_then(|val| parse_index(val, uv_coords.len()));
+                    let nor = iter.next().and_then(|val| parse_index(val, normals.len()));
                     let vert = match (pos, uv, nor) {
                         (None, _, _) => continue,
-                        (Some(pos), uv, nor) => Vertex { pos, uv, nor },
+                        (Some(pos), uv, nor) => Vertex {
+                            pos,
+                            uv,
+                            nor,
+                            col: None,
+                        },
                     };
                     face.push(vert);
                 }
                 faces.push(face);
+                face_materials.push(current_material);
+                face_groups.push(current_group);
+            } else if first_str == "g" || first_str == "o" {
+                let name = match line.trim()[1..].trim() {
+                    "" => "default",
+                    name => name,
+                };
+                current_group = match group_names.iter().position(|x| x == name) {
+                    Some(idx) => idx,
+                    None => {
+                        group_names.push(name.to_string());
+                        group_names.len() - 1
+                    }
+                };
+            } else if first_str == "mtllib" {
+                libraries.extend(args.map(String::from));
+            } else if first_str == "usemtl" {
+                let name = line.trim()[6..].trim();
+                current_material = match material_names.iter().position(|x| x == name) {
+                    Some(idx) => Some(idx),
+                    None => {
+                        material_names.push(name.to_string());
+                        Some(material_names.len() - 1)
+                    }
+                };
             }
         }
     }
-    PolygonMesh::try_new(positions, uv_coords, normals, faces)
+    Ok(OBJRawData {
+        positions,
+        colors,
+        uv_coords,
+        normals,
+        faces,
+        libraries,
+        material_names,
+        face_materials,
+        group_names,
+        face_groups,
+    })
+}
+
+/// Parses the one-based index or the negative index relative to the end of the array of `len`.
+fn parse_index(val: &str, len: usize) -> Option<usize> {
+    match val.parse::<isize>().ok()? {
+        0 => None,
+        i if i > 0 => Some(i as usize - 1),
+        i => len.checked_sub(i.unsigned_abs()),
+    }
+}
+
+/// Reads mesh data whose lengths are in `file_unit`, and converts the lengths into the unit of `context`.
+pub fn read_with_context<R: Read>(
+    reader: R,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<PolygonMesh> {
+    let mut mesh = read(reader)?;
+    if file_unit != context.length_unit {
+        mesh.transform_by(Matrix4::from_scale(context.scale_from(file_unit)));
+    }
+    Ok(mesh)
+}
+
+/// Writes mesh data whose lengths are converted from the unit of `context` into `file_unit`.
+pub fn write_with_context<W: Write>(
+    mesh: &PolygonMesh,
+    writer: W,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<()> {
+    match file_unit == context.length_unit {
+        true => write(mesh, writer),
+        false => write(
+            &mesh.transformed(Matrix4::from_scale(context.scale_to(file_unit))),
+            writer,
+        ),
+    }
+}
+
+/// Reads mesh data from wavefront obj file asynchronously.
+///
+/// The whole data is read into memory before parsing, so the reader never blocks the runtime.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # runtime.block_on(async {
+/// let obj = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let mesh = obj::read_async(obj.as_ref()).await.unwrap();
+/// assert_eq!(mesh.faces().len(), 1);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn read_async<R: AsyncRead + Unpin>(mut reader: R) -> Result<PolygonMesh> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    read(bytes.as_slice())
+}
+
+/// Writes obj data to the output stream asynchronously.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # runtime.block_on(async {
+/// let obj = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let mesh = obj::read(obj.as_ref()).unwrap();
+/// let mut bytes = Vec::new();
+/// obj::write_async(&mesh, &mut bytes).await.unwrap();
+/// assert_eq!(obj::read(bytes.as_slice()).unwrap(), mesh);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+#[inline(always)]
+pub async fn write_async<W: AsyncWrite + Unpin>(mesh: &PolygonMesh, writer: W) -> Result<()> {
+    write_with_options_async(mesh, writer, &OBJWriteOptions::default()).await
+}
+
+/// Writes obj data to the output stream asynchronously with the options.
+///
+/// The whole data is written into memory before sending, so the writer never blocks the runtime.
+#[cfg(feature = "async")]
+pub async fn write_with_options_async<W: AsyncWrite + Unpin>(
+    mesh: &PolygonMesh,
+    mut writer: W,
+    options: &OBJWriteOptions,
+) -> Result<()> {
+    let mut bytes = Vec::new();
+    write_with_options(mesh, &mut bytes, options)?;
+    writer.write_all(&bytes).await?;
+    writer.flush().await?;
+    Ok(())
 }
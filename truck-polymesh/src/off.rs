@@ -0,0 +1,131 @@
+use crate::*;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+fn syntax_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "syntax error")
+}
+
+/// Writes off data to output stream
+///
+/// Since OFF has no texture coordinates and normals, only positions and faces are written.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut bytes = Vec::new();
+/// off::write(&mesh, &mut bytes).unwrap();
+/// let mesh0 = off::read(bytes.as_slice()).unwrap();
+/// assert_eq!(mesh, mesh0);
+/// ```
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(b"OFF\n")?;
+    writer.write_fmt(format_args!(
+        "{} {} 0\n",
+        mesh.positions().len(),
+        mesh.faces().len()
+    ))?;
+    for p in mesh.positions() {
+        writer.write_fmt(format_args!("{:.10e} {:.10e} {:.10e}\n", p[0], p[1], p[2]))?;
+    }
+    for face in mesh.face_iter() {
+        writer.write_fmt(format_args!("{}", face.len()))?;
+        for v in face {
+            writer.write_fmt(format_args!(" {}", v.pos))?;
+        }
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads mesh data from OFF file.
+///
+/// Colors attached to vertices and faces are ignored.
+pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines().filter_map(|line| match line {
+        Ok(line) => {
+            let line = match line.find('#') {
+                Some(idx) => line[..idx].trim().to_string(),
+                None => line.trim().to_string(),
+            };
+            match line.is_empty() {
+                true => None,
+                false => Some(Ok(line)),
+            }
+        }
+        Err(e) => Some(Err(e)),
+    });
+    let mut line = lines.next().ok_or_else(syntax_error)??;
+    if line.starts_with("OFF") {
+        line = match line[3..].trim().is_empty() {
+            true => lines.next().ok_or_else(syntax_error)??,
+            false => line[3..].trim().to_string(),
+        };
+    }
+    let mut args = line.split_whitespace();
+    let n_pos = args.next().ok_or_else(syntax_error)?.parse::<usize>()?;
+    let n_face = args.next().ok_or_else(syntax_error)?.parse::<usize>()?;
+    let mut positions = Vec::with_capacity(n_pos);
+    for _ in 0..n_pos {
+        let line = lines.next().ok_or_else(syntax_error)??;
+        let mut args = line.split_whitespace();
+        let x = args.next().ok_or_else(syntax_error)?.parse::<f64>()?;
+        let y = args.next().ok_or_else(syntax_error)?.parse::<f64>()?;
+        let z = args.next().ok_or_else(syntax_error)?.parse::<f64>()?;
+        positions.push(Point3::new(x, y, z));
+    }
+    let mut faces = Faces::default();
+    for _ in 0..n_face {
+        let line = lines.next().ok_or_else(syntax_error)??;
+        let mut args = line.split_whitespace();
+        let len = args.next().ok_or_else(syntax_error)?.parse::<usize>()?;
+        let face = args
+            .take(len)
+            .map(|idx| idx.parse::<usize>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if face.len() != len {
+            return Err(syntax_error().into());
+        }
+        faces.push(face);
+    }
+    PolygonMesh::try_new(positions, Vec::new(), Vec::new(), faces)
+}
+
+/// Reads mesh data whose lengths are in `file_unit`, and converts the lengths into the unit of `context`.
+pub fn read_with_context<R: Read>(
+    reader: R,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<PolygonMesh> {
+    let mut mesh = read(reader)?;
+    if file_unit != context.length_unit {
+        mesh.transform_by(Matrix4::from_scale(context.scale_from(file_unit)));
+    }
+    Ok(mesh)
+}
+
+/// Writes mesh data whose lengths are converted from the unit of `context` into `file_unit`.
+pub fn write_with_context<W: Write>(
+    mesh: &PolygonMesh,
+    writer: W,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<()> {
+    match file_unit == context.length_unit {
+        true => write(mesh, writer),
+        false => write(
+            &mesh.transformed(Matrix4::from_scale(context.scale_to(file_unit))),
+            writer,
+        ),
+    }
+}
@@ -0,0 +1,333 @@
+use crate::*;
+use std::f64::consts::PI;
+
+impl Default for OrientationDescriptor {
+    #[inline(always)]
+    fn default() -> OrientationDescriptor {
+        OrientationDescriptor {
+            overhang_angle: Deg(45.0).into(),
+            samples: 64,
+        }
+    }
+}
+
+/// the number of the largest triangles whose normals are the candidates of the orientation
+const FACE_CANDIDATES: usize = 32;
+
+impl PolygonMesh {
+    /// Moves the centroid of the surface to the origin, and rotates the principal axes
+    /// onto the x, y and z axes in the descending order of the variances.
+    /// Returns the transform applied.
+    ///
+    /// The centroid and the covariance are of the area of the faces. If the area is zero,
+    /// the positions of the mesh are used instead.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// // a flat box with the size 1 x 2 x 4, turned arbitrarily
+    /// let positions: Vec<Point3> = (0..8)
+    ///     .map(|i| Point3::new((i % 2) as f64, (i / 2 % 2) as f64 * 2.0, (i / 4) as f64 * 4.0))
+    ///     .collect();
+    /// let faces = Faces::from_iter(&[
+    ///     [0, 2, 3, 1], [0, 1, 5, 4], [1, 3, 7, 5],
+    ///     [3, 2, 6, 7], [2, 0, 4, 6], [4, 5, 7, 6],
+    /// ]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// let turn = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+    ///     * Matrix4::from_axis_angle(Vector3::new(1.0, 1.0, 1.0).normalize(), Rad(1.0));
+    /// mesh.transform_by(turn);
+    ///
+    /// let trans = mesh.align_principal_axes();
+    /// let bdb = mesh.bounding_box();
+    /// assert!(bdb.center().near(&Point3::origin()));
+    /// assert!(bdb.diagonal().near(&Vector3::new(4.0, 2.0, 1.0)));
+    /// // the corner at the origin is moved to a corner of the aligned box.
+    /// let corner = (trans * turn).transform_point(Point3::origin());
+    /// assert!(corner.to_vec().magnitude().near(&f64::sqrt(5.25)));
+    /// ```
+    pub fn align_principal_axes(&mut self) -> Matrix4 {
+        let (centroid, covariance) = self.centroid_covariance();
+        let axes = sorted_eigenvectors(covariance);
+        let rotation = Matrix3::from_cols(axes[0], axes[1], axes[0].cross(axes[1])).transpose();
+        let trans = Matrix4::from(rotation) * Matrix4::from_translation(-centroid.to_vec());
+        self.transform_by(trans);
+        trans
+    }
+
+    /// Returns the volume of the supports needed for printing upward along the z axis
+    /// on the build plate at the bottom of the mesh.
+    ///
+    /// Each face whose overhang from the vertical exceeds `overhang_angle` is supported
+    /// by the prism between the face and the build plate. The mesh is assumed to be oriented outward.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// // a square facing downward at the height 2
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 2.0),
+    ///     Point3::new(1.0, 0.0, 2.0),
+    ///     Point3::new(1.0, 1.0, 2.0),
+    ///     Point3::new(0.0, 1.0, 2.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[1, 4, 3, 2]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// assert!(mesh.support_volume(Deg(45.0).into()).near(&2.0));
+    /// ```
+    #[inline(always)]
+    pub fn support_volume(&self, overhang_angle: Rad<f64>) -> f64 {
+        let triangles = self.area_triangles();
+        support_volume(
+            self.positions(),
+            &triangles,
+            Vector3::unit_z(),
+            overhang_angle,
+        )
+    }
+
+    /// Rotates the mesh so that the volume of the supports for 3D printing is minimized,
+    /// and puts the mesh on the build plate `z = 0`. Returns the transform applied.
+    ///
+    /// The candidates of the bottom direction are the axes, the normals of the largest faces and
+    /// the directions uniformly distributed on the sphere. The support volume is estimated
+    /// by [`PolygonMesh::support_volume`].
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// // a cone standing on its apex
+    /// const N: usize = 32;
+    /// let mut positions: Vec<Point3> = (0..N)
+    ///     .map(|i| {
+    ///         let t = 2.0 * std::f64::consts::PI * i as f64 / N as f64;
+    ///         Point3::new(2.0 * f64::cos(t), 2.0 * f64::sin(t), 1.0)
+    ///     })
+    ///     .collect();
+    /// positions.push(Point3::origin());
+    /// let mut faces = Faces::from_iter((0..N).map(|i| [N, (i + 1) % N, i]));
+    /// faces.push((0..N).collect::<Vec<_>>());
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// let descriptor = OrientationDescriptor::default();
+    /// assert!(mesh.support_volume(descriptor.overhang_angle) > 1.0);
+    ///
+    /// // the cone is turned upside down.
+    /// let trans = mesh.auto_orient(&descriptor);
+    /// assert!(mesh.support_volume(descriptor.overhang_angle).so_small());
+    /// assert!(trans.transform_point(Point3::origin()).near(&Point3::new(0.0, 0.0, 1.0)));
+    /// ```
+    ///
+    /// [`PolygonMesh::support_volume`]: ./struct.PolygonMesh.html#method.support_volume
+    pub fn auto_orient(&mut self, descriptor: &OrientationDescriptor) -> Matrix4 {
+        let triangles = self.area_triangles();
+        let mut largest: Vec<&AreaTriangle> = triangles.iter().collect();
+        largest.sort_by(|a, b| {
+            b.area_normal
+                .magnitude2()
+                .partial_cmp(&a.area_normal.magnitude2())
+                .unwrap()
+        });
+        let axes = [
+            Vector3::unit_z(),
+            -Vector3::unit_z(),
+            Vector3::unit_x(),
+            -Vector3::unit_x(),
+            Vector3::unit_y(),
+            -Vector3::unit_y(),
+        ];
+        let faces = largest
+            .into_iter()
+            .take(FACE_CANDIDATES)
+            .filter(|tri| !tri.area_normal.so_small())
+            .map(|tri| -tri.area_normal.normalize());
+        let (_, up) = axes
+            .iter()
+            .copied()
+            .chain(faces)
+            .chain(fibonacci_sphere(descriptor.samples))
+            .map(|up| {
+                let volume =
+                    support_volume(self.positions(), &triangles, up, descriptor.overhang_angle);
+                (volume, up)
+            })
+            .fold((f64::INFINITY, Vector3::unit_z()), |res, cand| {
+                match cand.0 < res.0 - TOLERANCE {
+                    true => cand,
+                    false => res,
+                }
+            });
+        let rotation = Matrix4::from(rotation_between(up, Vector3::unit_z()));
+        let bottom = self
+            .positions()
+            .iter()
+            .map(|p| rotation.transform_point(*p)[2])
+            .fold(f64::INFINITY, f64::min);
+        let trans = match bottom.is_finite() {
+            true => Matrix4::from_translation(Vector3::new(0.0, 0.0, -bottom)) * rotation,
+            false => rotation,
+        };
+        self.transform_by(trans);
+        trans
+    }
+
+    fn area_triangles(&self) -> Vec<AreaTriangle> {
+        let positions = self.positions();
+        self.face_iter()
+            .flat_map(|face| {
+                (2..face.len()).map(move |i| {
+                    let p = [
+                        positions[face[0].pos],
+                        positions[face[i - 1].pos],
+                        positions[face[i].pos],
+                    ];
+                    AreaTriangle {
+                        area_normal: (p[1] - p[0]).cross(p[2] - p[0]) / 2.0,
+                        positions: p,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn centroid_covariance(&self) -> (Point3, Matrix3) {
+        let triangles = self.area_triangles();
+        let area: f64 = triangles
+            .iter()
+            .map(|tri| tri.area_normal.magnitude())
+            .sum();
+        if area.so_small() {
+            let len = self.positions().len() as f64;
+            let sum = self
+                .positions()
+                .iter()
+                .fold(Vector3::zero(), |sum, p| sum + p.to_vec());
+            let centroid = sum / f64::max(len, 1.0);
+            let moment = self.positions().iter().fold(Matrix3::zero(), |sum, p| {
+                let v = p.to_vec() - centroid;
+                sum + outer(v, v)
+            });
+            return (Point3::from_vec(centroid), moment / f64::max(len, 1.0));
+        }
+        let centroid = triangles.iter().fold(Vector3::zero(), |sum, tri| {
+            let center =
+                (tri.positions[0].to_vec() + tri.positions[1].to_vec() + tri.positions[2].to_vec())
+                    / 3.0;
+            sum + center * tri.area_normal.magnitude()
+        }) / area;
+        // the second moments of uniform triangles: A / 12 * (sum of v v^T + s s^T), s = sum of v
+        let moment = triangles.iter().fold(Matrix3::zero(), |sum, tri| {
+            let v = tri
+                .positions
+                .iter()
+                .map(|p| p.to_vec() - centroid)
+                .collect::<Vec<_>>();
+            let s = v[0] + v[1] + v[2];
+            let m = outer(v[0], v[0]) + outer(v[1], v[1]) + outer(v[2], v[2]) + outer(s, s);
+            sum + m * (tri.area_normal.magnitude() / 12.0)
+        });
+        (Point3::from_vec(centroid), moment / area)
+    }
+}
+
+/// a triangle with the normal vector whose length is the area
+#[derive(Clone, Copy, Debug)]
+struct AreaTriangle {
+    positions: [Point3; 3],
+    area_normal: Vector3,
+}
+
+fn support_volume(
+    positions: &[Point3],
+    triangles: &[AreaTriangle],
+    up: Vector3,
+    overhang_angle: Rad<f64>,
+) -> f64 {
+    let bottom = positions
+        .iter()
+        .map(|p| p.to_vec().dot(up))
+        .fold(f64::INFINITY, f64::min);
+    let threshold = overhang_angle.sin();
+    triangles
+        .iter()
+        .filter_map(|tri| {
+            let projected = -tri.area_normal.dot(up);
+            let area = tri.area_normal.magnitude();
+            match area > 0.0 && projected > threshold * area {
+                true => {
+                    let height = tri
+                        .positions
+                        .iter()
+                        .map(|p| p.to_vec().dot(up))
+                        .sum::<f64>()
+                        / 3.0;
+                    Some(projected * (height - bottom))
+                }
+                false => None,
+            }
+        })
+        .sum()
+}
+
+/// the directions uniformly distributed on the sphere
+fn fibonacci_sphere(n: usize) -> impl Iterator<Item = Vector3> {
+    let golden_angle = PI * (3.0 - f64::sqrt(5.0));
+    (0..n).map(move |i| {
+        let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+        let r = f64::sqrt(1.0 - z * z);
+        let t = golden_angle * i as f64;
+        Vector3::new(r * f64::cos(t), r * f64::sin(t), z)
+    })
+}
+
+/// the rotation mapping the unit vector `from` to the unit vector `to`
+fn rotation_between(from: Vector3, to: Vector3) -> Matrix3 {
+    let axis = from.cross(to);
+    let cos = f64::clamp(from.dot(to), -1.0, 1.0);
+    if !axis.so_small() {
+        Matrix3::from_axis_angle(axis.normalize(), Rad(f64::acos(cos)))
+    } else if cos > 0.0 {
+        Matrix3::identity()
+    } else {
+        let perp = match from.x.abs() < 0.9 {
+            true => Vector3::unit_x(),
+            false => Vector3::unit_y(),
+        };
+        Matrix3::from_axis_angle(from.cross(perp).normalize(), Rad(PI))
+    }
+}
+
+#[inline(always)]
+fn outer(a: Vector3, b: Vector3) -> Matrix3 { Matrix3::from_cols(a * b[0], a * b[1], a * b[2]) }
+
+/// Eigenvectors of the symmetric matrix, in the descending order of the eigenvalues,
+/// by the Jacobi eigenvalue algorithm.
+fn sorted_eigenvectors(mut mat: Matrix3) -> [Vector3; 3] {
+    let mut vectors = Matrix3::identity();
+    for _ in 0..50 {
+        let (p, q) = [(0, 1), (0, 2), (1, 2)]
+            .iter()
+            .copied()
+            .fold((0, 1), |(p, q), (i, j)| {
+                match mat[j][i].abs() > mat[q][p].abs() {
+                    true => (i, j),
+                    false => (p, q),
+                }
+            });
+        // `<=` for the zero matrix, e.g. of the empty mesh, whose eigenvectors are the axes.
+        if mat[q][p].abs() <= 1.0e-15 * (mat[0][0].abs() + mat[1][1].abs() + mat[2][2].abs()) {
+            break;
+        }
+        let theta = (mat[q][q] - mat[p][p]) / (2.0 * mat[q][p]);
+        let t = theta.signum() / (theta.abs() + f64::sqrt(theta * theta + 1.0));
+        let c = 1.0 / f64::sqrt(t * t + 1.0);
+        let s = t * c;
+        let mut rot = Matrix3::identity();
+        rot[p][p] = c;
+        rot[q][q] = c;
+        rot[q][p] = s;
+        rot[p][q] = -s;
+        mat = rot.transpose() * mat * rot;
+        vectors = vectors * rot;
+    }
+    let mut order = [0, 1, 2];
+    order.sort_by(|i, j| mat[*j][*j].total_cmp(&mat[*i][*i]));
+    [vectors[order[0]], vectors[order[1]], vectors[order[2]]]
+}
@@ -0,0 +1,281 @@
+use crate::*;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+fn syntax_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "syntax error")
+}
+
+/// PCD data format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PCDFormat {
+    /// ascii format
+    ASCII,
+    /// binary format
+    Binary,
+}
+
+impl Default for PCDFormat {
+    /// the binary format
+    #[inline(always)]
+    fn default() -> PCDFormat { PCDFormat::Binary }
+}
+
+/// a field of the points declared in the header
+#[derive(Clone, Debug)]
+struct Field {
+    name: String,
+    size: usize,
+    field_type: u8,
+    count: usize,
+}
+
+impl Field {
+    /// Returns the value and the raw bits of the first element of the field in `bytes`.
+    fn parse_binary(&self, bytes: &[u8]) -> Result<(f64, u32)> {
+        let mut buf = [0; 8];
+        buf[..self.size].copy_from_slice(&bytes[..self.size]);
+        let bits = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+        let value = match (self.field_type, self.size) {
+            (b'F', 4) => f32::from_bits(bits) as f64,
+            (b'F', 8) => f64::from_le_bytes(buf),
+            (b'U', 1) => buf[0] as f64,
+            (b'U', 2) => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+            (b'U', 4) => bits as f64,
+            (b'U', 8) => u64::from_le_bytes(buf) as f64,
+            (b'I', 1) => buf[0] as i8 as f64,
+            (b'I', 2) => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+            (b'I', 4) => bits as i32 as f64,
+            (b'I', 8) => i64::from_le_bytes(buf) as f64,
+            _ => return Err(syntax_error().into()),
+        };
+        Ok((value, bits))
+    }
+
+    /// Returns the value and the raw bits of the ascii representation of the field.
+    fn parse_ascii(&self, token: &str) -> Result<(f64, u32)> {
+        match self.field_type {
+            b'F' => {
+                let value = token.parse::<f64>()?;
+                Ok((value, (value as f32).to_bits()))
+            }
+            _ => {
+                let value = token.parse::<i64>()?;
+                Ok((value as f64, value as u32))
+            }
+        }
+    }
+}
+
+/// Writes point cloud to output stream
+///
+/// The coordinates and the normals are written as 8-byte floats, and the colors are written
+/// as the packed `rgba` field.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let positions = vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0)];
+/// let colors = vec![Vector4::new(1.0, 0.0, 0.0, 1.0), Vector4::new(0.0, 0.0, 1.0, 1.0)];
+/// let cloud = PointCloud::new(positions, Vec::new(), colors);
+///
+/// let mut bytes = Vec::new();
+/// pcd::write(&cloud, &mut bytes, pcd::PCDFormat::ASCII).unwrap();
+/// let cloud0 = pcd::read(bytes.as_slice()).unwrap();
+/// assert_eq!(cloud, cloud0);
+/// ```
+pub fn write<W: Write>(cloud: &PointCloud, writer: W, format: PCDFormat) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let (has_normals, has_colors) = (!cloud.normals().is_empty(), !cloud.colors().is_empty());
+    let mut fields = vec!["x", "y", "z"];
+    if has_normals {
+        fields.extend(["normal_x", "normal_y", "normal_z"]);
+    }
+    let mut sizes = vec!["8"; fields.len()];
+    let mut types = vec!["F"; fields.len()];
+    if has_colors {
+        fields.push("rgba");
+        sizes.push("4");
+        types.push("U");
+    }
+    let n = cloud.len();
+    writer.write_all(b"# .PCD v0.7 - Point Cloud Data file format\nVERSION 0.7\n")?;
+    writer.write_fmt(format_args!("FIELDS {}\n", fields.join(" ")))?;
+    writer.write_fmt(format_args!("SIZE {}\n", sizes.join(" ")))?;
+    writer.write_fmt(format_args!("TYPE {}\n", types.join(" ")))?;
+    writer.write_fmt(format_args!("COUNT {}\n", vec!["1"; fields.len()].join(" ")))?;
+    writer.write_fmt(format_args!("WIDTH {}\nHEIGHT 1\n", n))?;
+    writer.write_fmt(format_args!("VIEWPOINT 0 0 0 1 0 0 0\nPOINTS {}\n", n))?;
+    match format {
+        PCDFormat::ASCII => writer.write_all(b"DATA ascii\n")?,
+        PCDFormat::Binary => writer.write_all(b"DATA binary\n")?,
+    }
+    for i in 0..n {
+        let p = cloud.positions()[i];
+        let mut values = vec![p[0], p[1], p[2]];
+        if has_normals {
+            let n = cloud.normals()[i];
+            values.extend([n[0], n[1], n[2]]);
+        }
+        let rgba = cloud.colors().get(i).map(|c| {
+            let c = c.map(|x| (f64::clamp(x, 0.0, 1.0) * 255.0).round() as u32);
+            c[3] << 24 | c[0] << 16 | c[1] << 8 | c[2]
+        });
+        match format {
+            PCDFormat::ASCII => {
+                let strs: Vec<String> = values.iter().map(|x| x.to_string()).collect();
+                writer.write_all(strs.join(" ").as_bytes())?;
+                if let Some(rgba) = rgba {
+                    writer.write_fmt(format_args!(" {}", rgba))?;
+                }
+                writer.write_all(b"\n")?;
+            }
+            PCDFormat::Binary => {
+                for x in values {
+                    writer.write_all(&x.to_le_bytes())?;
+                }
+                if let Some(rgba) = rgba {
+                    writer.write_all(&rgba.to_le_bytes())?;
+                }
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads point cloud from PCD file.
+///
+/// The fields `x`, `y`, `z` are required, and the normals `normal_x`, `normal_y`, `normal_z`
+/// and the packed colors `rgb` or `rgba` are read if exist. The other fields are ignored,
+/// and the points whose coordinates are NaN, which are the invalid points in organized
+/// point clouds, are skipped. The data format `binary_compressed` is not supported.
+pub fn read<R: Read>(reader: R) -> Result<PointCloud> {
+    let mut reader = BufReader::new(reader);
+    let mut fields: Vec<Field> = Vec::new();
+    let mut points = None;
+    let mut line = String::new();
+    let format = loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(syntax_error().into());
+        }
+        let mut args = line.split_whitespace();
+        let key = match args.next() {
+            Some(key) if !key.starts_with('#') => key.to_ascii_uppercase(),
+            _ => continue,
+        };
+        let args: Vec<&str> = args.collect();
+        match key.as_str() {
+            "FIELDS" => {
+                fields = args
+                    .iter()
+                    .map(|name| Field {
+                        name: name.to_string(),
+                        size: 4,
+                        field_type: b'F',
+                        count: 1,
+                    })
+                    .collect()
+            }
+            "SIZE" | "TYPE" | "COUNT" => {
+                if args.len() != fields.len() {
+                    return Err(syntax_error().into());
+                }
+                for (field, arg) in fields.iter_mut().zip(args) {
+                    match key.as_str() {
+                        "SIZE" => field.size = arg.parse()?,
+                        "TYPE" => field.field_type = arg.as_bytes()[0].to_ascii_uppercase(),
+                        _ => field.count = arg.parse()?,
+                    }
+                }
+            }
+            "POINTS" => points = Some(args.first().ok_or_else(syntax_error)?.parse::<usize>()?),
+            "DATA" => break args.first().ok_or_else(syntax_error)?.to_ascii_lowercase(),
+            _ => {}
+        }
+    };
+    if fields.iter().any(|f| !matches!(f.size, 1 | 2 | 4 | 8) || f.count == 0) {
+        return Err(syntax_error().into());
+    }
+    let points = points.ok_or_else(syntax_error)?;
+    let find = |name: &str| fields.iter().position(|f| f.name == name);
+    let position_indices = ["x", "y", "z"].map(find);
+    let normal_indices = ["normal_x", "normal_y", "normal_z"].map(find);
+    let color_index = find("rgba").map(|idx| (idx, true));
+    let color_index = color_index.or_else(|| find("rgb").map(|idx| (idx, false)));
+    let position_indices = match position_indices {
+        [Some(x), Some(y), Some(z)] => [x, y, z],
+        _ => return Err(syntax_error().into()),
+    };
+    let normal_indices = match normal_indices {
+        [Some(x), Some(y), Some(z)] => Some([x, y, z]),
+        _ => None,
+    };
+
+    let mut cloud = PointCloud::default();
+    let mut push_point = |values: &[(f64, u32)]| {
+        let position = Point3::from(position_indices.map(|idx| values[idx].0));
+        if position.x.is_nan() || position.y.is_nan() || position.z.is_nan() {
+            return;
+        }
+        let normal = normal_indices.map(|indices| Vector3::from(indices.map(|i| values[i].0)));
+        let color = color_index.map(|(idx, has_alpha)| {
+            let bits = values[idx].1;
+            let alpha = match has_alpha {
+                true => bits >> 24,
+                false => 255,
+            };
+            let c = [bits >> 16 & 255, bits >> 8 & 255, bits & 255, alpha];
+            Vector4::from(c.map(|x| x as f64 / 255.0))
+        });
+        cloud.push(position, normal, color);
+    };
+    match format.as_str() {
+        "ascii" => {
+            let mut count = 0;
+            for line in reader.lines() {
+                let line = line?;
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                if tokens.is_empty() || count == points {
+                    continue;
+                }
+                let mut idx = 0;
+                let values = fields
+                    .iter()
+                    .map(|field| {
+                        let token = tokens.get(idx).ok_or_else(syntax_error)?;
+                        idx += field.count;
+                        field.parse_ascii(token)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                push_point(&values);
+                count += 1;
+            }
+            if count != points {
+                return Err(syntax_error().into());
+            }
+        }
+        "binary" => {
+            let record_size: usize = fields.iter().map(|f| f.size * f.count).sum();
+            let mut record = vec![0; record_size];
+            for _ in 0..points {
+                reader.read_exact(&mut record)?;
+                let mut offset = 0;
+                let values = fields
+                    .iter()
+                    .map(|field| {
+                        let value = field.parse_binary(&record[offset..]);
+                        offset += field.size * field.count;
+                        value
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                push_point(&values);
+            }
+        }
+        _ => {
+            let message = format!("The PCD data format {} is not supported.", format);
+            let error = std::io::Error::new(std::io::ErrorKind::Unsupported, message);
+            return Err(error.into());
+        }
+    }
+    Ok(cloud)
+}
@@ -0,0 +1,500 @@
+use crate::*;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+fn syntax_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "syntax error")
+}
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+/// PLY format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PLYFormat {
+    /// ascii format
+    ASCII,
+    /// little-endian binary format
+    BinaryLittleEndian,
+    /// big-endian binary format, supported only for reading
+    BinaryBigEndian,
+}
+
+impl Default for PLYFormat {
+    /// the little-endian binary format
+    #[inline(always)]
+    fn default() -> PLYFormat { PLYFormat::BinaryLittleEndian }
+}
+
+/// the scalar types of properties
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ScalarType {
+    Char,
+    UChar,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Float,
+    Double,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> Result<ScalarType> {
+        match name {
+            "char" | "int8" => Ok(ScalarType::Char),
+            "uchar" | "uint8" => Ok(ScalarType::UChar),
+            "short" | "int16" => Ok(ScalarType::Short),
+            "ushort" | "uint16" => Ok(ScalarType::UShort),
+            "int" | "int32" => Ok(ScalarType::Int),
+            "uint" | "uint32" => Ok(ScalarType::UInt),
+            "float" | "float32" => Ok(ScalarType::Float),
+            "double" | "float64" => Ok(ScalarType::Double),
+            _ => Err(syntax_error().into()),
+        }
+    }
+
+    /// the value of the full intensity of colors
+    fn color_scale(self) -> f64 {
+        match self {
+            ScalarType::Char => i8::MAX as f64,
+            ScalarType::UChar => u8::MAX as f64,
+            ScalarType::Short => i16::MAX as f64,
+            ScalarType::UShort => u16::MAX as f64,
+            ScalarType::Int => i32::MAX as f64,
+            ScalarType::UInt => u32::MAX as f64,
+            ScalarType::Float | ScalarType::Double => 1.0,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ScalarType::Char | ScalarType::UChar => 1,
+            ScalarType::Short | ScalarType::UShort => 2,
+            ScalarType::Int | ScalarType::UInt | ScalarType::Float => 4,
+            ScalarType::Double => 8,
+        }
+    }
+
+    /// Reads a binary value in the byte order `format`.
+    fn read_binary<R: Read>(self, reader: &mut R, format: PLYFormat) -> Result<f64> {
+        let mut bytes = [0u8; 8];
+        let bytes = &mut bytes[..self.size()];
+        reader.read_exact(bytes)?;
+        if format == PLYFormat::BinaryBigEndian {
+            bytes.reverse();
+        }
+        let mut array = [0u8; 8];
+        array[..bytes.len()].copy_from_slice(bytes);
+        Ok(match self {
+            ScalarType::Char => array[0] as i8 as f64,
+            ScalarType::UChar => array[0] as f64,
+            ScalarType::Short => i16::from_le_bytes([array[0], array[1]]) as f64,
+            ScalarType::UShort => u16::from_le_bytes([array[0], array[1]]) as f64,
+            ScalarType::Int => i32::from_le_bytes([array[0], array[1], array[2], array[3]]) as f64,
+            ScalarType::UInt => u32::from_le_bytes([array[0], array[1], array[2], array[3]]) as f64,
+            ScalarType::Float => {
+                f32::from_le_bytes([array[0], array[1], array[2], array[3]]) as f64
+            }
+            ScalarType::Double => f64::from_le_bytes(array),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum PropertyType {
+    Scalar(ScalarType),
+    List(ScalarType, ScalarType),
+}
+
+#[derive(Clone, Debug)]
+struct Property {
+    name: String,
+    property_type: PropertyType,
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// the value of a property
+#[derive(Clone, Debug)]
+enum Value {
+    Scalar(f64),
+    List(Vec<f64>),
+}
+
+/// Writes PLY data to output stream in `format`.
+///
+/// The face vertices with the same indices of positions, uv coordinates, normals and colors are
+/// written as one vertex. The uv coordinates, normals and colors are written only if all the face
+/// vertices have them. The coordinates are written in double precision, and the colors are
+/// written as the properties `red`, `green`, `blue` and `alpha` of `uchar`.
+///
+/// Writing in `PLYFormat::BinaryBigEndian` is not supported and returns an error.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use ply::PLYFormat;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut bytes = Vec::new();
+/// ply::write(&mesh, &mut bytes, PLYFormat::BinaryLittleEndian).unwrap();
+/// let mesh0 = ply::read(bytes.as_slice()).unwrap();
+/// assert_eq!(mesh, mesh0);
+/// ```
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W, format: PLYFormat) -> Result<()> {
+    if format == PLYFormat::BinaryBigEndian {
+        return Err(invalid_input("writing big-endian binary PLY is not supported"));
+    }
+    let mut writer = BufWriter::new(writer);
+    let has_uv = mesh.face_iter().flatten().all(|v| v.uv.is_some());
+    let has_normal = mesh.face_iter().flatten().all(|v| v.nor.is_some());
+    let has_color = mesh.face_iter().flatten().all(|v| v.col.is_some());
+    let mut indices = HashMap::new();
+    let mut vertices = Vec::new();
+    let by_position = mesh
+        .face_iter()
+        .flatten()
+        .all(|v| !has_color || v.col == Some(v.pos));
+    if !has_uv && !has_normal && by_position {
+        // keeps the order of positions
+        (0..mesh.positions().len()).for_each(|i| {
+            let key = (i, None, None, Some(i).filter(|_| has_color));
+            indices.insert(key, i);
+            vertices.push(key);
+        });
+    }
+    let faces: Vec<Vec<usize>> = mesh
+        .face_iter()
+        .map(|face| {
+            face.iter()
+                .map(|v| {
+                    let key = (
+                        v.pos,
+                        v.uv.filter(|_| has_uv),
+                        v.nor.filter(|_| has_normal),
+                        v.col.filter(|_| has_color),
+                    );
+                    *indices.entry(key).or_insert_with(|| {
+                        vertices.push(key);
+                        vertices.len() - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    let format_name = match format {
+        PLYFormat::ASCII => "ascii",
+        _ => "binary_little_endian",
+    };
+    writer.write_fmt(format_args!("ply\nformat {} 1.0\n", format_name))?;
+    writer.write_all(b"comment written by truck-polymesh\n")?;
+    writer.write_fmt(format_args!("element vertex {}\n", vertices.len()))?;
+    writer.write_all(b"property double x\nproperty double y\nproperty double z\n")?;
+    if has_normal {
+        writer.write_all(b"property double nx\nproperty double ny\nproperty double nz\n")?;
+    }
+    if has_uv {
+        writer.write_all(b"property double u\nproperty double v\n")?;
+    }
+    if has_color {
+        writer.write_all(b"property uchar red\nproperty uchar green\n")?;
+        writer.write_all(b"property uchar blue\nproperty uchar alpha\n")?;
+    }
+    writer.write_fmt(format_args!("element face {}\n", faces.len()))?;
+    writer.write_all(b"property list uchar int vertex_indices\nend_header\n")?;
+    for (pos, uv, nor, col) in vertices {
+        let mut values: Vec<f64> = AsRef::<[f64; 3]>::as_ref(&mesh.positions()[pos]).to_vec();
+        if let Some(nor) = nor {
+            values.extend_from_slice(AsRef::<[f64; 3]>::as_ref(&mesh.normals()[nor]));
+        }
+        if let Some(uv) = uv {
+            values.extend_from_slice(AsRef::<[f64; 2]>::as_ref(&mesh.uv_coords()[uv]));
+        }
+        let color: Vec<u8> = match col {
+            Some(col) => AsRef::<[f64; 4]>::as_ref(&mesh.colors()[col])
+                .iter()
+                .map(|x| (x.clamp(0.0, 1.0) * u8::MAX as f64).round() as u8)
+                .collect(),
+            None => Vec::new(),
+        };
+        match format {
+            PLYFormat::ASCII => {
+                let mut line: Vec<String> = values.iter().map(|x| format!("{:.17e}", x)).collect();
+                line.extend(color.iter().map(|x| x.to_string()));
+                writer.write_fmt(format_args!("{}\n", line.join(" ")))?;
+            }
+            _ => {
+                values
+                    .iter()
+                    .try_for_each(|x| writer.write_all(&x.to_le_bytes()))?;
+                writer.write_all(&color)?;
+            }
+        }
+    }
+    for face in faces {
+        if face.len() > u8::MAX as usize {
+            return Err(invalid_input("PLY cannot store faces with more than 255 vertices"));
+        }
+        match format {
+            PLYFormat::ASCII => {
+                writer.write_fmt(format_args!("{}", face.len()))?;
+                face.iter()
+                    .try_for_each(|idx| writer.write_fmt(format_args!(" {}", idx)))?;
+                writer.write_all(b"\n")?;
+            }
+            _ => {
+                writer.write_all(&[face.len() as u8])?;
+                face.iter()
+                    .try_for_each(|idx| writer.write_all(&(*idx as i32).to_le_bytes()))?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads mesh data from PLY file, in either of the ascii and binary formats.
+///
+/// The properties `x`, `y` and `z` of the element `vertex` are read as positions,
+/// `nx`, `ny` and `nz` as normals, and `u` and `v` (or `s` and `t`, `texture_u` and
+/// `texture_v`) as uv coordinates, and `red`, `green`, `blue` and `alpha` (or `r`, `g`, `b` and
+/// `a`) as colors. The colors of the integer types are scaled so that the full intensity is `1.0`.
+/// The property `vertex_indices` (or `vertex_index`) of the element `face` is read as faces.
+/// Other elements and properties are ignored.
+pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> {
+    let mut reader = BufReader::new(reader);
+    let (format, elements) = read_header(&mut reader)?;
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uv_coords = Vec::new();
+    let mut colors = Vec::new();
+    let mut faces = Faces::default();
+    let mut has_normal = false;
+    let mut has_uv = false;
+    let mut has_color = false;
+    for element in &elements {
+        let index = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|property| names.contains(&property.name.as_str()))
+        };
+        let (x, y, z) = (index(&["x"]), index(&["y"]), index(&["z"]));
+        let (nx, ny, nz) = (index(&["nx"]), index(&["ny"]), index(&["nz"]));
+        let u = index(&["u", "s", "texture_u", "texture_s"]);
+        let v = index(&["v", "t", "texture_v", "texture_t"]);
+        let (r, g, b) = (index(&["red", "r"]), index(&["green", "g"]), index(&["blue", "b"]));
+        let a = index(&["alpha", "a"]);
+        let indices = index(&["vertex_indices", "vertex_index"]);
+        if element.name == "vertex" {
+            has_normal = nx.is_some() && ny.is_some() && nz.is_some();
+            has_uv = u.is_some() && v.is_some();
+            has_color = r.is_some() && g.is_some() && b.is_some();
+        }
+        let color_scale = |idx: Option<usize>| match idx.map(|i| &element.properties[i]) {
+            Some(Property {
+                property_type: PropertyType::Scalar(scalar_type),
+                ..
+            }) => scalar_type.color_scale(),
+            _ => 1.0,
+        };
+        let scales = [color_scale(r), color_scale(g), color_scale(b), color_scale(a)];
+        for _ in 0..element.count {
+            let values = match format {
+                PLYFormat::ASCII => {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line)? == 0 {
+                        return Err(syntax_error().into());
+                    }
+                    read_ascii_values(&line, &element.properties)?
+                }
+                _ => read_binary_values(&mut reader, &element.properties, format)?,
+            };
+            let scalar = |idx: Option<usize>| match idx.map(|i| &values[i]) {
+                Some(Value::Scalar(x)) => Ok(*x),
+                _ => Err(syntax_error()),
+            };
+            match element.name.as_str() {
+                "vertex" => {
+                    positions.push(Point3::new(scalar(x)?, scalar(y)?, scalar(z)?));
+                    if has_normal {
+                        normals.push(Vector3::new(scalar(nx)?, scalar(ny)?, scalar(nz)?));
+                    }
+                    if has_uv {
+                        uv_coords.push(Vector2::new(scalar(u)?, scalar(v)?));
+                    }
+                    if has_color {
+                        let alpha = match a {
+                            Some(_) => scalar(a)? / scales[3],
+                            None => 1.0,
+                        };
+                        colors.push(Vector4::new(
+                            scalar(r)? / scales[0],
+                            scalar(g)? / scales[1],
+                            scalar(b)? / scales[2],
+                            alpha,
+                        ));
+                    }
+                }
+                "face" => match indices.map(|i| &values[i]) {
+                    Some(Value::List(list)) => {
+                        let face: Vec<Vertex> = list
+                            .iter()
+                            .map(|idx| {
+                                let idx = *idx as usize;
+                                Vertex {
+                                    pos: idx,
+                                    uv: Some(idx).filter(|_| has_uv),
+                                    nor: Some(idx).filter(|_| has_normal),
+                                    col: None,
+                                }
+                            })
+                            .collect();
+                        faces.push(face);
+                    }
+                    _ => return Err(syntax_error().into()),
+                },
+                _ => {}
+            }
+        }
+    }
+    let mut mesh = PolygonMesh::try_new(positions, uv_coords, normals, faces)?;
+    if has_color {
+        mesh.set_position_colors(colors)?;
+    }
+    Ok(mesh)
+}
+
+fn read_header<R: BufRead>(reader: &mut R) -> Result<(PLYFormat, Vec<Element>)> {
+    let mut read_line = || -> Result<String> {
+        let mut line = String::new();
+        match reader.read_line(&mut line)? {
+            0 => Err(syntax_error().into()),
+            _ => Ok(line.trim().to_string()),
+        }
+    };
+    if read_line()? != "ply" {
+        return Err(syntax_error().into());
+    }
+    let mut format = None;
+    let mut elements = Vec::<Element>::new();
+    loop {
+        let line = read_line()?;
+        let args: Vec<&str> = line.split_whitespace().collect();
+        match args.as_slice() {
+            ["end_header"] => break,
+            ["format", name, _] => {
+                format = Some(match *name {
+                    "ascii" => PLYFormat::ASCII,
+                    "binary_little_endian" => PLYFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PLYFormat::BinaryBigEndian,
+                    _ => return Err(syntax_error().into()),
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse()?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, item_type, name] => {
+                let element = elements.last_mut().ok_or_else(syntax_error)?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    property_type: PropertyType::List(
+                        ScalarType::parse(count_type)?,
+                        ScalarType::parse(item_type)?,
+                    ),
+                });
+            }
+            ["property", scalar_type, name] => {
+                let element = elements.last_mut().ok_or_else(syntax_error)?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    property_type: PropertyType::Scalar(ScalarType::parse(scalar_type)?),
+                });
+            }
+            ["comment", ..] | ["obj_info", ..] | [] => {}
+            _ => return Err(syntax_error().into()),
+        }
+    }
+    Ok((format.ok_or_else(syntax_error)?, elements))
+}
+
+fn read_ascii_values(line: &str, properties: &[Property]) -> Result<Vec<Value>> {
+    let mut args = line.split_whitespace();
+    let mut next = || -> Result<f64> { Ok(args.next().ok_or_else(syntax_error)?.parse()?) };
+    properties
+        .iter()
+        .map(|property| match property.property_type {
+            PropertyType::Scalar(_) => Ok(Value::Scalar(next()?)),
+            PropertyType::List(_, _) => {
+                let len = next()? as usize;
+                Ok(Value::List((0..len).map(|_| next()).collect::<Result<_>>()?))
+            }
+        })
+        .collect()
+}
+
+fn read_binary_values<R: Read>(
+    reader: &mut R,
+    properties: &[Property],
+    format: PLYFormat,
+) -> Result<Vec<Value>> {
+    properties
+        .iter()
+        .map(|property| match property.property_type {
+            PropertyType::Scalar(scalar) => Ok(Value::Scalar(scalar.read_binary(reader, format)?)),
+            PropertyType::List(count, item) => {
+                let len = count.read_binary(reader, format)? as usize;
+                let list = (0..len)
+                    .map(|_| item.read_binary(reader, format))
+                    .collect::<Result<_>>()?;
+                Ok(Value::List(list))
+            }
+        })
+        .collect()
+}
+
+/// Reads mesh data whose lengths are in `file_unit`, and converts the lengths into the unit of `context`.
+pub fn read_with_context<R: Read>(
+    reader: R,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<PolygonMesh> {
+    let mut mesh = read(reader)?;
+    if file_unit != context.length_unit {
+        mesh.transform_by(Matrix4::from_scale(context.scale_from(file_unit)));
+    }
+    Ok(mesh)
+}
+
+/// Writes mesh data whose lengths are converted from the unit of `context` into `file_unit`.
+pub fn write_with_context<W: Write>(
+    mesh: &PolygonMesh,
+    writer: W,
+    format: PLYFormat,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<()> {
+    match file_unit == context.length_unit {
+        true => write(mesh, writer, format),
+        false => write(
+            &mesh.transformed(Matrix4::from_scale(context.scale_to(file_unit))),
+            writer,
+            format,
+        ),
+    }
+}
@@ -0,0 +1,210 @@
+use crate::*;
+use errors::Error;
+
+impl PointCloud {
+    /// complete constructor
+    /// # Panics
+    /// Panic occurs if the lengths of the normals or the colors are incompatible.
+    #[inline(always)]
+    pub fn new(positions: Vec<Point3>, normals: Vec<Vector3>, colors: Vec<Vector4>) -> PointCloud {
+        PointCloud::try_new(positions, normals, colors).unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    /// complete constructor
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if the normals or the colors are not empty
+    /// and their lengths are not the length of the positions.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)];
+    /// let normals = vec![Vector3::unit_z(); 2];
+    /// let cloud = PointCloud::try_new(positions.clone(), normals, Vec::new()).unwrap();
+    /// assert_eq!(cloud.len(), 2);
+    ///
+    /// let colors = vec![Vector4::new(1.0, 0.0, 0.0, 1.0)];
+    /// assert!(PointCloud::try_new(positions, Vec::new(), colors).is_err());
+    /// ```
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn try_new(
+        positions: Vec<Point3>,
+        normals: Vec<Vector3>,
+        colors: Vec<Vector4>,
+    ) -> Result<PointCloud> {
+        check_length("normals", positions.len(), normals.len())?;
+        check_length("colors", positions.len(), colors.len())?;
+        Ok(PointCloud {
+            positions,
+            normals,
+            colors,
+        })
+    }
+
+    /// Creates the point cloud from positions only.
+    #[inline(always)]
+    pub fn from_positions(positions: Vec<Point3>) -> PointCloud {
+        PointCloud {
+            positions,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the vector of all positions.
+    #[inline(always)]
+    pub fn positions(&self) -> &Vec<Point3> { &self.positions }
+
+    /// Returns the mutable slice of all positions.
+    #[inline(always)]
+    pub fn positions_mut(&mut self) -> &mut [Point3] { &mut self.positions }
+
+    /// Returns the vector of all normals. Empty if the point cloud has no normals.
+    #[inline(always)]
+    pub fn normals(&self) -> &Vec<Vector3> { &self.normals }
+
+    /// Returns the mutable slice of all normals.
+    #[inline(always)]
+    pub fn normals_mut(&mut self) -> &mut [Vector3] { &mut self.normals }
+
+    /// Returns the vector of all RGBA colors. Empty if the point cloud has no colors.
+    #[inline(always)]
+    pub fn colors(&self) -> &Vec<Vector4> { &self.colors }
+
+    /// Returns the mutable slice of all RGBA colors.
+    #[inline(always)]
+    pub fn colors_mut(&mut self) -> &mut [Vector4] { &mut self.colors }
+
+    /// Sets the normals of the points. Removes the normals if `normals` is empty.
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if `normals` is not empty
+    /// and its length is not the number of the points.
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn set_normals(&mut self, normals: Vec<Vector3>) -> Result<()> {
+        check_length("normals", self.positions.len(), normals.len())?;
+        self.normals = normals;
+        Ok(())
+    }
+
+    /// Sets the RGBA colors of the points. Removes the colors if `colors` is empty.
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if `colors` is not empty
+    /// and its length is not the number of the points.
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn set_colors(&mut self, colors: Vec<Vector4>) -> Result<()> {
+        check_length("colors", self.positions.len(), colors.len())?;
+        self.colors = colors;
+        Ok(())
+    }
+
+    /// Adds a point with its normal and color.
+    /// # Panics
+    /// Panic occurs if whether `normal` or `color` is given does not match the point cloud,
+    /// except that the first point decides whether the point cloud has normals and colors.
+    pub fn push(&mut self, position: Point3, normal: Option<Vector3>, color: Option<Vector4>) {
+        let first = self.positions.is_empty();
+        match normal {
+            Some(normal) if first || !self.normals.is_empty() => self.normals.push(normal),
+            None if first || self.normals.is_empty() => {}
+            _ => panic!("The normal is incompatible with the point cloud."),
+        }
+        match color {
+            Some(color) if first || !self.colors.is_empty() => self.colors.push(color),
+            None if first || self.colors.is_empty() => {}
+            _ => panic!("The color is incompatible with the point cloud."),
+        }
+        self.positions.push(position);
+    }
+
+    /// Returns the number of the points.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.positions.len() }
+
+    /// Returns whether there are no points or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.positions.is_empty() }
+
+    /// Creates the bounding box of the point cloud.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> { self.positions.iter().collect() }
+
+    /// Returns the polygon mesh with the points as positions and no faces,
+    /// for the functions which accept only polygon meshes.
+    pub fn into_polygon(self) -> PolygonMesh {
+        let faces = Faces::default();
+        let mut mesh = PolygonMesh::new(self.positions, Vec::new(), self.normals, faces);
+        if !self.colors.is_empty() {
+            mesh.set_position_colors(self.colors).unwrap();
+        }
+        mesh
+    }
+}
+
+fn check_length(name: &str, len: usize, attr_len: usize) -> Result<()> {
+    match attr_len == 0 || attr_len == len {
+        true => Ok(()),
+        false => Err(Error::AttributeLength(name.to_string(), len, attr_len)),
+    }
+}
+
+impl From<&PolygonMesh> for PointCloud {
+    /// Extracts the vertices of the polygon mesh.
+    /// The normal and the color of each position are taken from the first face vertex at
+    /// the position. The positions without normals have the zero vectors.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let normals = vec![Vector3::unit_z()];
+    /// let faces = Faces::from_iter(&[[(0, None, Some(0)), (1, None, Some(0)), (2, None, Some(0))]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), normals, faces);
+    ///
+    /// let cloud = PointCloud::from(&mesh);
+    /// assert_eq!(cloud.positions(), mesh.positions());
+    /// assert_eq!(cloud.normals(), &vec![Vector3::unit_z(); 3]);
+    /// assert!(cloud.colors().is_empty());
+    /// ```
+    fn from(mesh: &PolygonMesh) -> PointCloud {
+        let normals = match mesh.normals().is_empty() {
+            true => Vec::new(),
+            false => {
+                let mut normals = vec![None; mesh.positions().len()];
+                mesh.face_iter().flatten().for_each(|v| {
+                    if let Some(nor) = v.nor {
+                        normals[v.pos].get_or_insert(mesh.normals()[nor]);
+                    }
+                });
+                normals
+                    .into_iter()
+                    .map(|nor| nor.unwrap_or_else(Vector3::zero))
+                    .collect()
+            }
+        };
+        PointCloud {
+            positions: mesh.positions().clone(),
+            normals,
+            colors: mesh.position_colors().unwrap_or_default(),
+        }
+    }
+}
+
+impl Transformed<Matrix4> for PointCloud {
+    /// Transforms the positions by `trans`, and the normals by its inverse transpose.
+    fn transform_by(&mut self, trans: Matrix4) {
+        self.positions
+            .iter_mut()
+            .for_each(|p| *p = trans.transform_point(*p));
+        let normal_trans = trans.invert().unwrap_or(trans).transpose();
+        self.normals.iter_mut().for_each(|n| {
+            let m = normal_trans.transform_vector(*n);
+            if !m.so_small() {
+                *n = m.normalize();
+            }
+        });
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use attributes::{AttributeType, MeshAttributes};
 use errors::Error;
 use std::iter::FromIterator;
 
@@ -8,6 +9,7 @@ impl From<(usize, Option<usize>, Option<usize>)> for Vertex {
             pos: tuple.0,
             uv: tuple.1,
             nor: tuple.2,
+            col: None,
         }
     }
 }
@@ -18,6 +20,7 @@ impl From<&(usize, Option<usize>, Option<usize>)> for Vertex {
             pos: tuple.0,
             uv: tuple.1,
             nor: tuple.2,
+            col: None,
         }
     }
 }
@@ -28,6 +31,7 @@ impl From<[usize; 3]> for Vertex {
             pos: arr[0],
             uv: Some(arr[1]),
             nor: Some(arr[2]),
+            col: None,
         }
     }
 }
@@ -38,6 +42,7 @@ impl From<&[usize; 3]> for Vertex {
             pos: arr[0],
             uv: Some(arr[1]),
             nor: Some(arr[2]),
+            col: None,
         }
     }
 }
@@ -48,6 +53,7 @@ impl From<usize> for Vertex {
             pos: idx,
             uv: None,
             nor: None,
+            col: None,
         }
     }
 }
@@ -58,6 +64,7 @@ impl From<&usize> for Vertex {
             pos: *idx,
             uv: None,
             nor: None,
+            col: None,
         }
     }
 }
@@ -193,28 +200,28 @@ impl Faces {
     /// let faces = Faces::from_iter(slice);
     /// let mut iter = faces.face_iter();
     /// assert_eq!(iter.next(), Some([
-    ///     Vertex { pos: 0, uv: None, nor: None },
-    ///     Vertex { pos: 1, uv: None, nor: None },
-    ///     Vertex { pos: 2, uv: None, nor: None },
+    ///     Vertex { pos: 0, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 1, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 2, uv: None, nor: None, col: None },
     /// ].as_ref()));
     /// assert_eq!(iter.next(), Some([
-    ///     Vertex { pos: 0, uv: None, nor: None },
-    ///     Vertex { pos: 2, uv: None, nor: None },
-    ///     Vertex { pos: 3, uv: None, nor: None },
+    ///     Vertex { pos: 0, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 2, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 3, uv: None, nor: None, col: None },
     /// ].as_ref()));
     /// assert_eq!(iter.next(), Some([
-    ///     Vertex { pos: 0, uv: None, nor: None },
-    ///     Vertex { pos: 4, uv: None, nor: None },
-    ///     Vertex { pos: 5, uv: None, nor: None },
-    ///     Vertex { pos: 1, uv: None, nor: None },
+    ///     Vertex { pos: 0, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 4, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 5, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 1, uv: None, nor: None, col: None },
     /// ].as_ref()));
     /// assert_eq!(iter.next(), Some([
-    ///     Vertex { pos: 1, uv: None, nor: None },
-    ///     Vertex { pos: 2, uv: None, nor: None },
-    ///     Vertex { pos: 6, uv: None, nor: None },
-    ///     Vertex { pos: 7, uv: None, nor: None },
-    ///     Vertex { pos: 8, uv: None, nor: None },
-    ///     Vertex { pos: 9, uv: None, nor: None },
+    ///     Vertex { pos: 1, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 2, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 6, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 7, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 8, uv: None, nor: None, col: None },
+    ///     Vertex { pos: 9, uv: None, nor: None, col: None },
     /// ].as_ref()));
     /// assert_eq!(iter.next(), None);
     /// ```
@@ -256,21 +263,40 @@ impl Faces {
     }
 
     #[inline(always)]
-    fn is_compatible(&self, n_pos: usize, n_uv: usize, n_nor: usize) -> Result<()> {
-        self.face_iter().flatten().try_for_each(|v| {
-            if v.pos >= n_pos {
-                Err(Error::OutOfRange("positions", n_pos, v.pos))
-            } else if v.uv.map(|uv| uv >= n_uv).unwrap_or(false) {
-                Err(Error::OutOfRange("uv_coords", n_uv, v.uv.unwrap()))
-            } else if v.nor.map(|nor| nor >= n_nor).unwrap_or(false) {
-                Err(Error::OutOfRange("normals", n_nor, v.nor.unwrap()))
-            } else {
-                Ok(())
-            }
-        })
+    pub(crate) fn is_compatible(
+        &self,
+        n_pos: usize,
+        n_uv: usize,
+        n_nor: usize,
+        n_col: usize,
+    ) -> Result<()> {
+        is_compatible(self, n_pos, n_uv, n_nor, n_col)
     }
 }
 
+/// Checks whether all the indices of `faces` are in the ranges of the arrays.
+pub(crate) fn is_compatible<F: FaceStorage>(
+    faces: &F,
+    n_pos: usize,
+    n_uv: usize,
+    n_nor: usize,
+    n_col: usize,
+) -> Result<()> {
+    faces.face_iter().flatten().try_for_each(|v| {
+        if v.pos >= n_pos {
+            Err(Error::OutOfRange("positions", n_pos, v.pos))
+        } else if v.uv.map(|uv| uv >= n_uv).unwrap_or(false) {
+            Err(Error::OutOfRange("uv_coords", n_uv, v.uv.unwrap()))
+        } else if v.nor.map(|nor| nor >= n_nor).unwrap_or(false) {
+            Err(Error::OutOfRange("normals", n_nor, v.nor.unwrap()))
+        } else if v.col.map(|col| col >= n_col).unwrap_or(false) {
+            Err(Error::OutOfRange("colors", n_col, v.col.unwrap()))
+        } else {
+            Ok(())
+        }
+    })
+}
+
 impl std::ops::Index<usize> for Faces {
     type Output = [Vertex];
     fn index(&self, idx: usize) -> &Self::Output {
@@ -324,7 +350,7 @@ impl std::ops::IndexMut<usize> for Faces {
     }
 }
 
-impl PolygonMesh {
+impl<F: FaceStorage> PolygonMesh<F> {
     /// complete constructor
     /// # Panics
     /// Panic occurs if there is an index is out of range.
@@ -334,8 +360,8 @@ impl PolygonMesh {
         positions: Vec<Point3>,
         uv_coords: Vec<Vector2>,
         normals: Vec<Vector3>,
-        faces: Faces,
-    ) -> PolygonMesh {
+        faces: F,
+    ) -> PolygonMesh<F> {
         PolygonMesh::try_new(positions, uv_coords, normals, faces)
             .unwrap_or_else(|e| panic!("{:?}", e))
     }
@@ -352,10 +378,9 @@ impl PolygonMesh {
         positions: Vec<Point3>,
         uv_coords: Vec<Vector2>,
         normals: Vec<Vector3>,
-        faces: Faces,
-    ) -> Result<PolygonMesh> {
-        faces
-            .is_compatible(positions.len(), uv_coords.len(), normals.len())
+        faces: F,
+    ) -> Result<PolygonMesh<F>> {
+        is_compatible(&faces, positions.len(), uv_coords.len(), normals.len(), 0)
             .map(|_| PolygonMesh::new_unchecked(positions, uv_coords, normals, faces))
     }
 
@@ -365,24 +390,109 @@ impl PolygonMesh {
         positions: Vec<Point3>,
         uv_coords: Vec<Vector2>,
         normals: Vec<Vector3>,
-        faces: Faces,
-    ) -> PolygonMesh {
+        faces: F,
+    ) -> PolygonMesh<F> {
         PolygonMesh {
             positions,
             uv_coords,
             normals,
+            colors: Vec::new(),
             faces,
+            vertex_attributes: MeshAttributes::default(),
+            face_attributes: MeshAttributes::default(),
         }
     }
 
+    /// Creates a polygon mesh by taking the ownership of all the arrays, without boundary check.
+    ///
+    /// The boundary check, whose cost is linear in the number of the vertices of faces,
+    /// can be done afterwards by [`validate`](#method.validate) if needed.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let colors = vec![Vector4::new(1.0, 0.0, 0.0, 1.0)];
+    /// let faces = Faces::from_iter(&[[
+    ///     Vertex { pos: 0, uv: None, nor: None, col: Some(0) },
+    ///     Vertex { pos: 1, uv: None, nor: None, col: Some(0) },
+    ///     Vertex { pos: 2, uv: None, nor: None, col: Some(0) },
+    /// ]]);
+    /// let mesh = PolygonMesh::from_raw_parts(positions, Vec::new(), Vec::new(), colors, faces);
+    /// assert!(mesh.validate().is_ok());
+    ///
+    /// let faces = Faces::from_iter(&[[0, 1, 3]]);
+    /// let positions = vec![Point3::origin()];
+    /// let mesh = PolygonMesh::from_raw_parts(positions, vec![], vec![], vec![], faces);
+    /// assert!(mesh.validate().is_err());
+    /// ```
+    #[inline(always)]
+    pub fn from_raw_parts(
+        positions: Vec<Point3>,
+        uv_coords: Vec<Vector2>,
+        normals: Vec<Vector3>,
+        colors: Vec<Vector4>,
+        faces: F,
+    ) -> PolygonMesh<F> {
+        PolygonMesh {
+            colors,
+            ..PolygonMesh::new_unchecked(positions, uv_coords, normals, faces)
+        }
+    }
+
+    /// Checks whether all the indices of the faces are in the ranges of the arrays.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if there is an index is out of range.
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    #[inline(always)]
+    pub fn validate(&self) -> Result<()> {
+        is_compatible(
+            &self.faces,
+            self.positions.len(),
+            self.uv_coords.len(),
+            self.normals.len(),
+            self.colors.len(),
+        )
+    }
+
+    /// Returns the borrowed view of the arrays and the faces.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// let slice = mesh.as_slice();
+    /// assert_eq!(slice.positions().as_ptr(), mesh.positions().as_ptr());
+    /// assert_eq!(slice.bounding_box(), mesh.bounding_box());
+    /// ```
+    #[inline(always)]
+    pub fn as_slice(&self) -> PolygonMeshSlice<'_, F> {
+        PolygonMeshSlice::from_raw_parts(
+            &self.positions,
+            &self.uv_coords,
+            &self.normals,
+            &self.colors,
+            &self.faces,
+        )
+    }
+
     /// constructor, boundary check is acrivated only in debug mode.
     #[inline(always)]
     pub fn debug_new(
         positions: Vec<Point3>,
         uv_coords: Vec<Vector2>,
         normals: Vec<Vector3>,
-        faces: Faces,
-    ) -> PolygonMesh {
+        faces: F,
+    ) -> PolygonMesh<F> {
         match cfg!(debug_assertions) {
             true => Self::new(positions, uv_coords, normals, faces),
             false => Self::new_unchecked(positions, uv_coords, normals, faces),
@@ -439,10 +549,334 @@ impl PolygonMesh {
         self.normals.extend(iter)
     }
 
+    /// Returns the vector of all RGBA colors.
+    #[inline(always)]
+    pub fn colors(&self) -> &Vec<Vector4> { &self.colors }
+
+    /// Returns the mutable slice of all RGBA colors.
+    #[inline(always)]
+    pub fn colors_mut(&mut self) -> &mut [Vector4] { &mut self.colors }
+
+    /// Adds an RGBA color.
+    #[inline(always)]
+    pub fn push_color(&mut self, color: Vector4) { self.colors.push(color) }
+
+    /// Extend RGBA colors by iterator.
+    #[inline(always)]
+    pub fn extend_colors<I: IntoIterator<Item = Vector4>>(&mut self, iter: I) {
+        self.colors.extend(iter)
+    }
+
+    /// Sets the RGBA color of each position, e.g. the colors of a scanned point cloud or
+    /// the values of an analysis mapped to colors. The colors of all the face vertices are
+    /// replaced by the colors of their positions.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if `colors` is shorter than the positions.
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// let colors = vec![
+    ///     Vector4::new(1.0, 0.0, 0.0, 1.0),
+    ///     Vector4::new(0.0, 1.0, 0.0, 1.0),
+    ///     Vector4::new(0.0, 0.0, 1.0, 1.0),
+    /// ];
+    /// mesh.set_position_colors(colors).unwrap();
+    /// let vertex = mesh.faces()[0][1];
+    /// assert_eq!(mesh.colors()[vertex.col.unwrap()], Vector4::new(0.0, 1.0, 0.0, 1.0));
+    /// assert!(mesh.set_position_colors(Vec::new()).is_err());
+    /// ```
+    pub fn set_position_colors(&mut self, colors: Vec<Vector4>) -> Result<()> {
+        if colors.len() < self.positions.len() {
+            let n = self.positions.len() - 1;
+            return Err(Error::OutOfRange("colors", colors.len(), n));
+        }
+        self.colors = colors;
+        self.faces
+            .face_iter_mut()
+            .flatten()
+            .for_each(|v| v.col = Some(v.pos));
+        Ok(())
+    }
+
+    /// Returns the color of each position, taken from the first face vertex at the position.
+    /// The positions without colors are white. Returns `None` if the mesh has no colors.
+    pub(crate) fn position_colors(&self) -> Option<Vec<Vector4>> {
+        if self.colors.is_empty() {
+            return None;
+        }
+        let mut colors = vec![None; self.positions.len()];
+        self.faces.face_iter().flatten().for_each(|v| {
+            if let Some(col) = v.col {
+                colors[v.pos].get_or_insert(self.colors[col]);
+            }
+        });
+        let white = Vector4::new(1.0, 1.0, 1.0, 1.0);
+        Some(colors.into_iter().map(|col| col.unwrap_or(white)).collect())
+    }
+
+    /// Returns the triangles of positions, into which the faces are triangulated as fans.
+    /// The degenerate triangles sharing positions are removed.
+    pub(crate) fn position_triangles(&self) -> Vec<[usize; 3]> {
+        self.faces
+            .face_iter()
+            .flat_map(|face| {
+                (2..face.len()).map(move |i| [face[0].pos, face[i - 1].pos, face[i].pos])
+            })
+            .filter(|[v0, v1, v2]| v0 != v1 && v1 != v2 && v2 != v0)
+            .collect()
+    }
+
+    /// Returns the attributes of the vertices, whose `i`-th values belong to the `i`-th position.
+    #[inline(always)]
+    pub fn vertex_attributes(&self) -> &MeshAttributes { &self.vertex_attributes }
+
+    /// Returns the attributes of the faces, whose `i`-th values belong to the `i`-th face
+    /// in the order of [`face_iter`](./struct.PolygonMesh.html#method.face_iter).
+    #[inline(always)]
+    pub fn face_attributes(&self) -> &MeshAttributes { &self.face_attributes }
+
+    /// Returns the mutable slice of the values of the vertex attribute `name`.
+    #[inline(always)]
+    pub fn vertex_attribute_mut<T: AttributeType>(&mut self, name: &str) -> Option<&mut [T]> {
+        self.vertex_attributes.get_mut(name)
+    }
+
+    /// Returns the mutable slice of the values of the face attribute `name`.
+    #[inline(always)]
+    pub fn face_attribute_mut<T: AttributeType>(&mut self, name: &str) -> Option<&mut [T]> {
+        self.face_attributes.get_mut(name)
+    }
+
+    /// Sets the attribute `name` of the vertices, i.e. the positions, and returns the old values.
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if the length of `values` is not the number of
+    /// the positions.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// mesh.set_vertex_attribute("weight", vec![0.5, 1.0, 2.0]).unwrap();
+    /// mesh.set_face_attribute("label", vec![7_usize]).unwrap();
+    /// assert_eq!(mesh.vertex_attributes().get::<f64>("weight").unwrap()[2], 2.0);
+    /// assert_eq!(mesh.face_attributes().get::<usize>("label").unwrap()[0], 7);
+    /// // the attributes of the other types are not the same
+    /// assert!(mesh.vertex_attributes().get::<usize>("weight").is_none());
+    /// assert!(mesh.set_vertex_attribute("weight", vec![1.0]).is_err());
+    /// ```
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn set_vertex_attribute<T: AttributeType>(
+        &mut self,
+        name: &str,
+        values: Vec<T>,
+    ) -> Result<Option<Vec<T>>> {
+        let len = self.positions.len();
+        if values.len() != len {
+            return Err(Error::AttributeLength(name.to_string(), len, values.len()));
+        }
+        Ok(self.vertex_attributes.insert(name.to_string(), values))
+    }
+
+    /// Sets the attribute `name` of the faces in the order of
+    /// [`face_iter`](./struct.PolygonMesh.html#method.face_iter), and returns the old values.
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if the length of `values` is not the number of
+    /// the faces.
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn set_face_attribute<T: AttributeType>(
+        &mut self,
+        name: &str,
+        values: Vec<T>,
+    ) -> Result<Option<Vec<T>>> {
+        let len = self.faces.len();
+        if values.len() != len {
+            return Err(Error::AttributeLength(name.to_string(), len, values.len()));
+        }
+        Ok(self.face_attributes.insert(name.to_string(), values))
+    }
+
+    /// Removes the attribute `name` of the vertices, and returns its values.
+    #[inline(always)]
+    pub fn remove_vertex_attribute<T: AttributeType>(&mut self, name: &str) -> Option<Vec<T>> {
+        self.vertex_attributes.remove(name)
+    }
+
+    /// Removes the attribute `name` of the faces, and returns its values.
+    #[inline(always)]
+    pub fn remove_face_attribute<T: AttributeType>(&mut self, name: &str) -> Option<Vec<T>> {
+        self.face_attributes.remove(name)
+    }
+
+    /// Returns the mutable slice of the flags of the vertex selection `name`.
+    #[inline(always)]
+    pub fn vertex_selection_mut(&mut self, name: &str) -> Option<&mut [bool]> {
+        self.vertex_attributes.selection_mut(name)
+    }
+
+    /// Returns the mutable slice of the flags of the face selection `name`.
+    #[inline(always)]
+    pub fn face_selection_mut(&mut self, name: &str) -> Option<&mut [bool]> {
+        self.face_attributes.selection_mut(name)
+    }
+
+    /// Sets the selection `name` of the vertices, i.e. the positions, by the flags which are
+    /// `true` for the selected vertices, and returns the old flags.
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if the length of `flags` is not the number of
+    /// the positions.
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn set_vertex_selection(
+        &mut self,
+        name: &str,
+        flags: Vec<bool>,
+    ) -> Result<Option<Vec<bool>>> {
+        let len = self.positions.len();
+        if flags.len() != len {
+            return Err(Error::AttributeLength(name.to_string(), len, flags.len()));
+        }
+        Ok(self.vertex_attributes.insert_selection(name.to_string(), flags))
+    }
+
+    /// Sets the selection `name` of the faces in the order of
+    /// [`face_iter`](./struct.PolygonMesh.html#method.face_iter) by the flags which are
+    /// `true` for the selected faces, and returns the old flags.
+    /// # Errors
+    /// Returns [`Error::AttributeLength`] if the length of `flags` is not the number of
+    /// the faces.
+    ///
+    /// [`Error::AttributeLength`]: ./errors/enum.Error.html#variant.AttributeLength
+    pub fn set_face_selection(
+        &mut self,
+        name: &str,
+        flags: Vec<bool>,
+    ) -> Result<Option<Vec<bool>>> {
+        let len = self.faces.len();
+        if flags.len() != len {
+            return Err(Error::AttributeLength(name.to_string(), len, flags.len()));
+        }
+        Ok(self.face_attributes.insert_selection(name.to_string(), flags))
+    }
+
+    /// Sets the selection `name` of the vertices consisting of the positions of `indices`,
+    /// and returns the old flags.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if an index is not less than the number of the positions.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2], [0, 2, 3]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    ///
+    /// mesh.select_vertices("corner", [2]).unwrap();
+    /// mesh.select_faces("lower", [0]).unwrap();
+    /// let corner = mesh.vertex_attributes().selection("corner").unwrap();
+    /// assert_eq!(corner, &vec![false, false, true, false]);
+    /// let lower = mesh.face_attributes().selection("lower").unwrap();
+    /// assert_eq!(lower, &vec![true, false]);
+    /// assert!(mesh.select_vertices("corner", [4]).is_err());
+    ///
+    /// // the selections follow the elements
+    /// let mut other = mesh.clone();
+    /// other.select_faces("upper", [1]).unwrap();
+    /// mesh.merge(other);
+    /// let lower = mesh.face_attributes().selection("lower").unwrap();
+    /// assert_eq!(lower, &vec![true, false, true, false]);
+    /// let upper = mesh.face_attributes().selection("upper").unwrap();
+    /// assert_eq!(upper, &vec![false, false, false, true]);
+    /// ```
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    pub fn select_vertices<I: IntoIterator<Item = usize>>(
+        &mut self,
+        name: &str,
+        indices: I,
+    ) -> Result<Option<Vec<bool>>> {
+        let flags = selection_flags(indices, self.positions.len(), "positions")?;
+        Ok(self.vertex_attributes.insert_selection(name.to_string(), flags))
+    }
+
+    /// Sets the selection `name` of the faces consisting of the `indices`-th faces in the order
+    /// of [`face_iter`](./struct.PolygonMesh.html#method.face_iter), and returns the old flags.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if an index is not less than the number of the faces.
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    pub fn select_faces<I: IntoIterator<Item = usize>>(
+        &mut self,
+        name: &str,
+        indices: I,
+    ) -> Result<Option<Vec<bool>>> {
+        let flags = selection_flags(indices, self.faces.len(), "faces")?;
+        Ok(self.face_attributes.insert_selection(name.to_string(), flags))
+    }
+
+    /// Removes the selection `name` of the vertices, and returns its flags.
+    #[inline(always)]
+    pub fn remove_vertex_selection(&mut self, name: &str) -> Option<Vec<bool>> {
+        self.vertex_attributes.remove_selection(name)
+    }
+
+    /// Removes the selection `name` of the faces, and returns its flags.
+    #[inline(always)]
+    pub fn remove_face_selection(&mut self, name: &str) -> Option<Vec<bool>> {
+        self.face_attributes.remove_selection(name)
+    }
+
     /// Returns the faces of the polygon.
     #[inline(always)]
-    pub fn faces(&self) -> &Faces { &self.faces }
+    pub fn faces(&self) -> &F { &self.faces }
+
+    /// Returns the iterator of the slice.
+    ///
+    /// The order is the one of the face storage. By the internal optimization of [`Faces`],
+    /// this iterator does not runs in the simple order in which they are registered,
+    /// but runs order: triangle, square, and the others. [`FlatFaces`] runs in the order
+    /// in which they are registered.
+    /// cf: [`Faces::face_iter`](./struct.Faces.html#method.face_iter)
+    ///
+    /// [`Faces`]: ./struct.Faces.html
+    /// [`FlatFaces`]: ./struct.FlatFaces.html
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = &[Vertex]> { self.faces.face_iter() }
+
+    /// Returns the mutable iterator of the slice, in the same order as
+    /// [`face_iter`](./struct.PolygonMesh.html#method.face_iter).
+    #[inline(always)]
+    pub fn face_iter_mut(&mut self) -> impl Iterator<Item = &mut [Vertex]> {
+        self.faces.face_iter_mut()
+    }
+
+    /// Creates the bounding box of the polygon mesh.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> { self.positions().iter().collect() }
+}
 
+impl PolygonMesh {
     /// Returns the vector of all triangles of the polygon.
     #[inline(always)]
     pub fn tri_faces(&self) -> &Vec<[Vertex; 3]> { &self.faces.tri_faces }
@@ -467,24 +901,6 @@ impl PolygonMesh {
     #[inline(always)]
     pub fn other_faces_mut(&mut self) -> &mut [Vec<Vertex>] { &mut self.faces.other_faces }
 
-    /// Returns the iterator of the slice.
-    ///
-    /// By the internal optimization, this iterator does not runs in the simple order
-    /// in which they are registered, but runs order: triangle, square, and the others.
-    /// cf: [`Faces::face_iter`](./struct.Faces.html#method.face_iter)
-    #[inline(always)]
-    pub fn face_iter<'a>(&'a self) -> impl Iterator<Item = &'a [Vertex]> { self.faces.face_iter() }
-
-    /// Returns the iterator of the slice.
-    ///
-    /// By the internal optimization, this iterator does not runs in the simple order
-    /// in which they are registered, but runs order: triangle, square, and the others.
-    /// cf: [`Faces::face_iter`](./struct.Faces.html#method.face_iter)
-    #[inline(always)]
-    pub fn face_iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut [Vertex]> {
-        self.faces.face_iter_mut()
-    }
-
     /// Creates an editor that performs boundary checking on dropped.
     #[inline(always)]
     pub fn editor(&mut self) -> PolygonMeshEditor {
@@ -492,7 +908,10 @@ impl PolygonMesh {
             positions: &mut self.positions,
             uv_coords: &mut self.uv_coords,
             normals: &mut self.normals,
+            colors: &mut self.colors,
             faces: &mut self.faces,
+            vertex_attributes: &mut self.vertex_attributes,
+            face_attributes: &mut self.face_attributes,
             bound_check: true,
         }
     }
@@ -503,7 +922,10 @@ impl PolygonMesh {
             positions: &mut self.positions,
             uv_coords: &mut self.uv_coords,
             normals: &mut self.normals,
+            colors: &mut self.colors,
             faces: &mut self.faces,
+            vertex_attributes: &mut self.vertex_attributes,
+            face_attributes: &mut self.face_attributes,
             bound_check: false,
         }
     }
@@ -514,34 +936,100 @@ impl PolygonMesh {
             positions: &mut self.positions,
             uv_coords: &mut self.uv_coords,
             normals: &mut self.normals,
+            colors: &mut self.colors,
             faces: &mut self.faces,
+            vertex_attributes: &mut self.vertex_attributes,
+            face_attributes: &mut self.face_attributes,
             bound_check: cfg!(debug_assertions),
         }
     }
 
     /// Returns polygonmesh merged `self` and `mesh`.
+    ///
+    /// The attributes of the vertices and the faces are merged by names, and the values of
+    /// the attributes only in either of the meshes are padded by zeros.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let quad = Faces::from_iter(&[[0, 1, 2, 3]]);
+    /// let mut mesh = PolygonMesh::new(positions.clone(), Vec::new(), Vec::new(), quad);
+    /// mesh.set_face_attribute("label", vec![1_usize]).unwrap();
+    /// let tri = Faces::from_iter(&[[0, 1, 2]]);
+    /// let mut other = PolygonMesh::new(positions, Vec::new(), Vec::new(), tri);
+    /// other.set_face_attribute("label", vec![2_usize]).unwrap();
+    ///
+    /// mesh.merge(other);
+    /// // the triangle precedes the quadrangle
+    /// assert_eq!(mesh.faces()[0].len(), 3);
+    /// assert_eq!(mesh.face_attributes().get::<usize>("label").unwrap(), &vec![2, 1]);
+    /// ```
     pub fn merge(&mut self, mut mesh: PolygonMesh) {
         let n_pos = self.positions.len();
+        let n_faces = self.faces.len();
+        let (other_pos, other_faces) = (mesh.positions.len(), mesh.faces.len());
+        self.vertex_attributes.append(mesh.vertex_attributes, n_pos, other_pos);
+        // the faces are sorted into triangles, quadrangles and the others by concatenation.
+        let map = concatenated_face_order(&self.faces, &mesh.faces);
+        self.face_attributes.append(mesh.face_attributes, n_faces, other_faces);
+        self.face_attributes.reindex(&map);
         let n_uv = self.uv_coords.len();
         let n_nor = self.normals.len();
+        let n_col = self.colors.len();
         mesh.faces.face_iter_mut().for_each(move |face| {
             face.iter_mut().for_each(|v| {
                 v.pos += n_pos;
                 v.uv.as_mut().map(|uv| *uv += n_uv);
                 v.nor.as_mut().map(|nor| *nor += n_nor);
+                if let Some(col) = v.col.as_mut() {
+                    *col += n_col;
+                }
             })
         });
         self.positions.extend(mesh.positions);
         self.uv_coords.extend(mesh.uv_coords);
         self.normals.extend(mesh.normals);
+        self.colors.extend(mesh.colors);
         self.faces.naive_concat(mesh.faces);
     }
-    /// Creates the bounding box of the polygon mesh.
-    #[inline(always)]
-    pub fn bounding_box(&self) -> BoundingBox<Point3> { self.positions().iter().collect() }
 }
 
-impl Invertible for PolygonMesh {
+/// the flags of the selection of the `indices`-th of the `len` elements
+fn selection_flags<I: IntoIterator<Item = usize>>(
+    indices: I,
+    len: usize,
+    typename: &'static str,
+) -> Result<Vec<bool>> {
+    let mut flags = vec![false; len];
+    for i in indices {
+        match flags.get_mut(i) {
+            Some(flag) => *flag = true,
+            None => return Err(Error::OutOfRange(typename, len, i)),
+        }
+    }
+    Ok(flags)
+}
+
+/// the indices of the faces of `faces0` followed by `faces1`, in the order of the concatenation
+fn concatenated_face_order(faces0: &Faces, faces1: &Faces) -> Vec<usize> {
+    let ranges = |faces: &Faces, offset: usize| {
+        let tri = offset + faces.tri_faces.len();
+        let quad = tri + faces.quad_faces.len();
+        [offset..tri, tri..quad, quad..offset + faces.len()]
+    };
+    let ranges0 = ranges(faces0, 0);
+    let ranges1 = ranges(faces1, faces0.len());
+    (0..3)
+        .flat_map(|i| ranges0[i].clone().chain(ranges1[i].clone()))
+        .collect()
+}
+
+impl<F: FaceStorage + Invertible> Invertible for PolygonMesh<F> {
     #[inline(always)]
     fn invert(&mut self) {
         self.normals.iter_mut().for_each(|n| *n = -*n);
@@ -553,11 +1041,51 @@ impl Invertible for PolygonMesh {
             positions: self.positions.clone(),
             uv_coords: self.uv_coords.clone(),
             normals: self.normals.iter().map(|n| -n.clone()).collect(),
+            colors: self.colors.clone(),
             faces: self.faces.inverse(),
+            vertex_attributes: self.vertex_attributes.clone(),
+            face_attributes: self.face_attributes.clone(),
         }
     }
 }
 
+impl<F: FaceStorage> RoughlyBounded<Point3> for PolygonMesh<F> {
+    #[inline(always)]
+    fn roughly_bounding_box(&self) -> BoundingBox<Point3> { self.bounding_box() }
+}
+
+impl<F: FaceStorage> Transformed<Matrix4> for PolygonMesh<F> {
+    /// Transforms the positions by `trans`, and the normals by its inverse transpose.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let normals = vec![Vector3::new(0.0, 0.0, 1.0)];
+    /// let faces = Faces::from_iter(&[[(0, None, Some(0)), (1, None, Some(0)), (2, None, Some(0))]]);
+    /// let mut mesh = PolygonMesh::new(positions, Vec::new(), normals, faces);
+    ///
+    /// mesh.transform_by(Matrix4::from_nonuniform_scale(2.0, 1.0, 1.0));
+    /// assert_eq!(mesh.positions()[1], Point3::new(2.0, 0.0, 0.0));
+    /// assert_eq!(mesh.normals()[0], Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    fn transform_by(&mut self, trans: Matrix4) {
+        self.positions
+            .iter_mut()
+            .for_each(|p| *p = trans.transform_point(*p));
+        let normal_trans = trans.invert().unwrap_or(trans).transpose();
+        self.normals.iter_mut().for_each(|n| {
+            let m = normal_trans.transform_vector(*n);
+            if !m.so_small() {
+                *n = m.normalize();
+            }
+        });
+    }
+}
+
 /// Editor of polygon mesh
 ///
 /// It has mutable references to all member variables of the polygon mesh as public variables,
@@ -611,8 +1139,14 @@ pub struct PolygonMeshEditor<'a> {
     pub uv_coords: &'a mut Vec<Vector2>,
     /// mutable reference to the vector of normals
     pub normals: &'a mut Vec<Vector3>,
+    /// mutable reference to the vector of RGBA colors
+    pub colors: &'a mut Vec<Vector4>,
     /// mutable reference to the faces of the polygon mesh
     pub faces: &'a mut Faces,
+    /// mutable reference to the attributes of the vertices, which are not checked on dropped
+    pub vertex_attributes: &'a mut MeshAttributes,
+    /// mutable reference to the attributes of the faces, which are not checked on dropped
+    pub face_attributes: &'a mut MeshAttributes,
     bound_check: bool,
 }
 
@@ -623,6 +1157,7 @@ impl<'a> PolygonMeshEditor<'a> {
             self.positions.len(),
             self.uv_coords.len(),
             self.normals.len(),
+            self.colors.len(),
         )
     }
 
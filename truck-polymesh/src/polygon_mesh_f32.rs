@@ -0,0 +1,167 @@
+use crate::*;
+
+impl PolygonMeshF32 {
+    /// complete constructor
+    /// # Panics
+    /// Panic occurs if there is an index is out of range.
+    #[inline(always)]
+    pub fn new(
+        positions: Vec<[f32; 3]>,
+        uv_coords: Vec<[f32; 2]>,
+        normals: Vec<[f32; 3]>,
+        faces: Faces,
+    ) -> PolygonMeshF32 {
+        PolygonMeshF32::try_new(positions, uv_coords, normals, faces)
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    /// complete constructor
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if there is an index is out of range.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    /// let faces = Faces::from_iter(&[[0, 1, 2]]);
+    /// let mesh = PolygonMeshF32::try_new(positions.clone(), vec![], vec![], faces).unwrap();
+    /// assert_eq!(mesh.positions().len(), 3);
+    ///
+    /// let faces = Faces::from_iter(&[[0, 1, 3]]);
+    /// assert!(PolygonMeshF32::try_new(positions, vec![], vec![], faces).is_err());
+    /// ```
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    pub fn try_new(
+        positions: Vec<[f32; 3]>,
+        uv_coords: Vec<[f32; 2]>,
+        normals: Vec<[f32; 3]>,
+        faces: Faces,
+    ) -> Result<PolygonMeshF32> {
+        let mesh = PolygonMeshF32::from_raw_parts(positions, uv_coords, normals, vec![], faces);
+        mesh.validate().map(|_| mesh)
+    }
+
+    /// Creates a polygon mesh by taking the ownership of all the arrays, without boundary check.
+    #[inline(always)]
+    pub fn from_raw_parts(
+        positions: Vec<[f32; 3]>,
+        uv_coords: Vec<[f32; 2]>,
+        normals: Vec<[f32; 3]>,
+        colors: Vec<[f32; 4]>,
+        faces: Faces,
+    ) -> PolygonMeshF32 {
+        PolygonMeshF32 {
+            positions,
+            uv_coords,
+            normals,
+            colors,
+            faces,
+        }
+    }
+
+    /// Checks whether all the indices of the faces are in the ranges of the arrays.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if there is an index is out of range.
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    #[inline(always)]
+    pub fn validate(&self) -> Result<()> {
+        self.faces.is_compatible(
+            self.positions.len(),
+            self.uv_coords.len(),
+            self.normals.len(),
+            self.colors.len(),
+        )
+    }
+
+    /// Returns the vector of all positions.
+    #[inline(always)]
+    pub fn positions(&self) -> &Vec<[f32; 3]> { &self.positions }
+
+    /// Returns the mutable slice of all positions.
+    #[inline(always)]
+    pub fn positions_mut(&mut self) -> &mut [[f32; 3]] { &mut self.positions }
+
+    /// Returns the vector of all uv (texture) coordinates.
+    #[inline(always)]
+    pub fn uv_coords(&self) -> &Vec<[f32; 2]> { &self.uv_coords }
+
+    /// Returns the vector of all normals.
+    #[inline(always)]
+    pub fn normals(&self) -> &Vec<[f32; 3]> { &self.normals }
+
+    /// Returns the vector of all colors.
+    #[inline(always)]
+    pub fn colors(&self) -> &Vec<[f32; 4]> { &self.colors }
+
+    /// Returns the faces of the polygon.
+    #[inline(always)]
+    pub fn faces(&self) -> &Faces { &self.faces }
+
+    /// Returns the iterator of the slice.
+    ///
+    /// cf: [`Faces::face_iter`](./struct.Faces.html#method.face_iter)
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = &[Vertex]> { self.faces.face_iter() }
+
+    /// Returns the bounding box of the positions.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> {
+        self.positions.iter().map(|p| Point3::from(p.map(f64::from))).collect()
+    }
+}
+
+#[inline(always)]
+fn round(x: f64) -> f32 { x as f32 }
+
+impl From<PolygonMeshSlice<'_>> for PolygonMeshF32 {
+    /// Rounds the coordinates to the nearest `f32`.
+    fn from(mesh: PolygonMeshSlice<'_>) -> PolygonMeshF32 {
+        let (positions, uv_coords) = (mesh.positions().iter(), mesh.uv_coords().iter());
+        let (normals, colors) = (mesh.normals().iter(), mesh.colors().iter());
+        PolygonMeshF32 {
+            positions: positions.map(|p| Into::<[f64; 3]>::into(*p).map(round)).collect(),
+            uv_coords: uv_coords.map(|uv| Into::<[f64; 2]>::into(*uv).map(round)).collect(),
+            normals: normals.map(|n| Into::<[f64; 3]>::into(*n).map(round)).collect(),
+            colors: colors.map(|c| Into::<[f64; 4]>::into(*c).map(round)).collect(),
+            faces: mesh.faces().clone(),
+        }
+    }
+}
+
+impl From<&PolygonMesh> for PolygonMeshF32 {
+    /// Rounds the coordinates to the nearest `f32`. The named attributes are dropped.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 0.1, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2]]);
+    /// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    /// let mesh32 = PolygonMeshF32::from(&mesh);
+    /// assert_eq!(mesh32.positions()[2], [0.0, 0.1, 0.0]);
+    ///
+    /// // the conversion from f32 is lossless
+    /// let mesh64 = PolygonMesh::from(&mesh32);
+    /// assert_eq!(PolygonMeshF32::from(&mesh64), mesh32);
+    /// assert!(mesh64.positions()[2].near(&mesh.positions()[2]));
+    /// ```
+    #[inline(always)]
+    fn from(mesh: &PolygonMesh) -> PolygonMeshF32 { mesh.as_slice().into() }
+}
+
+impl From<&PolygonMeshF32> for PolygonMesh {
+    /// The conversion is lossless.
+    fn from(mesh: &PolygonMeshF32) -> PolygonMesh {
+        PolygonMesh::from_raw_parts(
+            mesh.positions.iter().map(|p| Point3::from(p.map(f64::from))).collect(),
+            mesh.uv_coords.iter().map(|uv| Vector2::from(uv.map(f64::from))).collect(),
+            mesh.normals.iter().map(|n| Vector3::from(n.map(f64::from))).collect(),
+            mesh.colors.iter().map(|c| Vector4::from(c.map(f64::from))).collect(),
+            mesh.faces.clone(),
+        )
+    }
+}
@@ -0,0 +1,151 @@
+use crate::*;
+use polygon_mesh::is_compatible;
+
+impl<'a, F: FaceStorage> PolygonMeshSlice<'a, F> {
+    /// complete constructor
+    /// # Panics
+    /// Panic occurs if there is an index is out of range.
+    #[inline(always)]
+    pub fn new(
+        positions: &'a [Point3],
+        uv_coords: &'a [Vector2],
+        normals: &'a [Vector3],
+        faces: &'a F,
+    ) -> PolygonMeshSlice<'a, F> {
+        PolygonMeshSlice::try_new(positions, uv_coords, normals, faces)
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    /// complete constructor
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if there is an index is out of range.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// // the buffer owned by others
+    /// let positions = [
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+    /// let slice = PolygonMeshSlice::try_new(&positions, &[], &[], &faces).unwrap();
+    /// assert_eq!(slice.positions().len(), 4);
+    ///
+    /// let faces = Faces::from_iter(&[[0, 1, 4]]);
+    /// assert!(PolygonMeshSlice::try_new(&positions, &[], &[], &faces).is_err());
+    /// ```
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    pub fn try_new(
+        positions: &'a [Point3],
+        uv_coords: &'a [Vector2],
+        normals: &'a [Vector3],
+        faces: &'a F,
+    ) -> Result<PolygonMeshSlice<'a, F>> {
+        is_compatible(faces, positions.len(), uv_coords.len(), normals.len(), 0)
+            .map(|_| PolygonMeshSlice::from_raw_parts(positions, uv_coords, normals, &[], faces))
+    }
+
+    /// Creates a view of all the arrays without boundary check.
+    #[inline(always)]
+    pub fn from_raw_parts(
+        positions: &'a [Point3],
+        uv_coords: &'a [Vector2],
+        normals: &'a [Vector3],
+        colors: &'a [Vector4],
+        faces: &'a F,
+    ) -> PolygonMeshSlice<'a, F> {
+        PolygonMeshSlice {
+            positions,
+            uv_coords,
+            normals,
+            colors,
+            faces,
+        }
+    }
+
+    /// Returns the view with the colors.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if there is an index is out of range.
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    pub fn try_with_colors(self, colors: &'a [Vector4]) -> Result<PolygonMeshSlice<'a, F>> {
+        let slice = PolygonMeshSlice { colors, ..self };
+        slice.validate().map(|_| slice)
+    }
+
+    /// Checks whether all the indices of the faces are in the ranges of the arrays.
+    /// # Errors
+    /// Returns [`Error::OutOfRange`] if there is an index is out of range.
+    ///
+    /// [`Error::OutOfRange`]: ./errors/enum.Error.html#variant.OutOfRange
+    #[inline(always)]
+    pub fn validate(&self) -> Result<()> {
+        is_compatible(
+            self.faces,
+            self.positions.len(),
+            self.uv_coords.len(),
+            self.normals.len(),
+            self.colors.len(),
+        )
+    }
+
+    /// Returns the slice of all positions.
+    #[inline(always)]
+    pub fn positions(&self) -> &'a [Point3] { self.positions }
+
+    /// Returns the slice of all uv (texture) coordinates.
+    #[inline(always)]
+    pub fn uv_coords(&self) -> &'a [Vector2] { self.uv_coords }
+
+    /// Returns the slice of all normals.
+    #[inline(always)]
+    pub fn normals(&self) -> &'a [Vector3] { self.normals }
+
+    /// Returns the slice of all colors.
+    #[inline(always)]
+    pub fn colors(&self) -> &'a [Vector4] { self.colors }
+
+    /// Returns the faces of the polygon.
+    #[inline(always)]
+    pub fn faces(&self) -> &'a F { self.faces }
+
+    /// Returns the iterator of the slice.
+    ///
+    /// The order is the one of the face storage.
+    /// cf: [`PolygonMesh::face_iter`](./struct.PolygonMesh.html#method.face_iter)
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = &'a [Vertex]> { self.faces.face_iter() }
+
+    /// Creates the bounding box of the polygon mesh.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> { self.positions.iter().collect() }
+}
+
+impl<F> Clone for PolygonMeshSlice<'_, F> {
+    #[inline(always)]
+    fn clone(&self) -> Self { *self }
+}
+
+impl<F> Copy for PolygonMeshSlice<'_, F> {}
+
+impl<'a, F: FaceStorage> From<&'a PolygonMesh<F>> for PolygonMeshSlice<'a, F> {
+    #[inline(always)]
+    fn from(mesh: &'a PolygonMesh<F>) -> PolygonMeshSlice<'a, F> { mesh.as_slice() }
+}
+
+impl<F: FaceStorage> From<PolygonMeshSlice<'_, F>> for PolygonMesh<F> {
+    /// Copies the arrays and the faces into an owned polygon mesh.
+    #[inline(always)]
+    fn from(slice: PolygonMeshSlice<'_, F>) -> PolygonMesh<F> {
+        PolygonMesh::from_raw_parts(
+            slice.positions.to_vec(),
+            slice.uv_coords.to_vec(),
+            slice.normals.to_vec(),
+            slice.colors.to_vec(),
+            slice.faces.clone(),
+        )
+    }
+}
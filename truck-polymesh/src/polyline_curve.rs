@@ -1,6 +1,7 @@
 use crate::*;
 use std::iter::FromIterator;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Index};
+use truck_base::bounding_box::Bounded;
 use truck_base::cgmath64::control_point::ControlPoint;
 
 impl<P> Deref for PolylineCurve<P> {
@@ -129,6 +130,13 @@ where
 	}
 }
 
+impl<P> RoughlyBounded<P> for PolylineCurve<P>
+where P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy
+{
+	#[inline(always)]
+	fn roughly_bounding_box(&self) -> BoundingBox<P> { self.0.iter().collect() }
+}
+
 #[test]
 fn polyline_test() {
 	let vec = vec![
@@ -0,0 +1,221 @@
+use crate::errors::Error;
+use crate::Result;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Receiver of the progress of long I/O and filters, which can cancel the work.
+///
+/// Closures `FnMut(usize, usize) -> bool` are `Progress`, and [`CancellationToken`] cancels
+/// the work from another thread, e.g. the UI thread.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::progress::Progress;
+///
+/// let mut log = Vec::new();
+/// let mut progress = |done: usize, total: usize| {
+///     log.push((done, total));
+///     done < 2
+/// };
+/// assert!(progress.report(1, 3));
+/// assert!(!progress.report(2, 3));
+/// assert_eq!(log, vec![(1, 3), (2, 3)]);
+/// ```
+///
+/// [`CancellationToken`]: ./struct.CancellationToken.html
+pub trait Progress {
+    /// Receives that `done` units of `total` units of the work are done.
+    /// Returns `false` to cancel the work.
+    ///
+    /// The unit is determined by the work, e.g. bytes of reading and faces of filters.
+    /// `total` is zero if it is unknown.
+    fn report(&mut self, done: usize, total: usize) -> bool;
+}
+
+impl<F: FnMut(usize, usize) -> bool> Progress for F {
+    #[inline(always)]
+    fn report(&mut self, done: usize, total: usize) -> bool { self(done, total) }
+}
+
+/// `None` reports to nothing and never cancels the work.
+impl<P: Progress> Progress for Option<P> {
+    #[inline(always)]
+    fn report(&mut self, done: usize, total: usize) -> bool {
+        match self {
+            Some(progress) => progress.report(done, total),
+            None => true,
+        }
+    }
+}
+
+/// The flag cancelling the work from another thread.
+///
+/// The clones share the same flag.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::progress::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let cloned = token.clone();
+/// std::thread::spawn(move || cloned.cancel()).join().unwrap();
+///
+/// let obj = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+/// let res = obj::read_with_progress(obj.as_ref(), obj.len(), &token);
+/// assert!(matches!(res, Err(errors::Error::Cancelled)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token which is not cancelled.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+    /// Cancels the works to which the token is given.
+    #[inline(always)]
+    pub fn cancel(&self) { self.0.store(true, Ordering::Relaxed) }
+    /// Returns whether the token is cancelled or not.
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool { self.0.load(Ordering::Relaxed) }
+}
+
+impl Progress for CancellationToken {
+    #[inline(always)]
+    fn report(&mut self, _: usize, _: usize) -> bool { !self.is_cancelled() }
+}
+
+impl Progress for &CancellationToken {
+    #[inline(always)]
+    fn report(&mut self, _: usize, _: usize) -> bool { !self.is_cancelled() }
+}
+
+/// Counter of the units of the work, reporting to [`Progress`] at most about a thousand times.
+///
+/// Once the work is cancelled, the counter never reports again.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::progress::ProgressCounter;
+///
+/// let mut log = Vec::new();
+/// let mut counter = ProgressCounter::new(10_000, |done, _| {
+///     log.push(done);
+///     done < 5_000
+/// });
+/// let done = (0..10_000).take_while(|_| counter.advance(1)).count();
+/// assert!(counter.is_cancelled());
+/// assert_eq!(done, 4_999);
+/// drop(counter);
+/// assert_eq!(log.len(), 500);
+/// assert_eq!(log[0], 10);
+/// ```
+///
+/// [`Progress`]: ./trait.Progress.html
+#[derive(Clone, Debug)]
+pub struct ProgressCounter<P> {
+    progress: P,
+    done: usize,
+    total: usize,
+    next: usize,
+    cancelled: bool,
+}
+
+impl<P: Progress> ProgressCounter<P> {
+    /// Creates a counter of the work of `total` units. `total` is zero if it is unknown.
+    #[inline(always)]
+    pub fn new(total: usize, progress: P) -> Self {
+        Self {
+            progress,
+            done: 0,
+            total,
+            next: usize::max(total / 1000, 1),
+            cancelled: false,
+        }
+    }
+
+    /// Advances the counter by `units`. Returns `false` if the work is cancelled.
+    pub fn advance(&mut self, units: usize) -> bool {
+        if self.cancelled {
+            return false;
+        }
+        self.done += units;
+        if self.done >= self.next || self.done >= self.total {
+            self.next = self.done + usize::max(self.total / 1000, 1);
+            self.cancelled = !self.progress.report(self.done, self.total);
+        }
+        !self.cancelled
+    }
+
+    /// Advances the counter by `units`. Returns [`Error::Cancelled`] if the work is cancelled.
+    ///
+    /// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+    #[inline(always)]
+    pub fn try_advance(&mut self, units: usize) -> Result<()> {
+        match self.advance(units) {
+            true => Ok(()),
+            false => Err(Error::Cancelled),
+        }
+    }
+
+    /// Returns the number of the done units.
+    #[inline(always)]
+    pub fn done(&self) -> usize { self.done }
+
+    /// Returns whether the work is cancelled or not.
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool { self.cancelled }
+
+    /// Returns [`Error::Cancelled`] if the work is cancelled, and `res` otherwise.
+    ///
+    /// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+    #[inline(always)]
+    pub fn check<T>(&self, res: Result<T>) -> Result<T> {
+        match self.cancelled {
+            true => Err(Error::Cancelled),
+            false => res,
+        }
+    }
+}
+
+/// Reader reporting the number of the read bytes to [`Progress`].
+///
+/// If the work is cancelled, the reading fails with the I/O error, which is converted into
+/// [`Error::Cancelled`] by [`ProgressReader::check`].
+///
+/// [`Progress`]: ./trait.Progress.html
+/// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+/// [`ProgressReader::check`]: ./struct.ProgressReader.html#method.check
+#[derive(Clone, Debug)]
+pub struct ProgressReader<R, P> {
+    reader: R,
+    counter: ProgressCounter<P>,
+}
+
+impl<R: Read, P: Progress> ProgressReader<R, P> {
+    /// Creates a reader of `len` bytes, e.g. the size of the file.
+    /// `len` is zero if it is unknown.
+    #[inline(always)]
+    pub fn new(reader: R, len: usize, progress: P) -> Self {
+        Self {
+            reader,
+            counter: ProgressCounter::new(len, progress),
+        }
+    }
+
+    /// Returns [`Error::Cancelled`] if the reading is cancelled, and `res` otherwise.
+    ///
+    /// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+    #[inline(always)]
+    pub fn check<T>(&self, res: Result<T>) -> Result<T> { self.counter.check(res) }
+}
+
+impl<R: Read, P: Progress> Read for ProgressReader<R, P> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        match len == 0 || self.counter.advance(len) {
+            true => Ok(len),
+            false => Err(std::io::Error::other("the reading is cancelled.")),
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use crate::*;
+use std::fmt::Arguments;
+use std::io::{BufWriter, Write};
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+/// Part, the mesh with its product name
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepPart<'a> {
+    /// the name of the product
+    pub name: String,
+    /// the mesh of the part
+    pub mesh: &'a PolygonMesh,
+}
+
+impl<'a> StepPart<'a> {
+    /// Creates the part named `name`.
+    #[inline(always)]
+    pub fn new(name: &str, mesh: &'a PolygonMesh) -> StepPart<'a> {
+        StepPart {
+            name: name.to_string(),
+            mesh,
+        }
+    }
+}
+
+/// Configures of STEP files
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepDescriptor {
+    /// the unit of the lengths of meshes. Default is `LengthUnit::Millimeter`.
+    pub unit: LengthUnit,
+    /// the name of the file in the header. Default is empty.
+    pub file_name: String,
+    /// the time stamp in the header, in the format of ISO 8601. Default is empty.
+    pub time_stamp: String,
+    /// the author in the header. Default is empty.
+    pub author: String,
+    /// the organization in the header. Default is empty.
+    pub organization: String,
+    /// the name of the assembly product, which is written only if there are two or more parts.
+    /// Default is `"assembly"`.
+    pub assembly_name: String,
+}
+
+impl Default for StepDescriptor {
+    #[inline(always)]
+    fn default() -> StepDescriptor {
+        StepDescriptor {
+            unit: LengthUnit::Millimeter,
+            file_name: String::new(),
+            time_stamp: String::new(),
+            author: String::new(),
+            organization: String::new(),
+            assembly_name: "assembly".to_string(),
+        }
+    }
+}
+
+/// Encodes `string` into the string literal of STEP. The non-ASCII characters are encoded
+/// in UTF-16 by the control directive `\X2\`.
+fn literal(string: &str) -> String {
+    let mut res = String::with_capacity(string.len() + 2);
+    res.push('\'');
+    let mut encoding = false;
+    for c in string.chars() {
+        let ascii = (' '..='~').contains(&c);
+        if encoding && ascii {
+            res.push_str("\\X0\\");
+        } else if !encoding && !ascii {
+            res.push_str("\\X2\\");
+        }
+        encoding = !ascii;
+        match c {
+            '\'' => res.push_str("''"),
+            '\\' => res.push_str("\\\\"),
+            _ if ascii => res.push(c),
+            _ => {
+                let mut buffer = [0; 2];
+                c.encode_utf16(&mut buffer)
+                    .iter()
+                    .for_each(|x| res.push_str(&format!("{:04X}", x)));
+            }
+        }
+    }
+    if encoding {
+        res.push_str("\\X0\\");
+    }
+    res.push('\'');
+    res
+}
+
+/// Encodes `x` into the real literal of STEP, which always has the decimal point.
+fn real(x: f64) -> String {
+    let debug = format!("{:?}", x);
+    match debug.find('e') {
+        Some(idx) => {
+            let (mantissa, exponent) = (&debug[..idx], &debug[idx + 1..]);
+            match mantissa.contains('.') {
+                true => format!("{}E{}", mantissa, exponent),
+                false => format!("{}.E{}", mantissa, exponent),
+            }
+        }
+        None => debug,
+    }
+}
+
+/// the writer of the entities with the serial ids
+struct EntityWriter<W> {
+    writer: W,
+    counter: usize,
+}
+
+impl<W: Write> EntityWriter<W> {
+    /// Writes the entity and returns its id.
+    fn entity(&mut self, args: Arguments<'_>) -> Result<usize> {
+        self.counter += 1;
+        self.writer.write_fmt(format_args!("#{}={};\n", self.counter, args))?;
+        Ok(self.counter)
+    }
+
+    /// Writes the unit of length and returns its id.
+    fn length_unit(&mut self, unit: LengthUnit) -> Result<usize> {
+        let si = |prefix| format!("(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT({},.METRE.))", prefix);
+        let (name, millimeters) = match unit {
+            LengthUnit::Micrometer => return self.entity(format_args!("{}", si(".MICRO."))),
+            LengthUnit::Millimeter => return self.entity(format_args!("{}", si(".MILLI."))),
+            LengthUnit::Centimeter => return self.entity(format_args!("{}", si(".CENTI."))),
+            LengthUnit::Meter => return self.entity(format_args!("{}", si("$"))),
+            LengthUnit::Inch => ("INCH", 25.4),
+            LengthUnit::Foot => ("FOOT", 304.8),
+        };
+        let millimeter = self.entity(format_args!("{}", si(".MILLI.")))?;
+        let measure = self.entity(format_args!(
+            "LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{})",
+            real(millimeters),
+            millimeter,
+        ))?;
+        let dimensions = self.entity(format_args!(
+            "DIMENSIONAL_EXPONENTS(1.,0.,0.,0.,0.,0.,0.)"
+        ))?;
+        self.entity(format_args!(
+            "(CONVERSION_BASED_UNIT('{}',#{}) LENGTH_UNIT() NAMED_UNIT(#{}))",
+            name, measure, dimensions,
+        ))
+    }
+
+    /// Writes the product and returns the ids of its product definition, its shape
+    /// representation and the placement in the representation.
+    fn product(&mut self, name: &str, contexts: (usize, usize, usize)) -> Result<[usize; 3]> {
+        let (product_context, definition_context, representation_context) = contexts;
+        let name = literal(name);
+        let product = self.entity(format_args!(
+            "PRODUCT({},{},'',(#{}))",
+            name, name, product_context,
+        ))?;
+        self.entity(format_args!(
+            "PRODUCT_RELATED_PRODUCT_CATEGORY('part',$,(#{}))",
+            product,
+        ))?;
+        let formation = self.entity(format_args!(
+            "PRODUCT_DEFINITION_FORMATION('','',#{})",
+            product,
+        ))?;
+        let definition = self.entity(format_args!(
+            "PRODUCT_DEFINITION('design','',#{},#{})",
+            formation, definition_context,
+        ))?;
+        let definition_shape = self.entity(format_args!(
+            "PRODUCT_DEFINITION_SHAPE('','',#{})",
+            definition,
+        ))?;
+        let placement = self.origin_placement()?;
+        let representation = self.entity(format_args!(
+            "SHAPE_REPRESENTATION({},(#{}),#{})",
+            name, placement, representation_context,
+        ))?;
+        self.entity(format_args!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+            definition_shape, representation,
+        ))?;
+        Ok([definition, representation, placement])
+    }
+
+    /// Writes the placement at the origin and returns its id.
+    fn origin_placement(&mut self) -> Result<usize> {
+        let origin = self.entity(format_args!("CARTESIAN_POINT('',(0.,0.,0.))"))?;
+        let axis = self.entity(format_args!("DIRECTION('',(0.,0.,1.))"))?;
+        let reference = self.entity(format_args!("DIRECTION('',(1.,0.,0.))"))?;
+        self.entity(format_args!(
+            "AXIS2_PLACEMENT_3D('',#{},#{},#{})",
+            origin, axis, reference,
+        ))
+    }
+
+    /// Writes the tessellated shape representation of the mesh, and relates it to
+    /// the shape representation of the product.
+    fn tessellation(
+        &mut self,
+        part: &StepPart<'_>,
+        triangles: &[[usize; 3]],
+        representation: usize,
+        context: usize,
+    ) -> Result<()> {
+        let name = literal(&part.name);
+        let positions = part.mesh.positions();
+        let coordinates = positions
+            .iter()
+            .map(|p| format!("({},{},{})", real(p[0]), real(p[1]), real(p[2])))
+            .collect::<Vec<_>>()
+            .join(",");
+        let coordinates = self.entity(format_args!(
+            "COORDINATES_LIST({},{},({}))",
+            name,
+            positions.len(),
+            coordinates,
+        ))?;
+        // The indices of STEP start from 1.
+        let triangles = triangles
+            .iter()
+            .map(|[a, b, c]| format!("({},{},{})", a + 1, b + 1, c + 1))
+            .collect::<Vec<_>>()
+            .join(",");
+        let surface_set = self.entity(format_args!(
+            "TRIANGULATED_SURFACE_SET({},#{},{},(),(),({}))",
+            name,
+            coordinates,
+            positions.len(),
+            triangles,
+        ))?;
+        let tessellated = self.entity(format_args!(
+            "TESSELLATED_SHAPE_REPRESENTATION({},(#{}),#{})",
+            name, surface_set, context,
+        ))?;
+        self.entity(format_args!(
+            "SHAPE_REPRESENTATION_RELATIONSHIP('','',#{},#{})",
+            representation, tessellated,
+        ))?;
+        Ok(())
+    }
+}
+
+/// Writes the meshes into a STEP AP242 file as the tessellated geometry.
+///
+/// Each part is written as a product whose shape is represented by a
+/// `TESSELLATED_SHAPE_REPRESENTATION` with a `TRIANGULATED_SURFACE_SET`. The faces are
+/// triangulated as fans, and the degenerate triangles are removed. Only positions are written.
+/// If there are two or more parts, they are gathered into the assembly product
+/// [`StepDescriptor::assembly_name`] at their own coordinates.
+///
+/// Writing no parts or a mesh without faces returns an error.
+///
+/// [`StepDescriptor::assembly_name`]: ./struct.StepDescriptor.html#structfield.assembly_name
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use step::{StepDescriptor, StepPart};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let square = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut bytes = Vec::new();
+/// let parts = [StepPart::new("square", &square)];
+/// step::write(&parts, &mut bytes, &StepDescriptor::default()).unwrap();
+/// let step = String::from_utf8(bytes).unwrap();
+/// assert!(step.starts_with("ISO-10303-21;\n"));
+/// assert!(step.contains("PRODUCT('square','square','',"));
+/// // the quadrangle is triangulated
+/// assert!(step.contains(",4,(),(),((1,2,3),(1,3,4)))"));
+/// ```
+pub fn write<W: Write>(parts: &[StepPart<'_>], writer: W, desc: &StepDescriptor) -> Result<()> {
+    if parts.is_empty() {
+        return Err(invalid_input("STEP files need one or more parts"));
+    }
+    let triangles = parts
+        .iter()
+        .map(|part| {
+            let triangles = part.mesh.position_triangles();
+            match triangles.is_empty() {
+                true => Err(invalid_input("STEP cannot store meshes without faces")),
+                false => Ok(triangles),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(b"ISO-10303-21;\nHEADER;\n")?;
+    writer.write_all(b"FILE_DESCRIPTION(('tessellated geometry'),'2;1');\n")?;
+    writer.write_fmt(format_args!(
+        "FILE_NAME({},{},({}),({}),'truck-polymesh','truck-polymesh','');\n",
+        literal(&desc.file_name),
+        literal(&desc.time_stamp),
+        literal(&desc.author),
+        literal(&desc.organization),
+    ))?;
+    writer.write_all(
+        b"FILE_SCHEMA(('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF \
+          { 1 0 10303 442 1 1 4 }'));\n",
+    )?;
+    writer.write_all(b"ENDSEC;\nDATA;\n")?;
+    let mut entities = EntityWriter {
+        writer,
+        counter: 0,
+    };
+    let application = entities.entity(format_args!(
+        "APPLICATION_CONTEXT('managed model based 3d engineering')"
+    ))?;
+    entities.entity(format_args!(
+        "APPLICATION_PROTOCOL_DEFINITION('international standard',\
+         'ap242_managed_model_based_3d_engineering',2014,#{})",
+        application,
+    ))?;
+    let product_context = entities.entity(format_args!(
+        "PRODUCT_CONTEXT('',#{},'mechanical')",
+        application,
+    ))?;
+    let definition_context = entities.entity(format_args!(
+        "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+        application,
+    ))?;
+    let length = entities.length_unit(desc.unit)?;
+    let angle = entities.entity(format_args!(
+        "(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.))"
+    ))?;
+    let solid_angle = entities.entity(format_args!(
+        "(NAMED_UNIT(*) SI_UNIT($,.STERADIAN.) SOLID_ANGLE_UNIT())"
+    ))?;
+    let uncertainty = entities.entity(format_args!(
+        "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{},'distance_accuracy_value','')",
+        real(TOLERANCE),
+        length,
+    ))?;
+    let context = entities.entity(format_args!(
+        "(GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{})) \
+         GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{})) REPRESENTATION_CONTEXT('',''))",
+        uncertainty, length, angle, solid_angle,
+    ))?;
+    let contexts = (product_context, definition_context, context);
+    let assembly = match parts.len() {
+        1 => None,
+        _ => Some(entities.product(&desc.assembly_name, contexts)?),
+    };
+    for (i, (part, triangles)) in parts.iter().zip(&triangles).enumerate() {
+        let [definition, representation, placement] = entities.product(&part.name, contexts)?;
+        entities.tessellation(part, triangles, representation, context)?;
+        if let Some([assembly_definition, assembly_representation, assembly_placement]) = assembly {
+            let occurrence = entities.entity(format_args!(
+                "NEXT_ASSEMBLY_USAGE_OCCURRENCE('{}',{},'',#{},#{},$)",
+                i + 1,
+                literal(&part.name),
+                assembly_definition,
+                definition,
+            ))?;
+            let occurrence_shape = entities.entity(format_args!(
+                "PRODUCT_DEFINITION_SHAPE('','',#{})",
+                occurrence,
+            ))?;
+            let transformation = entities.entity(format_args!(
+                "ITEM_DEFINED_TRANSFORMATION('','',#{},#{})",
+                placement, assembly_placement,
+            ))?;
+            let relationship = entities.entity(format_args!(
+                "(REPRESENTATION_RELATIONSHIP('','',#{},#{}) \
+                 REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#{}) \
+                 SHAPE_REPRESENTATION_RELATIONSHIP())",
+                representation, assembly_representation, transformation,
+            ))?;
+            entities.entity(format_args!(
+                "CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#{},#{})",
+                relationship, occurrence_shape,
+            ))?;
+        }
+    }
+    let mut writer = entities.writer;
+    writer.write_all(b"ENDSEC;\nEND-ISO-10303-21;\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the meshes into a STEP AP242 file whose lengths are converted from the unit of
+/// `context` into `desc.unit`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use step::{StepDescriptor, StepPart};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2]]);
+/// let triangle = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // the model is in meters, and the file is in millimeters.
+/// let context = ModelContext::new(LengthUnit::Meter, 1.0e-9);
+/// let mut bytes = Vec::new();
+/// let parts = [StepPart::new("triangle", &triangle)];
+/// step::write_with_context(&parts, &mut bytes, &StepDescriptor::default(), &context).unwrap();
+/// let step = String::from_utf8(bytes).unwrap();
+/// assert!(step.contains("(1000.0,1000.0,0.0)"));
+/// ```
+pub fn write_with_context<W: Write>(
+    parts: &[StepPart<'_>],
+    writer: W,
+    desc: &StepDescriptor,
+    context: &ModelContext,
+) -> Result<()> {
+    if desc.unit == context.length_unit {
+        return write(parts, writer, desc);
+    }
+    let scale = Matrix4::from_scale(context.scale_to(desc.unit));
+    let meshes: Vec<_> = parts.iter().map(|part| part.mesh.transformed(scale)).collect();
+    let parts: Vec<_> = parts
+        .iter()
+        .zip(&meshes)
+        .map(|(part, mesh)| StepPart::new(&part.name, mesh))
+        .collect();
+    write(&parts, writer, desc)
+}
@@ -1,7 +1,11 @@
+use crate::progress::{Progress, ProgressCounter, ProgressReader};
+use crate::validation::{FixPolicy, Validated};
 use crate::*;
 use bytemuck::{Pod, Zeroable};
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Lines, Read, Write};
+#[cfg(feature = "async")]
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 const FACESIZE: usize = std::mem::size_of::<STLFace>();
 const CHUNKSIZE: usize = FACESIZE + 2;
@@ -29,13 +33,13 @@ impl STLFace {
 #[derive(Debug)]
 pub enum STLReader<R: Read> {
     #[doc(hidden)]
-    ASCII(Lines<BufReader<R>>),
+    ASCII(Lines<BufReader<R>>, Vec<Option<String>>),
     #[doc(hidden)]
-    Binary(R, usize),
+    Binary(BufReader<R>, usize, [u8; 80]),
 }
 
 /// STL type
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum STLType {
     /// Determine stl type automatically.
     ///
@@ -56,21 +60,94 @@ impl Default for STLType {
     fn default() -> STLType { STLType::Automatic }
 }
 
+/// The convention of packing the color of a facet into the attribute byte count of binary STL.
+///
+/// The colors are 15-bit RGB, 5 bits for each channel, and are expanded to 8 bits
+/// for each channel in reading.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum STLColorFormat {
+    /// VisCAM and SolidView convention.
+    ///
+    /// Bits 0 to 4 are blue, bits 5 to 9 are green, bits 10 to 14 are red,
+    /// and bit 15 is set if the color of the facet is valid.
+    VisCAM,
+    /// Materialise Magics convention.
+    ///
+    /// Bits 0 to 4 are red, bits 5 to 9 are green, bits 10 to 14 are blue,
+    /// and bit 15 is set if the facet has the color of the whole object.
+    /// The color of the object is written in the header as `COLOR=` followed by RGBA bytes,
+    /// and the facet colors are read only if the header has the object color.
+    Materialise,
+}
+
+impl STLColorFormat {
+    fn channel_bits(self) -> [u32; 3] {
+        match self {
+            STLColorFormat::VisCAM => [10, 5, 0],
+            STLColorFormat::Materialise => [0, 5, 10],
+        }
+    }
+    fn decode(self, attribute: u16, object_color: Option<[u8; 3]>) -> Option<[u8; 3]> {
+        let valid = attribute & 0x8000 != 0;
+        match (self, valid) {
+            (STLColorFormat::VisCAM, false) => return None,
+            (STLColorFormat::Materialise, true) => return object_color,
+            _ => {}
+        }
+        let bits = self.channel_bits();
+        let channel = |i: usize| {
+            let c = ((attribute >> bits[i]) & 0x1F) as u8;
+            (c << 3) | (c >> 2)
+        };
+        Some([channel(0), channel(1), channel(2)])
+    }
+    fn encode(self, color: Option<[u8; 3]>) -> u16 {
+        let color = match (self, color) {
+            (STLColorFormat::VisCAM, None) => return 0,
+            (STLColorFormat::Materialise, None) => return 0x8000,
+            (_, Some(color)) => color,
+        };
+        let bits = self.channel_bits();
+        let packed = (0..3).fold(0, |packed, i| packed | ((color[i] as u16 >> 3) << bits[i]));
+        match self {
+            STLColorFormat::VisCAM => packed | 0x8000,
+            STLColorFormat::Materialise => packed,
+        }
+    }
+}
+
+/// The colors of the facets of STL, `None` for the facets without colors.
+pub type FacetColors = Vec<Option<[u8; 3]>>;
+
+/// Returns the object color in the header of Materialise Magics.
+fn materialise_object_color(header: &[u8; 80]) -> Option<[u8; 3]> {
+    let pos = header.windows(6).position(|window| window == b"COLOR=")?;
+    let color = header.get(pos + 6..pos + 9)?;
+    Some([color[0], color[1], color[2]])
+}
+
 impl<R: Read> STLReader<R> {
     #[inline(always)]
-    fn text_reader(reader: R) -> STLReader<R> { STLReader::ASCII(BufReader::new(reader).lines()) }
-    fn binary_reader(mut reader: R, header_judge: bool) -> Result<STLReader<R>> {
-        let mut header = [0; 5];
-        reader.read(&mut header)?;
-        if header_judge && &header == b"solid" {
-            return Ok(Self::text_reader(reader));
+    fn text_reader(reader: R) -> STLReader<R> {
+        STLReader::ASCII(BufReader::new(reader).lines(), Vec::new())
+    }
+    fn binary_reader(reader: R, header_judge: bool) -> Result<STLReader<R>> {
+        let mut reader = BufReader::new(reader);
+        let mut header = [0; 80];
+        reader.read_exact(&mut header[..5])?;
+        if header_judge && &header[..5] == b"solid" {
+            // the rest of the first line `solid name`
+            let mut lines = reader.lines();
+            let name = lines.next().transpose()?.unwrap_or_default();
+            let name = name.trim();
+            let names = vec![Some(name.to_string()).filter(|_| !name.is_empty())];
+            return Ok(STLReader::ASCII(lines, names));
         }
-        let mut header = [0; 75];
-        reader.read(&mut header)?;
+        reader.read_exact(&mut header[5..])?;
         let mut length_bytes = [0; 4];
-        reader.read(&mut length_bytes)?;
+        reader.read_exact(&mut length_bytes)?;
         let length = u32::from_le_bytes(length_bytes) as usize;
-        Ok(STLReader::Binary(reader, length))
+        Ok(STLReader::Binary(reader, length, header))
     }
     /// Creates new STL reader
     #[inline(always)]
@@ -85,17 +162,33 @@ impl<R: Read> STLReader<R> {
     #[inline(always)]
     pub fn stl_type(&self) -> STLType {
         match self {
-            STLReader::ASCII(_) => STLType::ASCII,
-            STLReader::Binary(_, _) => STLType::Binary,
+            STLReader::ASCII(..) => STLType::ASCII,
+            STLReader::Binary(..) => STLType::Binary,
         }
     }
-}
-
-impl<R: Read> Iterator for STLReader<R> {
-    type Item = Result<STLFace>;
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the 80 bytes header of binary STL. Returns `None` for ASCII STL.
+    #[inline(always)]
+    pub fn header(&self) -> Option<&[u8; 80]> {
+        match self {
+            STLReader::ASCII(..) => None,
+            STLReader::Binary(_, _, header) => Some(header),
+        }
+    }
+    /// Returns the names of the solids begun by `solid` in ASCII STL so far, whose last one is
+    /// the solid of the last read face. The name is `None` if the line is only `solid`.
+    /// Returns the empty slice for binary STL.
+    #[inline(always)]
+    pub fn solid_names(&self) -> &[Option<String>] {
+        match self {
+            STLReader::ASCII(_, names) => names,
+            STLReader::Binary(..) => &[],
+        }
+    }
+    /// Reads the next face with its attribute byte count.
+    /// The attribute byte count of ASCII STL is always zero.
+    pub fn next_with_attribute(&mut self) -> Option<Result<(STLFace, u16)>> {
         let res = match self {
-            STLReader::Binary(reader, length) => {
+            STLReader::Binary(reader, length, _) => {
                 if *length == 0 {
                     Ok(None)
                 } else {
@@ -103,7 +196,9 @@ impl<R: Read> Iterator for STLReader<R> {
                     binary_one_read(reader)
                 }
             }
-            STLReader::ASCII(lines) => ascii_one_read(lines),
+            STLReader::ASCII(lines, names) => {
+                ascii_one_read(lines, names).map(|face| face.map(|f| (f, 0)))
+            }
         };
         match res {
             Ok(Some(got)) => Some(Ok(got)),
@@ -113,7 +208,25 @@ impl<R: Read> Iterator for STLReader<R> {
     }
 }
 
-fn ascii_one_read<R: BufRead>(lines: &mut Lines<R>) -> Result<Option<STLFace>> {
+impl<R: Read> Iterator for STLReader<R> {
+    type Item = Result<STLFace>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_attribute().map(|res| res.map(|(face, _)| face))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            STLReader::Binary(_, length, _) => (*length, Some(*length)),
+            STLReader::ASCII(..) => (0, None),
+        }
+    }
+}
+
+/// Reads the next face, pushing the names of the solids begun before the face to `names`.
+fn ascii_one_read<R: BufRead>(
+    lines: &mut Lines<R>,
+    names: &mut Vec<Option<String>>,
+) -> Result<Option<STLFace>> {
     let mut face = STLFace::default();
     let mut num_ver = 0;
     loop {
@@ -125,7 +238,10 @@ fn ascii_one_read<R: BufRead>(lines: &mut Lines<R>) -> Result<Option<STLFace>> {
             },
         };
         let line = line.trim();
-        if line.len() < 8 {
+        if line.split_whitespace().next() == Some("solid") {
+            let name = line["solid".len()..].trim();
+            names.push(Some(name.to_string()).filter(|_| !name.is_empty()));
+        } else if line.len() < 8 {
             continue;
         } else if &line[0..5] == "facet" {
             let args: Vec<_> = line.split_whitespace().collect();
@@ -154,15 +270,19 @@ fn ascii_one_read<R: BufRead>(lines: &mut Lines<R>) -> Result<Option<STLFace>> {
     }
 }
 
-fn binary_one_read<R: Read>(reader: &mut R) -> Result<Option<STLFace>> {
+fn binary_one_read<R: Read>(reader: &mut R) -> Result<Option<(STLFace, u16)>> {
     let mut chunk = [0; CHUNKSIZE];
-    let size = reader.read(&mut chunk)?;
-    if size == CHUNKSIZE {
-        let mut buf = [0; FACESIZE];
-        buf.copy_from_slice(&chunk[..FACESIZE]);
-        Ok(Some(bytemuck::cast(buf)))
-    } else {
-        Err(syntax_error().into())
+    match reader.read_exact(&mut chunk) {
+        Ok(()) => {
+            let mut buf = [0; FACESIZE];
+            buf.copy_from_slice(&chunk[..FACESIZE]);
+            let attribute = u16::from_le_bytes([chunk[FACESIZE], chunk[FACESIZE + 1]]);
+            Ok(Some((bytemuck::cast(buf), attribute)))
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => {
+            Err(syntax_error().into())
+        }
+        Err(error) => Err(error.into()),
     }
 }
 
@@ -177,47 +297,316 @@ pub fn write<I: IntoSTLIterator, W: Write>(
 ) -> Result<()> {
     match stl_type {
         STLType::ASCII => write_ascii(iter, writer),
-        _ => write_binary(iter, writer),
+        _ => write_binary(iter, writer, &[0u8; 80], std::iter::repeat(0)),
     }
 }
 
+/// Options of [`write_with_options`](./fn.write_with_options.html)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct STLWriteOptions {
+    /// the format, where `STLType::Automatic` is binary. Default is `STLType::Binary`.
+    pub stl_type: STLType,
+    /// the name of the solid in ASCII STL, or the text at the front of the 80 bytes header
+    /// of binary STL, which is cut at 80 bytes. Default is `None`.
+    ///
+    /// The header of binary STL should not begin with `solid`, or the readers take the file
+    /// for ASCII STL.
+    pub name: Option<String>,
+    /// the scale multiplied to the coordinates, e.g. `1000.0` for writing the mesh in meters
+    /// into the file in millimeters. Default is `1.0`.
+    pub scale: f64,
+}
+
+impl Default for STLWriteOptions {
+    #[inline(always)]
+    fn default() -> STLWriteOptions {
+        STLWriteOptions {
+            stl_type: STLType::Binary,
+            name: None,
+            scale: 1.0,
+        }
+    }
+}
+
+/// Writes STL file with the options of the format, the name and the scale.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use stl::{STLFace, STLType, STLWriteOptions};
+/// let face = STLFace {
+///     normal: [0.0, 0.0, 1.0],
+///     vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+/// };
+///
+/// // from meters to millimeters
+/// let options = STLWriteOptions {
+///     stl_type: STLType::ASCII,
+///     name: Some("bracket".to_string()),
+///     scale: 1000.0,
+/// };
+/// let mut bytes = Vec::new();
+/// stl::write_with_options(vec![face], &mut bytes, &options).unwrap();
+/// let ascii = String::from_utf8(bytes).unwrap();
+/// assert!(ascii.starts_with("solid bracket\n"));
+/// assert!(ascii.contains("vertex 1e3 0e0 0e0"));
+/// assert!(ascii.ends_with("endsolid bracket\n"));
+///
+/// let options = STLWriteOptions {
+///     name: Some("exported by truck".to_string()),
+///     ..Default::default()
+/// };
+/// let mut bytes = Vec::new();
+/// stl::write_with_options(vec![face], &mut bytes, &options).unwrap();
+/// let reader = stl::read_iter(bytes.as_slice(), STLType::Automatic).unwrap();
+/// assert!(reader.header().unwrap().starts_with(b"exported by truck\0"));
+/// ```
+pub fn write_with_options<I: IntoSTLIterator, W: Write>(
+    iter: I,
+    writer: &mut W,
+    options: &STLWriteOptions,
+) -> Result<()> {
+    let scale = options.scale as f32;
+    let iter = iter.into_iter().map(move |mut face| {
+        face.vertices
+            .iter_mut()
+            .flatten()
+            .for_each(|x| *x *= scale);
+        face
+    });
+    match options.stl_type {
+        STLType::ASCII => write_ascii_solid(options.name.as_deref(), iter, writer),
+        _ => {
+            let mut header = [0u8; 80];
+            if let Some(name) = &options.name {
+                let len = usize::min(name.len(), 80);
+                header[..len].copy_from_slice(&name.as_bytes()[..len]);
+            }
+            write_binary(iter, writer, &header, std::iter::repeat(0))
+        }
+    }
+}
+
+/// Writes the named solids into STL file in `stl_type` format.
+///
+/// In ASCII STL, each solid is written in the block between `solid name` and `endsolid name`,
+/// where the name is omitted for `None`. Binary STL has no solids, and all the faces are
+/// written as one solid without the names.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use stl::{STLFace, STLType};
+/// let face = STLFace {
+///     normal: [0.0, 0.0, 1.0],
+///     vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+/// };
+/// let solids = vec![(Some("bracket"), vec![face; 2]), (None, vec![face])];
+/// let mut bytes = Vec::new();
+/// stl::write_solids(solids, &mut bytes, STLType::ASCII).unwrap();
+///
+/// let solids = stl::read_solids(bytes.as_slice(), STLType::Automatic).unwrap();
+/// assert_eq!(solids.len(), 2);
+/// assert_eq!(solids[0].0.as_deref(), Some("bracket"));
+/// assert_eq!(solids[0].1.faces().len(), 2);
+/// assert_eq!(solids[1].0, None);
+/// assert_eq!(solids[1].1.faces().len(), 1);
+/// ```
+pub fn write_solids<S, I, J, W>(solids: J, writer: &mut W, stl_type: STLType) -> Result<()>
+where
+    S: AsRef<str>,
+    I: IntoSTLIterator,
+    J: IntoIterator<Item = (Option<S>, I)>,
+    W: Write,
+{
+    match stl_type {
+        STLType::ASCII => solids.into_iter().try_for_each(|(name, iter)| {
+            write_ascii_solid(name.as_ref().map(AsRef::as_ref), iter, writer)
+        }),
+        _ => {
+            let faces: Vec<STLFace> = solids
+                .into_iter()
+                .flat_map(|(_, iter)| iter.into_iter())
+                .collect();
+            write_binary(faces, writer, &[0u8; 80], std::iter::repeat(0))
+        }
+    }
+}
+
+/// Writes STL file in `stl_type` format, reporting the number of the written faces to `progress`.
+///
+/// If the writing is cancelled, returns [`Error::Cancelled`] and the output is incomplete.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use stl::{STLFace, STLType};
+/// let face = STLFace {
+///     normal: [0.0, 0.0, 1.0],
+///     vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+/// };
+/// let mut log = Vec::new();
+/// let progress = |done, total| {
+///     log.push((done, total));
+///     true
+/// };
+/// let mut bytes = Vec::new();
+/// stl::write_with_progress(vec![face; 3], &mut bytes, STLType::Binary, progress).unwrap();
+/// assert_eq!(log, vec![(1, 3), (2, 3), (3, 3)]);
+///
+/// let progress = |done, _| done < 2;
+/// let res = stl::write_with_progress(vec![face; 3], &mut Vec::new(), STLType::ASCII, progress);
+/// assert!(matches!(res, Err(errors::Error::Cancelled)));
+/// ```
+///
+/// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+pub fn write_with_progress<I: IntoSTLIterator, W: Write, P: Progress>(
+    iter: I,
+    writer: &mut W,
+    stl_type: STLType,
+    progress: P,
+) -> Result<()> {
+    let iter = iter.into_iter();
+    let mut counter = ProgressCounter::new(iter.len(), progress);
+    let iter = ProgressFaces {
+        iter,
+        counter: &mut counter,
+    };
+    let res = write(iter, writer, stl_type);
+    counter.check(res)
+}
+
+/// the faces which end when the writing is cancelled
+struct ProgressFaces<'a, I, P> {
+    iter: I,
+    counter: &'a mut ProgressCounter<P>,
+}
+
+impl<I: ExactSizeIterator<Item = STLFace>, P: Progress> Iterator for ProgressFaces<'_, I, P> {
+    type Item = STLFace;
+    fn next(&mut self) -> Option<STLFace> {
+        match self.counter.is_cancelled() {
+            true => None,
+            false => {
+                let face = self.iter.next()?;
+                self.counter.advance(1);
+                Some(face)
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.counter.is_cancelled() {
+            true => (0, Some(0)),
+            false => self.iter.size_hint(),
+        }
+    }
+}
+
+impl<I: ExactSizeIterator<Item = STLFace>, P: Progress> ExactSizeIterator
+    for ProgressFaces<'_, I, P>
+{
+}
+
 /// Writes ASCII STL data
+#[inline(always)]
 fn write_ascii<I: IntoSTLIterator, W: Write>(iter: I, writer: &mut W) -> Result<()> {
+    write_ascii_solid(None, iter, writer)
+}
+
+/// Writes the solid named `name` of ASCII STL data
+fn write_ascii_solid<I: IntoSTLIterator, W: Write>(
+    name: Option<&str>,
+    iter: I,
+    writer: &mut W,
+) -> Result<()> {
     let mut iter = iter.into_iter();
-    writer.write(b"solid\n")?;
+    match name {
+        Some(name) => writer.write_fmt(format_args!("solid {}\n", name))?,
+        None => writer.write_all(b"solid\n")?,
+    }
     iter.try_for_each::<_, Result<()>>(|face| {
         writer.write_fmt(format_args!(
             "  facet normal {:e} {:e} {:e}\n",
             face.normal[0], face.normal[1], face.normal[2]
         ))?;
-        writer.write(b"    outer loop\n")?;
+        writer.write_all(b"    outer loop\n")?;
         face.vertices.iter().try_for_each(|pt| {
             writer.write_fmt(format_args!(
                 "      vertex {:e} {:e} {:e}\n",
                 pt[0], pt[1], pt[2]
             ))
         })?;
-        writer.write(b"    endloop\n  endfacet\n")?;
+        writer.write_all(b"    endloop\n  endfacet\n")?;
         Ok(())
     })?;
-    writer.write(b"endsolid\n")?;
+    match name {
+        Some(name) => writer.write_fmt(format_args!("endsolid {}\n", name))?,
+        None => writer.write_all(b"endsolid\n")?,
+    }
     Ok(())
 }
 
 /// Writes binary STL data
-#[inline(always)]
-fn write_binary<I: IntoSTLIterator, W: Write>(iter: I, writer: &mut W) -> Result<()> {
+fn write_binary<I, W, A>(iter: I, writer: &mut W, header: &[u8; 80], attributes: A) -> Result<()>
+where
+    I: IntoSTLIterator,
+    W: Write,
+    A: Iterator<Item = u16>,
+{
     let mut iter = iter.into_iter();
     let len = iter.len() as u32;
-    writer.write(&[0u8; 80])?;
-    writer.write(&len.to_le_bytes())?;
+    writer.write_all(header)?;
+    writer.write_all(&len.to_le_bytes())?;
+    let mut attributes = attributes.chain(std::iter::repeat(0));
     iter.try_for_each(|face| {
-        writer.write(bytemuck::cast_slice(&[face]))?;
-        writer.write(&[0u8, 0u8])?;
+        writer.write_all(bytemuck::cast_slice(&[face]))?;
+        writer.write_all(&attributes.next().unwrap_or(0).to_le_bytes())?;
         Ok(())
     })
 }
 
+/// Writes binary STL with the colors of facets packed into the attribute byte counts
+/// in the convention `format`.
+///
+/// The `i`th color is the color of the `i`th facet, and `None` means the facet has no color.
+/// The facets after the end of `colors` have no color. In [`STLColorFormat::Materialise`],
+/// the header has the object color white, which is the color of the facets without colors.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use stl::{STLColorFormat, STLFace, STLType};
+/// let face = STLFace {
+///     normal: [0.0, 0.0, 1.0],
+///     vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+/// };
+/// let colors = [Some([255, 0, 0]), None];
+/// let mut bytes = Vec::new();
+/// stl::write_with_colors(vec![face, face], &mut bytes, colors, STLColorFormat::VisCAM).unwrap();
+///
+/// let format = STLColorFormat::VisCAM;
+/// let (mesh, colors) = stl::read_with_colors(bytes.as_slice(), STLType::Automatic, format)
+///     .unwrap();
+/// assert_eq!(mesh.faces().len(), 2);
+/// assert_eq!(colors, Some(vec![Some([255, 0, 0]), None]));
+/// ```
+///
+/// [`STLColorFormat::Materialise`]: ./enum.STLColorFormat.html#variant.Materialise
+pub fn write_with_colors<I, W, C>(
+    iter: I,
+    writer: &mut W,
+    colors: C,
+    format: STLColorFormat,
+) -> Result<()>
+where
+    I: IntoSTLIterator,
+    W: Write,
+    C: IntoIterator<Item = Option<[u8; 3]>>,
+{
+    let mut header = [0u8; 80];
+    if format == STLColorFormat::Materialise {
+        header[..10].copy_from_slice(b"COLOR=\xFF\xFF\xFF\xFF");
+    }
+    let attributes = colors.into_iter().map(|color| format.encode(color));
+    write_binary(iter, writer, &header, attributes)
+}
+
 /// By implementing `IntoSTLIterator` for a type, you define how it will be converted to an iterator.
 /// This is common for types which describe a collection of some kind.
 pub trait IntoSTLIterator {
@@ -288,7 +677,7 @@ impl<'a> ExactSizeIterator for PolygonMeshSTLFaceIterator<'a> {}
 impl<'a> IntoSTLIterator for &'a PolygonMesh {
     type IntoIter = PolygonMeshSTLFaceIterator<'a>;
     fn into_iter(self) -> Self::IntoIter {
-        let len = self.face_iter().fold(0, |len, face| len + face.len());
+        let len = self.face_iter().fold(0, |len, face| len + face.len() - 2);
         Self::IntoIter {
             positions: self.positions(),
             tri_faces: self.tri_faces().iter(),
@@ -310,73 +699,442 @@ where
     fn into_iter(self) -> I::IntoIter { self.into_iter() }
 }
 
-fn signup_vector(vector: [f32; 3], map: &mut HashMap<[i64; 3], usize>) -> usize {
+/// Builder of `PolygonMesh` from STL faces, putting together the same positions and normals
+/// incrementally.
+///
+/// The faces are pushed one by one, so a mesh can be built from the lazy iterator
+/// [`read_iter`] without keeping all the STL faces in memory.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use stl::{STLFace, STLMeshBuilder, STLType};
+/// let faces = vec![
+///     STLFace {
+///         normal: [0.0, 0.0, 1.0],
+///         vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+///     },
+///     STLFace {
+///         normal: [0.0, 0.0, 1.0],
+///         vertices: [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+///     },
+/// ];
+/// let mut bytes = Vec::new();
+/// stl::write(faces, &mut bytes, STLType::Binary).unwrap();
+///
+/// let mut builder = STLMeshBuilder::new();
+/// for face in stl::read_iter(bytes.as_slice(), STLType::Automatic).unwrap() {
+///     builder.push(face.unwrap());
+/// }
+/// let mesh = builder.build();
+/// assert_eq!(mesh.positions().len(), 4);
+/// assert_eq!(mesh.normals().len(), 1);
+/// assert_eq!(mesh.faces().len(), 2);
+/// ```
+///
+/// [`read_iter`]: ./fn.read_iter.html
+#[derive(Clone, Debug, Default)]
+pub struct STLMeshBuilder {
+    position_map: HashMap<[i64; 3], usize>,
+    normal_map: HashMap<[i64; 3], usize>,
+    positions: Vec<Point3>,
+    normals: Vec<Vector3>,
+    faces: Faces,
+}
+
+impl STLMeshBuilder {
+    /// Creates an empty builder.
+    #[inline(always)]
+    pub fn new() -> STLMeshBuilder { STLMeshBuilder::default() }
+    /// Reserves the capacity for at least `additional` more faces.
+    ///
+    /// The reservation is only a hint: nothing is reserved if the memory cannot be allocated,
+    /// e.g. by the broken number of the faces in the header of a binary STL.
+    #[inline(always)]
+    pub fn reserve(&mut self, additional: usize) {
+        let _ = self.faces.tri_faces.try_reserve(additional);
+    }
+    /// Pushes a face.
+    pub fn push(&mut self, face: STLFace) {
+        let n = signup_vector(face.normal, &mut self.normal_map, &mut self.normals);
+        let (map, positions) = (&mut self.position_map, &mut self.positions);
+        let mut signup = |vector| signup_vector(vector, map, positions);
+        let p = [
+            signup(face.vertices[0]),
+            signup(face.vertices[1]),
+            signup(face.vertices[2]),
+        ];
+        self.faces.push([
+            (p[0], None, Some(n)),
+            (p[1], None, Some(n)),
+            (p[2], None, Some(n)),
+        ]);
+    }
+    /// Builds the mesh of the pushed faces.
+    #[inline(always)]
+    pub fn build(self) -> PolygonMesh {
+        PolygonMesh::debug_new(self.positions, Vec::new(), self.normals, self.faces)
+    }
+    /// Builds the mesh of the pushed faces, with validating the coordinates and the faces,
+    /// and fixes the problems by `policy`. The indices of the faces in the report are
+    /// in the order of pushing.
+    ///
+    /// The vertices closer than the tolerance are put together in pushing, so the facets
+    /// with such vertices are reported as the degenerate faces.
+    pub fn build_validated(self, policy: FixPolicy) -> Validated<PolygonMesh> {
+        let faces = self.faces.face_iter().map(<[Vertex]>::to_vec).collect();
+        PolygonMesh::validate_raw_parts(
+            self.positions,
+            Vec::new(),
+            self.normals,
+            Vec::new(),
+            faces,
+            policy,
+        )
+    }
+}
+
+impl Extend<STLFace> for STLMeshBuilder {
+    fn extend<I: IntoIterator<Item = STLFace>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|face| self.push(face))
+    }
+}
+
+fn signup_vector<T: From<[f64; 3]>>(
+    vector: [f32; 3],
+    map: &mut HashMap<[i64; 3], usize>,
+    list: &mut Vec<T>,
+) -> usize {
+    // the non-finite vectors are kept as they are, for the validation
+    if !vector.iter().all(|x| x.is_finite()) {
+        list.push(T::from([vector[0] as f64, vector[1] as f64, vector[2] as f64]));
+        return list.len() - 1;
+    }
     let vector = [
         ((vector[0] as f64 + TOLERANCE * 0.25) / (TOLERANCE * 0.5)) as i64,
         ((vector[1] as f64 + TOLERANCE * 0.25) / (TOLERANCE * 0.5)) as i64,
         ((vector[2] as f64 + TOLERANCE * 0.25) / (TOLERANCE * 0.5)) as i64,
     ];
-    match map.get(&vector) {
-        Some(res) => *res,
-        None => {
-            let res = map.len();
-            map.insert(vector, res);
-            res
-        }
-    }
+    *map.entry(vector).or_insert_with(|| {
+        list.push(T::from([
+            vector[0] as f64 * TOLERANCE * 0.5,
+            vector[1] as f64 * TOLERANCE * 0.5,
+            vector[2] as f64 * TOLERANCE * 0.5,
+        ]));
+        list.len() - 1
+    })
 }
 
 impl std::iter::FromIterator<STLFace> for PolygonMesh {
     fn from_iter<I: IntoIterator<Item = STLFace>>(iter: I) -> PolygonMesh {
-        let mut positions = HashMap::<[i64; 3], usize>::new();
-        let mut normals = HashMap::<[i64; 3], usize>::new();
-        let faces: Vec<[Vertex; 3]> = iter
-            .into_iter()
-            .map(|face| {
-                let n = signup_vector(face.normal, &mut normals);
-                let p = [
-                    signup_vector(face.vertices[0], &mut positions),
-                    signup_vector(face.vertices[1], &mut positions),
-                    signup_vector(face.vertices[2], &mut positions),
-                ];
-                [
-                    (p[0], None, Some(n)).into(),
-                    (p[1], None, Some(n)).into(),
-                    (p[2], None, Some(n)).into(),
-                ]
-            })
-            .collect();
-        let faces = Faces::from_tri_and_quad_faces(faces, Vec::new());
-        let mut positions: Vec<([i64; 3], usize)> = positions.into_iter().collect();
-        positions.sort_by(|a, b| a.1.cmp(&b.1));
-        let positions: Vec<Point3> = positions
-            .into_iter()
-            .map(|(p, _)| {
-                Point3::new(
-                    p[0] as f64 * TOLERANCE * 0.5,
-                    p[1] as f64 * TOLERANCE * 0.5,
-                    p[2] as f64 * TOLERANCE * 0.5,
-                )
-            })
-            .collect();
-        let mut normals: Vec<([i64; 3], usize)> = normals.into_iter().collect();
-        normals.sort_by(|a, b| a.1.cmp(&b.1));
-        let normals: Vec<Vector3> = normals
-            .into_iter()
-            .map(|(p, _)| {
-                Vector3::new(
-                    p[0] as f64 * TOLERANCE * 0.5,
-                    p[1] as f64 * TOLERANCE * 0.5,
-                    p[2] as f64 * TOLERANCE * 0.5,
-                )
-            })
-            .collect();
-        PolygonMesh::debug_new(positions, Vec::new(), normals, faces)
+        let iter = iter.into_iter();
+        let mut builder = STLMeshBuilder::new();
+        builder.reserve(iter.size_hint().0);
+        builder.extend(iter);
+        builder.build()
     }
 }
 
 /// Read STL file and parse to `PolygonMesh`.
-#[inline(always)]
+///
+/// The faces are read one by one and put together into the mesh by [`STLMeshBuilder`],
+/// so the whole STL faces are never kept in memory.
+///
+/// [`STLMeshBuilder`]: ./struct.STLMeshBuilder.html
 pub fn read<R: Read>(reader: R, stl_type: STLType) -> Result<PolygonMesh> {
-    STLReader::new(reader, stl_type)?.collect()
+    let iter = read_iter(reader, stl_type)?;
+    let mut builder = STLMeshBuilder::new();
+    builder.reserve(iter.size_hint().0);
+    for face in iter {
+        builder.push(face?);
+    }
+    Ok(builder.build())
+}
+
+/// Reads STL file and parse to the meshes of the solids with their names.
+///
+/// ASCII STL may have several blocks between `solid name` and `endsolid name`, and each block
+/// is read into a mesh with the name, which is `None` if the line is only `solid`. The faces
+/// out of the blocks are in the solid before them, or in the solid without the name at the
+/// front. Binary STL is read as one solid without the name. See [`write_solids`] for an
+/// example.
+///
+/// [`write_solids`]: ./fn.write_solids.html
+pub fn read_solids<R: Read>(
+    reader: R,
+    stl_type: STLType,
+) -> Result<Vec<(Option<String>, PolygonMesh)>> {
+    let mut iter = read_iter(reader, stl_type)?;
+    let mut solids: Vec<(Option<String>, STLMeshBuilder)> = Vec::new();
+    if iter.stl_type() == STLType::Binary {
+        solids.push((None, STLMeshBuilder::new()));
+        solids[0].1.reserve(iter.size_hint().0);
+    }
+    let mut begun = 0;
+    loop {
+        let face = iter.next().transpose()?;
+        let names = iter.solid_names();
+        solids.extend(names[begun..].iter().map(|name| (name.clone(), STLMeshBuilder::new())));
+        begun = names.len();
+        let face = match face {
+            Some(face) => face,
+            None => break,
+        };
+        if solids.is_empty() {
+            solids.push((None, STLMeshBuilder::new()));
+        }
+        solids.last_mut().unwrap().1.push(face);
+    }
+    Ok(solids
+        .into_iter()
+        .map(|(name, builder)| (name, builder.build()))
+        .collect())
+}
+
+/// Reads STL file and parse to `PolygonMesh`, reporting the number of the read bytes of
+/// `len` bytes to `progress`, e.g. `len` is the size of the file.
+///
+/// If the reading is cancelled, returns [`Error::Cancelled`].
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::progress::CancellationToken;
+/// use stl::{STLFace, STLType};
+/// let face = STLFace {
+///     normal: [0.0, 0.0, 1.0],
+///     vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+/// };
+/// let mut bytes = Vec::new();
+/// stl::write(vec![face; 10], &mut bytes, STLType::Binary).unwrap();
+///
+/// let mut read = 0;
+/// let progress = |done, _| {
+///     read = done;
+///     true
+/// };
+/// let mesh = stl::read_with_progress(bytes.as_slice(), STLType::Automatic, bytes.len(), progress);
+/// assert_eq!(mesh.unwrap().faces().len(), 10);
+/// assert_eq!(read, bytes.len());
+///
+/// let token = CancellationToken::new();
+/// token.cancel();
+/// let res = stl::read_with_progress(bytes.as_slice(), STLType::Automatic, bytes.len(), &token);
+/// assert!(matches!(res, Err(errors::Error::Cancelled)));
+/// ```
+///
+/// [`Error::Cancelled`]: ../errors/enum.Error.html#variant.Cancelled
+pub fn read_with_progress<R: Read, P: Progress>(
+    reader: R,
+    stl_type: STLType,
+    len: usize,
+    progress: P,
+) -> Result<PolygonMesh> {
+    let mut reader = ProgressReader::new(reader, len, progress);
+    let res = read(&mut reader, stl_type);
+    reader.check(res)
+}
+
+/// Reads STL file and parse to `PolygonMesh`, with validating the coordinates and the faces
+/// by [`STLMeshBuilder::build_validated`], and fixes the problems by `policy`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use truck_polymesh::validation::FixPolicy;
+/// let stl = b"solid broken
+/// facet normal 0 0 1
+/// outer loop
+/// vertex 0 0 0
+/// vertex 1 0 0
+/// vertex 0 1 0
+/// endloop
+/// endfacet
+/// facet normal 0 0 1
+/// outer loop
+/// vertex 0 0 0
+/// vertex 1 0 0
+/// vertex 2 0 0
+/// endloop
+/// endfacet
+/// facet normal 0 0 1
+/// outer loop
+/// vertex 0 0 0
+/// vertex 1 0 0
+/// vertex 0 NaN 0
+/// endloop
+/// endfacet
+/// endsolid broken
+/// ";
+/// let validated = stl::read_validated(stl.as_ref(), stl::STLType::ASCII, FixPolicy::Drop);
+/// let validated = validated.unwrap();
+/// assert_eq!(validated.value.faces().len(), 1);
+/// assert_eq!(validated.report.degenerate_face_count(), 1);
+/// assert_eq!(validated.report.non_finite_count(), 1);
+/// assert_eq!(validated.report.dropped_faces, vec![1, 2]);
+/// assert!(validated.value.positions().iter().all(|p| p.to_vec().is_finite()));
+/// ```
+///
+/// [`STLMeshBuilder::build_validated`]: ./struct.STLMeshBuilder.html#method.build_validated
+pub fn read_validated<R: Read>(
+    reader: R,
+    stl_type: STLType,
+    policy: FixPolicy,
+) -> Result<Validated<PolygonMesh>> {
+    let iter = read_iter(reader, stl_type)?;
+    let mut builder = STLMeshBuilder::new();
+    builder.reserve(iter.size_hint().0);
+    for face in iter {
+        builder.push(face?);
+    }
+    Ok(builder.build_validated(policy))
+}
+
+/// Reads STL file and parse to `PolygonMesh` with the colors of facets packed into
+/// the attribute byte counts in the convention `format`.
+///
+/// The `i`th color is the color of the `i`th triangle in `mesh.tri_faces()`, and `None` means
+/// the facet has no color. The colors are `None` if no facet has a color, e.g. ASCII STL.
+/// See [`write_with_colors`] for an example.
+///
+/// [`write_with_colors`]: ./fn.write_with_colors.html
+pub fn read_with_colors<R: Read>(
+    reader: R,
+    stl_type: STLType,
+    format: STLColorFormat,
+) -> Result<(PolygonMesh, Option<FacetColors>)> {
+    let mut iter = read_iter(reader, stl_type)?;
+    let object_color = iter.header().and_then(materialise_object_color);
+    let colored = match format {
+        STLColorFormat::VisCAM => iter.header().is_some(),
+        STLColorFormat::Materialise => object_color.is_some(),
+    };
+    let mut builder = STLMeshBuilder::new();
+    builder.reserve(iter.size_hint().0);
+    let mut colors = Vec::new();
+    while let Some(res) = iter.next_with_attribute() {
+        let (face, attribute) = res?;
+        builder.push(face);
+        if colored {
+            colors.push(format.decode(attribute, object_color));
+        }
+    }
+    let colors = match colors.iter().any(Option::is_some) {
+        true => Some(colors),
+        false => None,
+    };
+    Ok((builder.build(), colors))
+}
+
+/// Reads STL faces lazily from `reader`. The returned iterator reads one face at each step,
+/// for processing huge files without loading them at once.
+///
+/// This is the same as [`STLReader::new`], and the faces can be put together into a mesh by
+/// [`STLMeshBuilder`].
+///
+/// [`STLReader::new`]: ./enum.STLReader.html#method.new
+/// [`STLMeshBuilder`]: ./struct.STLMeshBuilder.html
+#[inline(always)]
+pub fn read_iter<R: Read>(reader: R, stl_type: STLType) -> Result<STLReader<R>> {
+    STLReader::new(reader, stl_type)
+}
+
+/// Reads STL file whose lengths are in `file_unit`, and converts the lengths into the unit of `context`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let mesh = PolygonMesh::new(
+///     vec![
+///         Point3::new(0.0, 0.0, 0.0),
+///         Point3::new(1.0, 0.0, 0.0),
+///         Point3::new(0.0, 1.0, 0.0),
+///     ],
+///     Vec::new(),
+///     Vec::new(),
+///     Faces::from_iter(&[[0, 1, 2]]),
+/// );
+/// // The model is in meters, but the file is written in millimeters.
+/// let context = ModelContext::new(LengthUnit::Meter, 1.0e-9);
+/// let mut bytes = Vec::new();
+/// let file_unit = LengthUnit::Millimeter;
+/// stl::write_with_context(&mesh, &mut bytes, stl::STLType::ASCII, file_unit, &context).unwrap();
+/// assert!(String::from_utf8(bytes.clone()).unwrap().contains("vertex 1e3 0e0 0e0"));
+///
+/// let stl_type = stl::STLType::Automatic;
+/// let mesh0 = stl::read_with_context(bytes.as_slice(), stl_type, file_unit, &context).unwrap();
+/// assert_eq!(mesh0.bounding_box(), mesh.bounding_box());
+/// ```
+pub fn read_with_context<R: Read>(
+    reader: R,
+    stl_type: STLType,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<PolygonMesh> {
+    let mut mesh = read(reader, stl_type)?;
+    if file_unit != context.length_unit {
+        mesh.transform_by(Matrix4::from_scale(context.scale_from(file_unit)));
+    }
+    Ok(mesh)
+}
+
+/// Writes STL file whose lengths are converted from the unit of `context` into `file_unit`.
+pub fn write_with_context<I: IntoSTLIterator, W: Write>(
+    iter: I,
+    writer: &mut W,
+    stl_type: STLType,
+    file_unit: LengthUnit,
+    context: &ModelContext,
+) -> Result<()> {
+    let scale = context.scale_to(file_unit) as f32;
+    let iter = iter.into_iter().map(move |mut face| {
+        face.vertices
+            .iter_mut()
+            .flatten()
+            .for_each(|x| *x *= scale);
+        face
+    });
+    write(iter, writer, stl_type)
+}
+
+/// Reads STL file asynchronously and parse to `PolygonMesh`.
+///
+/// The whole data is read into memory before parsing, so the reader never blocks the runtime.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use stl::{STLFace, STLType};
+/// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # runtime.block_on(async {
+/// let face = STLFace {
+///     normal: [0.0, 0.0, 1.0],
+///     vertices: [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+/// };
+/// let mut bytes = Vec::new();
+/// stl::write_async(vec![face; 2], &mut bytes, STLType::ASCII).await.unwrap();
+/// let mesh = stl::read_async(bytes.as_slice(), STLType::Automatic).await.unwrap();
+/// assert_eq!(mesh.faces().len(), 2);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn read_async<R: AsyncRead + Unpin>(
+    mut reader: R,
+    stl_type: STLType,
+) -> Result<PolygonMesh> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    read(bytes.as_slice(), stl_type)
+}
+
+/// Writes STL file asynchronously in `stl_type` format.
+///
+/// If `stl_type == STLType::Automatic`, write the binary format.
+/// The whole data is written into memory before sending, so the writer never blocks the runtime.
+#[cfg(feature = "async")]
+pub async fn write_async<I: IntoSTLIterator, W: AsyncWrite + Unpin>(
+    iter: I,
+    writer: &mut W,
+    stl_type: STLType,
+) -> Result<()> {
+    let mut bytes = Vec::new();
+    write(iter, &mut bytes, stl_type)?;
+    writer.write_all(&bytes).await?;
+    writer.flush().await?;
+    Ok(())
 }
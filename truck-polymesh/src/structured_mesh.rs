@@ -1,5 +1,7 @@
 use crate::errors::Error;
 use crate::*;
+#[cfg(feature = "heightmap")]
+use image::{DynamicImage, GenericImageView};
 
 impl Vertex {
     #[inline(always)]
@@ -7,7 +9,12 @@ impl Vertex {
         let pos = x;
         let uv = if uv { Some(x) } else { None };
         let nor = if nor { Some(x) } else { None };
-        Vertex { pos, uv, nor }
+        Vertex {
+            pos,
+            uv,
+            nor,
+            col: None,
+        }
     }
 }
 
@@ -146,6 +153,129 @@ impl StructuredMesh {
         }
     }
 
+    /// Creates a structured mesh of the terrain from the grayscale heightmap image.
+    ///
+    /// The pixels are placed on the regular grid over `x_range` and `y_range`: the columns
+    /// from left to right go along the x-axis, and the rows from bottom to top go along the
+    /// y-axis. The height of each pixel is its luminance scaled so that the white is `z_scale`.
+    /// The uv divisions are the normalized coordinates in `[0, 1]`, and the normals are
+    /// computed by the central differences of the heights.
+    ///
+    /// This method requires the feature `heightmap`.
+    /// # Panics
+    /// Panic occurs if the image has less than two pixels in width or height, or if `x_range`
+    /// or `y_range` has no width.
+    #[cfg(feature = "heightmap")]
+    #[inline(always)]
+    pub fn from_heightmap(
+        image: &DynamicImage,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_scale: f64,
+    ) -> StructuredMesh {
+        StructuredMesh::try_from_heightmap(image, x_range, y_range, z_scale)
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    /// Creates a structured mesh of the terrain from the grayscale heightmap image.
+    /// cf. [`StructuredMesh::from_heightmap`](#method.from_heightmap)
+    /// # Errors
+    /// Returns [`Error::TooSmallHeightmap`] if the image has less than two pixels in width
+    /// or height, and [`Error::EmptyHeightmapRange`] if `x_range` or `y_range` has no width.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use image::{DynamicImage, GrayImage, Luma};
+    ///
+    /// // the slope rising to the right
+    /// let image = GrayImage::from_fn(3, 2, |i, _| Luma([(i * 100) as u8]));
+    /// let image = DynamicImage::ImageLuma8(image);
+    /// let mesh = StructuredMesh::try_from_heightmap(&image, (0.0, 2.0), (0.0, 1.0), 2.55)
+    ///     .unwrap();
+    /// assert_eq!(mesh.positions().len(), 3);
+    /// assert_eq!(mesh.positions()[0].len(), 2);
+    /// assert!(mesh.positions()[2][1].near(&Point3::new(2.0, 1.0, 2.0)));
+    /// let normal = mesh.normals().unwrap()[1][0];
+    /// assert!(normal.near(&Vector3::new(-1.0, 0.0, 1.0).normalize()));
+    /// ```
+    ///
+    /// [`Error::TooSmallHeightmap`]: ./errors/enum.Error.html#variant.TooSmallHeightmap
+    /// [`Error::EmptyHeightmapRange`]: ./errors/enum.Error.html#variant.EmptyHeightmapRange
+    #[cfg(feature = "heightmap")]
+    pub fn try_from_heightmap(
+        image: &DynamicImage,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        z_scale: f64,
+    ) -> Result<StructuredMesh> {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        if width < 2 || height < 2 {
+            return Err(Error::TooSmallHeightmap);
+        }
+        // the zero width gives the infinite slopes
+        if x_range.0 == x_range.1 || y_range.0 == y_range.1 {
+            return Err(Error::EmptyHeightmapRange);
+        }
+        let udiv: Vec<f64> = (0..width).map(|i| i as f64 / (width - 1) as f64).collect();
+        let vdiv: Vec<f64> = (0..height).map(|j| j as f64 / (height - 1) as f64).collect();
+        // the luminances in `[0, 1]`, keeping the precision of 16-bit images
+        let color = image.color();
+        let luminance: Box<dyn Fn(u32, u32) -> f64> =
+            match color.bytes_per_pixel() > color.channel_count() {
+                true => {
+                    let image = image.to_luma16();
+                    Box::new(move |i, j| image.get_pixel(i, j).0[0] as f64 / u16::MAX as f64)
+                }
+                false => {
+                    let image = image.to_luma8();
+                    Box::new(move |i, j| image.get_pixel(i, j).0[0] as f64 / u8::MAX as f64)
+                }
+            };
+        // the rows of the image are from top to bottom
+        let heights: Vec<Vec<f64>> = (0..width)
+            .map(|i| {
+                (0..height)
+                    .map(|j| luminance(i as u32, (height - 1 - j) as u32) * z_scale)
+                    .collect()
+            })
+            .collect();
+        let positions = udiv
+            .iter()
+            .zip(&heights)
+            .map(|(u, heights)| {
+                vdiv.iter()
+                    .zip(heights)
+                    .map(|(v, z)| {
+                        let x = x_range.0 + (x_range.1 - x_range.0) * u;
+                        let y = y_range.0 + (y_range.1 - y_range.0) * v;
+                        Point3::new(x, y, *z)
+                    })
+                    .collect()
+            })
+            .collect();
+        let dx = (x_range.1 - x_range.0) / (width - 1) as f64;
+        let dy = (y_range.1 - y_range.0) / (height - 1) as f64;
+        let normals = (0..width)
+            .map(|i| {
+                let (i0, i1) = (usize::max(i, 1) - 1, usize::min(i + 1, width - 1));
+                let heights = &heights;
+                (0..height)
+                    .map(move |j| {
+                        let (j0, j1) = (usize::max(j, 1) - 1, usize::min(j + 1, height - 1));
+                        let dzdx = (heights[i1][j] - heights[i0][j]) / (dx * (i1 - i0) as f64);
+                        let dzdy = (heights[i][j1] - heights[i][j0]) / (dy * (j1 - j0) as f64);
+                        Vector3::new(-dzdx, -dzdy, 1.0).normalize()
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(StructuredMesh::new_unchecked(
+            positions,
+            (udiv, vdiv),
+            normals,
+        ))
+    }
+
     /// Returns the matrix of all positions.
     #[inline(always)]
     pub fn positions(&self) -> &Vec<Vec<Point3>> { &self.positions }
@@ -225,6 +355,7 @@ impl StructuredMesh {
             uv_coords,
             normals,
             faces,
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,373 @@
+use crate::*;
+use std::io::{BufWriter, Write};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+ <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+ <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+ <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+/// the namespace of the core specification of 3MF
+const CORE_NAMESPACE: &str = "http://schemas.microsoft.com/3dmanufacturing/core/2015/02";
+
+/// the date 1980-01-01 in MS-DOS format, the origin of zip
+const ZIP_DATE: u16 = 0x21;
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+/// Placement of an object in a component object or in the build
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThreeMFItem {
+    /// the index of the object. The meshes are indexed from `0`, and the component objects
+    /// [`ThreeMFDescriptor::assemblies`] follow the meshes.
+    ///
+    /// [`ThreeMFDescriptor::assemblies`]: ./struct.ThreeMFDescriptor.html#structfield.assemblies
+    pub object: usize,
+    /// the affine transform of the object. The last row is ignored.
+    pub transform: Matrix4,
+}
+
+/// Component object consisting of placed meshes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ThreeMFAssembly {
+    /// the name of the object
+    pub name: String,
+    /// the components, whose objects are the indices of meshes
+    pub components: Vec<ThreeMFItem>,
+}
+
+/// Configures of 3MF packages
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreeMFDescriptor {
+    /// the unit of the lengths of meshes. Default is `LengthUnit::Millimeter`.
+    pub unit: LengthUnit,
+    /// the component objects, following the mesh objects. Default is empty.
+    pub assemblies: Vec<ThreeMFAssembly>,
+    /// the items to be built. If empty, all the meshes are built as they are.
+    /// Default is empty.
+    pub build: Vec<ThreeMFItem>,
+}
+
+impl Default for ThreeMFDescriptor {
+    #[inline(always)]
+    fn default() -> ThreeMFDescriptor {
+        ThreeMFDescriptor {
+            unit: LengthUnit::Millimeter,
+            assemblies: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+}
+
+fn unit_name(unit: LengthUnit) -> &'static str {
+    match unit {
+        LengthUnit::Micrometer => "micron",
+        LengthUnit::Millimeter => "millimeter",
+        LengthUnit::Centimeter => "centimeter",
+        LengthUnit::Meter => "meter",
+        LengthUnit::Inch => "inch",
+        LengthUnit::Foot => "foot",
+    }
+}
+
+fn escape(string: &str) -> String {
+    string
+        .chars()
+        .fold(String::with_capacity(string.len()), |mut res, c| {
+            match c {
+                '&' => res.push_str("&amp;"),
+                '<' => res.push_str("&lt;"),
+                '>' => res.push_str("&gt;"),
+                '"' => res.push_str("&quot;"),
+                '\'' => res.push_str("&apos;"),
+                _ => res.push(c),
+            }
+            res
+        })
+}
+
+/// the matrix in the order of 3MF: the row vectors are transformed by the 4x3 matrix.
+fn transform_attribute(matrix: &Matrix4) -> String {
+    let values: Vec<String> = (0..4)
+        .flat_map(|i| (0..3).map(move |j| matrix[i][j].to_string()))
+        .collect();
+    values.join(" ")
+}
+
+fn write_model<W: Write>(
+    meshes: &[(String, PolygonMesh)],
+    desc: &ThreeMFDescriptor,
+    writer: &mut W,
+) -> Result<()> {
+    let number_of_objects = meshes.len() + desc.assemblies.len();
+    let check_index = |item: &ThreeMFItem, len: usize| match item.object < len {
+        true => Ok(()),
+        false => Err(errors::Error::OutOfRange("objects", len, item.object)),
+    };
+    desc.assemblies
+        .iter()
+        .flat_map(|assembly| &assembly.components)
+        .try_for_each(|item| check_index(item, meshes.len()))?;
+    desc.build
+        .iter()
+        .try_for_each(|item| check_index(item, number_of_objects))?;
+    writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+    writer.write_fmt(format_args!(
+        "<model unit=\"{}\" xml:lang=\"en-US\" xmlns=\"{}\">\n",
+        unit_name(desc.unit),
+        CORE_NAMESPACE,
+    ))?;
+    writer.write_all(b" <metadata name=\"Application\">truck-polymesh</metadata>\n")?;
+    writer.write_all(b" <resources>\n")?;
+    for (i, (name, mesh)) in meshes.iter().enumerate() {
+        // the degenerate triangles are not allowed in 3MF
+        let triangles = mesh.position_triangles();
+        if triangles.is_empty() {
+            return Err(invalid_input("3MF cannot store meshes without faces"));
+        }
+        writer.write_fmt(format_args!(
+            "  <object id=\"{}\" type=\"model\" name=\"{}\">\n   <mesh>\n    <vertices>\n",
+            i + 1,
+            escape(name),
+        ))?;
+        for p in mesh.positions() {
+            writer.write_fmt(format_args!(
+                "     <vertex x=\"{}\" y=\"{}\" z=\"{}\"/>\n",
+                p[0], p[1], p[2],
+            ))?;
+        }
+        writer.write_all(b"    </vertices>\n    <triangles>\n")?;
+        for [v0, v1, v2] in triangles {
+            writer.write_fmt(format_args!(
+                "     <triangle v1=\"{}\" v2=\"{}\" v3=\"{}\"/>\n",
+                v0, v1, v2,
+            ))?;
+        }
+        writer.write_all(b"    </triangles>\n   </mesh>\n  </object>\n")?;
+    }
+    for (i, assembly) in desc.assemblies.iter().enumerate() {
+        writer.write_fmt(format_args!(
+            "  <object id=\"{}\" type=\"model\" name=\"{}\">\n   <components>\n",
+            meshes.len() + i + 1,
+            escape(&assembly.name),
+        ))?;
+        for item in &assembly.components {
+            writer.write_fmt(format_args!(
+                "    <component objectid=\"{}\" transform=\"{}\"/>\n",
+                item.object + 1,
+                transform_attribute(&item.transform),
+            ))?;
+        }
+        writer.write_all(b"   </components>\n  </object>\n")?;
+    }
+    writer.write_all(b" </resources>\n <build>\n")?;
+    match desc.build.is_empty() {
+        true => (0..meshes.len()).try_for_each(|i| {
+            writer.write_fmt(format_args!("  <item objectid=\"{}\"/>\n", i + 1))
+        })?,
+        false => desc.build.iter().try_for_each(|item| {
+            writer.write_fmt(format_args!(
+                "  <item objectid=\"{}\" transform=\"{}\"/>\n",
+                item.object + 1,
+                transform_attribute(&item.transform),
+            ))
+        })?,
+    }
+    writer.write_all(b" </build>\n</model>\n")?;
+    Ok(())
+}
+
+/// Writes the files into a zip archive compressed by deflate.
+fn write_zip<W: Write>(files: &[(&str, Vec<u8>)], writer: &mut W) -> Result<()> {
+    let too_large = || invalid_input("3MF package larger than 4GB is not supported");
+    let mut offset = 0usize;
+    let mut central_directory = Vec::new();
+    for (name, data) in files {
+        let compressed = miniz_oxide::deflate::compress_to_vec(data, 6);
+        let crc = crc32fast::hash(data);
+        if data.len() > u32::MAX as usize || offset > u32::MAX as usize {
+            return Err(too_large());
+        }
+        // the common part of the local file header and the central directory header
+        let mut header = Vec::new();
+        header.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+        header.extend_from_slice(&0u16.to_le_bytes()); // general purpose bit flag
+        header.extend_from_slice(&8u16.to_le_bytes()); // compression method: deflate
+        header.extend_from_slice(&0u16.to_le_bytes()); // last modified time
+        header.extend_from_slice(&ZIP_DATE.to_le_bytes()); // last modified date
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+
+        writer.write_all(&0x0403_4B50u32.to_le_bytes())?;
+        writer.write_all(&header)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&compressed)?;
+
+        central_directory.extend_from_slice(&0x0201_4B50u32.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central_directory.extend_from_slice(&header);
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // file comment length
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // disk number start
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central_directory.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central_directory.extend_from_slice(&(offset as u32).to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+        offset += 30 + name.len() + compressed.len();
+    }
+    if offset > u32::MAX as usize {
+        return Err(too_large());
+    }
+    writer.write_all(&central_directory)?;
+    writer.write_all(&0x0605_4B50u32.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?; // number of this disk
+    writer.write_all(&0u16.to_le_bytes())?; // disk where central directory starts
+    writer.write_all(&(files.len() as u16).to_le_bytes())?;
+    writer.write_all(&(files.len() as u16).to_le_bytes())?;
+    writer.write_all(&(central_directory.len() as u32).to_le_bytes())?;
+    writer.write_all(&(offset as u32).to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?; // comment length
+    Ok(())
+}
+
+/// Writes named meshes into a 3MF package.
+///
+/// Each mesh is written as a mesh object, whose faces are triangulated as fans.
+/// The degenerate triangles are removed, while the other defects such as holes and
+/// non-manifold edges are not repaired. Only positions are written.
+///
+/// The component objects and the build items are configured by `desc`.
+/// Writing a mesh without faces returns an error.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use threemf::{ThreeMFAssembly, ThreeMFDescriptor, ThreeMFItem};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // the pair of tetrahedra, built twice
+/// let translation = |x: f64| Matrix4::from_translation(Vector3::new(x, 0.0, 0.0));
+/// let pair = ThreeMFAssembly {
+///     name: "pair".to_string(),
+///     components: vec![
+///         ThreeMFItem { object: 0, transform: translation(0.0) },
+///         ThreeMFItem { object: 0, transform: translation(2.0) },
+///     ],
+/// };
+/// let desc = ThreeMFDescriptor {
+///     unit: LengthUnit::Centimeter,
+///     assemblies: vec![pair],
+///     build: vec![
+///         ThreeMFItem { object: 1, transform: translation(0.0) },
+///         ThreeMFItem { object: 1, transform: translation(5.0) },
+///     ],
+/// };
+///
+/// let mut bytes = Vec::new();
+/// threemf::write(&[("tetrahedron".to_string(), mesh)], &mut bytes, &desc).unwrap();
+/// // 3MF is a zip archive
+/// assert_eq!(&bytes[0..4], b"PK\x03\x04");
+/// ```
+pub fn write<W: Write>(
+    meshes: &[(String, PolygonMesh)],
+    writer: W,
+    desc: &ThreeMFDescriptor,
+) -> Result<()> {
+    let mut model = Vec::new();
+    write_model(meshes, desc, &mut model)?;
+    let files = [
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes().to_vec()),
+        ("_rels/.rels", RELATIONSHIPS.as_bytes().to_vec()),
+        ("3D/3dmodel.model", model),
+    ];
+    let mut writer = BufWriter::new(writer);
+    write_zip(&files, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes 3MF package whose lengths, the positions of meshes and the translations of the items,
+/// are converted from the unit of `context` into `desc.unit`.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use threemf::{ThreeMFDescriptor, ThreeMFItem};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // the model is in meters, and the package is in millimeters.
+/// let context = ModelContext::new(LengthUnit::Meter, 1.0e-9);
+/// let desc = ThreeMFDescriptor {
+///     build: vec![ThreeMFItem {
+///         object: 0,
+///         transform: Matrix4::from_translation(Vector3::new(2.0, 0.0, 0.0)),
+///     }],
+///     ..Default::default()
+/// };
+/// let meshes = [("tetrahedron".to_string(), mesh)];
+/// let mut bytes = Vec::new();
+/// threemf::write_with_context(&meshes, &mut bytes, &desc, &context).unwrap();
+/// assert_eq!(&bytes[0..4], b"PK\x03\x04");
+/// ```
+pub fn write_with_context<W: Write>(
+    meshes: &[(String, PolygonMesh)],
+    writer: W,
+    desc: &ThreeMFDescriptor,
+    context: &ModelContext,
+) -> Result<()> {
+    if desc.unit == context.length_unit {
+        return write(meshes, writer, desc);
+    }
+    let scale = Matrix4::from_scale(context.scale_to(desc.unit));
+    let meshes: Vec<_> = meshes
+        .iter()
+        .map(|(name, mesh)| (name.clone(), mesh.transformed(scale)))
+        .collect();
+    // conjugation by the scaling converts only the translation part
+    let inverse = scale.invert().unwrap();
+    let convert = |items: &Vec<ThreeMFItem>| -> Vec<ThreeMFItem> {
+        items
+            .iter()
+            .map(|item| ThreeMFItem {
+                object: item.object,
+                transform: scale * item.transform * inverse,
+            })
+            .collect()
+    };
+    let desc = ThreeMFDescriptor {
+        unit: desc.unit,
+        assemblies: desc
+            .assemblies
+            .iter()
+            .map(|assembly| ThreeMFAssembly {
+                name: assembly.name.clone(),
+                components: convert(&assembly.components),
+            })
+            .collect(),
+        build: convert(&desc.build),
+    };
+    write(&meshes, writer, &desc)
+}
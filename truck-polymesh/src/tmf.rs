@@ -0,0 +1,285 @@
+use crate::*;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+/// the magic number at the head of files
+const MAGIC: &[u8; 4] = b"\x89TMF";
+/// the major version. Files with the greater major versions cannot be read.
+const MAJOR_VERSION: u16 = 1;
+/// the minor version. The chunks added in the minor versions are skipped by the old readers.
+const MINOR_VERSION: u16 = 0;
+/// none of the indices
+const NONE_INDEX: u32 = u32::MAX;
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+fn invalid_data(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message).into()
+}
+
+/// Compression of the chunks of TMF files
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TMFCompression {
+    /// stores the chunks as they are, the fastest
+    None,
+    /// compresses the chunks by deflate
+    Deflate,
+}
+
+impl Default for TMFCompression {
+    /// no compression
+    #[inline(always)]
+    fn default() -> TMFCompression { TMFCompression::None }
+}
+
+impl TMFCompression {
+    fn code(self) -> u8 {
+        match self {
+            TMFCompression::None => 0,
+            TMFCompression::Deflate => 1,
+        }
+    }
+}
+
+fn write_chunk<W: Write>(
+    writer: &mut W,
+    tag: &[u8; 4],
+    data: &[u8],
+    compression: TMFCompression,
+) -> Result<()> {
+    let compressed;
+    let stored = match compression {
+        TMFCompression::None => data,
+        TMFCompression::Deflate => {
+            compressed = miniz_oxide::deflate::compress_to_vec(data, 6);
+            &compressed
+        }
+    };
+    writer.write_all(tag)?;
+    writer.write_all(&[compression.code(), 0, 0, 0])?;
+    writer.write_all(&(stored.len() as u64).to_le_bytes())?;
+    writer.write_all(&(data.len() as u64).to_le_bytes())?;
+    writer.write_all(stored)?;
+    Ok(())
+}
+
+fn float_bytes<'a, I: IntoIterator<Item = &'a f64>>(values: I) -> Vec<u8> {
+    values.into_iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn face_bytes(faces: &Faces) -> Result<Vec<u8>> {
+    let index = |idx: Option<usize>| match idx {
+        Some(idx) if idx < NONE_INDEX as usize => Ok(idx as u32),
+        Some(_) => Err(invalid_input("TMF cannot store indices greater than u32::MAX - 1")),
+        None => Ok(NONE_INDEX),
+    };
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(faces.len() as u64).to_le_bytes());
+    for face in faces.face_iter() {
+        bytes.extend_from_slice(&index(Some(face.len()))?.to_le_bytes());
+        for v in face {
+            for idx in &[Some(v.pos), v.uv, v.nor, v.col] {
+                bytes.extend_from_slice(&index(*idx)?.to_le_bytes());
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// Writes the mesh into the native binary format TMF.
+///
+/// The file consists of the header, with the magic number and the version, and the chunks of
+/// positions, uv coordinates, normals, colors and faces. Each chunk is compressed
+/// by `compression`. The named attributes are not written.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use tmf::TMFCompression;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut bytes = Vec::new();
+/// tmf::write(&mesh, &mut bytes, TMFCompression::Deflate).unwrap();
+/// assert_eq!(&bytes[0..4], b"\x89TMF");
+/// let mesh0 = tmf::read(bytes.as_slice()).unwrap();
+/// assert_eq!(mesh, mesh0);
+/// ```
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W, compression: TMFCompression) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&MAJOR_VERSION.to_le_bytes())?;
+    writer.write_all(&MINOR_VERSION.to_le_bytes())?;
+    let positions = float_bytes(mesh.positions().iter().flat_map(AsRef::<[f64; 3]>::as_ref));
+    write_chunk(&mut writer, b"POS3", &positions, compression)?;
+    let uv_coords = float_bytes(mesh.uv_coords().iter().flat_map(AsRef::<[f64; 2]>::as_ref));
+    write_chunk(&mut writer, b"UV2\0", &uv_coords, compression)?;
+    let normals = float_bytes(mesh.normals().iter().flat_map(AsRef::<[f64; 3]>::as_ref));
+    write_chunk(&mut writer, b"NOR3", &normals, compression)?;
+    let colors = float_bytes(mesh.colors().iter().flat_map(AsRef::<[f64; 4]>::as_ref));
+    write_chunk(&mut writer, b"COL4", &colors, compression)?;
+    write_chunk(&mut writer, b"FACE", &face_bytes(mesh.faces())?, compression)?;
+    write_chunk(&mut writer, b"END\0", &[], TMFCompression::None)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    match bytes.len() == len {
+        true => Ok(bytes),
+        false => Err(invalid_data("the file is truncated")),
+    }
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut array = [0; 4];
+    array.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(array)
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    let mut array = [0; 8];
+    array.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(array)
+}
+
+/// decompresses the stored bytes of the chunk
+fn chunk_data(compression: u8, stored: Vec<u8>, raw_len: usize) -> Result<Vec<u8>> {
+    let data = match compression {
+        0 => stored,
+        1 => miniz_oxide::inflate::decompress_to_vec(&stored)
+            .map_err(|_| invalid_data("failed to decompress the chunk"))?,
+        _ => return Err(invalid_data("unknown compression")),
+    };
+    match data.len() == raw_len {
+        true => Ok(data),
+        false => Err(invalid_data("the length of the decompressed chunk is wrong")),
+    }
+}
+
+/// Reads the values of the chunk, whose elements consist of `dim` floats.
+fn floats(data: &[u8], dim: usize) -> Result<Vec<f64>> {
+    if !data.chunks_exact(8 * dim).remainder().is_empty() {
+        return Err(invalid_data("the length of the chunk is not a multiple of the elements"));
+    }
+    let res = data
+        .chunks_exact(8)
+        .map(|bytes| {
+            let mut array = [0; 8];
+            array.copy_from_slice(bytes);
+            f64::from_le_bytes(array)
+        })
+        .collect();
+    Ok(res)
+}
+
+fn faces(data: &[u8]) -> Result<Faces> {
+    let truncated = || invalid_data("the chunk of faces is truncated");
+    if data.len() < 8 {
+        return Err(truncated());
+    }
+    let len = u64_at(data, 0) as usize;
+    let mut offset = 8;
+    let mut next = || match offset + 4 <= data.len() {
+        true => {
+            offset += 4;
+            Ok(u32_at(data, offset - 4))
+        }
+        false => Err(truncated()),
+    };
+    let mut faces = Faces::default();
+    for _ in 0..len {
+        let face_len = next()? as usize;
+        let face = (0..face_len)
+            .map(|_| {
+                let mut index = || -> Result<Option<usize>> {
+                    let idx = next()?;
+                    Ok(Some(idx as usize).filter(|_| idx != NONE_INDEX))
+                };
+                Ok(Vertex {
+                    pos: index()?.ok_or_else(|| invalid_data("vertex without position"))?,
+                    uv: index()?,
+                    nor: index()?,
+                    col: index()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        faces.push(face);
+    }
+    Ok(faces)
+}
+
+/// Reads the mesh from the native binary format TMF.
+///
+/// The chunks unknown to this version, added by the newer minor versions, are skipped.
+/// Returns an error if the magic number is wrong, the major version is newer than
+/// this reader, or the file is truncated.
+pub fn read<R: Read>(reader: R) -> Result<PolygonMesh> {
+    let mut reader = BufReader::new(reader);
+    let header = read_bytes(&mut reader, 8)?;
+    if &header[0..4] != MAGIC {
+        return Err(invalid_data("this is not a TMF file"));
+    }
+    if u16_at(&header, 4) > MAJOR_VERSION {
+        return Err(invalid_data("the major version of the file is not supported"));
+    }
+    let (mut positions, mut uv_coords, mut normals, mut colors) = Default::default();
+    let mut faces0 = Faces::default();
+    loop {
+        let chunk_header = read_bytes(&mut reader, 24)?;
+        let stored_len = u64_at(&chunk_header, 8) as usize;
+        let raw_len = u64_at(&chunk_header, 16) as usize;
+        let stored = read_bytes(&mut reader, stored_len)?;
+        let data = || chunk_data(chunk_header[4], stored, raw_len);
+        match &chunk_header[0..4] {
+            b"POS3" => positions = floats(&data()?, 3)?,
+            b"UV2\0" => uv_coords = floats(&data()?, 2)?,
+            b"NOR3" => normals = floats(&data()?, 3)?,
+            b"COL4" => colors = floats(&data()?, 4)?,
+            b"FACE" => faces0 = faces(&data()?)?,
+            b"END\0" => break,
+            // The unknown chunks may be compressed by the methods unknown to this version.
+            _ => {}
+        }
+    }
+    let positions = positions.chunks_exact(3).map(|x| Point3::new(x[0], x[1], x[2]));
+    let uv_coords = uv_coords.chunks_exact(2).map(|x| Vector2::new(x[0], x[1]));
+    let normals = normals.chunks_exact(3).map(|x| Vector3::new(x[0], x[1], x[2]));
+    let colors = colors.chunks_exact(4).map(|x| Vector4::new(x[0], x[1], x[2], x[3]));
+    let mesh = PolygonMesh::from_raw_parts(
+        positions.collect(),
+        uv_coords.collect(),
+        normals.collect(),
+        colors.collect(),
+        faces0,
+    );
+    mesh.validate()?;
+    Ok(mesh)
+}
+
+impl PolygonMesh {
+    /// Writes the mesh into the native binary format TMF. Same as [`tmf::write`].
+    ///
+    /// [`tmf::write`]: ./tmf/fn.write.html
+    #[inline(always)]
+    pub fn write_tmf<W: Write>(&self, writer: W, compression: TMFCompression) -> Result<()> {
+        write(self, writer, compression)
+    }
+    /// Reads the mesh from the native binary format TMF. Same as [`tmf::read`].
+    ///
+    /// [`tmf::read`]: ./tmf/fn.read.html
+    #[inline(always)]
+    pub fn read_tmf<R: Read>(reader: R) -> Result<PolygonMesh> { read(reader) }
+}
@@ -0,0 +1,263 @@
+use crate::*;
+
+/// The policies for fixing the problems found by the validation in importing meshes.
+///
+/// In all policies, the faces with less than three corners are dropped, and the non-finite
+/// coordinates are replaced with zero in the arrays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FixPolicy {
+    /// Drops the faces with any problems.
+    Drop,
+    /// Clamps the out-of-range indices into the ranges of the arrays, and keeps the faces.
+    /// The indices of the empty arrays of uv coordinates, normals and colors are removed, and
+    /// the faces referring to the empty array of positions are dropped.
+    Clamp,
+    /// Removes the out-of-range or non-finite uv coordinates, normals and colors from
+    /// the corners, and removes the repeated positions of the consecutive corners.
+    /// The faces with out-of-range or non-finite positions, and the faces degenerate even after
+    /// the repair, are dropped.
+    Repair,
+}
+
+/// A problem found by the validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MeshProblem {
+    /// A corner of a face refers to the index out of the range of an array.
+    OutOfRange {
+        /// the index of the face, in the order of reading
+        face: usize,
+        /// the index of the corner in the face
+        corner: usize,
+        /// the name of the array: `"positions"`, `"uv_coords"`, `"normals"` or `"colors"`
+        array: &'static str,
+        /// the index out of range
+        index: usize,
+    },
+    /// An element of an array has NaN or infinite coordinates.
+    NonFinite {
+        /// the name of the array: `"positions"`, `"uv_coords"`, `"normals"` or `"colors"`
+        array: &'static str,
+        /// the index of the element
+        index: usize,
+    },
+    /// A face has less than three distinct positions or zero area.
+    DegenerateFace {
+        /// the index of the face, in the order of reading
+        face: usize,
+    },
+}
+
+/// The diagnostics of the validation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// all the problems found, in the order of the arrays and the faces
+    pub problems: Vec<MeshProblem>,
+    /// the indices of the dropped faces, in the order of reading
+    pub dropped_faces: Vec<usize>,
+}
+
+/// The value validated in importing, with the report of the problems.
+#[derive(Clone, Debug)]
+pub struct Validated<T> {
+    /// the value fixed by the policy
+    pub value: T,
+    /// the report of the problems
+    pub report: ValidationReport,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no problems are found.
+    #[inline(always)]
+    pub fn is_clean(&self) -> bool { self.problems.is_empty() }
+    /// Returns the number of the out-of-range indices.
+    #[inline(always)]
+    pub fn out_of_range_count(&self) -> usize {
+        self.count(|problem| matches!(problem, MeshProblem::OutOfRange { .. }))
+    }
+    /// Returns the number of the elements with non-finite coordinates.
+    #[inline(always)]
+    pub fn non_finite_count(&self) -> usize {
+        self.count(|problem| matches!(problem, MeshProblem::NonFinite { .. }))
+    }
+    /// Returns the number of the degenerate faces.
+    #[inline(always)]
+    pub fn degenerate_face_count(&self) -> usize {
+        self.count(|problem| matches!(problem, MeshProblem::DegenerateFace { .. }))
+    }
+    #[inline(always)]
+    fn count(&self, pred: impl Fn(&MeshProblem) -> bool) -> usize {
+        self.problems.iter().filter(|problem| pred(problem)).count()
+    }
+}
+
+impl PolygonMesh {
+    /// Creates the polygon mesh from the raw arrays read from a file, with validating the indices,
+    /// the coordinates and the faces, and fixing the problems by `policy`.
+    ///
+    /// `faces` are the faces in the order of reading, and the indices of the faces
+    /// in the report are the ones in `faces`.
+    /// # Examples
+    /// ```
+    /// use truck_polymesh::*;
+    /// use truck_polymesh::validation::*;
+    /// let positions = vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(f64::NAN, 0.0, 0.0),
+    /// ];
+    /// let vertex = |pos: usize, nor: Option<usize>| Vertex { pos, uv: None, nor, col: None };
+    /// let faces = vec![
+    ///     vec![vertex(0, Some(0)), vertex(1, Some(0)), vertex(2, Some(1))],
+    ///     vec![vertex(0, None), vertex(1, None), vertex(3, None)],
+    ///     vec![vertex(0, None), vertex(1, None), vertex(1, None)],
+    /// ];
+    /// let normals = vec![Vector3::unit_z()];
+    ///
+    /// let validated = PolygonMesh::validate_raw_parts(
+    ///     positions.clone(),
+    ///     Vec::new(),
+    ///     normals.clone(),
+    ///     Vec::new(),
+    ///     faces.clone(),
+    ///     FixPolicy::Drop,
+    /// );
+    /// let report = &validated.report;
+    /// assert_eq!(report.out_of_range_count(), 1);
+    /// assert_eq!(report.non_finite_count(), 1);
+    /// assert_eq!(report.degenerate_face_count(), 1);
+    /// assert_eq!(report.dropped_faces, vec![0, 1, 2]);
+    /// assert_eq!(validated.value.faces().len(), 0);
+    ///
+    /// // the normal out of range is removed from the corner
+    /// let validated = PolygonMesh::validate_raw_parts(
+    ///     positions,
+    ///     Vec::new(),
+    ///     normals,
+    ///     Vec::new(),
+    ///     faces,
+    ///     FixPolicy::Repair,
+    /// );
+    /// assert_eq!(validated.report.dropped_faces, vec![1, 2]);
+    /// assert_eq!(validated.value.faces()[0][2].nor, None);
+    /// ```
+    pub fn validate_raw_parts(
+        mut positions: Vec<Point3>,
+        mut uv_coords: Vec<Vector2>,
+        mut normals: Vec<Vector3>,
+        mut colors: Vec<Vector4>,
+        faces: Vec<Vec<Vertex>>,
+        policy: FixPolicy,
+    ) -> Validated<PolygonMesh> {
+        let mut report = ValidationReport::default();
+        let non_finite = [
+            replace_non_finite(&mut positions, "positions", &mut report),
+            replace_non_finite(&mut uv_coords, "uv_coords", &mut report),
+            replace_non_finite(&mut normals, "normals", &mut report),
+            replace_non_finite(&mut colors, "colors", &mut report),
+        ];
+        let lens = [positions.len(), uv_coords.len(), normals.len(), colors.len()];
+        let mut fixed_faces = Faces::default();
+        for (face_index, face) in faces.into_iter().enumerate() {
+            let mut dropped = false;
+            let mut fixed = Vec::with_capacity(face.len());
+            for (corner, vertex) in face.into_iter().enumerate() {
+                let mut indices = [Some(vertex.pos), vertex.uv, vertex.nor, vertex.col];
+                for i in 0..4 {
+                    let index = match indices[i] {
+                        Some(index) => index,
+                        None => continue,
+                    };
+                    let out_of_range = index >= lens[i];
+                    if out_of_range {
+                        report.problems.push(MeshProblem::OutOfRange {
+                            face: face_index,
+                            corner,
+                            array: ARRAY_NAMES[i],
+                            index,
+                        });
+                    }
+                    let invalid = out_of_range || non_finite[i][index];
+                    indices[i] = match (policy, invalid) {
+                        (_, false) => Some(index),
+                        (FixPolicy::Drop, true) => {
+                            dropped = true;
+                            None
+                        }
+                        (FixPolicy::Clamp, true) => match lens[i] {
+                            0 => None,
+                            len => Some(usize::min(index, len - 1)),
+                        },
+                        (FixPolicy::Repair, true) => None,
+                    };
+                }
+                match indices[0] {
+                    Some(pos) => fixed.push(Vertex {
+                        pos,
+                        uv: indices[1],
+                        nor: indices[2],
+                        col: indices[3],
+                    }),
+                    None => dropped = true,
+                }
+            }
+            if !dropped && is_degenerate(&positions, &fixed) {
+                report.problems.push(MeshProblem::DegenerateFace { face: face_index });
+                if policy == FixPolicy::Repair {
+                    fixed.dedup_by_key(|v| v.pos);
+                    if fixed.len() > 1 && fixed[0].pos == fixed[fixed.len() - 1].pos {
+                        fixed.pop();
+                    }
+                }
+                dropped = match policy {
+                    FixPolicy::Drop => true,
+                    FixPolicy::Clamp => fixed.len() < 3,
+                    FixPolicy::Repair => is_degenerate(&positions, &fixed),
+                };
+            }
+            match dropped {
+                true => report.dropped_faces.push(face_index),
+                false => fixed_faces.push(fixed),
+            }
+        }
+        let value = PolygonMesh::from_raw_parts(positions, uv_coords, normals, colors, fixed_faces);
+        Validated { value, report }
+    }
+}
+
+const ARRAY_NAMES: [&str; 4] = ["positions", "uv_coords", "normals", "colors"];
+
+/// Replaces the non-finite elements with zero, and returns the flags of the non-finite elements.
+fn replace_non_finite<T: Array<Element = f64>>(
+    array: &mut [T],
+    name: &'static str,
+    report: &mut ValidationReport,
+) -> Vec<bool> {
+    array
+        .iter_mut()
+        .enumerate()
+        .map(|(index, x)| {
+            let non_finite = !x.is_finite();
+            if non_finite {
+                report.problems.push(MeshProblem::NonFinite { array: name, index });
+                *x = T::from_value(0.0);
+            }
+            non_finite
+        })
+        .collect()
+}
+
+/// whether `face` has less than three distinct positions or zero area
+fn is_degenerate(positions: &[Point3], face: &[Vertex]) -> bool {
+    let distinct = (0..face.len()).all(|i| face[..i].iter().all(|v| v.pos != face[i].pos));
+    if face.len() < 3 || !distinct {
+        return true;
+    }
+    // the normal of Newell's method, whose length is twice the area of a planar face
+    let normal = (0..face.len()).fold(Vector3::zero(), |sum, i| {
+        let p = positions[face[i].pos];
+        let q = positions[face[(i + 1) % face.len()].pos];
+        sum + p.to_vec().cross(q.to_vec())
+    });
+    normal.magnitude2() < TOLERANCE2 * TOLERANCE2
+}
@@ -0,0 +1,436 @@
+use crate::*;
+use std::io::{BufWriter, Write};
+
+fn invalid_input(message: &str) -> errors::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+/// VTK file format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VTKFormat {
+    /// the legacy ascii format, ".vtk"
+    Legacy,
+    /// the XML format with the ascii data arrays, ".vtp" for polygon meshes and ".vts" for
+    /// structured meshes
+    XML,
+}
+
+impl Default for VTKFormat {
+    /// the XML format
+    #[inline(always)]
+    fn default() -> VTKFormat { VTKFormat::XML }
+}
+
+/// The meshes which can be written into VTK files
+pub trait VTKMesh {
+    /// Writes the mesh into `writer` in `format`. cf. [`vtk::write`](./fn.write.html)
+    fn write_vtk<W: Write>(&self, writer: W, format: VTKFormat) -> Result<()>;
+}
+
+/// the role of data arrays in the attributes of points and cells
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ArrayRole {
+    Scalars,
+    Vectors,
+    Normals,
+    TCoords,
+}
+
+/// the values of data arrays
+#[derive(Clone, Debug)]
+enum ArrayValues {
+    Float64(Vec<f64>),
+    UInt64(Vec<u64>),
+}
+
+/// a named data array of the attributes
+#[derive(Clone, Debug)]
+struct DataArray {
+    name: String,
+    role: ArrayRole,
+    components: usize,
+    values: ArrayValues,
+}
+
+impl DataArray {
+    #[inline(always)]
+    fn float64(name: &str, role: ArrayRole, components: usize, values: Vec<f64>) -> Self {
+        DataArray {
+            name: name.to_string(),
+            role,
+            components,
+            values: ArrayValues::Float64(values),
+        }
+    }
+    /// the strings of values separated by spaces, `components` values per line
+    fn lines(&self) -> Vec<String> {
+        let values: Vec<String> = match &self.values {
+            ArrayValues::Float64(values) => values.iter().map(|x| format!("{:?}", x)).collect(),
+            ArrayValues::UInt64(values) => values.iter().map(u64::to_string).collect(),
+        };
+        values.chunks(self.components).map(|c| c.join(" ")).collect()
+    }
+}
+
+/// the data arrays of the attributes of the elements of `attributes`
+fn attribute_arrays(attributes: &MeshAttributes) -> Vec<DataArray> {
+    let scalars = attributes.attributes::<f64>().iter().map(|(name, values)| {
+        DataArray::float64(name, ArrayRole::Scalars, 1, values.clone())
+    });
+    let labels = attributes
+        .attributes::<usize>()
+        .iter()
+        .map(|(name, values)| DataArray {
+            name: name.clone(),
+            role: ArrayRole::Scalars,
+            components: 1,
+            values: ArrayValues::UInt64(values.iter().map(|x| *x as u64).collect()),
+        });
+    let vectors = attributes.attributes::<Vector3>().iter().map(|(name, values)| {
+        let values = values.iter().flat_map(|v| [v[0], v[1], v[2]].to_vec()).collect();
+        DataArray::float64(name, ArrayRole::Vectors, 3, values)
+    });
+    scalars.chain(labels).chain(vectors).collect()
+}
+
+/// the cells of data sets
+#[derive(Clone, Debug)]
+enum Cells {
+    /// the polygons of the polydata
+    Polygons(Vec<Vec<usize>>),
+    /// the size of the structured grid, the first index runs fastest
+    Grid(usize, usize),
+}
+
+/// the intermediate representation of data sets
+#[derive(Clone, Debug)]
+struct DataSet {
+    points: Vec<Point3>,
+    cells: Cells,
+    point_data: Vec<DataArray>,
+    cell_data: Vec<DataArray>,
+}
+
+/// legacy data arrays cannot have names with white spaces
+#[inline(always)]
+fn legacy_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect()
+}
+
+fn escape(string: &str) -> String {
+    string
+        .chars()
+        .fold(String::with_capacity(string.len()), |mut res, c| {
+            match c {
+                '&' => res.push_str("&amp;"),
+                '<' => res.push_str("&lt;"),
+                '>' => res.push_str("&gt;"),
+                '"' => res.push_str("&quot;"),
+                _ => res.push(c),
+            }
+            res
+        })
+}
+
+impl DataSet {
+    fn write_legacy<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(b"# vtk DataFile Version 3.0\nwritten by truck-polymesh\nASCII\n")?;
+        match &self.cells {
+            Cells::Polygons(_) => writer.write_all(b"DATASET POLYDATA\n")?,
+            Cells::Grid(n, m) => writer.write_fmt(format_args!(
+                "DATASET STRUCTURED_GRID\nDIMENSIONS {} {} 1\n",
+                n, m
+            ))?,
+        }
+        writer.write_fmt(format_args!("POINTS {} double\n", self.points.len()))?;
+        for p in &self.points {
+            writer.write_fmt(format_args!("{:?} {:?} {:?}\n", p[0], p[1], p[2]))?;
+        }
+        if let Cells::Polygons(polygons) = &self.cells {
+            let size = polygons.iter().map(|poly| poly.len() + 1).sum::<usize>();
+            writer.write_fmt(format_args!("POLYGONS {} {}\n", polygons.len(), size))?;
+            for poly in polygons {
+                writer.write_fmt(format_args!("{}", poly.len()))?;
+                poly.iter()
+                    .try_for_each(|idx| writer.write_fmt(format_args!(" {}", idx)))?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        let cells_len = match &self.cells {
+            Cells::Polygons(polygons) => polygons.len(),
+            Cells::Grid(n, m) => (n - 1) * (m - 1),
+        };
+        let data = [
+            ("POINT_DATA", self.points.len(), &self.point_data),
+            ("CELL_DATA", cells_len, &self.cell_data),
+        ];
+        for (keyword, len, arrays) in data.iter() {
+            if arrays.is_empty() {
+                continue;
+            }
+            writer.write_fmt(format_args!("{} {}\n", keyword, len))?;
+            for array in arrays.iter() {
+                let name = legacy_name(&array.name);
+                let type_name = match array.values {
+                    ArrayValues::Float64(_) => "double",
+                    ArrayValues::UInt64(_) => "unsigned_long",
+                };
+                match array.role {
+                    ArrayRole::Scalars => writer.write_fmt(format_args!(
+                        "SCALARS {} {} {}\nLOOKUP_TABLE default\n",
+                        name, type_name, array.components
+                    ))?,
+                    ArrayRole::Vectors => {
+                        writer.write_fmt(format_args!("VECTORS {} {}\n", name, type_name))?
+                    }
+                    ArrayRole::Normals => {
+                        writer.write_fmt(format_args!("NORMALS {} {}\n", name, type_name))?
+                    }
+                    ArrayRole::TCoords => writer.write_fmt(format_args!(
+                        "TEXTURE_COORDINATES {} {} {}\n",
+                        name, array.components, type_name
+                    ))?,
+                }
+                array
+                    .lines()
+                    .iter()
+                    .try_for_each(|line| writer.write_fmt(format_args!("{}\n", line)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_xml_array<W: Write>(
+        writer: &mut W,
+        array: &DataArray,
+        name: Option<&str>,
+        indent: &str,
+    ) -> Result<()> {
+        let type_name = match array.values {
+            ArrayValues::Float64(_) => "Float64",
+            ArrayValues::UInt64(_) => "UInt64",
+        };
+        let name = match name {
+            Some(name) => format!(" Name=\"{}\"", escape(name)),
+            None => String::new(),
+        };
+        writer.write_fmt(format_args!(
+            "{}<DataArray type=\"{}\"{} NumberOfComponents=\"{}\" format=\"ascii\">\n",
+            indent, type_name, name, array.components
+        ))?;
+        array
+            .lines()
+            .iter()
+            .try_for_each(|line| writer.write_fmt(format_args!("{}  {}\n", indent, line)))?;
+        writer.write_fmt(format_args!("{}</DataArray>\n", indent))?;
+        Ok(())
+    }
+
+    fn write_xml_attributes<W: Write>(
+        writer: &mut W,
+        tag: &str,
+        arrays: &[DataArray],
+    ) -> Result<()> {
+        if arrays.is_empty() {
+            return Ok(());
+        }
+        // the active attributes
+        let active: String = [
+            (ArrayRole::Scalars, "Scalars"),
+            (ArrayRole::Vectors, "Vectors"),
+            (ArrayRole::Normals, "Normals"),
+            (ArrayRole::TCoords, "TCoords"),
+        ]
+        .iter()
+        .filter_map(|(role, key)| {
+            let array = arrays.iter().find(|array| array.role == *role)?;
+            Some(format!(" {}=\"{}\"", key, escape(&array.name)))
+        })
+        .collect();
+        writer.write_fmt(format_args!("      <{}{}>\n", tag, active))?;
+        for array in arrays {
+            Self::write_xml_array(writer, array, Some(&array.name), "        ")?;
+        }
+        writer.write_fmt(format_args!("      </{}>\n", tag))?;
+        Ok(())
+    }
+
+    fn write_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let type_name = match self.cells {
+            Cells::Polygons(_) => "PolyData",
+            Cells::Grid(..) => "StructuredGrid",
+        };
+        writer.write_all(b"<?xml version=\"1.0\"?>\n")?;
+        writer.write_fmt(format_args!(
+            "<VTKFile type=\"{}\" version=\"1.0\" byte_order=\"LittleEndian\" \
+             header_type=\"UInt64\">\n",
+            type_name
+        ))?;
+        match &self.cells {
+            Cells::Polygons(polygons) => {
+                writer.write_all(b"  <PolyData>\n")?;
+                writer.write_fmt(format_args!(
+                    "    <Piece NumberOfPoints=\"{}\" NumberOfVerts=\"0\" NumberOfLines=\"0\" \
+                     NumberOfStrips=\"0\" NumberOfPolys=\"{}\">\n",
+                    self.points.len(),
+                    polygons.len()
+                ))?;
+            }
+            Cells::Grid(n, m) => {
+                let extent = format!("0 {} 0 {} 0 0", n - 1, m - 1);
+                writer.write_fmt(format_args!(
+                    "  <StructuredGrid WholeExtent=\"{}\">\n    <Piece Extent=\"{}\">\n",
+                    extent, extent
+                ))?;
+            }
+        }
+        Self::write_xml_attributes(writer, "PointData", &self.point_data)?;
+        Self::write_xml_attributes(writer, "CellData", &self.cell_data)?;
+        let points = self
+            .points
+            .iter()
+            .flat_map(|p| [p[0], p[1], p[2]].to_vec())
+            .collect();
+        let points = DataArray::float64("Points", ArrayRole::Vectors, 3, points);
+        writer.write_all(b"      <Points>\n")?;
+        Self::write_xml_array(writer, &points, None, "        ")?;
+        writer.write_all(b"      </Points>\n")?;
+        if let Cells::Polygons(polygons) = &self.cells {
+            let connectivity: Vec<u64> = polygons.iter().flatten().map(|i| *i as u64).collect();
+            let offsets = polygons
+                .iter()
+                .scan(0, |offset, poly| {
+                    *offset += poly.len() as u64;
+                    Some(*offset)
+                })
+                .collect();
+            let arrays = [("connectivity", connectivity), ("offsets", offsets)];
+            writer.write_all(b"      <Polys>\n")?;
+            for (name, values) in arrays.iter() {
+                let array = DataArray {
+                    name: name.to_string(),
+                    role: ArrayRole::Scalars,
+                    components: 1,
+                    values: ArrayValues::UInt64(values.clone()),
+                };
+                Self::write_xml_array(writer, &array, Some(name), "        ")?;
+            }
+            writer.write_all(b"      </Polys>\n")?;
+        }
+        writer.write_fmt(format_args!("    </Piece>\n  </{}>\n</VTKFile>\n", type_name))?;
+        Ok(())
+    }
+
+    fn write<W: Write>(&self, writer: W, format: VTKFormat) -> Result<()> {
+        let mut writer = BufWriter::new(writer);
+        match format {
+            VTKFormat::Legacy => self.write_legacy(&mut writer)?,
+            VTKFormat::XML => self.write_xml(&mut writer)?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl VTKMesh for PolygonMesh {
+    fn write_vtk<W: Write>(&self, writer: W, format: VTKFormat) -> Result<()> {
+        let polygons = self
+            .face_iter()
+            .map(|face| face.iter().map(|v| v.pos).collect())
+            .collect();
+        DataSet {
+            points: self.positions().clone(),
+            cells: Cells::Polygons(polygons),
+            point_data: attribute_arrays(self.vertex_attributes()),
+            cell_data: attribute_arrays(self.face_attributes()),
+        }
+        .write(writer, format)
+    }
+}
+
+impl VTKMesh for StructuredMesh {
+    fn write_vtk<W: Write>(&self, writer: W, format: VTKFormat) -> Result<()> {
+        let positions = self.positions();
+        let (n, m) = match positions.first() {
+            Some(row) if row.len() > 1 && positions.len() > 1 => (positions.len(), row.len()),
+            _ => return Err(invalid_input("the structured mesh has no quadrangles")),
+        };
+        // the first index of the positions runs fastest
+        let grid = |i: usize| (i % n, i / n);
+        let points = (0..n * m).map(grid).map(|(i, j)| positions[i][j]).collect();
+        let mut point_data = Vec::new();
+        if let Some(normals) = self.normals() {
+            let values = (0..n * m)
+                .map(grid)
+                .flat_map(|(i, j)| {
+                    let normal = normals[i][j];
+                    [normal[0], normal[1], normal[2]].to_vec()
+                })
+                .collect();
+            point_data.push(DataArray::float64("Normals", ArrayRole::Normals, 3, values));
+        }
+        if let Some((udiv, vdiv)) = self.uv_division() {
+            let values = (0..n * m)
+                .map(grid)
+                .flat_map(|(i, j)| [udiv[i], vdiv[j]].to_vec())
+                .collect();
+            point_data.push(DataArray::float64("TCoords", ArrayRole::TCoords, 2, values));
+        }
+        DataSet {
+            points,
+            cells: Cells::Grid(n, m),
+            point_data,
+            cell_data: Vec::new(),
+        }
+        .write(writer, format)
+    }
+}
+
+/// Writes the mesh into VTK file, as the polydata for [`PolygonMesh`] or as the structured
+/// grid for [`StructuredMesh`].
+///
+/// The attributes of the vertices and the faces of polygon meshes are written as the point data
+/// and the cell data: the scalars as `double`, the labels as `unsigned long`, and the vectors as
+/// the vectors. The normals and the uv divisions of structured meshes are written as the point
+/// data `Normals` and `TCoords`.
+/// # Errors
+/// Returns the error of `InvalidInput` if the structured mesh has no quadrangles.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use vtk::VTKFormat;
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter([[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+/// let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+/// mesh.set_vertex_attribute("temperature", vec![20.0, 25.0, 30.0, 35.0]).unwrap();
+///
+/// let mut bytes = Vec::new();
+/// vtk::write(&mesh, &mut bytes, VTKFormat::Legacy).unwrap();
+/// let string = String::from_utf8(bytes).unwrap();
+/// assert!(string.contains("DATASET POLYDATA\nPOINTS 4 double\n"));
+/// assert!(string.contains("POLYGONS 4 16\n3 0 2 1\n"));
+/// assert!(string.contains("POINT_DATA 4\nSCALARS temperature double 1\n"));
+///
+/// let structured = StructuredMesh::from_positions(vec![
+///     vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+///     vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+/// ]);
+/// let mut bytes = Vec::new();
+/// vtk::write(&structured, &mut bytes, VTKFormat::XML).unwrap();
+/// let string = String::from_utf8(bytes).unwrap();
+/// assert!(string.contains("<StructuredGrid WholeExtent=\"0 1 0 1 0 0\">"));
+/// ```
+///
+/// [`PolygonMesh`]: ../struct.PolygonMesh.html
+/// [`StructuredMesh`]: ../struct.StructuredMesh.html
+#[inline(always)]
+pub fn write<M: VTKMesh, W: Write>(mesh: &M, writer: W, format: VTKFormat) -> Result<()> {
+    mesh.write_vtk(writer, format)
+}
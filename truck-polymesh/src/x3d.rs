@@ -0,0 +1,181 @@
+use crate::*;
+use std::io::{BufWriter, Write};
+use std::ops::Index;
+
+/// Encodings of the scene files
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum X3DEncoding {
+    /// X3D in the XML encoding, `*.x3d`
+    XML,
+    /// classic VRML97, `*.wrl`
+    VRML97,
+}
+
+impl Default for X3DEncoding {
+    /// the XML encoding
+    #[inline(always)]
+    fn default() -> X3DEncoding { X3DEncoding::XML }
+}
+
+/// Configures of X3D and VRML97 files
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct X3DDescriptor {
+    /// the encoding of the file. Default is `X3DEncoding::XML`.
+    pub encoding: X3DEncoding,
+    /// the crease angle in radians. Default is `None`.
+    ///
+    /// If `Some`, the normals of the mesh are not written, and the viewers generate
+    /// the normals smoothed between the faces whose angle is smaller than the crease angle.
+    /// If `None`, the normals of the mesh are written if all the vertices have normals.
+    pub crease_angle: Option<f64>,
+}
+
+/// the fields of `IndexedFaceSet`
+#[derive(Clone, Debug, Default)]
+struct IndexedFaceSet {
+    /// the pairs of the names and the values of the index fields
+    indices: Vec<(&'static str, String)>,
+    /// the field names, the node names, the attribute names and the values of the
+    /// attribute nodes
+    nodes: Vec<(&'static str, &'static str, &'static str, String)>,
+}
+
+fn index_field<F: Fn(&Vertex) -> usize>(mesh: &PolygonMesh, index: F) -> String {
+    mesh.face_iter()
+        .map(|face| {
+            let mut res: Vec<String> = face.iter().map(|v| index(v).to_string()).collect();
+            res.push("-1".to_string());
+            res.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn value_field<T: Index<usize, Output = f64>>(values: &[T], dim: usize) -> String {
+    values
+        .iter()
+        .map(|value| {
+            let value: Vec<String> = (0..dim).map(|i| value[i].to_string()).collect();
+            value.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl IndexedFaceSet {
+    fn new(mesh: &PolygonMesh, desc: &X3DDescriptor) -> IndexedFaceSet {
+        let mut res = IndexedFaceSet::default();
+        res.indices.push(("coordIndex", index_field(mesh, |v| v.pos)));
+        let point = value_field(mesh.positions(), 3);
+        res.nodes.push(("coord", "Coordinate", "point", point));
+        let has_normal = mesh.face_iter().flatten().all(|v| v.nor.is_some());
+        if desc.crease_angle.is_none() && has_normal && !mesh.normals().is_empty() {
+            res.indices.push(("normalIndex", index_field(mesh, |v| v.nor.unwrap())));
+            let vector = value_field(mesh.normals(), 3);
+            res.nodes.push(("normal", "Normal", "vector", vector));
+        }
+        let has_uv = mesh.face_iter().flatten().all(|v| v.uv.is_some());
+        if has_uv && !mesh.uv_coords().is_empty() {
+            res.indices.push(("texCoordIndex", index_field(mesh, |v| v.uv.unwrap())));
+            let point = value_field(mesh.uv_coords(), 2);
+            res.nodes.push(("texCoord", "TextureCoordinate", "point", point));
+        }
+        let has_color = mesh.face_iter().flatten().all(|v| v.col.is_some());
+        if has_color && !mesh.colors().is_empty() {
+            res.indices.push(("colorIndex", index_field(mesh, |v| v.col.unwrap())));
+            // the alpha values are dropped, since VRML97 has no RGBA colors
+            let rgb: Vec<Vector3> = mesh.colors().iter().map(|c| c.truncate()).collect();
+            res.nodes.push(("color", "Color", "color", value_field(&rgb, 3)));
+        }
+        res
+    }
+
+    fn write_xml<W: Write>(&self, crease_angle: Option<f64>, writer: &mut W) -> Result<()> {
+        writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+        writer.write_all(b"<X3D profile=\"Interchange\" version=\"3.3\">\n")?;
+        writer.write_all(b" <head>\n  <meta name=\"generator\" content=\"truck-polymesh\"/>\n")?;
+        writer.write_all(b" </head>\n <Scene>\n  <Shape>\n")?;
+        writer.write_all(b"   <Appearance>\n    <Material/>\n   </Appearance>\n")?;
+        writer.write_all(b"   <IndexedFaceSet solid=\"false\"")?;
+        if let Some(crease_angle) = crease_angle {
+            writer.write_fmt(format_args!(" creaseAngle=\"{}\"", crease_angle))?;
+        }
+        for (name, value) in &self.indices {
+            writer.write_fmt(format_args!(" {}=\"{}\"", name, value))?;
+        }
+        writer.write_all(b">\n")?;
+        for (_, node, attribute, value) in &self.nodes {
+            writer.write_fmt(format_args!("    <{} {}=\"{}\"/>\n", node, attribute, value))?;
+        }
+        writer.write_all(b"   </IndexedFaceSet>\n  </Shape>\n </Scene>\n</X3D>\n")?;
+        Ok(())
+    }
+
+    fn write_vrml<W: Write>(&self, crease_angle: Option<f64>, writer: &mut W) -> Result<()> {
+        writer.write_all(b"#VRML V2.0 utf8\n")?;
+        writer.write_all(b"# written by truck-polymesh\n")?;
+        writer.write_all(b"Shape {\n  appearance Appearance { material Material {} }\n")?;
+        writer.write_all(b"  geometry IndexedFaceSet {\n    solid FALSE\n")?;
+        if let Some(crease_angle) = crease_angle {
+            writer.write_fmt(format_args!("    creaseAngle {}\n", crease_angle))?;
+        }
+        for (field, node, attribute, value) in &self.nodes {
+            writer.write_fmt(format_args!(
+                "    {} {} {{ {} [ {} ] }}\n",
+                field, node, attribute, value
+            ))?;
+        }
+        for (name, value) in &self.indices {
+            writer.write_fmt(format_args!("    {} [ {} ]\n", name, value))?;
+        }
+        writer.write_all(b"  }\n}\n")?;
+        Ok(())
+    }
+}
+
+/// Writes a mesh into an X3D or a VRML97 file as one `IndexedFaceSet`.
+///
+/// The polygons are written as they are, without triangulation. The texture coordinates,
+/// the normals and the colors are written if all the vertices have them. The normals are
+/// replaced by the crease angle if `desc.crease_angle` is `Some`. The alpha values of
+/// the colors are dropped.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// use x3d::{X3DDescriptor, X3DEncoding};
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let square = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut bytes = Vec::new();
+/// x3d::write(&square, &mut bytes, &X3DDescriptor::default()).unwrap();
+/// let x3d = String::from_utf8(bytes).unwrap();
+/// assert!(x3d.contains("coordIndex=\"0 1 2 3 -1\""));
+/// assert!(x3d.contains("<Coordinate point=\"0 0 0, 1 0 0, 1 1 0, 0 1 0\"/>"));
+///
+/// let desc = X3DDescriptor {
+///     encoding: X3DEncoding::VRML97,
+///     crease_angle: Some(0.5),
+/// };
+/// let mut bytes = Vec::new();
+/// x3d::write(&square, &mut bytes, &desc).unwrap();
+/// let wrl = String::from_utf8(bytes).unwrap();
+/// assert!(wrl.starts_with("#VRML V2.0 utf8\n"));
+/// assert!(wrl.contains("creaseAngle 0.5\n"));
+/// assert!(wrl.contains("coordIndex [ 0 1 2 3 -1 ]"));
+/// ```
+pub fn write<W: Write>(mesh: &PolygonMesh, writer: W, desc: &X3DDescriptor) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let face_set = IndexedFaceSet::new(mesh, desc);
+    match desc.encoding {
+        X3DEncoding::XML => face_set.write_xml(desc.crease_angle, &mut writer)?,
+        X3DEncoding::VRML97 => face_set.write_vrml(desc.crease_angle, &mut writer)?,
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::*;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+fn syntax_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "syntax error")
+}
+
+/// Writes point cloud to output stream
+///
+/// Each line has the coordinates `x y z`, followed by the normal `nx ny nz`
+/// and the color `r g b` in `0..=255` if the point cloud has them. The alpha of the colors
+/// is not written, and the points with colors and no normals are written with zero normals.
+/// # Examples
+/// ```
+/// use truck_polymesh::*;
+/// let positions = vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0)];
+/// let normals = vec![Vector3::unit_x(), Vector3::unit_z()];
+/// let cloud = PointCloud::new(positions, normals, Vec::new());
+///
+/// let mut bytes = Vec::new();
+/// xyz::write(&cloud, &mut bytes).unwrap();
+/// let cloud0 = xyz::read(bytes.as_slice()).unwrap();
+/// assert_eq!(cloud, cloud0);
+/// ```
+pub fn write<W: Write>(cloud: &PointCloud, writer: W) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let has_colors = !cloud.colors().is_empty();
+    for (i, p) in cloud.positions().iter().enumerate() {
+        writer.write_fmt(format_args!("{:.10e} {:.10e} {:.10e}", p[0], p[1], p[2]))?;
+        if !cloud.normals().is_empty() || has_colors {
+            let n = cloud.normals().get(i).copied().unwrap_or_else(Vector3::zero);
+            writer.write_fmt(format_args!(" {:.10e} {:.10e} {:.10e}", n[0], n[1], n[2]))?;
+        }
+        if has_colors {
+            let c = cloud.colors()[i].map(|x| (f64::clamp(x, 0.0, 1.0) * 255.0).round() as u8);
+            writer.write_fmt(format_args!(" {} {} {}", c[0], c[1], c[2]))?;
+        }
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads point cloud from plain XYZ file.
+///
+/// The values are separated by white spaces or commas, and the lines starting with `#`
+/// are comments. All the lines have the same number of values:
+/// - 3 values: the coordinates `x y z`,
+/// - 6 values: the coordinates and the normal `nx ny nz`,
+/// - 9 values: the coordinates, the normal and the color `r g b` in `0..=255`.
+pub fn read<R: Read>(reader: R) -> Result<PointCloud> {
+    let reader = BufReader::new(reader);
+    let mut cloud = PointCloud::default();
+    let mut columns = None;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let values = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(str::parse::<f64>)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let len = *columns.get_or_insert(values.len());
+        if len != values.len() || !matches!(len, 3 | 6 | 9) {
+            return Err(syntax_error().into());
+        }
+        let position = Point3::new(values[0], values[1], values[2]);
+        let normal = match len {
+            3 => None,
+            _ => Some(Vector3::new(values[3], values[4], values[5])),
+        };
+        let color = match len {
+            9 => Some(Vector4::new(values[6], values[7], values[8], 255.0) / 255.0),
+            _ => None,
+        };
+        cloud.push(position, normal, color);
+    }
+    Ok(cloud)
+}
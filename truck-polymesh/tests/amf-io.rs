@@ -0,0 +1,160 @@
+use amf::{AMFConstellation, AMFDescriptor, AMFInstance, AMFObject};
+use truck_polymesh::*;
+mod common;
+use common::*;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+#[test]
+fn teapot_amf_test() {
+    let mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let mut object = AMFObject::new("teapot & lid", &mesh);
+    object
+        .metadata
+        .push(("material".to_string(), "PLA".to_string()));
+    let desc = AMFDescriptor {
+        metadata: vec![("author".to_string(), "truck".to_string())],
+        ..Default::default()
+    };
+    let amf = write_string(|bytes| amf::write(&[object], bytes, &desc)).unwrap();
+    assert!(amf.contains("<amf unit=\"millimeter\" version=\"1.1\">"));
+    assert!(amf.contains(" <metadata type=\"author\">truck</metadata>"));
+    assert!(amf.contains("<object id=\"0\">"));
+    assert!(amf.contains("<metadata type=\"name\">teapot &amp; lid</metadata>"));
+    assert!(amf.contains("<metadata type=\"material\">PLA</metadata>"));
+    assert_eq!(amf.matches("<vertex>").count(), mesh.positions().len());
+    // triangle only
+    let triangles: usize = mesh.face_iter().map(|face| face.len() - 2).sum();
+    assert_eq!(amf.matches("<triangle>").count(), triangles);
+    assert_eq!(amf.matches("<v3>").count(), triangles);
+    assert!(!amf.contains("<v4>"));
+    assert!(!amf.contains("<constellation"));
+    let p = mesh.positions()[0];
+    let vertex = format!("<x>{}</x><y>{}</y><z>{}</z>", p[0], p[1], p[2]);
+    assert!(amf.contains(&vertex));
+}
+
+#[test]
+fn amf_units() {
+    let mesh = tetrahedron();
+    let objects = [AMFObject::new("tetrahedron", &mesh)];
+    let instance = AMFInstance {
+        object: 0,
+        translation: Vector3::new(2.0, 0.0, 0.0),
+        rotation: Vector3::new(0.0, 90.0, 0.0),
+    };
+    let constellation = AMFConstellation {
+        metadata: Vec::new(),
+        instances: vec![instance],
+    };
+    let mut desc = AMFDescriptor {
+        constellations: vec![constellation],
+        ..Default::default()
+    };
+    let units = [
+        (LengthUnit::Micrometer, "micron"),
+        (LengthUnit::Millimeter, "millimeter"),
+        (LengthUnit::Meter, "meter"),
+        (LengthUnit::Inch, "inch"),
+        (LengthUnit::Foot, "feet"),
+    ];
+    for (unit, name) in units.iter() {
+        desc.unit = *unit;
+        let amf = write_string(|bytes| amf::write(&objects, bytes, &desc)).unwrap();
+        assert!(amf.contains(&format!("<amf unit=\"{}\" version=\"1.1\">", name)));
+        assert!(amf.contains("<x>1</x><y>0</y><z>0</z>"));
+        assert!(amf.contains("<deltax>2</deltax>"));
+    }
+    // centimeters are converted into millimeters
+    desc.unit = LengthUnit::Centimeter;
+    let amf = write_string(|bytes| amf::write(&objects, bytes, &desc)).unwrap();
+    assert!(amf.contains("<amf unit=\"millimeter\" version=\"1.1\">"));
+    assert!(amf.contains("<x>10</x><y>0</y><z>0</z>"));
+    assert!(amf.contains("<deltax>20</deltax>"));
+    assert!(amf.contains("<ry>90</ry>"));
+}
+
+#[test]
+fn amf_constellations() {
+    let mesh0 = tetrahedron();
+    let mesh1 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let objects = [
+        AMFObject::new("tetrahedron", &mesh0),
+        AMFObject::new("teapot", &mesh1),
+    ];
+    let shifted = |object: usize, x: f64| AMFInstance {
+        object,
+        translation: Vector3::new(x, 0.0, 0.0),
+        rotation: Vector3::zero(),
+    };
+    let pair = AMFConstellation {
+        metadata: vec![("name".to_string(), "pair".to_string())],
+        instances: vec![shifted(0, 0.0), shifted(1, 5.0)],
+    };
+    // the nested constellation
+    let plate = AMFConstellation {
+        metadata: vec![("name".to_string(), "plate".to_string())],
+        instances: vec![shifted(2, 0.0), shifted(2, 20.0)],
+    };
+    let mut desc = AMFDescriptor {
+        constellations: vec![pair, plate],
+        ..Default::default()
+    };
+    let amf = write_string(|bytes| amf::write(&objects, bytes, &desc)).unwrap();
+    assert!(amf.contains("<constellation id=\"2\">"));
+    assert!(amf.contains("<constellation id=\"3\">"));
+    assert!(amf.contains("<metadata type=\"name\">plate</metadata>"));
+    assert_eq!(amf.matches("<instance objectid=\"2\">").count(), 2);
+    assert!(amf.contains("<instance objectid=\"1\"><deltax>5</deltax>"));
+
+    // constellations cannot refer to themselves or the following ones
+    desc.constellations[1].instances.push(AMFInstance::new(3));
+    match write_string(|bytes| amf::write(&objects, bytes, &desc)) {
+        Err(errors::Error::OutOfRange("objects", 3, 3)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn amf_empty_mesh() {
+    let mesh = PolygonMesh::default();
+    let objects = [AMFObject::new("empty", &mesh)];
+    assert!(write_string(|bytes| amf::write(&objects, bytes, &Default::default())).is_err());
+}
+
+#[test]
+fn amf_instance_transform() {
+    let instance = AMFInstance {
+        object: 0,
+        translation: Vector3::new(1.0, 2.0, 3.0),
+        rotation: Vector3::new(90.0, 90.0, 0.0),
+    };
+    // rotated around the x-axis, and then around the y-axis
+    let pt = instance.transform().transform_point(Point3::new(0.0, 1.0, 0.0));
+    assert!(pt.near(&Point3::new(2.0, 2.0, 3.0)));
+}
+
+#[test]
+fn amf_context() {
+    let mesh = tetrahedron();
+    let objects = [AMFObject::new("tetrahedron", &mesh)];
+    let desc = AMFDescriptor {
+        unit: LengthUnit::Centimeter,
+        constellations: vec![AMFConstellation {
+            metadata: Vec::new(),
+            instances: vec![AMFInstance {
+                object: 0,
+                translation: Vector3::new(2.0, 0.0, 0.0),
+                rotation: Vector3::zero(),
+            }],
+        }],
+        ..Default::default()
+    };
+    let context = ModelContext::new(LengthUnit::Meter, 1.0e-9);
+    let write = |bytes: &mut Vec<u8>| amf::write_with_context(&objects, bytes, &desc, &context);
+    let amf = write_string(write).unwrap();
+    // meters are converted into centimeters, which are written in millimeters
+    assert!(amf.contains("<amf unit=\"millimeter\" version=\"1.1\">"));
+    assert!(amf.contains("<x>1000</x><y>0</y><z>0</z>"));
+    assert!(amf.contains("<deltax>2000</deltax>"));
+}
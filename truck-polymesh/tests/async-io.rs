@@ -0,0 +1,51 @@
+#![cfg(feature = "async")]
+
+use stl::STLType;
+use truck_polymesh::*;
+
+const TEAPOT_WITHNORMALS_OBJ: &[u8] = include_bytes!("data/teapot-with-normals.obj");
+const BUNNY_BINARY_STL: &[u8] = include_bytes!("data/bunny_binary.stl");
+
+#[tokio::test]
+async fn obj_async_ioi_test() {
+    let mesh = obj::read_async(TEAPOT_WITHNORMALS_OBJ).await.unwrap();
+    assert_eq!(mesh, obj::read(TEAPOT_WITHNORMALS_OBJ).unwrap());
+
+    // The pipe is much smaller than the data, so the writer waits for the reader.
+    let (client, server) = tokio::io::duplex(1024);
+    let (written, read) = tokio::join!(obj::write_async(&mesh, client), obj::read_async(server));
+    written.unwrap();
+    assert_eq!(read.unwrap(), mesh);
+}
+
+#[tokio::test]
+async fn stl_async_ioi_test() {
+    let mesh = stl::read_async(BUNNY_BINARY_STL, STLType::Automatic)
+        .await
+        .unwrap();
+    assert_eq!(
+        mesh,
+        stl::read(BUNNY_BINARY_STL, STLType::Automatic).unwrap()
+    );
+
+    for stl_type in [STLType::Binary, STLType::ASCII] {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let write = async {
+            let res = stl::write_async(&mesh, &mut client, stl_type).await;
+            drop(client);
+            res
+        };
+        let (written, read) = tokio::join!(write, stl::read_async(server, stl_type));
+        written.unwrap();
+        let read = read.unwrap();
+        assert_eq!(read.faces().len(), mesh.faces().len());
+    }
+
+    let mut bytes = Vec::new();
+    stl::write_async(&mesh, &mut bytes, STLType::Binary)
+        .await
+        .unwrap();
+    let mut sync_bytes = Vec::new();
+    stl::write(&mesh, &mut sync_bytes, STLType::Binary).unwrap();
+    assert_eq!(bytes, sync_bytes);
+}
@@ -0,0 +1,21 @@
+// Each test uses only some of the fixtures.
+#![allow(dead_code)]
+use truck_polymesh::*;
+
+pub fn tetrahedron() -> PolygonMesh {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    ];
+    let faces = Faces::from_iter([[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+    PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+}
+
+// the output of `write` as a string
+pub fn write_string<F: FnOnce(&mut Vec<u8>) -> Result<()>>(write: F) -> Result<String> {
+    let mut bytes = Vec::new();
+    write(&mut bytes)?;
+    Ok(String::from_utf8(bytes).unwrap())
+}
@@ -0,0 +1,32 @@
+use truck_polymesh::*;
+
+const PONY_OBJ: &[u8] = include_bytes!("data/pony-complete.obj");
+
+#[test]
+fn compact_mesh_conversion() {
+    let mesh = obj::read(PONY_OBJ).unwrap();
+    let compact = CompactMesh::from(&mesh);
+    assert_eq!(compact.len(), mesh.faces().len());
+    assert_eq!(compact.uv_coords().len(), compact.positions().len());
+    assert_eq!(compact.normals().len(), compact.positions().len());
+    assert!(compact.colors().is_empty());
+    let corners = mesh.face_iter().flatten().collect::<std::collections::HashSet<_>>();
+    assert_eq!(compact.positions().len(), corners.len());
+    assert_eq!(*compact.offsets().last().unwrap() as usize, compact.indices().len());
+
+    // the corners refer to the same attributes
+    mesh.face_iter().zip(compact.face_iter()).for_each(|(face, cface)| {
+        assert_eq!(face.len(), cface.len());
+        face.iter().zip(cface).for_each(|(v, i)| {
+            let i = *i as usize;
+            assert_eq!(mesh.positions()[v.pos], compact.positions()[i]);
+            assert_eq!(mesh.uv_coords()[v.uv.unwrap()], compact.uv_coords()[i]);
+            assert_eq!(mesh.normals()[v.nor.unwrap()], compact.normals()[i]);
+        })
+    });
+
+    let restored = PolygonMesh::from(&compact);
+    assert!(restored.validate().is_ok());
+    assert_eq!(restored.bounding_box(), mesh.bounding_box());
+    assert_eq!(CompactMesh::from(&restored), compact);
+}
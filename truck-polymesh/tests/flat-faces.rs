@@ -0,0 +1,43 @@
+use truck_polymesh::*;
+
+const PONY_OBJ: &[u8] = include_bytes!("data/pony-complete.obj");
+
+#[test]
+fn flat_faces_compatibility() {
+    let mesh = obj::read(PONY_OBJ).unwrap();
+    let faces = mesh.faces();
+    let mut flat_faces = FlatFaces::from(faces);
+    assert_eq!(flat_faces.len(), faces.len());
+    assert!(faces.face_iter().eq(flat_faces.face_iter()));
+    (0..faces.len()).for_each(|i| assert_eq!(&faces[i], &flat_faces[i]));
+    assert_eq!(&Faces::from(&flat_faces), faces);
+
+    let inverse = faces.inverse();
+    flat_faces.invert();
+    assert!(inverse.face_iter().eq(flat_faces.face_iter()));
+
+    let mut concat = FlatFaces::from(faces);
+    concat.naive_concat(flat_faces.clone());
+    assert_eq!(concat.len(), 2 * faces.len());
+    assert!(concat
+        .face_iter()
+        .skip(faces.len())
+        .eq(flat_faces.face_iter()));
+}
+
+#[test]
+fn polygon_mesh_backed_by_flat_faces() {
+    let mesh = obj::read(PONY_OBJ).unwrap();
+    let flat = PolygonMesh::<FlatFaces>::from(&mesh);
+    assert!(flat.validate().is_ok());
+    assert!(mesh.face_iter().eq(flat.face_iter()));
+    assert_eq!(CompactMesh::from(&flat), CompactMesh::from(&mesh));
+    assert_eq!(PolygonMesh::from(&flat), mesh);
+
+    let inverse = flat.inverse();
+    assert!(mesh.inverse().face_iter().eq(inverse.face_iter()));
+
+    let faces = FlatFaces::from_iter([[0, 1, 2], [0, 1, 4]]);
+    let positions = mesh.positions()[..4].to_vec();
+    assert!(PolygonMesh::try_new(positions, Vec::new(), Vec::new(), faces).is_err());
+}
@@ -0,0 +1,383 @@
+use gltf::{GLTFFormat, GLTFImage, GLTFMaterial, ImageMimeType};
+use serde_json::Value;
+use truck_polymesh::*;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+// https://sketchfab.com/3d-models/pony-cartoon-885d9f60b3a9429bb4077cfac5653cf9
+// Pony Cartoon - CC Attribution © Slava Z.
+const PONY_COMPLETE_OBJ: &[u8] = include_bytes!("data/pony-complete.obj");
+
+fn u32_at(bytes: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ]) as usize
+}
+
+// splits glb into the json and the binary buffer
+fn parse_glb(bytes: &[u8]) -> (Value, Vec<u8>) {
+    assert_eq!(&bytes[0..4], b"glTF");
+    assert_eq!(u32_at(bytes, 4), 2);
+    assert_eq!(u32_at(bytes, 8), bytes.len());
+    let json_length = u32_at(bytes, 12);
+    assert_eq!(&bytes[16..20], b"JSON");
+    let json = serde_json::from_slice(&bytes[20..20 + json_length]).unwrap();
+    let bin_offset = 20 + json_length;
+    let bin_length = u32_at(bytes, bin_offset);
+    assert_eq!(&bytes[bin_offset + 4..bin_offset + 8], b"BIN\0");
+    let bin = bytes[bin_offset + 8..bin_offset + 8 + bin_length].to_vec();
+    assert_eq!(bin_offset + 8 + bin_length, bytes.len());
+    (json, bin)
+}
+
+fn base64_decode(string: &str) -> Vec<u8> {
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => panic!("invalid base64 character"),
+    };
+    let bytes = string.as_bytes();
+    assert_eq!(bytes.len() % 4, 0);
+    bytes
+        .chunks(4)
+        .flat_map(|chunk| {
+            let len = chunk.iter().filter(|c| **c != b'=').count();
+            let n = chunk[..len]
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, c)| n | (value(*c) as u32) << (18 - 6 * i));
+            (0..len - 1).map(move |i| (n >> (16 - 8 * i)) as u8)
+        })
+        .collect()
+}
+
+// the elements of the accessor `index` as f64
+fn accessor(json: &Value, bin: &[u8], index: &Value) -> Vec<Vec<f64>> {
+    let accessor = &json["accessors"][index.as_u64().unwrap() as usize];
+    let view = &json["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+    let offset = view["byteOffset"].as_u64().unwrap() as usize;
+    assert_eq!(offset % 4, 0);
+    let dim = match accessor["type"].as_str().unwrap() {
+        "SCALAR" => 1,
+        "VEC2" => 2,
+        "VEC3" => 3,
+        _ => panic!("unexpected type"),
+    };
+    let count = accessor["count"].as_u64().unwrap() as usize;
+    assert_eq!(view["byteLength"].as_u64().unwrap() as usize, 4 * dim * count);
+    (0..count)
+        .map(|i| {
+            (0..dim)
+                .map(|j| {
+                    let bytes = &bin[offset + 4 * (dim * i + j)..offset + 4 * (dim * i + j + 1)];
+                    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+                    match accessor["componentType"].as_u64().unwrap() {
+                        5126 => f32::from_le_bytes(bytes) as f64,
+                        5125 => u32::from_le_bytes(bytes) as f64,
+                        _ => panic!("unexpected component type"),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn number_of_triangles(mesh: &PolygonMesh) -> usize {
+    mesh.face_iter().map(|face| face.len() - 2).sum()
+}
+
+#[test]
+fn position_glb_test() {
+    let mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let mut bytes = Vec::new();
+    gltf::write(&mesh, &mut bytes, GLTFFormat::Binary, None).unwrap();
+    let (json, bin) = parse_glb(&bytes);
+    assert_eq!(json["asset"]["version"], "2.0");
+    assert!(json.get("materials").is_none());
+    let primitive = &json["meshes"][0]["primitives"][0];
+    assert!(primitive["attributes"].get("NORMAL").is_none());
+    assert!(primitive["attributes"].get("TEXCOORD_0").is_none());
+    let positions = accessor(&json, &bin, &primitive["attributes"]["POSITION"]);
+    let indices = accessor(&json, &bin, &primitive["indices"]);
+    assert_eq!(positions.len(), mesh.positions().len());
+    assert_eq!(indices.len(), 3 * number_of_triangles(&mesh));
+    // the triangles are the same as the fans of the faces
+    let triangles = mesh.face_iter().flat_map(|face| {
+        (2..face.len()).flat_map(move |i| vec![face[0].pos, face[i - 1].pos, face[i].pos])
+    });
+    indices.iter().zip(triangles).for_each(|(idx, pos)| {
+        let p = &positions[idx[0] as usize];
+        let q = mesh.positions()[pos].cast::<f32>().unwrap();
+        assert_eq!(p, &vec![q[0] as f64, q[1] as f64, q[2] as f64]);
+    });
+    // the bounding box
+    let index = primitive["attributes"]["POSITION"].as_u64().unwrap() as usize;
+    let accessor = &json["accessors"][index];
+    let bdd = mesh.bounding_box();
+    (0..3).for_each(|i| {
+        assert_eq!(accessor["min"][i].as_f64().unwrap(), bdd.min()[i] as f32 as f64);
+        assert_eq!(accessor["max"][i].as_f64().unwrap(), bdd.max()[i] as f32 as f64);
+    });
+}
+
+#[test]
+fn complete_embedded_gltf_test() {
+    let mesh = obj::read(PONY_COMPLETE_OBJ).unwrap();
+    let mut glb = Vec::new();
+    gltf::write(&mesh, &mut glb, GLTFFormat::Binary, None).unwrap();
+    let mut gltf = Vec::new();
+    gltf::write(&mesh, &mut gltf, GLTFFormat::Embedded, None).unwrap();
+    let (json0, bin0) = parse_glb(&glb);
+    let json1: Value = serde_json::from_slice(&gltf).unwrap();
+    let uri = json1["buffers"][0]["uri"].as_str().unwrap();
+    let bin1 = base64_decode(uri.strip_prefix("data:application/octet-stream;base64,").unwrap());
+    assert_eq!(bin0, bin1);
+    assert_eq!(json0["accessors"], json1["accessors"]);
+
+    let primitive = &json1["meshes"][0]["primitives"][0];
+    let normals = accessor(&json1, &bin1, &primitive["attributes"]["NORMAL"]);
+    let uv_coords = accessor(&json1, &bin1, &primitive["attributes"]["TEXCOORD_0"]);
+    let indices = accessor(&json1, &bin1, &primitive["indices"]);
+    assert_eq!(indices.len(), 3 * number_of_triangles(&mesh));
+    normals
+        .iter()
+        .for_each(|n| assert!(f64::abs(n[0] * n[0] + n[1] * n[1] + n[2] * n[2] - 1.0) < 1.0e-5));
+    // the v-coordinates are flipped
+    let face = &mesh.face_iter().next().unwrap();
+    let uv = mesh.uv_coords()[face[0].uv.unwrap()];
+    let uv0 = &uv_coords[indices[0][0] as usize];
+    assert!(f64::abs(uv0[0] - uv[0]) < 1.0e-6);
+    assert!(f64::abs(uv0[1] - (1.0 - uv[1])) < 1.0e-6);
+}
+
+#[test]
+fn material_gltf_test() {
+    let mesh = obj::read(PONY_COMPLETE_OBJ).unwrap();
+    // the contents of the image are not validated
+    let image = GLTFImage {
+        mime_type: ImageMimeType::PNG,
+        data: b"\x89PNG\r\n\x1a\ndummy".to_vec(),
+    };
+    let material = GLTFMaterial {
+        base_color_factor: [0.5, 0.25, 1.0, 1.0],
+        base_color_texture: Some(image.clone()),
+        metallic_factor: 0.0,
+        roughness_factor: 0.75,
+        double_sided: true,
+        ..Default::default()
+    };
+    let mut bytes = Vec::new();
+    gltf::write(&mesh, &mut bytes, GLTFFormat::Binary, Some(&material)).unwrap();
+    let (json, bin) = parse_glb(&bytes);
+    assert_eq!(json["meshes"][0]["primitives"][0]["material"], 0);
+    let gltf_material = &json["materials"][0];
+    let pbr = &gltf_material["pbrMetallicRoughness"];
+    assert_eq!(pbr["baseColorFactor"], serde_json::json!([0.5, 0.25, 1.0, 1.0]));
+    assert_eq!(pbr["metallicFactor"], 0.0);
+    assert_eq!(pbr["roughnessFactor"], 0.75);
+    assert_eq!(gltf_material["doubleSided"], true);
+    let texture = &json["textures"][pbr["baseColorTexture"]["index"].as_u64().unwrap() as usize];
+    let gltf_image = &json["images"][texture["source"].as_u64().unwrap() as usize];
+    assert_eq!(gltf_image["mimeType"], "image/png");
+    let view = &json["bufferViews"][gltf_image["bufferView"].as_u64().unwrap() as usize];
+    let offset = view["byteOffset"].as_u64().unwrap() as usize;
+    let length = view["byteLength"].as_u64().unwrap() as usize;
+    assert_eq!(&bin[offset..offset + length], image.data.as_slice());
+}
+
+#[test]
+fn gltf_with_context_test() {
+    let mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let context = ModelContext {
+        length_unit: LengthUnit::Millimeter,
+        ..Default::default()
+    };
+    let mut bytes = Vec::new();
+    gltf::write_with_context(&mesh, &mut bytes, GLTFFormat::Binary, None, &context).unwrap();
+    let (json, _) = parse_glb(&bytes);
+    let max = *mesh.bounding_box().max();
+    let max0 = &json["accessors"][0]["max"];
+    (0..3).for_each(|i| {
+        let x = max0[i].as_f64().unwrap();
+        assert!(f64::abs(x - max[i] / 1000.0) < 1.0e-6 * f64::max(1.0, x.abs()));
+    });
+}
+
+#[test]
+fn empty_gltf_test() {
+    let mesh = PolygonMesh::default();
+    let mut bytes = Vec::new();
+    assert!(gltf::write(&mesh, &mut bytes, GLTFFormat::Binary, None).is_err());
+}
+
+fn make_glb(json: &Value, bin: &[u8]) -> Vec<u8> {
+    let mut json = serde_json::to_vec(json).unwrap();
+    json.resize(json.len().div_ceil(4) * 4, b' ');
+    let mut bin = bin.to_vec();
+    bin.resize(bin.len().div_ceil(4) * 4, 0);
+    let length = 12 + 8 + json.len() + 8 + bin.len();
+    let mut bytes = b"glTF".to_vec();
+    [2, length, json.len()]
+        .iter()
+        .for_each(|x| bytes.extend_from_slice(&(*x as u32).to_le_bytes()));
+    bytes.extend_from_slice(b"JSON");
+    bytes.extend_from_slice(&json);
+    bytes.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"BIN\0");
+    bytes.extend_from_slice(&bin);
+    bytes
+}
+
+#[test]
+fn complete_gltf_io_test() {
+    let mesh0 = obj::read(PONY_COMPLETE_OBJ).unwrap();
+    for format in [GLTFFormat::Binary, GLTFFormat::Embedded] {
+        let mut bytes = Vec::new();
+        gltf::write(&mesh0, &mut bytes, format, None).unwrap();
+        let meshes = gltf::read(bytes.as_slice()).unwrap();
+        assert_eq!(meshes.len(), 1);
+        assert_eq!(meshes[0].0, "mesh0");
+        let mesh1 = &meshes[0].1;
+        assert_eq!(mesh1.tri_faces().len(), number_of_triangles(&mesh0));
+        let triangles = mesh0.face_iter().flat_map(|face| {
+            (2..face.len()).flat_map(move |i| vec![face[0], face[i - 1], face[i]])
+        });
+        mesh1
+            .tri_faces()
+            .iter()
+            .flatten()
+            .zip(triangles)
+            .for_each(|(v1, v0)| {
+                let near = |x: f64, y: f64| f64::abs(x - y) < 1.0e-5 * f64::max(1.0, x.abs());
+                let (p0, p1) = (mesh0.positions()[v0.pos], mesh1.positions()[v1.pos]);
+                assert!((0..3).all(|i| near(p0[i], p1[i])));
+                let (uv0, uv1) = (
+                    mesh0.uv_coords()[v0.uv.unwrap()],
+                    mesh1.uv_coords()[v1.uv.unwrap()],
+                );
+                assert!((0..2).all(|i| near(uv0[i], uv1[i])));
+                let n0 = mesh0.normals()[v0.nor.unwrap()].normalize();
+                let n1 = mesh1.normals()[v1.nor.unwrap()];
+                assert!((0..3).all(|i| near(n0[i], n1[i])));
+            });
+    }
+}
+
+#[test]
+fn interleaved_strip_glb_test() {
+    // interleaved vertices: the position in float and the uv in normalized unsigned byte
+    let vertices = [
+        ([0.0f32, 0.0, 0.0], [0u8, 255]),
+        ([0.0, 1.0, 0.0], [0, 0]),
+        ([1.0, 0.0, 0.0], [255, 255]),
+        ([1.0, 1.0, 0.0], [255, 0]),
+    ];
+    let mut bin: Vec<u8> = vertices
+        .iter()
+        .flat_map(|(p, uv)| {
+            let mut bytes: Vec<u8> = p.iter().flat_map(|x| x.to_le_bytes().to_vec()).collect();
+            bytes.extend_from_slice(&[uv[0], uv[1], 0, 0]);
+            bytes
+        })
+        .collect();
+    // the indices of the strip in unsigned short
+    [0u16, 1, 2, 3]
+        .iter()
+        .for_each(|i| bin.extend_from_slice(&i.to_le_bytes()));
+    let json = serde_json::json!({
+        "asset": { "version": "2.0" },
+        "meshes": [
+            {
+                "name": "square",
+                "primitives": [
+                    { "attributes": { "POSITION": 0, "TEXCOORD_0": 1 }, "indices": 2, "mode": 5 },
+                    { "attributes": { "POSITION": 0 }, "mode": 0 },
+                ],
+            },
+            { "primitives": [{ "attributes": { "POSITION": 0 } }] },
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3" },
+            {
+                "bufferView": 0,
+                "byteOffset": 12,
+                "componentType": 5121,
+                "normalized": true,
+                "count": 4,
+                "type": "VEC2",
+            },
+            { "bufferView": 1, "componentType": 5123, "count": 4, "type": "SCALAR" },
+        ],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 64, "byteStride": 16 },
+            { "buffer": 0, "byteOffset": 64, "byteLength": 8 },
+        ],
+        "buffers": [{ "byteLength": 72 }],
+    });
+    let meshes = gltf::read(make_glb(&json, &bin).as_slice()).unwrap();
+    // the primitive of points is skipped
+    assert_eq!(meshes.len(), 2);
+    assert_eq!(meshes[0].0, "square");
+    assert_eq!(meshes[1].0, "mesh1");
+    let square = &meshes[0].1;
+    assert_eq!(square.positions()[3], Point3::new(1.0, 1.0, 0.0));
+    assert_eq!(square.uv_coords()[1], Vector2::new(0.0, 1.0));
+    assert_eq!(square.uv_coords()[2], Vector2::new(1.0, 0.0));
+    assert!(square.normals().is_empty());
+    // the triangles of the strip have the same orientation
+    let faces: Vec<[usize; 3]> = square
+        .tri_faces()
+        .iter()
+        .map(|tri| [tri[0].pos, tri[1].pos, tri[2].pos])
+        .collect();
+    assert_eq!(faces, vec![[0, 1, 2], [2, 1, 3]]);
+    // the triangles without indices
+    let faces: Vec<[usize; 3]> = meshes[1]
+        .1
+        .tri_faces()
+        .iter()
+        .map(|tri| [tri[0].pos, tri[1].pos, tri[2].pos])
+        .collect();
+    assert_eq!(faces, vec![[0, 1, 2]]);
+}
+
+#[test]
+fn invalid_gltf_test() {
+    let json = serde_json::json!({
+        "asset": { "version": "2.0" },
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }],
+        "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "buffers": [{ "byteLength": 36, "uri": "triangle.bin" }],
+    });
+    let bytes = serde_json::to_vec(&json).unwrap();
+    // buffers in external files are not supported
+    assert!(gltf::read(bytes.as_slice()).is_err());
+    // the buffer is too short
+    assert!(gltf::read(make_glb(&json_without_uri(json), &[0; 24]).as_slice()).is_err());
+}
+
+fn json_without_uri(mut json: Value) -> Value {
+    json["buffers"][0].as_object_mut().unwrap().remove("uri");
+    json
+}
+
+#[test]
+fn read_gltf_with_context_test() {
+    let mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let mut bytes = Vec::new();
+    gltf::write(&mesh, &mut bytes, GLTFFormat::Binary, None).unwrap();
+    let context = ModelContext {
+        length_unit: LengthUnit::Millimeter,
+        ..Default::default()
+    };
+    let meshes = gltf::read_with_context(bytes.as_slice(), &context).unwrap();
+    let (max0, max1) = (*mesh.bounding_box().max(), *meshes[0].1.bounding_box().max());
+    (0..3).for_each(|i| assert!(f64::abs(max0[i] * 1000.0 - max1[i]) < 1.0e-3 * max1[i].abs()));
+}
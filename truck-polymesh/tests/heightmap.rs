@@ -0,0 +1,57 @@
+#![cfg(feature = "heightmap")]
+
+use image::{DynamicImage, GrayImage, ImageBuffer, Luma};
+use truck_polymesh::*;
+
+#[test]
+fn heightmap_orientation_test() {
+    // the top row of the image is white, so the terrain rises to the back
+    let image = GrayImage::from_fn(4, 3, |_, j| Luma([255 - 127 * j as u8]));
+    let image = DynamicImage::ImageLuma8(image);
+    let mesh = StructuredMesh::from_heightmap(&image, (-1.0, 2.0), (10.0, 14.0), 3.0);
+    let positions = mesh.positions();
+    assert_eq!(positions.len(), 4);
+    assert!(positions.iter().all(|row| row.len() == 3));
+    assert!(positions[0][0].near(&Point3::new(-1.0, 10.0, 3.0 / 255.0)));
+    assert!(positions[3][2].near(&Point3::new(2.0, 14.0, 3.0)));
+    let (udiv, vdiv) = mesh.uv_division().unwrap();
+    assert_eq!(udiv, &vec![0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0]);
+    assert_eq!(vdiv, &vec![0.0, 0.5, 1.0]);
+    // the normals lean to the front, and agree with the orientation of the faces
+    let normal = mesh.normals().unwrap()[1][1];
+    assert!(normal[0].so_small() && normal[1] < 0.0 && normal[2] > 0.0);
+    let mesh = mesh.destruct();
+    let face = mesh.face_iter().next().unwrap();
+    let p: Vec<Point3> = face.iter().map(|v| mesh.positions()[v.pos]).collect();
+    let face_normal = (p[1] - p[0]).cross(p[2] - p[0]);
+    assert!(face_normal.dot(normal) > 0.0);
+}
+
+#[test]
+fn heightmap_16bit_test() {
+    let image = ImageBuffer::<Luma<u16>, Vec<u16>>::from_fn(2, 2, |i, j| Luma([(i + j) as u16]));
+    let image = DynamicImage::ImageLuma16(image);
+    let mesh = StructuredMesh::from_heightmap(&image, (0.0, 1.0), (0.0, 1.0), 65535.0);
+    // the precision of 16-bit images is kept
+    assert!(mesh.positions()[1][0][2].near(&2.0));
+    assert!(mesh.positions()[0][1][2].near(&0.0));
+
+    let line = DynamicImage::ImageLuma8(GrayImage::new(5, 1));
+    assert!(matches!(
+        StructuredMesh::try_from_heightmap(&line, (0.0, 1.0), (0.0, 1.0), 1.0),
+        Err(errors::Error::TooSmallHeightmap),
+    ));
+}
+
+#[test]
+fn heightmap_empty_range_test() {
+    let image = DynamicImage::ImageLuma8(GrayImage::new(2, 2));
+    assert!(matches!(
+        StructuredMesh::try_from_heightmap(&image, (1.0, 1.0), (0.0, 1.0), 1.0),
+        Err(errors::Error::EmptyHeightmapRange),
+    ));
+    assert!(matches!(
+        StructuredMesh::try_from_heightmap(&image, (0.0, 1.0), (2.0, 2.0), 1.0),
+        Err(errors::Error::EmptyHeightmapRange),
+    ));
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "laz")]
+
+use laz_rs::{LasZipCompressor, LazItemRecordBuilder, LazVlr};
+use std::io::{Cursor, Write};
+use truck_polymesh::*;
+
+const TEAPOT_WITH_NORMALS_OBJ: &[u8] = include_bytes!("data/teapot-with-normals.obj");
+
+/// the size of the header of LAS 1.2
+const HEADER_SIZE: usize = 227;
+
+/// Compresses LAS 1.2 of the point data record format 2 written by `las::write`.
+fn compress(las: &[u8]) -> Vec<u8> {
+    let items = LazItemRecordBuilder::default_for_point_format_id(2, 0).unwrap();
+    let vlr = LazVlr::from_laz_items(items);
+    let mut vlr_data = Vec::new();
+    vlr.write_to(&mut vlr_data).unwrap();
+
+    let mut header = las[..HEADER_SIZE].to_vec();
+    let offset = HEADER_SIZE as u32 + 54 + vlr_data.len() as u32;
+    header[96..100].copy_from_slice(&offset.to_le_bytes());
+    header[100..104].copy_from_slice(&1_u32.to_le_bytes());
+    header[104] |= 0x80;
+    let mut vlr_header = [0_u8; 54];
+    vlr_header[2..2 + LazVlr::USER_ID.len()].copy_from_slice(LazVlr::USER_ID.as_bytes());
+    vlr_header[18..20].copy_from_slice(&LazVlr::RECORD_ID.to_le_bytes());
+    vlr_header[20..22].copy_from_slice(&(vlr_data.len() as u16).to_le_bytes());
+
+    let mut laz = Cursor::new(Vec::new());
+    laz.write_all(&header).unwrap();
+    laz.write_all(&vlr_header).unwrap();
+    laz.write_all(&vlr_data).unwrap();
+    let mut compressor = LasZipCompressor::new(laz, vlr).unwrap();
+    compressor.compress_many(&las[HEADER_SIZE..]).unwrap();
+    compressor.done().unwrap();
+    compressor.into_inner().into_inner()
+}
+
+#[test]
+fn laz_read_test() {
+    let mesh = obj::read(TEAPOT_WITH_NORMALS_OBJ).unwrap();
+    let mut cloud0 = PointCloud::from_positions(mesh.positions().clone());
+    let colors = (0..cloud0.len())
+        .map(|i| Vector4::new((i % 256) as f64 / 255.0, 0.0, 1.0, 1.0))
+        .collect();
+    cloud0.set_colors(colors).unwrap();
+    let mut bytes = Vec::new();
+    las::write(&cloud0, &mut bytes).unwrap();
+    let cloud1 = las::read(bytes.as_slice()).unwrap();
+
+    let laz = compress(&bytes);
+    assert!(laz.len() < bytes.len());
+    let cloud2 = las::read(laz.as_slice()).unwrap();
+    assert_eq!(cloud1, cloud2);
+
+    // the truncated data
+    assert!(las::read(&laz[..laz.len() / 2]).is_err());
+    // the LASzip record is missing
+    let mut laz = laz;
+    laz[100..104].copy_from_slice(&0_u32.to_le_bytes());
+    assert!(las::read(laz.as_slice()).is_err());
+}
@@ -246,3 +246,276 @@ fn incomplete_obj_oi_test() {
     let read_mesh = obj::read(AsRef::<[u8]>::as_ref(&gened_obj)).unwrap();
     assert_eq!(mesh, read_mesh);
 }
+
+#[test]
+fn materials_obj_test() {
+    let model = obj::read_with_materials(PONY_COMPLETE_OBJ, |path| {
+        assert_eq!(path, "Pony_cartoon.mtl");
+        Ok(b"newmtl Body_SG1\nKd 0.8 0.6 0.4\nmap_Kd Body.png\n".as_ref())
+    })
+    .unwrap();
+    assert_eq!(model.mesh, obj::read(PONY_COMPLETE_OBJ).unwrap());
+    assert_eq!(model.face_materials.len(), model.mesh.faces().len());
+    let names: Vec<&str> = model.materials.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["Body_SG1", "Ground_SG", "Windows_SG", "Interior_SG"]);
+    assert_eq!(model.materials[0].diffuse, Some([0.8, 0.6, 0.4]));
+    assert_eq!(model.materials[0].diffuse_texture.as_deref(), Some("Body.png"));
+    // the materials not defined in the MTL files have no property
+    assert_eq!(model.materials[1].diffuse, None);
+    assert!(model.face_materials.iter().all(Option::is_some));
+    (0..4).for_each(|i| assert!(model.face_materials.contains(&Some(i))));
+
+    // the missing MTL files
+    let res = obj::read_with_materials(PONY_COMPLETE_OBJ, |path| std::fs::File::open(path));
+    assert!(res.is_err());
+}
+
+#[test]
+fn materials_face_order_test() {
+    // the material of each face follows the order of `face_iter`
+    let obj = b"mtllib a.mtl b.mtl
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0.5 1.5 0
+usemtl quad
+f 1 2 3 4
+usemtl pentagon
+f 1 2 3 5 4
+usemtl triangle
+f 1 2 3
+f 1 3 4
+usemtl quad
+f 4 3 2 1
+";
+    let mtl_a = b"# the first library
+newmtl triangle
+Kd 1 0 0
+Ks 0.5 0.5 0.5
+Ns 10
+d 0.5
+newmtl quad
+Tr 0.25
+";
+    let mtl_b = b"newmtl pentagon\nmap_Kd textures/pentagon.png\n";
+    let dir = std::env::temp_dir().join("truck-polymesh-obj-materials");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("model.obj"), obj).unwrap();
+    std::fs::write(dir.join("a.mtl"), mtl_a).unwrap();
+    std::fs::write(dir.join("b.mtl"), mtl_b).unwrap();
+    let model = obj::read_file_with_materials(dir.join("model.obj")).unwrap();
+    assert_eq!(model.face_materials, [Some(0), Some(0), Some(1), Some(1), Some(2)]);
+    let materials = &model.materials;
+    assert_eq!(materials[0].name, "triangle");
+    assert_eq!(materials[0].diffuse, Some([1.0, 0.0, 0.0]));
+    assert_eq!(materials[0].specular, Some([0.5, 0.5, 0.5]));
+    assert_eq!(materials[0].shininess, Some(10.0));
+    assert_eq!(materials[0].dissolve, Some(0.5));
+    assert_eq!(materials[1].name, "quad");
+    assert_eq!(materials[1].dissolve, Some(0.75));
+    assert_eq!(materials[2].name, "pentagon");
+    let texture = materials[2].diffuse_texture.as_deref();
+    assert_eq!(texture, Some("textures/pentagon.png"));
+
+    assert!(obj::read_mtl(b"Kd 1 0 0\n".as_ref()).is_err());
+    assert!(obj::read_mtl(b"newmtl a\nKd 1 0\n".as_ref()).is_err());
+}
+
+#[test]
+fn grouped_obj_ioi_test() {
+    let meshes = vec![
+        ("teapot".to_string(), obj::read(TEAPOT_WITHNORMALS_OBJ).unwrap()),
+        ("skull".to_string(), obj::read(SKULL_WITHTEXCOORD_OBJ).unwrap()),
+        ("pony".to_string(), obj::read(PONY_COMPLETE_OBJ).unwrap()),
+    ];
+    let mut gened_obj: Vec<u8> = Vec::new();
+    obj::write_grouped(&meshes, &mut gened_obj).unwrap();
+    let read_meshes = obj::read_grouped(gened_obj.as_slice()).unwrap();
+    assert_eq!(read_meshes.len(), meshes.len());
+    // the unused attributes are removed
+    let attributes = |mesh: &PolygonMesh| -> Vec<_> {
+        mesh.face_iter()
+            .flatten()
+            .map(|v| {
+                let uv = v.uv.map(|uv| mesh.uv_coords()[uv]);
+                let nor = v.nor.map(|nor| mesh.normals()[nor]);
+                (mesh.positions()[v.pos], uv, nor)
+            })
+            .collect()
+    };
+    for ((name0, mesh0), (name1, mesh1)) in read_meshes.iter().zip(&meshes) {
+        assert_eq!(name0, name1);
+        assert_eq!(mesh0.faces().len(), mesh1.faces().len());
+        assert_eq!(attributes(mesh0), attributes(mesh1));
+    }
+
+    // the indices of the later meshes are shifted by the attributes of the former meshes
+    let meshes: Vec<PolygonMesh> = meshes.into_iter().map(|(_, mesh)| mesh).collect();
+    let mut gened_obj: Vec<u8> = Vec::new();
+    obj::write_vec(&meshes, &mut gened_obj).unwrap();
+    let mut merged = PolygonMesh::default();
+    meshes.iter().for_each(|mesh| merged.merge(mesh.clone()));
+    assert_eq!(obj::read(gened_obj.as_slice()).unwrap(), merged);
+}
+
+#[test]
+fn grouped_obj_read_test() {
+    let obj = b"v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+vn 0 0 1
+f 1 2 3
+g left side
+f 1//1 3//1 4//1
+o right
+f 1 2 4 3
+g left side
+f 2 4 3
+";
+    let meshes = obj::read_grouped(obj.as_ref()).unwrap();
+    let names: Vec<&str> = meshes.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["default", "left side", "right"]);
+    let left = &meshes[1].1;
+    assert_eq!(left.positions().len(), 4);
+    assert_eq!(left.normals().len(), 1);
+    assert_eq!(left.faces().len(), 2);
+    let faces: Vec<_> = left.face_iter().collect();
+    assert_eq!(left.positions()[faces[1][0].pos], Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(meshes[2].1.faces().quad_faces().len(), 1);
+}
+
+#[test]
+fn colored_obj_ioi_test() {
+    let mut mesh0 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let colors: Vec<Vector4> = mesh0
+        .positions()
+        .iter()
+        .map(|p| Vector4::new(p[0].abs() % 1.0, p[1].abs() % 1.0, p[2].abs() % 1.0, 1.0))
+        .collect();
+    mesh0.set_position_colors(colors).unwrap();
+    let mut bytes = Vec::new();
+    obj::write(&mesh0, &mut bytes).unwrap();
+    assert!(String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter(|line| line.starts_with("v "))
+        .all(|line| line.split_whitespace().count() == 7));
+    let mesh1 = obj::read(bytes.as_slice()).unwrap();
+    assert_eq!(mesh0.colors().len(), mesh1.colors().len());
+    mesh0
+        .face_iter()
+        .flatten()
+        .zip(mesh1.face_iter().flatten())
+        .for_each(|(v0, v1)| {
+            let col0 = mesh0.colors()[v0.col.unwrap()];
+            assert!(col0.near(&mesh1.colors()[v1.col.unwrap()]));
+        });
+}
+
+#[test]
+fn colored_obj_read_test() {
+    let obj = b"v 0 0 0 1 0 0
+v 1 0 0 0 1 0
+v 0 1 0 0 0 1
+v 1 1 0
+f 1 2 3
+f 2 4 3
+";
+    // the colors are ignored since the last position has no color.
+    let mesh = obj::read(obj.as_ref()).unwrap();
+    assert!(mesh.colors().is_empty());
+    assert!(mesh.face_iter().flatten().all(|v| v.col.is_none()));
+
+    let obj = b"v 0 0 0 1 0 0
+v 1 0 0 0 1 0
+v 0 1 0 0 0 1
+f 1 2 3
+";
+    let mesh = obj::read(obj.as_ref()).unwrap();
+    assert_eq!(
+        mesh.colors()[mesh.faces()[0][1].col.unwrap()],
+        Vector4::new(0.0, 1.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn write_options_obj_ioi_test() {
+    let read_mesh0 = obj::read(PONY_COMPLETE_OBJ).unwrap();
+
+    // the shortest notation is lossless, and the relative indices are restored
+    let options = obj::OBJWriteOptions {
+        float_format: obj::OBJFloatFormat::Shortest,
+        relative_indices: true,
+        header: Some("pony\n\nCC Attribution".to_string()),
+        line_ending: obj::OBJLineEnding::CrLf,
+        ..Default::default()
+    };
+    let mut gened_obj: Vec<u8> = Vec::new();
+    obj::write_with_options(&read_mesh0, &mut gened_obj, &options).unwrap();
+    assert!(gened_obj.starts_with(b"# pony\r\n#\r\n# CC Attribution\r\n"));
+    let read_mesh1 = obj::read(gened_obj.as_slice()).unwrap();
+    assert_eq!(read_mesh0, read_mesh1);
+
+    // the omitted attributes are removed from the faces
+    let options = obj::OBJWriteOptions {
+        float_format: obj::OBJFloatFormat::Decimal(4),
+        write_uv_coords: false,
+        write_normals: false,
+        ..Default::default()
+    };
+    let mut gened_obj: Vec<u8> = Vec::new();
+    obj::write_with_options(&read_mesh0, &mut gened_obj, &options).unwrap();
+    let read_mesh1 = obj::read(gened_obj.as_slice()).unwrap();
+    assert!(read_mesh1.uv_coords().is_empty() && read_mesh1.normals().is_empty());
+    assert_eq!(read_mesh0.faces().len(), read_mesh1.faces().len());
+    read_mesh0
+        .face_iter()
+        .flatten()
+        .zip(read_mesh1.face_iter().flatten())
+        .for_each(|(v0, v1)| {
+            assert_eq!(*v1, Vertex::from(v0.pos));
+            let (p0, p1) = (read_mesh0.positions()[v0.pos], read_mesh1.positions()[v1.pos]);
+            assert!((p0 - p1).magnitude() < 1.0e-4);
+        });
+}
+
+#[test]
+fn progress_obj_ioi_test() {
+    let token = progress::CancellationToken::new();
+    let mut log = Vec::new();
+    let progress = |done, total| {
+        log.push((done, total));
+        !token.is_cancelled()
+    };
+    let len = SKULL_WITHTEXCOORD_OBJ.len();
+    let read_mesh0 = obj::read_with_progress(SKULL_WITHTEXCOORD_OBJ, len, progress).unwrap();
+    assert_eq!(read_mesh0, obj::read(SKULL_WITHTEXCOORD_OBJ).unwrap());
+    assert!(log.windows(2).all(|a| a[0].0 < a[1].0));
+    assert_eq!(log.last(), Some(&(len, len)));
+
+    // cancels from the other thread in writing
+    let options = obj::OBJWriteOptions::default();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let cloned = token.clone();
+    let canceller = std::thread::spawn(move || {
+        receiver.recv().unwrap();
+        cloned.cancel();
+    });
+    let mut sent = false;
+    let progress = |done: usize, total: usize| {
+        if !sent && done > total / 2 {
+            sender.send(()).unwrap();
+            sent = true;
+            // waits for cancelling
+            while !token.is_cancelled() {
+                std::thread::yield_now();
+            }
+        }
+        !token.is_cancelled()
+    };
+    let mut gened_obj = Vec::new();
+    let res = obj::write_with_progress(&read_mesh0, &mut gened_obj, &options, progress);
+    canceller.join().unwrap();
+    assert!(matches!(res, Err(errors::Error::Cancelled)));
+}
@@ -0,0 +1,59 @@
+use truck_polymesh::*;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+#[test]
+fn off_io_test() {
+    let mesh0 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let mut bytes = Vec::new();
+    off::write(&mesh0, &mut bytes).unwrap();
+    let mesh1 = off::read(bytes.as_slice()).unwrap();
+    assert_eq!(mesh0.positions(), mesh1.positions());
+    mesh0
+        .face_iter()
+        .zip(mesh1.face_iter())
+        .for_each(|(face0, face1)| {
+            assert_eq!(face0.len(), face1.len());
+            face0
+                .iter()
+                .zip(face1)
+                .for_each(|(v0, v1)| assert_eq!(v0.pos, v1.pos));
+        });
+}
+
+#[test]
+fn off_read_with_comments() {
+    let off = b"OFF # header
+# comment line
+4 2 0
+0.0 0.0 0.0
+1.0 0.0 0.0
+1.0 1.0 0.0 # inline comment
+0.0 1.0 0.0
+3 0 1 2 255 0 0
+3 0 2 3
+";
+    let mesh = off::read(off.as_ref()).unwrap();
+    assert_eq!(mesh.positions().len(), 4);
+    assert_eq!(mesh.faces().len(), 2);
+    assert_eq!(mesh.faces()[1][2].pos, 3);
+}
+
+#[test]
+fn off_io_with_context() {
+    let mesh0 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let context = ModelContext::new(LengthUnit::Millimeter, 1.0e-6);
+    let mut bytes = Vec::new();
+    off::write_with_context(&mesh0, &mut bytes, LengthUnit::Inch, &context).unwrap();
+    let inches = off::read(bytes.as_slice()).unwrap();
+    let mesh1 = off::read_with_context(bytes.as_slice(), LengthUnit::Inch, &context).unwrap();
+    mesh0
+        .positions()
+        .iter()
+        .zip(inches.positions())
+        .zip(mesh1.positions())
+        .for_each(|((p0, p), p1)| {
+            assert!((p0 / 25.4).distance(*p) < 1.0e-9);
+            assert!(p0.distance(*p1) < 1.0e-8);
+        });
+}
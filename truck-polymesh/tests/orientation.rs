@@ -0,0 +1,70 @@
+use truck_polymesh::*;
+
+const PONY_OBJ: &[u8] = include_bytes!("data/pony-complete.obj");
+
+#[test]
+fn align_principal_axes_of_pony() {
+    let mut mesh = obj::read(PONY_OBJ).unwrap();
+    let original = mesh.clone();
+    let trans = mesh.align_principal_axes();
+    // the transform is a rigid motion.
+    assert!(trans.determinant().near(&1.0));
+    original
+        .positions()
+        .iter()
+        .zip(mesh.positions())
+        .for_each(|(p, q)| assert!(trans.transform_point(*p).near(q)));
+
+    // aligned again, nothing changes except the directions of the axes.
+    let trans = mesh.align_principal_axes();
+    assert!(trans
+        .transform_point(Point3::origin())
+        .near(&Point3::origin()));
+    (0..3).for_each(|i| assert!(f64::abs(trans[i][i]).near(&1.0)));
+}
+
+#[test]
+fn auto_orient_of_pony() {
+    let mut mesh = obj::read(PONY_OBJ).unwrap();
+    let descriptor = OrientationDescriptor::default();
+    let before = mesh.support_volume(descriptor.overhang_angle);
+    let trans = mesh.auto_orient(&descriptor);
+    let after = mesh.support_volume(descriptor.overhang_angle);
+    assert!(after <= before + TOLERANCE, "{} {}", after, before);
+    assert!(trans.determinant().near(&1.0));
+    // on the build plate
+    assert!(mesh.bounding_box().min()[2].so_small());
+}
+
+#[test]
+fn align_principal_axes_of_empty_mesh() {
+    let mut mesh = PolygonMesh::default();
+    let trans = mesh.align_principal_axes();
+    assert_eq!(trans, Matrix4::identity());
+}
+
+#[test]
+fn align_principal_axes_of_degenerate_triangle() {
+    let positions = vec![Point3::new(1.0, 2.0, 3.0); 3];
+    let faces = Faces::from_iter([[0, 1, 2]]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let trans = mesh.align_principal_axes();
+    assert!(trans.determinant().near(&1.0));
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!(p.near(&Point3::origin())));
+
+    // collinear points
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(0.0, 0.0, 3.0),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2]]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let trans = mesh.align_principal_axes();
+    assert!(trans.determinant().near(&1.0));
+    mesh.positions()
+        .iter()
+        .for_each(|p| assert!(p[1].so_small() && p[2].so_small()));
+}
@@ -0,0 +1,224 @@
+use truck_polymesh::*;
+use ply::PLYFormat;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+// https://sketchfab.com/3d-models/pony-cartoon-885d9f60b3a9429bb4077cfac5653cf9
+// Pony Cartoon - CC Attribution © Slava Z.
+const PONY_COMPLETE_OBJ: &[u8] = include_bytes!("data/pony-complete.obj");
+
+// the attributes of face vertices are the same
+fn same_attributes(mesh0: &PolygonMesh, mesh1: &PolygonMesh) {
+    assert_eq!(mesh0.faces().len(), mesh1.faces().len());
+    mesh0
+        .face_iter()
+        .zip(mesh1.face_iter())
+        .for_each(|(face0, face1)| {
+            assert_eq!(face0.len(), face1.len());
+            face0.iter().zip(face1).for_each(|(v0, v1)| {
+                assert_eq!(mesh0.positions()[v0.pos], mesh1.positions()[v1.pos]);
+                let uv0 = v0.uv.map(|uv| mesh0.uv_coords()[uv]);
+                assert_eq!(uv0, v1.uv.map(|uv| mesh1.uv_coords()[uv]));
+                let nor0 = v0.nor.map(|nor| mesh0.normals()[nor]);
+                assert_eq!(nor0, v1.nor.map(|nor| mesh1.normals()[nor]));
+            });
+        });
+}
+
+#[test]
+fn position_ply_io_test() {
+    let mesh0 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    for format in [PLYFormat::ASCII, PLYFormat::BinaryLittleEndian] {
+        let mut bytes = Vec::new();
+        ply::write(&mesh0, &mut bytes, format).unwrap();
+        let mesh1 = ply::read(bytes.as_slice()).unwrap();
+        assert_eq!(mesh0, mesh1);
+    }
+}
+
+#[test]
+fn complete_ply_io_test() {
+    let mesh0 = obj::read(PONY_COMPLETE_OBJ).unwrap();
+    assert!(!mesh0.uv_coords().is_empty() && !mesh0.normals().is_empty());
+    for format in [PLYFormat::ASCII, PLYFormat::BinaryLittleEndian] {
+        let mut bytes = Vec::new();
+        ply::write(&mesh0, &mut bytes, format).unwrap();
+        let mesh1 = ply::read(bytes.as_slice()).unwrap();
+        assert_eq!(mesh1.positions().len(), mesh1.normals().len());
+        assert_eq!(mesh1.positions().len(), mesh1.uv_coords().len());
+        same_attributes(&mesh0, &mesh1);
+    }
+}
+
+#[test]
+fn read_ascii_ply_with_other_properties() {
+    let ply = b"ply
+format ascii 1.0
+comment made by hand
+element vertex 4
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+property float s
+property float t
+element face 1
+property list uchar uint vertex_index
+property int flags
+element edge 1
+property int vertex1
+property int vertex2
+end_header
+0 0 0 255 0 0 0 0
+1 0 0 0 255 0 1 0
+1 1 0 0 0 255 1 1
+0 1 0 255 255 255 0 1
+4 0 1 2 3 7
+0 2
+";
+    let mesh = ply::read(ply.as_ref()).unwrap();
+    assert_eq!(mesh.positions().len(), 4);
+    assert!(mesh.normals().is_empty());
+    assert_eq!(mesh.uv_coords()[2], Vector2::new(1.0, 1.0));
+    assert_eq!(mesh.faces().len(), 1);
+    let face = &mesh.faces()[0];
+    assert_eq!(face.len(), 4);
+    let vertex = Vertex {
+        pos: 2,
+        uv: Some(2),
+        nor: None,
+        col: Some(2),
+    };
+    assert_eq!(face[2], vertex);
+    assert_eq!(mesh.colors()[2], Vector4::new(0.0, 0.0, 1.0, 1.0));
+}
+
+#[test]
+fn read_big_endian_ply() {
+    let mut ply = b"ply
+format binary_big_endian 1.0
+element vertex 3
+property double x
+property double y
+property double z
+property float nx
+property float ny
+property float nz
+element face 1
+property list uchar ushort vertex_indices
+end_header
+"
+    .to_vec();
+    let positions = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    positions.iter().for_each(|p: &[f64; 3]| {
+        p.iter().for_each(|x| ply.extend(&x.to_be_bytes()));
+        [0.0f32, 0.0, 1.0].iter().for_each(|x| ply.extend(&x.to_be_bytes()));
+    });
+    ply.push(3);
+    [0u16, 1, 2].iter().for_each(|i| ply.extend(&i.to_be_bytes()));
+    let mesh = ply::read(ply.as_slice()).unwrap();
+    assert_eq!(mesh.positions()[1], Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(mesh.normals()[2], Vector3::new(0.0, 0.0, 1.0));
+    assert_eq!(mesh.faces()[0][1], (1, None, Some(1)).into());
+
+    // writing big endian is not supported.
+    let mut bytes = Vec::new();
+    assert!(ply::write(&mesh, &mut bytes, PLYFormat::BinaryBigEndian).is_err());
+}
+
+#[test]
+fn read_broken_ply() {
+    // the face refers the vertex out of range.
+    let ply = b"ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+0 1 0
+3 0 1 3
+";
+    assert!(ply::read(ply.as_ref()).is_err());
+    // the data is truncated.
+    assert!(ply::read(&ply[..ply.len() - 8]).is_err());
+    // not a PLY file
+    assert!(ply::read(b"OFF\n3 1 0\n".as_ref()).is_err());
+}
+
+#[test]
+fn ply_io_with_context() {
+    let mesh0 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let context = ModelContext::new(LengthUnit::Millimeter, 1.0e-6);
+    let format = PLYFormat::BinaryLittleEndian;
+    let mut bytes = Vec::new();
+    ply::write_with_context(&mesh0, &mut bytes, format, LengthUnit::Inch, &context).unwrap();
+    let inches = ply::read(bytes.as_slice()).unwrap();
+    let mesh1 = ply::read_with_context(bytes.as_slice(), LengthUnit::Inch, &context).unwrap();
+    mesh0
+        .positions()
+        .iter()
+        .zip(inches.positions())
+        .zip(mesh1.positions())
+        .for_each(|((p0, p), p1)| {
+            assert!((p0 / 25.4).distance(*p) < 1.0e-9);
+            assert!(p0.distance(*p1) < 1.0e-8);
+        });
+}
+
+#[test]
+fn colored_ply_io_test() {
+    let mut mesh0 = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let colors: Vec<Vector4> = (0..mesh0.positions().len())
+        .map(|i| Vector4::new((i % 256) as f64 / 255.0, 0.0, 1.0, 0.2))
+        .collect();
+    mesh0.set_position_colors(colors).unwrap();
+    for format in [PLYFormat::ASCII, PLYFormat::BinaryLittleEndian] {
+        let mut bytes = Vec::new();
+        ply::write(&mesh0, &mut bytes, format).unwrap();
+        let mesh1 = ply::read(bytes.as_slice()).unwrap();
+        assert_eq!(mesh0.positions(), mesh1.positions());
+        mesh0
+            .face_iter()
+            .flatten()
+            .zip(mesh1.face_iter().flatten())
+            .for_each(|(v0, v1)| {
+                let col0 = mesh0.colors()[v0.col.unwrap()];
+                let col1 = mesh1.colors()[v1.col.unwrap()];
+                assert!((col0 - col1).magnitude() < 1.0 / 255.0);
+            });
+    }
+}
+
+#[test]
+fn read_ply_with_float_colors() {
+    let ply = b"ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+property float red
+property float green
+property float blue
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 1 0 0
+1 0 0 0 0.5 0
+0 1 0 0 0 1
+3 0 1 2
+";
+    let mesh = ply::read(ply.as_ref()).unwrap();
+    let vertex = mesh.faces()[0][1];
+    assert_eq!(
+        mesh.colors()[vertex.col.unwrap()],
+        Vector4::new(0.0, 0.5, 0.0, 1.0)
+    );
+}
@@ -0,0 +1,168 @@
+use truck_polymesh::*;
+
+const TEAPOT_WITH_NORMALS_OBJ: &[u8] = include_bytes!("data/teapot-with-normals.obj");
+
+fn teapot_cloud() -> PointCloud {
+    let mesh = obj::read(TEAPOT_WITH_NORMALS_OBJ).unwrap();
+    let mut cloud = PointCloud::from(&mesh);
+    let colors = (0..cloud.len())
+        .map(|i| Vector4::new((i % 256) as f64 / 255.0, 0.0, 1.0, 1.0))
+        .collect();
+    cloud.set_colors(colors).unwrap();
+    cloud
+}
+
+#[test]
+fn point_cloud_from_mesh() {
+    let mesh = obj::read(TEAPOT_WITH_NORMALS_OBJ).unwrap();
+    let cloud = PointCloud::from(&mesh);
+    assert_eq!(cloud.positions(), mesh.positions());
+    assert_eq!(cloud.normals().len(), cloud.len());
+    assert!(cloud.colors().is_empty());
+    assert_eq!(cloud.bounding_box(), mesh.bounding_box());
+
+    let mesh = cloud.clone().into_polygon();
+    assert_eq!(mesh.positions(), cloud.positions());
+    assert_eq!(mesh.faces().len(), 0);
+}
+
+#[test]
+fn xyz_io_test() {
+    let cloud0 = teapot_cloud();
+    let mut bytes = Vec::new();
+    xyz::write(&cloud0, &mut bytes).unwrap();
+    let cloud1 = xyz::read(bytes.as_slice()).unwrap();
+    assert_eq!(cloud0.len(), cloud1.len());
+    for i in 0..cloud0.len() {
+        assert!(cloud0.positions()[i].near(&cloud1.positions()[i]));
+        assert!(cloud0.normals()[i].near(&cloud1.normals()[i]));
+        assert!(cloud0.colors()[i].near(&cloud1.colors()[i]));
+    }
+}
+
+#[test]
+fn xyz_read_variants() {
+    let xyz = b"# comment line
+0.0, 0.0, 0.0
+1.0 0.0 0.0
+
+0.0,1.0,0.0
+";
+    let cloud = xyz::read(xyz.as_ref()).unwrap();
+    assert_eq!(cloud.len(), 3);
+    assert_eq!(cloud.positions()[2], Point3::new(0.0, 1.0, 0.0));
+    assert!(cloud.normals().is_empty());
+
+    let xyz = b"0 0 0 0 0 1 255 0 0\n1 0 0 0 0 1 0 255 0\n";
+    let cloud = xyz::read(xyz.as_ref()).unwrap();
+    assert_eq!(cloud.normals()[1], Vector3::unit_z());
+    assert_eq!(cloud.colors()[1], Vector4::new(0.0, 1.0, 0.0, 1.0));
+
+    let xyz = b"0 0 0\n1 0 0 0 0 1\n";
+    assert!(xyz::read(xyz.as_ref()).is_err());
+    let xyz = b"0 0\n";
+    assert!(xyz::read(xyz.as_ref()).is_err());
+}
+
+#[test]
+fn pcd_io_test() {
+    let cloud0 = teapot_cloud();
+    for format in [pcd::PCDFormat::ASCII, pcd::PCDFormat::Binary] {
+        let mut bytes = Vec::new();
+        pcd::write(&cloud0, &mut bytes, format).unwrap();
+        let cloud1 = pcd::read(bytes.as_slice()).unwrap();
+        assert_eq!(cloud0, cloud1);
+    }
+    let cloud0 = PointCloud::from_positions(cloud0.positions().clone());
+    let mut bytes = Vec::new();
+    pcd::write(&cloud0, &mut bytes, pcd::PCDFormat::Binary).unwrap();
+    assert_eq!(cloud0, pcd::read(bytes.as_slice()).unwrap());
+}
+
+#[test]
+fn pcd_read_pcl_ascii() {
+    // the packed rgb as a float, the extra field and the invalid point
+    let pcd = b"# .PCD v.7 - Point Cloud Data file format
+VERSION .7
+FIELDS x y z rgb intensity
+SIZE 4 4 4 4 2
+TYPE F F F F U
+COUNT 1 1 1 1 1
+WIDTH 3
+HEIGHT 1
+VIEWPOINT 0 0 0 1 0 0 0
+POINTS 3
+DATA ascii
+0.5 0.25 1 9.14767638e-41 10
+nan nan nan 0 0
+-1 2 3 2.34180515e-38 20
+";
+    let cloud = pcd::read(pcd.as_ref()).unwrap();
+    assert_eq!(cloud.len(), 2);
+    assert_eq!(cloud.positions()[0], Point3::new(0.5, 0.25, 1.0));
+    assert_eq!(cloud.positions()[1], Point3::new(-1.0, 2.0, 3.0));
+    assert!(cloud.normals().is_empty());
+    // 9.14767638e-41 is (0, 255, 0) in the packed rgb
+    let color = cloud.colors()[0] * 255.0;
+    assert_eq!(color.map(f64::round), Vector4::new(0.0, 255.0, 0.0, 255.0));
+    // 2.34180515e-38 is (255, 0, 0) in the packed rgb
+    let color = cloud.colors()[1] * 255.0;
+    assert_eq!(color.map(f64::round), Vector4::new(255.0, 0.0, 0.0, 255.0));
+}
+
+#[test]
+fn pcd_read_pcl_binary() {
+    let mut pcd = b"VERSION .7
+FIELDS normal_x normal_y normal_z x y z
+SIZE 4 4 4 4 4 4
+TYPE F F F F F F
+COUNT 1 1 1 1 1 1
+WIDTH 2
+HEIGHT 1
+POINTS 2
+DATA binary
+"
+    .to_vec();
+    for x in [0.0_f32, 0.0, 1.0, 1.0, 2.0, 3.0, 1.0, 0.0, 0.0, -1.0, -2.0, -3.0] {
+        pcd.extend(x.to_le_bytes());
+    }
+    let cloud = pcd::read(pcd.as_slice()).unwrap();
+    assert_eq!(cloud.positions()[0], Point3::new(1.0, 2.0, 3.0));
+    assert_eq!(cloud.normals()[1], Vector3::unit_x());
+    assert_eq!(cloud.positions()[1], Point3::new(-1.0, -2.0, -3.0));
+
+    // truncated data
+    assert!(pcd::read(&pcd[..pcd.len() - 1]).is_err());
+    // compressed data
+    let pcd = b"FIELDS x y z\nPOINTS 0\nDATA binary_compressed\n";
+    assert!(pcd::read(pcd.as_ref()).is_err());
+}
+
+#[test]
+fn las_io_test() {
+    let cloud0 = teapot_cloud();
+    let mut bytes = Vec::new();
+    las::write(&cloud0, &mut bytes).unwrap();
+    let cloud1 = las::read(bytes.as_slice()).unwrap();
+    assert_eq!(cloud0.len(), cloud1.len());
+    assert!(cloud1.normals().is_empty());
+    for i in 0..cloud0.len() {
+        assert!(cloud0.positions()[i].near(&cloud1.positions()[i]));
+        let color = cloud1.colors()[i];
+        assert!(cloud0.colors()[i].distance(color) < 1.0e-4);
+    }
+    // not a LAS file
+    assert!(las::read(TEAPOT_WITH_NORMALS_OBJ).is_err());
+}
+
+#[cfg(not(feature = "laz"))]
+#[test]
+fn laz_without_feature() {
+    let mut bytes = Vec::new();
+    las::write(&teapot_cloud(), &mut bytes).unwrap();
+    bytes[104] |= 0x80;
+    match las::read(bytes.as_slice()) {
+        Err(errors::Error::FromIO(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        _ => panic!("LAZ must be unsupported without the feature `laz`."),
+    }
+}
@@ -0,0 +1,27 @@
+use truck_polymesh::*;
+
+const SKULL_OBJ: &[u8] = include_bytes!("data/skull-with-texcoord.obj");
+
+#[test]
+fn f32_conversion_test() {
+    let mut mesh = obj::read(SKULL_OBJ).unwrap();
+    let len = mesh.positions().len();
+    mesh.set_position_colors(vec![Vector4::new(0.1, 0.2, 0.3, 1.0); len]).unwrap();
+    let mesh32 = PolygonMeshF32::from(&mesh);
+    assert!(mesh32.validate().is_ok());
+    assert_eq!(mesh32.faces(), mesh.faces());
+    assert_eq!(mesh32.uv_coords().len(), mesh.uv_coords().len());
+    assert_eq!(mesh32.colors()[0], [0.1, 0.2, 0.3, 1.0]);
+
+    // f32 -> f64 -> f32 is the identity
+    let mesh64 = PolygonMesh::from(&mesh32);
+    assert!(mesh64.validate().is_ok());
+    assert_eq!(PolygonMeshF32::from(&mesh64), mesh32);
+    let diameter = mesh.bounding_box().diameter();
+    mesh.positions()
+        .iter()
+        .zip(mesh64.positions())
+        .for_each(|(p, q)| assert!(p.distance(*q) < 1.0e-6 * diameter));
+    let (bdd, bdd32) = (mesh.bounding_box(), mesh32.bounding_box());
+    assert!(bdd.min().distance(*bdd32.min()) < 1.0e-6 * diameter);
+}
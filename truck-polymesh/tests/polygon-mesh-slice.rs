@@ -0,0 +1,61 @@
+use truck_polymesh::*;
+
+const PONY_OBJ: &[u8] = include_bytes!("data/pony-complete.obj");
+
+#[test]
+fn polygon_mesh_slice_compatibility() {
+    let mesh = obj::read(PONY_OBJ).unwrap();
+    let slice = mesh.as_slice();
+    assert_eq!(slice.positions(), mesh.positions().as_slice());
+    assert_eq!(slice.uv_coords(), mesh.uv_coords().as_slice());
+    assert_eq!(slice.normals(), mesh.normals().as_slice());
+    assert_eq!(slice.faces(), mesh.faces());
+    assert!(slice.face_iter().eq(mesh.face_iter()));
+    assert!(slice.validate().is_ok());
+
+    let owned = PolygonMesh::from(slice);
+    assert_eq!(owned, mesh);
+
+    // the view of the buffers owned by others
+    let slice = PolygonMeshSlice::new(
+        mesh.positions(),
+        mesh.uv_coords(),
+        mesh.normals(),
+        mesh.faces(),
+    );
+    assert_eq!(PolygonMesh::from(slice), mesh);
+}
+
+#[test]
+fn polygon_mesh_slice_with_colors() {
+    let positions = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let colors = [Vector4::new(1.0, 0.0, 0.0, 1.0), Vector4::new(0.0, 1.0, 0.0, 1.0)];
+    let vertex = |pos: usize, col: usize| Vertex {
+        pos,
+        uv: None,
+        nor: None,
+        col: Some(col),
+    };
+    let faces = Faces::from_iter([[vertex(0, 0), vertex(1, 1), vertex(2, 1)]]);
+    let slice = PolygonMeshSlice::from_raw_parts(&positions, &[], &[], &[], &faces);
+    assert!(slice.validate().is_err());
+    assert!(slice.try_with_colors(&colors[..1]).is_err());
+    let slice = slice.try_with_colors(&colors).unwrap();
+    let mesh = PolygonMesh::from(slice);
+    assert_eq!(mesh.colors(), &colors.to_vec());
+    assert_eq!(mesh.as_slice().colors(), &colors);
+
+    let raw = PolygonMesh::from_raw_parts(
+        positions.to_vec(),
+        Vec::new(),
+        Vec::new(),
+        colors.to_vec(),
+        faces,
+    );
+    assert_eq!(raw, mesh);
+    assert!(raw.validate().is_ok());
+}
@@ -0,0 +1,124 @@
+use step::{StepDescriptor, StepPart};
+use truck_polymesh::*;
+mod common;
+use common::*;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+// the tetrahedron with the apex very close to the base
+fn flat_tetrahedron() -> PolygonMesh {
+    tetrahedron().transformed(Matrix4::from_nonuniform_scale(1.0, 1.0, 1.0e-7))
+}
+
+// the entity lines whose names are `name`
+fn entities<'a>(step: &'a str, name: &str) -> Vec<&'a str> {
+    let pattern = format!("={}(", name);
+    step.lines().filter(|line| line.contains(&pattern)).collect()
+}
+
+#[test]
+fn teapot_step_test() {
+    let mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let desc = StepDescriptor {
+        file_name: "teapot.stp".to_string(),
+        author: "Tea's author".to_string(),
+        ..Default::default()
+    };
+    let parts = [StepPart::new("teapot", &mesh)];
+    let step = write_string(|bytes| step::write(&parts, bytes, &desc)).unwrap();
+    assert!(step.starts_with("ISO-10303-21;\nHEADER;\n"));
+    assert!(step.ends_with("ENDSEC;\nEND-ISO-10303-21;\n"));
+    assert!(step.contains("FILE_NAME('teapot.stp','',('Tea''s author'),"));
+    assert!(step.contains("AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF"));
+    assert!(step.contains("SI_UNIT(.MILLI.,.METRE.)"));
+    assert_eq!(entities(&step, "PRODUCT").len(), 1);
+    assert!(entities(&step, "NEXT_ASSEMBLY_USAGE_OCCURRENCE").is_empty());
+
+    let coordinates = entities(&step, "COORDINATES_LIST");
+    assert_eq!(coordinates.len(), 1);
+    let len = format!("COORDINATES_LIST('teapot',{},(", mesh.positions().len());
+    assert!(coordinates[0].contains(&len));
+    let p = mesh.positions()[0];
+    let first: Vec<f64> = coordinates[0][len.len() + 5..]
+        .split(')')
+        .next()
+        .unwrap()
+        .trim_start_matches('(')
+        .split(',')
+        .map(|x| x.trim_end_matches('.').parse().unwrap())
+        .collect();
+    assert!(Point3::new(first[0], first[1], first[2]).near(&p));
+    let surface_sets = entities(&step, "TRIANGULATED_SURFACE_SET");
+    assert_eq!(surface_sets.len(), 1);
+    let triangles = mesh
+        .face_iter()
+        .map(|face| face.len() - 2)
+        .sum::<usize>();
+    // the fan triangles without the degenerate ones
+    let start = surface_sets[0].find("(),(),(").unwrap();
+    let count = surface_sets[0][start..].matches("),(").count() - 1;
+    assert!(count <= triangles && count > triangles / 2);
+    assert_eq!(entities(&step, "TESSELLATED_SHAPE_REPRESENTATION").len(), 1);
+}
+
+#[test]
+fn assembly_step_test() {
+    let mesh = flat_tetrahedron();
+    let parts = [StepPart::new("tetra", &mesh), StepPart::new("テトラ", &mesh)];
+    let desc = StepDescriptor {
+        unit: LengthUnit::Inch,
+        assembly_name: "pair".to_string(),
+        ..Default::default()
+    };
+    let step = write_string(|bytes| step::write(&parts, bytes, &desc)).unwrap();
+    assert_eq!(entities(&step, "PRODUCT").len(), 3);
+    assert!(step.contains("=PRODUCT('pair','pair',"));
+    // the non-ASCII characters
+    assert!(step.contains("=PRODUCT('\\X2\\30C630C830E9\\X0\\',"));
+    assert_eq!(entities(&step, "NEXT_ASSEMBLY_USAGE_OCCURRENCE").len(), 2);
+    assert_eq!(entities(&step, "CONTEXT_DEPENDENT_SHAPE_REPRESENTATION").len(), 2);
+    assert!(step.contains("CONVERSION_BASED_UNIT('INCH',"));
+    assert!(step.contains("LENGTH_MEASURE(25.4)"));
+    // the reals always have the decimal points.
+    assert!(step.contains("(0.0,0.0,1.E-7)"));
+    assert!(step.contains("((1,3,2),(1,2,4),(2,3,4),(3,1,4))"));
+    // the references are defined
+    let defined = step.lines().filter(|line| line.starts_with('#')).count();
+    let max_reference = step
+        .split('#')
+        .skip(1)
+        .map(|x| {
+            let digits: String = x.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<usize>().unwrap()
+        })
+        .max()
+        .unwrap();
+    assert_eq!(max_reference, defined);
+}
+
+#[test]
+fn invalid_step_test() {
+    assert!(write_string(|bytes| step::write(&[], bytes, &StepDescriptor::default())).is_err());
+    let positions = vec![Point3::origin()];
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), Faces::default());
+    let parts = [StepPart::new("empty", &mesh)];
+    assert!(write_string(|bytes| step::write(&parts, bytes, &StepDescriptor::default())).is_err());
+}
+
+#[test]
+fn context_step_test() {
+    let mesh = flat_tetrahedron();
+    let parts = [StepPart::new("tetra", &mesh)];
+    let context = ModelContext::new(LengthUnit::Centimeter, 1.0e-9);
+    let desc = StepDescriptor::default();
+    let write = |bytes: &mut Vec<u8>| step::write_with_context(&parts, bytes, &desc, &context);
+    let step = write_string(write).unwrap();
+    assert!(step.contains("(10.0,0.0,0.0)"));
+    assert!(step.contains("(0.0,0.0,1.E-6)"));
+    // the same unit writes the meshes as they are
+    let context = ModelContext::new(LengthUnit::Millimeter, 1.0e-9);
+    let write = |bytes: &mut Vec<u8>| step::write_with_context(&parts, bytes, &desc, &context);
+    let converted = write_string(write).unwrap();
+    let written = write_string(|bytes| step::write(&parts, bytes, &desc)).unwrap();
+    assert_eq!(converted, written);
+}
@@ -1,4 +1,4 @@
-use stl::{IntoSTLIterator, STLFace, STLReader, STLType};
+use stl::{IntoSTLIterator, STLColorFormat, STLFace, STLMeshBuilder, STLReader, STLType};
 use truck_base::assert_near;
 use truck_polymesh::*;
 
@@ -92,3 +92,178 @@ fn through_polymesh() {
         assert!(f32::abs(face0.normal[2] - face1.normal[2]) < 1.0e-4);
     }
 }
+
+#[test]
+fn streaming_read() {
+    let bytes = include_bytes!("data/bunny_binary.stl");
+    let mesh = stl::read(bytes.as_ref(), STLType::Automatic).unwrap();
+    let iter = stl::read_iter(bytes.as_ref(), STLType::Automatic).unwrap();
+    assert_eq!(iter.size_hint().0, mesh.faces().len());
+    let mut builder = STLMeshBuilder::new();
+    for face in iter {
+        builder.push(face.unwrap());
+    }
+    assert_eq!(builder.build(), mesh);
+    let mesh0 = stl::read(include_bytes!("data/bunny_ascii.stl").as_ref(), STLType::Automatic);
+    assert_eq!(mesh0.unwrap(), mesh);
+    // the positions shared by faces are put together
+    assert!(mesh.positions().len() < mesh.faces().len());
+
+    // the truncated data
+    let iter = stl::read_iter(&bytes[..bytes.len() - 10], STLType::Binary).unwrap();
+    assert!(iter.last().unwrap().is_err());
+    assert!(stl::read(&bytes[..bytes.len() - 10], STLType::Binary).is_err());
+}
+
+#[test]
+fn colored_stl() {
+    let bytes = include_bytes!("data/bunny_binary.stl");
+    let faces: Vec<STLFace> = stl::read_iter(bytes.as_ref(), STLType::Automatic)
+        .unwrap()
+        .map(|face| face.unwrap())
+        .collect();
+    let colors: Vec<Option<[u8; 3]>> = (0..faces.len())
+        .map(|i| match i % 3 {
+            0 => None,
+            1 => Some([255, 0, 132]),
+            _ => Some([8, 255, 0]),
+        })
+        .collect();
+    for format in [STLColorFormat::VisCAM, STLColorFormat::Materialise] {
+        let mut bytes = Vec::new();
+        stl::write_with_colors(faces.iter().copied(), &mut bytes, colors.clone(), format).unwrap();
+        let (mesh, colors0) =
+            stl::read_with_colors(bytes.as_slice(), STLType::Automatic, format).unwrap();
+        assert_eq!(mesh.tri_faces().len(), faces.len());
+        let colors0 = colors0.unwrap();
+        colors.iter().zip(&colors0).for_each(|(color, color0)| match format {
+            STLColorFormat::VisCAM => assert_eq!(color, color0),
+            // the facets without colors take the object color
+            STLColorFormat::Materialise => assert_eq!(color.unwrap_or([255; 3]), color0.unwrap()),
+        });
+        // the colors are ignored in reading as a plain STL
+        assert_eq!(stl::read(bytes.as_slice(), STLType::Automatic).unwrap(), mesh);
+    }
+
+    // plain STL has no colors in both conventions
+    for format in [STLColorFormat::VisCAM, STLColorFormat::Materialise] {
+        let (_, colors) = stl::read_with_colors(bytes.as_ref(), STLType::Binary, format).unwrap();
+        assert!(colors.is_none());
+        let ascii = include_bytes!("data/bunny_ascii.stl");
+        let (_, colors) = stl::read_with_colors(ascii.as_ref(), STLType::ASCII, format).unwrap();
+        assert!(colors.is_none());
+    }
+}
+
+#[test]
+fn stl_progress() {
+    let bytes = include_bytes!("data/bunny_ascii.stl");
+    let mut log = Vec::new();
+    let progress = |done, total| {
+        log.push((done, total));
+        true
+    };
+    let mesh = stl::read_with_progress(bytes.as_ref(), STLType::Automatic, bytes.len(), progress);
+    let mesh = mesh.unwrap();
+    assert_eq!(mesh, stl::read(bytes.as_ref(), STLType::Automatic).unwrap());
+    assert!(log.windows(2).all(|a| a[0].0 < a[1].0));
+    assert_eq!(log.last(), Some(&(bytes.len(), bytes.len())));
+    assert!(log.len() <= 1001);
+
+    // cancels in the middle of the file
+    let progress = |done, total| done < total / 2;
+    let res = stl::read_with_progress(bytes.as_ref(), STLType::Automatic, bytes.len(), progress);
+    assert!(matches!(res, Err(errors::Error::Cancelled)));
+
+    let mut written = 0;
+    let progress = |done, _| {
+        written = done;
+        true
+    };
+    let mut gened = Vec::new();
+    stl::write_with_progress(&mesh, &mut gened, STLType::Binary, progress).unwrap();
+    assert_eq!(written, IntoSTLIterator::into_iter(&mesh).len());
+    let read = stl::read(gened.as_slice(), STLType::Binary).unwrap();
+    assert_eq!(read.faces().len(), mesh.faces().len());
+}
+
+#[test]
+fn truncated_binary_header() {
+    let bytes = [0u8; 82];
+    assert!(stl::read(bytes.as_ref(), STLType::Binary).is_err());
+    let mut bytes = vec![0u8; 84];
+    bytes[80] = 1;
+    assert!(stl::read(bytes.as_slice(), STLType::Binary).is_err());
+}
+
+#[test]
+fn multi_solid_stl() {
+    let facet = |x: f32| {
+        format!(
+            "facet normal 0 0 1\nouter loop\nvertex {x} 0 0\nvertex {y} 0 0\nvertex {x} 1 0\n\
+             endloop\nendfacet\n",
+            x = x,
+            y = x + 1.0,
+        )
+    };
+    let stl = format!(
+        "solid base plate\n{}{}endsolid base plate\n\
+         solid\n{}endsolid\n\
+         solid bolt\n{}endsolid bolt\n",
+        facet(0.0),
+        facet(1.0),
+        facet(2.0),
+        facet(3.0),
+    );
+    let solids = stl::read_solids(stl.as_bytes(), STLType::Automatic).unwrap();
+    let names: Vec<_> = solids.iter().map(|(name, _)| name.as_deref()).collect();
+    assert_eq!(names, vec![Some("base plate"), None, Some("bolt")]);
+    let lens: Vec<_> = solids.iter().map(|(_, mesh)| mesh.faces().len()).collect();
+    assert_eq!(lens, vec![2, 1, 1]);
+    assert_near!(solids[2].1.positions()[0], Point3::new(3.0, 0.0, 0.0));
+    let mesh = stl::read(stl.as_bytes(), STLType::ASCII).unwrap();
+    assert_eq!(mesh.faces().len(), 4);
+
+    let mut ascii = Vec::new();
+    let named = solids.iter().map(|(name, mesh)| (name.as_ref(), mesh));
+    stl::write_solids(named, &mut ascii, STLType::ASCII).unwrap();
+    let solids0 = stl::read_solids(ascii.as_slice(), STLType::ASCII).unwrap();
+    assert_eq!(solids0, solids);
+
+    // binary STL has only one solid
+    let mut binary = Vec::new();
+    let named = solids.iter().map(|(name, mesh)| (name.as_ref(), mesh));
+    stl::write_solids(named, &mut binary, STLType::Binary).unwrap();
+    let solids0 = stl::read_solids(binary.as_slice(), STLType::Automatic).unwrap();
+    assert_eq!(solids0.len(), 1);
+    assert_eq!(solids0[0].0, None);
+    assert_eq!(solids0[0].1.faces().len(), 4);
+}
+
+#[test]
+fn write_with_options() {
+    let mesh = stl::read(
+        include_bytes!("data/bunny_binary.stl").as_ref(),
+        STLType::Automatic,
+    )
+    .unwrap();
+    for stl_type in [STLType::ASCII, STLType::Binary, STLType::Automatic] {
+        let options = stl::STLWriteOptions {
+            stl_type,
+            name: Some("x".repeat(100)),
+            scale: 0.001,
+        };
+        let mut bytes = Vec::new();
+        stl::write_with_options(&mesh, &mut bytes, &options).unwrap();
+        let reader = stl::read_iter(bytes.as_slice(), STLType::Automatic).unwrap();
+        match stl_type {
+            STLType::ASCII => assert_eq!(reader.stl_type(), STLType::ASCII),
+            _ => assert_eq!(reader.header(), Some(&[b'x'; 80])),
+        }
+        let mesh0 = stl::read(bytes.as_slice(), STLType::Automatic).unwrap();
+        assert_eq!(mesh0.faces().len(), mesh.faces().len());
+        let (bdb, bdb0) = (mesh.bounding_box(), mesh0.bounding_box());
+        assert!((bdb0.max() * 1000.0 - bdb.max()).magnitude() < 1.0e-3);
+        assert!((bdb0.min() * 1000.0 - bdb.min()).magnitude() < 1.0e-3);
+    }
+}
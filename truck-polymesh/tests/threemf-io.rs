@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use threemf::{ThreeMFAssembly, ThreeMFDescriptor, ThreeMFItem};
+use truck_polymesh::*;
+mod common;
+use common::tetrahedron;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+fn u16_at(bytes: &[u8], offset: usize) -> usize {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]]) as usize
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ]) as usize
+}
+
+// extracts the files of the zip archive through its central directory
+fn unzip(bytes: &[u8]) -> HashMap<String, String> {
+    let end = bytes.len() - 22;
+    assert_eq!(u32_at(bytes, end), 0x0605_4B50);
+    let number_of_files = u16_at(bytes, end + 10);
+    let mut offset = u32_at(bytes, end + 16);
+    let mut files = HashMap::new();
+    for _ in 0..number_of_files {
+        assert_eq!(u32_at(bytes, offset), 0x0201_4B50);
+        let crc = u32_at(bytes, offset + 16) as u32;
+        let compressed_size = u32_at(bytes, offset + 20);
+        let name_length = u16_at(bytes, offset + 28);
+        let local = u32_at(bytes, offset + 42);
+        let name = String::from_utf8(bytes[offset + 46..offset + 46 + name_length].to_vec());
+        assert_eq!(u32_at(bytes, local), 0x0403_4B50);
+        let start = local + 30 + u16_at(bytes, local + 26) + u16_at(bytes, local + 28);
+        let compressed = &bytes[start..start + compressed_size];
+        let data = miniz_oxide::inflate::decompress_to_vec(compressed).unwrap();
+        assert_eq!(data.len(), u32_at(bytes, offset + 24));
+        assert_eq!(crc32fast::hash(&data), crc);
+        files.insert(name.unwrap(), String::from_utf8(data).unwrap());
+        offset += 46 + name_length + u16_at(bytes, offset + 30) + u16_at(bytes, offset + 32);
+    }
+    files
+}
+
+#[test]
+fn teapot_3mf_test() {
+    let mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let mut bytes = Vec::new();
+    let meshes = [("teapot".to_string(), mesh.clone())];
+    threemf::write(&meshes, &mut bytes, &Default::default()).unwrap();
+    let files = unzip(&bytes);
+    assert_eq!(files.len(), 3);
+    assert!(files["[Content_Types].xml"].contains("3dmanufacturing-3dmodel+xml"));
+    assert!(files["_rels/.rels"].contains("Target=\"/3D/3dmodel.model\""));
+    let model = &files["3D/3dmodel.model"];
+    assert!(model.contains("unit=\"millimeter\""));
+    assert!(model.contains("<object id=\"1\" type=\"model\" name=\"teapot\">"));
+    assert_eq!(model.matches("<vertex ").count(), mesh.positions().len());
+    let triangles: usize = mesh.face_iter().map(|face| face.len() - 2).sum();
+    assert_eq!(model.matches("<triangle ").count(), triangles);
+    assert!(model.contains("<item objectid=\"1\"/>"));
+    // the first vertex
+    let p = mesh.positions()[0];
+    let vertex = format!("<vertex x=\"{}\" y=\"{}\" z=\"{}\"/>", p[0], p[1], p[2]);
+    assert!(model.contains(&vertex));
+    // the package is compressed
+    assert!(bytes.len() < model.len() / 2);
+}
+
+#[test]
+fn assembly_3mf_test() {
+    let meshes = [
+        ("tetrahedron <0>".to_string(), tetrahedron()),
+        ("tetrahedron & 1".to_string(), tetrahedron()),
+    ];
+    let translation = |x: f64| Matrix4::from_translation(Vector3::new(x, 2.0, 3.0));
+    let desc = ThreeMFDescriptor {
+        unit: LengthUnit::Inch,
+        assemblies: vec![ThreeMFAssembly {
+            name: "pair".to_string(),
+            components: vec![
+                ThreeMFItem {
+                    object: 0,
+                    transform: Matrix4::identity(),
+                },
+                ThreeMFItem {
+                    object: 1,
+                    transform: translation(1.5),
+                },
+            ],
+        }],
+        build: vec![ThreeMFItem {
+            object: 2,
+            transform: Matrix4::from_scale(2.0),
+        }],
+    };
+    let mut bytes = Vec::new();
+    threemf::write(&meshes, &mut bytes, &desc).unwrap();
+    let model = &unzip(&bytes)["3D/3dmodel.model"];
+    assert!(model.contains("unit=\"inch\""));
+    assert!(model.contains("name=\"tetrahedron &lt;0&gt;\""));
+    assert!(model.contains("name=\"tetrahedron &amp; 1\""));
+    assert!(model.contains("<object id=\"3\" type=\"model\" name=\"pair\">"));
+    assert!(model.contains("<component objectid=\"1\" transform=\"1 0 0 0 1 0 0 0 1 0 0 0\"/>"));
+    assert!(model.contains("<component objectid=\"2\" transform=\"1 0 0 0 1 0 0 0 1 1.5 2 3\"/>"));
+    assert_eq!(model.matches("<item ").count(), 1);
+    assert!(model.contains("<item objectid=\"3\" transform=\"2 0 0 0 2 0 0 0 2 0 0 0\"/>"));
+}
+
+#[test]
+fn invalid_3mf_test() {
+    let meshes = [("tetrahedron".to_string(), tetrahedron())];
+    let mut bytes = Vec::new();
+    // the components must be meshes
+    let desc = ThreeMFDescriptor {
+        assemblies: vec![ThreeMFAssembly {
+            name: "assembly".to_string(),
+            components: vec![ThreeMFItem {
+                object: 1,
+                transform: Matrix4::identity(),
+            }],
+        }],
+        ..Default::default()
+    };
+    assert!(threemf::write(&meshes, &mut bytes, &desc).is_err());
+    let desc = ThreeMFDescriptor {
+        build: vec![ThreeMFItem {
+            object: 1,
+            transform: Matrix4::identity(),
+        }],
+        ..Default::default()
+    };
+    assert!(threemf::write(&meshes, &mut bytes, &desc).is_err());
+    // a mesh with only degenerate faces
+    let positions = vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)];
+    let faces = Faces::from_iter([[0, 1, 1]]);
+    let mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let meshes = [("degenerate".to_string(), mesh)];
+    assert!(threemf::write(&meshes, &mut bytes, &Default::default()).is_err());
+}
+
+#[test]
+fn context_3mf_test() {
+    let meshes = [("tetrahedron".to_string(), tetrahedron())];
+    let context = ModelContext::new(LengthUnit::Centimeter, 1.0e-9);
+    let desc = ThreeMFDescriptor {
+        build: vec![ThreeMFItem {
+            object: 0,
+            transform: Matrix4::from_translation(Vector3::new(1.5, 2.0, 3.0))
+                * Matrix4::from_scale(2.0),
+        }],
+        ..Default::default()
+    };
+    let mut bytes = Vec::new();
+    threemf::write_with_context(&meshes, &mut bytes, &desc, &context).unwrap();
+    let model = &unzip(&bytes)["3D/3dmodel.model"];
+    assert!(model.contains("unit=\"millimeter\""));
+    assert!(model.contains("<vertex x=\"10\" y=\"0\" z=\"0\"/>"));
+    assert!(model.contains("<item objectid=\"1\" transform=\"2 0 0 0 2 0 0 0 2 15 20 30\"/>"));
+}
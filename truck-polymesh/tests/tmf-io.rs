@@ -0,0 +1,90 @@
+use tmf::TMFCompression;
+use truck_polymesh::*;
+
+const TEAPOT_NORMAL_OBJ: &[u8] = include_bytes!("data/teapot-with-normals.obj");
+const SKULL_TEXCOORD_OBJ: &[u8] = include_bytes!("data/skull-with-texcoord.obj");
+
+fn write_bytes(mesh: &PolygonMesh, compression: TMFCompression) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    mesh.write_tmf(&mut bytes, compression).unwrap();
+    bytes
+}
+
+#[test]
+fn tmf_io_test() {
+    for obj in &[TEAPOT_NORMAL_OBJ, SKULL_TEXCOORD_OBJ] {
+        let mesh = obj::read(*obj).unwrap();
+        let raw = write_bytes(&mesh, TMFCompression::None);
+        let compressed = write_bytes(&mesh, TMFCompression::Deflate);
+        assert!(compressed.len() < raw.len());
+        // smaller than JSON
+        let json = serde_json::to_vec(&mesh).unwrap();
+        assert!(raw.len() < json.len());
+        assert_eq!(PolygonMesh::read_tmf(raw.as_slice()).unwrap(), mesh);
+        assert_eq!(PolygonMesh::read_tmf(compressed.as_slice()).unwrap(), mesh);
+    }
+}
+
+#[test]
+fn tmf_colors_test() {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.5, 0.5, 1.0),
+    ];
+    let faces = Faces::from_iter([&[0, 3, 2, 1][..], &[0, 1, 4], &[1, 2, 4], &[2, 3, 4]]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let colors = (0..5)
+        .map(|i| Vector4::new(i as f64 / 4.0, 0.5, 1.0, 1.0))
+        .collect();
+    mesh.set_position_colors(colors).unwrap();
+    let bytes = write_bytes(&mesh, TMFCompression::Deflate);
+    let mesh0 = tmf::read(bytes.as_slice()).unwrap();
+    assert_eq!(mesh0, mesh);
+    assert_eq!(mesh0.colors()[3], Vector4::new(0.75, 0.5, 1.0, 1.0));
+}
+
+#[test]
+fn tmf_compatibility_test() {
+    let mesh = obj::read(TEAPOT_NORMAL_OBJ).unwrap();
+    let bytes = write_bytes(&mesh, TMFCompression::None);
+
+    // the unknown chunk, added by the newer minor version, is skipped.
+    let mut newer = bytes[..8].to_vec();
+    newer[6] = 3;
+    newer.extend_from_slice(b"NEW\0");
+    newer.extend_from_slice(&[0; 4]);
+    newer.extend_from_slice(&5u64.to_le_bytes());
+    newer.extend_from_slice(&5u64.to_le_bytes());
+    newer.extend_from_slice(b"hello");
+    // the unknown chunk compressed by the unknown method
+    newer.extend_from_slice(b"NEW2");
+    newer.extend_from_slice(&[7, 0, 0, 0]);
+    newer.extend_from_slice(&3u64.to_le_bytes());
+    newer.extend_from_slice(&5u64.to_le_bytes());
+    newer.extend_from_slice(b"abc");
+    newer.extend_from_slice(&bytes[8..]);
+    assert_eq!(tmf::read(newer.as_slice()).unwrap(), mesh);
+
+    // the known chunk compressed by the unknown method
+    let mut unknown = bytes.clone();
+    unknown[12] = 7;
+    assert!(tmf::read(unknown.as_slice()).is_err());
+
+    // the newer major version
+    let mut newer = bytes.clone();
+    newer[4] = 2;
+    assert!(tmf::read(newer.as_slice()).is_err());
+    // not TMF
+    assert!(tmf::read(&bytes[1..]).is_err());
+    // truncated
+    assert!(tmf::read(&bytes[..bytes.len() - 1]).is_err());
+    // the index out of range
+    let mut invalid = bytes.clone();
+    let len = invalid.len();
+    invalid[len - 30] = 0xFE;
+    invalid[len - 29] = 0xFF;
+    assert!(tmf::read(invalid.as_slice()).is_err());
+}
@@ -0,0 +1,79 @@
+use truck_polymesh::validation::*;
+use truck_polymesh::*;
+
+const TEAPOT_WITHNORMALS_OBJ: &[u8] = include_bytes!("data/teapot-with-normals.obj");
+const BUNNY_BINARY_STL: &[u8] = include_bytes!("data/bunny_binary.stl");
+
+#[test]
+fn clean_files() {
+    for policy in [FixPolicy::Drop, FixPolicy::Clamp, FixPolicy::Repair] {
+        let validated = obj::read_validated(TEAPOT_WITHNORMALS_OBJ, policy).unwrap();
+        assert!(validated.report.is_clean(), "{:?}", validated.report);
+        assert_eq!(validated.value, obj::read(TEAPOT_WITHNORMALS_OBJ).unwrap());
+    }
+    let stl_type = stl::STLType::Automatic;
+    let validated = stl::read_validated(BUNNY_BINARY_STL, stl_type, FixPolicy::Drop).unwrap();
+    let mesh = stl::read(BUNNY_BINARY_STL, stl_type).unwrap();
+    // the bunny has some facets degenerated by putting together the close vertices
+    let report = &validated.report;
+    assert_eq!(report.problems.len(), report.degenerate_face_count());
+    assert_eq!(report.dropped_faces.len(), report.degenerate_face_count());
+    assert_eq!(
+        validated.value.faces().len() + report.dropped_faces.len(),
+        mesh.faces().len()
+    );
+}
+
+#[test]
+fn fix_policies() {
+    let obj = b"v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 inf 0
+vn 0 0 1
+f 1//1 2//1 3//2
+f 1 3 3 4
+f 1 2 5
+f 1 9 3
+";
+    let read = |policy| obj::read_validated(obj.as_ref(), policy).unwrap();
+
+    let validated = read(FixPolicy::Drop);
+    let report = &validated.report;
+    assert_eq!(report.out_of_range_count(), 2);
+    assert_eq!(report.non_finite_count(), 1);
+    assert_eq!(report.degenerate_face_count(), 1);
+    assert_eq!(report.dropped_faces, vec![0, 1, 2, 3]);
+    let problem = MeshProblem::OutOfRange {
+        face: 3,
+        corner: 1,
+        array: "positions",
+        index: 8,
+    };
+    assert!(report.problems.contains(&problem));
+    assert!(report.problems.contains(&MeshProblem::NonFinite {
+        array: "positions",
+        index: 4,
+    }));
+    // the non-finite coordinates are replaced with zero
+    assert_eq!(validated.value.positions()[4], Point3::origin());
+
+    // all the faces are kept with the indices in the ranges
+    let validated = read(FixPolicy::Clamp);
+    assert!(validated.report.dropped_faces.is_empty());
+    let mesh = validated.value;
+    assert_eq!(mesh.faces().len(), 4);
+    assert_eq!(mesh.faces().tri_faces()[0][2].nor, Some(0));
+    assert_eq!(mesh.faces().tri_faces()[2][1].pos, 4);
+    assert_eq!(mesh.faces().quad_faces()[0][2].pos, 2);
+
+    // the quadrangle with the repeated corner is repaired into the triangle
+    let validated = read(FixPolicy::Repair);
+    assert_eq!(validated.report.dropped_faces, vec![2, 3]);
+    let mesh = validated.value;
+    assert_eq!(mesh.faces().len(), 2);
+    assert_eq!(mesh.faces().tri_faces()[0][2].nor, None);
+    let positions: Vec<usize> = mesh.faces().tri_faces()[1].iter().map(|v| v.pos).collect();
+    assert_eq!(positions, vec![0, 2, 3]);
+}
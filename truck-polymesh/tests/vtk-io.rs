@@ -0,0 +1,118 @@
+use truck_polymesh::*;
+use vtk::VTKFormat;
+
+const TEAPOT_POSITION_OBJ: &[u8] = include_bytes!("data/teapot-position.obj");
+
+fn write_string<M: vtk::VTKMesh>(mesh: &M, format: VTKFormat) -> String {
+    let mut bytes = Vec::new();
+    vtk::write(mesh, &mut bytes, format).unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+// the lines between the line `start` and the next line starting with `end`
+fn section<'a>(string: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    string
+        .lines()
+        .skip_while(|line| !line.starts_with(start))
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with(end))
+        .collect()
+}
+
+fn structured_mesh() -> StructuredMesh {
+    let udiv = vec![0.0, 0.5, 1.0];
+    let vdiv = vec![0.0, 1.0];
+    let positions = udiv
+        .iter()
+        .map(|u| vdiv.iter().map(|v| Point3::new(*u, *v, u * v)).collect())
+        .collect();
+    let normals = udiv
+        .iter()
+        .map(|u| vdiv.iter().map(|v| Vector3::new(-v, -u, 1.0)).collect())
+        .collect();
+    StructuredMesh::new(positions, (udiv, vdiv), normals)
+}
+
+#[test]
+fn polydata_test() {
+    let mut mesh = obj::read(TEAPOT_POSITION_OBJ).unwrap();
+    let len = mesh.positions().len();
+    let heights = mesh.positions().iter().map(|p| p[2]).collect::<Vec<_>>();
+    mesh.set_vertex_attribute("height", heights).unwrap();
+    let faces_len = mesh.faces().len();
+    let labels = (0..faces_len).map(|i| i % 3).collect::<Vec<_>>();
+    mesh.set_face_attribute("part <id>", labels).unwrap();
+    let vectors = vec![Vector3::unit_z(); faces_len];
+    mesh.set_face_attribute("up", vectors).unwrap();
+
+    let legacy = write_string(&mesh, VTKFormat::Legacy);
+    assert!(legacy.starts_with("# vtk DataFile Version 3.0\n"));
+    assert!(legacy.contains(&format!("DATASET POLYDATA\nPOINTS {} double\n", len)));
+    let points = section(&legacy, "POINTS", "POLYGONS");
+    assert_eq!(points.len(), len);
+    let p: Vec<f64> = points[0].split(' ').map(|x| x.parse().unwrap()).collect();
+    assert_eq!(Point3::new(p[0], p[1], p[2]), mesh.positions()[0]);
+    let polygons = section(&legacy, "POLYGONS", "POINT_DATA");
+    assert_eq!(polygons.len(), faces_len);
+    let first: Vec<usize> = polygons[0].split(' ').map(|x| x.parse().unwrap()).collect();
+    let face = mesh.face_iter().next().unwrap();
+    assert_eq!(first[0], face.len());
+    assert!(first[1..].iter().zip(face).all(|(i, v)| *i == v.pos));
+    assert!(legacy.contains(&format!("POINT_DATA {}\nSCALARS height double 1\n", len)));
+    assert!(legacy.contains(&format!("CELL_DATA {}\n", faces_len)));
+    // the white spaces are replaced in the legacy format
+    let labels = "SCALARS part_<id> unsigned_long 1\nLOOKUP_TABLE default\n0\n1\n2\n";
+    assert!(legacy.contains(labels));
+    assert!(legacy.contains("VECTORS up double\n0.0 0.0 1.0\n"));
+
+    let xml = write_string(&mesh, VTKFormat::XML);
+    assert!(xml.starts_with("<?xml version=\"1.0\"?>\n<VTKFile type=\"PolyData\""));
+    assert!(xml.contains(&format!(
+        "<Piece NumberOfPoints=\"{}\" NumberOfVerts=\"0\" NumberOfLines=\"0\" \
+         NumberOfStrips=\"0\" NumberOfPolys=\"{}\">",
+        len, faces_len
+    )));
+    assert!(xml.contains("<PointData Scalars=\"height\">"));
+    assert!(xml.contains("<CellData Scalars=\"part &lt;id&gt;\" Vectors=\"up\">"));
+    assert!(xml.contains("type=\"UInt64\" Name=\"part &lt;id&gt;\" NumberOfComponents=\"1\""));
+    let offsets = section(&xml, "        <DataArray type=\"UInt64\" Name=\"offsets\"", "</");
+    assert_eq!(offsets.len(), faces_len);
+    let last: usize = offsets[faces_len - 1].trim().parse().unwrap();
+    assert_eq!(last, mesh.face_iter().map(|face| face.len()).sum::<usize>());
+    assert!(xml.ends_with("    </Piece>\n  </PolyData>\n</VTKFile>\n"));
+}
+
+#[test]
+fn structured_grid_test() {
+    let mesh = structured_mesh();
+    let legacy = write_string(&mesh, VTKFormat::Legacy);
+    assert!(legacy.contains("DATASET STRUCTURED_GRID\nDIMENSIONS 3 2 1\nPOINTS 6 double\n"));
+    // the first index runs fastest
+    let points = section(&legacy, "POINTS", "POINT_DATA");
+    let answer = [
+        "0.0 0.0 0.0",
+        "0.5 0.0 0.0",
+        "1.0 0.0 0.0",
+        "0.0 1.0 0.0",
+        "0.5 1.0 0.5",
+        "1.0 1.0 1.0",
+    ];
+    assert_eq!(points, answer);
+    assert!(legacy.contains("POINT_DATA 6\nNORMALS Normals double\n-0.0 -0.0 1.0\n"));
+    let tcoords = section(&legacy, "TEXTURE_COORDINATES TCoords 2 double", "$");
+    assert_eq!(tcoords, ["0.0 0.0", "0.5 0.0", "1.0 0.0", "0.0 1.0", "0.5 1.0", "1.0 1.0"]);
+    assert!(!legacy.contains("CELL_DATA"));
+
+    let xml = write_string(&mesh, VTKFormat::XML);
+    assert!(xml.contains("<VTKFile type=\"StructuredGrid\""));
+    assert!(xml.contains("<StructuredGrid WholeExtent=\"0 2 0 1 0 0\">"));
+    assert!(xml.contains("<Piece Extent=\"0 2 0 1 0 0\">"));
+    assert!(xml.contains("<PointData Normals=\"Normals\" TCoords=\"TCoords\">"));
+    assert!(!xml.contains("<Polys>"));
+
+    // the mesh without quadrangles
+    let positions = vec![vec![Point3::origin(), Point3::new(1.0, 0.0, 0.0)]];
+    let line = StructuredMesh::from_positions(positions);
+    let mut bytes = Vec::new();
+    assert!(vtk::write(&line, &mut bytes, VTKFormat::XML).is_err());
+}
@@ -0,0 +1,106 @@
+use truck_polymesh::*;
+use x3d::{X3DDescriptor, X3DEncoding};
+
+const TEAPOT_NORMAL_OBJ: &[u8] = include_bytes!("data/teapot-with-normals.obj");
+const SKULL_TEXCOORD_OBJ: &[u8] = include_bytes!("data/skull-with-texcoord.obj");
+
+fn write_string(mesh: &PolygonMesh, desc: &X3DDescriptor) -> String {
+    let mut bytes = Vec::new();
+    x3d::write(mesh, &mut bytes, desc).unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+// extracts the values of the field whose name is `name`
+fn field<'a>(x3d: &'a str, name: &str) -> &'a str {
+    let start = x3d.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+    let end = start + x3d[start..].find('"').unwrap();
+    &x3d[start..end]
+}
+
+#[test]
+fn teapot_x3d_test() {
+    let mesh = obj::read(TEAPOT_NORMAL_OBJ).unwrap();
+    let x3d = write_string(&mesh, &Default::default());
+    assert!(x3d.contains("<X3D profile=\"Interchange\" version=\"3.3\">"));
+    assert!(!x3d.contains("creaseAngle"));
+    let face_len = mesh.face_iter().count();
+    let index_len: usize = mesh.face_iter().map(|face| face.len() + 1).sum();
+    let coord_index: Vec<isize> = field(&x3d, "coordIndex")
+        .split(' ')
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert_eq!(coord_index.len(), index_len);
+    assert_eq!(coord_index.iter().filter(|i| **i == -1).count(), face_len);
+    let face = mesh.face_iter().next().unwrap();
+    assert_eq!(coord_index[0], face[0].pos as isize);
+    // the explicit normals
+    let normal_index = field(&x3d, "normalIndex");
+    assert_eq!(normal_index.split(' ').count(), index_len);
+    let vector = field(&x3d, "vector");
+    assert_eq!(vector.split(", ").count(), mesh.normals().len());
+    let normal: Vec<f64> = vector
+        .split(", ")
+        .next()
+        .unwrap()
+        .split(' ')
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert!(Vector3::new(normal[0], normal[1], normal[2]).near(&mesh.normals()[0]));
+    let point = field(&x3d, "point");
+    assert_eq!(point.split(", ").count(), mesh.positions().len());
+    assert!(!x3d.contains("texCoordIndex"));
+}
+
+#[test]
+fn crease_angle_test() {
+    let mesh = obj::read(TEAPOT_NORMAL_OBJ).unwrap();
+    let desc = X3DDescriptor {
+        crease_angle: Some(1.0),
+        ..Default::default()
+    };
+    let x3d = write_string(&mesh, &desc);
+    assert!(x3d.contains(" creaseAngle=\"1\""));
+    assert!(!x3d.contains("normalIndex"));
+    assert!(!x3d.contains("<Normal"));
+}
+
+#[test]
+fn skull_vrml_test() {
+    let mesh = obj::read(SKULL_TEXCOORD_OBJ).unwrap();
+    let desc = X3DDescriptor {
+        encoding: X3DEncoding::VRML97,
+        crease_angle: Some(0.5),
+    };
+    let wrl = write_string(&mesh, &desc);
+    assert!(wrl.starts_with("#VRML V2.0 utf8\n"));
+    assert!(wrl.contains("    creaseAngle 0.5\n"));
+    assert!(wrl.contains("    coord Coordinate { point [ "));
+    assert!(wrl.contains("    texCoord TextureCoordinate { point [ "));
+    assert!(wrl.contains("    texCoordIndex [ "));
+    let uv = mesh.uv_coords()[0];
+    assert!(wrl.contains(&format!("point [ {} {}, ", uv[0], uv[1])));
+    // the braces and the brackets are balanced
+    assert_eq!(wrl.matches('{').count(), wrl.matches('}').count());
+    assert_eq!(wrl.matches('[').count(), wrl.matches(']').count());
+}
+
+#[test]
+fn color_x3d_test() {
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let faces = Faces::from_iter([[0, 1, 2]]);
+    let mut mesh = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+    let colors = vec![
+        Vector4::new(1.0, 0.0, 0.0, 1.0),
+        Vector4::new(0.0, 1.0, 0.0, 0.5),
+        Vector4::new(0.0, 0.0, 1.0, 1.0),
+    ];
+    mesh.set_position_colors(colors).unwrap();
+    let x3d = write_string(&mesh, &Default::default());
+    assert!(x3d.contains(" colorIndex=\"0 1 2 -1\""));
+    // the alpha values are dropped
+    assert!(x3d.contains("<Color color=\"1 0 0, 0 1 0, 0 0 1\"/>"));
+}
@@ -101,6 +101,8 @@ impl App for MyApp {
                         roughness: t,
                         ambient_ratio: 0.02,
                         alpha_blend: false,
+                        double_sided: false,
+                        flat_shading: false,
                     },
                     ..Default::default()
                 };
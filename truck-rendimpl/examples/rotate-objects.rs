@@ -96,6 +96,8 @@ impl MyRender {
                 reflectance: 0.04 + 0.96 * (0.5 + (time / 2.0).sin() / 2.0),
                 ambient_ratio: 0.02,
                 alpha_blend: false,
+                double_sided: false,
+                flat_shading: false,
             };
             self.scene.update_bind_group(&*instance);
         }
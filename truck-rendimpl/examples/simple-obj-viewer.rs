@@ -58,6 +58,8 @@ impl MyApp {
                     roughness: 0.1,
                     ambient_ratio: 0.02,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 };
                 self.scene.add_object(&self.instance);
             }
@@ -72,6 +74,8 @@ impl MyApp {
                     roughness: 0.0,
                     ambient_ratio: 1.0,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 };
                 self.wireframe.instance_state_mut().color = Vector4::new(1.0, 1.0, 1.0, 1.0);
                 self.scene.add_object(&self.instance);
@@ -84,6 +88,8 @@ impl MyApp {
                     roughness: 0.1,
                     ambient_ratio: 0.02,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 };
                 self.wireframe.instance_state_mut().color = Vector4::new(0.0, 0.0, 0.0, 1.0);
                 self.scene.add_object(&self.instance);
@@ -75,9 +75,13 @@ impl App for MyApp {
                     reflectance: 0.04,
                     ambient_ratio: 0.05,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 },
                 texture: Some(std::sync::Arc::new(texture)),
                 backface_culling: true,
+                clipping_planes: Vec::new(),
+                depth: Default::default(),
             },
             ..Default::default()
         };
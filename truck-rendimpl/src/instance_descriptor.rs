@@ -9,6 +9,8 @@ impl Default for Material {
             reflectance: 0.25,
             ambient_ratio: 0.02,
             alpha_blend: false,
+            double_sided: false,
+            flat_shading: false,
         }
     }
 }
@@ -24,11 +26,13 @@ impl Material {
     ///     float roughness;
     ///     float reflectance;
     ///     float ambient_ratio;
+    ///     uint flags; // 1: double-sided, 2: flat shading
     /// };
     /// ```
     #[inline(always)]
     pub fn buffer(&self, device: &Device) -> BufferHandler {
-        let material_data: [f32; 7] = [
+        let flags = self.double_sided as u32 | (self.flat_shading as u32) << 1;
+        let material_data: [f32; 8] = [
             self.albedo[0] as f32,
             self.albedo[1] as f32,
             self.albedo[2] as f32,
@@ -36,6 +40,7 @@ impl Material {
             self.roughness as f32,
             self.reflectance as f32,
             self.ambient_ratio as f32,
+            f32::from_bits(flags),
         ];
         BufferHandler::from_slice(&material_data, device, BufferUsages::UNIFORM)
     }
@@ -63,23 +68,68 @@ impl Default for InstanceState {
             material: Default::default(),
             texture: None,
             backface_culling: true,
+            clipping_planes: Vec::new(),
+            depth: Default::default(),
+        }
+    }
+}
+
+impl Default for DepthState {
+    #[inline(always)]
+    fn default() -> DepthState {
+        DepthState {
+            depth_compare: CompareFunction::Less,
+            depth_write_enabled: true,
+            bias_constant: 0,
+            bias_slope_scale: 0.0,
+            bias_clamp: 0.0,
+        }
+    }
+}
+
+impl DepthState {
+    /// Returns the depth stencil state of the pipelines.
+    #[inline(always)]
+    pub fn depth_stencil_state(&self) -> DepthStencilState {
+        DepthStencilState {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: self.depth_write_enabled,
+            depth_compare: self.depth_compare,
+            stencil: Default::default(),
+            bias: DepthBiasState {
+                constant: self.bias_constant,
+                slope_scale: self.bias_slope_scale,
+                clamp: self.bias_clamp,
+            },
         }
     }
 }
 
 impl InstanceState {
-    /// Creates a `UNIFORM` buffer of instance matrix.
+    /// Creates a `UNIFORM` buffer of instance matrix and clipping planes.
     ///
     /// The bind group provided by the instances holds this uniform buffer.
     /// # Shader Examples
     /// ```glsl
     /// layout(set = 1, binding = 0) uniform ModelMatrix {
     ///     mat4 uniform_matrix;
+    ///     uint nplanes;
+    ///     vec4 clipping_planes[8];
     /// };
     /// ```
     #[inline(always)]
     pub fn matrix_buffer(&self, device: &Device) -> BufferHandler {
-        let matrix_data: [[f32; 4]; 4] = self.matrix.cast::<f32>().unwrap().into();
+        let matrix: [[f32; 4]; 4] = self.matrix.cast::<f32>().unwrap().into();
+        let nplanes = usize::min(self.clipping_planes.len(), MAX_CLIPPING_PLANES);
+        let mut planes = [[0.0_f32; 4]; MAX_CLIPPING_PLANES];
+        planes
+            .iter_mut()
+            .zip(&self.clipping_planes)
+            .for_each(|(plane, vec)| *plane = vec.cast::<f32>().unwrap().into());
+        let mut matrix_data: Vec<f32> = Vec::with_capacity(4 * (5 + MAX_CLIPPING_PLANES));
+        matrix_data.extend(matrix.iter().flatten());
+        matrix_data.extend(&[f32::from_bits(nplanes as u32), 0.0, 0.0, 0.0]);
+        matrix_data.extend(planes.iter().flatten());
         BufferHandler::from_slice(&matrix_data, device, BufferUsages::UNIFORM)
     }
 
@@ -107,6 +157,7 @@ impl InstanceState {
     ///     float roughness;
     ///     float reflectance;
     ///     float ambient_ratio;
+    ///     uint flags; // 1: double-sided, 2: flat shading
     /// };
     /// ```
     #[inline(always)]
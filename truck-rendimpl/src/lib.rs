@@ -12,16 +12,22 @@
 )]
 
 extern crate truck_meshalgo;
-extern crate truck_topology;
 extern crate truck_platform;
+extern crate truck_topology;
 use bytemuck::{Pod, Zeroable};
 use image::DynamicImage;
-use std::sync::Arc;
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use truck_meshalgo::prelude::progress::Progress;
 use truck_platform::{wgpu::*, *};
 
 /// Re-exports `truck_polymesh`.
 pub mod polymesh {
-    pub use truck_meshalgo::prelude::{base::*, PolygonMesh, StructuredMesh, Vertex};
+    pub use truck_meshalgo::prelude::progress;
+    pub use truck_meshalgo::prelude::{
+        base::*, CompactMesh, FaceStorage, FlatFaces, PointCloud, PolygonMesh, PolygonMeshF32,
+        PolygonMeshSlice, PolylineCurve, StructuredMesh, Vertex,
+    };
 }
 pub use polymesh::*;
 
@@ -41,6 +47,14 @@ pub struct Material {
     pub ambient_ratio: f64,
     /// alpha blend flag
     pub alpha_blend: bool,
+    /// If this flag is true, both sides of the faces are rendered: the backface culling is
+    /// disabled regardless of `InstanceState::backface_culling`, and the normals are flipped
+    /// on the back faces. Default is `false`.
+    pub double_sided: bool,
+    /// If this flag is true, the faces are shaded by the normals of the faces, calculated by
+    /// the derivatives of the positions in the fragment shader, instead of the vertex normals.
+    /// Default is `false`.
+    pub flat_shading: bool,
 }
 
 /// Configures of instances.
@@ -54,6 +68,38 @@ pub struct InstanceState {
     pub texture: Option<Arc<Texture>>,
     /// If this parameter is true, the backface culling will be activated.
     pub backface_culling: bool,
+    /// clipping planes of instance. Each plane `(a, b, c, d)` keeps the part
+    /// `a * x + b * y + c * z + d >= 0` in the world coordinate, and the other part is clipped.
+    /// The first [`MAX_CLIPPING_PLANES`] planes are used. Default is empty.
+    ///
+    /// [`MAX_CLIPPING_PLANES`]: ./constant.MAX_CLIPPING_PLANES.html
+    pub clipping_planes: Vec<Vector4>,
+    /// configure of the depth test and the depth bias
+    pub depth: DepthState,
+}
+
+/// The maximum number of the clipping planes of each instance.
+pub const MAX_CLIPPING_PLANES: usize = 8;
+
+/// Configures of the depth test and the depth bias of instances.
+///
+/// The instance whose `depth_compare` is `CompareFunction::Always` is rendered on top of
+/// the others, e.g. overlays, and the positive bias draws the coplanar decals over the faces
+/// without z-fighting. The depth bias is applied only to polygons, not to wireframes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthState {
+    /// comparison function of the depth test. Default is `CompareFunction::Less`.
+    pub depth_compare: CompareFunction,
+    /// If this parameter is true, the depth of the instance is written to the depth buffer.
+    /// Default is `true`.
+    pub depth_write_enabled: bool,
+    /// constant depth bias, in the units of the minimum resolvable difference of depths.
+    /// The positive bias makes the instance farther. Default is `0`.
+    pub bias_constant: i32,
+    /// depth bias scaled by the slope of the polygon. Default is `0.0`.
+    pub bias_slope_scale: f32,
+    /// the maximum absolute value of the depth bias, or no limit if `0.0`. Default is `0.0`.
+    pub bias_clamp: f32,
 }
 
 /// Configures of `WireFrameInstance`.
@@ -61,8 +107,23 @@ pub struct InstanceState {
 pub struct WireFrameState {
     /// instance matrix
     pub matrix: Matrix4,
-    /// color of instance
+    /// color of instance, multiplied by the colors of vertices
     pub color: Vector4,
+    /// configure of the depth test and the depth bias
+    pub depth: DepthState,
+}
+
+/// Line segment with the colors of the end points, for the wire frames colored
+/// per vertex or per segment, e.g. by convexity or by boundary status of edges.
+///
+/// The colors are multiplied by the color of [`WireFrameState`].
+/// The vertices of wire frames created from other data are white.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColoredSegment {
+    /// the end points
+    pub points: [Point3; 2],
+    /// the colors of the end points
+    pub colors: [Vector4; 2],
 }
 
 /// Configures of polygon instance
@@ -70,8 +131,21 @@ pub struct WireFrameState {
 pub struct PolygonInstanceDescriptor {
     /// configure of instance
     pub instance_state: InstanceState,
+    /// If this parameter is true, smooth normals of the vertices without normals
+    /// are generated by compute shaders at creating instance. The normals are averaged over
+    /// the triangles sharing the position index of the mesh. Default is `false`.
+    pub generate_normals: bool,
 }
 
+/// [`Progress`] of meshing shapes, shared by the clones of the descriptor.
+///
+/// The progress receives the number of tessellated faces and the total number of faces,
+/// and cancels the creation of the instance by returning `false`.
+///
+/// [`Progress`]: ./progress/trait.Progress.html
+#[derive(Clone)]
+pub struct MeshingProgress(Arc<Mutex<dyn Progress + Send>>);
+
 /// Configures of shape instance
 #[derive(Clone, Debug)]
 pub struct ShapeInstanceDescriptor {
@@ -79,6 +153,22 @@ pub struct ShapeInstanceDescriptor {
     pub instance_state: InstanceState,
     /// precision for meshing
     pub mesh_precision: f64,
+    /// callback for the progress of meshing. Default is `None`.
+    pub meshing_progress: Option<MeshingProgress>,
+    /// configure of the adaptive tessellation, used only by [`ShapeInstance`].
+    pub adaptive_tessellation: AdaptiveTessellation,
+}
+
+/// Configures of the adaptive screen-space tessellation of [`ShapeInstance`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveTessellation {
+    /// the maximum chordal error on the screen in pixels. Default is `0.5`.
+    pub pixels: f64,
+    /// the finest precision for meshing, the lower bound of the precisions. Default is `1.0e-4`.
+    pub min_precision: f64,
+    /// the maximum number of faces re-tessellated by one [`ShapeInstance::update`],
+    /// for keeping the frame rate. Default is `8`.
+    pub faces_per_update: usize,
 }
 
 /// Configures of wire frame instance of polygon
@@ -86,6 +176,11 @@ pub struct ShapeInstanceDescriptor {
 pub struct PolygonWireFrameDescriptor {
     /// configure of wire frame
     pub wireframe_state: WireFrameState,
+    /// If this parameter is `Some(angle)`, the wire frame of `PolygonMesh` consists of only
+    /// the feature edges: the boundary edges and the sharp edges whose adjacent faces make
+    /// the angle larger than `angle`. Otherwise, all edges of faces are drawn.
+    /// This parameter is ignored for `StructuredMesh`. Default is `None`.
+    pub feature_angle: Option<Rad<f64>>,
 }
 
 /// Configures of wire frame instance of shape
@@ -94,7 +189,29 @@ pub struct ShapeWireFrameDescriptor {
     /// configure of wire frame
     pub wireframe_state: WireFrameState,
     /// precision for polyline
+    ///
+    /// If `screen_space_precision` is `Some`, this is used only for the edges behind the camera.
     pub polyline_precision: f64,
+    /// If this parameter is `Some`, the edge curves are divided by the precision
+    /// corresponding to the given pixels at the nearest point of each edge on the screen,
+    /// giving smooth curves at any zoom. Default is `None`.
+    pub screen_space_precision: Option<ScreenSpacePrecision>,
+}
+
+/// Configures of the precision of polylines of wire frames based on the screen.
+///
+/// The wire frame instance should be recreated when the camera zooms.
+#[derive(Clone, Debug)]
+pub struct ScreenSpacePrecision {
+    /// the camera rendering the wire frame
+    pub camera: Camera,
+    /// the height of the screen in pixels
+    pub screen_height: u32,
+    /// the maximum distance in pixels between the polylines and the curves
+    pub pixels: f64,
+    /// the finest precision for polylines, the lower bound of the precisions,
+    /// e.g. `1.0e-4` as [`AdaptiveTessellation::min_precision`].
+    pub min_precision: f64,
 }
 
 /// shaders for rendering polygons
@@ -128,21 +245,202 @@ pub struct WireShaders {
 #[derive(Debug)]
 pub struct PolygonInstance {
     polygon: (Arc<BufferHandler>, Arc<BufferHandler>),
+    bounding_box: BoundingBox<Point3>,
     state: InstanceState,
     shaders: PolygonShaders,
     id: RenderID,
 }
 
+/// Polygon instance of a shape re-tessellated adaptively as the camera moves.
+///
+/// The instance keeps the B-rep of the shape, and [`ShapeInstance::update`] re-tessellates
+/// the faces whose chordal errors on the screen exceed [`AdaptiveTessellation::pixels`],
+/// so that zooming into a face never shows faceting.
+/// The shape is tessellated by [`ShapeInstanceDescriptor::mesh_precision`] at first,
+/// and the faces are only refined, never coarsened.
+#[derive(Debug)]
+pub struct ShapeInstance<C, S> {
+    shells: Vec<truck_topology::Shell<Point3, C, S>>,
+    meshed: Vec<truck_topology::Shell<Point3, PolylineCurve<Point3>, PolygonMesh>>,
+    faces: Vec<Vec<AdaptiveFace>>,
+    adaptive: AdaptiveTessellation,
+    instance: PolygonInstance,
+    handler: DeviceHandler,
+}
+
+/// the state of a face of `ShapeInstance`
+#[derive(Debug, Clone)]
+struct AdaptiveFace {
+    /// the current precision, `NaN` if the re-tessellation has failed
+    precision: f64,
+    /// the indices of the faces sharing edges
+    neighbors: Vec<usize>,
+    /// the bounding box of the first tessellation
+    bounding_box: BoundingBox<Point3>,
+}
+
 /// Wire frame rendering
 #[derive(Debug)]
 pub struct WireFrameInstance {
     vertices: Arc<BufferHandler>,
     strips: Arc<BufferHandler>,
+    bounding_box: BoundingBox<Point3>,
     state: WireFrameState,
     shaders: WireShaders,
     id: RenderID,
 }
 
+/// Still-image renderer by path tracing on CPU.
+///
+/// The camera, the lights and the background are given by the same `SceneDescriptor` as `Scene`,
+/// and the polygon meshes are placed and painted by `InstanceState`.
+/// [`RayTracer::from_scene`] takes all of them, including the polygon instances, from `Scene`.
+///
+/// The direct lighting is based on the same microfacet model as the standard shaders, with the
+/// shadows and the mirror reflections traced by the bounding volume hierarchy. The indirect
+/// lighting is estimated by Monte-Carlo sampling of the diffuse bounces, and the rays escaping
+/// from the scene bring the background color. The number of the paths per pixel is
+/// `supersampling * supersampling`, and the image is less noisy for the larger supersampling.
+/// # Examples
+/// ```
+/// use truck_platform::*;
+/// use truck_rendimpl::*;
+/// use truck_meshalgo::prelude::Faces;
+/// let positions = vec![
+///     Point3::new(-0.1, -0.1, -2.0),
+///     Point3::new(0.1, -0.1, -2.0),
+///     Point3::new(0.1, 0.1, -2.0),
+///     Point3::new(-0.1, 0.1, -2.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let square = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// // The default camera is placed at the origin and looks toward -z.
+/// let mut raytracer = RayTracer::new(SceneDescriptor::default());
+/// raytracer.add_polygon(&square, &InstanceState::default());
+/// let image = raytracer.render((16, 16), 2).unwrap();
+/// assert_eq!(image.len(), 16 * 16 * 4);
+/// // the center is the square
+/// let center = (8 * 16 + 8) * 4;
+/// assert!(image[center] > 0);
+/// // the corner is the black background
+/// assert_eq!(&image[0..4], &[0, 0, 0, 255]);
+///
+/// // the image without pixels
+/// assert!(raytracer.render((16, 0), 2).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RayTracer {
+    descriptor: SceneDescriptor,
+    objects: Vec<RayTracedObject>,
+    indirect_bounces: usize,
+}
+
+/// Errors occurred in rendering by [`RayTracer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RayTracingError {
+    /// the width or the height of the image is zero.
+    #[error("the size of the image is empty: {0:?}")]
+    EmptyImage((u32, u32)),
+    /// the projection of the camera is not invertible.
+    #[error("the projection of the camera is not invertible")]
+    SingularProjection,
+    /// the buffers of a polygon instance in the scene cannot be read.
+    #[error("failed to read the buffers of a polygon instance")]
+    BufferMapping,
+}
+
+/// The buffers and the state of a polygon instance in `Scene`, read by [`RayTracer::from_scene`].
+#[derive(Debug)]
+struct OfflinePolygon {
+    polygon: (Arc<BufferHandler>, Arc<BufferHandler>),
+    matrix: Matrix4,
+    material: Material,
+}
+
+#[derive(Debug, Clone)]
+struct RayTracedObject {
+    mesh: PolygonMesh,
+    bvh: truck_meshalgo::analyzers::MeshBVH,
+    material: Material,
+}
+
+/// The kinds of the points snapped by [`Snapper`], in the order of the priorities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SnapKind {
+    /// a vertex of the mesh
+    Vertex,
+    /// the midpoint of an edge of the mesh
+    EdgeMidpoint,
+    /// the center of a face of the mesh, the average of the vertices
+    FaceCenter,
+}
+
+/// The point snapped by [`Snapper`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapPoint {
+    /// the position in the world coordinates
+    pub position: Point3,
+    /// the kind of the point
+    pub kind: SnapKind,
+    /// the index of the mesh returned by `Snapper::add_polygon`
+    pub mesh_index: usize,
+    /// the distance between the point and the cursor in pixels
+    pub pixel_distance: f64,
+}
+
+/// Snapping service for measurement and placement tools.
+///
+/// Returns the nearest vertex, edge midpoint or face center of the polygon meshes
+/// within a pixel radius of the cursor.
+/// # Examples
+/// ```
+/// use truck_platform::*;
+/// use truck_rendimpl::*;
+/// use truck_meshalgo::prelude::Faces;
+/// let positions = vec![
+///     Point3::new(-0.5, -0.5, -2.0),
+///     Point3::new(0.5, -0.5, -2.0),
+///     Point3::new(0.5, 0.5, -2.0),
+///     Point3::new(-0.5, 0.5, -2.0),
+/// ];
+/// let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+/// let square = PolygonMesh::new(positions, Vec::new(), Vec::new(), faces);
+///
+/// let mut snapper = Snapper::new();
+/// snapper.add_polygon(&square, &InstanceState::default());
+/// // The default camera is placed at the origin and looks toward -z.
+/// let camera = Camera::default();
+/// let size = (512, 512);
+///
+/// // the cursor near the center of the screen
+/// let point = snapper.snap(&camera, size, (260.0, 250.0), 10.0).unwrap();
+/// assert_eq!(point.kind, SnapKind::FaceCenter);
+/// assert!(point.position.near(&Point3::new(0.0, 0.0, -2.0)));
+///
+/// // the upper right vertex is prior to the face center.
+/// let pt = camera.projection(1.0).transform_point(Point3::new(0.5, 0.5, -2.0));
+/// let cursor = ((pt[0] + 1.0) * 256.0, (1.0 - pt[1]) * 256.0);
+/// let point = snapper.snap(&camera, size, cursor, 400.0).unwrap();
+/// assert_eq!(point.kind, SnapKind::Vertex);
+/// assert!(point.position.near(&Point3::new(0.5, 0.5, -2.0)));
+///
+/// // no points near the corner of the screen
+/// assert!(snapper.snap(&camera, size, (0.0, 0.0), 10.0).is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Snapper {
+    objects: Vec<SnapObject>,
+}
+
+#[derive(Debug, Clone)]
+struct SnapObject {
+    vertices: Vec<Point3>,
+    edge_midpoints: Vec<Point3>,
+    face_centers: Vec<Point3>,
+    bvh: truck_meshalgo::analyzers::MeshBVH,
+}
+
 /// Constroctor for instances
 #[derive(Debug, Clone)]
 pub struct InstanceCreator {
@@ -211,6 +509,13 @@ struct AttrVertex {
     pub normal: [f32; 3],
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+struct WireVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
 #[derive(Debug, Clone)]
 struct ExpandedPolygon<V> {
     vertices: Vec<V>,
@@ -221,7 +526,11 @@ struct ExpandedPolygon<V> {
 pub mod image2texture;
 mod instance_creator;
 mod instance_descriptor;
+mod normal_generator;
 mod polygon_instance;
 mod polyrend;
+mod raytracer;
+mod shape_instance;
 mod shaperend;
+mod snapping;
 mod wireframe_instance;
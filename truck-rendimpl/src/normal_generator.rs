@@ -0,0 +1,170 @@
+use crate::*;
+use polymesh::Vertex;
+
+const WORKGROUP_SIZE: u32 = 64;
+/// the maximum number of the workgroups in one dimension of a dispatch
+const MAX_WORKGROUPS: u32 = 65535;
+
+fn storage_entry(read_only: bool) -> PreBindGroupLayoutEntry {
+    PreBindGroupLayoutEntry {
+        visibility: ShaderStages::COMPUTE,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn uniform_entry() -> PreBindGroupLayoutEntry {
+    PreBindGroupLayoutEntry {
+        visibility: ShaderStages::COMPUTE,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Returns the indices of the positions in the original mesh of the expanded vertices from
+/// the corners of the original faces and the indices of the expanded vertices of them.
+pub(crate) fn position_ids<'a>(
+    vertices_len: usize,
+    corners: impl Iterator<Item = (Vertex, &'a u32)>,
+) -> Vec<u32> {
+    let mut ids = vec![0; vertices_len];
+    corners.for_each(|(corner, idx)| ids[*idx as usize] = corner.pos as u32);
+    ids
+}
+
+impl ExpandedPolygon<AttrVertex> {
+    /// Returns the triangles around the positions in the compressed sparse row format.
+    /// `position_ids` are the indices of the positions of the vertices.
+    fn adjacency(&self, position_ids: &[u32]) -> (Vec<u32>, Vec<u32>) {
+        let len = position_ids
+            .iter()
+            .map(|id| *id as usize + 1)
+            .max()
+            .unwrap_or(0);
+        // the positions of the corners of the triangles, without the repeated ones
+        let corners = self.indices.chunks(3).enumerate().flat_map(|(i, tri)| {
+            let ids = [0, 1, 2].map(|k| position_ids[tri[k] as usize]);
+            (0..3)
+                .filter(move |k| !ids[..*k].contains(&ids[*k]))
+                .map(move |k| (ids[k] as usize, i as u32))
+        });
+        let mut offsets = vec![0_u32; len + 1];
+        corners.clone().for_each(|(id, _)| offsets[id + 1] += 1);
+        (0..len).for_each(|id| offsets[id + 1] += offsets[id]);
+        let mut filled = offsets.clone();
+        let mut adjacency = vec![0; offsets[len] as usize];
+        corners.for_each(|(id, face)| {
+            adjacency[filled[id] as usize] = face;
+            filled[id] += 1;
+        });
+        (offsets, adjacency)
+    }
+
+    /// Creates buffers, and generates smooth normals of the vertices without normals
+    /// by compute shaders. `position_ids` are the indices of the positions of the vertices
+    /// in the original mesh, and the vertices at the same position share adjacent triangles.
+    /// The vertex buffer can be copied for reading back the normals.
+    pub fn normal_generated_buffers(
+        &self,
+        position_ids: &[u32],
+        vertex_usage: BufferUsages,
+        index_usage: BufferUsages,
+        handler: &DeviceHandler,
+    ) -> (BufferHandler, BufferHandler) {
+        let (device, queue) = (handler.device(), handler.queue());
+        let face_len = self.indices.len() / 3;
+        if face_len == 0 {
+            return self.buffers(vertex_usage, index_usage, device);
+        }
+        let vertex_usage = vertex_usage | BufferUsages::STORAGE | BufferUsages::COPY_SRC;
+        let index_usage = index_usage | BufferUsages::STORAGE;
+        let (vb, ib) = self.buffers(vertex_usage, index_usage, device);
+        let (offsets, adjacency) = self.adjacency(position_ids);
+        let face_normals = vec![0.0_f32; face_len * 3];
+        let fb = BufferHandler::from_slice(&face_normals, device, BufferUsages::STORAGE);
+        let ob = BufferHandler::from_slice(&offsets, device, BufferUsages::STORAGE);
+        let ab = BufferHandler::from_slice(&adjacency, device, BufferUsages::STORAGE);
+        let pb = BufferHandler::from_slice(&position_ids, device, BufferUsages::STORAGE);
+        // the invocations are dispatched in two dimensions, since the number of the workgroups
+        // in one dimension is limited, and indexed by `x + y * stride`.
+        // `len` is not zero, since there are faces.
+        let workgroups = |len: usize| (len as u32 - 1) / WORKGROUP_SIZE + 1;
+        let x_groups = u32::min(
+            workgroups(usize::max(face_len, self.vertices.len())),
+            MAX_WORKGROUPS,
+        );
+        let y_groups = |len: usize| (workgroups(len) - 1) / x_groups + 1;
+        let stride = x_groups * WORKGROUP_SIZE;
+        let counts = [face_len as u32, self.vertices.len() as u32, stride, 0];
+        let cb = BufferHandler::from_slice(&counts, device, BufferUsages::UNIFORM);
+
+        let layout = bind_group_util::create_bind_group_layout(
+            device,
+            &[
+                storage_entry(false),
+                storage_entry(true),
+                storage_entry(false),
+                storage_entry(true),
+                storage_entry(true),
+                uniform_entry(),
+                storage_entry(true),
+            ],
+        );
+        let bind_group = bind_group_util::create_bind_group(
+            device,
+            &layout,
+            vec![
+                vb.binding_resource(),
+                ib.binding_resource(),
+                fb.binding_resource(),
+                ob.binding_resource(),
+                ab.binding_resource(),
+                cb.binding_resource(),
+                pb.binding_resource(),
+            ],
+        );
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+            label: None,
+        });
+        let module = device.create_shader_module(&ShaderModuleDescriptor {
+            source: ShaderSource::Wgsl(include_str!("shaders/normals.wgsl").into()),
+            label: None,
+        });
+        let create_pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point,
+                label: None,
+            })
+        };
+        let face_pipeline = create_pipeline("face_main");
+        let vertex_pipeline = create_pipeline("vertex_main");
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        {
+            let mut cpass = encoder.begin_compute_pass(&ComputePassDescriptor { label: None });
+            cpass.set_pipeline(&face_pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch(x_groups, y_groups(face_len), 1);
+        }
+        {
+            let mut cpass = encoder.begin_compute_pass(&ComputePassDescriptor { label: None });
+            cpass.set_pipeline(&vertex_pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch(x_groups, y_groups(self.vertices.len()), 1);
+        }
+        queue.submit(Some(encoder.finish()));
+        (vb, ib)
+    }
+}
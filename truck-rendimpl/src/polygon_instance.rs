@@ -6,6 +6,7 @@ impl PolygonInstance {
     pub fn clone_instance(&self) -> PolygonInstance {
         PolygonInstance {
             polygon: self.polygon.clone(),
+            bounding_box: self.bounding_box.clone(),
             state: self.state.clone(),
             shaders: self.shaders.clone(),
             id: RenderID::gen(),
@@ -28,6 +29,13 @@ impl PolygonInstance {
         let polygon = self.polygon.clone();
         self.polygon = other.polygon.clone();
         other.polygon = polygon;
+        std::mem::swap(&mut self.bounding_box, &mut other.bounding_box);
+    }
+
+    /// Returns the bounding box in the world coordinates, transformed by the instance matrix.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> {
+        transformed_bounding_box(&self.bounding_box, &self.state.matrix)
     }
 
     #[inline(always)]
@@ -83,6 +91,18 @@ impl PolygonInstance {
 impl Rendered for PolygonInstance {
     impl_render_id!(id);
 
+    #[inline(always)]
+    fn bounding_box(&self) -> Option<BoundingBox<Point3>> { Some(self.bounding_box()) }
+
+    #[inline(always)]
+    fn offline_data(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        Some(Arc::new(OfflinePolygon {
+            polygon: self.polygon.clone(),
+            matrix: self.state.matrix,
+            material: self.state.material,
+        }))
+    }
+
     #[inline(always)]
     fn vertex_buffer(&self, _: &DeviceHandler) -> (Arc<BufferHandler>, Option<Arc<BufferHandler>>) {
         let polygon = self.polygon.clone();
@@ -122,7 +142,8 @@ impl Rendered for PolygonInstance {
             ),
             false => (&self.shaders.fragment_module, self.shaders.fragment_entry),
         };
-        let cull_mode = match self.state.backface_culling {
+        let culling = self.state.backface_culling && !self.state.material.double_sided;
+        let cull_mode = match culling {
             true => Some(wgpu::Face::Back),
             false => None,
         };
@@ -174,13 +195,7 @@ impl Rendered for PolygonInstance {
                 clamp_depth: false,
                 ..Default::default()
             },
-            depth_stencil: Some(DepthStencilState {
-                format: TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: Default::default(),
-                bias: Default::default(),
-            }),
+            depth_stencil: Some(self.state.depth.depth_stencil_state()),
             multisample: MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -191,3 +206,24 @@ impl Rendered for PolygonInstance {
         Arc::new(pipeline)
     }
 }
+
+/// Returns the bounding box of the eight corners of `bounding_box` transformed by `matrix`.
+pub(crate) fn transformed_bounding_box(
+    bounding_box: &BoundingBox<Point3>,
+    matrix: &Matrix4,
+) -> BoundingBox<Point3> {
+    if bounding_box.is_empty() {
+        return BoundingBox::new();
+    }
+    let (min, max) = (bounding_box.min(), bounding_box.max());
+    (0..8)
+        .map(|i| {
+            let corner = Point3::new(
+                if i & 1 == 0 { min[0] } else { max[0] },
+                if i & 2 == 0 { min[1] } else { max[1] },
+                if i & 4 == 0 { min[2] } else { max[2] },
+            );
+            matrix.transform_point(corner)
+        })
+        .collect()
+}
@@ -1,6 +1,9 @@
+use crate::normal_generator::position_ids;
 use crate::*;
 use polymesh::Vertex;
 use std::collections::HashMap;
+use truck_meshalgo::analyzers::FeatureEdges;
+use truck_meshalgo::prelude::Faces;
 
 impl<V: Sized + Zeroable + Pod> ExpandedPolygon<V> {
     pub fn buffers(
@@ -24,7 +27,31 @@ impl<V> Default for ExpandedPolygon<V> {
     }
 }
 
-impl CreateBuffers for PolygonMesh {
+impl<F: FaceStorage> CreateBuffers for PolygonMesh<F> {
+    #[inline(always)]
+    fn buffers(
+        &self,
+        vertex_usage: BufferUsages,
+        index_usage: BufferUsages,
+        device: &Device,
+    ) -> (BufferHandler, BufferHandler) {
+        self.as_slice().buffers(vertex_usage, index_usage, device)
+    }
+}
+
+impl<F: FaceStorage> CreateBuffers for PolygonMeshSlice<'_, F> {
+    #[inline(always)]
+    fn buffers(
+        &self,
+        vertex_usage: BufferUsages,
+        index_usage: BufferUsages,
+        device: &Device,
+    ) -> (BufferHandler, BufferHandler) {
+        ExpandedPolygon::from(*self).buffers(vertex_usage, index_usage, device)
+    }
+}
+
+impl CreateBuffers for PolygonMeshF32 {
     #[inline(always)]
     fn buffers(
         &self,
@@ -36,6 +63,27 @@ impl CreateBuffers for PolygonMesh {
     }
 }
 
+impl CreateBuffers for CompactMesh {
+    #[inline(always)]
+    fn buffers(
+        &self,
+        vertex_usage: BufferUsages,
+        index_usage: BufferUsages,
+        device: &Device,
+    ) -> (BufferHandler, BufferHandler) {
+        ExpandedPolygon::from(self).buffers(vertex_usage, index_usage, device)
+    }
+}
+
+/// Returns the usages of the vertex and the index buffers of polygon instances.
+/// The buffers are copied for ray tracing by `RayTracer::from_scene`.
+fn polygon_usages() -> (BufferUsages, BufferUsages) {
+    (
+        BufferUsages::VERTEX | BufferUsages::COPY_SRC,
+        BufferUsages::INDEX | BufferUsages::COPY_SRC,
+    )
+}
+
 impl Instance for PolygonInstance {
     type Shaders = PolygonShaders;
     fn standard_shaders(creator: &InstanceCreator) -> PolygonShaders {
@@ -43,7 +91,50 @@ impl Instance for PolygonInstance {
     }
 }
 
-impl IntoInstance<PolygonInstance> for PolygonMesh {
+impl<F: FaceStorage> IntoInstance<PolygonInstance> for PolygonMesh<F> {
+    type Descriptor = PolygonInstanceDescriptor;
+    #[inline(always)]
+    fn into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &PolygonShaders,
+        desc: &PolygonInstanceDescriptor,
+    ) -> PolygonInstance {
+        self.as_slice().into_instance(handler, shaders, desc)
+    }
+}
+
+impl<F: FaceStorage> IntoInstance<PolygonInstance> for PolygonMeshSlice<'_, F> {
+    type Descriptor = PolygonInstanceDescriptor;
+    #[inline(always)]
+    fn into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &PolygonShaders,
+        desc: &PolygonInstanceDescriptor,
+    ) -> PolygonInstance {
+        let (vertex_usage, index_usage) = polygon_usages();
+        let (vb, ib) = match desc.generate_normals {
+            true => {
+                let compact = CompactMesh::from(*self);
+                let expanded = ExpandedPolygon::from(&compact);
+                let corners = self.face_iter().flatten().copied().zip(compact.indices());
+                let ids = position_ids(expanded.vertices.len(), corners);
+                expanded.normal_generated_buffers(&ids, vertex_usage, index_usage, handler)
+            }
+            false => self.buffers(vertex_usage, index_usage, handler.device()),
+        };
+        PolygonInstance {
+            polygon: (Arc::new(vb), Arc::new(ib)),
+            bounding_box: self.bounding_box(),
+            state: desc.instance_state.clone(),
+            shaders: shaders.clone(),
+            id: RenderID::gen(),
+        }
+    }
+}
+
+impl IntoInstance<PolygonInstance> for PolygonMeshF32 {
     type Descriptor = PolygonInstanceDescriptor;
     #[inline(always)]
     fn into_instance(
@@ -52,9 +143,19 @@ impl IntoInstance<PolygonInstance> for PolygonMesh {
         shaders: &PolygonShaders,
         desc: &PolygonInstanceDescriptor,
     ) -> PolygonInstance {
-        let (vb, ib) = self.buffers(BufferUsages::VERTEX, BufferUsages::INDEX, handler.device());
+        let expanded = ExpandedPolygon::from(self);
+        let (vertex_usage, index_usage) = polygon_usages();
+        let (vb, ib) = match desc.generate_normals {
+            true => {
+                let corners = expanded_corners(self.faces()).zip(&expanded.indices);
+                let ids = position_ids(expanded.vertices.len(), corners);
+                expanded.normal_generated_buffers(&ids, vertex_usage, index_usage, handler)
+            }
+            false => expanded.buffers(vertex_usage, index_usage, handler.device()),
+        };
         PolygonInstance {
             polygon: (Arc::new(vb), Arc::new(ib)),
+            bounding_box: self.bounding_box(),
             state: desc.instance_state.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
@@ -63,6 +164,20 @@ impl IntoInstance<PolygonInstance> for PolygonMesh {
 }
 
 impl IntoInstance<WireFrameInstance> for PolygonMesh {
+    type Descriptor = PolygonWireFrameDescriptor;
+    #[doc(hidden)]
+    #[inline(always)]
+    fn into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &WireShaders,
+        desc: &PolygonWireFrameDescriptor,
+    ) -> WireFrameInstance {
+        self.as_slice().into_instance(handler, shaders, desc)
+    }
+}
+
+impl IntoInstance<WireFrameInstance> for PolygonMeshSlice<'_> {
     type Descriptor = PolygonWireFrameDescriptor;
     #[doc(hidden)]
     fn into_instance(
@@ -72,23 +187,30 @@ impl IntoInstance<WireFrameInstance> for PolygonMesh {
         desc: &PolygonWireFrameDescriptor,
     ) -> WireFrameInstance {
         let device = handler.device();
-        let positions: Vec<[f32; 3]> = self
+        let positions: Vec<WireVertex> = self
             .positions()
             .iter()
-            .map(|p| p.cast().unwrap().into())
+            .map(|p| WireVertex::white(*p))
             .collect();
         let mut strips = Vec::<u32>::new();
-        self.faces().face_iter().for_each(|face| {
-            for i in 0..face.len() {
-                strips.push(face[i].pos as u32);
-                strips.push(face[(i + 1) % face.len()].pos as u32);
-            }
-        });
+        match desc.feature_angle {
+            Some(angle) => self.extract_feature_edges(angle).iter().for_each(|edge| {
+                strips.push(edge[0] as u32);
+                strips.push(edge[1] as u32);
+            }),
+            None => self.faces().face_iter().for_each(|face| {
+                for i in 0..face.len() {
+                    strips.push(face[i].pos as u32);
+                    strips.push(face[(i + 1) % face.len()].pos as u32);
+                }
+            }),
+        }
         let vb = BufferHandler::from_slice(&positions, device, BufferUsages::VERTEX);
         let ib = BufferHandler::from_slice(&strips, device, BufferUsages::INDEX);
         WireFrameInstance {
             vertices: Arc::new(vb),
             strips: Arc::new(ib),
+            bounding_box: self.bounding_box(),
             state: desc.wireframe_state.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
@@ -117,9 +239,19 @@ impl IntoInstance<PolygonInstance> for StructuredMesh {
         shaders: &PolygonShaders,
         desc: &PolygonInstanceDescriptor,
     ) -> PolygonInstance {
-        let (vb, ib) = self.buffers(BufferUsages::VERTEX, BufferUsages::INDEX, handler.device());
+        let expanded = ExpandedPolygon::from(self);
+        let (vertex_usage, index_usage) = polygon_usages();
+        let (vb, ib) = match desc.generate_normals {
+            true => {
+                // The vertices of the grid are not duplicated.
+                let ids: Vec<u32> = (0..expanded.vertices.len() as u32).collect();
+                expanded.normal_generated_buffers(&ids, vertex_usage, index_usage, handler)
+            }
+            false => expanded.buffers(vertex_usage, index_usage, handler.device()),
+        };
         PolygonInstance {
             polygon: (Arc::new(vb), Arc::new(ib)),
+            bounding_box: self.positions().iter().flatten().collect(),
             state: desc.instance_state.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
@@ -137,15 +269,16 @@ impl IntoInstance<WireFrameInstance> for StructuredMesh {
         desc: &PolygonWireFrameDescriptor,
     ) -> WireFrameInstance {
         let device = handler.device();
-        let positions: Vec<[f32; 3]> = self
+        let positions: Vec<WireVertex> = self
             .positions()
             .iter()
             .flat_map(|vec| vec)
-            .map(|p| p.cast().unwrap().into())
+            .map(|p| WireVertex::white(*p))
             .collect();
         let mut strips = Vec::<u32>::new();
-        let len = positions[0].len() as u32;
-        for i in 1..positions.len() as u32 {
+        let rows = self.positions().len() as u32;
+        let len = self.positions()[0].len() as u32;
+        for i in 1..rows {
             strips.push((i - 1) * len);
             strips.push(i * len);
         }
@@ -153,7 +286,7 @@ impl IntoInstance<WireFrameInstance> for StructuredMesh {
             strips.push(j - 1);
             strips.push(j);
         }
-        for i in 1..positions.len() as u32 {
+        for i in 1..rows {
             for j in 1..len {
                 strips.push((i - 1) * len + j);
                 strips.push(i * len + j);
@@ -166,6 +299,7 @@ impl IntoInstance<WireFrameInstance> for StructuredMesh {
         WireFrameInstance {
             vertices: Arc::new(vb),
             strips: Arc::new(ib),
+            bounding_box: self.positions().iter().flatten().collect(),
             state: desc.wireframe_state.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
@@ -173,8 +307,33 @@ impl IntoInstance<WireFrameInstance> for StructuredMesh {
     }
 }
 
-fn signup_vertex(
-    polymesh: &PolygonMesh,
+// the meshes whose face vertices are expanded to the vertices of GPU
+trait ExpandVertices {
+    fn faces(&self) -> &Faces;
+    fn attr_vertex(&self, vertex: Vertex) -> AttrVertex;
+}
+
+impl ExpandVertices for PolygonMeshF32 {
+    #[inline(always)]
+    fn faces(&self) -> &Faces { PolygonMeshF32::faces(self) }
+    #[inline(always)]
+    fn attr_vertex(&self, vertex: Vertex) -> AttrVertex {
+        AttrVertex {
+            position: self.positions()[vertex.pos],
+            uv_coord: vertex
+                .uv
+                .map(|uv| self.uv_coords()[uv])
+                .unwrap_or([0.0, 0.0]),
+            normal: vertex
+                .nor
+                .map(|nor| self.normals()[nor])
+                .unwrap_or([0.0, 0.0, 0.0]),
+        }
+    }
+}
+
+fn signup_vertex<M: ExpandVertices>(
+    polymesh: &M,
     vertex: Vertex,
     glpolymesh: &mut ExpandedPolygon<AttrVertex>,
     vertex_map: &mut HashMap<Vertex, u32>,
@@ -183,56 +342,79 @@ fn signup_vertex(
         Some(idx) => *idx,
         None => {
             let idx = glpolymesh.vertices.len() as u32;
-            let position = polymesh.positions()[vertex.pos].cast().unwrap().into();
-            let uv_coord = match vertex.uv {
-                Some(uv) => polymesh.uv_coords()[uv].cast().unwrap().into(),
-                None => [0.0, 0.0],
-            };
-            let normal = match vertex.nor {
-                Some(nor) => polymesh.normals()[nor].cast().unwrap().into(),
-                None => [0.0, 0.0, 0.0],
-            };
-            let wgpuvertex = AttrVertex {
-                position,
-                uv_coord,
-                normal,
-            };
             vertex_map.insert(vertex, idx);
-            glpolymesh.vertices.push(wgpuvertex);
+            glpolymesh.vertices.push(polymesh.attr_vertex(vertex));
             idx
         }
     };
     glpolymesh.indices.push(idx);
 }
 
-impl From<&PolygonMesh> for ExpandedPolygon<AttrVertex> {
-    fn from(polymesh: &PolygonMesh) -> ExpandedPolygon<AttrVertex> {
-        let mut glpolymesh = ExpandedPolygon::default();
-        let mut vertex_map = HashMap::<Vertex, u32>::new();
-        for tri in polymesh.faces().tri_faces() {
-            signup_vertex(polymesh, tri[0], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, tri[1], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, tri[2], &mut glpolymesh, &mut vertex_map);
-        }
-        for quad in polymesh.faces().quad_faces() {
-            signup_vertex(polymesh, quad[0], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, quad[1], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, quad[3], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, quad[1], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, quad[2], &mut glpolymesh, &mut vertex_map);
-            signup_vertex(polymesh, quad[3], &mut glpolymesh, &mut vertex_map);
-        }
-        for face in polymesh.faces().other_faces() {
-            for i in 2..face.len() {
-                signup_vertex(polymesh, face[0], &mut glpolymesh, &mut vertex_map);
-                signup_vertex(polymesh, face[i - 1], &mut glpolymesh, &mut vertex_map);
-                signup_vertex(polymesh, face[i], &mut glpolymesh, &mut vertex_map);
-            }
-        }
-        glpolymesh
+/// the corners of the triangles divided from the faces, in the order of the indices of
+/// the expanded polygon
+fn expanded_corners(faces: &Faces) -> impl Iterator<Item = Vertex> + '_ {
+    let tris = faces.tri_faces().iter().flat_map(|tri| tri.iter().copied());
+    let quads = (faces.quad_faces().iter()).flat_map(|quad| {
+        let [a, b, c, d] = *quad;
+        [a, b, d, b, c, d]
+    });
+    let others = (faces.other_faces().iter())
+        .flat_map(|face| (2..face.len()).flat_map(move |i| [face[0], face[i - 1], face[i]]));
+    tris.chain(quads).chain(others)
+}
+
+fn expand_polygon<M: ExpandVertices>(polymesh: &M) -> ExpandedPolygon<AttrVertex> {
+    let mut glpolymesh = ExpandedPolygon::default();
+    let mut vertex_map = HashMap::<Vertex, u32>::new();
+    expanded_corners(polymesh.faces())
+        .for_each(|vertex| signup_vertex(polymesh, vertex, &mut glpolymesh, &mut vertex_map));
+    glpolymesh
+}
+
+impl<F: FaceStorage> From<&PolygonMesh<F>> for ExpandedPolygon<AttrVertex> {
+    #[inline(always)]
+    fn from(polymesh: &PolygonMesh<F>) -> ExpandedPolygon<AttrVertex> {
+        ExpandedPolygon::from(polymesh.as_slice())
     }
 }
 
+impl<F: FaceStorage> From<PolygonMeshSlice<'_, F>> for ExpandedPolygon<AttrVertex> {
+    #[inline(always)]
+    fn from(polymesh: PolygonMeshSlice<'_, F>) -> ExpandedPolygon<AttrVertex> {
+        ExpandedPolygon::from(&CompactMesh::from(polymesh))
+    }
+}
+
+impl From<&CompactMesh> for ExpandedPolygon<AttrVertex> {
+    fn from(mesh: &CompactMesh) -> ExpandedPolygon<AttrVertex> {
+        let vertices = (0..mesh.positions().len())
+            .map(|i| AttrVertex {
+                position: mesh.positions()[i].cast().unwrap().into(),
+                uv_coord: match mesh.uv_coords().is_empty() {
+                    true => [0.0, 0.0],
+                    false => mesh.uv_coords()[i].cast().unwrap().into(),
+                },
+                normal: match mesh.normals().is_empty() {
+                    true => [0.0, 0.0, 0.0],
+                    false => mesh.normals()[i].cast().unwrap().into(),
+                },
+            })
+            .collect();
+        let mut indices = Vec::with_capacity(mesh.indices().len() * 2);
+        mesh.face_iter().for_each(|face| match face.len() {
+            4 => indices.extend(&[face[0], face[1], face[3], face[1], face[2], face[3]]),
+            _ => (2..face.len()).for_each(|i| indices.extend(&[face[0], face[i - 1], face[i]])),
+        });
+        ExpandedPolygon { vertices, indices }
+    }
+}
+
+impl From<&PolygonMeshF32> for ExpandedPolygon<AttrVertex> {
+    /// The `f32` arrays are copied into the vertices without conversion.
+    #[inline(always)]
+    fn from(polymesh: &PolygonMeshF32) -> ExpandedPolygon<AttrVertex> { expand_polygon(polymesh) }
+}
+
 impl From<&StructuredMesh> for ExpandedPolygon<AttrVertex> {
     fn from(mesh: &StructuredMesh) -> ExpandedPolygon<AttrVertex> {
         let mut glpolymesh = ExpandedPolygon::default();
@@ -0,0 +1,430 @@
+use crate::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::f64::consts::PI;
+use truck_meshalgo::analyzers::MeshBVH;
+use truck_meshalgo::prelude::Faces;
+
+/// the maximum number of reflections
+const MAX_DEPTH: usize = 4;
+/// offset of the origins of secondary rays for avoiding self-intersections
+const RAY_OFFSET: f64 = 1.0e-6;
+/// the number of `f32` in `AttrVertex`: position(3), uv(2) and normal(3)
+const ATTR_VERTEX_FLOATS: usize = 8;
+
+impl RayTracedObject {
+    fn new(mesh: &PolygonMesh, state: &InstanceState) -> Self {
+        let mut mesh = mesh.clone();
+        let matrix = state.matrix;
+        mesh.positions_mut()
+            .iter_mut()
+            .for_each(|p| *p = matrix.transform_point(*p));
+        if let Some(normal_matrix) = matrix.invert().map(|m| m.transpose()) {
+            mesh.normals_mut().iter_mut().for_each(|n| {
+                let vec = normal_matrix.transform_vector(*n);
+                if !vec.so_small() {
+                    *n = vec.normalize();
+                }
+            });
+        }
+        RayTracedObject {
+            bvh: MeshBVH::new(&mesh),
+            mesh,
+            material: state.material,
+        }
+    }
+}
+
+/// Reads the contents of `buffer` by copying it to a mappable buffer.
+fn read_buffer(
+    handler: &DeviceHandler,
+    buffer: &BufferHandler,
+) -> Result<Vec<u8>, RayTracingError> {
+    let (device, queue) = (handler.device(), handler.queue());
+    let staging = device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: buffer.size(),
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(buffer.buffer(), 0, &staging, 0, buffer.size());
+    queue.submit(Some(encoder.finish()));
+    let slice = staging.slice(..);
+    let future = slice.map_async(MapMode::Read);
+    device.poll(Maintain::Wait);
+    futures::executor::block_on(future).map_err(|_| RayTracingError::BufferMapping)?;
+    let bytes = slice.get_mapped_range().to_vec();
+    staging.unmap();
+    Ok(bytes)
+}
+
+impl OfflinePolygon {
+    /// Reads the mesh from the vertex buffer of `AttrVertex` and the index buffer of triangles.
+    fn read_mesh(&self, handler: &DeviceHandler) -> Result<PolygonMesh, RayTracingError> {
+        let floats: Vec<f32> = read_buffer(handler, &self.polygon.0)?
+            .chunks_exact(4)
+            .map(|x| f32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+            .collect();
+        let indices: Vec<usize> = read_buffer(handler, &self.polygon.1)?
+            .chunks_exact(4)
+            .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]) as usize)
+            .collect();
+        let vertices = floats.chunks_exact(ATTR_VERTEX_FLOATS);
+        let positions = vertices
+            .clone()
+            .map(|v| Point3::new(v[0] as f64, v[1] as f64, v[2] as f64))
+            .collect();
+        // The vertices without normals have zero vectors, replaced by the normals of faces.
+        let normals = vertices
+            .map(|v| Vector3::new(v[5] as f64, v[6] as f64, v[7] as f64))
+            .collect();
+        let tri_faces: Vec<[Vertex; 3]> = indices
+            .chunks_exact(3)
+            .map(|tri| [0, 1, 2].map(|k| (tri[k], None, Some(tri[k])).into()))
+            .collect();
+        let faces = Faces::from_tri_and_quad_faces(tri_faces, Vec::new());
+        PolygonMesh::try_new(positions, Vec::new(), normals, faces)
+            .map_err(|_| RayTracingError::BufferMapping)
+    }
+}
+
+impl RayTracer {
+    /// Creates an empty ray tracer with the camera, the lights and the background of `descriptor`.
+    #[inline(always)]
+    pub fn new(descriptor: SceneDescriptor) -> Self {
+        RayTracer {
+            descriptor,
+            objects: Vec::new(),
+            indirect_bounces: 2,
+        }
+    }
+
+    /// Creates a ray tracer with the camera, the lights, the background and the polygon
+    /// instances of `scene`.
+    ///
+    /// The meshes are read back from the GPU buffers of the instances, which blocks until the
+    /// copies are finished. The instances are placed and painted by the `InstanceState`
+    /// at the time when they are added to `scene` or their bind groups are updated.
+    /// The textures and the other kinds of instances, e.g. wire frames, are ignored.
+    pub fn from_scene(scene: &Scene) -> Result<Self, RayTracingError> {
+        let mut raytracer = Self::new(scene.descriptor().clone());
+        let handler = scene.device_handler();
+        let polygons = scene
+            .offline_data()
+            .filter_map(|data| data.downcast_ref::<OfflinePolygon>());
+        for polygon in polygons {
+            let mesh = polygon.read_mesh(handler)?;
+            let state = InstanceState {
+                matrix: polygon.matrix,
+                material: polygon.material,
+                ..Default::default()
+            };
+            raytracer.add_polygon(&mesh, &state);
+        }
+        Ok(raytracer)
+    }
+
+    /// Returns the reference of the descriptor.
+    #[inline(always)]
+    pub fn descriptor(&self) -> &SceneDescriptor { &self.descriptor }
+
+    /// Returns the mutable reference of the descriptor.
+    #[inline(always)]
+    pub fn descriptor_mut(&mut self) -> &mut SceneDescriptor { &mut self.descriptor }
+
+    /// Adds a polygon mesh placed and painted by `state`.
+    ///
+    /// The texture of `state` is ignored.
+    #[inline(always)]
+    pub fn add_polygon(&mut self, mesh: &PolygonMesh, state: &InstanceState) {
+        self.objects.push(RayTracedObject::new(mesh, state))
+    }
+
+    /// Removes all polygon meshes.
+    #[inline(always)]
+    pub fn clear_polygons(&mut self) { self.objects.clear() }
+
+    /// Returns the number of polygon meshes.
+    #[inline(always)]
+    pub fn number_of_polygons(&self) -> usize { self.objects.len() }
+
+    /// Returns the maximum number of the diffuse bounces of the indirect lighting.
+    #[inline(always)]
+    pub fn indirect_bounces(&self) -> usize { self.indirect_bounces }
+
+    /// Sets the maximum number of the diffuse bounces of the indirect lighting. Default is `2`.
+    ///
+    /// The paths are terminated by the ambient term `albedo * ambient_ratio` of the materials,
+    /// as in the standard shaders. In particular, if `bounces == 0`, the indirect lighting is
+    /// not sampled and the images have no noise.
+    /// # Examples
+    /// ```
+    /// use truck_platform::*;
+    /// use truck_rendimpl::*;
+    /// use truck_meshalgo::prelude::Faces;
+    /// let square = |size: f64, z: f64| {
+    ///     let positions = vec![
+    ///         Point3::new(-size, -size, z),
+    ///         Point3::new(size, -size, z),
+    ///         Point3::new(size, size, z),
+    ///         Point3::new(-size, size, z),
+    ///     ];
+    ///     let faces = Faces::from_iter(&[[0, 1, 2, 3]]);
+    ///     PolygonMesh::new(positions, Vec::new(), Vec::new(), faces)
+    /// };
+    /// let state = InstanceState {
+    ///     material: Material {
+    ///         reflectance: 0.0,
+    ///         ambient_ratio: 0.0,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // The light comes from -z, so the front of the square seen by the camera is not lit
+    /// // directly. The wall behind the camera is lit, and reflects the light to the square.
+    /// let mut raytracer = RayTracer::new(SceneDescriptor {
+    ///     lights: vec![Light {
+    ///         position: Point3::new(0.0, 0.0, -1.0),
+    ///         color: Vector3::new(1.0, 1.0, 1.0),
+    ///         light_type: LightType::Uniform,
+    ///     }],
+    ///     ..Default::default()
+    /// });
+    /// raytracer.add_polygon(&square(0.1, -2.0), &state);
+    /// raytracer.add_polygon(&square(10.0, 1.0), &state);
+    /// let center = (8 * 16 + 8) * 4;
+    ///
+    /// raytracer.set_indirect_bounces(0);
+    /// let image = raytracer.render((16, 16), 1).unwrap();
+    /// assert_eq!(&image[center..center + 4], &[0, 0, 0, 255]);
+    ///
+    /// raytracer.set_indirect_bounces(1);
+    /// let image = raytracer.render((16, 16), 4).unwrap();
+    /// assert!(image[center] > 0);
+    /// ```
+    #[inline(always)]
+    pub fn set_indirect_bounces(&mut self, bounces: usize) { self.indirect_bounces = bounces }
+
+    /// Renders the image with `size = (width, height)` and returns the RGBA pixels,
+    /// aligned in the same way as `Rgba8Unorm` textures.
+    ///
+    /// Each pixel is divided into `supersampling * supersampling` sub-pixels for anti-aliasing,
+    /// and a path is traced from each sub-pixel. The random numbers are seeded by the pixels,
+    /// so the same scene is always rendered to the same image.
+    /// Returns an error if the width or the height is zero, or the projection of the camera
+    /// is not invertible.
+    pub fn render(&self, size: (u32, u32), supersampling: u32) -> Result<Vec<u8>, RayTracingError> {
+        let (width, height) = size;
+        if width == 0 || height == 0 {
+            return Err(RayTracingError::EmptyImage(size));
+        }
+        let supersampling = u32::max(supersampling, 1);
+        let as_rat = width as f64 / height as f64;
+        let inverse = self
+            .descriptor
+            .camera
+            .projection(as_rat)
+            .invert()
+            .ok_or(RayTracingError::SingularProjection)?;
+        let background = self.background();
+        let n = supersampling as f64;
+        let weight = 1.0 / (n * n);
+        let mut res = Vec::with_capacity(width as usize * height as usize * 4);
+        for row in 0..height {
+            for col in 0..width {
+                let mut rng = StdRng::seed_from_u64((row as u64) << 32 | col as u64);
+                let mut color = Vector4::zero();
+                for i in 0..supersampling {
+                    for j in 0..supersampling {
+                        let x = (col as f64 + (j as f64 + 0.5) / n) / width as f64;
+                        let y = (row as f64 + (i as f64 + 0.5) / n) / height as f64;
+                        let (x, y) = (2.0 * x - 1.0, 1.0 - 2.0 * y);
+                        let near = inverse.transform_point(Point3::new(x, y, 0.0));
+                        let far = inverse.transform_point(Point3::new(x, y, 1.0));
+                        let direction = (far - near).normalize();
+                        let sample =
+                            self.trace(near, direction, 0, self.indirect_bounces, &mut rng);
+                        color += sample.unwrap_or(background) * weight;
+                    }
+                }
+                let pixel: [u8; 4] = color
+                    .map(|c| (f64::clamp(c, 0.0, 1.0) * 255.0).round() as u8)
+                    .into();
+                res.extend_from_slice(&pixel);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns the color of the nearest object, or `None` if the ray hits nothing.
+    /// `depth` is the number of the reflections so far, and `bounces` is the number of
+    /// the remaining diffuse bounces.
+    fn trace(
+        &self,
+        origin: Point3,
+        direction: Vector3,
+        depth: usize,
+        bounces: usize,
+        rng: &mut StdRng,
+    ) -> Option<Vector4> {
+        let (object, hit) = self
+            .objects
+            .iter()
+            .filter_map(|object| Some((object, object.bvh.ray_cast(origin, direction)?)))
+            .min_by(|(_, hit0), (_, hit1)| {
+                hit0.distance
+                    .partial_cmp(&hit1.distance)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+        let material = &object.material;
+        let position = origin + hit.distance * direction;
+        let camera_dir = -direction;
+        let normal = {
+            let normals = object.mesh.normals();
+            let interpolated = hit
+                .vertices
+                .iter()
+                .zip(&hit.barycentric)
+                .try_fold(Vector3::zero(), |sum, (v, w)| {
+                    Some(sum + normals[v.nor?] * *w)
+                });
+            let normal = match interpolated {
+                Some(normal) if !normal.so_small() => normal.normalize(),
+                _ => hit.face_normal(),
+            };
+            // the back faces are shaded in the same way as the front faces.
+            match normal.dot(camera_dir) < 0.0 {
+                true => -normal,
+                false => normal,
+            }
+        };
+        let origin = position + RAY_OFFSET * normal;
+        let mut color = self
+            .descriptor
+            .lights
+            .iter()
+            .filter(|light| !self.in_shadow(origin, light))
+            .fold(Vector3::zero(), |sum, light| {
+                sum + microfacet_color(position, normal, light, camera_dir, material)
+            });
+        color = color.map(|c| f64::clamp(c, 0.0, 1.0));
+        if depth + 1 < MAX_DEPTH && material.reflectance > 0.0 {
+            let reflect = direction - 2.0 * direction.dot(normal) * normal;
+            if let Some(reflected) = self.trace(origin, reflect, depth + 1, bounces, rng) {
+                let specular = material.albedo.truncate() * material.reflectance;
+                let gloss = 1.0 - material.roughness;
+                let f = fresnel(specular, normal, camera_dir) * gloss;
+                color += reflected.truncate().mul_element_wise(f);
+            }
+        }
+        let albedo = material.albedo.truncate();
+        let color = match bounces {
+            0 => color * (1.0 - material.ambient_ratio) + albedo * material.ambient_ratio,
+            _ => {
+                // The cosine-weighted sampling cancels the cosine term of the Lambertian BRDF.
+                let bounce = cosine_weighted_direction(normal, rng);
+                let incoming = self
+                    .trace(origin, bounce, depth + 1, bounces - 1, rng)
+                    .map_or_else(|| self.background().truncate(), |c| c.truncate());
+                let diffuse = albedo * (1.0 - material.reflectance);
+                color + diffuse.mul_element_wise(incoming)
+            }
+        };
+        Some(color.extend(material.albedo[3]))
+    }
+
+    fn background(&self) -> Vector4 {
+        let background = self.descriptor.background;
+        Vector4::new(background.r, background.g, background.b, background.a)
+    }
+
+    fn in_shadow(&self, origin: Point3, light: &Light) -> bool {
+        let (direction, distance) = match light.light_type {
+            LightType::Point => {
+                let vec = light.position - origin;
+                (vec.normalize(), vec.magnitude())
+            }
+            LightType::Uniform => (light.position.to_vec().normalize(), f64::INFINITY),
+        };
+        self.objects
+            .iter()
+            .any(|object| object.bvh.is_occluded(origin, direction, distance))
+    }
+}
+
+/// Returns a random direction in the hemisphere around `normal` with the density
+/// proportional to the cosine of the angle from `normal`.
+fn cosine_weighted_direction(normal: Vector3, rng: &mut StdRng) -> Vector3 {
+    let (u, v): (f64, f64) = (rng.gen(), rng.gen());
+    let (r, phi) = (u.sqrt(), 2.0 * PI * v);
+    let axis = match normal.x.abs() < 0.9 {
+        true => Vector3::unit_x(),
+        false => Vector3::unit_y(),
+    };
+    let tangent = axis.cross(normal).normalize();
+    let bitangent = normal.cross(tangent);
+    tangent * (r * phi.cos()) + bitangent * (r * phi.sin()) + normal * f64::sqrt(1.0 - u)
+}
+
+// The following functions are ports of "shaders/microfacet-module.wgsl".
+
+fn light_direction(light: &Light, position: Point3) -> Vector3 {
+    match light.light_type {
+        LightType::Point => (light.position - position).normalize(),
+        LightType::Uniform => light.position.to_vec().normalize(),
+    }
+}
+
+fn microfacet_color(
+    position: Point3,
+    normal: Vector3,
+    light: &Light,
+    camera_dir: Vector3,
+    material: &Material,
+) -> Vector3 {
+    let light_dir = light_direction(light, position);
+    let irradiance = light.color * f64::clamp(light_dir.dot(normal), 0.0, 1.0);
+    let albedo = material.albedo.truncate();
+    let diffuse = albedo * (1.0 - material.reflectance);
+    let specular = specular_brdf(material, camera_dir, light_dir, normal);
+    (diffuse + specular).mul_element_wise(irradiance)
+}
+
+fn specular_brdf(
+    material: &Material,
+    camera_dir: Vector3,
+    light_dir: Vector3,
+    normal: Vector3,
+) -> Vector3 {
+    let specular_color = material.albedo.truncate() * material.reflectance;
+    let middle = (camera_dir + light_dir).normalize();
+    let alpha = material.roughness * material.roughness;
+    let distribution = {
+        let dot_nh = normal.dot(middle);
+        let alpha2 = alpha * alpha;
+        let sqrt_denom = 1.0 - dot_nh * dot_nh * (1.0 - alpha2);
+        alpha2 / (sqrt_denom * sqrt_denom)
+    };
+    let decay = {
+        let k = alpha / 2.0;
+        let schlick = |vec: Vector3| {
+            let dot_nv = normal.dot(vec);
+            dot_nv / (dot_nv * (1.0 - k) + k)
+        };
+        schlick(light_dir) * schlick(camera_dir)
+    };
+    let fresnel_color = fresnel(specular_color, middle, camera_dir);
+    let dot_cn = f64::clamp(camera_dir.dot(normal), 0.0, 1.0);
+    let dot_ln = f64::clamp(light_dir.dot(normal), 0.0, 1.0);
+    let denom = 4.0 * dot_cn * dot_ln;
+    match denom < 1.0e-6 {
+        true => Vector3::zero(),
+        false => fresnel_color * (distribution * decay / denom),
+    }
+}
+
+fn fresnel(f0: Vector3, middle: Vector3, camera_dir: Vector3) -> Vector3 {
+    let c = (1.0 - middle.dot(camera_dir)).powi(5);
+    f0 + (Vector3::new(1.0, 1.0, 1.0) - f0) * c
+}
@@ -23,14 +23,22 @@ struct Color {
 [[group(1), binding(1)]]
 var<uniform> color: Color;
 
+struct VertexOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]] color: vec4<f32>;
+};
+
 [[stage(vertex)]]
-fn vs_main([[location(0)]] position: vec3<f32>) -> [[builtin(position)]] vec4<f32> {
+fn vs_main(
+    [[location(0)]] position: vec3<f32>,
+    [[location(1)]] vertex_color: vec4<f32>,
+) -> VertexOutput {
     var res: vec4<f32> = camera.projection * model_matrix.matrix * vec4<f32>(position, 1.0);
     res.z = res.z - 1.0e-4;
-    return res;
+    return VertexOutput(res, vertex_color);
 }
 
 [[stage(fragment)]]
-fn fs_main() -> [[location(0)]] vec4<f32> {
-    return color.color;
+fn fs_main(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    return in.color * color.color;
 }
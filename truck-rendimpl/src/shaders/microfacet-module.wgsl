@@ -12,6 +12,7 @@ struct Material {
     roughness: f32;
     reflectance: f32;
     ambient_ratio: f32;
+    flags: u32;
 };
 
 fn light_direction(light: Light, position: vec3<f32>) -> vec3<f32> {
@@ -0,0 +1,103 @@
+// Generates smooth normals of the vertices without normals.
+// The vertices are packed as [position(3), uv(2), normal(3)]. The vertices at the same
+// position of the original mesh share the adjacent triangles.
+
+[[block]]
+struct Floats {
+    data: [[stride(4)]] array<f32>;
+};
+
+[[block]]
+struct Indices {
+    data: [[stride(4)]] array<u32>;
+};
+
+[[block]]
+struct Counts {
+    faces: u32;
+    vertices: u32;
+    // the number of the invocations in the x dimension
+    stride: u32;
+    padding: u32;
+};
+
+[[group(0), binding(0)]]
+var<storage, read_write> vertices: Floats;
+
+[[group(0), binding(1)]]
+var<storage> indices: Indices;
+
+[[group(0), binding(2)]]
+var<storage, read_write> face_normals: Floats;
+
+// the offsets of the adjacent triangles of the positions
+[[group(0), binding(3)]]
+var<storage> adjacency_offsets: Indices;
+
+[[group(0), binding(4)]]
+var<storage> adjacency: Indices;
+
+[[group(0), binding(5)]]
+var<uniform> counts: Counts;
+
+// the indices of the positions of the vertices
+[[group(0), binding(6)]]
+var<storage> position_ids: Indices;
+
+fn position(idx: u32) -> vec3<f32> {
+    let i = idx * 8u;
+    return vec3<f32>(vertices.data[i], vertices.data[i + 1u], vertices.data[i + 2u]);
+}
+
+// The lengths of face normals are the double of the areas.
+[[stage(compute), workgroup_size(64)]]
+fn face_main([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    let f = id.x + id.y * counts.stride;
+    if (f >= counts.faces) {
+        return;
+    }
+    let p0 = position(indices.data[3u * f]);
+    let p1 = position(indices.data[3u * f + 1u]);
+    let p2 = position(indices.data[3u * f + 2u]);
+    let n = cross(p1 - p0, p2 - p0);
+    face_normals.data[3u * f] = n.x;
+    face_normals.data[3u * f + 1u] = n.y;
+    face_normals.data[3u * f + 2u] = n.z;
+}
+
+[[stage(compute), workgroup_size(64)]]
+fn vertex_main([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    let v = id.x + id.y * counts.stride;
+    if (v >= counts.vertices) {
+        return;
+    }
+    let i = v * 8u;
+    let normal = vec3<f32>(vertices.data[i + 5u], vertices.data[i + 6u], vertices.data[i + 7u]);
+    if (dot(normal, normal) > 0.0) {
+        return;
+    }
+    let p = position_ids.data[v];
+    var n: vec3<f32> = vec3<f32>(0.0, 0.0, 0.0);
+    var k: u32 = adjacency_offsets.data[p];
+    loop {
+        if (k >= adjacency_offsets.data[p + 1u]) {
+            break;
+        }
+        let f = adjacency.data[k];
+        n = n + vec3<f32>(
+            face_normals.data[3u * f],
+            face_normals.data[3u * f + 1u],
+            face_normals.data[3u * f + 2u]
+        );
+        continuing {
+            k = k + 1u;
+        }
+    }
+    let len = length(n);
+    if (len > 0.0) {
+        n = n / len;
+    }
+    vertices.data[i + 5u] = n.x;
+    vertices.data[i + 6u] = n.y;
+    vertices.data[i + 7u] = n.z;
+}
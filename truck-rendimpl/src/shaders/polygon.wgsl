@@ -33,6 +33,8 @@ var<uniform> info: SceneInfo;
 [[block]]
 struct ModelMatrix {
     matrix: mat4x4<f32>;
+    nplanes: u32;
+    clipping_planes: array<vec4<f32>, 8>;
 };
 
 [[group(1), binding(0)]]
@@ -72,11 +74,45 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 }
 
 let e: vec2<f32> = vec2<f32>(1.0, 0.0);
+let DOUBLE_SIDED: u32 = 1u;
+let FLAT_SHADING: u32 = 2u;
+
+fn is_clipped(position: vec3<f32>) -> bool {
+    for (var i: u32 = 0u; i < model_matrix.nplanes; i = i + 1u) {
+        if (dot(model_matrix.clipping_planes[i], vec4<f32>(position, 1.0)) < 0.0) {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn shading_normal(in: VertexOutput, front_facing: bool, camera_dir: vec3<f32>) -> vec3<f32> {
+    // the normal of the face facing the camera if the face is front-facing
+    var face_normal: vec3<f32> = normalize(cross(dpdx(in.position), dpdy(in.position)));
+    if ((dot(face_normal, camera_dir) > 0.0) != front_facing) {
+        face_normal = -face_normal;
+    }
+    let flags = material.material.flags;
+    var normal: vec3<f32> = normalize(in.normal);
+    if ((flags & FLAT_SHADING) != 0u) {
+        normal = face_normal;
+    }
+    if ((flags & DOUBLE_SIDED) != 0u && !front_facing) {
+        normal = -normal;
+    }
+    return normal;
+}
 
 [[stage(fragment)]]
-fn nontex_main(in: VertexInput) -> [[location(0)]] vec4<f32> {
+fn nontex_main(
+    in: VertexOutput,
+    [[builtin(front_facing)]] front_facing: bool,
+) -> [[location(0)]] vec4<f32> {
+    if (is_clipped(in.position)) {
+        discard;
+    }
     let camera_dir = normalize((camera.matrix * e.yyyx).xyz - in.position);
-    let normal = normalize(in.normal);
+    let normal = shading_normal(in, front_facing, camera_dir);
     var pre_color: vec3<f32> = vec3<f32>(0.0);
     for (var i: u32 = 0u; i < info.nlights; i = i + 1u) {
         pre_color = pre_color + microfacet_color(
@@ -94,11 +130,17 @@ fn nontex_main(in: VertexInput) -> [[location(0)]] vec4<f32> {
 }
 
 [[stage(fragment)]]
-fn tex_main(in: VertexInput) -> [[location(0)]] vec4<f32> {
+fn tex_main(
+    in: VertexOutput,
+    [[builtin(front_facing)]] front_facing: bool,
+) -> [[location(0)]] vec4<f32> {
     var mat: Material = material.material;
     mat.albedo = textureSample(r_color, r_sampler, in.uv);
+    if (is_clipped(in.position)) {
+        discard;
+    }
     let camera_dir = normalize((camera.matrix * e.yyyx).xyz - in.position);
-    let normal = normalize(in.normal);
+    let normal = shading_normal(in, front_facing, camera_dir);
     var pre_color: vec3<f32> = vec3<f32>(0.0);
     for (var i: u32 = 0u; i < info.nlights; i = i + 1u) {
         pre_color = pre_color + microfacet_color(
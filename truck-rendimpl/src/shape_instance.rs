@@ -0,0 +1,256 @@
+use crate::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use truck_meshalgo::tessellation::*;
+use truck_topology::*;
+
+/// the ratio of the precision of a refined face to the required one,
+/// for not re-tessellating the face at every small move of the camera
+const REFINEMENT_RATIO: f64 = 0.5;
+
+type MeshedShell = Shell<Point3, PolylineCurve<Point3>, PolygonMesh>;
+
+fn merged_polygon(meshed: &[MeshedShell]) -> PolygonMesh {
+    let mut polygon = PolygonMesh::default();
+    meshed.iter().for_each(|shell| polygon.merge(shell.into_polygon()));
+    polygon
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> ShapeInstance<C, S> {
+    fn new(
+        shells: Vec<Shell<Point3, C, S>>,
+        meshed: Vec<MeshedShell>,
+        handler: &DeviceHandler,
+        shaders: &PolygonShaders,
+        desc: &ShapeInstanceDescriptor,
+    ) -> Self {
+        let faces = shells
+            .iter()
+            .zip(&meshed)
+            .map(|(shell, meshed)| {
+                let mut edge_faces = HashMap::<EdgeID<C>, Vec<usize>>::new();
+                shell.face_iter().enumerate().for_each(|(i, face)| {
+                    face.absolute_boundaries()
+                        .iter()
+                        .flatten()
+                        .for_each(|edge| edge_faces.entry(edge.id()).or_default().push(i))
+                });
+                shell
+                    .face_iter()
+                    .zip(meshed.face_iter())
+                    .enumerate()
+                    .map(|(i, (face, meshed_face))| {
+                        let mut neighbors: Vec<usize> = face
+                            .absolute_boundaries()
+                            .iter()
+                            .flatten()
+                            .flat_map(|edge| edge_faces[&edge.id()].iter().copied())
+                            .filter(|j| *j != i)
+                            .collect();
+                        neighbors.sort_unstable();
+                        neighbors.dedup();
+                        AdaptiveFace {
+                            precision: desc.mesh_precision,
+                            neighbors,
+                            bounding_box: meshed_face.read_surface().bounding_box(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let instance = merged_polygon(&meshed).into_instance(
+            handler,
+            shaders,
+            &PolygonInstanceDescriptor {
+                instance_state: desc.instance_state.clone(),
+                generate_normals: false,
+            },
+        );
+        ShapeInstance {
+            shells,
+            meshed,
+            faces,
+            adaptive: desc.adaptive_tessellation,
+            instance,
+            handler: handler.clone(),
+        }
+    }
+
+    /// Returns the polygon instance of the current tessellation.
+    #[inline(always)]
+    pub fn polygon_instance(&self) -> &PolygonInstance { &self.instance }
+    /// Returns a reference to the instance state.
+    #[inline(always)]
+    pub fn instance_state(&self) -> &InstanceState { self.instance.instance_state() }
+    /// Returns the mutable reference to the instance state.
+    #[inline(always)]
+    pub fn instance_state_mut(&mut self) -> &mut InstanceState {
+        self.instance.instance_state_mut()
+    }
+    /// Returns the configure of the adaptive tessellation.
+    #[inline(always)]
+    pub fn adaptive_tessellation(&self) -> &AdaptiveTessellation { &self.adaptive }
+    /// Returns the mutable reference to the configure of the adaptive tessellation.
+    #[inline(always)]
+    pub fn adaptive_tessellation_mut(&mut self) -> &mut AdaptiveTessellation { &mut self.adaptive }
+
+    /// Returns the precision by which the chordal errors of the polygon in `bounding_box`
+    /// are within `self.adaptive.pixels` on the screen.
+    fn required_precision(
+        &self,
+        bounding_box: &BoundingBox<Point3>,
+        camera: &Camera,
+        screen_height: u32,
+    ) -> f64 {
+        if bounding_box.is_empty() {
+            return f64::INFINITY;
+        }
+        let matrix = self.instance.instance_state().matrix;
+        let (min, max) = (bounding_box.min(), bounding_box.max());
+        // the pixel size is affine in the depth, so it is the smallest at a corner of the box
+        let pixel_size = (0..8)
+            .map(|i| {
+                let corner = Point3::new(
+                    if i & 1 == 0 { min[0] } else { max[0] },
+                    if i & 2 == 0 { min[1] } else { max[1] },
+                    if i & 4 == 0 { min[2] } else { max[2] },
+                );
+                camera.pixel_size(matrix.transform_point(corner), screen_height)
+            })
+            .fold(f64::INFINITY, f64::min);
+        // the precision is measured in the local coordinates of the shape
+        let scale = (0..3)
+            .map(|i| matrix[i].truncate().magnitude())
+            .fold(0.0, f64::max);
+        f64::max(
+            pixel_size * self.adaptive.pixels / scale,
+            self.adaptive.min_precision,
+        )
+    }
+
+    /// Re-tessellates the faces whose chordal errors on the screen of `camera` exceed
+    /// [`AdaptiveTessellation::pixels`], and returns the number of the re-tessellated faces.
+    ///
+    /// The faces with the coarsest tessellation relative to the required precision are
+    /// re-tessellated first, at most [`AdaptiveTessellation::faces_per_update`] faces.
+    /// The neighbors of a re-tessellated face are also re-tessellated for keeping the mesh
+    /// watertight. If the returned number is positive, the vertex buffer has to be
+    /// updated by [`Scene::update_vertex_buffer`].
+    /// Call this method at each frame while the camera moves.
+    ///
+    /// A face whose re-tessellation has failed is never re-tessellated again.
+    pub fn update(&mut self, camera: &Camera, screen_height: u32) -> usize {
+        let mut candidates = Vec::new();
+        for (k, faces) in self.faces.iter().enumerate() {
+            for (i, face) in faces.iter().enumerate() {
+                let required = self.required_precision(&face.bounding_box, camera, screen_height);
+                if face.precision > required {
+                    candidates.push((k, i, required, face.precision / required));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(Ordering::Equal));
+        candidates.truncate(self.adaptive.faces_per_update);
+        let mut count = 0;
+        for (k, i, required, _) in candidates {
+            let faces = &mut self.faces[k];
+            // the face may have been refined as a neighbor of another face
+            if faces[i].precision <= required {
+                continue;
+            }
+            let tol = faces[i]
+                .neighbors
+                .iter()
+                .map(|j| faces[*j].precision)
+                .fold(REFINEMENT_RATIO * required, f64::min);
+            let tol = f64::max(tol, self.adaptive.min_precision);
+            let face = &self.shells[k][i];
+            let edges: Vec<EdgeID<C>> = face
+                .absolute_boundaries()
+                .iter()
+                .flatten()
+                .map(|edge| edge.id())
+                .collect();
+            let meshed = &mut self.meshed[k];
+            match self.shells[k].retriangulation(meshed, &[face.id()], &edges, tol) {
+                Some(_) => {
+                    faces[i].precision = tol;
+                    let neighbors = faces[i].neighbors.clone();
+                    neighbors.into_iter().for_each(|j| faces[j].precision = tol);
+                    count += 1;
+                }
+                None => faces[i].precision = f64::NAN,
+            }
+        }
+        if count > 0 {
+            let mut instance = merged_polygon(&self.meshed).into_instance(
+                &self.handler,
+                &self.instance.shaders,
+                &PolygonInstanceDescriptor {
+                    instance_state: self.instance.instance_state().clone(),
+                    generate_normals: false,
+                },
+            );
+            self.instance.swap_vertex(&mut instance);
+        }
+        count
+    }
+}
+
+impl<C, S> Instance for ShapeInstance<C, S> {
+    type Shaders = PolygonShaders;
+    fn standard_shaders(creator: &InstanceCreator) -> PolygonShaders {
+        creator.polygon_shaders.clone()
+    }
+}
+
+impl<C, S> TryIntoInstance<ShapeInstance<C, S>> for Shell<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    type Descriptor = ShapeInstanceDescriptor;
+    /// Creates `ShapeInstance` from `Shell`. Returns `None` if the meshing has failed.
+    fn try_into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &PolygonShaders,
+        desc: &ShapeInstanceDescriptor,
+    ) -> Option<ShapeInstance<C, S>> {
+        let progress = desc.meshing_progress.as_ref();
+        let meshed = self.triangulation_with_progress(desc.mesh_precision, progress)?;
+        let shells = vec![self.clone()];
+        Some(ShapeInstance::new(shells, vec![meshed], handler, shaders, desc))
+    }
+}
+
+impl<C, S> TryIntoInstance<ShapeInstance<C, S>> for Solid<Point3, C, S>
+where
+    C: PolylineableCurve,
+    S: MeshableSurface,
+{
+    type Descriptor = ShapeInstanceDescriptor;
+    /// Creates `ShapeInstance` from `Solid`. Returns `None` if the meshing has failed.
+    fn try_into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &PolygonShaders,
+        desc: &ShapeInstanceDescriptor,
+    ) -> Option<ShapeInstance<C, S>> {
+        let progress = desc.meshing_progress.as_ref();
+        let meshed = self.triangulation_with_progress(desc.mesh_precision, progress)?;
+        let shells = self.boundaries().clone();
+        let meshed = meshed.boundaries().clone();
+        Some(ShapeInstance::new(shells, meshed, handler, shaders, desc))
+    }
+}
+
+impl<C, S> Rendered for ShapeInstance<C, S> {
+    derive_render_id!(instance);
+    derive_vertex_buffer!(instance);
+    derive_bind_group_layout!(instance);
+    derive_bind_group!(instance);
+    derive_pipeline!(instance);
+    #[inline(always)]
+    fn bounding_box(&self) -> Option<BoundingBox<Point3>> { Some(self.instance.bounding_box()) }
+}
@@ -2,12 +2,72 @@ use crate::*;
 use truck_meshalgo::tessellation::*;
 use truck_topology::*;
 
+impl MeshingProgress {
+    /// Creates the shared progress from [`Progress`], e.g. a closure or
+    /// [`CancellationToken`].
+    /// # Examples
+    /// ```
+    /// use truck_rendimpl::*;
+    /// use truck_rendimpl::progress::{CancellationToken, Progress};
+    ///
+    /// let mut progress = MeshingProgress::new(|i, n| {
+    ///     println!("meshing... {}/{}", i, n);
+    ///     true
+    /// });
+    /// assert!(progress.report(1, 6));
+    ///
+    /// let token = CancellationToken::new();
+    /// let mut progress = MeshingProgress::new(token.clone());
+    /// token.cancel();
+    /// assert!(!progress.report(1, 6));
+    /// ```
+    ///
+    /// [`Progress`]: ./progress/trait.Progress.html
+    /// [`CancellationToken`]: ./progress/struct.CancellationToken.html
+    #[inline(always)]
+    pub fn new<P: Progress + Send + 'static>(progress: P) -> Self {
+        MeshingProgress(Arc::new(Mutex::new(progress)))
+    }
+}
+
+impl Progress for &MeshingProgress {
+    #[inline(always)]
+    fn report(&mut self, done: usize, total: usize) -> bool {
+        self.0.lock().unwrap().report(done, total)
+    }
+}
+
+impl Progress for MeshingProgress {
+    #[inline(always)]
+    fn report(&mut self, done: usize, total: usize) -> bool { (&*self).report(done, total) }
+}
+
+impl std::fmt::Debug for MeshingProgress {
+    #[inline(always)]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("MeshingProgress")
+    }
+}
+
 impl Default for ShapeInstanceDescriptor {
     #[inline(always)]
     fn default() -> Self {
         ShapeInstanceDescriptor {
             instance_state: Default::default(),
             mesh_precision: 0.005,
+            meshing_progress: None,
+            adaptive_tessellation: Default::default(),
+        }
+    }
+}
+
+impl Default for AdaptiveTessellation {
+    #[inline(always)]
+    fn default() -> Self {
+        AdaptiveTessellation {
+            pixels: 0.5,
+            min_precision: 1.0e-4,
+            faces_per_update: 8,
         }
     }
 }
@@ -28,12 +88,15 @@ impl<Shape: MeshableShape> TryIntoInstance<PolygonInstance> for Shape {
         shaders: &PolygonShaders,
         desc: &ShapeInstanceDescriptor,
     ) -> Option<PolygonInstance> {
-        let polygon = self.triangulation(desc.mesh_precision)?.into_polygon();
+        let polygon = self
+            .triangulation_with_progress(desc.mesh_precision, desc.meshing_progress.as_ref())?
+            .into_polygon();
         Some(polygon.into_instance(
             handler,
             shaders,
             &PolygonInstanceDescriptor {
                 instance_state: desc.instance_state.clone(),
+                generate_normals: false,
             },
         ))
     }
@@ -101,20 +164,19 @@ where
         desc: &ShapeWireFrameDescriptor,
     ) -> WireFrameInstance {
         let mut lengths = Vec::new();
-        let points: Vec<[f32; 3]> = self
+        let points: Vec<Point3> = self
             .face_iter()
             .flat_map(|face| face.boundary_iters())
             .flatten()
             .flat_map(|edge| {
                 let curve = edge.oriented_curve();
-                let division =
-                    curve.parameter_division(curve.parameter_range(), desc.polyline_precision);
+                let division = curve_division(&curve, desc);
                 lengths.push(division.len() as u32);
-                division
-                    .into_iter()
-                    .map(move |t| curve.subs(t).cast().unwrap().into())
+                division.into_iter().map(move |t| curve.subs(t))
             })
             .collect();
+        let bounding_box = points.iter().collect();
+        let points: Vec<WireVertex> = points.iter().map(|p| WireVertex::white(*p)).collect();
         let mut strips = Vec::<u32>::new();
         let mut counter = 0_u32;
         for len in lengths {
@@ -129,6 +191,7 @@ where
         WireFrameInstance {
             vertices: Arc::new(vertices),
             strips: Arc::new(strips),
+            bounding_box,
             state: desc.wireframe_state.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
@@ -148,7 +211,7 @@ where
         desc: &ShapeWireFrameDescriptor,
     ) -> WireFrameInstance {
         let mut lengths = Vec::new();
-        let points: Vec<[f32; 3]> = self
+        let points: Vec<Point3> = self
             .boundaries()
             .iter()
             .flatten()
@@ -156,14 +219,13 @@ where
             .flatten()
             .flat_map(|edge| {
                 let curve = edge.oriented_curve();
-                let division =
-                    curve.parameter_division(curve.parameter_range(), desc.polyline_precision);
+                let division = curve_division(&curve, desc);
                 lengths.push(division.len() as u32);
-                division
-                    .into_iter()
-                    .map(move |t| curve.subs(t).cast().unwrap().into())
+                division.into_iter().map(move |t| curve.subs(t))
             })
             .collect();
+        let bounding_box = points.iter().collect();
+        let points: Vec<WireVertex> = points.iter().map(|p| WireVertex::white(*p)).collect();
         let mut strips = Vec::<u32>::new();
         let mut counter = 0_u32;
         for len in lengths {
@@ -178,9 +240,54 @@ where
         WireFrameInstance {
             vertices: Arc::new(vertices),
             strips: Arc::new(strips),
+            bounding_box,
             state: desc.wireframe_state.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
         }
     }
 }
+
+/// the number of the sample points for estimating the depth of edges
+const DEPTH_SAMPLES: usize = 8;
+
+fn curve_division<C: PolylineableCurve>(curve: &C, desc: &ShapeWireFrameDescriptor) -> Vec<f64> {
+    let range = curve.parameter_range();
+    let precision = desc
+        .screen_space_precision
+        .as_ref()
+        .and_then(|screen| screen_precision(curve, range, &desc.wireframe_state.matrix, screen))
+        .unwrap_or(desc.polyline_precision);
+    curve.parameter_division(range, precision)
+}
+
+/// Returns the precision giving `screen.pixels` at the nearest point of the curve,
+/// or `None` if the whole curve is behind the camera or the matrix is degenerate.
+fn screen_precision<C: PolylineableCurve>(
+    curve: &C,
+    range: (f64, f64),
+    matrix: &Matrix4,
+    screen: &ScreenSpacePrecision,
+) -> Option<f64> {
+    // the scale from the local coordinates of the instance to the world
+    let scale = (0..3)
+        .map(|i| matrix[i].truncate().magnitude())
+        .fold(0.0, f64::max);
+    if scale == 0.0 {
+        return None;
+    }
+    // the points behind the camera have the pixel size 0 and are not drawn
+    let pixel_size = (0..=DEPTH_SAMPLES)
+        .map(|i| {
+            let t = range.0 + (range.1 - range.0) * i as f64 / DEPTH_SAMPLES as f64;
+            let pt = matrix.transform_point(curve.subs(t));
+            screen.camera.pixel_size(pt, screen.screen_height)
+        })
+        .filter(|size| *size > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    if pixel_size.is_finite() {
+        Some(f64::max(pixel_size * screen.pixels / scale, screen.min_precision))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,147 @@
+use crate::*;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use truck_meshalgo::analyzers::MeshBVH;
+
+/// the relative margin of the occlusion tests, for ignoring the faces through the snap points
+const OCCLUSION_MARGIN: f64 = 1.0e-6;
+
+impl SnapObject {
+    fn new(mesh: &PolygonMesh, matrix: Matrix4) -> Self {
+        let mut mesh = mesh.clone();
+        mesh.positions_mut()
+            .iter_mut()
+            .for_each(|p| *p = matrix.transform_point(*p));
+        let positions = mesh.positions();
+        let mut edges = HashSet::new();
+        let mut face_centers = Vec::new();
+        mesh.face_iter().for_each(|face| {
+            let len = face.len();
+            (0..len).for_each(|i| {
+                let (v0, v1) = (face[i].pos, face[(i + 1) % len].pos);
+                if v0 != v1 {
+                    edges.insert([usize::min(v0, v1), usize::max(v0, v1)]);
+                }
+            });
+            let sum = face
+                .iter()
+                .fold(Vector3::zero(), |sum, v| sum + positions[v.pos].to_vec());
+            face_centers.push(Point3::from_vec(sum / len as f64));
+        });
+        let mut edges: Vec<[usize; 2]> = edges.into_iter().collect();
+        edges.sort();
+        SnapObject {
+            edge_midpoints: edges
+                .into_iter()
+                .map(|[v0, v1]| positions[v0].midpoint(positions[v1]))
+                .collect(),
+            face_centers,
+            bvh: MeshBVH::new(&mesh),
+            vertices: mesh.positions().clone(),
+        }
+    }
+
+    fn candidates(&self) -> impl Iterator<Item = (Point3, SnapKind)> + '_ {
+        let vertices = self.vertices.iter().map(|p| (*p, SnapKind::Vertex));
+        let midpoints = self.edge_midpoints.iter().map(|p| (*p, SnapKind::EdgeMidpoint));
+        let centers = self.face_centers.iter().map(|p| (*p, SnapKind::FaceCenter));
+        vertices.chain(midpoints).chain(centers)
+    }
+}
+
+impl Snapper {
+    /// Creates an empty snapper.
+    #[inline(always)]
+    pub fn new() -> Self { Snapper::default() }
+
+    /// Adds a polygon mesh placed by `state`, and returns the index of the mesh.
+    ///
+    /// Only the instance matrix of `state` is used.
+    #[inline(always)]
+    pub fn add_polygon(&mut self, mesh: &PolygonMesh, state: &InstanceState) -> usize {
+        self.objects.push(SnapObject::new(mesh, state.matrix));
+        self.objects.len() - 1
+    }
+
+    /// Removes all polygon meshes.
+    #[inline(always)]
+    pub fn clear_polygons(&mut self) { self.objects.clear() }
+
+    /// Returns the number of polygon meshes.
+    #[inline(always)]
+    pub fn number_of_polygons(&self) -> usize { self.objects.len() }
+
+    /// Returns the visible snap point within `radius` pixels of `cursor`, or `None` if there is
+    /// no such point.
+    ///
+    /// # Arguments
+    /// * `camera`: the camera of the scene
+    /// * `size`: the size of the screen `(width, height)` in pixels
+    /// * `cursor`: the position of the cursor in pixels from the upper left corner
+    /// * `radius`: the radius of the snapping in pixels
+    ///
+    /// The vertices take priority over the edge midpoints, and the edge midpoints over
+    /// the face centers. The points of the same kind are compared by the distances from
+    /// the cursor. The points hidden by the faces of the meshes are not snapped.
+    pub fn snap(
+        &self,
+        camera: &Camera,
+        size: (u32, u32),
+        cursor: (f64, f64),
+        radius: f64,
+    ) -> Option<SnapPoint> {
+        let (width, height) = (size.0 as f64, size.1 as f64);
+        let projection = camera.projection(width / height);
+        let eye = camera.eye_direction();
+        self.objects
+            .iter()
+            .enumerate()
+            .flat_map(|(i, object)| object.candidates().map(move |(p, kind)| (i, p, kind)))
+            .filter_map(|(mesh_index, position, kind)| {
+                let depth = (position - camera.position()).dot(eye);
+                if depth <= 0.0 {
+                    return None;
+                }
+                let ndc = projection.transform_point(position);
+                let x = (ndc[0] + 1.0) / 2.0 * width;
+                let y = (1.0 - ndc[1]) / 2.0 * height;
+                let pixel_distance = f64::hypot(x - cursor.0, y - cursor.1);
+                match pixel_distance <= radius {
+                    true => Some(SnapPoint {
+                        position,
+                        kind,
+                        mesh_index,
+                        pixel_distance,
+                    }),
+                    false => None,
+                }
+            })
+            .filter(|point| self.is_visible(camera, point.position))
+            .min_by(|point0, point1| {
+                point0.kind.cmp(&point1.kind).then(
+                    point0
+                        .pixel_distance
+                        .partial_cmp(&point1.pixel_distance)
+                        .unwrap_or(Ordering::Equal),
+                )
+            })
+    }
+
+    fn is_visible(&self, camera: &Camera, point: Point3) -> bool {
+        let eye = camera.eye_direction();
+        let (origin, direction) = match camera.projection_type() {
+            ProjectionType::Perspective => {
+                let origin = camera.position();
+                (origin, (point - origin).normalize())
+            }
+            ProjectionType::Parallel => {
+                let depth = (point - camera.position()).dot(eye);
+                (point - eye * depth, eye)
+            }
+        };
+        let distance = (point - origin).magnitude() * (1.0 - OCCLUSION_MARGIN);
+        self.objects
+            .iter()
+            .all(|object| !object.bvh.is_occluded(origin, direction, distance))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use crate::polygon_instance::transformed_bounding_box;
 
 impl Default for WireFrameState {
     #[inline(always)]
@@ -6,6 +7,7 @@ impl Default for WireFrameState {
         WireFrameState {
             matrix: Matrix4::identity(),
             color: Vector4::new(1.0, 1.0, 1.0, 1.0),
+            depth: Default::default(),
         }
     }
 }
@@ -16,10 +18,38 @@ impl Default for ShapeWireFrameDescriptor {
         ShapeWireFrameDescriptor {
             wireframe_state: WireFrameState::default(),
             polyline_precision: 0.005,
+            screen_space_precision: None,
         }
     }
 }
 
+impl ColoredSegment {
+    /// Creates the segment from `p0` to `p1` painted by `color`.
+    #[inline(always)]
+    pub fn new(p0: Point3, p1: Point3, color: Vector4) -> ColoredSegment {
+        ColoredSegment {
+            points: [p0, p1],
+            colors: [color, color],
+        }
+    }
+}
+
+impl WireVertex {
+    #[inline(always)]
+    pub(crate) fn new(position: Point3, color: Vector4) -> WireVertex {
+        WireVertex {
+            position: position.cast().unwrap().into(),
+            color: color.cast().unwrap().into(),
+        }
+    }
+
+    /// the white vertex
+    #[inline(always)]
+    pub(crate) fn white(position: Point3) -> WireVertex {
+        WireVertex::new(position, Vector4::new(1.0, 1.0, 1.0, 1.0))
+    }
+}
+
 impl WireFrameInstance {
     /// Clone the instance as another drawn element.
     #[inline(always)]
@@ -27,6 +57,7 @@ impl WireFrameInstance {
         Self {
             vertices: Arc::clone(&self.vertices),
             strips: Arc::clone(&self.strips),
+            bounding_box: self.bounding_box.clone(),
             state: self.state.clone(),
             shaders: self.shaders.clone(),
             id: RenderID::gen(),
@@ -42,6 +73,11 @@ impl WireFrameInstance {
     pub fn instance_state_mut(&mut self) -> &mut WireFrameState {
         &mut self.state
     }
+    /// Returns the bounding box in the world coordinates, transformed by the instance matrix.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<Point3> {
+        transformed_bounding_box(&self.bounding_box, &self.state.matrix)
+    }
 }
 
 impl Instance for WireFrameInstance {
@@ -53,6 +89,10 @@ impl Instance for WireFrameInstance {
 
 impl Rendered for WireFrameInstance {
     impl_render_id!(id);
+
+    #[inline(always)]
+    fn bounding_box(&self) -> Option<BoundingBox<Point3>> { Some(self.bounding_box()) }
+
     fn vertex_buffer(&self, _: &DeviceHandler) -> (Arc<BufferHandler>, Option<Arc<BufferHandler>>) {
         (self.vertices.clone(), Some(self.strips.clone()))
     }
@@ -111,13 +151,20 @@ impl Rendered for WireFrameInstance {
                 module: &self.shaders.vertex_module,
                 entry_point: self.shaders.vertex_entry,
                 buffers: &[VertexBufferLayout {
-                    array_stride: std::mem::size_of::<[f32; 3]>() as BufferAddress,
+                    array_stride: size_of::<WireVertex>() as BufferAddress,
                     step_mode: VertexStepMode::Vertex,
-                    attributes: &[VertexAttribute {
-                        format: VertexFormat::Float32x3,
-                        offset: 0,
-                        shader_location: 0,
-                    }],
+                    attributes: &[
+                        VertexAttribute {
+                            format: VertexFormat::Float32x3,
+                            offset: 0,
+                            shader_location: 0,
+                        },
+                        VertexAttribute {
+                            format: VertexFormat::Float32x4,
+                            offset: 3 * 4,
+                            shader_location: 1,
+                        },
+                    ],
                 }],
             },
             fragment: Some(FragmentState {
@@ -133,13 +180,7 @@ impl Rendered for WireFrameInstance {
                 topology: PrimitiveTopology::LineList,
                 ..Default::default()
             },
-            depth_stencil: Some(DepthStencilState {
-                format: TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: Default::default(),
-                bias: Default::default(),
-            }),
+            depth_stencil: Some(self.state.depth.depth_stencil_state()),
             multisample: MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -160,17 +201,133 @@ impl IntoInstance<WireFrameInstance> for Vec<(Point3, Point3)> {
         desc: &WireFrameState,
     ) -> WireFrameInstance {
         let device = handler.device();
-        let positions: Vec<[f32; 3]> = self
+        let positions: Vec<WireVertex> = self
             .iter()
             .flat_map(|p| vec![p.0, p.1])
-            .map(|p| p.cast().unwrap().into())
+            .map(WireVertex::white)
             .collect();
+        let bounding_box = self.iter().flat_map(|p| vec![p.0, p.1]).collect();
         let strips: Vec<u32> = (0..2 * self.len()).map(|i| i as u32).collect();
         let vb = BufferHandler::from_slice(&positions, device, BufferUsages::VERTEX);
         let ib = BufferHandler::from_slice(&strips, device, BufferUsages::INDEX);
         WireFrameInstance {
             vertices: Arc::new(vb),
             strips: Arc::new(ib),
+            bounding_box,
+            state: desc.clone(),
+            shaders: shaders.clone(),
+            id: RenderID::gen(),
+        }
+    }
+}
+
+impl IntoInstance<WireFrameInstance> for Vec<PolylineCurve<Point3>> {
+    type Descriptor = WireFrameState;
+    fn into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &WireShaders,
+        desc: &WireFrameState,
+    ) -> WireFrameInstance {
+        let device = handler.device();
+        let positions: Vec<WireVertex> = self
+            .iter()
+            .flat_map(|curve| curve.iter())
+            .map(|p| WireVertex::white(*p))
+            .collect();
+        let bounding_box = self.iter().flat_map(|curve| curve.iter()).collect();
+        let mut strips = Vec::<u32>::new();
+        let mut counter = 0_u32;
+        for curve in self {
+            let len = curve.len() as u32;
+            for i in 1..len {
+                strips.push(counter + i - 1);
+                strips.push(counter + i);
+            }
+            counter += len;
+        }
+        let vb = BufferHandler::from_slice(&positions, device, BufferUsages::VERTEX);
+        let ib = BufferHandler::from_slice(&strips, device, BufferUsages::INDEX);
+        WireFrameInstance {
+            vertices: Arc::new(vb),
+            strips: Arc::new(ib),
+            bounding_box,
+            state: desc.clone(),
+            shaders: shaders.clone(),
+            id: RenderID::gen(),
+        }
+    }
+}
+
+impl IntoInstance<WireFrameInstance> for Vec<ColoredSegment> {
+    type Descriptor = WireFrameState;
+    fn into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &WireShaders,
+        desc: &WireFrameState,
+    ) -> WireFrameInstance {
+        let device = handler.device();
+        let vertices: Vec<WireVertex> = self
+            .iter()
+            .flat_map(|segment| {
+                let [p0, p1] = segment.points;
+                let [c0, c1] = segment.colors;
+                vec![WireVertex::new(p0, c0), WireVertex::new(p1, c1)]
+            })
+            .collect();
+        let strips: Vec<u32> = (0..2 * self.len()).map(|i| i as u32).collect();
+        let vb = BufferHandler::from_slice(&vertices, device, BufferUsages::VERTEX);
+        let ib = BufferHandler::from_slice(&strips, device, BufferUsages::INDEX);
+        WireFrameInstance {
+            vertices: Arc::new(vb),
+            strips: Arc::new(ib),
+            bounding_box: self.iter().flat_map(|segment| &segment.points).collect(),
+            state: desc.clone(),
+            shaders: shaders.clone(),
+            id: RenderID::gen(),
+        }
+    }
+}
+
+impl IntoInstance<WireFrameInstance> for PointCloud {
+    type Descriptor = WireFrameState;
+    /// Each point is drawn as the cross of three segments parallel to the axes, whose lengths
+    /// are 1% of the diagonal of the bounding box, painted by the color of the point.
+    fn into_instance(
+        &self,
+        handler: &DeviceHandler,
+        shaders: &WireShaders,
+        desc: &WireFrameState,
+    ) -> WireFrameInstance {
+        let device = handler.device();
+        let bounding_box = self.bounding_box();
+        let size = match bounding_box.is_empty() {
+            true => 0.0,
+            false => bounding_box.diagonal().magnitude() * 0.005,
+        };
+        let size = if size > 0.0 { size } else { 0.005 };
+        let white = Vector4::new(1.0, 1.0, 1.0, 1.0);
+        let vertices: Vec<WireVertex> = self
+            .positions()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, p)| {
+                let color = self.colors().get(i).copied().unwrap_or(white);
+                let axes = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()];
+                axes.iter()
+                    .flat_map(|axis| vec![*p - *axis * size, *p + *axis * size])
+                    .map(move |q| WireVertex::new(q, color))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let strips: Vec<u32> = (0..vertices.len()).map(|i| i as u32).collect();
+        let vb = BufferHandler::from_slice(&vertices, device, BufferUsages::VERTEX);
+        let ib = BufferHandler::from_slice(&strips, device, BufferUsages::INDEX);
+        WireFrameInstance {
+            vertices: Arc::new(vb),
+            strips: Arc::new(ib),
+            bounding_box,
             state: desc.clone(),
             shaders: shaders.clone(),
             id: RenderID::gen(),
@@ -0,0 +1,73 @@
+mod common;
+use std::sync::{Arc, Mutex};
+use truck_meshalgo::prelude::Faces;
+use truck_platform::*;
+use truck_rendimpl::*;
+use wgpu::*;
+
+// the octahedron whose corners have the uv coordinates of the faces, so that the vertices
+// at the same position are divided into the several vertices of GPU.
+fn octahedron() -> PolygonMesh {
+    let positions = vec![
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(-1.0, 0.0, 0.0),
+        Point3::new(0.0, -1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(0.0, 0.0, -1.0),
+    ];
+    let uv_coords = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(1.0, 0.0),
+        Vector2::new(0.0, 1.0),
+    ];
+    let faces = Faces::from_iter((0..4).flat_map(|i| {
+        let (a, b) = (i, (i + 1) % 4);
+        vec![
+            [(a, Some(0), None), (b, Some(1), None), (4, Some(2), None)],
+            [(b, Some(0), None), (a, Some(1), None), (5, Some(2), None)],
+        ]
+    }));
+    PolygonMesh::new(positions, uv_coords, Vec::new(), faces)
+}
+
+fn exec_normal_generation_test(backend: Backends, _: &str) {
+    let instance = wgpu::Instance::new(backend);
+    let (device, queue) = common::init_device(&instance);
+    let config = Arc::new(Mutex::new(common::swap_chain_descriptor((256, 256))));
+    let handler = DeviceHandler::new(device, queue, config);
+    let scene = Scene::new(handler, &Default::default());
+    let creator = scene.instance_creator();
+    let instance: PolygonInstance = creator.create_instance(
+        &octahedron(),
+        &PolygonInstanceDescriptor {
+            generate_normals: true,
+            ..Default::default()
+        },
+    );
+    let (vb, _) = instance.vertex_buffer(scene.device_handler());
+
+    let device = scene.device();
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: None,
+        mapped_at_creation: false,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        size: vb.size(),
+    });
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(vb.buffer(), 0, &buffer, 0, vb.size());
+    scene.queue().submit(Some(encoder.finish()));
+    let bytes = common::read_buffer(device, &buffer);
+    let floats: &[f32] = bytemuck::cast_slice(&bytes);
+
+    // [position(3), uv(2), normal(3)]
+    assert_eq!(floats.len(), 24 * 8);
+    for vertex in floats.chunks(8) {
+        let position = Vector3::new(vertex[0].into(), vertex[1].into(), vertex[2].into());
+        let normal = Vector3::new(vertex[5].into(), vertex[6].into(), vertex[7].into());
+        assert!((position - normal).magnitude() < 1.0e-5, "{:?}", vertex);
+    }
+}
+
+#[test]
+fn normal_generation_test() { common::os_alt_exec_test(exec_normal_generation_test) }
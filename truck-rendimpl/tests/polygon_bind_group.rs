@@ -78,10 +78,15 @@ fn nontex_inst_desc() -> PolygonInstanceDescriptor {
                 reflectance: 0.29613,
                 ambient_ratio: 0.92,
                 alpha_blend: false,
+                double_sided: false,
+                flat_shading: false,
             },
             texture: None,
             backface_culling: true,
+            clipping_planes: Vec::new(),
+            depth: Default::default(),
         },
+        generate_normals: false,
     }
 }
 
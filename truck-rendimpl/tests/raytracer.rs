@@ -0,0 +1,62 @@
+mod common;
+use std::sync::{Arc, Mutex};
+use truck_meshalgo::prelude::obj;
+use truck_platform::*;
+use truck_rendimpl::*;
+use wgpu::*;
+
+const PICTURE_SIZE: (u32, u32) = (64, 48);
+
+fn exec_raytracer_from_scene_test(backend: Backends, _: &str) {
+    let instance = wgpu::Instance::new(backend);
+    let (device, queue) = common::init_device(&instance);
+    let config = Arc::new(Mutex::new(common::swap_chain_descriptor(PICTURE_SIZE)));
+    let handler = DeviceHandler::new(device, queue, config);
+    let camera = Camera::perspective_camera(
+        Matrix4::look_at_rh(
+            Point3::new(-1.0, 2.5, 2.0),
+            Point3::new(0.25, 0.25, 0.25),
+            Vector3::unit_y(),
+        )
+        .invert()
+        .unwrap(),
+        Rad(std::f64::consts::PI / 4.0),
+        0.1,
+        100.0,
+    );
+    let mut scene = Scene::new(
+        handler,
+        &SceneDescriptor {
+            camera,
+            ..Default::default()
+        },
+    );
+    let mesh = obj::read(include_bytes!("cube.obj").as_ref()).unwrap();
+    let state = InstanceState {
+        matrix: Matrix4::from_translation(Vector3::new(0.1, 0.0, -0.2)),
+        ..Default::default()
+    };
+    let cube: PolygonInstance = scene.instance_creator().create_instance(
+        &mesh,
+        &PolygonInstanceDescriptor {
+            instance_state: state.clone(),
+            ..Default::default()
+        },
+    );
+    scene.add_object(&cube);
+
+    let mut raytracer = RayTracer::from_scene(&scene).unwrap();
+    assert_eq!(raytracer.number_of_polygons(), 1);
+    raytracer.set_indirect_bounces(0);
+    let image0 = raytracer.render(PICTURE_SIZE, 1).unwrap();
+
+    let mut raytracer = RayTracer::new(scene.descriptor().clone());
+    raytracer.add_polygon(&mesh, &state);
+    raytracer.set_indirect_bounces(0);
+    let image1 = raytracer.render(PICTURE_SIZE, 1).unwrap();
+    assert!(image0.iter().any(|c| *c != image0[0]));
+    assert!(common::count_difference(&image0, &image1) < 10);
+}
+
+#[test]
+fn raytracer_from_scene_test() { common::os_alt_exec_test(exec_raytracer_from_scene_test) }
@@ -76,9 +76,12 @@ fn nontex_polygon(scene: &mut Scene, creator: &InstanceCreator) -> Vec<u8> {
                     reflectance: 0.25,
                     ambient_ratio: 0.02,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 },
                 ..Default::default()
             },
+            generate_normals: false,
         },
     );
     common::render_one(scene, &texture, &cube);
@@ -98,6 +101,8 @@ fn nontex_shape(scene: &mut Scene, creator: &InstanceCreator) -> Vec<u8> {
                     reflectance: 0.25,
                     ambient_ratio: 0.02,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 },
                 ..Default::default()
             },
@@ -179,10 +184,13 @@ fn tex_polygon(
                     reflectance: 0.25,
                     ambient_ratio: 0.02,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 },
                 texture: Some(attach),
                 ..Default::default()
             },
+            generate_normals: false,
         },
     );
     common::render_one(scene, &texture, &cube);
@@ -203,6 +211,8 @@ fn tex_shape(scene: &mut Scene, creator: &InstanceCreator, gradtex: &Arc<Dynamic
                     reflectance: 0.25,
                     ambient_ratio: 0.02,
                     alpha_blend: false,
+                    double_sided: false,
+                    flat_shading: false,
                 },
                 texture: Some(attach),
                 ..Default::default()
@@ -1,21 +1,40 @@
 use crate::*;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
+type StdResult<T, E> = std::result::Result<T, E>;
+/// the pairs of the indices of the vertices and their tolerances
+type VertexTolerances = Vec<(usize, f64)>;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct CompressedEdge<C> {
     vertices: (usize, usize),
     curve: C,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tolerance: Option<f64>,
 }
 
 impl<C> CompressedEdge<C> {
     fn create_edge<P>(self, v: &Vec<Vertex<P>>) -> Result<Edge<P, C>> {
         let front = &v[self.vertices.0];
         let back = &v[self.vertices.1];
-        Edge::try_new(front, back, self.curve)
+        let edge = Edge::try_new(front, back, self.curve)?;
+        edge.set_tolerance(self.tolerance);
+        Ok(edge)
     }
 }
 
+/// the vertices of `points` with the tolerances of the vertices at the indices
+fn create_vertices<P>(points: Vec<P>, tolerances: VertexTolerances) -> Vec<Vertex<P>> {
+    let vertices: Vec<_> = points.into_iter().map(Vertex::new).collect();
+    tolerances.into_iter().for_each(|(idx, tol)| {
+        if let Some(vertex) = vertices.get(idx) {
+            vertex.set_tolerance(Some(tol));
+        }
+    });
+    vertices
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct CompressedFace<S> {
     boundaries: Vec<Vec<(usize, bool)>>,
@@ -49,18 +68,45 @@ impl<S> CompressedFace<S> {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CompressedShell<P, C, S> {
     vertices: Vec<P>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    vertex_tolerances: VertexTolerances,
     edges: Vec<CompressedEdge<C>>,
     faces: Vec<CompressedFace<S>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    edge_metadata: Vec<(usize, Metadata)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    face_metadata: Vec<(usize, Metadata)>,
 }
 
 /// Serialized compressed solid
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CompressedSolid<P, C, S> {
     boundaries: Vec<CompressedShell<P, C, S>>,
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    metadata: Metadata,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CompressedWire<P, C> {
+    vertices: Vec<P>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    vertex_tolerances: VertexTolerances,
+    edges: Vec<CompressedEdge<C>>,
+    wire: Vec<(usize, bool)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CompressedSingleFace<P, C, S> {
+    vertices: Vec<P>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    vertex_tolerances: VertexTolerances,
+    edges: Vec<CompressedEdge<C>>,
+    face: CompressedFace<S>,
 }
 
 struct CompressDirector<P, C> {
     vmap: HashMap<VertexID<P>, (usize, P)>,
+    vertex_tolerances: VertexTolerances,
     emap: HashMap<EdgeID<C>, (usize, CompressedEdge<C>)>,
 }
 
@@ -69,6 +115,7 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
     fn new() -> Self {
         Self {
             vmap: HashMap::new(),
+            vertex_tolerances: Vec::new(),
             emap: HashMap::new(),
         }
     }
@@ -80,6 +127,9 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
                 let id = self.vmap.len();
                 let pt = vertex.get_point();
                 self.vmap.insert(vertex.id(), (id, pt));
+                if let Some(tol) = vertex.tolerance() {
+                    self.vertex_tolerances.push((id, tol));
+                }
                 id
             }
         }
@@ -97,6 +147,7 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
                 let cedge = CompressedEdge {
                     vertices: (front_id, back_id),
                     curve,
+                    tolerance: edge.tolerance(),
                 };
                 self.emap.insert(edge.id(), (id, cedge));
                 (id, edge.orientation())
@@ -130,64 +181,357 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
     }
 
     #[inline(always)]
-    fn vertices_edges(self) -> (Vec<P>, Vec<CompressedEdge<C>>) {
-        (Self::map2vec(self.vmap), Self::map2vec(self.emap))
+    fn vertices_edges(self) -> (Vec<P>, VertexTolerances, Vec<CompressedEdge<C>>) {
+        let vertices = Self::map2vec(self.vmap);
+        (vertices, self.vertex_tolerances, Self::map2vec(self.emap))
     }
 }
 
 impl<P: Clone, C: Clone, S: Clone> Shell<P, C, S> {
     /// Compresses the shell into the serialized compressed shell.
+    #[inline(always)]
     pub fn compress(&self) -> CompressedShell<P, C, S> {
+        self.compress_with_metadata(&MetadataMap::new())
+    }
+
+    /// Compresses the shell into the serialized compressed shell
+    /// with the metadata of the faces and edges.
+    pub fn compress_with_metadata(&self, metadata: &MetadataMap<C, S>) -> CompressedShell<P, C, S> {
         let mut director = CompressDirector::new();
         let mut face_closure = |face: &Face<P, C, S>| director.create_cface(face);
         let faces = self.iter().map(&mut face_closure).collect();
-        let (vertices, edges) = director.vertices_edges();
+        let mut edge_metadata: Vec<_> = director
+            .emap
+            .iter()
+            .filter_map(|(id, (idx, _))| Some((*idx, metadata.edge(*id)?.clone())))
+            .collect();
+        edge_metadata.sort_by_key(|(idx, _)| *idx);
+        let face_metadata = self
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, face)| Some((idx, metadata.face(face.id())?.clone())))
+            .collect();
+        let (vertices, vertex_tolerances, edges) = director.vertices_edges();
         CompressedShell {
             vertices,
+            vertex_tolerances,
             edges,
             faces,
+            edge_metadata,
+            face_metadata,
         }
     }
 
     /// Extracts the serialized compressed shell into the shell.
+    #[inline(always)]
     pub fn extract(cshell: CompressedShell<P, C, S>) -> Result<Self> {
+        Self::extract_into(cshell, &mut MetadataMap::new())
+    }
+
+    /// Extracts the serialized compressed shell into the shell and the metadata.
+    #[inline(always)]
+    pub fn extract_with_metadata(
+        cshell: CompressedShell<P, C, S>,
+    ) -> Result<(Self, MetadataMap<C, S>)> {
+        let mut metadata = MetadataMap::new();
+        let shell = Self::extract_into(cshell, &mut metadata)?;
+        Ok((shell, metadata))
+    }
+
+    fn extract_into(
+        cshell: CompressedShell<P, C, S>,
+        metadata: &mut MetadataMap<C, S>,
+    ) -> Result<Self> {
         let CompressedShell {
             vertices,
+            vertex_tolerances,
             edges,
             faces,
+            edge_metadata,
+            face_metadata,
         } = cshell;
-        let vertices: Vec<_> = vertices.into_iter().map(Vertex::new).collect();
+        let vertices = create_vertices(vertices, vertex_tolerances);
         let edges = edges
             .into_iter()
             .map(move |edge| edge.create_edge(&vertices))
             .collect::<Result<Vec<_>>>()?;
-        faces
+        let shell = faces
             .into_iter()
-            .map(move |face| face.create_face(&edges))
-            .collect()
+            .map(|face| face.create_face(&edges))
+            .collect::<Result<Self>>()?;
+        edge_metadata.into_iter().for_each(|(idx, data)| {
+            if let Some(edge) = edges.get(idx) {
+                *metadata.edge_mut(edge.id()) = data;
+            }
+        });
+        face_metadata.into_iter().for_each(|(idx, data)| {
+            if let Some(face) = shell.get(idx) {
+                *metadata.face_mut(face.id()) = data;
+            }
+        });
+        Ok(shell)
     }
 }
 
 impl<P: Clone, C: Clone, S: Clone> Solid<P, C, S> {
     /// Compresses the solid into the serialized compressed solid.
+    #[inline(always)]
     pub fn compress(&self) -> CompressedSolid<P, C, S> {
+        self.compress_with_metadata(&MetadataMap::new())
+    }
+
+    /// Compresses the solid into the serialized compressed solid with the metadata.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[0, 1]);
+    /// let edge = [Edge::new(&v[0], &v[1], 0), Edge::new(&v[1], &v[0], 1)];
+    /// let wire: Wire<_, _> = edge.iter().collect();
+    /// let shell: Shell<_, _, _> = vec![
+    ///     Face::new(vec![wire.clone()], 0),
+    ///     Face::new(vec![wire.inverse()], 1),
+    /// ].into();
+    /// let solid = Solid::new(vec![shell]);
+    ///
+    /// let mut metadata = MetadataMap::new();
+    /// metadata.solid_mut().name = Some("lens".to_string());
+    /// metadata.face_mut(solid.boundaries()[0][1].id()).layer = Some("back".to_string());
+    /// metadata.edge_mut(edge[0].id()).name = Some("rim".to_string());
+    ///
+    /// let json = serde_json::to_string(&solid.compress_with_metadata(&metadata)).unwrap();
+    /// let (solid0, metadata0) = Solid::<usize, usize, usize>::extract_with_metadata(
+    ///     serde_json::from_str(&json).unwrap(),
+    /// ).unwrap();
+    ///
+    /// assert_eq!(metadata0.solid(), &Metadata::with_name("lens"));
+    /// let face = &solid0.boundaries()[0][1];
+    /// assert_eq!(face.get_surface(), 1);
+    /// assert_eq!(metadata0.face(face.id()).unwrap().layer.as_deref(), Some("back"));
+    /// let edge = face.boundary_iters()[0].find(|edge| edge.get_curve() == 0).unwrap();
+    /// assert_eq!(metadata0.edge(edge.id()), Some(&Metadata::with_name("rim")));
+    /// ```
+    pub fn compress_with_metadata(&self, metadata: &MetadataMap<C, S>) -> CompressedSolid<P, C, S> {
         CompressedSolid {
             boundaries: self
                 .boundaries()
                 .iter()
-                .map(|shell| shell.compress())
+                .map(|shell| shell.compress_with_metadata(metadata))
                 .collect(),
+            metadata: metadata.solid().clone(),
         }
     }
 
     /// Extracts the serialized compressed shell into the shell.
+    #[inline(always)]
     pub fn extract(csolid: CompressedSolid<P, C, S>) -> Result<Self> {
-        let shells: Result<Vec<Shell<P, C, S>>> = csolid
+        Self::extract_with_metadata(csolid).map(|(solid, _)| solid)
+    }
+
+    /// Extracts the serialized compressed solid into the solid and the metadata.
+    pub fn extract_with_metadata(
+        csolid: CompressedSolid<P, C, S>,
+    ) -> Result<(Self, MetadataMap<C, S>)> {
+        let mut metadata = MetadataMap::new();
+        *metadata.solid_mut() = csolid.metadata;
+        let shells = csolid
             .boundaries
             .into_iter()
-            .map(|cshell| Shell::extract(cshell))
-            .collect();
-        Solid::try_new(shells?)
+            .map(|cshell| Shell::extract_into(cshell, &mut metadata))
+            .collect::<Result<Vec<Shell<P, C, S>>>>()?;
+        Ok((Solid::try_new(shells)?, metadata))
+    }
+}
+
+impl<P: Clone, C: Clone> CompressedWire<P, C> {
+    fn compress(wire: &Wire<P, C>) -> Self {
+        let mut director = CompressDirector::new();
+        let wire = director.create_boundary(wire);
+        let (vertices, vertex_tolerances, edges) = director.vertices_edges();
+        Self {
+            vertices,
+            vertex_tolerances,
+            edges,
+            wire,
+        }
+    }
+
+    fn extract(self) -> Result<Wire<P, C>> {
+        let vertices = create_vertices(self.vertices, self.vertex_tolerances);
+        let edges = self
+            .edges
+            .into_iter()
+            .map(|edge| edge.create_edge(&vertices))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self
+            .wire
+            .into_iter()
+            .map(|(idx, ori)| match ori {
+                true => edges[idx].clone(),
+                false => edges[idx].inverse(),
+            })
+            .collect())
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> CompressedSingleFace<P, C, S> {
+    fn compress(face: &Face<P, C, S>) -> Self {
+        let mut director = CompressDirector::new();
+        let face = director.create_cface(face);
+        let (vertices, vertex_tolerances, edges) = director.vertices_edges();
+        Self {
+            vertices,
+            vertex_tolerances,
+            edges,
+            face,
+        }
+    }
+
+    fn extract(self) -> Result<Face<P, C, S>> {
+        let vertices = create_vertices(self.vertices, self.vertex_tolerances);
+        let edges = self
+            .edges
+            .into_iter()
+            .map(|edge| edge.create_edge(&vertices))
+            .collect::<Result<Vec<_>>>()?;
+        self.face.create_face(&edges)
+    }
+}
+
+/// Serializes the point of the vertex.
+impl<P: Clone + Serialize> Serialize for Vertex<P> {
+    #[inline(always)]
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> StdResult<Ser::Ok, Ser::Error> {
+        self.get_point().serialize(serializer)
+    }
+}
+
+/// Creates a new vertex from the deserialized point.
+impl<'de, P: Deserialize<'de>> Deserialize<'de> for Vertex<P> {
+    #[inline(always)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        P::deserialize(deserializer).map(Vertex::new)
+    }
+}
+
+/// Serializes the edge as a wire with one edge.
+impl<P: Clone + Serialize, C: Clone + Serialize> Serialize for Edge<P, C> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> StdResult<Ser::Ok, Ser::Error> {
+        let wire: Wire<P, C> = std::iter::once(self).collect();
+        CompressedWire::compress(&wire).serialize(serializer)
+    }
+}
+
+impl<'de, P, C> Deserialize<'de> for Edge<P, C>
+where
+    P: Clone + Deserialize<'de>,
+    C: Clone + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let wire = CompressedWire::deserialize(deserializer)?
+            .extract()
+            .map_err(D::Error::custom)?;
+        match wire.len() {
+            1 => Ok(wire[0].clone()),
+            len => Err(D::Error::invalid_length(len, &"one edge")),
+        }
+    }
+}
+
+/// Serializes the wire. The vertices and edges shared in the wire are serialized only once.
+impl<P: Clone + Serialize, C: Clone + Serialize> Serialize for Wire<P, C> {
+    #[inline(always)]
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> StdResult<Ser::Ok, Ser::Error> {
+        CompressedWire::compress(self).serialize(serializer)
+    }
+}
+
+impl<'de, P, C> Deserialize<'de> for Wire<P, C>
+where
+    P: Clone + Deserialize<'de>,
+    C: Clone + Deserialize<'de>,
+{
+    #[inline(always)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        CompressedWire::deserialize(deserializer)?
+            .extract()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Serializes the face. The vertices and edges shared in the face are serialized only once.
+impl<P, C, S> Serialize for Face<P, C, S>
+where
+    P: Clone + Serialize,
+    C: Clone + Serialize,
+    S: Clone + Serialize,
+{
+    #[inline(always)]
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> StdResult<Ser::Ok, Ser::Error> {
+        CompressedSingleFace::compress(self).serialize(serializer)
+    }
+}
+
+impl<'de, P, C, S> Deserialize<'de> for Face<P, C, S>
+where
+    P: Clone + Deserialize<'de>,
+    C: Clone + Deserialize<'de>,
+    S: Clone + Deserialize<'de>,
+{
+    #[inline(always)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        CompressedSingleFace::deserialize(deserializer)?
+            .extract()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Serializes the shell via [`CompressedShell`](./struct.CompressedShell.html).
+impl<P, C, S> Serialize for Shell<P, C, S>
+where
+    P: Clone + Serialize,
+    C: Clone + Serialize,
+    S: Clone + Serialize,
+{
+    #[inline(always)]
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> StdResult<Ser::Ok, Ser::Error> {
+        self.compress().serialize(serializer)
+    }
+}
+
+impl<'de, P, C, S> Deserialize<'de> for Shell<P, C, S>
+where
+    P: Clone + Deserialize<'de>,
+    C: Clone + Deserialize<'de>,
+    S: Clone + Deserialize<'de>,
+{
+    #[inline(always)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        Shell::extract(CompressedShell::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Serializes the solid via [`CompressedSolid`](./struct.CompressedSolid.html).
+impl<P, C, S> Serialize for Solid<P, C, S>
+where
+    P: Clone + Serialize,
+    C: Clone + Serialize,
+    S: Clone + Serialize,
+{
+    #[inline(always)]
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> StdResult<Ser::Ok, Ser::Error> {
+        self.compress().serialize(serializer)
+    }
+}
+
+impl<'de, P, C, S> Deserialize<'de> for Solid<P, C, S>
+where
+    P: Clone + Deserialize<'de>,
+    C: Clone + Deserialize<'de>,
+    S: Clone + Deserialize<'de>,
+{
+    #[inline(always)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        Solid::extract(CompressedSolid::deserialize(deserializer)?).map_err(D::Error::custom)
     }
 }
 
@@ -43,7 +43,8 @@ impl<P, C> Edge<P, C> {
         Edge {
             vertices: (front.clone(), back.clone()),
             orientation: true,
-            curve: Arc::new(Mutex::new(curve)),
+            curve: Arc::new(RwLock::new(curve)),
+            tolerance: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -230,9 +231,25 @@ impl<P, C> Edge<P, C> {
     #[inline(always)]
     pub fn get_curve(&self) -> C
     where C: Clone {
-        self.curve.lock().unwrap().clone()
+        self.curve.read().unwrap().clone()
     }
 
+    /// Returns the read guard of the curve.
+    ///
+    /// Unlike `get_curve`, the curve is not cloned, and the guards can be held
+    /// by some threads at the same time.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(), ()]);
+    /// let edge = Edge::new(&v[0], &v[1], vec![0, 1, 2]);
+    /// assert_eq!(edge.read_curve()[1], 1);
+    /// ```
+    /// # Remarks
+    /// Setting the curve while holding the guard in the same thread will result in a deadlock.
+    #[inline(always)]
+    pub fn read_curve(&self) -> RwLockReadGuard<'_, C> { self.curve.read().unwrap() }
+
     /// Set the curve.
     /// # Examples
     /// ```
@@ -253,7 +270,43 @@ impl<P, C> Edge<P, C> {
     /// assert_eq!(edge1.get_curve(), 1);
     /// ```
     #[inline(always)]
-    pub fn set_curve(&self, curve: C) { *self.curve.lock().unwrap() = curve; }
+    pub fn set_curve(&self, curve: C) { *self.curve.write().unwrap() = curve; }
+
+    /// Returns the tolerance of the edge, i.e. the distance within which the curve
+    /// may deviate from the exact edge. `None` means that the edge is as precise as
+    /// the global `TOLERANCE`.
+    #[inline(always)]
+    pub fn tolerance(&self) -> Option<f64> { *self.tolerance.read().unwrap() }
+
+    /// Sets the tolerance of the edge. The tolerance is shared by the cloned and inverted edges.
+    ///
+    /// The tolerances given by the imported data are used in the consistency checks
+    /// instead of the global `TOLERANCE`, if they are larger than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(), ()]);
+    /// let edge0 = Edge::new(&v[0], &v[1], ());
+    /// let edge1 = edge0.inverse();
+    /// assert_eq!(edge0.tolerance(), None);
+    ///
+    /// edge0.set_tolerance(Some(1.0e-3));
+    /// assert_eq!(edge1.tolerance(), Some(1.0e-3));
+    /// ```
+    #[inline(always)]
+    pub fn set_tolerance(&self, tolerance: Option<f64>) {
+        *self.tolerance.write().unwrap() = tolerance;
+    }
+
+    /// Returns the tolerance for the distance between the curve and the vertex `v`,
+    /// the maximum of `TOLERANCE` and the tolerances of the edge and `v`.
+    #[inline(always)]
+    fn tolerance_at(&self, v: &Vertex<P>) -> f64 {
+        [self.tolerance(), v.tolerance()]
+            .iter()
+            .flatten()
+            .fold(TOLERANCE, |tol, x| f64::max(tol, *x))
+    }
 
     /// Returns the id that does not depend on the direction of the edge.
     /// # Examples
@@ -273,8 +326,8 @@ impl<P, C> Edge<P, C> {
     pub fn oriented_curve(&self) -> C
     where C: Clone + Invertible {
         match self.orientation {
-            true => self.curve.lock().unwrap().clone(),
-            false => self.curve.lock().unwrap().inverse(),
+            true => self.curve.read().unwrap().clone(),
+            false => self.curve.read().unwrap().inverse(),
         }
     }
 
@@ -292,8 +345,9 @@ impl<P, C> Edge<P, C> {
     ) -> Option<Edge<Q, D>> {
         let v0 = self.absolute_front().try_mapped(&mut point_mapping)?;
         let v1 = self.absolute_back().try_mapped(&mut point_mapping)?;
-        let curve = curve_mapping(&*self.curve.lock().unwrap())?;
+        let curve = curve_mapping(&*self.curve.read().unwrap())?;
         let mut edge = Edge::debug_new(&v0, &v1, curve);
+        edge.set_tolerance(self.tolerance());
         if edge.orientation() != self.orientation() {
             edge.invert();
         }
@@ -329,8 +383,9 @@ impl<P, C> Edge<P, C> {
     ) -> Edge<Q, D> {
         let v0 = self.absolute_front().mapped(&mut point_mapping);
         let v1 = self.absolute_back().mapped(&mut point_mapping);
-        let curve = curve_mapping(&*self.curve.lock().unwrap());
+        let curve = curve_mapping(&*self.curve.read().unwrap());
         let mut edge = Edge::debug_new(&v0, &v1, curve);
+        edge.set_tolerance(self.tolerance());
         if edge.orientation() != self.orientation() {
             edge.invert();
         }
@@ -339,17 +394,32 @@ impl<P, C> Edge<P, C> {
 
     /// Returns the consistence of the geometry of end vertices
     /// and the geometry of edge.
+    ///
+    /// The ends of the curve may be apart from the points of the vertices by the tolerances
+    /// of the edge and the vertices, if they are larger than `TOLERANCE`.
     #[inline(always)]
     pub fn is_geometric_consistent(&self) -> bool
     where
         P: Tolerance,
         C: ParametricCurve<Point = P>, {
-        let curve = self.curve.lock().unwrap();
+        let front_tol = self.tolerance_at(self.absolute_front());
+        let back_tol = self.tolerance_at(self.absolute_back());
+        let curve = self.curve.read().unwrap();
         let geom_front = curve.front();
         let geom_back = curve.back();
-        let top_front = self.absolute_front().point.lock().unwrap();
-        let top_back = self.absolute_back().point.lock().unwrap();
-        geom_front.near(&*top_front) && geom_back.near(&*top_back)
+        let top_front = self.absolute_front().point.read().unwrap();
+        let top_back = self.absolute_back().point.read().unwrap();
+        geom_front.abs_diff_eq(&*top_front, front_tol)
+            && geom_back.abs_diff_eq(&*top_back, back_tol)
+    }
+
+    /// Returns the bounding box of the curve by `RoughlyBounded`, without tessellation.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<P>
+    where
+        P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy,
+        C: RoughlyBounded<P>, {
+        self.curve.read().unwrap().roughly_bounding_box()
     }
 
     /// Cuts the edge at a point `pt`.
@@ -365,12 +435,14 @@ impl<P, C> Edge<P, C> {
         let edge0 = Edge {
             vertices: (self.absolute_front().clone(), vertex.clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve0)),
+            curve: Arc::new(RwLock::new(curve0)),
+            tolerance: Arc::new(RwLock::new(self.tolerance())),
         };
         let edge1 = Edge {
             vertices: (vertex.clone(), self.absolute_back().clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve1)),
+            curve: Arc::new(RwLock::new(curve1)),
+            tolerance: Arc::new(RwLock::new(self.tolerance())),
         };
         if self.orientation {
             Some((edge0, edge1))
@@ -399,7 +471,12 @@ impl<P, C> Edge<P, C> {
         let t1 = curve1.parameter_range().0;
         curve1.parameter_transform(1.0, t0 - t1);
         let curve = curve0.try_concat(&curve1)?;
-        Ok(Edge::debug_new(self.front(), rhs.back(), curve))
+        let edge = Edge::debug_new(self.front(), rhs.back(), curve);
+        edge.set_tolerance(match (self.tolerance(), rhs.tolerance()) {
+            (Some(tol0), Some(tol1)) => Some(f64::max(tol0, tol1)),
+            (tol0, tol1) => tol0.or(tol1),
+        });
+        Ok(edge)
     }
 }
 
@@ -429,6 +506,7 @@ impl<P, C> Clone for Edge<P, C> {
             vertices: self.vertices.clone(),
             orientation: self.orientation,
             curve: Arc::clone(&self.curve),
+            tolerance: Arc::clone(&self.tolerance),
         }
     }
 }
@@ -63,7 +63,7 @@ impl<P, C, S> Face<P, C, S> {
         Face {
             boundaries: boundaries,
             orientation: true,
-            surface: Arc::new(Mutex::new(surface)),
+            surface: Arc::new(RwLock::new(surface)),
         }
     }
 
@@ -190,7 +190,7 @@ impl<P, C, S> Face<P, C, S> {
     fn renew_pointer(&mut self)
     where S: Clone {
         let surface = self.get_surface();
-        self.surface = Arc::new(Mutex::new(surface));
+        self.surface = Arc::new(RwLock::new(surface));
     }
 
     /// Adds a boundary to the face.
@@ -378,7 +378,7 @@ impl<P, C, S> Face<P, C, S> {
             .iter()
             .map(|wire| wire.try_mapped(&mut point_mapping, &mut curve_mapping))
             .collect::<Option<Vec<_>>>()?;
-        let surface = surface_mapping(&*self.surface.lock().unwrap())?;
+        let surface = surface_mapping(&*self.surface.read().unwrap())?;
         let mut face = Face::debug_new(wires, surface);
         if !self.orientation() {
             face.invert();
@@ -453,7 +453,7 @@ impl<P, C, S> Face<P, C, S> {
             .iter()
             .map(|wire| wire.mapped(&mut point_mapping, &mut curve_mapping))
             .collect();
-        let surface = surface_mapping(&*self.surface.lock().unwrap());
+        let surface = surface_mapping(&*self.surface.read().unwrap());
         let mut face = Face::debug_new(wires, surface);
         if !self.orientation() {
             face.invert();
@@ -472,7 +472,51 @@ impl<P, C, S> Face<P, C, S> {
     #[inline(always)]
     pub fn get_surface(&self) -> S
     where S: Clone {
-        self.surface.lock().unwrap().clone()
+        self.surface.read().unwrap().clone()
+    }
+
+    /// Returns the read guard of the surface of face.
+    ///
+    /// Unlike `get_surface`, the surface is not cloned, and the guards can be held
+    /// by some threads at the same time.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(), (), ()]);
+    /// let wire = Wire::from(vec![
+    ///      Edge::new(&v[0], &v[1], ()),
+    ///      Edge::new(&v[1], &v[2], ()),
+    ///      Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// let face = Face::new(vec![wire], vec![0, 1, 2]);
+    /// assert_eq!(face.read_surface()[1], 1);
+    /// ```
+    /// # Remarks
+    /// Setting the surface while holding the guard in the same thread will result in a deadlock.
+    #[inline(always)]
+    pub fn read_surface(&self) -> RwLockReadGuard<'_, S> { self.surface.read().unwrap() }
+
+    /// Returns the bounding box of the face without tessellation: the bounding box of
+    /// the surface by `RoughlyBounded`, or the bounding box of the boundaries if the surface is
+    /// unbounded.
+    ///
+    /// The unbounded surfaces are assumed to be planes, whose faces are included in the convex
+    /// hulls of the boundaries.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<P>
+    where
+        P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy,
+        C: RoughlyBounded<P>,
+        S: RoughlyBounded<P>, {
+        let bdb = self.surface.read().unwrap().roughly_bounding_box();
+        match bdb.is_empty() || bdb.size().is_finite() {
+            true => bdb,
+            false => self
+                .boundaries
+                .iter()
+                .map(Wire::bounding_box)
+                .fold(BoundingBox::new(), |sum, bdb| sum + bdb),
+        }
     }
 
     /// Sets the surface of face.
@@ -500,7 +544,7 @@ impl<P, C, S> Face<P, C, S> {
     /// assert_eq!(face1.get_surface(), 1);
     /// ```
     #[inline(always)]
-    pub fn set_surface(&self, surface: S) { *self.surface.lock().unwrap() = surface; }
+    pub fn set_surface(&self, surface: S) { *self.surface.write().unwrap() = surface; }
 
     /// Inverts the direction of the face.
     /// # Examples
@@ -722,7 +766,7 @@ impl<P, C, S> Face<P, C, S> {
         Some(Face {
             boundaries: vec![new_wire],
             orientation: self.orientation,
-            surface: Arc::new(Mutex::new(self.get_surface())),
+            surface: Arc::new(RwLock::new(self.get_surface())),
         })
     }
 
@@ -824,7 +868,7 @@ impl<P, C, S> Face<P, C, S> {
         Some(Face {
             boundaries,
             orientation: self.orientation(),
-            surface: Arc::new(Mutex::new(surface)),
+            surface: Arc::new(RwLock::new(surface)),
         })
     }
 }
@@ -835,8 +879,8 @@ impl<P, C, S: Clone + Invertible> Face<P, C, S> {
     #[inline(always)]
     pub fn oriented_surface(&self) -> S {
         match self.orientation {
-            true => self.surface.lock().unwrap().clone(),
-            false => self.surface.lock().unwrap().inverse(),
+            true => self.surface.read().unwrap().clone(),
+            false => self.surface.read().unwrap().inverse(),
         }
     }
 }
@@ -851,10 +895,10 @@ where
     /// and the geometry of edge.
     #[inline(always)]
     pub fn is_geometric_consistent(&self) -> bool {
-        let surface = &*self.surface.lock().unwrap();
+        let surface = &*self.surface.read().unwrap();
         self.boundary_iters().into_iter().flatten().all(|edge| {
             let edge_consist = edge.is_geometric_consistent();
-            let curve = &*edge.curve.lock().unwrap();
+            let curve = &*edge.curve.read().unwrap();
             let curve_consist = surface.include(curve);
             edge_consist && curve_consist
         })
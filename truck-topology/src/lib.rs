@@ -75,10 +75,17 @@
     unused_qualifications
 )]
 
-use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::hash::{Hash, Hasher};
-use std::sync::{Arc, Mutex};
-use truck_base::{id::ID, tolerance::*};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::ops::Index;
+use truck_base::{
+    bounding_box::*,
+    cgmath64::{MetricSpace, Vector4},
+    id::ID,
+    tolerance::*,
+};
 use truck_geotrait::*;
 
 const SEARCH_PARAMETER_TRIALS: usize = 100;
@@ -93,9 +100,13 @@ const SEARCH_PARAMETER_TRIALS: usize = 100;
 /// let v1 = Vertex::new(()); // another vertex
 /// assert_ne!(v0, v1); // two vertices are different
 /// ```
+///
+/// A vertex can have its own tolerance, which is shared by the cloned vertices.
+/// See [`Vertex::set_tolerance`](./struct.Vertex.html#method.set_tolerance).
 #[derive(Debug)]
 pub struct Vertex<P> {
-    point: Arc<Mutex<P>>,
+    point: Arc<RwLock<P>>,
+    tolerance: Arc<RwLock<Option<f64>>>,
 }
 
 /// Edge, which consists two vertices.
@@ -110,11 +121,15 @@ pub struct Vertex<P> {
 /// let edge1 = Edge::new(&v[0], &v[1], ());
 /// assert_ne!(edge0.id(), edge1.id());
 /// ```
+///
+/// An edge can have its own tolerance, which is shared by the cloned and inverted edges.
+/// See [`Edge::set_tolerance`](./struct.Edge.html#method.set_tolerance).
 #[derive(Debug)]
 pub struct Edge<P, C> {
     vertices: (Vertex<P>, Vertex<P>),
     orientation: bool,
-    curve: Arc<Mutex<C>>,
+    curve: Arc<RwLock<C>>,
+    tolerance: Arc<RwLock<Option<f64>>>,
 }
 
 /// Wire, a path or cycle which consists some edges.
@@ -146,7 +161,7 @@ pub struct Wire<P, C> {
 pub struct Face<P, C, S> {
     boundaries: Vec<Wire<P, C>>,
     orientation: bool,
-    surface: Arc<Mutex<S>>,
+    surface: Arc<RwLock<S>>,
 }
 
 /// Shell, a connected compounded faces.
@@ -164,6 +179,44 @@ pub struct Solid<P, C, S> {
     boundaries: Vec<Shell<P, C, S>>,
 }
 
+/// Metadata of shapes: name, color, layer and custom key/values.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// the name of the element
+    pub name: Option<String>,
+    /// the RGBA color of the element
+    pub color: Option<Vector4>,
+    /// the name of the layer which the element belongs to
+    pub layer: Option<String>,
+    /// custom key/values
+    pub custom: BTreeMap<String, String>,
+}
+
+/// Metadata attached to a solid, and its faces and edges.
+///
+/// Like other attributes of topological elements in higher-level packages,
+/// the metadata of faces and edges are mapped from their ids.
+/// The metadata are persisted with the solid by
+/// [`Solid::compress_with_metadata`](./struct.Solid.html#method.compress_with_metadata).
+#[derive(Clone, Debug)]
+pub struct MetadataMap<C, S> {
+    solid: Metadata,
+    edges: HashMap<EdgeID<C>, Metadata>,
+    faces: HashMap<FaceID<S>, Metadata>,
+}
+
+/// Parameter-space curves of edges on faces, i.e. pcurves.
+///
+/// Like [`MetadataMap`](./struct.MetadataMap.html), the pcurves are mapped from the ids of
+/// edges and faces. The pcurve of an edge on a face is the curve in the parameter space of
+/// the surface of the face, whose image by the surface is the curve of the edge.
+/// The pcurve has the same parameter range and the same direction as the curve of the edge,
+/// regardless of the orientations of the edge and the face.
+#[derive(Clone, Debug)]
+pub struct PCurveMap<C, S, Q> {
+    pcurves: HashMap<(EdgeID<C>, FaceID<S>), Q>,
+}
+
 /// `Result` with crate's errors.
 pub type Result<T> = std::result::Result<T, crate::errors::Error>;
 
@@ -207,7 +260,7 @@ impl<T> RemoveTry<T> for Result<T> {
 /// assert_ne!(entity, v.get_point());
 /// assert_eq!(v_id, v.id());
 /// ```
-pub type VertexID<P> = ID<Mutex<P>>;
+pub type VertexID<P> = ID<RwLock<P>>;
 
 /// The id that does not depend on the direction of the edge.
 /// # Examples
@@ -219,7 +272,7 @@ pub type VertexID<P> = ID<Mutex<P>>;
 /// assert_ne!(edge0, edge1);
 /// assert_eq!(edge0.id(), edge1.id());
 /// ```
-pub type EdgeID<C> = ID<Mutex<C>>;
+pub type EdgeID<C> = ID<RwLock<C>>;
 
 /// The id that does not depend on the direction of the face.
 /// # Examples
@@ -239,7 +292,7 @@ pub type EdgeID<C> = ID<Mutex<C>>;
 /// assert_eq!(face0.id(), face1.id());
 /// assert_ne!(face0.id(), face2.id());
 /// ```
-pub type FaceID<S> = ID<Mutex<S>>;
+pub type FaceID<S> = ID<RwLock<S>>;
 
 mod compress;
 mod edge;
@@ -247,6 +300,8 @@ mod edge;
 pub mod errors;
 /// Defines the boundary iterator.
 pub mod face;
+mod metadata;
+mod pcurve;
 /// classifies shell conditions and defines the face iterators.
 pub mod shell;
 mod solid;
@@ -0,0 +1,118 @@
+use crate::*;
+
+impl Metadata {
+    /// Creates the metadata with only the name.
+    #[inline(always)]
+    pub fn with_name<T: Into<String>>(name: T) -> Metadata {
+        Metadata {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+    /// Returns whether no metadata is set or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.color.is_none()
+            && self.layer.is_none()
+            && self.custom.is_empty()
+    }
+}
+
+impl<C, S> Default for MetadataMap<C, S> {
+    #[inline(always)]
+    fn default() -> Self {
+        MetadataMap {
+            solid: Metadata::default(),
+            edges: HashMap::new(),
+            faces: HashMap::new(),
+        }
+    }
+}
+
+impl<C, S> MetadataMap<C, S> {
+    /// Creates an empty metadata map.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+
+    /// Returns the metadata of the solid.
+    #[inline(always)]
+    pub fn solid(&self) -> &Metadata { &self.solid }
+
+    /// Returns the mutable reference to the metadata of the solid.
+    #[inline(always)]
+    pub fn solid_mut(&mut self) -> &mut Metadata { &mut self.solid }
+
+    /// Returns the metadata of the edge.
+    #[inline(always)]
+    pub fn edge(&self, id: EdgeID<C>) -> Option<&Metadata> { self.edges.get(&id) }
+
+    /// Returns the mutable reference to the metadata of the edge.
+    /// If the edge has no metadata, the empty metadata is inserted.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(), ()]);
+    /// let edge = Edge::new(&v[0], &v[1], ());
+    ///
+    /// let mut map = MetadataMap::<(), ()>::new();
+    /// assert!(map.edge(edge.id()).is_none());
+    /// map.edge_mut(edge.id()).name = Some("seam".to_string());
+    /// assert_eq!(map.edge(edge.inverse().id()), Some(&Metadata::with_name("seam")));
+    /// ```
+    #[inline(always)]
+    pub fn edge_mut(&mut self, id: EdgeID<C>) -> &mut Metadata { self.edges.entry(id).or_default() }
+
+    /// Removes the metadata of the edge.
+    #[inline(always)]
+    pub fn remove_edge(&mut self, id: EdgeID<C>) -> Option<Metadata> { self.edges.remove(&id) }
+
+    /// Returns the metadata of the face.
+    #[inline(always)]
+    pub fn face(&self, id: FaceID<S>) -> Option<&Metadata> { self.faces.get(&id) }
+
+    /// Returns the mutable reference to the metadata of the face.
+    /// If the face has no metadata, the empty metadata is inserted.
+    #[inline(always)]
+    pub fn face_mut(&mut self, id: FaceID<S>) -> &mut Metadata { self.faces.entry(id).or_default() }
+
+    /// Removes the metadata of the face.
+    #[inline(always)]
+    pub fn remove_face(&mut self, id: FaceID<S>) -> Option<Metadata> { self.faces.remove(&id) }
+
+    /// Returns the iterator of the ids of the edges in `layer`.
+    #[inline(always)]
+    pub fn edges_in_layer<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = EdgeID<C>> + 'a {
+        self.edges
+            .iter()
+            .filter(move |(_, metadata)| metadata.layer.as_deref() == Some(layer))
+            .map(|(id, _)| *id)
+    }
+
+    /// Returns the iterator of the ids of the faces in `layer`.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 3]);
+    /// let wire = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// let faces: Vec<Face<(), (), ()>> =
+    ///     (0..3).map(|_| Face::new(vec![wire.clone()], ())).collect();
+    ///
+    /// let mut map = MetadataMap::<(), ()>::new();
+    /// map.face_mut(faces[0].id()).layer = Some("top".to_string());
+    /// map.face_mut(faces[1].id()).layer = Some("side".to_string());
+    /// map.face_mut(faces[2].id()).layer = Some("side".to_string());
+    /// assert_eq!(map.faces_in_layer("side").count(), 2);
+    /// ```
+    #[inline(always)]
+    pub fn faces_in_layer<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = FaceID<S>> + 'a {
+        self.faces
+            .iter()
+            .filter(move |(_, metadata)| metadata.layer.as_deref() == Some(layer))
+            .map(|(id, _)| *id)
+    }
+}
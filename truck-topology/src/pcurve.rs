@@ -0,0 +1,133 @@
+use crate::*;
+use truck_base::cgmath64::{MetricSpace, Point2};
+
+/// the number of the divisions of the parameter range for checking the synchronization
+const SYNCHRONIZATION_DIVISION: usize = 16;
+
+impl<C, S, Q> Default for PCurveMap<C, S, Q> {
+    #[inline(always)]
+    fn default() -> Self {
+        PCurveMap {
+            pcurves: HashMap::new(),
+        }
+    }
+}
+
+impl<C, S, Q> PCurveMap<C, S, Q> {
+    /// Creates an empty pcurve map.
+    #[inline(always)]
+    pub fn new() -> Self { Self::default() }
+
+    /// Returns the number of the pcurves.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.pcurves.len() }
+
+    /// Returns whether the map has no pcurve or not.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.pcurves.is_empty() }
+
+    /// Inserts the pcurve of the edge on the face, and returns the old pcurve if exists.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 3]);
+    /// let wire = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// let face: Face<(), (), ()> = Face::new(vec![wire.clone()], ());
+    ///
+    /// let mut map = PCurveMap::<(), (), usize>::new();
+    /// map.insert(wire[0].id(), face.id(), 0);
+    /// // the pcurve is shared by the inverted edge
+    /// assert_eq!(map.get(wire[0].inverse().id(), face.id()), Some(&0));
+    /// assert_eq!(map.get(wire[1].id(), face.id()), None);
+    /// assert_eq!(map.insert(wire[0].id(), face.id(), 1), Some(0));
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    #[inline(always)]
+    pub fn insert(&mut self, edge: EdgeID<C>, face: FaceID<S>, pcurve: Q) -> Option<Q> {
+        self.pcurves.insert((edge, face), pcurve)
+    }
+
+    /// Returns the pcurve of the edge on the face.
+    #[inline(always)]
+    pub fn get(&self, edge: EdgeID<C>, face: FaceID<S>) -> Option<&Q> {
+        self.pcurves.get(&(edge, face))
+    }
+
+    /// Removes the pcurve of the edge on the face.
+    #[inline(always)]
+    pub fn remove(&mut self, edge: EdgeID<C>, face: FaceID<S>) -> Option<Q> {
+        self.pcurves.remove(&(edge, face))
+    }
+
+    /// Removes all the pcurves of the edge.
+    #[inline(always)]
+    pub fn remove_edge(&mut self, edge: EdgeID<C>) {
+        self.pcurves.retain(|(id, _), _| *id != edge)
+    }
+
+    /// Removes all the pcurves on the face.
+    #[inline(always)]
+    pub fn remove_face(&mut self, face: FaceID<S>) {
+        self.pcurves.retain(|(_, id), _| *id != face)
+    }
+
+    /// Returns whether the pcurve of `edge` on `face` is synchronized with the curve of `edge`,
+    /// i.e. the parameter ranges are the same and the image of the pcurve by the surface
+    /// is within `tol` from the curve at the sampled parameters.
+    /// Returns `None` if `edge` has no pcurve on `face`.
+    pub fn is_synchronized<P>(
+        &self,
+        edge: &Edge<P, C>,
+        face: &Face<P, C, S>,
+        tol: f64,
+    ) -> Option<bool>
+    where
+        P: MetricSpace<Metric = f64>,
+        C: ParametricCurve<Point = P>,
+        S: ParametricSurface<Point = P>,
+        Q: ParametricCurve<Point = Point2>, {
+        let pcurve = self.get(edge.id(), face.id())?;
+        let curve = edge.read_curve();
+        let surface = face.read_surface();
+        let (t0, t1) = curve.parameter_range();
+        let (s0, s1) = pcurve.parameter_range();
+        if !t0.near(&s0) || !t1.near(&s1) {
+            return Some(false);
+        }
+        let synchronized = (0..=SYNCHRONIZATION_DIVISION).all(|i| {
+            let t = t0 + (t1 - t0) * i as f64 / SYNCHRONIZATION_DIVISION as f64;
+            let uv = pcurve.subs(t);
+            surface.subs(uv[0], uv[1]).distance(curve.subs(t)) <= tol
+        });
+        Some(synchronized)
+    }
+
+    /// Returns the pairs of the edges and the faces of `shell` whose pcurves are not
+    /// synchronized with the curves of the edges. The edges without pcurves are skipped.
+    /// cf. [`PCurveMap::is_synchronized`](#method.is_synchronized)
+    pub fn desynchronized<P>(
+        &self,
+        shell: &Shell<P, C, S>,
+        tol: f64,
+    ) -> Vec<(EdgeID<C>, FaceID<S>)>
+    where
+        P: MetricSpace<Metric = f64>,
+        C: ParametricCurve<Point = P>,
+        S: ParametricSurface<Point = P>,
+        Q: ParametricCurve<Point = Point2>, {
+        shell
+            .face_iter()
+            .flat_map(|face| {
+                face.absolute_boundaries()
+                    .iter()
+                    .flatten()
+                    .filter(move |edge| self.is_synchronized(edge, face, tol) == Some(false))
+                    .map(move |edge| (edge.id(), face.id()))
+            })
+            .collect()
+    }
+}
@@ -472,8 +472,9 @@ impl<P, C, S> Shell<P, C, S> {
                     } else {
                         let v0 = vmap.get(&edge.absolute_front().id()).unwrap();
                         let v1 = vmap.get(&edge.absolute_back().id()).unwrap();
-                        let curve = curve_mapping(&*edge.curve.lock().unwrap())?;
+                        let curve = curve_mapping(&*edge.curve.read().unwrap())?;
                         let new_edge = Edge::debug_new(v0, v1, curve);
+                        new_edge.set_tolerance(edge.tolerance());
                         if edge.orientation() {
                             wire.push_back(new_edge.clone());
                         } else {
@@ -484,7 +485,7 @@ impl<P, C, S> Shell<P, C, S> {
                 }
                 wires.push(wire);
             }
-            let surface = surface_mapping(&*face.surface.lock().unwrap())?;
+            let surface = surface_mapping(&*face.surface.read().unwrap())?;
             let mut new_face = Face::debug_new(wires, surface);
             if !face.orientation() {
                 new_face.invert();
@@ -593,8 +594,9 @@ impl<P, C, S> Shell<P, C, S> {
                     } else {
                         let v0 = vmap.get(&edge.absolute_front().id()).unwrap();
                         let v1 = vmap.get(&edge.absolute_back().id()).unwrap();
-                        let curve = curve_mapping(&*edge.curve.lock().unwrap());
+                        let curve = curve_mapping(&*edge.curve.read().unwrap());
                         let new_edge = Edge::debug_new(v0, v1, curve);
+                        new_edge.set_tolerance(edge.tolerance());
                         if edge.orientation() {
                             wire.push_back(new_edge.clone());
                         } else {
@@ -605,7 +607,7 @@ impl<P, C, S> Shell<P, C, S> {
                 }
                 wires.push(wire);
             }
-            let surface = surface_mapping(&*face.surface.lock().unwrap());
+            let surface = surface_mapping(&*face.surface.read().unwrap());
             let mut new_face = Face::debug_new(wires, surface);
             if !face.orientation() {
                 new_face.invert();
@@ -626,6 +628,16 @@ impl<P, C, S> Shell<P, C, S> {
         self.iter().all(|face| face.is_geometric_consistent())
     }
 
+    /// Returns the union of the bounding boxes of the faces, without tessellation.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<P>
+    where
+        P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy,
+        C: RoughlyBounded<P>,
+        S: RoughlyBounded<P>, {
+        self.iter().map(Face::bounding_box).fold(BoundingBox::new(), |sum, bdb| sum + bdb)
+    }
+
     /// Cuts one edge into two edges at vertex.
     /// # Failures
     /// Returns `false` and not edit `self` if:
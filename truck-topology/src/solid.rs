@@ -72,6 +72,22 @@ impl<P, C, S> Solid<P, C, S> {
         self.face_iter().flat_map(Face::boundaries).flatten()
     }
 
+    /// Returns the union of the bounding boxes of the boundary shells, without tessellation.
+    ///
+    /// The box is conservative, so it can be used for fitting scenes and the broad phases of
+    /// the collision checks before tessellation.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<P>
+    where
+        P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy,
+        C: RoughlyBounded<P>,
+        S: RoughlyBounded<P>, {
+        self.boundaries
+            .iter()
+            .map(Shell::bounding_box)
+            .fold(BoundingBox::new(), |sum, bdb| sum + bdb)
+    }
+
     /// Returns an iterator over the vertices.
     #[inline(always)]
     pub fn vertex_iter<'a>(&'a self) -> impl Iterator<Item = Vertex<P>> + 'a {
@@ -14,7 +14,8 @@ impl<P> Vertex<P> {
     #[inline(always)]
     pub fn new(point: P) -> Vertex<P> {
         Vertex {
-            point: Arc::new(Mutex::new(point)),
+            point: Arc::new(RwLock::new(point)),
+            tolerance: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -35,9 +36,24 @@ impl<P> Vertex<P> {
     /// Returns the point of vertex.
     #[inline(always)]
     pub fn get_point(&self) -> P where P: Clone {
-        self.point.lock().unwrap().clone()
+        self.point.read().unwrap().clone()
     }
 
+    /// Returns the read guard of the point of vertex.
+    ///
+    /// Unlike `get_point`, the point is not cloned, and the guards can be held
+    /// by some threads at the same time.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::new(vec![0, 1, 2]);
+    /// assert_eq!(v.read_point()[1], 1);
+    /// ```
+    /// # Remarks
+    /// Setting the point while holding the guard in the same thread will result in a deadlock.
+    #[inline(always)]
+    pub fn read_point(&self) -> RwLockReadGuard<'_, P> { self.point.read().unwrap() }
+
     /// Sets the point of vertex.
     /// # Examples
     /// ```
@@ -58,7 +74,31 @@ impl<P> Vertex<P> {
     /// ``` 
     #[inline(always)]
     pub fn set_point(&self, point: P) {
-        *self.point.lock().unwrap() = point;
+        *self.point.write().unwrap() = point;
+    }
+
+    /// Returns the tolerance of the vertex, i.e. the radius of the ball in which the vertex lies.
+    /// `None` means that the vertex is as precise as the global `TOLERANCE`.
+    #[inline(always)]
+    pub fn tolerance(&self) -> Option<f64> { *self.tolerance.read().unwrap() }
+
+    /// Sets the tolerance of vertex. The tolerance is shared by the cloned vertices.
+    ///
+    /// The tolerances given by the imported data are used in the consistency checks
+    /// instead of the global `TOLERANCE`, if they are larger than `TOLERANCE`.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v0 = Vertex::new(());
+    /// let v1 = v0.clone();
+    /// assert_eq!(v0.tolerance(), None);
+    ///
+    /// v0.set_tolerance(Some(1.0e-3));
+    /// assert_eq!(v1.tolerance(), Some(1.0e-3));
+    /// ```
+    #[inline(always)]
+    pub fn set_tolerance(&self, tolerance: Option<f64>) {
+        *self.tolerance.write().unwrap() = tolerance;
     }
 
     /// Returns vertex whose point is converted by `point_mapping`.
@@ -68,7 +108,9 @@ impl<P> Vertex<P> {
     #[doc(hidden)]
     #[inline(always)]
     pub fn try_mapped<Q>(&self, mut point_mapping: impl FnMut(&P) -> Option<Q>) -> Option<Vertex<Q>> {
-        Some(Vertex::new(point_mapping(&*self.point.lock().unwrap())?))
+        let vertex = Vertex::new(point_mapping(&*self.point.read().unwrap())?);
+        vertex.set_tolerance(self.tolerance());
+        Some(vertex)
     }
 
     /// Returns vertex whose point is converted by `point_mapping`.
@@ -85,7 +127,9 @@ impl<P> Vertex<P> {
     #[doc(hidden)]
     #[inline(always)]
     pub fn mapped<Q>(&self, mut point_mapping: impl FnMut(&P) -> Q) -> Vertex<Q> {
-        Vertex::new(point_mapping(&*self.point.lock().unwrap()))
+        let vertex = Vertex::new(point_mapping(&*self.point.read().unwrap()));
+        vertex.set_tolerance(self.tolerance());
+        vertex
     }
 
     /// Returns the id of the vertex.
@@ -98,6 +142,7 @@ impl<P> Clone for Vertex<P> {
     fn clone(&self) -> Vertex<P> {
         Vertex {
             point: Arc::clone(&self.point),
+            tolerance: Arc::clone(&self.tolerance),
         }
     }
 }
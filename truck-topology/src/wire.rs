@@ -376,8 +376,9 @@ impl<P, C> Wire<P, C> {
             } else {
                 let vertex0 = vertex_map.get(&edge.absolute_front().id()).unwrap().clone();
                 let vertex1 = vertex_map.get(&edge.absolute_back().id()).unwrap().clone();
-                let curve = curve_mapping(&*edge.curve.lock().unwrap())?;
+                let curve = curve_mapping(&*edge.curve.read().unwrap())?;
                 let new_edge = Edge::debug_new(&vertex0, &vertex1, curve);
+                new_edge.set_tolerance(edge.tolerance());
                 if edge.orientation() {
                     wire.push_back(new_edge.clone());
                 } else {
@@ -456,8 +457,9 @@ impl<P, C> Wire<P, C> {
             } else {
                 let vertex0 = vertex_map.get(&edge.absolute_front().id()).unwrap().clone();
                 let vertex1 = vertex_map.get(&edge.absolute_back().id()).unwrap().clone();
-                let curve = curve_mapping(&*edge.curve.lock().unwrap());
+                let curve = curve_mapping(&*edge.curve.read().unwrap());
                 let new_edge = Edge::debug_new(&vertex0, &vertex1, curve);
+                new_edge.set_tolerance(edge.tolerance());
                 if edge.orientation() {
                     wire.push_back(new_edge.clone());
                 } else {
@@ -478,6 +480,15 @@ impl<P, C> Wire<P, C> {
         C: ParametricCurve<Point = P>, {
         self.iter().all(|edge| edge.is_geometric_consistent())
     }
+
+    /// Returns the union of the bounding boxes of the edges, without tessellation.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> BoundingBox<P>
+    where
+        P: MetricSpace<Metric = f64> + Index<usize, Output = f64> + Bounded<f64> + Copy,
+        C: RoughlyBounded<P>, {
+        self.iter().map(Edge::bounding_box).fold(BoundingBox::new(), |sum, bdb| sum + bdb)
+    }
 }
 
 impl<T, P, C> From<T> for Wire<P, C>
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+use truck_base::cgmath64::Vector4;
+use truck_topology::{shell::ShellCondition, *};
+
+fn tetrahedron() -> Solid<usize, usize, usize> {
+    let v = Vertex::news([0, 1, 2, 3]);
+    let e = [
+        Edge::new(&v[0], &v[1], 0),
+        Edge::new(&v[1], &v[2], 1),
+        Edge::new(&v[2], &v[0], 2),
+        Edge::new(&v[0], &v[3], 3),
+        Edge::new(&v[1], &v[3], 4),
+        Edge::new(&v[2], &v[3], 5),
+    ];
+    let wires = vec![
+        Wire::from_iter(vec![e[2].inverse(), e[1].inverse(), e[0].inverse()]),
+        Wire::from_iter(vec![e[0].clone(), e[4].clone(), e[3].inverse()]),
+        Wire::from_iter(vec![e[1].clone(), e[5].clone(), e[4].inverse()]),
+        Wire::from_iter(vec![e[2].clone(), e[3].clone(), e[5].inverse()]),
+    ];
+    let shell: Shell<_, _, _> = wires
+        .into_iter()
+        .enumerate()
+        .map(|(i, wire)| Face::new(vec![wire], i))
+        .collect();
+    Solid::new(vec![shell])
+}
+
+#[test]
+fn serialize_vertex_edge() {
+    let v = Vertex::news([0, 1]);
+    let edge = Edge::new(&v[0], &v[1], 2).inverse();
+    let json = serde_json::to_string(&v[0]).unwrap();
+    assert_eq!(json, "0");
+    let vertex: Vertex<usize> = serde_json::from_str(&json).unwrap();
+    assert_eq!(vertex.get_point(), 0);
+    assert_ne!(vertex, v[0]);
+
+    let json = serde_json::to_string(&edge).unwrap();
+    let edge0: Edge<usize, usize> = serde_json::from_str(&json).unwrap();
+    assert_eq!(edge0.front().get_point(), 1);
+    assert_eq!(edge0.back().get_point(), 0);
+    assert_eq!(edge0.orientation(), edge.orientation());
+    assert_eq!(edge0.get_curve(), 2);
+}
+
+#[test]
+fn serialize_wire_face() {
+    let solid = tetrahedron();
+    let face = &solid.boundaries()[0][1];
+    let json = serde_json::to_value(face).unwrap();
+    assert_eq!(json["vertices"].as_array().unwrap().len(), 3);
+    assert_eq!(json["edges"].as_array().unwrap().len(), 3);
+    let face0: Face<usize, usize, usize> = serde_json::from_value(json).unwrap();
+    assert_eq!(face0.get_surface(), 1);
+    assert_eq!(face0.orientation(), face.orientation());
+    let points: Vec<_> = face0.boundaries()[0]
+        .vertex_iter()
+        .map(|v| v.get_point())
+        .collect();
+    assert_eq!(points, vec![0, 1, 3]);
+
+    let wire = face.boundaries()[0].inverse();
+    let json = serde_json::to_string(&wire).unwrap();
+    let wire0: Wire<usize, usize> = serde_json::from_str(&json).unwrap();
+    assert!(wire0.is_closed());
+    let points: Vec<_> = wire0.vertex_iter().map(|v| v.get_point()).collect();
+    assert_eq!(points, vec![0, 3, 1]);
+}
+
+#[test]
+fn serialize_solid_sharing() {
+    let solid = tetrahedron();
+    let json = serde_json::to_value(&solid).unwrap();
+    let shell_json = &json["boundaries"][0];
+    // each vertex and edge is serialized only once
+    assert_eq!(shell_json["vertices"].as_array().unwrap().len(), 4);
+    assert_eq!(shell_json["edges"].as_array().unwrap().len(), 6);
+
+    let solid0: Solid<usize, usize, usize> = serde_json::from_value(json).unwrap();
+    let shell = &solid0.boundaries()[0];
+    assert_eq!(shell.len(), 4);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    let edges: HashSet<_> = shell.edge_iter().map(|edge| edge.id()).collect();
+    assert_eq!(edges.len(), 6);
+    let vertices: HashSet<_> = shell.vertex_iter().map(|v| v.id()).collect();
+    assert_eq!(vertices.len(), 4);
+
+    let json = serde_json::to_string(shell).unwrap();
+    let shell0: Shell<usize, usize, usize> = serde_json::from_str(&json).unwrap();
+    assert_eq!(shell0.shell_condition(), ShellCondition::Closed);
+}
+
+#[test]
+fn serialize_solid_metadata() {
+    let solid = tetrahedron();
+    let shell = &solid.boundaries()[0];
+    let mut metadata = MetadataMap::new();
+    let solid_data = metadata.solid_mut();
+    solid_data.name = Some("tetrahedron".to_string());
+    solid_data
+        .custom
+        .insert("material".to_string(), "steel".to_string());
+    for face in shell.face_iter() {
+        let data = metadata.face_mut(face.id());
+        data.name = Some(format!("face{}", face.get_surface()));
+        data.layer = Some("faces".to_string());
+    }
+    metadata.face_mut(shell[2].id()).color = Some(Vector4::new(1.0, 0.0, 0.0, 1.0));
+    for edge in shell.edge_iter().filter(|edge| edge.get_curve() % 2 == 0) {
+        metadata.edge_mut(edge.id()).layer = Some("even".to_string());
+    }
+
+    // without metadata, no extra fields are serialized.
+    let json = serde_json::to_value(&solid).unwrap();
+    assert!(json.get("metadata").is_none());
+    assert!(json["boundaries"][0].get("face_metadata").is_none());
+    let (_, metadata0) =
+        Solid::<usize, usize, usize>::extract_with_metadata(serde_json::from_value(json).unwrap())
+            .unwrap();
+    assert!(metadata0.solid().is_empty());
+    assert_eq!(metadata0.faces_in_layer("faces").count(), 0);
+
+    let json = serde_json::to_string(&solid.compress_with_metadata(&metadata)).unwrap();
+    let (solid0, metadata0) =
+        Solid::<usize, usize, usize>::extract_with_metadata(serde_json::from_str(&json).unwrap())
+            .unwrap();
+    assert_eq!(metadata0.solid(), metadata.solid());
+    let shell0 = &solid0.boundaries()[0];
+    for face in shell0.face_iter() {
+        let data = metadata0.face(face.id()).unwrap();
+        assert_eq!(data.name, Some(format!("face{}", face.get_surface())));
+    }
+    assert_eq!(metadata0.faces_in_layer("faces").count(), 4);
+    assert_eq!(
+        metadata0.face(shell0[2].id()).unwrap().color,
+        Some(Vector4::new(1.0, 0.0, 0.0, 1.0))
+    );
+    let even: HashSet<usize> = metadata0
+        .edges_in_layer("even")
+        .map(|id| {
+            shell0
+                .edge_iter()
+                .find(|edge| edge.id() == id)
+                .unwrap()
+                .get_curve()
+        })
+        .collect();
+    assert_eq!(even, HashSet::from_iter(vec![0, 2, 4]));
+}
+
+#[test]
+fn serialize_solid_tolerance() {
+    let solid = tetrahedron();
+    let shell = &solid.boundaries()[0];
+    let json = serde_json::to_string(&solid).unwrap();
+    assert!(!json.contains("tolerance"));
+    let edge = shell[1].boundaries()[0][1].clone();
+    edge.set_tolerance(Some(0.5));
+    edge.back().set_tolerance(Some(0.25));
+    let json = serde_json::to_string(&solid).unwrap();
+    let solid0: Solid<usize, usize, usize> = serde_json::from_str(&json).unwrap();
+    let edges: Vec<_> = solid0.edge_iter().filter(|edge| edge.get_curve() == 4).collect();
+    assert!(edges.iter().all(|edge| edge.tolerance() == Some(0.5)));
+    let mut others = solid0.edge_iter().filter(|edge| edge.get_curve() != 4);
+    assert!(others.all(|edge| edge.tolerance().is_none()));
+    let vertices: Vec<_> = solid0.vertex_iter().filter(|v| v.get_point() == 3).collect();
+    assert!(vertices.iter().all(|v| v.tolerance() == Some(0.25)));
+    let mut others = solid0.vertex_iter().filter(|v| v.get_point() != 3);
+    assert!(others.all(|v| v.tolerance().is_none()));
+}